@@ -1,6 +1,6 @@
 # Print an optspec for argparse to handle cmd's options that are independent of any subcommand.
 function __fish_rona_global_optspecs
-	string join \n v/verbose f/config-file= h/help V/version
+	string join \n v/verbose f/config-file= C/chdir= read-only lang= template= scope= output= h/help V/version
 end
 
 function __fish_rona_needs_command
@@ -25,106 +25,984 @@ function __fish_rona_using_subcommand
 end
 
 complete -c rona -n "__fish_rona_needs_command" -s f -l config-file -d 'Config file to use instead of the default global/project hierarchy' -r -F
+complete -c rona -n "__fish_rona_needs_command" -s C -l chdir -d 'Run as if rona was started in PATH instead of the current directory (like `git -C`)' -r -f -a "(__fish_complete_directories)"
+complete -c rona -n "__fish_rona_needs_command" -l lang -d 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`' -r
+complete -c rona -n "__fish_rona_needs_command" -l template -d 'Commit template to use: the one named NAME in `[templates]`' -r
+complete -c rona -n "__fish_rona_needs_command" -l scope -d 'Only consider the files under a directory: a name from `[scopes]` or a path' -r
+complete -c rona -n "__fish_rona_needs_command" -l output -d 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`' -r -f -a "text\t'Human-readable text'
+json\t'A JSON object with a `schema_version`, for scripts and editor plugins'"
 complete -c rona -n "__fish_rona_needs_command" -s v -l verbose -d 'Verbose output - show detailed information about operations'
-complete -c rona -n "__fish_rona_needs_command" -s h -l help -d 'Print help'
+complete -c rona -n "__fish_rona_needs_command" -l read-only -d 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)'
+complete -c rona -n "__fish_rona_needs_command" -s h -l help -d 'Print help (see more with \'--help\')'
 complete -c rona -n "__fish_rona_needs_command" -s V -l version -d 'Print version'
+complete -c rona -n "__fish_rona_needs_command" -f -a "am" -d 'Apply mailbox patches, with a three-way fallback for patches that do not apply'
+complete -c rona -n "__fish_rona_needs_command" -f -a "audit" -d 'Report which existing commits follow the commit template and lint rules'
+complete -c rona -n "__fish_rona_needs_command" -f -a "backport" -d 'Cherry-pick a commit onto release branches with a re-templated message'
 complete -c rona -n "__fish_rona_needs_command" -f -a "branch" -d 'Create a new branch interactively using a branch name template'
+complete -c rona -n "__fish_rona_needs_command" -f -a "browse" -d 'Browse recent commits: filter by typing, then view, copy, fix up or revert one'
+complete -c rona -n "__fish_rona_needs_command" -f -a "bundle" -d 'Carry branches to a clone without a shared remote in `git bundle` files'
 complete -c rona -n "__fish_rona_needs_command" -f -a "add-with-exclude" -d 'Add all files to the `git add` command and exclude the patterns passed as positional arguments'
 complete -c rona -n "__fish_rona_needs_command" -f -a "commit" -d 'Directly commit the file with the text in `commit_message.md`'
 complete -c rona -n "__fish_rona_needs_command" -f -a "completion" -d 'Generate shell completions for your shell'
 complete -c rona -n "__fish_rona_needs_command" -f -a "config" -d 'Manage configuration files (create or inspect)'
+complete -c rona -n "__fish_rona_needs_command" -f -a "copy" -d 'Copy a reference to a commit to the clipboard, e.g. `abc1234 ("fix: handle empty index")`, for pull request descriptions and review comments'
+complete -c rona -n "__fish_rona_needs_command" -f -a "daemon" -d 'Keep the changed files warm for `rona -l`, served over a unix socket'
+complete -c rona -n "__fish_rona_needs_command" -f -a "check-msg" -d 'Check a commit message file against the commit template'
+complete -c rona -n "__fish_rona_needs_command" -f -a "env" -d 'Show what rona resolved here: repository, config files, template, editor, signing, guards and scope. Paste it in bug reports'
+complete -c rona -n "__fish_rona_needs_command" -f -a "which" -d 'Show what rona resolved here: repository, config files, template, editor, signing, guards and scope. Paste it in bug reports'
+complete -c rona -n "__fish_rona_needs_command" -f -a "explain-status" -d 'Show git status with plain-language explanations of each state and the rona commands that act on it'
+complete -c rona -n "__fish_rona_needs_command" -f -a "format-patch" -d 'Export commits as mbox patch files for mailing-list review'
+complete -c rona -n "__fish_rona_needs_command" -f -a "fragment" -d 'Write changelog fragments and assemble them into the changelog at release time'
 complete -c rona -n "__fish_rona_needs_command" -f -a "generate" -d 'Directly generate the `commit_message.md` file'
+complete -c rona -n "__fish_rona_needs_command" -f -a "handoff" -d 'Continue a half-finished commit in another clone: push the staged changes and `commit_message.md` of a branch to a ref, then pull them on the other machine'
+complete -c rona -n "__fish_rona_needs_command" -f -a "health" -d 'Check the repository for signs of missing maintenance (loose objects, missing commit-graph, large files in history, broken refs)'
+complete -c rona -n "__fish_rona_needs_command" -f -a "history" -d 'Review what rona did in this repository (recorded in `.git/rona/oplog.jsonl`)'
+complete -c rona -n "__fish_rona_needs_command" -f -a "hooks" -d 'Install, list or remove git hooks that run rona\'s checks on plain `git commit` and `git push`'
 complete -c rona -n "__fish_rona_needs_command" -f -a "init" -d 'Initialize the rona configuration file'
 complete -c rona -n "__fish_rona_needs_command" -f -a "list-status" -d 'List files from git status (for shell completion on the -a)'
+complete -c rona -n "__fish_rona_needs_command" -f -a "prepare-msg" -d 'Prefill a commit message file from the project template'
+complete -c rona -n "__fish_rona_needs_command" -f -a "prune" -d 'Clean up the repository: prune deleted remote branches, expire reflogs, remove unreachable objects and stale rona state files'
 complete -c rona -n "__fish_rona_needs_command" -f -a "push" -d 'Push to a git repository'
+complete -c rona -n "__fish_rona_needs_command" -f -a "release" -d 'Tag the next version, adding its changelog section from the fragments'
 complete -c rona -n "__fish_rona_needs_command" -f -a "reset" -d 'Unstage files, moving them out of the staging area without losing changes'
 complete -c rona -n "__fish_rona_needs_command" -f -a "restore" -d 'Discard working-tree changes, restoring files to their staged or committed state'
 complete -c rona -n "__fish_rona_needs_command" -f -a "set-editor" -d 'Set the editor to use for editing the commit message'
-complete -c rona -n "__fish_rona_needs_command" -f -a "sync" -d 'Sync current branch with main (or another branch) by pulling and merging/rebasing'
+complete -c rona -n "__fish_rona_needs_command" -f -a "schema" -d 'Print the JSON Schema of the `--json` outputs, for tools that read them'
+complete -c rona -n "__fish_rona_needs_command" -f -a "show" -d 'Show a commit: its template fields, trailers, notes, signature and diff stat'
+complete -c rona -n "__fish_rona_needs_command" -f -a "snippet" -d 'Insert reusable message body text defined under `[snippets]`'
+complete -c rona -n "__fish_rona_needs_command" -f -a "stats" -d 'List the commits made with rona, with their size and lead time, or export them for dashboards. Computed locally from the operation log'
+complete -c rona -n "__fish_rona_needs_command" -f -a "status" -d 'Show the current branch, how it compares to its upstream, and the changed files grouped by state'
+complete -c rona -n "__fish_rona_needs_command" -f -a "sync" -d 'Sync current branch with the default branch (or another one) by pulling and merging/rebasing'
+complete -c rona -n "__fish_rona_needs_command" -f -a "template" -d 'Check the configured templates for unknown variables and malformed blocks'
+complete -c rona -n "__fish_rona_needs_command" -f -a "todo" -d 'List TODO, FIXME and HACK markers on the lines the staged changes add'
+complete -c rona -n "__fish_rona_needs_command" -f -a "tour" -d 'Walk through the rona workflow in a throwaway demo repository'
+complete -c rona -n "__fish_rona_needs_command" -f -a "usage" -d 'Summarize your own rona usage in this repository from the operation log. Computed locally; nothing is sent anywhere'
 complete -c rona -n "__fish_rona_needs_command" -f -a "help" -d 'Print this message or the help of the given subcommand(s)'
-complete -c rona -n "__fish_rona_using_subcommand branch" -s f -l config-file -d 'Config file to use instead of the default global/project hierarchy' -r -F
-complete -c rona -n "__fish_rona_using_subcommand branch" -l dry-run -d 'Show what would be created without actually creating the branch'
-complete -c rona -n "__fish_rona_using_subcommand branch" -l no-switch -d 'Create the branch without switching to it'
-complete -c rona -n "__fish_rona_using_subcommand branch" -s h -l help -d 'Print help'
+complete -c rona -n "__fish_rona_using_subcommand am" -s f -l config-file -d 'Config file to use instead of the default global/project hierarchy' -r -F
+complete -c rona -n "__fish_rona_using_subcommand am" -s C -l chdir -d 'Run as if rona was started in PATH instead of the current directory (like `git -C`)' -r -f -a "(__fish_complete_directories)"
+complete -c rona -n "__fish_rona_using_subcommand am" -l lang -d 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`' -r
+complete -c rona -n "__fish_rona_using_subcommand am" -l template -d 'Commit template to use: the one named NAME in `[templates]`' -r
+complete -c rona -n "__fish_rona_using_subcommand am" -l scope -d 'Only consider the files under a directory: a name from `[scopes]` or a path' -r
+complete -c rona -n "__fish_rona_using_subcommand am" -l output -d 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`' -r -f -a "text\t'Human-readable text'
+json\t'A JSON object with a `schema_version`, for scripts and editor plugins'"
+complete -c rona -n "__fish_rona_using_subcommand am" -l retemplate -d 'Rewrite each subject through the commit template, numbered from the next commit'
+complete -c rona -n "__fish_rona_using_subcommand am" -l continue -d 'Apply the rest of the patches after resolving conflicts'
+complete -c rona -n "__fish_rona_using_subcommand am" -l skip -d 'Drop the patch that stopped and apply the rest'
+complete -c rona -n "__fish_rona_using_subcommand am" -l abort -d 'Stop applying and restore the branch to where it was'
+complete -c rona -n "__fish_rona_using_subcommand am" -l dry-run -d 'Show which patches would be applied, with their subjects, without applying them'
+complete -c rona -n "__fish_rona_using_subcommand am" -l read-only -d 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)'
+complete -c rona -n "__fish_rona_using_subcommand am" -s h -l help -d 'Print help (see more with \'--help\')'
+complete -c rona -n "__fish_rona_using_subcommand audit" -l since -d 'Only audit the commits after this ref (e.g. the tag of the last release)' -r
+complete -c rona -n "__fish_rona_using_subcommand audit" -s f -l config-file -d 'Config file to use instead of the default global/project hierarchy' -r -F
+complete -c rona -n "__fish_rona_using_subcommand audit" -s C -l chdir -d 'Run as if rona was started in PATH instead of the current directory (like `git -C`)' -r -f -a "(__fish_complete_directories)"
+complete -c rona -n "__fish_rona_using_subcommand audit" -l lang -d 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`' -r
+complete -c rona -n "__fish_rona_using_subcommand audit" -l template -d 'Commit template to use: the one named NAME in `[templates]`' -r
+complete -c rona -n "__fish_rona_using_subcommand audit" -l scope -d 'Only consider the files under a directory: a name from `[scopes]` or a path' -r
+complete -c rona -n "__fish_rona_using_subcommand audit" -l output -d 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`' -r -f -a "text\t'Human-readable text'
+json\t'A JSON object with a `schema_version`, for scripts and editor plugins'"
+complete -c rona -n "__fish_rona_using_subcommand audit" -l read-only -d 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)'
+complete -c rona -n "__fish_rona_using_subcommand audit" -s h -l help -d 'Print help (see more with \'--help\')'
+complete -c rona -n "__fish_rona_using_subcommand backport" -l to -d 'Target branches, comma-separated or repeated (`--to release/1.x,release/2.x`)' -r
+complete -c rona -n "__fish_rona_using_subcommand backport" -l remote -d 'Remote the target branches are fetched from and pushed to' -r
+complete -c rona -n "__fish_rona_using_subcommand backport" -s f -l config-file -d 'Config file to use instead of the default global/project hierarchy' -r -F
+complete -c rona -n "__fish_rona_using_subcommand backport" -s C -l chdir -d 'Run as if rona was started in PATH instead of the current directory (like `git -C`)' -r -f -a "(__fish_complete_directories)"
+complete -c rona -n "__fish_rona_using_subcommand backport" -l lang -d 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`' -r
+complete -c rona -n "__fish_rona_using_subcommand backport" -l template -d 'Commit template to use: the one named NAME in `[templates]`' -r
+complete -c rona -n "__fish_rona_using_subcommand backport" -l scope -d 'Only consider the files under a directory: a name from `[scopes]` or a path' -r
+complete -c rona -n "__fish_rona_using_subcommand backport" -l output -d 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`' -r -f -a "text\t'Human-readable text'
+json\t'A JSON object with a `schema_version`, for scripts and editor plugins'"
+complete -c rona -n "__fish_rona_using_subcommand backport" -s p -l push -d 'Push each target branch after committing the backport'
+complete -c rona -n "__fish_rona_using_subcommand backport" -l pr -d 'Commit on a `backport/<sha>/<target>` branch, push it and open a pull request with the GitHub CLI (`gh`)'
+complete -c rona -n "__fish_rona_using_subcommand backport" -s u -l unsigned -d 'Create unsigned commits'
+complete -c rona -n "__fish_rona_using_subcommand backport" -l dry-run -d 'Show what would be backported without changing any branch'
+complete -c rona -n "__fish_rona_using_subcommand backport" -l read-only -d 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)'
+complete -c rona -n "__fish_rona_using_subcommand backport" -s h -l help -d 'Print help (see more with \'--help\')'
+complete -c rona -n "__fish_rona_using_subcommand branch; and not __fish_seen_subcommand_from describe list new rename switch merge rebase pull help" -s f -l config-file -d 'Config file to use instead of the default global/project hierarchy' -r -F
+complete -c rona -n "__fish_rona_using_subcommand branch; and not __fish_seen_subcommand_from describe list new rename switch merge rebase pull help" -s C -l chdir -d 'Run as if rona was started in PATH instead of the current directory (like `git -C`)' -r -f -a "(__fish_complete_directories)"
+complete -c rona -n "__fish_rona_using_subcommand branch; and not __fish_seen_subcommand_from describe list new rename switch merge rebase pull help" -l lang -d 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`' -r
+complete -c rona -n "__fish_rona_using_subcommand branch; and not __fish_seen_subcommand_from describe list new rename switch merge rebase pull help" -l template -d 'Commit template to use: the one named NAME in `[templates]`' -r
+complete -c rona -n "__fish_rona_using_subcommand branch; and not __fish_seen_subcommand_from describe list new rename switch merge rebase pull help" -l scope -d 'Only consider the files under a directory: a name from `[scopes]` or a path' -r
+complete -c rona -n "__fish_rona_using_subcommand branch; and not __fish_seen_subcommand_from describe list new rename switch merge rebase pull help" -l output -d 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`' -r -f -a "text\t'Human-readable text'
+json\t'A JSON object with a `schema_version`, for scripts and editor plugins'"
+complete -c rona -n "__fish_rona_using_subcommand branch; and not __fish_seen_subcommand_from describe list new rename switch merge rebase pull help" -l dry-run -d 'Show what would be created without actually creating the branch'
+complete -c rona -n "__fish_rona_using_subcommand branch; and not __fish_seen_subcommand_from describe list new rename switch merge rebase pull help" -l no-switch -d 'Create the branch without switching to it'
+complete -c rona -n "__fish_rona_using_subcommand branch; and not __fish_seen_subcommand_from describe list new rename switch merge rebase pull help" -l read-only -d 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)'
+complete -c rona -n "__fish_rona_using_subcommand branch; and not __fish_seen_subcommand_from describe list new rename switch merge rebase pull help" -s h -l help -d 'Print help (see more with \'--help\')'
+complete -c rona -n "__fish_rona_using_subcommand branch; and not __fish_seen_subcommand_from describe list new rename switch merge rebase pull help" -f -a "describe" -d 'Set the purpose of the current branch, exposed as `{branch_description}`'
+complete -c rona -n "__fish_rona_using_subcommand branch; and not __fish_seen_subcommand_from describe list new rename switch merge rebase pull help" -f -a "list" -d 'List local branches with their descriptions'
+complete -c rona -n "__fish_rona_using_subcommand branch; and not __fish_seen_subcommand_from describe list new rename switch merge rebase pull help" -f -a "new" -d 'Create a branch from `branch_template` without prompting for the description'
+complete -c rona -n "__fish_rona_using_subcommand branch; and not __fish_seen_subcommand_from describe list new rename switch merge rebase pull help" -f -a "rename" -d 'Rename the current branch, push it under the new name and track it'
+complete -c rona -n "__fish_rona_using_subcommand branch; and not __fish_seen_subcommand_from describe list new rename switch merge rebase pull help" -f -a "switch" -d 'Switch to a local branch, picked with a fuzzy finder when omitted'
+complete -c rona -n "__fish_rona_using_subcommand branch; and not __fish_seen_subcommand_from describe list new rename switch merge rebase pull help" -f -a "merge" -d 'Merge a local branch into the current one, picked with a fuzzy finder when omitted'
+complete -c rona -n "__fish_rona_using_subcommand branch; and not __fish_seen_subcommand_from describe list new rename switch merge rebase pull help" -f -a "rebase" -d 'Rebase the current branch onto a local branch, picked with a fuzzy finder when omitted'
+complete -c rona -n "__fish_rona_using_subcommand branch; and not __fish_seen_subcommand_from describe list new rename switch merge rebase pull help" -f -a "pull" -d 'Pull the upstream changes of the current branch'
+complete -c rona -n "__fish_rona_using_subcommand branch; and not __fish_seen_subcommand_from describe list new rename switch merge rebase pull help" -f -a "help" -d 'Print this message or the help of the given subcommand(s)'
+complete -c rona -n "__fish_rona_using_subcommand branch; and __fish_seen_subcommand_from describe" -s f -l config-file -d 'Config file to use instead of the default global/project hierarchy' -r -F
+complete -c rona -n "__fish_rona_using_subcommand branch; and __fish_seen_subcommand_from describe" -s C -l chdir -d 'Run as if rona was started in PATH instead of the current directory (like `git -C`)' -r -f -a "(__fish_complete_directories)"
+complete -c rona -n "__fish_rona_using_subcommand branch; and __fish_seen_subcommand_from describe" -l lang -d 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`' -r
+complete -c rona -n "__fish_rona_using_subcommand branch; and __fish_seen_subcommand_from describe" -l template -d 'Commit template to use: the one named NAME in `[templates]`' -r
+complete -c rona -n "__fish_rona_using_subcommand branch; and __fish_seen_subcommand_from describe" -l scope -d 'Only consider the files under a directory: a name from `[scopes]` or a path' -r
+complete -c rona -n "__fish_rona_using_subcommand branch; and __fish_seen_subcommand_from describe" -l output -d 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`' -r -f -a "text\t'Human-readable text'
+json\t'A JSON object with a `schema_version`, for scripts and editor plugins'"
+complete -c rona -n "__fish_rona_using_subcommand branch; and __fish_seen_subcommand_from describe" -l clear -d 'Remove the description of the current branch'
+complete -c rona -n "__fish_rona_using_subcommand branch; and __fish_seen_subcommand_from describe" -l dry-run -d 'Show the description that would be stored without storing it'
+complete -c rona -n "__fish_rona_using_subcommand branch; and __fish_seen_subcommand_from describe" -l read-only -d 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)'
+complete -c rona -n "__fish_rona_using_subcommand branch; and __fish_seen_subcommand_from describe" -s h -l help -d 'Print help (see more with \'--help\')'
+complete -c rona -n "__fish_rona_using_subcommand branch; and __fish_seen_subcommand_from list" -s f -l config-file -d 'Config file to use instead of the default global/project hierarchy' -r -F
+complete -c rona -n "__fish_rona_using_subcommand branch; and __fish_seen_subcommand_from list" -s C -l chdir -d 'Run as if rona was started in PATH instead of the current directory (like `git -C`)' -r -f -a "(__fish_complete_directories)"
+complete -c rona -n "__fish_rona_using_subcommand branch; and __fish_seen_subcommand_from list" -l lang -d 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`' -r
+complete -c rona -n "__fish_rona_using_subcommand branch; and __fish_seen_subcommand_from list" -l template -d 'Commit template to use: the one named NAME in `[templates]`' -r
+complete -c rona -n "__fish_rona_using_subcommand branch; and __fish_seen_subcommand_from list" -l scope -d 'Only consider the files under a directory: a name from `[scopes]` or a path' -r
+complete -c rona -n "__fish_rona_using_subcommand branch; and __fish_seen_subcommand_from list" -l output -d 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`' -r -f -a "text\t'Human-readable text'
+json\t'A JSON object with a `schema_version`, for scripts and editor plugins'"
+complete -c rona -n "__fish_rona_using_subcommand branch; and __fish_seen_subcommand_from list" -l read-only -d 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)'
+complete -c rona -n "__fish_rona_using_subcommand branch; and __fish_seen_subcommand_from list" -s h -l help -d 'Print help (see more with \'--help\')'
+complete -c rona -n "__fish_rona_using_subcommand branch; and __fish_seen_subcommand_from new" -s t -l ticket -d 'Ticket reference, available as `{ticket}` (e.g. `PROJ-1`)' -r
+complete -c rona -n "__fish_rona_using_subcommand branch; and __fish_seen_subcommand_from new" -l type -d 'Branch type, available as `{branch_type}` or `{type}`; prompted when missing' -r
+complete -c rona -n "__fish_rona_using_subcommand branch; and __fish_seen_subcommand_from new" -s f -l config-file -d 'Config file to use instead of the default global/project hierarchy' -r -F
+complete -c rona -n "__fish_rona_using_subcommand branch; and __fish_seen_subcommand_from new" -s C -l chdir -d 'Run as if rona was started in PATH instead of the current directory (like `git -C`)' -r -f -a "(__fish_complete_directories)"
+complete -c rona -n "__fish_rona_using_subcommand branch; and __fish_seen_subcommand_from new" -l lang -d 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`' -r
+complete -c rona -n "__fish_rona_using_subcommand branch; and __fish_seen_subcommand_from new" -l template -d 'Commit template to use: the one named NAME in `[templates]`' -r
+complete -c rona -n "__fish_rona_using_subcommand branch; and __fish_seen_subcommand_from new" -l scope -d 'Only consider the files under a directory: a name from `[scopes]` or a path' -r
+complete -c rona -n "__fish_rona_using_subcommand branch; and __fish_seen_subcommand_from new" -l output -d 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`' -r -f -a "text\t'Human-readable text'
+json\t'A JSON object with a `schema_version`, for scripts and editor plugins'"
+complete -c rona -n "__fish_rona_using_subcommand branch; and __fish_seen_subcommand_from new" -l no-switch -d 'Create the branch without switching to it'
+complete -c rona -n "__fish_rona_using_subcommand branch; and __fish_seen_subcommand_from new" -l dry-run -d 'Show the branch name without creating it'
+complete -c rona -n "__fish_rona_using_subcommand branch; and __fish_seen_subcommand_from new" -l read-only -d 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)'
+complete -c rona -n "__fish_rona_using_subcommand branch; and __fish_seen_subcommand_from new" -s h -l help -d 'Print help (see more with \'--help\')'
+complete -c rona -n "__fish_rona_using_subcommand branch; and __fish_seen_subcommand_from rename" -s f -l config-file -d 'Config file to use instead of the default global/project hierarchy' -r -F
+complete -c rona -n "__fish_rona_using_subcommand branch; and __fish_seen_subcommand_from rename" -s C -l chdir -d 'Run as if rona was started in PATH instead of the current directory (like `git -C`)' -r -f -a "(__fish_complete_directories)"
+complete -c rona -n "__fish_rona_using_subcommand branch; and __fish_seen_subcommand_from rename" -l lang -d 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`' -r
+complete -c rona -n "__fish_rona_using_subcommand branch; and __fish_seen_subcommand_from rename" -l template -d 'Commit template to use: the one named NAME in `[templates]`' -r
+complete -c rona -n "__fish_rona_using_subcommand branch; and __fish_seen_subcommand_from rename" -l scope -d 'Only consider the files under a directory: a name from `[scopes]` or a path' -r
+complete -c rona -n "__fish_rona_using_subcommand branch; and __fish_seen_subcommand_from rename" -l output -d 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`' -r -f -a "text\t'Human-readable text'
+json\t'A JSON object with a `schema_version`, for scripts and editor plugins'"
+complete -c rona -n "__fish_rona_using_subcommand branch; and __fish_seen_subcommand_from rename" -l local -d 'Only rename the local branch; the remote branch and tracking are left as they are'
+complete -c rona -n "__fish_rona_using_subcommand branch; and __fish_seen_subcommand_from rename" -s y -l yes -d 'Delete the old remote branch without asking'
+complete -c rona -n "__fish_rona_using_subcommand branch; and __fish_seen_subcommand_from rename" -l keep-old -d 'Keep the old remote branch'
+complete -c rona -n "__fish_rona_using_subcommand branch; and __fish_seen_subcommand_from rename" -l dry-run -d 'Show what would be renamed, pushed and deleted without doing it'
+complete -c rona -n "__fish_rona_using_subcommand branch; and __fish_seen_subcommand_from rename" -l read-only -d 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)'
+complete -c rona -n "__fish_rona_using_subcommand branch; and __fish_seen_subcommand_from rename" -s h -l help -d 'Print help (see more with \'--help\')'
+complete -c rona -n "__fish_rona_using_subcommand branch; and __fish_seen_subcommand_from switch" -s f -l config-file -d 'Config file to use instead of the default global/project hierarchy' -r -F
+complete -c rona -n "__fish_rona_using_subcommand branch; and __fish_seen_subcommand_from switch" -s C -l chdir -d 'Run as if rona was started in PATH instead of the current directory (like `git -C`)' -r -f -a "(__fish_complete_directories)"
+complete -c rona -n "__fish_rona_using_subcommand branch; and __fish_seen_subcommand_from switch" -l lang -d 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`' -r
+complete -c rona -n "__fish_rona_using_subcommand branch; and __fish_seen_subcommand_from switch" -l template -d 'Commit template to use: the one named NAME in `[templates]`' -r
+complete -c rona -n "__fish_rona_using_subcommand branch; and __fish_seen_subcommand_from switch" -l scope -d 'Only consider the files under a directory: a name from `[scopes]` or a path' -r
+complete -c rona -n "__fish_rona_using_subcommand branch; and __fish_seen_subcommand_from switch" -l output -d 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`' -r -f -a "text\t'Human-readable text'
+json\t'A JSON object with a `schema_version`, for scripts and editor plugins'"
+complete -c rona -n "__fish_rona_using_subcommand branch; and __fish_seen_subcommand_from switch" -l dry-run -d 'Show the branch that would be checked out without switching'
+complete -c rona -n "__fish_rona_using_subcommand branch; and __fish_seen_subcommand_from switch" -l read-only -d 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)'
+complete -c rona -n "__fish_rona_using_subcommand branch; and __fish_seen_subcommand_from switch" -s h -l help -d 'Print help (see more with \'--help\')'
+complete -c rona -n "__fish_rona_using_subcommand branch; and __fish_seen_subcommand_from merge" -s f -l config-file -d 'Config file to use instead of the default global/project hierarchy' -r -F
+complete -c rona -n "__fish_rona_using_subcommand branch; and __fish_seen_subcommand_from merge" -s C -l chdir -d 'Run as if rona was started in PATH instead of the current directory (like `git -C`)' -r -f -a "(__fish_complete_directories)"
+complete -c rona -n "__fish_rona_using_subcommand branch; and __fish_seen_subcommand_from merge" -l lang -d 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`' -r
+complete -c rona -n "__fish_rona_using_subcommand branch; and __fish_seen_subcommand_from merge" -l template -d 'Commit template to use: the one named NAME in `[templates]`' -r
+complete -c rona -n "__fish_rona_using_subcommand branch; and __fish_seen_subcommand_from merge" -l scope -d 'Only consider the files under a directory: a name from `[scopes]` or a path' -r
+complete -c rona -n "__fish_rona_using_subcommand branch; and __fish_seen_subcommand_from merge" -l output -d 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`' -r -f -a "text\t'Human-readable text'
+json\t'A JSON object with a `schema_version`, for scripts and editor plugins'"
+complete -c rona -n "__fish_rona_using_subcommand branch; and __fish_seen_subcommand_from merge" -l dry-run -d 'Show the branch that would be merged without merging it'
+complete -c rona -n "__fish_rona_using_subcommand branch; and __fish_seen_subcommand_from merge" -l read-only -d 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)'
+complete -c rona -n "__fish_rona_using_subcommand branch; and __fish_seen_subcommand_from merge" -s h -l help -d 'Print help (see more with \'--help\')'
+complete -c rona -n "__fish_rona_using_subcommand branch; and __fish_seen_subcommand_from rebase" -s f -l config-file -d 'Config file to use instead of the default global/project hierarchy' -r -F
+complete -c rona -n "__fish_rona_using_subcommand branch; and __fish_seen_subcommand_from rebase" -s C -l chdir -d 'Run as if rona was started in PATH instead of the current directory (like `git -C`)' -r -f -a "(__fish_complete_directories)"
+complete -c rona -n "__fish_rona_using_subcommand branch; and __fish_seen_subcommand_from rebase" -l lang -d 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`' -r
+complete -c rona -n "__fish_rona_using_subcommand branch; and __fish_seen_subcommand_from rebase" -l template -d 'Commit template to use: the one named NAME in `[templates]`' -r
+complete -c rona -n "__fish_rona_using_subcommand branch; and __fish_seen_subcommand_from rebase" -l scope -d 'Only consider the files under a directory: a name from `[scopes]` or a path' -r
+complete -c rona -n "__fish_rona_using_subcommand branch; and __fish_seen_subcommand_from rebase" -l output -d 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`' -r -f -a "text\t'Human-readable text'
+json\t'A JSON object with a `schema_version`, for scripts and editor plugins'"
+complete -c rona -n "__fish_rona_using_subcommand branch; and __fish_seen_subcommand_from rebase" -l dry-run -d 'Show the branch that would be rebased onto without rebasing'
+complete -c rona -n "__fish_rona_using_subcommand branch; and __fish_seen_subcommand_from rebase" -l read-only -d 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)'
+complete -c rona -n "__fish_rona_using_subcommand branch; and __fish_seen_subcommand_from rebase" -s h -l help -d 'Print help (see more with \'--help\')'
+complete -c rona -n "__fish_rona_using_subcommand branch; and __fish_seen_subcommand_from pull" -s f -l config-file -d 'Config file to use instead of the default global/project hierarchy' -r -F
+complete -c rona -n "__fish_rona_using_subcommand branch; and __fish_seen_subcommand_from pull" -s C -l chdir -d 'Run as if rona was started in PATH instead of the current directory (like `git -C`)' -r -f -a "(__fish_complete_directories)"
+complete -c rona -n "__fish_rona_using_subcommand branch; and __fish_seen_subcommand_from pull" -l lang -d 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`' -r
+complete -c rona -n "__fish_rona_using_subcommand branch; and __fish_seen_subcommand_from pull" -l template -d 'Commit template to use: the one named NAME in `[templates]`' -r
+complete -c rona -n "__fish_rona_using_subcommand branch; and __fish_seen_subcommand_from pull" -l scope -d 'Only consider the files under a directory: a name from `[scopes]` or a path' -r
+complete -c rona -n "__fish_rona_using_subcommand branch; and __fish_seen_subcommand_from pull" -l output -d 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`' -r -f -a "text\t'Human-readable text'
+json\t'A JSON object with a `schema_version`, for scripts and editor plugins'"
+complete -c rona -n "__fish_rona_using_subcommand branch; and __fish_seen_subcommand_from pull" -l dry-run -d 'Show what would be pulled without pulling'
+complete -c rona -n "__fish_rona_using_subcommand branch; and __fish_seen_subcommand_from pull" -l read-only -d 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)'
+complete -c rona -n "__fish_rona_using_subcommand branch; and __fish_seen_subcommand_from pull" -s h -l help -d 'Print help (see more with \'--help\')'
+complete -c rona -n "__fish_rona_using_subcommand branch; and __fish_seen_subcommand_from help" -f -a "describe" -d 'Set the purpose of the current branch, exposed as `{branch_description}`'
+complete -c rona -n "__fish_rona_using_subcommand branch; and __fish_seen_subcommand_from help" -f -a "list" -d 'List local branches with their descriptions'
+complete -c rona -n "__fish_rona_using_subcommand branch; and __fish_seen_subcommand_from help" -f -a "new" -d 'Create a branch from `branch_template` without prompting for the description'
+complete -c rona -n "__fish_rona_using_subcommand branch; and __fish_seen_subcommand_from help" -f -a "rename" -d 'Rename the current branch, push it under the new name and track it'
+complete -c rona -n "__fish_rona_using_subcommand branch; and __fish_seen_subcommand_from help" -f -a "switch" -d 'Switch to a local branch, picked with a fuzzy finder when omitted'
+complete -c rona -n "__fish_rona_using_subcommand branch; and __fish_seen_subcommand_from help" -f -a "merge" -d 'Merge a local branch into the current one, picked with a fuzzy finder when omitted'
+complete -c rona -n "__fish_rona_using_subcommand branch; and __fish_seen_subcommand_from help" -f -a "rebase" -d 'Rebase the current branch onto a local branch, picked with a fuzzy finder when omitted'
+complete -c rona -n "__fish_rona_using_subcommand branch; and __fish_seen_subcommand_from help" -f -a "pull" -d 'Pull the upstream changes of the current branch'
+complete -c rona -n "__fish_rona_using_subcommand branch; and __fish_seen_subcommand_from help" -f -a "help" -d 'Print this message or the help of the given subcommand(s)'
+complete -c rona -n "__fish_rona_using_subcommand browse" -s n -l limit -d 'Number of recent commits to list' -r
+complete -c rona -n "__fish_rona_using_subcommand browse" -s f -l config-file -d 'Config file to use instead of the default global/project hierarchy' -r -F
+complete -c rona -n "__fish_rona_using_subcommand browse" -s C -l chdir -d 'Run as if rona was started in PATH instead of the current directory (like `git -C`)' -r -f -a "(__fish_complete_directories)"
+complete -c rona -n "__fish_rona_using_subcommand browse" -l lang -d 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`' -r
+complete -c rona -n "__fish_rona_using_subcommand browse" -l template -d 'Commit template to use: the one named NAME in `[templates]`' -r
+complete -c rona -n "__fish_rona_using_subcommand browse" -l scope -d 'Only consider the files under a directory: a name from `[scopes]` or a path' -r
+complete -c rona -n "__fish_rona_using_subcommand browse" -l output -d 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`' -r -f -a "text\t'Human-readable text'
+json\t'A JSON object with a `schema_version`, for scripts and editor plugins'"
+complete -c rona -n "__fish_rona_using_subcommand browse" -l dry-run -d 'Show what a fixup or revert would do without running it'
+complete -c rona -n "__fish_rona_using_subcommand browse" -l read-only -d 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)'
+complete -c rona -n "__fish_rona_using_subcommand browse" -s h -l help -d 'Print help (see more with \'--help\')'
+complete -c rona -n "__fish_rona_using_subcommand bundle; and not __fish_seen_subcommand_from create apply help" -s f -l config-file -d 'Config file to use instead of the default global/project hierarchy' -r -F
+complete -c rona -n "__fish_rona_using_subcommand bundle; and not __fish_seen_subcommand_from create apply help" -s C -l chdir -d 'Run as if rona was started in PATH instead of the current directory (like `git -C`)' -r -f -a "(__fish_complete_directories)"
+complete -c rona -n "__fish_rona_using_subcommand bundle; and not __fish_seen_subcommand_from create apply help" -l lang -d 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`' -r
+complete -c rona -n "__fish_rona_using_subcommand bundle; and not __fish_seen_subcommand_from create apply help" -l template -d 'Commit template to use: the one named NAME in `[templates]`' -r
+complete -c rona -n "__fish_rona_using_subcommand bundle; and not __fish_seen_subcommand_from create apply help" -l scope -d 'Only consider the files under a directory: a name from `[scopes]` or a path' -r
+complete -c rona -n "__fish_rona_using_subcommand bundle; and not __fish_seen_subcommand_from create apply help" -l output -d 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`' -r -f -a "text\t'Human-readable text'
+json\t'A JSON object with a `schema_version`, for scripts and editor plugins'"
+complete -c rona -n "__fish_rona_using_subcommand bundle; and not __fish_seen_subcommand_from create apply help" -l read-only -d 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)'
+complete -c rona -n "__fish_rona_using_subcommand bundle; and not __fish_seen_subcommand_from create apply help" -s h -l help -d 'Print help (see more with \'--help\')'
+complete -c rona -n "__fish_rona_using_subcommand bundle; and not __fish_seen_subcommand_from create apply help" -f -a "create" -d 'Write branches to a bundle file'
+complete -c rona -n "__fish_rona_using_subcommand bundle; and not __fish_seen_subcommand_from create apply help" -f -a "apply" -d 'Fast-forward branches from a bundle file, after checking it holds them'
+complete -c rona -n "__fish_rona_using_subcommand bundle; and not __fish_seen_subcommand_from create apply help" -f -a "help" -d 'Print this message or the help of the given subcommand(s)'
+complete -c rona -n "__fish_rona_using_subcommand bundle; and __fish_seen_subcommand_from create" -l since -d 'Leave out the commits reachable from this ref, which the receiving clone has' -r
+complete -c rona -n "__fish_rona_using_subcommand bundle; and __fish_seen_subcommand_from create" -s b -l branch -d 'Branch to bundle, can be repeated (default: the current branch)' -r
+complete -c rona -n "__fish_rona_using_subcommand bundle; and __fish_seen_subcommand_from create" -s f -l config-file -d 'Config file to use instead of the default global/project hierarchy' -r -F
+complete -c rona -n "__fish_rona_using_subcommand bundle; and __fish_seen_subcommand_from create" -s C -l chdir -d 'Run as if rona was started in PATH instead of the current directory (like `git -C`)' -r -f -a "(__fish_complete_directories)"
+complete -c rona -n "__fish_rona_using_subcommand bundle; and __fish_seen_subcommand_from create" -l lang -d 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`' -r
+complete -c rona -n "__fish_rona_using_subcommand bundle; and __fish_seen_subcommand_from create" -l template -d 'Commit template to use: the one named NAME in `[templates]`' -r
+complete -c rona -n "__fish_rona_using_subcommand bundle; and __fish_seen_subcommand_from create" -l scope -d 'Only consider the files under a directory: a name from `[scopes]` or a path' -r
+complete -c rona -n "__fish_rona_using_subcommand bundle; and __fish_seen_subcommand_from create" -l output -d 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`' -r -f -a "text\t'Human-readable text'
+json\t'A JSON object with a `schema_version`, for scripts and editor plugins'"
+complete -c rona -n "__fish_rona_using_subcommand bundle; and __fish_seen_subcommand_from create" -l dry-run -d 'Show what would be bundled without writing the file'
+complete -c rona -n "__fish_rona_using_subcommand bundle; and __fish_seen_subcommand_from create" -l read-only -d 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)'
+complete -c rona -n "__fish_rona_using_subcommand bundle; and __fish_seen_subcommand_from create" -s h -l help -d 'Print help (see more with \'--help\')'
+complete -c rona -n "__fish_rona_using_subcommand bundle; and __fish_seen_subcommand_from apply" -s b -l branch -d 'Branch expected in the bundle and applied, can be repeated (default: the current branch)' -r
+complete -c rona -n "__fish_rona_using_subcommand bundle; and __fish_seen_subcommand_from apply" -s f -l config-file -d 'Config file to use instead of the default global/project hierarchy' -r -F
+complete -c rona -n "__fish_rona_using_subcommand bundle; and __fish_seen_subcommand_from apply" -s C -l chdir -d 'Run as if rona was started in PATH instead of the current directory (like `git -C`)' -r -f -a "(__fish_complete_directories)"
+complete -c rona -n "__fish_rona_using_subcommand bundle; and __fish_seen_subcommand_from apply" -l lang -d 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`' -r
+complete -c rona -n "__fish_rona_using_subcommand bundle; and __fish_seen_subcommand_from apply" -l template -d 'Commit template to use: the one named NAME in `[templates]`' -r
+complete -c rona -n "__fish_rona_using_subcommand bundle; and __fish_seen_subcommand_from apply" -l scope -d 'Only consider the files under a directory: a name from `[scopes]` or a path' -r
+complete -c rona -n "__fish_rona_using_subcommand bundle; and __fish_seen_subcommand_from apply" -l output -d 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`' -r -f -a "text\t'Human-readable text'
+json\t'A JSON object with a `schema_version`, for scripts and editor plugins'"
+complete -c rona -n "__fish_rona_using_subcommand bundle; and __fish_seen_subcommand_from apply" -l dry-run -d 'Show which branches would be updated without updating them'
+complete -c rona -n "__fish_rona_using_subcommand bundle; and __fish_seen_subcommand_from apply" -l read-only -d 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)'
+complete -c rona -n "__fish_rona_using_subcommand bundle; and __fish_seen_subcommand_from apply" -s h -l help -d 'Print help (see more with \'--help\')'
+complete -c rona -n "__fish_rona_using_subcommand bundle; and __fish_seen_subcommand_from help" -f -a "create" -d 'Write branches to a bundle file'
+complete -c rona -n "__fish_rona_using_subcommand bundle; and __fish_seen_subcommand_from help" -f -a "apply" -d 'Fast-forward branches from a bundle file, after checking it holds them'
+complete -c rona -n "__fish_rona_using_subcommand bundle; and __fish_seen_subcommand_from help" -f -a "help" -d 'Print this message or the help of the given subcommand(s)'
+complete -c rona -n "__fish_rona_using_subcommand add-with-exclude" -l filter -d 'Only offer files whose path fuzzy-matches this query (with `-i`)' -r
 complete -c rona -n "__fish_rona_using_subcommand add-with-exclude" -s f -l config-file -d 'Config file to use instead of the default global/project hierarchy' -r -F
-complete -c rona -n "__fish_rona_using_subcommand add-with-exclude" -s i -l interactive -d 'Interactively pick which changed files to stage (`MultiSelect` of git status)'
+complete -c rona -n "__fish_rona_using_subcommand add-with-exclude" -s C -l chdir -d 'Run as if rona was started in PATH instead of the current directory (like `git -C`)' -r -f -a "(__fish_complete_directories)"
+complete -c rona -n "__fish_rona_using_subcommand add-with-exclude" -l lang -d 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`' -r
+complete -c rona -n "__fish_rona_using_subcommand add-with-exclude" -l template -d 'Commit template to use: the one named NAME in `[templates]`' -r
+complete -c rona -n "__fish_rona_using_subcommand add-with-exclude" -l scope -d 'Only consider the files under a directory: a name from `[scopes]` or a path' -r
+complete -c rona -n "__fish_rona_using_subcommand add-with-exclude" -l output -d 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`' -r -f -a "text\t'Human-readable text'
+json\t'A JSON object with a `schema_version`, for scripts and editor plugins'"
+complete -c rona -n "__fish_rona_using_subcommand add-with-exclude" -s i -l interactive -d 'Interactively pick which changed files to stage; excluded files start deselected'
 complete -c rona -n "__fish_rona_using_subcommand add-with-exclude" -l dry-run -d 'Show what would be added without actually adding files'
-complete -c rona -n "__fish_rona_using_subcommand add-with-exclude" -s h -l help -d 'Print help'
+complete -c rona -n "__fish_rona_using_subcommand add-with-exclude" -l read-only -d 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)'
+complete -c rona -n "__fish_rona_using_subcommand add-with-exclude" -s h -l help -d 'Print help (see more with \'--help\')'
+complete -c rona -n "__fish_rona_using_subcommand commit" -s m -l message -d 'Commit with this message run through the commit template, instead of `commit_message.md`' -r
+complete -c rona -n "__fish_rona_using_subcommand commit" -s t -l type -d 'Commit type for `--message` (inferred from the branch prefix by default)' -r
 complete -c rona -n "__fish_rona_using_subcommand commit" -s f -l config-file -d 'Config file to use instead of the default global/project hierarchy' -r -F
+complete -c rona -n "__fish_rona_using_subcommand commit" -s C -l chdir -d 'Run as if rona was started in PATH instead of the current directory (like `git -C`)' -r -f -a "(__fish_complete_directories)"
+complete -c rona -n "__fish_rona_using_subcommand commit" -l lang -d 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`' -r
+complete -c rona -n "__fish_rona_using_subcommand commit" -l template -d 'Commit template to use: the one named NAME in `[templates]`' -r
+complete -c rona -n "__fish_rona_using_subcommand commit" -l scope -d 'Only consider the files under a directory: a name from `[scopes]` or a path' -r
+complete -c rona -n "__fish_rona_using_subcommand commit" -l output -d 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`' -r -f -a "text\t'Human-readable text'
+json\t'A JSON object with a `schema_version`, for scripts and editor plugins'"
 complete -c rona -n "__fish_rona_using_subcommand commit" -s p -l push -d 'Whether to push the commit after committing'
 complete -c rona -n "__fish_rona_using_subcommand commit" -s d -l dry-run -d 'Show what would be committed without actually committing'
 complete -c rona -n "__fish_rona_using_subcommand commit" -s u -l unsigned -d 'Create unsigned commit (default is to auto-detect GPG availability and sign if possible)'
 complete -c rona -n "__fish_rona_using_subcommand commit" -s y -l yes -d 'Skip confirmation prompt and commit directly'
 complete -c rona -n "__fish_rona_using_subcommand commit" -l copy -d 'Copy commit message to clipboard instead of committing'
-complete -c rona -n "__fish_rona_using_subcommand commit" -s h -l help -d 'Print help'
-complete -c rona -n "__fish_rona_using_subcommand completion" -s f -l config-file -d 'Config file to use instead of the default global/project hierarchy' -r -F
-complete -c rona -n "__fish_rona_using_subcommand completion" -s h -l help -d 'Print help'
-complete -c rona -n "__fish_rona_using_subcommand config; and not __fish_seen_subcommand_from create which find help" -s f -l config-file -d 'Config file to use instead of the default global/project hierarchy' -r -F
-complete -c rona -n "__fish_rona_using_subcommand config; and not __fish_seen_subcommand_from create which find help" -s h -l help -d 'Print help'
-complete -c rona -n "__fish_rona_using_subcommand config; and not __fish_seen_subcommand_from create which find help" -f -a "create" -d 'Create or manage a local or global configuration file'
-complete -c rona -n "__fish_rona_using_subcommand config; and not __fish_seen_subcommand_from create which find help" -f -a "which" -d 'Show which configuration files would be used from a directory'
-complete -c rona -n "__fish_rona_using_subcommand config; and not __fish_seen_subcommand_from create which find help" -f -a "find" -d 'Show which configuration files would be used from a directory'
-complete -c rona -n "__fish_rona_using_subcommand config; and not __fish_seen_subcommand_from create which find help" -f -a "help" -d 'Print this message or the help of the given subcommand(s)'
+complete -c rona -n "__fish_rona_using_subcommand commit" -s n -l no-commit-number -d 'Leave the commit number out of a `--message` commit'
+complete -c rona -n "__fish_rona_using_subcommand commit" -l read-only -d 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)'
+complete -c rona -n "__fish_rona_using_subcommand commit" -s h -l help -d 'Print help (see more with \'--help\')'
+complete -c rona -n "__fish_rona_using_subcommand completion; and not __fish_seen_subcommand_from check help" -s f -l config-file -d 'Config file to use instead of the default global/project hierarchy' -r -F
+complete -c rona -n "__fish_rona_using_subcommand completion; and not __fish_seen_subcommand_from check help" -s C -l chdir -d 'Run as if rona was started in PATH instead of the current directory (like `git -C`)' -r -f -a "(__fish_complete_directories)"
+complete -c rona -n "__fish_rona_using_subcommand completion; and not __fish_seen_subcommand_from check help" -l lang -d 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`' -r
+complete -c rona -n "__fish_rona_using_subcommand completion; and not __fish_seen_subcommand_from check help" -l template -d 'Commit template to use: the one named NAME in `[templates]`' -r
+complete -c rona -n "__fish_rona_using_subcommand completion; and not __fish_seen_subcommand_from check help" -l scope -d 'Only consider the files under a directory: a name from `[scopes]` or a path' -r
+complete -c rona -n "__fish_rona_using_subcommand completion; and not __fish_seen_subcommand_from check help" -l output -d 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`' -r -f -a "text\t'Human-readable text'
+json\t'A JSON object with a `schema_version`, for scripts and editor plugins'"
+complete -c rona -n "__fish_rona_using_subcommand completion; and not __fish_seen_subcommand_from check help" -l read-only -d 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)'
+complete -c rona -n "__fish_rona_using_subcommand completion; and not __fish_seen_subcommand_from check help" -s h -l help -d 'Print help (see more with \'--help\')'
+complete -c rona -n "__fish_rona_using_subcommand completion; and not __fish_seen_subcommand_from check help" -a "check" -d 'Check that the completions are installed, up to date and able to list files'
+complete -c rona -n "__fish_rona_using_subcommand completion; and not __fish_seen_subcommand_from check help" -a "help" -d 'Print this message or the help of the given subcommand(s)'
+complete -c rona -n "__fish_rona_using_subcommand completion; and __fish_seen_subcommand_from check" -s f -l config-file -d 'Config file to use instead of the default global/project hierarchy' -r -F
+complete -c rona -n "__fish_rona_using_subcommand completion; and __fish_seen_subcommand_from check" -s C -l chdir -d 'Run as if rona was started in PATH instead of the current directory (like `git -C`)' -r -f -a "(__fish_complete_directories)"
+complete -c rona -n "__fish_rona_using_subcommand completion; and __fish_seen_subcommand_from check" -l lang -d 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`' -r
+complete -c rona -n "__fish_rona_using_subcommand completion; and __fish_seen_subcommand_from check" -l template -d 'Commit template to use: the one named NAME in `[templates]`' -r
+complete -c rona -n "__fish_rona_using_subcommand completion; and __fish_seen_subcommand_from check" -l scope -d 'Only consider the files under a directory: a name from `[scopes]` or a path' -r
+complete -c rona -n "__fish_rona_using_subcommand completion; and __fish_seen_subcommand_from check" -l output -d 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`' -r -f -a "text\t'Human-readable text'
+json\t'A JSON object with a `schema_version`, for scripts and editor plugins'"
+complete -c rona -n "__fish_rona_using_subcommand completion; and __fish_seen_subcommand_from check" -l read-only -d 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)'
+complete -c rona -n "__fish_rona_using_subcommand completion; and __fish_seen_subcommand_from check" -s h -l help -d 'Print help (see more with \'--help\')'
+complete -c rona -n "__fish_rona_using_subcommand completion; and __fish_seen_subcommand_from help" -f -a "check" -d 'Check that the completions are installed, up to date and able to list files'
+complete -c rona -n "__fish_rona_using_subcommand completion; and __fish_seen_subcommand_from help" -f -a "help" -d 'Print this message or the help of the given subcommand(s)'
+complete -c rona -n "__fish_rona_using_subcommand config; and not __fish_seen_subcommand_from create edit show sync which find help" -s f -l config-file -d 'Config file to use instead of the default global/project hierarchy' -r -F
+complete -c rona -n "__fish_rona_using_subcommand config; and not __fish_seen_subcommand_from create edit show sync which find help" -s C -l chdir -d 'Run as if rona was started in PATH instead of the current directory (like `git -C`)' -r -f -a "(__fish_complete_directories)"
+complete -c rona -n "__fish_rona_using_subcommand config; and not __fish_seen_subcommand_from create edit show sync which find help" -l lang -d 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`' -r
+complete -c rona -n "__fish_rona_using_subcommand config; and not __fish_seen_subcommand_from create edit show sync which find help" -l template -d 'Commit template to use: the one named NAME in `[templates]`' -r
+complete -c rona -n "__fish_rona_using_subcommand config; and not __fish_seen_subcommand_from create edit show sync which find help" -l scope -d 'Only consider the files under a directory: a name from `[scopes]` or a path' -r
+complete -c rona -n "__fish_rona_using_subcommand config; and not __fish_seen_subcommand_from create edit show sync which find help" -l output -d 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`' -r -f -a "text\t'Human-readable text'
+json\t'A JSON object with a `schema_version`, for scripts and editor plugins'"
+complete -c rona -n "__fish_rona_using_subcommand config; and not __fish_seen_subcommand_from create edit show sync which find help" -l read-only -d 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)'
+complete -c rona -n "__fish_rona_using_subcommand config; and not __fish_seen_subcommand_from create edit show sync which find help" -s h -l help -d 'Print help (see more with \'--help\')'
+complete -c rona -n "__fish_rona_using_subcommand config; and not __fish_seen_subcommand_from create edit show sync which find help" -f -a "create" -d 'Create or manage a local or global configuration file'
+complete -c rona -n "__fish_rona_using_subcommand config; and not __fish_seen_subcommand_from create edit show sync which find help" -f -a "edit" -d 'Edit a configuration file: in the editor, or key by key with `--interactive`'
+complete -c rona -n "__fish_rona_using_subcommand config; and not __fish_seen_subcommand_from create edit show sync which find help" -f -a "show" -d 'Print the effective configuration, merged from every config file'
+complete -c rona -n "__fish_rona_using_subcommand config; and not __fish_seen_subcommand_from create edit show sync which find help" -f -a "sync" -d 'Compare the project `.rona.toml` with the team\'s canonical one, set under `[config_sync]`, and apply the differences after confirmation'
+complete -c rona -n "__fish_rona_using_subcommand config; and not __fish_seen_subcommand_from create edit show sync which find help" -f -a "which" -d 'Show which configuration files would be used from a directory'
+complete -c rona -n "__fish_rona_using_subcommand config; and not __fish_seen_subcommand_from create edit show sync which find help" -f -a "find" -d 'Show which configuration files would be used from a directory'
+complete -c rona -n "__fish_rona_using_subcommand config; and not __fish_seen_subcommand_from create edit show sync which find help" -f -a "help" -d 'Print this message or the help of the given subcommand(s)'
 complete -c rona -n "__fish_rona_using_subcommand config; and __fish_seen_subcommand_from create" -s f -l config-file -d 'Config file to use instead of the default global/project hierarchy' -r -F
+complete -c rona -n "__fish_rona_using_subcommand config; and __fish_seen_subcommand_from create" -s C -l chdir -d 'Run as if rona was started in PATH instead of the current directory (like `git -C`)' -r -f -a "(__fish_complete_directories)"
+complete -c rona -n "__fish_rona_using_subcommand config; and __fish_seen_subcommand_from create" -l lang -d 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`' -r
+complete -c rona -n "__fish_rona_using_subcommand config; and __fish_seen_subcommand_from create" -l template -d 'Commit template to use: the one named NAME in `[templates]`' -r
+complete -c rona -n "__fish_rona_using_subcommand config; and __fish_seen_subcommand_from create" -l scope -d 'Only consider the files under a directory: a name from `[scopes]` or a path' -r
+complete -c rona -n "__fish_rona_using_subcommand config; and __fish_seen_subcommand_from create" -l output -d 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`' -r -f -a "text\t'Human-readable text'
+json\t'A JSON object with a `schema_version`, for scripts and editor plugins'"
 complete -c rona -n "__fish_rona_using_subcommand config; and __fish_seen_subcommand_from create" -s e -l exclude -d 'Add .rona.toml to .git/info/exclude (only applies to local scope)'
 complete -c rona -n "__fish_rona_using_subcommand config; and __fish_seen_subcommand_from create" -l dry-run -d 'Show what would be created without actually creating the config file'
+complete -c rona -n "__fish_rona_using_subcommand config; and __fish_seen_subcommand_from create" -l read-only -d 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)'
 complete -c rona -n "__fish_rona_using_subcommand config; and __fish_seen_subcommand_from create" -s h -l help -d 'Print help (see more with \'--help\')'
+complete -c rona -n "__fish_rona_using_subcommand config; and __fish_seen_subcommand_from edit" -s f -l config-file -d 'Config file to use instead of the default global/project hierarchy' -r -F
+complete -c rona -n "__fish_rona_using_subcommand config; and __fish_seen_subcommand_from edit" -s C -l chdir -d 'Run as if rona was started in PATH instead of the current directory (like `git -C`)' -r -f -a "(__fish_complete_directories)"
+complete -c rona -n "__fish_rona_using_subcommand config; and __fish_seen_subcommand_from edit" -l lang -d 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`' -r
+complete -c rona -n "__fish_rona_using_subcommand config; and __fish_seen_subcommand_from edit" -l template -d 'Commit template to use: the one named NAME in `[templates]`' -r
+complete -c rona -n "__fish_rona_using_subcommand config; and __fish_seen_subcommand_from edit" -l scope -d 'Only consider the files under a directory: a name from `[scopes]` or a path' -r
+complete -c rona -n "__fish_rona_using_subcommand config; and __fish_seen_subcommand_from edit" -l output -d 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`' -r -f -a "text\t'Human-readable text'
+json\t'A JSON object with a `schema_version`, for scripts and editor plugins'"
+complete -c rona -n "__fish_rona_using_subcommand config; and __fish_seen_subcommand_from edit" -s i -l interactive -d 'Pick known keys from a list and edit them with prompts showing the current value, the default and a description. Comments in the file are kept'
+complete -c rona -n "__fish_rona_using_subcommand config; and __fish_seen_subcommand_from edit" -l read-only -d 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)'
+complete -c rona -n "__fish_rona_using_subcommand config; and __fish_seen_subcommand_from edit" -s h -l help -d 'Print help (see more with \'--help\')'
+complete -c rona -n "__fish_rona_using_subcommand config; and __fish_seen_subcommand_from show" -s f -l config-file -d 'Config file to use instead of the default global/project hierarchy' -r -F
+complete -c rona -n "__fish_rona_using_subcommand config; and __fish_seen_subcommand_from show" -s C -l chdir -d 'Run as if rona was started in PATH instead of the current directory (like `git -C`)' -r -f -a "(__fish_complete_directories)"
+complete -c rona -n "__fish_rona_using_subcommand config; and __fish_seen_subcommand_from show" -l lang -d 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`' -r
+complete -c rona -n "__fish_rona_using_subcommand config; and __fish_seen_subcommand_from show" -l template -d 'Commit template to use: the one named NAME in `[templates]`' -r
+complete -c rona -n "__fish_rona_using_subcommand config; and __fish_seen_subcommand_from show" -l scope -d 'Only consider the files under a directory: a name from `[scopes]` or a path' -r
+complete -c rona -n "__fish_rona_using_subcommand config; and __fish_seen_subcommand_from show" -l output -d 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`' -r -f -a "text\t'Human-readable text'
+json\t'A JSON object with a `schema_version`, for scripts and editor plugins'"
+complete -c rona -n "__fish_rona_using_subcommand config; and __fish_seen_subcommand_from show" -l origins -d 'Print each value with the file, environment variable or option it came from'
+complete -c rona -n "__fish_rona_using_subcommand config; and __fish_seen_subcommand_from show" -l read-only -d 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)'
+complete -c rona -n "__fish_rona_using_subcommand config; and __fish_seen_subcommand_from show" -s h -l help -d 'Print help (see more with \'--help\')'
+complete -c rona -n "__fish_rona_using_subcommand config; and __fish_seen_subcommand_from sync" -l url -d 'Download the canonical config from this URL (default: `[config_sync] url`)' -r
+complete -c rona -n "__fish_rona_using_subcommand config; and __fish_seen_subcommand_from sync" -l remote -d 'Remote name or repository URL of the branch holding the canonical config (default: `[config_sync] remote`, else `origin`)' -r
+complete -c rona -n "__fish_rona_using_subcommand config; and __fish_seen_subcommand_from sync" -l branch -d 'Branch holding the canonical config (default: `[config_sync] branch`, else `rona-config`)' -r
+complete -c rona -n "__fish_rona_using_subcommand config; and __fish_seen_subcommand_from sync" -s f -l config-file -d 'Config file to use instead of the default global/project hierarchy' -r -F
+complete -c rona -n "__fish_rona_using_subcommand config; and __fish_seen_subcommand_from sync" -s C -l chdir -d 'Run as if rona was started in PATH instead of the current directory (like `git -C`)' -r -f -a "(__fish_complete_directories)"
+complete -c rona -n "__fish_rona_using_subcommand config; and __fish_seen_subcommand_from sync" -l lang -d 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`' -r
+complete -c rona -n "__fish_rona_using_subcommand config; and __fish_seen_subcommand_from sync" -l template -d 'Commit template to use: the one named NAME in `[templates]`' -r
+complete -c rona -n "__fish_rona_using_subcommand config; and __fish_seen_subcommand_from sync" -l scope -d 'Only consider the files under a directory: a name from `[scopes]` or a path' -r
+complete -c rona -n "__fish_rona_using_subcommand config; and __fish_seen_subcommand_from sync" -l output -d 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`' -r -f -a "text\t'Human-readable text'
+json\t'A JSON object with a `schema_version`, for scripts and editor plugins'"
+complete -c rona -n "__fish_rona_using_subcommand config; and __fish_seen_subcommand_from sync" -l check -d 'Fail when the local config differs, without changing it (for CI)'
+complete -c rona -n "__fish_rona_using_subcommand config; and __fish_seen_subcommand_from sync" -s y -l yes -d 'Apply the differences without asking'
+complete -c rona -n "__fish_rona_using_subcommand config; and __fish_seen_subcommand_from sync" -l dry-run -d 'Show the differences without applying them'
+complete -c rona -n "__fish_rona_using_subcommand config; and __fish_seen_subcommand_from sync" -l read-only -d 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)'
+complete -c rona -n "__fish_rona_using_subcommand config; and __fish_seen_subcommand_from sync" -s h -l help -d 'Print help (see more with \'--help\')'
 complete -c rona -n "__fish_rona_using_subcommand config; and __fish_seen_subcommand_from which" -s f -l config-file -d 'Config file to use instead of the default global/project hierarchy' -r -F
+complete -c rona -n "__fish_rona_using_subcommand config; and __fish_seen_subcommand_from which" -s C -l chdir -d 'Run as if rona was started in PATH instead of the current directory (like `git -C`)' -r -f -a "(__fish_complete_directories)"
+complete -c rona -n "__fish_rona_using_subcommand config; and __fish_seen_subcommand_from which" -l lang -d 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`' -r
+complete -c rona -n "__fish_rona_using_subcommand config; and __fish_seen_subcommand_from which" -l template -d 'Commit template to use: the one named NAME in `[templates]`' -r
+complete -c rona -n "__fish_rona_using_subcommand config; and __fish_seen_subcommand_from which" -l scope -d 'Only consider the files under a directory: a name from `[scopes]` or a path' -r
+complete -c rona -n "__fish_rona_using_subcommand config; and __fish_seen_subcommand_from which" -l output -d 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`' -r -f -a "text\t'Human-readable text'
+json\t'A JSON object with a `schema_version`, for scripts and editor plugins'"
 complete -c rona -n "__fish_rona_using_subcommand config; and __fish_seen_subcommand_from which" -s e -l effective -d 'Show the effective (merged) configuration values'
-complete -c rona -n "__fish_rona_using_subcommand config; and __fish_seen_subcommand_from which" -s h -l help -d 'Print help'
+complete -c rona -n "__fish_rona_using_subcommand config; and __fish_seen_subcommand_from which" -l read-only -d 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)'
+complete -c rona -n "__fish_rona_using_subcommand config; and __fish_seen_subcommand_from which" -s h -l help -d 'Print help (see more with \'--help\')'
 complete -c rona -n "__fish_rona_using_subcommand config; and __fish_seen_subcommand_from find" -s f -l config-file -d 'Config file to use instead of the default global/project hierarchy' -r -F
+complete -c rona -n "__fish_rona_using_subcommand config; and __fish_seen_subcommand_from find" -s C -l chdir -d 'Run as if rona was started in PATH instead of the current directory (like `git -C`)' -r -f -a "(__fish_complete_directories)"
+complete -c rona -n "__fish_rona_using_subcommand config; and __fish_seen_subcommand_from find" -l lang -d 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`' -r
+complete -c rona -n "__fish_rona_using_subcommand config; and __fish_seen_subcommand_from find" -l template -d 'Commit template to use: the one named NAME in `[templates]`' -r
+complete -c rona -n "__fish_rona_using_subcommand config; and __fish_seen_subcommand_from find" -l scope -d 'Only consider the files under a directory: a name from `[scopes]` or a path' -r
+complete -c rona -n "__fish_rona_using_subcommand config; and __fish_seen_subcommand_from find" -l output -d 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`' -r -f -a "text\t'Human-readable text'
+json\t'A JSON object with a `schema_version`, for scripts and editor plugins'"
 complete -c rona -n "__fish_rona_using_subcommand config; and __fish_seen_subcommand_from find" -s e -l effective -d 'Show the effective (merged) configuration values'
-complete -c rona -n "__fish_rona_using_subcommand config; and __fish_seen_subcommand_from find" -s h -l help -d 'Print help'
+complete -c rona -n "__fish_rona_using_subcommand config; and __fish_seen_subcommand_from find" -l read-only -d 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)'
+complete -c rona -n "__fish_rona_using_subcommand config; and __fish_seen_subcommand_from find" -s h -l help -d 'Print help (see more with \'--help\')'
 complete -c rona -n "__fish_rona_using_subcommand config; and __fish_seen_subcommand_from help" -f -a "create" -d 'Create or manage a local or global configuration file'
+complete -c rona -n "__fish_rona_using_subcommand config; and __fish_seen_subcommand_from help" -f -a "edit" -d 'Edit a configuration file: in the editor, or key by key with `--interactive`'
+complete -c rona -n "__fish_rona_using_subcommand config; and __fish_seen_subcommand_from help" -f -a "show" -d 'Print the effective configuration, merged from every config file'
+complete -c rona -n "__fish_rona_using_subcommand config; and __fish_seen_subcommand_from help" -f -a "sync" -d 'Compare the project `.rona.toml` with the team\'s canonical one, set under `[config_sync]`, and apply the differences after confirmation'
 complete -c rona -n "__fish_rona_using_subcommand config; and __fish_seen_subcommand_from help" -f -a "which" -d 'Show which configuration files would be used from a directory'
 complete -c rona -n "__fish_rona_using_subcommand config; and __fish_seen_subcommand_from help" -f -a "help" -d 'Print this message or the help of the given subcommand(s)'
+complete -c rona -n "__fish_rona_using_subcommand copy" -l format -d '`reference`, `sha`, `short`, `subject`, a format of `[copy] formats`, or a template such as `{short_sha} {subject}` (default: `[copy] format`)' -r
+complete -c rona -n "__fish_rona_using_subcommand copy" -s f -l config-file -d 'Config file to use instead of the default global/project hierarchy' -r -F
+complete -c rona -n "__fish_rona_using_subcommand copy" -s C -l chdir -d 'Run as if rona was started in PATH instead of the current directory (like `git -C`)' -r -f -a "(__fish_complete_directories)"
+complete -c rona -n "__fish_rona_using_subcommand copy" -l lang -d 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`' -r
+complete -c rona -n "__fish_rona_using_subcommand copy" -l template -d 'Commit template to use: the one named NAME in `[templates]`' -r
+complete -c rona -n "__fish_rona_using_subcommand copy" -l scope -d 'Only consider the files under a directory: a name from `[scopes]` or a path' -r
+complete -c rona -n "__fish_rona_using_subcommand copy" -l output -d 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`' -r -f -a "text\t'Human-readable text'
+json\t'A JSON object with a `schema_version`, for scripts and editor plugins'"
+complete -c rona -n "__fish_rona_using_subcommand copy" -l print -d 'Print the text instead of copying it'
+complete -c rona -n "__fish_rona_using_subcommand copy" -l read-only -d 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)'
+complete -c rona -n "__fish_rona_using_subcommand copy" -s h -l help -d 'Print help (see more with \'--help\')'
+complete -c rona -n "__fish_rona_using_subcommand daemon" -s f -l config-file -d 'Config file to use instead of the default global/project hierarchy' -r -F
+complete -c rona -n "__fish_rona_using_subcommand daemon" -s C -l chdir -d 'Run as if rona was started in PATH instead of the current directory (like `git -C`)' -r -f -a "(__fish_complete_directories)"
+complete -c rona -n "__fish_rona_using_subcommand daemon" -l lang -d 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`' -r
+complete -c rona -n "__fish_rona_using_subcommand daemon" -l template -d 'Commit template to use: the one named NAME in `[templates]`' -r
+complete -c rona -n "__fish_rona_using_subcommand daemon" -l scope -d 'Only consider the files under a directory: a name from `[scopes]` or a path' -r
+complete -c rona -n "__fish_rona_using_subcommand daemon" -l output -d 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`' -r -f -a "text\t'Human-readable text'
+json\t'A JSON object with a `schema_version`, for scripts and editor plugins'"
+complete -c rona -n "__fish_rona_using_subcommand daemon" -l stop -d 'Stop the daemon serving the current repository'
+complete -c rona -n "__fish_rona_using_subcommand daemon" -l read-only -d 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)'
+complete -c rona -n "__fish_rona_using_subcommand daemon" -s h -l help -d 'Print help (see more with \'--help\')'
+complete -c rona -n "__fish_rona_using_subcommand check-msg" -s f -l config-file -d 'Config file to use instead of the default global/project hierarchy' -r -F
+complete -c rona -n "__fish_rona_using_subcommand check-msg" -s C -l chdir -d 'Run as if rona was started in PATH instead of the current directory (like `git -C`)' -r -f -a "(__fish_complete_directories)"
+complete -c rona -n "__fish_rona_using_subcommand check-msg" -l lang -d 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`' -r
+complete -c rona -n "__fish_rona_using_subcommand check-msg" -l template -d 'Commit template to use: the one named NAME in `[templates]`' -r
+complete -c rona -n "__fish_rona_using_subcommand check-msg" -l scope -d 'Only consider the files under a directory: a name from `[scopes]` or a path' -r
+complete -c rona -n "__fish_rona_using_subcommand check-msg" -l output -d 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`' -r -f -a "text\t'Human-readable text'
+json\t'A JSON object with a `schema_version`, for scripts and editor plugins'"
+complete -c rona -n "__fish_rona_using_subcommand check-msg" -l read-only -d 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)'
+complete -c rona -n "__fish_rona_using_subcommand check-msg" -s h -l help -d 'Print help (see more with \'--help\')'
+complete -c rona -n "__fish_rona_using_subcommand env" -s f -l config-file -d 'Config file to use instead of the default global/project hierarchy' -r -F
+complete -c rona -n "__fish_rona_using_subcommand env" -s C -l chdir -d 'Run as if rona was started in PATH instead of the current directory (like `git -C`)' -r -f -a "(__fish_complete_directories)"
+complete -c rona -n "__fish_rona_using_subcommand env" -l lang -d 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`' -r
+complete -c rona -n "__fish_rona_using_subcommand env" -l template -d 'Commit template to use: the one named NAME in `[templates]`' -r
+complete -c rona -n "__fish_rona_using_subcommand env" -l scope -d 'Only consider the files under a directory: a name from `[scopes]` or a path' -r
+complete -c rona -n "__fish_rona_using_subcommand env" -l output -d 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`' -r -f -a "text\t'Human-readable text'
+json\t'A JSON object with a `schema_version`, for scripts and editor plugins'"
+complete -c rona -n "__fish_rona_using_subcommand env" -l json -d 'Print the environment as JSON'
+complete -c rona -n "__fish_rona_using_subcommand env" -l read-only -d 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)'
+complete -c rona -n "__fish_rona_using_subcommand env" -s h -l help -d 'Print help (see more with \'--help\')'
+complete -c rona -n "__fish_rona_using_subcommand which" -s f -l config-file -d 'Config file to use instead of the default global/project hierarchy' -r -F
+complete -c rona -n "__fish_rona_using_subcommand which" -s C -l chdir -d 'Run as if rona was started in PATH instead of the current directory (like `git -C`)' -r -f -a "(__fish_complete_directories)"
+complete -c rona -n "__fish_rona_using_subcommand which" -l lang -d 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`' -r
+complete -c rona -n "__fish_rona_using_subcommand which" -l template -d 'Commit template to use: the one named NAME in `[templates]`' -r
+complete -c rona -n "__fish_rona_using_subcommand which" -l scope -d 'Only consider the files under a directory: a name from `[scopes]` or a path' -r
+complete -c rona -n "__fish_rona_using_subcommand which" -l output -d 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`' -r -f -a "text\t'Human-readable text'
+json\t'A JSON object with a `schema_version`, for scripts and editor plugins'"
+complete -c rona -n "__fish_rona_using_subcommand which" -l json -d 'Print the environment as JSON'
+complete -c rona -n "__fish_rona_using_subcommand which" -l read-only -d 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)'
+complete -c rona -n "__fish_rona_using_subcommand which" -s h -l help -d 'Print help (see more with \'--help\')'
+complete -c rona -n "__fish_rona_using_subcommand explain-status" -s f -l config-file -d 'Config file to use instead of the default global/project hierarchy' -r -F
+complete -c rona -n "__fish_rona_using_subcommand explain-status" -s C -l chdir -d 'Run as if rona was started in PATH instead of the current directory (like `git -C`)' -r -f -a "(__fish_complete_directories)"
+complete -c rona -n "__fish_rona_using_subcommand explain-status" -l lang -d 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`' -r
+complete -c rona -n "__fish_rona_using_subcommand explain-status" -l template -d 'Commit template to use: the one named NAME in `[templates]`' -r
+complete -c rona -n "__fish_rona_using_subcommand explain-status" -l scope -d 'Only consider the files under a directory: a name from `[scopes]` or a path' -r
+complete -c rona -n "__fish_rona_using_subcommand explain-status" -l output -d 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`' -r -f -a "text\t'Human-readable text'
+json\t'A JSON object with a `schema_version`, for scripts and editor plugins'"
+complete -c rona -n "__fish_rona_using_subcommand explain-status" -l read-only -d 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)'
+complete -c rona -n "__fish_rona_using_subcommand explain-status" -s h -l help -d 'Print help (see more with \'--help\')'
+complete -c rona -n "__fish_rona_using_subcommand format-patch" -s n -l count -d 'Export the last N commits (default: 1 when no range is given)' -r
+complete -c rona -n "__fish_rona_using_subcommand format-patch" -s o -l output-dir -d 'Directory the patches are written to (default: `patches`)' -r -f -a "(__fish_complete_directories)"
+complete -c rona -n "__fish_rona_using_subcommand format-patch" -s f -l config-file -d 'Config file to use instead of the default global/project hierarchy' -r -F
+complete -c rona -n "__fish_rona_using_subcommand format-patch" -s C -l chdir -d 'Run as if rona was started in PATH instead of the current directory (like `git -C`)' -r -f -a "(__fish_complete_directories)"
+complete -c rona -n "__fish_rona_using_subcommand format-patch" -l lang -d 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`' -r
+complete -c rona -n "__fish_rona_using_subcommand format-patch" -l template -d 'Commit template to use: the one named NAME in `[templates]`' -r
+complete -c rona -n "__fish_rona_using_subcommand format-patch" -l scope -d 'Only consider the files under a directory: a name from `[scopes]` or a path' -r
+complete -c rona -n "__fish_rona_using_subcommand format-patch" -l output -d 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`' -r -f -a "text\t'Human-readable text'
+json\t'A JSON object with a `schema_version`, for scripts and editor plugins'"
+complete -c rona -n "__fish_rona_using_subcommand format-patch" -l cover-letter -d 'Write a cover letter (`0000-cover-letter.patch`) from the cover letter template'
+complete -c rona -n "__fish_rona_using_subcommand format-patch" -l dry-run -d 'Show which commits would be exported without writing files'
+complete -c rona -n "__fish_rona_using_subcommand format-patch" -l read-only -d 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)'
+complete -c rona -n "__fish_rona_using_subcommand format-patch" -s h -l help -d 'Print help (see more with \'--help\')'
+complete -c rona -n "__fish_rona_using_subcommand fragment; and not __fish_seen_subcommand_from add list build help" -s f -l config-file -d 'Config file to use instead of the default global/project hierarchy' -r -F
+complete -c rona -n "__fish_rona_using_subcommand fragment; and not __fish_seen_subcommand_from add list build help" -s C -l chdir -d 'Run as if rona was started in PATH instead of the current directory (like `git -C`)' -r -f -a "(__fish_complete_directories)"
+complete -c rona -n "__fish_rona_using_subcommand fragment; and not __fish_seen_subcommand_from add list build help" -l lang -d 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`' -r
+complete -c rona -n "__fish_rona_using_subcommand fragment; and not __fish_seen_subcommand_from add list build help" -l template -d 'Commit template to use: the one named NAME in `[templates]`' -r
+complete -c rona -n "__fish_rona_using_subcommand fragment; and not __fish_seen_subcommand_from add list build help" -l scope -d 'Only consider the files under a directory: a name from `[scopes]` or a path' -r
+complete -c rona -n "__fish_rona_using_subcommand fragment; and not __fish_seen_subcommand_from add list build help" -l output -d 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`' -r -f -a "text\t'Human-readable text'
+json\t'A JSON object with a `schema_version`, for scripts and editor plugins'"
+complete -c rona -n "__fish_rona_using_subcommand fragment; and not __fish_seen_subcommand_from add list build help" -l read-only -d 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)'
+complete -c rona -n "__fish_rona_using_subcommand fragment; and not __fish_seen_subcommand_from add list build help" -s h -l help -d 'Print help (see more with \'--help\')'
+complete -c rona -n "__fish_rona_using_subcommand fragment; and not __fish_seen_subcommand_from add list build help" -f -a "add" -d 'Write a changelog fragment for the current branch'
+complete -c rona -n "__fish_rona_using_subcommand fragment; and not __fish_seen_subcommand_from add list build help" -f -a "list" -d 'List the fragments waiting for the next release'
+complete -c rona -n "__fish_rona_using_subcommand fragment; and not __fish_seen_subcommand_from add list build help" -f -a "build" -d 'Add the fragments to the changelog as a new release, then delete them'
+complete -c rona -n "__fish_rona_using_subcommand fragment; and not __fish_seen_subcommand_from add list build help" -f -a "help" -d 'Print this message or the help of the given subcommand(s)'
+complete -c rona -n "__fish_rona_using_subcommand fragment; and __fish_seen_subcommand_from add" -l ticket -d 'Ticket the change belongs to, added after the entry (e.g. `ABC-123`)' -r
+complete -c rona -n "__fish_rona_using_subcommand fragment; and __fish_seen_subcommand_from add" -l package -d 'Package of `[packages]` the fragment belongs to, in a monorepo' -r
+complete -c rona -n "__fish_rona_using_subcommand fragment; and __fish_seen_subcommand_from add" -s f -l config-file -d 'Config file to use instead of the default global/project hierarchy' -r -F
+complete -c rona -n "__fish_rona_using_subcommand fragment; and __fish_seen_subcommand_from add" -s C -l chdir -d 'Run as if rona was started in PATH instead of the current directory (like `git -C`)' -r -f -a "(__fish_complete_directories)"
+complete -c rona -n "__fish_rona_using_subcommand fragment; and __fish_seen_subcommand_from add" -l lang -d 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`' -r
+complete -c rona -n "__fish_rona_using_subcommand fragment; and __fish_seen_subcommand_from add" -l template -d 'Commit template to use: the one named NAME in `[templates]`' -r
+complete -c rona -n "__fish_rona_using_subcommand fragment; and __fish_seen_subcommand_from add" -l scope -d 'Only consider the files under a directory: a name from `[scopes]` or a path' -r
+complete -c rona -n "__fish_rona_using_subcommand fragment; and __fish_seen_subcommand_from add" -l output -d 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`' -r -f -a "text\t'Human-readable text'
+json\t'A JSON object with a `schema_version`, for scripts and editor plugins'"
+complete -c rona -n "__fish_rona_using_subcommand fragment; and __fish_seen_subcommand_from add" -l dry-run -d 'Show the fragment that would be written without writing it'
+complete -c rona -n "__fish_rona_using_subcommand fragment; and __fish_seen_subcommand_from add" -l read-only -d 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)'
+complete -c rona -n "__fish_rona_using_subcommand fragment; and __fish_seen_subcommand_from add" -s h -l help -d 'Print help (see more with \'--help\')'
+complete -c rona -n "__fish_rona_using_subcommand fragment; and __fish_seen_subcommand_from list" -l package -d 'Package of `[packages]` to list the fragments of, in a monorepo' -r
+complete -c rona -n "__fish_rona_using_subcommand fragment; and __fish_seen_subcommand_from list" -s f -l config-file -d 'Config file to use instead of the default global/project hierarchy' -r -F
+complete -c rona -n "__fish_rona_using_subcommand fragment; and __fish_seen_subcommand_from list" -s C -l chdir -d 'Run as if rona was started in PATH instead of the current directory (like `git -C`)' -r -f -a "(__fish_complete_directories)"
+complete -c rona -n "__fish_rona_using_subcommand fragment; and __fish_seen_subcommand_from list" -l lang -d 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`' -r
+complete -c rona -n "__fish_rona_using_subcommand fragment; and __fish_seen_subcommand_from list" -l template -d 'Commit template to use: the one named NAME in `[templates]`' -r
+complete -c rona -n "__fish_rona_using_subcommand fragment; and __fish_seen_subcommand_from list" -l scope -d 'Only consider the files under a directory: a name from `[scopes]` or a path' -r
+complete -c rona -n "__fish_rona_using_subcommand fragment; and __fish_seen_subcommand_from list" -l output -d 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`' -r -f -a "text\t'Human-readable text'
+json\t'A JSON object with a `schema_version`, for scripts and editor plugins'"
+complete -c rona -n "__fish_rona_using_subcommand fragment; and __fish_seen_subcommand_from list" -l read-only -d 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)'
+complete -c rona -n "__fish_rona_using_subcommand fragment; and __fish_seen_subcommand_from list" -s h -l help -d 'Print help (see more with \'--help\')'
+complete -c rona -n "__fish_rona_using_subcommand fragment; and __fish_seen_subcommand_from build" -l date -d 'Date of the release (defaults to today)' -r
+complete -c rona -n "__fish_rona_using_subcommand fragment; and __fish_seen_subcommand_from build" -l package -d 'Package of `[packages]` to build the changelog of, in a monorepo' -r
+complete -c rona -n "__fish_rona_using_subcommand fragment; and __fish_seen_subcommand_from build" -s f -l config-file -d 'Config file to use instead of the default global/project hierarchy' -r -F
+complete -c rona -n "__fish_rona_using_subcommand fragment; and __fish_seen_subcommand_from build" -s C -l chdir -d 'Run as if rona was started in PATH instead of the current directory (like `git -C`)' -r -f -a "(__fish_complete_directories)"
+complete -c rona -n "__fish_rona_using_subcommand fragment; and __fish_seen_subcommand_from build" -l lang -d 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`' -r
+complete -c rona -n "__fish_rona_using_subcommand fragment; and __fish_seen_subcommand_from build" -l template -d 'Commit template to use: the one named NAME in `[templates]`' -r
+complete -c rona -n "__fish_rona_using_subcommand fragment; and __fish_seen_subcommand_from build" -l scope -d 'Only consider the files under a directory: a name from `[scopes]` or a path' -r
+complete -c rona -n "__fish_rona_using_subcommand fragment; and __fish_seen_subcommand_from build" -l output -d 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`' -r -f -a "text\t'Human-readable text'
+json\t'A JSON object with a `schema_version`, for scripts and editor plugins'"
+complete -c rona -n "__fish_rona_using_subcommand fragment; and __fish_seen_subcommand_from build" -l keep -d 'Keep the fragment files'
+complete -c rona -n "__fish_rona_using_subcommand fragment; and __fish_seen_subcommand_from build" -l dry-run -d 'Print the release section without changing any file'
+complete -c rona -n "__fish_rona_using_subcommand fragment; and __fish_seen_subcommand_from build" -l read-only -d 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)'
+complete -c rona -n "__fish_rona_using_subcommand fragment; and __fish_seen_subcommand_from build" -s h -l help -d 'Print help (see more with \'--help\')'
+complete -c rona -n "__fish_rona_using_subcommand fragment; and __fish_seen_subcommand_from help" -f -a "add" -d 'Write a changelog fragment for the current branch'
+complete -c rona -n "__fish_rona_using_subcommand fragment; and __fish_seen_subcommand_from help" -f -a "list" -d 'List the fragments waiting for the next release'
+complete -c rona -n "__fish_rona_using_subcommand fragment; and __fish_seen_subcommand_from help" -f -a "build" -d 'Add the fragments to the changelog as a new release, then delete them'
+complete -c rona -n "__fish_rona_using_subcommand fragment; and __fish_seen_subcommand_from help" -f -a "help" -d 'Print this message or the help of the given subcommand(s)'
 complete -c rona -n "__fish_rona_using_subcommand generate" -s f -l config-file -d 'Config file to use instead of the default global/project hierarchy' -r -F
+complete -c rona -n "__fish_rona_using_subcommand generate" -s C -l chdir -d 'Run as if rona was started in PATH instead of the current directory (like `git -C`)' -r -f -a "(__fish_complete_directories)"
+complete -c rona -n "__fish_rona_using_subcommand generate" -l lang -d 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`' -r
+complete -c rona -n "__fish_rona_using_subcommand generate" -l template -d 'Commit template to use: the one named NAME in `[templates]`' -r
+complete -c rona -n "__fish_rona_using_subcommand generate" -l scope -d 'Only consider the files under a directory: a name from `[scopes]` or a path' -r
+complete -c rona -n "__fish_rona_using_subcommand generate" -l output -d 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`' -r -f -a "text\t'Human-readable text'
+json\t'A JSON object with a `schema_version`, for scripts and editor plugins'"
 complete -c rona -n "__fish_rona_using_subcommand generate" -l dry-run -d 'Show what would be generated without creating files'
 complete -c rona -n "__fish_rona_using_subcommand generate" -s i -l interactive -d 'Interactive mode - input the commit message directly in the terminal'
 complete -c rona -n "__fish_rona_using_subcommand generate" -s n -l no-commit-number -d 'No commit number'
-complete -c rona -n "__fish_rona_using_subcommand generate" -s h -l help -d 'Print help'
+complete -c rona -n "__fish_rona_using_subcommand generate" -l print -d 'Print the generated message to stdout instead of writing `commit_message.md` (safe to use from git hooks)'
+complete -c rona -n "__fish_rona_using_subcommand generate" -l stdin -d 'Read the message from stdin and write `commit_message.md` without prompting (the commit type is inferred from the branch prefix)'
+complete -c rona -n "__fish_rona_using_subcommand generate" -l from-clipboard -d 'Read the message from the clipboard and write `commit_message.md` without prompting (the commit type is inferred from the branch prefix)'
+complete -c rona -n "__fish_rona_using_subcommand generate" -l read-only -d 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)'
+complete -c rona -n "__fish_rona_using_subcommand generate" -s h -l help -d 'Print help (see more with \'--help\')'
+complete -c rona -n "__fish_rona_using_subcommand handoff; and not __fish_seen_subcommand_from push pull help" -s f -l config-file -d 'Config file to use instead of the default global/project hierarchy' -r -F
+complete -c rona -n "__fish_rona_using_subcommand handoff; and not __fish_seen_subcommand_from push pull help" -s C -l chdir -d 'Run as if rona was started in PATH instead of the current directory (like `git -C`)' -r -f -a "(__fish_complete_directories)"
+complete -c rona -n "__fish_rona_using_subcommand handoff; and not __fish_seen_subcommand_from push pull help" -l lang -d 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`' -r
+complete -c rona -n "__fish_rona_using_subcommand handoff; and not __fish_seen_subcommand_from push pull help" -l template -d 'Commit template to use: the one named NAME in `[templates]`' -r
+complete -c rona -n "__fish_rona_using_subcommand handoff; and not __fish_seen_subcommand_from push pull help" -l scope -d 'Only consider the files under a directory: a name from `[scopes]` or a path' -r
+complete -c rona -n "__fish_rona_using_subcommand handoff; and not __fish_seen_subcommand_from push pull help" -l output -d 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`' -r -f -a "text\t'Human-readable text'
+json\t'A JSON object with a `schema_version`, for scripts and editor plugins'"
+complete -c rona -n "__fish_rona_using_subcommand handoff; and not __fish_seen_subcommand_from push pull help" -l read-only -d 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)'
+complete -c rona -n "__fish_rona_using_subcommand handoff; and not __fish_seen_subcommand_from push pull help" -s h -l help -d 'Print help (see more with \'--help\')'
+complete -c rona -n "__fish_rona_using_subcommand handoff; and not __fish_seen_subcommand_from push pull help" -f -a "push" -d 'Push the staged changes and `commit_message.md` of the current branch'
+complete -c rona -n "__fish_rona_using_subcommand handoff; and not __fish_seen_subcommand_from push pull help" -f -a "pull" -d 'Check out a branch handed off from another clone and stage its changes'
+complete -c rona -n "__fish_rona_using_subcommand handoff; and not __fish_seen_subcommand_from push pull help" -f -a "help" -d 'Print this message or the help of the given subcommand(s)'
+complete -c rona -n "__fish_rona_using_subcommand handoff; and __fish_seen_subcommand_from push" -l remote -d 'Remote to push to (default: `[handoff] remote`, else `origin`)' -r
+complete -c rona -n "__fish_rona_using_subcommand handoff; and __fish_seen_subcommand_from push" -s f -l config-file -d 'Config file to use instead of the default global/project hierarchy' -r -F
+complete -c rona -n "__fish_rona_using_subcommand handoff; and __fish_seen_subcommand_from push" -s C -l chdir -d 'Run as if rona was started in PATH instead of the current directory (like `git -C`)' -r -f -a "(__fish_complete_directories)"
+complete -c rona -n "__fish_rona_using_subcommand handoff; and __fish_seen_subcommand_from push" -l lang -d 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`' -r
+complete -c rona -n "__fish_rona_using_subcommand handoff; and __fish_seen_subcommand_from push" -l template -d 'Commit template to use: the one named NAME in `[templates]`' -r
+complete -c rona -n "__fish_rona_using_subcommand handoff; and __fish_seen_subcommand_from push" -l scope -d 'Only consider the files under a directory: a name from `[scopes]` or a path' -r
+complete -c rona -n "__fish_rona_using_subcommand handoff; and __fish_seen_subcommand_from push" -l output -d 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`' -r -f -a "text\t'Human-readable text'
+json\t'A JSON object with a `schema_version`, for scripts and editor plugins'"
+complete -c rona -n "__fish_rona_using_subcommand handoff; and __fish_seen_subcommand_from push" -l dry-run -d 'Show what would be handed off without pushing'
+complete -c rona -n "__fish_rona_using_subcommand handoff; and __fish_seen_subcommand_from push" -l read-only -d 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)'
+complete -c rona -n "__fish_rona_using_subcommand handoff; and __fish_seen_subcommand_from push" -s h -l help -d 'Print help (see more with \'--help\')'
+complete -c rona -n "__fish_rona_using_subcommand handoff; and __fish_seen_subcommand_from pull" -l remote -d 'Remote to pull from (default: `[handoff] remote`, else `origin`)' -r
+complete -c rona -n "__fish_rona_using_subcommand handoff; and __fish_seen_subcommand_from pull" -s f -l config-file -d 'Config file to use instead of the default global/project hierarchy' -r -F
+complete -c rona -n "__fish_rona_using_subcommand handoff; and __fish_seen_subcommand_from pull" -s C -l chdir -d 'Run as if rona was started in PATH instead of the current directory (like `git -C`)' -r -f -a "(__fish_complete_directories)"
+complete -c rona -n "__fish_rona_using_subcommand handoff; and __fish_seen_subcommand_from pull" -l lang -d 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`' -r
+complete -c rona -n "__fish_rona_using_subcommand handoff; and __fish_seen_subcommand_from pull" -l template -d 'Commit template to use: the one named NAME in `[templates]`' -r
+complete -c rona -n "__fish_rona_using_subcommand handoff; and __fish_seen_subcommand_from pull" -l scope -d 'Only consider the files under a directory: a name from `[scopes]` or a path' -r
+complete -c rona -n "__fish_rona_using_subcommand handoff; and __fish_seen_subcommand_from pull" -l output -d 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`' -r -f -a "text\t'Human-readable text'
+json\t'A JSON object with a `schema_version`, for scripts and editor plugins'"
+complete -c rona -n "__fish_rona_using_subcommand handoff; and __fish_seen_subcommand_from pull" -l dry-run -d 'Show the handed-off files without changing anything'
+complete -c rona -n "__fish_rona_using_subcommand handoff; and __fish_seen_subcommand_from pull" -l read-only -d 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)'
+complete -c rona -n "__fish_rona_using_subcommand handoff; and __fish_seen_subcommand_from pull" -s h -l help -d 'Print help (see more with \'--help\')'
+complete -c rona -n "__fish_rona_using_subcommand handoff; and __fish_seen_subcommand_from help" -f -a "push" -d 'Push the staged changes and `commit_message.md` of the current branch'
+complete -c rona -n "__fish_rona_using_subcommand handoff; and __fish_seen_subcommand_from help" -f -a "pull" -d 'Check out a branch handed off from another clone and stage its changes'
+complete -c rona -n "__fish_rona_using_subcommand handoff; and __fish_seen_subcommand_from help" -f -a "help" -d 'Print this message or the help of the given subcommand(s)'
+complete -c rona -n "__fish_rona_using_subcommand health" -s f -l config-file -d 'Config file to use instead of the default global/project hierarchy' -r -F
+complete -c rona -n "__fish_rona_using_subcommand health" -s C -l chdir -d 'Run as if rona was started in PATH instead of the current directory (like `git -C`)' -r -f -a "(__fish_complete_directories)"
+complete -c rona -n "__fish_rona_using_subcommand health" -l lang -d 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`' -r
+complete -c rona -n "__fish_rona_using_subcommand health" -l template -d 'Commit template to use: the one named NAME in `[templates]`' -r
+complete -c rona -n "__fish_rona_using_subcommand health" -l scope -d 'Only consider the files under a directory: a name from `[scopes]` or a path' -r
+complete -c rona -n "__fish_rona_using_subcommand health" -l output -d 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`' -r -f -a "text\t'Human-readable text'
+json\t'A JSON object with a `schema_version`, for scripts and editor plugins'"
+complete -c rona -n "__fish_rona_using_subcommand health" -l fix -d 'Offer to run the suggested git commands, asking before each one'
+complete -c rona -n "__fish_rona_using_subcommand health" -l optimize -d 'Write a commit-graph (with changed-path filters) and keep it updated on fetch, which speeds up commit counting and history walks'
+complete -c rona -n "__fish_rona_using_subcommand health" -l read-only -d 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)'
+complete -c rona -n "__fish_rona_using_subcommand health" -s h -l help -d 'Print help (see more with \'--help\')'
+complete -c rona -n "__fish_rona_using_subcommand history; and not __fish_seen_subcommand_from ops help" -s f -l config-file -d 'Config file to use instead of the default global/project hierarchy' -r -F
+complete -c rona -n "__fish_rona_using_subcommand history; and not __fish_seen_subcommand_from ops help" -s C -l chdir -d 'Run as if rona was started in PATH instead of the current directory (like `git -C`)' -r -f -a "(__fish_complete_directories)"
+complete -c rona -n "__fish_rona_using_subcommand history; and not __fish_seen_subcommand_from ops help" -l lang -d 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`' -r
+complete -c rona -n "__fish_rona_using_subcommand history; and not __fish_seen_subcommand_from ops help" -l template -d 'Commit template to use: the one named NAME in `[templates]`' -r
+complete -c rona -n "__fish_rona_using_subcommand history; and not __fish_seen_subcommand_from ops help" -l scope -d 'Only consider the files under a directory: a name from `[scopes]` or a path' -r
+complete -c rona -n "__fish_rona_using_subcommand history; and not __fish_seen_subcommand_from ops help" -l output -d 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`' -r -f -a "text\t'Human-readable text'
+json\t'A JSON object with a `schema_version`, for scripts and editor plugins'"
+complete -c rona -n "__fish_rona_using_subcommand history; and not __fish_seen_subcommand_from ops help" -l read-only -d 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)'
+complete -c rona -n "__fish_rona_using_subcommand history; and not __fish_seen_subcommand_from ops help" -s h -l help -d 'Print help (see more with \'--help\')'
+complete -c rona -n "__fish_rona_using_subcommand history; and not __fish_seen_subcommand_from ops help" -f -a "ops" -d 'List the state-changing operations rona performed, newest first'
+complete -c rona -n "__fish_rona_using_subcommand history; and not __fish_seen_subcommand_from ops help" -f -a "help" -d 'Print this message or the help of the given subcommand(s)'
+complete -c rona -n "__fish_rona_using_subcommand history; and __fish_seen_subcommand_from ops" -s n -l limit -d 'Number of operations to show' -r
+complete -c rona -n "__fish_rona_using_subcommand history; and __fish_seen_subcommand_from ops" -s f -l config-file -d 'Config file to use instead of the default global/project hierarchy' -r -F
+complete -c rona -n "__fish_rona_using_subcommand history; and __fish_seen_subcommand_from ops" -s C -l chdir -d 'Run as if rona was started in PATH instead of the current directory (like `git -C`)' -r -f -a "(__fish_complete_directories)"
+complete -c rona -n "__fish_rona_using_subcommand history; and __fish_seen_subcommand_from ops" -l lang -d 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`' -r
+complete -c rona -n "__fish_rona_using_subcommand history; and __fish_seen_subcommand_from ops" -l template -d 'Commit template to use: the one named NAME in `[templates]`' -r
+complete -c rona -n "__fish_rona_using_subcommand history; and __fish_seen_subcommand_from ops" -l scope -d 'Only consider the files under a directory: a name from `[scopes]` or a path' -r
+complete -c rona -n "__fish_rona_using_subcommand history; and __fish_seen_subcommand_from ops" -l output -d 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`' -r -f -a "text\t'Human-readable text'
+json\t'A JSON object with a `schema_version`, for scripts and editor plugins'"
+complete -c rona -n "__fish_rona_using_subcommand history; and __fish_seen_subcommand_from ops" -l json -d 'Print the operations as JSON lines'
+complete -c rona -n "__fish_rona_using_subcommand history; and __fish_seen_subcommand_from ops" -l read-only -d 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)'
+complete -c rona -n "__fish_rona_using_subcommand history; and __fish_seen_subcommand_from ops" -s h -l help -d 'Print help (see more with \'--help\')'
+complete -c rona -n "__fish_rona_using_subcommand history; and __fish_seen_subcommand_from help" -f -a "ops" -d 'List the state-changing operations rona performed, newest first'
+complete -c rona -n "__fish_rona_using_subcommand history; and __fish_seen_subcommand_from help" -f -a "help" -d 'Print this message or the help of the given subcommand(s)'
+complete -c rona -n "__fish_rona_using_subcommand hooks; and not __fish_seen_subcommand_from install uninstall list run help" -s f -l config-file -d 'Config file to use instead of the default global/project hierarchy' -r -F
+complete -c rona -n "__fish_rona_using_subcommand hooks; and not __fish_seen_subcommand_from install uninstall list run help" -s C -l chdir -d 'Run as if rona was started in PATH instead of the current directory (like `git -C`)' -r -f -a "(__fish_complete_directories)"
+complete -c rona -n "__fish_rona_using_subcommand hooks; and not __fish_seen_subcommand_from install uninstall list run help" -l lang -d 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`' -r
+complete -c rona -n "__fish_rona_using_subcommand hooks; and not __fish_seen_subcommand_from install uninstall list run help" -l template -d 'Commit template to use: the one named NAME in `[templates]`' -r
+complete -c rona -n "__fish_rona_using_subcommand hooks; and not __fish_seen_subcommand_from install uninstall list run help" -l scope -d 'Only consider the files under a directory: a name from `[scopes]` or a path' -r
+complete -c rona -n "__fish_rona_using_subcommand hooks; and not __fish_seen_subcommand_from install uninstall list run help" -l output -d 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`' -r -f -a "text\t'Human-readable text'
+json\t'A JSON object with a `schema_version`, for scripts and editor plugins'"
+complete -c rona -n "__fish_rona_using_subcommand hooks; and not __fish_seen_subcommand_from install uninstall list run help" -l read-only -d 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)'
+complete -c rona -n "__fish_rona_using_subcommand hooks; and not __fish_seen_subcommand_from install uninstall list run help" -s h -l help -d 'Print help (see more with \'--help\')'
+complete -c rona -n "__fish_rona_using_subcommand hooks; and not __fish_seen_subcommand_from install uninstall list run help" -f -a "install" -d 'Write managed hook scripts that call back into rona'
+complete -c rona -n "__fish_rona_using_subcommand hooks; and not __fish_seen_subcommand_from install uninstall list run help" -f -a "uninstall" -d 'Remove the hook scripts rona installed, leaving other hooks alone'
+complete -c rona -n "__fish_rona_using_subcommand hooks; and not __fish_seen_subcommand_from install uninstall list run help" -f -a "list" -d 'Show which hooks are installed and whether rona manages them'
+complete -c rona -n "__fish_rona_using_subcommand hooks; and not __fish_seen_subcommand_from install uninstall list run help" -f -a "run" -d 'Run a hook\'s checks; called by the installed hook scripts'
+complete -c rona -n "__fish_rona_using_subcommand hooks; and not __fish_seen_subcommand_from install uninstall list run help" -f -a "help" -d 'Print this message or the help of the given subcommand(s)'
+complete -c rona -n "__fish_rona_using_subcommand hooks; and __fish_seen_subcommand_from install" -s f -l config-file -d 'Config file to use instead of the default global/project hierarchy' -r -F
+complete -c rona -n "__fish_rona_using_subcommand hooks; and __fish_seen_subcommand_from install" -s C -l chdir -d 'Run as if rona was started in PATH instead of the current directory (like `git -C`)' -r -f -a "(__fish_complete_directories)"
+complete -c rona -n "__fish_rona_using_subcommand hooks; and __fish_seen_subcommand_from install" -l lang -d 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`' -r
+complete -c rona -n "__fish_rona_using_subcommand hooks; and __fish_seen_subcommand_from install" -l template -d 'Commit template to use: the one named NAME in `[templates]`' -r
+complete -c rona -n "__fish_rona_using_subcommand hooks; and __fish_seen_subcommand_from install" -l scope -d 'Only consider the files under a directory: a name from `[scopes]` or a path' -r
+complete -c rona -n "__fish_rona_using_subcommand hooks; and __fish_seen_subcommand_from install" -l output -d 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`' -r -f -a "text\t'Human-readable text'
+json\t'A JSON object with a `schema_version`, for scripts and editor plugins'"
+complete -c rona -n "__fish_rona_using_subcommand hooks; and __fish_seen_subcommand_from install" -l dry-run -d 'Show what would be installed without writing anything'
+complete -c rona -n "__fish_rona_using_subcommand hooks; and __fish_seen_subcommand_from install" -l read-only -d 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)'
+complete -c rona -n "__fish_rona_using_subcommand hooks; and __fish_seen_subcommand_from install" -s h -l help -d 'Print help (see more with \'--help\')'
+complete -c rona -n "__fish_rona_using_subcommand hooks; and __fish_seen_subcommand_from uninstall" -s f -l config-file -d 'Config file to use instead of the default global/project hierarchy' -r -F
+complete -c rona -n "__fish_rona_using_subcommand hooks; and __fish_seen_subcommand_from uninstall" -s C -l chdir -d 'Run as if rona was started in PATH instead of the current directory (like `git -C`)' -r -f -a "(__fish_complete_directories)"
+complete -c rona -n "__fish_rona_using_subcommand hooks; and __fish_seen_subcommand_from uninstall" -l lang -d 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`' -r
+complete -c rona -n "__fish_rona_using_subcommand hooks; and __fish_seen_subcommand_from uninstall" -l template -d 'Commit template to use: the one named NAME in `[templates]`' -r
+complete -c rona -n "__fish_rona_using_subcommand hooks; and __fish_seen_subcommand_from uninstall" -l scope -d 'Only consider the files under a directory: a name from `[scopes]` or a path' -r
+complete -c rona -n "__fish_rona_using_subcommand hooks; and __fish_seen_subcommand_from uninstall" -l output -d 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`' -r -f -a "text\t'Human-readable text'
+json\t'A JSON object with a `schema_version`, for scripts and editor plugins'"
+complete -c rona -n "__fish_rona_using_subcommand hooks; and __fish_seen_subcommand_from uninstall" -l dry-run -d 'Show what would be removed without removing anything'
+complete -c rona -n "__fish_rona_using_subcommand hooks; and __fish_seen_subcommand_from uninstall" -l read-only -d 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)'
+complete -c rona -n "__fish_rona_using_subcommand hooks; and __fish_seen_subcommand_from uninstall" -s h -l help -d 'Print help (see more with \'--help\')'
+complete -c rona -n "__fish_rona_using_subcommand hooks; and __fish_seen_subcommand_from list" -s f -l config-file -d 'Config file to use instead of the default global/project hierarchy' -r -F
+complete -c rona -n "__fish_rona_using_subcommand hooks; and __fish_seen_subcommand_from list" -s C -l chdir -d 'Run as if rona was started in PATH instead of the current directory (like `git -C`)' -r -f -a "(__fish_complete_directories)"
+complete -c rona -n "__fish_rona_using_subcommand hooks; and __fish_seen_subcommand_from list" -l lang -d 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`' -r
+complete -c rona -n "__fish_rona_using_subcommand hooks; and __fish_seen_subcommand_from list" -l template -d 'Commit template to use: the one named NAME in `[templates]`' -r
+complete -c rona -n "__fish_rona_using_subcommand hooks; and __fish_seen_subcommand_from list" -l scope -d 'Only consider the files under a directory: a name from `[scopes]` or a path' -r
+complete -c rona -n "__fish_rona_using_subcommand hooks; and __fish_seen_subcommand_from list" -l output -d 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`' -r -f -a "text\t'Human-readable text'
+json\t'A JSON object with a `schema_version`, for scripts and editor plugins'"
+complete -c rona -n "__fish_rona_using_subcommand hooks; and __fish_seen_subcommand_from list" -l read-only -d 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)'
+complete -c rona -n "__fish_rona_using_subcommand hooks; and __fish_seen_subcommand_from list" -s h -l help -d 'Print help (see more with \'--help\')'
+complete -c rona -n "__fish_rona_using_subcommand hooks; and __fish_seen_subcommand_from run" -s f -l config-file -d 'Config file to use instead of the default global/project hierarchy' -r -F
+complete -c rona -n "__fish_rona_using_subcommand hooks; and __fish_seen_subcommand_from run" -s C -l chdir -d 'Run as if rona was started in PATH instead of the current directory (like `git -C`)' -r -f -a "(__fish_complete_directories)"
+complete -c rona -n "__fish_rona_using_subcommand hooks; and __fish_seen_subcommand_from run" -l lang -d 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`' -r
+complete -c rona -n "__fish_rona_using_subcommand hooks; and __fish_seen_subcommand_from run" -l template -d 'Commit template to use: the one named NAME in `[templates]`' -r
+complete -c rona -n "__fish_rona_using_subcommand hooks; and __fish_seen_subcommand_from run" -l scope -d 'Only consider the files under a directory: a name from `[scopes]` or a path' -r
+complete -c rona -n "__fish_rona_using_subcommand hooks; and __fish_seen_subcommand_from run" -l output -d 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`' -r -f -a "text\t'Human-readable text'
+json\t'A JSON object with a `schema_version`, for scripts and editor plugins'"
+complete -c rona -n "__fish_rona_using_subcommand hooks; and __fish_seen_subcommand_from run" -l read-only -d 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)'
+complete -c rona -n "__fish_rona_using_subcommand hooks; and __fish_seen_subcommand_from run" -s h -l help -d 'Print help (see more with \'--help\')'
+complete -c rona -n "__fish_rona_using_subcommand hooks; and __fish_seen_subcommand_from help" -f -a "install" -d 'Write managed hook scripts that call back into rona'
+complete -c rona -n "__fish_rona_using_subcommand hooks; and __fish_seen_subcommand_from help" -f -a "uninstall" -d 'Remove the hook scripts rona installed, leaving other hooks alone'
+complete -c rona -n "__fish_rona_using_subcommand hooks; and __fish_seen_subcommand_from help" -f -a "list" -d 'Show which hooks are installed and whether rona manages them'
+complete -c rona -n "__fish_rona_using_subcommand hooks; and __fish_seen_subcommand_from help" -f -a "run" -d 'Run a hook\'s checks; called by the installed hook scripts'
+complete -c rona -n "__fish_rona_using_subcommand hooks; and __fish_seen_subcommand_from help" -f -a "help" -d 'Print this message or the help of the given subcommand(s)'
 complete -c rona -n "__fish_rona_using_subcommand init" -s f -l config-file -d 'Config file to use instead of the default global/project hierarchy' -r -F
+complete -c rona -n "__fish_rona_using_subcommand init" -s C -l chdir -d 'Run as if rona was started in PATH instead of the current directory (like `git -C`)' -r -f -a "(__fish_complete_directories)"
+complete -c rona -n "__fish_rona_using_subcommand init" -l lang -d 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`' -r
+complete -c rona -n "__fish_rona_using_subcommand init" -l template -d 'Commit template to use: the one named NAME in `[templates]`' -r
+complete -c rona -n "__fish_rona_using_subcommand init" -l scope -d 'Only consider the files under a directory: a name from `[scopes]` or a path' -r
+complete -c rona -n "__fish_rona_using_subcommand init" -l output -d 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`' -r -f -a "text\t'Human-readable text'
+json\t'A JSON object with a `schema_version`, for scripts and editor plugins'"
 complete -c rona -n "__fish_rona_using_subcommand init" -l dry-run -d 'Show what would be initialized without creating files'
-complete -c rona -n "__fish_rona_using_subcommand init" -s h -l help -d 'Print help'
+complete -c rona -n "__fish_rona_using_subcommand init" -l repo -d 'Prepare the current repository instead of creating a config file: create `commit_message.md` and `.commitignore`, with the patterns of the `preset` list (or of the ecosystems detected at the root when it is empty)'
+complete -c rona -n "__fish_rona_using_subcommand init" -l read-only -d 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)'
+complete -c rona -n "__fish_rona_using_subcommand init" -s h -l help -d 'Print help (see more with \'--help\')'
+complete -c rona -n "__fish_rona_using_subcommand list-status" -l filter -d 'Only list files whose path fuzzy-matches this query, best matches first' -r
 complete -c rona -n "__fish_rona_using_subcommand list-status" -s f -l config-file -d 'Config file to use instead of the default global/project hierarchy' -r -F
-complete -c rona -n "__fish_rona_using_subcommand list-status" -s h -l help -d 'Print help'
+complete -c rona -n "__fish_rona_using_subcommand list-status" -s C -l chdir -d 'Run as if rona was started in PATH instead of the current directory (like `git -C`)' -r -f -a "(__fish_complete_directories)"
+complete -c rona -n "__fish_rona_using_subcommand list-status" -l lang -d 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`' -r
+complete -c rona -n "__fish_rona_using_subcommand list-status" -l template -d 'Commit template to use: the one named NAME in `[templates]`' -r
+complete -c rona -n "__fish_rona_using_subcommand list-status" -l scope -d 'Only consider the files under a directory: a name from `[scopes]` or a path' -r
+complete -c rona -n "__fish_rona_using_subcommand list-status" -l output -d 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`' -r -f -a "text\t'Human-readable text'
+json\t'A JSON object with a `schema_version`, for scripts and editor plugins'"
+complete -c rona -n "__fish_rona_using_subcommand list-status" -l read-only -d 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)'
+complete -c rona -n "__fish_rona_using_subcommand list-status" -s h -l help -d 'Print help (see more with \'--help\')'
+complete -c rona -n "__fish_rona_using_subcommand prepare-msg" -s f -l config-file -d 'Config file to use instead of the default global/project hierarchy' -r -F
+complete -c rona -n "__fish_rona_using_subcommand prepare-msg" -s C -l chdir -d 'Run as if rona was started in PATH instead of the current directory (like `git -C`)' -r -f -a "(__fish_complete_directories)"
+complete -c rona -n "__fish_rona_using_subcommand prepare-msg" -l lang -d 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`' -r
+complete -c rona -n "__fish_rona_using_subcommand prepare-msg" -l template -d 'Commit template to use: the one named NAME in `[templates]`' -r
+complete -c rona -n "__fish_rona_using_subcommand prepare-msg" -l scope -d 'Only consider the files under a directory: a name from `[scopes]` or a path' -r
+complete -c rona -n "__fish_rona_using_subcommand prepare-msg" -l output -d 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`' -r -f -a "text\t'Human-readable text'
+json\t'A JSON object with a `schema_version`, for scripts and editor plugins'"
+complete -c rona -n "__fish_rona_using_subcommand prepare-msg" -l dry-run -d 'Show the message that would be written without changing the file'
+complete -c rona -n "__fish_rona_using_subcommand prepare-msg" -l read-only -d 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)'
+complete -c rona -n "__fish_rona_using_subcommand prepare-msg" -s h -l help -d 'Print help (see more with \'--help\')'
+complete -c rona -n "__fish_rona_using_subcommand prune" -l max-age -d 'Remove rona state files (under `.git/rona/`) older than this (units: s, m, h, d)' -r
+complete -c rona -n "__fish_rona_using_subcommand prune" -s f -l config-file -d 'Config file to use instead of the default global/project hierarchy' -r -F
+complete -c rona -n "__fish_rona_using_subcommand prune" -s C -l chdir -d 'Run as if rona was started in PATH instead of the current directory (like `git -C`)' -r -f -a "(__fish_complete_directories)"
+complete -c rona -n "__fish_rona_using_subcommand prune" -l lang -d 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`' -r
+complete -c rona -n "__fish_rona_using_subcommand prune" -l template -d 'Commit template to use: the one named NAME in `[templates]`' -r
+complete -c rona -n "__fish_rona_using_subcommand prune" -l scope -d 'Only consider the files under a directory: a name from `[scopes]` or a path' -r
+complete -c rona -n "__fish_rona_using_subcommand prune" -l output -d 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`' -r -f -a "text\t'Human-readable text'
+json\t'A JSON object with a `schema_version`, for scripts and editor plugins'"
+complete -c rona -n "__fish_rona_using_subcommand prune" -l dry-run -d 'Show what would be removed without removing anything'
+complete -c rona -n "__fish_rona_using_subcommand prune" -l read-only -d 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)'
+complete -c rona -n "__fish_rona_using_subcommand prune" -s h -l help -d 'Print help (see more with \'--help\')'
+complete -c rona -n "__fish_rona_using_subcommand push" -l target -d 'Branch the review is for, the `{target}` of the review ref (default: the default branch)' -r
 complete -c rona -n "__fish_rona_using_subcommand push" -s f -l config-file -d 'Config file to use instead of the default global/project hierarchy' -r -F
+complete -c rona -n "__fish_rona_using_subcommand push" -s C -l chdir -d 'Run as if rona was started in PATH instead of the current directory (like `git -C`)' -r -f -a "(__fish_complete_directories)"
+complete -c rona -n "__fish_rona_using_subcommand push" -l lang -d 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`' -r
+complete -c rona -n "__fish_rona_using_subcommand push" -l template -d 'Commit template to use: the one named NAME in `[templates]`' -r
+complete -c rona -n "__fish_rona_using_subcommand push" -l scope -d 'Only consider the files under a directory: a name from `[scopes]` or a path' -r
+complete -c rona -n "__fish_rona_using_subcommand push" -l output -d 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`' -r -f -a "text\t'Human-readable text'
+json\t'A JSON object with a `schema_version`, for scripts and editor plugins'"
 complete -c rona -n "__fish_rona_using_subcommand push" -l dry-run -d 'Show what would be pushed without actually pushing'
-complete -c rona -n "__fish_rona_using_subcommand push" -s h -l help -d 'Print help'
+complete -c rona -n "__fish_rona_using_subcommand push" -l review -d 'Push HEAD to the `[review] ref` (default `refs/for/<target>`) instead of the branch'
+complete -c rona -n "__fish_rona_using_subcommand push" -l read-only -d 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)'
+complete -c rona -n "__fish_rona_using_subcommand push" -s h -l help -d 'Print help (see more with \'--help\')'
+complete -c rona -n "__fish_rona_using_subcommand release" -l package -d 'Release a package of `[packages]`, with its own tags and changelog' -r
+complete -c rona -n "__fish_rona_using_subcommand release" -s f -l config-file -d 'Config file to use instead of the default global/project hierarchy' -r -F
+complete -c rona -n "__fish_rona_using_subcommand release" -s C -l chdir -d 'Run as if rona was started in PATH instead of the current directory (like `git -C`)' -r -f -a "(__fish_complete_directories)"
+complete -c rona -n "__fish_rona_using_subcommand release" -l lang -d 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`' -r
+complete -c rona -n "__fish_rona_using_subcommand release" -l template -d 'Commit template to use: the one named NAME in `[templates]`' -r
+complete -c rona -n "__fish_rona_using_subcommand release" -l scope -d 'Only consider the files under a directory: a name from `[scopes]` or a path' -r
+complete -c rona -n "__fish_rona_using_subcommand release" -l output -d 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`' -r -f -a "text\t'Human-readable text'
+json\t'A JSON object with a `schema_version`, for scripts and editor plugins'"
+complete -c rona -n "__fish_rona_using_subcommand release" -l major -d 'Bump the major version'
+complete -c rona -n "__fish_rona_using_subcommand release" -l minor -d 'Bump the minor version'
+complete -c rona -n "__fish_rona_using_subcommand release" -l patch -d 'Bump the patch version (the default)'
+complete -c rona -n "__fish_rona_using_subcommand release" -l alpha -d 'Release an alpha prerelease (e.g. `1.3.0-alpha.1`)'
+complete -c rona -n "__fish_rona_using_subcommand release" -l beta -d 'Release a beta prerelease (e.g. `1.3.0-beta.1`)'
+complete -c rona -n "__fish_rona_using_subcommand release" -l rc -d 'Release a release candidate (e.g. `1.3.0-rc.1`)'
+complete -c rona -n "__fish_rona_using_subcommand release" -l dry-run -d 'Show the version, the tag and the changelog section without changing anything'
+complete -c rona -n "__fish_rona_using_subcommand release" -l read-only -d 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)'
+complete -c rona -n "__fish_rona_using_subcommand release" -s h -l help -d 'Print help (see more with \'--help\')'
 complete -c rona -n "__fish_rona_using_subcommand reset" -s f -l config-file -d 'Config file to use instead of the default global/project hierarchy' -r -F
+complete -c rona -n "__fish_rona_using_subcommand reset" -s C -l chdir -d 'Run as if rona was started in PATH instead of the current directory (like `git -C`)' -r -f -a "(__fish_complete_directories)"
+complete -c rona -n "__fish_rona_using_subcommand reset" -l lang -d 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`' -r
+complete -c rona -n "__fish_rona_using_subcommand reset" -l template -d 'Commit template to use: the one named NAME in `[templates]`' -r
+complete -c rona -n "__fish_rona_using_subcommand reset" -l scope -d 'Only consider the files under a directory: a name from `[scopes]` or a path' -r
+complete -c rona -n "__fish_rona_using_subcommand reset" -l output -d 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`' -r -f -a "text\t'Human-readable text'
+json\t'A JSON object with a `schema_version`, for scripts and editor plugins'"
 complete -c rona -n "__fish_rona_using_subcommand reset" -s i -l interactive -d 'Interactively pick which staged files to unstage (`MultiSelect` of staged files)'
 complete -c rona -n "__fish_rona_using_subcommand reset" -l dry-run -d 'Show what would be unstaged without actually unstaging files'
-complete -c rona -n "__fish_rona_using_subcommand reset" -s h -l help -d 'Print help'
+complete -c rona -n "__fish_rona_using_subcommand reset" -l read-only -d 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)'
+complete -c rona -n "__fish_rona_using_subcommand reset" -s h -l help -d 'Print help (see more with \'--help\')'
 complete -c rona -n "__fish_rona_using_subcommand restore" -s f -l config-file -d 'Config file to use instead of the default global/project hierarchy' -r -F
+complete -c rona -n "__fish_rona_using_subcommand restore" -s C -l chdir -d 'Run as if rona was started in PATH instead of the current directory (like `git -C`)' -r -f -a "(__fish_complete_directories)"
+complete -c rona -n "__fish_rona_using_subcommand restore" -l lang -d 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`' -r
+complete -c rona -n "__fish_rona_using_subcommand restore" -l template -d 'Commit template to use: the one named NAME in `[templates]`' -r
+complete -c rona -n "__fish_rona_using_subcommand restore" -l scope -d 'Only consider the files under a directory: a name from `[scopes]` or a path' -r
+complete -c rona -n "__fish_rona_using_subcommand restore" -l output -d 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`' -r -f -a "text\t'Human-readable text'
+json\t'A JSON object with a `schema_version`, for scripts and editor plugins'"
 complete -c rona -n "__fish_rona_using_subcommand restore" -s i -l interactive -d 'Interactively pick which modified files to discard (`MultiSelect` of changed files)'
 complete -c rona -n "__fish_rona_using_subcommand restore" -s y -l yes -d 'Skip the confirmation prompt before discarding changes'
 complete -c rona -n "__fish_rona_using_subcommand restore" -l dry-run -d 'Show what would be restored without actually discarding changes'
-complete -c rona -n "__fish_rona_using_subcommand restore" -s h -l help -d 'Print help'
+complete -c rona -n "__fish_rona_using_subcommand restore" -l read-only -d 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)'
+complete -c rona -n "__fish_rona_using_subcommand restore" -s h -l help -d 'Print help (see more with \'--help\')'
 complete -c rona -n "__fish_rona_using_subcommand set-editor" -s f -l config-file -d 'Config file to use instead of the default global/project hierarchy' -r -F
+complete -c rona -n "__fish_rona_using_subcommand set-editor" -s C -l chdir -d 'Run as if rona was started in PATH instead of the current directory (like `git -C`)' -r -f -a "(__fish_complete_directories)"
+complete -c rona -n "__fish_rona_using_subcommand set-editor" -l lang -d 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`' -r
+complete -c rona -n "__fish_rona_using_subcommand set-editor" -l template -d 'Commit template to use: the one named NAME in `[templates]`' -r
+complete -c rona -n "__fish_rona_using_subcommand set-editor" -l scope -d 'Only consider the files under a directory: a name from `[scopes]` or a path' -r
+complete -c rona -n "__fish_rona_using_subcommand set-editor" -l output -d 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`' -r -f -a "text\t'Human-readable text'
+json\t'A JSON object with a `schema_version`, for scripts and editor plugins'"
 complete -c rona -n "__fish_rona_using_subcommand set-editor" -l dry-run -d 'Show what would be changed without modifying config'
-complete -c rona -n "__fish_rona_using_subcommand set-editor" -s h -l help -d 'Print help'
-complete -c rona -n "__fish_rona_using_subcommand sync" -s b -l branch -d 'Branch to sync from (default: main)' -r
+complete -c rona -n "__fish_rona_using_subcommand set-editor" -l read-only -d 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)'
+complete -c rona -n "__fish_rona_using_subcommand set-editor" -s h -l help -d 'Print help (see more with \'--help\')'
+complete -c rona -n "__fish_rona_using_subcommand schema" -s f -l config-file -d 'Config file to use instead of the default global/project hierarchy' -r -F
+complete -c rona -n "__fish_rona_using_subcommand schema" -s C -l chdir -d 'Run as if rona was started in PATH instead of the current directory (like `git -C`)' -r -f -a "(__fish_complete_directories)"
+complete -c rona -n "__fish_rona_using_subcommand schema" -l lang -d 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`' -r
+complete -c rona -n "__fish_rona_using_subcommand schema" -l template -d 'Commit template to use: the one named NAME in `[templates]`' -r
+complete -c rona -n "__fish_rona_using_subcommand schema" -l scope -d 'Only consider the files under a directory: a name from `[scopes]` or a path' -r
+complete -c rona -n "__fish_rona_using_subcommand schema" -l output -d 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`' -r -f -a "text\t'Human-readable text'
+json\t'A JSON object with a `schema_version`, for scripts and editor plugins'"
+complete -c rona -n "__fish_rona_using_subcommand schema" -l read-only -d 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)'
+complete -c rona -n "__fish_rona_using_subcommand schema" -s h -l help -d 'Print help (see more with \'--help\')'
+complete -c rona -n "__fish_rona_using_subcommand show" -s f -l config-file -d 'Config file to use instead of the default global/project hierarchy' -r -F
+complete -c rona -n "__fish_rona_using_subcommand show" -s C -l chdir -d 'Run as if rona was started in PATH instead of the current directory (like `git -C`)' -r -f -a "(__fish_complete_directories)"
+complete -c rona -n "__fish_rona_using_subcommand show" -l lang -d 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`' -r
+complete -c rona -n "__fish_rona_using_subcommand show" -l template -d 'Commit template to use: the one named NAME in `[templates]`' -r
+complete -c rona -n "__fish_rona_using_subcommand show" -l scope -d 'Only consider the files under a directory: a name from `[scopes]` or a path' -r
+complete -c rona -n "__fish_rona_using_subcommand show" -l output -d 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`' -r -f -a "text\t'Human-readable text'
+json\t'A JSON object with a `schema_version`, for scripts and editor plugins'"
+complete -c rona -n "__fish_rona_using_subcommand show" -l json -d 'Print the commit as JSON'
+complete -c rona -n "__fish_rona_using_subcommand show" -l read-only -d 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)'
+complete -c rona -n "__fish_rona_using_subcommand show" -s h -l help -d 'Print help (see more with \'--help\')'
+complete -c rona -n "__fish_rona_using_subcommand snippet; and not __fish_seen_subcommand_from list insert help" -s f -l config-file -d 'Config file to use instead of the default global/project hierarchy' -r -F
+complete -c rona -n "__fish_rona_using_subcommand snippet; and not __fish_seen_subcommand_from list insert help" -s C -l chdir -d 'Run as if rona was started in PATH instead of the current directory (like `git -C`)' -r -f -a "(__fish_complete_directories)"
+complete -c rona -n "__fish_rona_using_subcommand snippet; and not __fish_seen_subcommand_from list insert help" -l lang -d 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`' -r
+complete -c rona -n "__fish_rona_using_subcommand snippet; and not __fish_seen_subcommand_from list insert help" -l template -d 'Commit template to use: the one named NAME in `[templates]`' -r
+complete -c rona -n "__fish_rona_using_subcommand snippet; and not __fish_seen_subcommand_from list insert help" -l scope -d 'Only consider the files under a directory: a name from `[scopes]` or a path' -r
+complete -c rona -n "__fish_rona_using_subcommand snippet; and not __fish_seen_subcommand_from list insert help" -l output -d 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`' -r -f -a "text\t'Human-readable text'
+json\t'A JSON object with a `schema_version`, for scripts and editor plugins'"
+complete -c rona -n "__fish_rona_using_subcommand snippet; and not __fish_seen_subcommand_from list insert help" -l read-only -d 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)'
+complete -c rona -n "__fish_rona_using_subcommand snippet; and not __fish_seen_subcommand_from list insert help" -s h -l help -d 'Print help (see more with \'--help\')'
+complete -c rona -n "__fish_rona_using_subcommand snippet; and not __fish_seen_subcommand_from list insert help" -f -a "list" -d 'List the configured snippets'
+complete -c rona -n "__fish_rona_using_subcommand snippet; and not __fish_seen_subcommand_from list insert help" -f -a "insert" -d 'Append a rendered snippet to the commit message file'
+complete -c rona -n "__fish_rona_using_subcommand snippet; and not __fish_seen_subcommand_from list insert help" -f -a "help" -d 'Print this message or the help of the given subcommand(s)'
+complete -c rona -n "__fish_rona_using_subcommand snippet; and __fish_seen_subcommand_from list" -s f -l config-file -d 'Config file to use instead of the default global/project hierarchy' -r -F
+complete -c rona -n "__fish_rona_using_subcommand snippet; and __fish_seen_subcommand_from list" -s C -l chdir -d 'Run as if rona was started in PATH instead of the current directory (like `git -C`)' -r -f -a "(__fish_complete_directories)"
+complete -c rona -n "__fish_rona_using_subcommand snippet; and __fish_seen_subcommand_from list" -l lang -d 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`' -r
+complete -c rona -n "__fish_rona_using_subcommand snippet; and __fish_seen_subcommand_from list" -l template -d 'Commit template to use: the one named NAME in `[templates]`' -r
+complete -c rona -n "__fish_rona_using_subcommand snippet; and __fish_seen_subcommand_from list" -l scope -d 'Only consider the files under a directory: a name from `[scopes]` or a path' -r
+complete -c rona -n "__fish_rona_using_subcommand snippet; and __fish_seen_subcommand_from list" -l output -d 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`' -r -f -a "text\t'Human-readable text'
+json\t'A JSON object with a `schema_version`, for scripts and editor plugins'"
+complete -c rona -n "__fish_rona_using_subcommand snippet; and __fish_seen_subcommand_from list" -l read-only -d 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)'
+complete -c rona -n "__fish_rona_using_subcommand snippet; and __fish_seen_subcommand_from list" -s h -l help -d 'Print help (see more with \'--help\')'
+complete -c rona -n "__fish_rona_using_subcommand snippet; and __fish_seen_subcommand_from insert" -l file -d 'Message file to insert into (defaults to `commit_message.md`)' -r -F
+complete -c rona -n "__fish_rona_using_subcommand snippet; and __fish_seen_subcommand_from insert" -s f -l config-file -d 'Config file to use instead of the default global/project hierarchy' -r -F
+complete -c rona -n "__fish_rona_using_subcommand snippet; and __fish_seen_subcommand_from insert" -s C -l chdir -d 'Run as if rona was started in PATH instead of the current directory (like `git -C`)' -r -f -a "(__fish_complete_directories)"
+complete -c rona -n "__fish_rona_using_subcommand snippet; and __fish_seen_subcommand_from insert" -l lang -d 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`' -r
+complete -c rona -n "__fish_rona_using_subcommand snippet; and __fish_seen_subcommand_from insert" -l template -d 'Commit template to use: the one named NAME in `[templates]`' -r
+complete -c rona -n "__fish_rona_using_subcommand snippet; and __fish_seen_subcommand_from insert" -l scope -d 'Only consider the files under a directory: a name from `[scopes]` or a path' -r
+complete -c rona -n "__fish_rona_using_subcommand snippet; and __fish_seen_subcommand_from insert" -l output -d 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`' -r -f -a "text\t'Human-readable text'
+json\t'A JSON object with a `schema_version`, for scripts and editor plugins'"
+complete -c rona -n "__fish_rona_using_subcommand snippet; and __fish_seen_subcommand_from insert" -l dry-run -d 'Print the rendered snippet without changing the file'
+complete -c rona -n "__fish_rona_using_subcommand snippet; and __fish_seen_subcommand_from insert" -l read-only -d 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)'
+complete -c rona -n "__fish_rona_using_subcommand snippet; and __fish_seen_subcommand_from insert" -s h -l help -d 'Print help (see more with \'--help\')'
+complete -c rona -n "__fish_rona_using_subcommand snippet; and __fish_seen_subcommand_from help" -f -a "list" -d 'List the configured snippets'
+complete -c rona -n "__fish_rona_using_subcommand snippet; and __fish_seen_subcommand_from help" -f -a "insert" -d 'Append a rendered snippet to the commit message file'
+complete -c rona -n "__fish_rona_using_subcommand snippet; and __fish_seen_subcommand_from help" -f -a "help" -d 'Print this message or the help of the given subcommand(s)'
+complete -c rona -n "__fish_rona_using_subcommand stats" -l export -d 'Print the metrics in this format instead of a table' -r -f -a "prometheus\t'Prometheus text exposition format, e.g. for the node exporter\'s textfile collector'
+csv\t'One row per commit with a header row'"
+complete -c rona -n "__fish_rona_using_subcommand stats" -s f -l config-file -d 'Config file to use instead of the default global/project hierarchy' -r -F
+complete -c rona -n "__fish_rona_using_subcommand stats" -s C -l chdir -d 'Run as if rona was started in PATH instead of the current directory (like `git -C`)' -r -f -a "(__fish_complete_directories)"
+complete -c rona -n "__fish_rona_using_subcommand stats" -l lang -d 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`' -r
+complete -c rona -n "__fish_rona_using_subcommand stats" -l template -d 'Commit template to use: the one named NAME in `[templates]`' -r
+complete -c rona -n "__fish_rona_using_subcommand stats" -l scope -d 'Only consider the files under a directory: a name from `[scopes]` or a path' -r
+complete -c rona -n "__fish_rona_using_subcommand stats" -l output -d 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`' -r -f -a "text\t'Human-readable text'
+json\t'A JSON object with a `schema_version`, for scripts and editor plugins'"
+complete -c rona -n "__fish_rona_using_subcommand stats" -l read-only -d 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)'
+complete -c rona -n "__fish_rona_using_subcommand stats" -s h -l help -d 'Print help (see more with \'--help\')'
+complete -c rona -n "__fish_rona_using_subcommand status" -s f -l config-file -d 'Config file to use instead of the default global/project hierarchy' -r -F
+complete -c rona -n "__fish_rona_using_subcommand status" -s C -l chdir -d 'Run as if rona was started in PATH instead of the current directory (like `git -C`)' -r -f -a "(__fish_complete_directories)"
+complete -c rona -n "__fish_rona_using_subcommand status" -l lang -d 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`' -r
+complete -c rona -n "__fish_rona_using_subcommand status" -l template -d 'Commit template to use: the one named NAME in `[templates]`' -r
+complete -c rona -n "__fish_rona_using_subcommand status" -l scope -d 'Only consider the files under a directory: a name from `[scopes]` or a path' -r
+complete -c rona -n "__fish_rona_using_subcommand status" -l output -d 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`' -r -f -a "text\t'Human-readable text'
+json\t'A JSON object with a `schema_version`, for scripts and editor plugins'"
+complete -c rona -n "__fish_rona_using_subcommand status" -l read-only -d 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)'
+complete -c rona -n "__fish_rona_using_subcommand status" -s h -l help -d 'Print help (see more with \'--help\')'
+complete -c rona -n "__fish_rona_using_subcommand sync" -s b -l branch -d 'Branch to sync from (default: the default branch, from `origin/HEAD`)' -r
 complete -c rona -n "__fish_rona_using_subcommand sync" -s n -l new-branch -d 'Create a new branch before syncing' -r
 complete -c rona -n "__fish_rona_using_subcommand sync" -s f -l config-file -d 'Config file to use instead of the default global/project hierarchy' -r -F
+complete -c rona -n "__fish_rona_using_subcommand sync" -s C -l chdir -d 'Run as if rona was started in PATH instead of the current directory (like `git -C`)' -r -f -a "(__fish_complete_directories)"
+complete -c rona -n "__fish_rona_using_subcommand sync" -l lang -d 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`' -r
+complete -c rona -n "__fish_rona_using_subcommand sync" -l template -d 'Commit template to use: the one named NAME in `[templates]`' -r
+complete -c rona -n "__fish_rona_using_subcommand sync" -l scope -d 'Only consider the files under a directory: a name from `[scopes]` or a path' -r
+complete -c rona -n "__fish_rona_using_subcommand sync" -l output -d 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`' -r -f -a "text\t'Human-readable text'
+json\t'A JSON object with a `schema_version`, for scripts and editor plugins'"
 complete -c rona -n "__fish_rona_using_subcommand sync" -s r -l rebase -d 'Use rebase instead of merge'
 complete -c rona -n "__fish_rona_using_subcommand sync" -l dry-run -d 'Show what would be done without actually doing it'
-complete -c rona -n "__fish_rona_using_subcommand sync" -s h -l help -d 'Print help'
-complete -c rona -n "__fish_rona_using_subcommand help; and not __fish_seen_subcommand_from branch add-with-exclude commit completion config generate init list-status push reset restore set-editor sync help" -f -a "branch" -d 'Create a new branch interactively using a branch name template'
-complete -c rona -n "__fish_rona_using_subcommand help; and not __fish_seen_subcommand_from branch add-with-exclude commit completion config generate init list-status push reset restore set-editor sync help" -f -a "add-with-exclude" -d 'Add all files to the `git add` command and exclude the patterns passed as positional arguments'
-complete -c rona -n "__fish_rona_using_subcommand help; and not __fish_seen_subcommand_from branch add-with-exclude commit completion config generate init list-status push reset restore set-editor sync help" -f -a "commit" -d 'Directly commit the file with the text in `commit_message.md`'
-complete -c rona -n "__fish_rona_using_subcommand help; and not __fish_seen_subcommand_from branch add-with-exclude commit completion config generate init list-status push reset restore set-editor sync help" -f -a "completion" -d 'Generate shell completions for your shell'
-complete -c rona -n "__fish_rona_using_subcommand help; and not __fish_seen_subcommand_from branch add-with-exclude commit completion config generate init list-status push reset restore set-editor sync help" -f -a "config" -d 'Manage configuration files (create or inspect)'
-complete -c rona -n "__fish_rona_using_subcommand help; and not __fish_seen_subcommand_from branch add-with-exclude commit completion config generate init list-status push reset restore set-editor sync help" -f -a "generate" -d 'Directly generate the `commit_message.md` file'
-complete -c rona -n "__fish_rona_using_subcommand help; and not __fish_seen_subcommand_from branch add-with-exclude commit completion config generate init list-status push reset restore set-editor sync help" -f -a "init" -d 'Initialize the rona configuration file'
-complete -c rona -n "__fish_rona_using_subcommand help; and not __fish_seen_subcommand_from branch add-with-exclude commit completion config generate init list-status push reset restore set-editor sync help" -f -a "list-status" -d 'List files from git status (for shell completion on the -a)'
-complete -c rona -n "__fish_rona_using_subcommand help; and not __fish_seen_subcommand_from branch add-with-exclude commit completion config generate init list-status push reset restore set-editor sync help" -f -a "push" -d 'Push to a git repository'
-complete -c rona -n "__fish_rona_using_subcommand help; and not __fish_seen_subcommand_from branch add-with-exclude commit completion config generate init list-status push reset restore set-editor sync help" -f -a "reset" -d 'Unstage files, moving them out of the staging area without losing changes'
-complete -c rona -n "__fish_rona_using_subcommand help; and not __fish_seen_subcommand_from branch add-with-exclude commit completion config generate init list-status push reset restore set-editor sync help" -f -a "restore" -d 'Discard working-tree changes, restoring files to their staged or committed state'
-complete -c rona -n "__fish_rona_using_subcommand help; and not __fish_seen_subcommand_from branch add-with-exclude commit completion config generate init list-status push reset restore set-editor sync help" -f -a "set-editor" -d 'Set the editor to use for editing the commit message'
-complete -c rona -n "__fish_rona_using_subcommand help; and not __fish_seen_subcommand_from branch add-with-exclude commit completion config generate init list-status push reset restore set-editor sync help" -f -a "sync" -d 'Sync current branch with main (or another branch) by pulling and merging/rebasing'
-complete -c rona -n "__fish_rona_using_subcommand help; and not __fish_seen_subcommand_from branch add-with-exclude commit completion config generate init list-status push reset restore set-editor sync help" -f -a "help" -d 'Print this message or the help of the given subcommand(s)'
+complete -c rona -n "__fish_rona_using_subcommand sync" -l read-only -d 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)'
+complete -c rona -n "__fish_rona_using_subcommand sync" -s h -l help -d 'Print help (see more with \'--help\')'
+complete -c rona -n "__fish_rona_using_subcommand template; and not __fish_seen_subcommand_from check help" -s f -l config-file -d 'Config file to use instead of the default global/project hierarchy' -r -F
+complete -c rona -n "__fish_rona_using_subcommand template; and not __fish_seen_subcommand_from check help" -s C -l chdir -d 'Run as if rona was started in PATH instead of the current directory (like `git -C`)' -r -f -a "(__fish_complete_directories)"
+complete -c rona -n "__fish_rona_using_subcommand template; and not __fish_seen_subcommand_from check help" -l lang -d 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`' -r
+complete -c rona -n "__fish_rona_using_subcommand template; and not __fish_seen_subcommand_from check help" -l template -d 'Commit template to use: the one named NAME in `[templates]`' -r
+complete -c rona -n "__fish_rona_using_subcommand template; and not __fish_seen_subcommand_from check help" -l scope -d 'Only consider the files under a directory: a name from `[scopes]` or a path' -r
+complete -c rona -n "__fish_rona_using_subcommand template; and not __fish_seen_subcommand_from check help" -l output -d 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`' -r -f -a "text\t'Human-readable text'
+json\t'A JSON object with a `schema_version`, for scripts and editor plugins'"
+complete -c rona -n "__fish_rona_using_subcommand template; and not __fish_seen_subcommand_from check help" -l read-only -d 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)'
+complete -c rona -n "__fish_rona_using_subcommand template; and not __fish_seen_subcommand_from check help" -s h -l help -d 'Print help (see more with \'--help\')'
+complete -c rona -n "__fish_rona_using_subcommand template; and not __fish_seen_subcommand_from check help" -f -a "check" -d 'Lint the configured templates for unknown variables and malformed blocks'
+complete -c rona -n "__fish_rona_using_subcommand template; and not __fish_seen_subcommand_from check help" -f -a "help" -d 'Print this message or the help of the given subcommand(s)'
+complete -c rona -n "__fish_rona_using_subcommand template; and __fish_seen_subcommand_from check" -s f -l config-file -d 'Config file to use instead of the default global/project hierarchy' -r -F
+complete -c rona -n "__fish_rona_using_subcommand template; and __fish_seen_subcommand_from check" -s C -l chdir -d 'Run as if rona was started in PATH instead of the current directory (like `git -C`)' -r -f -a "(__fish_complete_directories)"
+complete -c rona -n "__fish_rona_using_subcommand template; and __fish_seen_subcommand_from check" -l lang -d 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`' -r
+complete -c rona -n "__fish_rona_using_subcommand template; and __fish_seen_subcommand_from check" -l template -d 'Commit template to use: the one named NAME in `[templates]`' -r
+complete -c rona -n "__fish_rona_using_subcommand template; and __fish_seen_subcommand_from check" -l scope -d 'Only consider the files under a directory: a name from `[scopes]` or a path' -r
+complete -c rona -n "__fish_rona_using_subcommand template; and __fish_seen_subcommand_from check" -l output -d 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`' -r -f -a "text\t'Human-readable text'
+json\t'A JSON object with a `schema_version`, for scripts and editor plugins'"
+complete -c rona -n "__fish_rona_using_subcommand template; and __fish_seen_subcommand_from check" -l read-only -d 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)'
+complete -c rona -n "__fish_rona_using_subcommand template; and __fish_seen_subcommand_from check" -s h -l help -d 'Print help (see more with \'--help\')'
+complete -c rona -n "__fish_rona_using_subcommand template; and __fish_seen_subcommand_from help" -f -a "check" -d 'Lint the configured templates for unknown variables and malformed blocks'
+complete -c rona -n "__fish_rona_using_subcommand template; and __fish_seen_subcommand_from help" -f -a "help" -d 'Print this message or the help of the given subcommand(s)'
+complete -c rona -n "__fish_rona_using_subcommand todo" -s f -l config-file -d 'Config file to use instead of the default global/project hierarchy' -r -F
+complete -c rona -n "__fish_rona_using_subcommand todo" -s C -l chdir -d 'Run as if rona was started in PATH instead of the current directory (like `git -C`)' -r -f -a "(__fish_complete_directories)"
+complete -c rona -n "__fish_rona_using_subcommand todo" -l lang -d 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`' -r
+complete -c rona -n "__fish_rona_using_subcommand todo" -l template -d 'Commit template to use: the one named NAME in `[templates]`' -r
+complete -c rona -n "__fish_rona_using_subcommand todo" -l scope -d 'Only consider the files under a directory: a name from `[scopes]` or a path' -r
+complete -c rona -n "__fish_rona_using_subcommand todo" -l output -d 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`' -r -f -a "text\t'Human-readable text'
+json\t'A JSON object with a `schema_version`, for scripts and editor plugins'"
+complete -c rona -n "__fish_rona_using_subcommand todo" -l check -d 'Exit with an error when markers are found (for hooks and CI)'
+complete -c rona -n "__fish_rona_using_subcommand todo" -l read-only -d 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)'
+complete -c rona -n "__fish_rona_using_subcommand todo" -s h -l help -d 'Print help (see more with \'--help\')'
+complete -c rona -n "__fish_rona_using_subcommand tour" -s f -l config-file -d 'Config file to use instead of the default global/project hierarchy' -r -F
+complete -c rona -n "__fish_rona_using_subcommand tour" -s C -l chdir -d 'Run as if rona was started in PATH instead of the current directory (like `git -C`)' -r -f -a "(__fish_complete_directories)"
+complete -c rona -n "__fish_rona_using_subcommand tour" -l lang -d 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`' -r
+complete -c rona -n "__fish_rona_using_subcommand tour" -l template -d 'Commit template to use: the one named NAME in `[templates]`' -r
+complete -c rona -n "__fish_rona_using_subcommand tour" -l scope -d 'Only consider the files under a directory: a name from `[scopes]` or a path' -r
+complete -c rona -n "__fish_rona_using_subcommand tour" -l output -d 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`' -r -f -a "text\t'Human-readable text'
+json\t'A JSON object with a `schema_version`, for scripts and editor plugins'"
+complete -c rona -n "__fish_rona_using_subcommand tour" -s y -l yes -d 'Run every step without pausing'
+complete -c rona -n "__fish_rona_using_subcommand tour" -l keep -d 'Keep the demo repository after the tour'
+complete -c rona -n "__fish_rona_using_subcommand tour" -l read-only -d 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)'
+complete -c rona -n "__fish_rona_using_subcommand tour" -s h -l help -d 'Print help (see more with \'--help\')'
+complete -c rona -n "__fish_rona_using_subcommand usage" -s f -l config-file -d 'Config file to use instead of the default global/project hierarchy' -r -F
+complete -c rona -n "__fish_rona_using_subcommand usage" -s C -l chdir -d 'Run as if rona was started in PATH instead of the current directory (like `git -C`)' -r -f -a "(__fish_complete_directories)"
+complete -c rona -n "__fish_rona_using_subcommand usage" -l lang -d 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`' -r
+complete -c rona -n "__fish_rona_using_subcommand usage" -l template -d 'Commit template to use: the one named NAME in `[templates]`' -r
+complete -c rona -n "__fish_rona_using_subcommand usage" -l scope -d 'Only consider the files under a directory: a name from `[scopes]` or a path' -r
+complete -c rona -n "__fish_rona_using_subcommand usage" -l output -d 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`' -r -f -a "text\t'Human-readable text'
+json\t'A JSON object with a `schema_version`, for scripts and editor plugins'"
+complete -c rona -n "__fish_rona_using_subcommand usage" -l json -d 'Print the report as JSON'
+complete -c rona -n "__fish_rona_using_subcommand usage" -l read-only -d 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)'
+complete -c rona -n "__fish_rona_using_subcommand usage" -s h -l help -d 'Print help (see more with \'--help\')'
+complete -c rona -n "__fish_rona_using_subcommand help; and not __fish_seen_subcommand_from am audit backport branch browse bundle add-with-exclude commit completion config copy daemon check-msg env explain-status format-patch fragment generate handoff health history hooks init list-status prepare-msg prune push release reset restore set-editor schema show snippet stats status sync template todo tour usage help" -f -a "am" -d 'Apply mailbox patches, with a three-way fallback for patches that do not apply'
+complete -c rona -n "__fish_rona_using_subcommand help; and not __fish_seen_subcommand_from am audit backport branch browse bundle add-with-exclude commit completion config copy daemon check-msg env explain-status format-patch fragment generate handoff health history hooks init list-status prepare-msg prune push release reset restore set-editor schema show snippet stats status sync template todo tour usage help" -f -a "audit" -d 'Report which existing commits follow the commit template and lint rules'
+complete -c rona -n "__fish_rona_using_subcommand help; and not __fish_seen_subcommand_from am audit backport branch browse bundle add-with-exclude commit completion config copy daemon check-msg env explain-status format-patch fragment generate handoff health history hooks init list-status prepare-msg prune push release reset restore set-editor schema show snippet stats status sync template todo tour usage help" -f -a "backport" -d 'Cherry-pick a commit onto release branches with a re-templated message'
+complete -c rona -n "__fish_rona_using_subcommand help; and not __fish_seen_subcommand_from am audit backport branch browse bundle add-with-exclude commit completion config copy daemon check-msg env explain-status format-patch fragment generate handoff health history hooks init list-status prepare-msg prune push release reset restore set-editor schema show snippet stats status sync template todo tour usage help" -f -a "branch" -d 'Create a new branch interactively using a branch name template'
+complete -c rona -n "__fish_rona_using_subcommand help; and not __fish_seen_subcommand_from am audit backport branch browse bundle add-with-exclude commit completion config copy daemon check-msg env explain-status format-patch fragment generate handoff health history hooks init list-status prepare-msg prune push release reset restore set-editor schema show snippet stats status sync template todo tour usage help" -f -a "browse" -d 'Browse recent commits: filter by typing, then view, copy, fix up or revert one'
+complete -c rona -n "__fish_rona_using_subcommand help; and not __fish_seen_subcommand_from am audit backport branch browse bundle add-with-exclude commit completion config copy daemon check-msg env explain-status format-patch fragment generate handoff health history hooks init list-status prepare-msg prune push release reset restore set-editor schema show snippet stats status sync template todo tour usage help" -f -a "bundle" -d 'Carry branches to a clone without a shared remote in `git bundle` files'
+complete -c rona -n "__fish_rona_using_subcommand help; and not __fish_seen_subcommand_from am audit backport branch browse bundle add-with-exclude commit completion config copy daemon check-msg env explain-status format-patch fragment generate handoff health history hooks init list-status prepare-msg prune push release reset restore set-editor schema show snippet stats status sync template todo tour usage help" -f -a "add-with-exclude" -d 'Add all files to the `git add` command and exclude the patterns passed as positional arguments'
+complete -c rona -n "__fish_rona_using_subcommand help; and not __fish_seen_subcommand_from am audit backport branch browse bundle add-with-exclude commit completion config copy daemon check-msg env explain-status format-patch fragment generate handoff health history hooks init list-status prepare-msg prune push release reset restore set-editor schema show snippet stats status sync template todo tour usage help" -f -a "commit" -d 'Directly commit the file with the text in `commit_message.md`'
+complete -c rona -n "__fish_rona_using_subcommand help; and not __fish_seen_subcommand_from am audit backport branch browse bundle add-with-exclude commit completion config copy daemon check-msg env explain-status format-patch fragment generate handoff health history hooks init list-status prepare-msg prune push release reset restore set-editor schema show snippet stats status sync template todo tour usage help" -f -a "completion" -d 'Generate shell completions for your shell'
+complete -c rona -n "__fish_rona_using_subcommand help; and not __fish_seen_subcommand_from am audit backport branch browse bundle add-with-exclude commit completion config copy daemon check-msg env explain-status format-patch fragment generate handoff health history hooks init list-status prepare-msg prune push release reset restore set-editor schema show snippet stats status sync template todo tour usage help" -f -a "config" -d 'Manage configuration files (create or inspect)'
+complete -c rona -n "__fish_rona_using_subcommand help; and not __fish_seen_subcommand_from am audit backport branch browse bundle add-with-exclude commit completion config copy daemon check-msg env explain-status format-patch fragment generate handoff health history hooks init list-status prepare-msg prune push release reset restore set-editor schema show snippet stats status sync template todo tour usage help" -f -a "copy" -d 'Copy a reference to a commit to the clipboard, e.g. `abc1234 ("fix: handle empty index")`, for pull request descriptions and review comments'
+complete -c rona -n "__fish_rona_using_subcommand help; and not __fish_seen_subcommand_from am audit backport branch browse bundle add-with-exclude commit completion config copy daemon check-msg env explain-status format-patch fragment generate handoff health history hooks init list-status prepare-msg prune push release reset restore set-editor schema show snippet stats status sync template todo tour usage help" -f -a "daemon" -d 'Keep the changed files warm for `rona -l`, served over a unix socket'
+complete -c rona -n "__fish_rona_using_subcommand help; and not __fish_seen_subcommand_from am audit backport branch browse bundle add-with-exclude commit completion config copy daemon check-msg env explain-status format-patch fragment generate handoff health history hooks init list-status prepare-msg prune push release reset restore set-editor schema show snippet stats status sync template todo tour usage help" -f -a "check-msg" -d 'Check a commit message file against the commit template'
+complete -c rona -n "__fish_rona_using_subcommand help; and not __fish_seen_subcommand_from am audit backport branch browse bundle add-with-exclude commit completion config copy daemon check-msg env explain-status format-patch fragment generate handoff health history hooks init list-status prepare-msg prune push release reset restore set-editor schema show snippet stats status sync template todo tour usage help" -f -a "env" -d 'Show what rona resolved here: repository, config files, template, editor, signing, guards and scope. Paste it in bug reports'
+complete -c rona -n "__fish_rona_using_subcommand help; and not __fish_seen_subcommand_from am audit backport branch browse bundle add-with-exclude commit completion config copy daemon check-msg env explain-status format-patch fragment generate handoff health history hooks init list-status prepare-msg prune push release reset restore set-editor schema show snippet stats status sync template todo tour usage help" -f -a "explain-status" -d 'Show git status with plain-language explanations of each state and the rona commands that act on it'
+complete -c rona -n "__fish_rona_using_subcommand help; and not __fish_seen_subcommand_from am audit backport branch browse bundle add-with-exclude commit completion config copy daemon check-msg env explain-status format-patch fragment generate handoff health history hooks init list-status prepare-msg prune push release reset restore set-editor schema show snippet stats status sync template todo tour usage help" -f -a "format-patch" -d 'Export commits as mbox patch files for mailing-list review'
+complete -c rona -n "__fish_rona_using_subcommand help; and not __fish_seen_subcommand_from am audit backport branch browse bundle add-with-exclude commit completion config copy daemon check-msg env explain-status format-patch fragment generate handoff health history hooks init list-status prepare-msg prune push release reset restore set-editor schema show snippet stats status sync template todo tour usage help" -f -a "fragment" -d 'Write changelog fragments and assemble them into the changelog at release time'
+complete -c rona -n "__fish_rona_using_subcommand help; and not __fish_seen_subcommand_from am audit backport branch browse bundle add-with-exclude commit completion config copy daemon check-msg env explain-status format-patch fragment generate handoff health history hooks init list-status prepare-msg prune push release reset restore set-editor schema show snippet stats status sync template todo tour usage help" -f -a "generate" -d 'Directly generate the `commit_message.md` file'
+complete -c rona -n "__fish_rona_using_subcommand help; and not __fish_seen_subcommand_from am audit backport branch browse bundle add-with-exclude commit completion config copy daemon check-msg env explain-status format-patch fragment generate handoff health history hooks init list-status prepare-msg prune push release reset restore set-editor schema show snippet stats status sync template todo tour usage help" -f -a "handoff" -d 'Continue a half-finished commit in another clone: push the staged changes and `commit_message.md` of a branch to a ref, then pull them on the other machine'
+complete -c rona -n "__fish_rona_using_subcommand help; and not __fish_seen_subcommand_from am audit backport branch browse bundle add-with-exclude commit completion config copy daemon check-msg env explain-status format-patch fragment generate handoff health history hooks init list-status prepare-msg prune push release reset restore set-editor schema show snippet stats status sync template todo tour usage help" -f -a "health" -d 'Check the repository for signs of missing maintenance (loose objects, missing commit-graph, large files in history, broken refs)'
+complete -c rona -n "__fish_rona_using_subcommand help; and not __fish_seen_subcommand_from am audit backport branch browse bundle add-with-exclude commit completion config copy daemon check-msg env explain-status format-patch fragment generate handoff health history hooks init list-status prepare-msg prune push release reset restore set-editor schema show snippet stats status sync template todo tour usage help" -f -a "history" -d 'Review what rona did in this repository (recorded in `.git/rona/oplog.jsonl`)'
+complete -c rona -n "__fish_rona_using_subcommand help; and not __fish_seen_subcommand_from am audit backport branch browse bundle add-with-exclude commit completion config copy daemon check-msg env explain-status format-patch fragment generate handoff health history hooks init list-status prepare-msg prune push release reset restore set-editor schema show snippet stats status sync template todo tour usage help" -f -a "hooks" -d 'Install, list or remove git hooks that run rona\'s checks on plain `git commit` and `git push`'
+complete -c rona -n "__fish_rona_using_subcommand help; and not __fish_seen_subcommand_from am audit backport branch browse bundle add-with-exclude commit completion config copy daemon check-msg env explain-status format-patch fragment generate handoff health history hooks init list-status prepare-msg prune push release reset restore set-editor schema show snippet stats status sync template todo tour usage help" -f -a "init" -d 'Initialize the rona configuration file'
+complete -c rona -n "__fish_rona_using_subcommand help; and not __fish_seen_subcommand_from am audit backport branch browse bundle add-with-exclude commit completion config copy daemon check-msg env explain-status format-patch fragment generate handoff health history hooks init list-status prepare-msg prune push release reset restore set-editor schema show snippet stats status sync template todo tour usage help" -f -a "list-status" -d 'List files from git status (for shell completion on the -a)'
+complete -c rona -n "__fish_rona_using_subcommand help; and not __fish_seen_subcommand_from am audit backport branch browse bundle add-with-exclude commit completion config copy daemon check-msg env explain-status format-patch fragment generate handoff health history hooks init list-status prepare-msg prune push release reset restore set-editor schema show snippet stats status sync template todo tour usage help" -f -a "prepare-msg" -d 'Prefill a commit message file from the project template'
+complete -c rona -n "__fish_rona_using_subcommand help; and not __fish_seen_subcommand_from am audit backport branch browse bundle add-with-exclude commit completion config copy daemon check-msg env explain-status format-patch fragment generate handoff health history hooks init list-status prepare-msg prune push release reset restore set-editor schema show snippet stats status sync template todo tour usage help" -f -a "prune" -d 'Clean up the repository: prune deleted remote branches, expire reflogs, remove unreachable objects and stale rona state files'
+complete -c rona -n "__fish_rona_using_subcommand help; and not __fish_seen_subcommand_from am audit backport branch browse bundle add-with-exclude commit completion config copy daemon check-msg env explain-status format-patch fragment generate handoff health history hooks init list-status prepare-msg prune push release reset restore set-editor schema show snippet stats status sync template todo tour usage help" -f -a "push" -d 'Push to a git repository'
+complete -c rona -n "__fish_rona_using_subcommand help; and not __fish_seen_subcommand_from am audit backport branch browse bundle add-with-exclude commit completion config copy daemon check-msg env explain-status format-patch fragment generate handoff health history hooks init list-status prepare-msg prune push release reset restore set-editor schema show snippet stats status sync template todo tour usage help" -f -a "release" -d 'Tag the next version, adding its changelog section from the fragments'
+complete -c rona -n "__fish_rona_using_subcommand help; and not __fish_seen_subcommand_from am audit backport branch browse bundle add-with-exclude commit completion config copy daemon check-msg env explain-status format-patch fragment generate handoff health history hooks init list-status prepare-msg prune push release reset restore set-editor schema show snippet stats status sync template todo tour usage help" -f -a "reset" -d 'Unstage files, moving them out of the staging area without losing changes'
+complete -c rona -n "__fish_rona_using_subcommand help; and not __fish_seen_subcommand_from am audit backport branch browse bundle add-with-exclude commit completion config copy daemon check-msg env explain-status format-patch fragment generate handoff health history hooks init list-status prepare-msg prune push release reset restore set-editor schema show snippet stats status sync template todo tour usage help" -f -a "restore" -d 'Discard working-tree changes, restoring files to their staged or committed state'
+complete -c rona -n "__fish_rona_using_subcommand help; and not __fish_seen_subcommand_from am audit backport branch browse bundle add-with-exclude commit completion config copy daemon check-msg env explain-status format-patch fragment generate handoff health history hooks init list-status prepare-msg prune push release reset restore set-editor schema show snippet stats status sync template todo tour usage help" -f -a "set-editor" -d 'Set the editor to use for editing the commit message'
+complete -c rona -n "__fish_rona_using_subcommand help; and not __fish_seen_subcommand_from am audit backport branch browse bundle add-with-exclude commit completion config copy daemon check-msg env explain-status format-patch fragment generate handoff health history hooks init list-status prepare-msg prune push release reset restore set-editor schema show snippet stats status sync template todo tour usage help" -f -a "schema" -d 'Print the JSON Schema of the `--json` outputs, for tools that read them'
+complete -c rona -n "__fish_rona_using_subcommand help; and not __fish_seen_subcommand_from am audit backport branch browse bundle add-with-exclude commit completion config copy daemon check-msg env explain-status format-patch fragment generate handoff health history hooks init list-status prepare-msg prune push release reset restore set-editor schema show snippet stats status sync template todo tour usage help" -f -a "show" -d 'Show a commit: its template fields, trailers, notes, signature and diff stat'
+complete -c rona -n "__fish_rona_using_subcommand help; and not __fish_seen_subcommand_from am audit backport branch browse bundle add-with-exclude commit completion config copy daemon check-msg env explain-status format-patch fragment generate handoff health history hooks init list-status prepare-msg prune push release reset restore set-editor schema show snippet stats status sync template todo tour usage help" -f -a "snippet" -d 'Insert reusable message body text defined under `[snippets]`'
+complete -c rona -n "__fish_rona_using_subcommand help; and not __fish_seen_subcommand_from am audit backport branch browse bundle add-with-exclude commit completion config copy daemon check-msg env explain-status format-patch fragment generate handoff health history hooks init list-status prepare-msg prune push release reset restore set-editor schema show snippet stats status sync template todo tour usage help" -f -a "stats" -d 'List the commits made with rona, with their size and lead time, or export them for dashboards. Computed locally from the operation log'
+complete -c rona -n "__fish_rona_using_subcommand help; and not __fish_seen_subcommand_from am audit backport branch browse bundle add-with-exclude commit completion config copy daemon check-msg env explain-status format-patch fragment generate handoff health history hooks init list-status prepare-msg prune push release reset restore set-editor schema show snippet stats status sync template todo tour usage help" -f -a "status" -d 'Show the current branch, how it compares to its upstream, and the changed files grouped by state'
+complete -c rona -n "__fish_rona_using_subcommand help; and not __fish_seen_subcommand_from am audit backport branch browse bundle add-with-exclude commit completion config copy daemon check-msg env explain-status format-patch fragment generate handoff health history hooks init list-status prepare-msg prune push release reset restore set-editor schema show snippet stats status sync template todo tour usage help" -f -a "sync" -d 'Sync current branch with the default branch (or another one) by pulling and merging/rebasing'
+complete -c rona -n "__fish_rona_using_subcommand help; and not __fish_seen_subcommand_from am audit backport branch browse bundle add-with-exclude commit completion config copy daemon check-msg env explain-status format-patch fragment generate handoff health history hooks init list-status prepare-msg prune push release reset restore set-editor schema show snippet stats status sync template todo tour usage help" -f -a "template" -d 'Check the configured templates for unknown variables and malformed blocks'
+complete -c rona -n "__fish_rona_using_subcommand help; and not __fish_seen_subcommand_from am audit backport branch browse bundle add-with-exclude commit completion config copy daemon check-msg env explain-status format-patch fragment generate handoff health history hooks init list-status prepare-msg prune push release reset restore set-editor schema show snippet stats status sync template todo tour usage help" -f -a "todo" -d 'List TODO, FIXME and HACK markers on the lines the staged changes add'
+complete -c rona -n "__fish_rona_using_subcommand help; and not __fish_seen_subcommand_from am audit backport branch browse bundle add-with-exclude commit completion config copy daemon check-msg env explain-status format-patch fragment generate handoff health history hooks init list-status prepare-msg prune push release reset restore set-editor schema show snippet stats status sync template todo tour usage help" -f -a "tour" -d 'Walk through the rona workflow in a throwaway demo repository'
+complete -c rona -n "__fish_rona_using_subcommand help; and not __fish_seen_subcommand_from am audit backport branch browse bundle add-with-exclude commit completion config copy daemon check-msg env explain-status format-patch fragment generate handoff health history hooks init list-status prepare-msg prune push release reset restore set-editor schema show snippet stats status sync template todo tour usage help" -f -a "usage" -d 'Summarize your own rona usage in this repository from the operation log. Computed locally; nothing is sent anywhere'
+complete -c rona -n "__fish_rona_using_subcommand help; and not __fish_seen_subcommand_from am audit backport branch browse bundle add-with-exclude commit completion config copy daemon check-msg env explain-status format-patch fragment generate handoff health history hooks init list-status prepare-msg prune push release reset restore set-editor schema show snippet stats status sync template todo tour usage help" -f -a "help" -d 'Print this message or the help of the given subcommand(s)'
+complete -c rona -n "__fish_rona_using_subcommand help; and __fish_seen_subcommand_from branch" -f -a "describe" -d 'Set the purpose of the current branch, exposed as `{branch_description}`'
+complete -c rona -n "__fish_rona_using_subcommand help; and __fish_seen_subcommand_from branch" -f -a "list" -d 'List local branches with their descriptions'
+complete -c rona -n "__fish_rona_using_subcommand help; and __fish_seen_subcommand_from branch" -f -a "new" -d 'Create a branch from `branch_template` without prompting for the description'
+complete -c rona -n "__fish_rona_using_subcommand help; and __fish_seen_subcommand_from branch" -f -a "rename" -d 'Rename the current branch, push it under the new name and track it'
+complete -c rona -n "__fish_rona_using_subcommand help; and __fish_seen_subcommand_from branch" -f -a "switch" -d 'Switch to a local branch, picked with a fuzzy finder when omitted'
+complete -c rona -n "__fish_rona_using_subcommand help; and __fish_seen_subcommand_from branch" -f -a "merge" -d 'Merge a local branch into the current one, picked with a fuzzy finder when omitted'
+complete -c rona -n "__fish_rona_using_subcommand help; and __fish_seen_subcommand_from branch" -f -a "rebase" -d 'Rebase the current branch onto a local branch, picked with a fuzzy finder when omitted'
+complete -c rona -n "__fish_rona_using_subcommand help; and __fish_seen_subcommand_from branch" -f -a "pull" -d 'Pull the upstream changes of the current branch'
+complete -c rona -n "__fish_rona_using_subcommand help; and __fish_seen_subcommand_from bundle" -f -a "create" -d 'Write branches to a bundle file'
+complete -c rona -n "__fish_rona_using_subcommand help; and __fish_seen_subcommand_from bundle" -f -a "apply" -d 'Fast-forward branches from a bundle file, after checking it holds them'
+complete -c rona -n "__fish_rona_using_subcommand help; and __fish_seen_subcommand_from completion" -f -a "check" -d 'Check that the completions are installed, up to date and able to list files'
 complete -c rona -n "__fish_rona_using_subcommand help; and __fish_seen_subcommand_from config" -f -a "create" -d 'Create or manage a local or global configuration file'
+complete -c rona -n "__fish_rona_using_subcommand help; and __fish_seen_subcommand_from config" -f -a "edit" -d 'Edit a configuration file: in the editor, or key by key with `--interactive`'
+complete -c rona -n "__fish_rona_using_subcommand help; and __fish_seen_subcommand_from config" -f -a "show" -d 'Print the effective configuration, merged from every config file'
+complete -c rona -n "__fish_rona_using_subcommand help; and __fish_seen_subcommand_from config" -f -a "sync" -d 'Compare the project `.rona.toml` with the team\'s canonical one, set under `[config_sync]`, and apply the differences after confirmation'
 complete -c rona -n "__fish_rona_using_subcommand help; and __fish_seen_subcommand_from config" -f -a "which" -d 'Show which configuration files would be used from a directory'
+complete -c rona -n "__fish_rona_using_subcommand help; and __fish_seen_subcommand_from fragment" -f -a "add" -d 'Write a changelog fragment for the current branch'
+complete -c rona -n "__fish_rona_using_subcommand help; and __fish_seen_subcommand_from fragment" -f -a "list" -d 'List the fragments waiting for the next release'
+complete -c rona -n "__fish_rona_using_subcommand help; and __fish_seen_subcommand_from fragment" -f -a "build" -d 'Add the fragments to the changelog as a new release, then delete them'
+complete -c rona -n "__fish_rona_using_subcommand help; and __fish_seen_subcommand_from handoff" -f -a "push" -d 'Push the staged changes and `commit_message.md` of the current branch'
+complete -c rona -n "__fish_rona_using_subcommand help; and __fish_seen_subcommand_from handoff" -f -a "pull" -d 'Check out a branch handed off from another clone and stage its changes'
+complete -c rona -n "__fish_rona_using_subcommand help; and __fish_seen_subcommand_from history" -f -a "ops" -d 'List the state-changing operations rona performed, newest first'
+complete -c rona -n "__fish_rona_using_subcommand help; and __fish_seen_subcommand_from hooks" -f -a "install" -d 'Write managed hook scripts that call back into rona'
+complete -c rona -n "__fish_rona_using_subcommand help; and __fish_seen_subcommand_from hooks" -f -a "uninstall" -d 'Remove the hook scripts rona installed, leaving other hooks alone'
+complete -c rona -n "__fish_rona_using_subcommand help; and __fish_seen_subcommand_from hooks" -f -a "list" -d 'Show which hooks are installed and whether rona manages them'
+complete -c rona -n "__fish_rona_using_subcommand help; and __fish_seen_subcommand_from hooks" -f -a "run" -d 'Run a hook\'s checks; called by the installed hook scripts'
+complete -c rona -n "__fish_rona_using_subcommand help; and __fish_seen_subcommand_from snippet" -f -a "list" -d 'List the configured snippets'
+complete -c rona -n "__fish_rona_using_subcommand help; and __fish_seen_subcommand_from snippet" -f -a "insert" -d 'Append a rendered snippet to the commit message file'
+complete -c rona -n "__fish_rona_using_subcommand help; and __fish_seen_subcommand_from template" -f -a "check" -d 'Lint the configured templates for unknown variables and malformed blocks'
 
 # === CUSTOM RONA COMPLETIONS ===
 # Helper function to get git status files
@@ -23,50 +23,427 @@ Register-ArgumentCompleter -Native -CommandName 'rona' -ScriptBlock {
         'rona' {
             [CompletionResult]::new('-f', '-f', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
             [CompletionResult]::new('--config-file', '--config-file', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('-C', '-C ', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--chdir', '--chdir', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--lang', '--lang', [CompletionResultType]::ParameterName, 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`')
+            [CompletionResult]::new('--template', '--template', [CompletionResultType]::ParameterName, 'Commit template to use: the one named NAME in `[templates]`')
+            [CompletionResult]::new('--scope', '--scope', [CompletionResultType]::ParameterName, 'Only consider the files under a directory: a name from `[scopes]` or a path')
+            [CompletionResult]::new('--output', '--output', [CompletionResultType]::ParameterName, 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`')
             [CompletionResult]::new('-v', '-v', [CompletionResultType]::ParameterName, 'Verbose output - show detailed information about operations')
             [CompletionResult]::new('--verbose', '--verbose', [CompletionResultType]::ParameterName, 'Verbose output - show detailed information about operations')
-            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
-            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--read-only', '--read-only', [CompletionResultType]::ParameterName, 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
             [CompletionResult]::new('-V', '-V ', [CompletionResultType]::ParameterName, 'Print version')
             [CompletionResult]::new('--version', '--version', [CompletionResultType]::ParameterName, 'Print version')
+            [CompletionResult]::new('am', 'am', [CompletionResultType]::ParameterValue, 'Apply mailbox patches, with a three-way fallback for patches that do not apply')
+            [CompletionResult]::new('audit', 'audit', [CompletionResultType]::ParameterValue, 'Report which existing commits follow the commit template and lint rules')
+            [CompletionResult]::new('backport', 'backport', [CompletionResultType]::ParameterValue, 'Cherry-pick a commit onto release branches with a re-templated message')
             [CompletionResult]::new('branch', 'branch', [CompletionResultType]::ParameterValue, 'Create a new branch interactively using a branch name template')
+            [CompletionResult]::new('browse', 'browse', [CompletionResultType]::ParameterValue, 'Browse recent commits: filter by typing, then view, copy, fix up or revert one')
+            [CompletionResult]::new('bundle', 'bundle', [CompletionResultType]::ParameterValue, 'Carry branches to a clone without a shared remote in `git bundle` files')
             [CompletionResult]::new('add-with-exclude', 'add-with-exclude', [CompletionResultType]::ParameterValue, 'Add all files to the `git add` command and exclude the patterns passed as positional arguments')
             [CompletionResult]::new('commit', 'commit', [CompletionResultType]::ParameterValue, 'Directly commit the file with the text in `commit_message.md`')
             [CompletionResult]::new('completion', 'completion', [CompletionResultType]::ParameterValue, 'Generate shell completions for your shell')
             [CompletionResult]::new('config', 'config', [CompletionResultType]::ParameterValue, 'Manage configuration files (create or inspect)')
+            [CompletionResult]::new('copy', 'copy', [CompletionResultType]::ParameterValue, 'Copy a reference to a commit to the clipboard, e.g. `abc1234 ("fix: handle empty index")`, for pull request descriptions and review comments')
+            [CompletionResult]::new('daemon', 'daemon', [CompletionResultType]::ParameterValue, 'Keep the changed files warm for `rona -l`, served over a unix socket')
+            [CompletionResult]::new('check-msg', 'check-msg', [CompletionResultType]::ParameterValue, 'Check a commit message file against the commit template')
+            [CompletionResult]::new('env', 'env', [CompletionResultType]::ParameterValue, 'Show what rona resolved here: repository, config files, template, editor, signing, guards and scope. Paste it in bug reports')
+            [CompletionResult]::new('which', 'which', [CompletionResultType]::ParameterValue, 'Show what rona resolved here: repository, config files, template, editor, signing, guards and scope. Paste it in bug reports')
+            [CompletionResult]::new('explain-status', 'explain-status', [CompletionResultType]::ParameterValue, 'Show git status with plain-language explanations of each state and the rona commands that act on it')
+            [CompletionResult]::new('format-patch', 'format-patch', [CompletionResultType]::ParameterValue, 'Export commits as mbox patch files for mailing-list review')
+            [CompletionResult]::new('fragment', 'fragment', [CompletionResultType]::ParameterValue, 'Write changelog fragments and assemble them into the changelog at release time')
             [CompletionResult]::new('generate', 'generate', [CompletionResultType]::ParameterValue, 'Directly generate the `commit_message.md` file')
+            [CompletionResult]::new('handoff', 'handoff', [CompletionResultType]::ParameterValue, 'Continue a half-finished commit in another clone: push the staged changes and `commit_message.md` of a branch to a ref, then pull them on the other machine')
+            [CompletionResult]::new('health', 'health', [CompletionResultType]::ParameterValue, 'Check the repository for signs of missing maintenance (loose objects, missing commit-graph, large files in history, broken refs)')
+            [CompletionResult]::new('history', 'history', [CompletionResultType]::ParameterValue, 'Review what rona did in this repository (recorded in `.git/rona/oplog.jsonl`)')
+            [CompletionResult]::new('hooks', 'hooks', [CompletionResultType]::ParameterValue, 'Install, list or remove git hooks that run rona''s checks on plain `git commit` and `git push`')
             [CompletionResult]::new('init', 'init', [CompletionResultType]::ParameterValue, 'Initialize the rona configuration file')
             [CompletionResult]::new('list-status', 'list-status', [CompletionResultType]::ParameterValue, 'List files from git status (for shell completion on the -a)')
+            [CompletionResult]::new('prepare-msg', 'prepare-msg', [CompletionResultType]::ParameterValue, 'Prefill a commit message file from the project template')
+            [CompletionResult]::new('prune', 'prune', [CompletionResultType]::ParameterValue, 'Clean up the repository: prune deleted remote branches, expire reflogs, remove unreachable objects and stale rona state files')
             [CompletionResult]::new('push', 'push', [CompletionResultType]::ParameterValue, 'Push to a git repository')
+            [CompletionResult]::new('release', 'release', [CompletionResultType]::ParameterValue, 'Tag the next version, adding its changelog section from the fragments')
             [CompletionResult]::new('reset', 'reset', [CompletionResultType]::ParameterValue, 'Unstage files, moving them out of the staging area without losing changes')
             [CompletionResult]::new('restore', 'restore', [CompletionResultType]::ParameterValue, 'Discard working-tree changes, restoring files to their staged or committed state')
             [CompletionResult]::new('set-editor', 'set-editor', [CompletionResultType]::ParameterValue, 'Set the editor to use for editing the commit message')
-            [CompletionResult]::new('sync', 'sync', [CompletionResultType]::ParameterValue, 'Sync current branch with main (or another branch) by pulling and merging/rebasing')
+            [CompletionResult]::new('schema', 'schema', [CompletionResultType]::ParameterValue, 'Print the JSON Schema of the `--json` outputs, for tools that read them')
+            [CompletionResult]::new('show', 'show', [CompletionResultType]::ParameterValue, 'Show a commit: its template fields, trailers, notes, signature and diff stat')
+            [CompletionResult]::new('snippet', 'snippet', [CompletionResultType]::ParameterValue, 'Insert reusable message body text defined under `[snippets]`')
+            [CompletionResult]::new('stats', 'stats', [CompletionResultType]::ParameterValue, 'List the commits made with rona, with their size and lead time, or export them for dashboards. Computed locally from the operation log')
+            [CompletionResult]::new('status', 'status', [CompletionResultType]::ParameterValue, 'Show the current branch, how it compares to its upstream, and the changed files grouped by state')
+            [CompletionResult]::new('sync', 'sync', [CompletionResultType]::ParameterValue, 'Sync current branch with the default branch (or another one) by pulling and merging/rebasing')
+            [CompletionResult]::new('template', 'template', [CompletionResultType]::ParameterValue, 'Check the configured templates for unknown variables and malformed blocks')
+            [CompletionResult]::new('todo', 'todo', [CompletionResultType]::ParameterValue, 'List TODO, FIXME and HACK markers on the lines the staged changes add')
+            [CompletionResult]::new('tour', 'tour', [CompletionResultType]::ParameterValue, 'Walk through the rona workflow in a throwaway demo repository')
+            [CompletionResult]::new('usage', 'usage', [CompletionResultType]::ParameterValue, 'Summarize your own rona usage in this repository from the operation log. Computed locally; nothing is sent anywhere')
             [CompletionResult]::new('help', 'help', [CompletionResultType]::ParameterValue, 'Print this message or the help of the given subcommand(s)')
             break
         }
+        'rona;am' {
+            [CompletionResult]::new('-f', '-f', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('--config-file', '--config-file', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('-C', '-C ', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--chdir', '--chdir', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--lang', '--lang', [CompletionResultType]::ParameterName, 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`')
+            [CompletionResult]::new('--template', '--template', [CompletionResultType]::ParameterName, 'Commit template to use: the one named NAME in `[templates]`')
+            [CompletionResult]::new('--scope', '--scope', [CompletionResultType]::ParameterName, 'Only consider the files under a directory: a name from `[scopes]` or a path')
+            [CompletionResult]::new('--output', '--output', [CompletionResultType]::ParameterName, 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`')
+            [CompletionResult]::new('--retemplate', '--retemplate', [CompletionResultType]::ParameterName, 'Rewrite each subject through the commit template, numbered from the next commit')
+            [CompletionResult]::new('--continue', '--continue', [CompletionResultType]::ParameterName, 'Apply the rest of the patches after resolving conflicts')
+            [CompletionResult]::new('--skip', '--skip', [CompletionResultType]::ParameterName, 'Drop the patch that stopped and apply the rest')
+            [CompletionResult]::new('--abort', '--abort', [CompletionResultType]::ParameterName, 'Stop applying and restore the branch to where it was')
+            [CompletionResult]::new('--dry-run', '--dry-run', [CompletionResultType]::ParameterName, 'Show which patches would be applied, with their subjects, without applying them')
+            [CompletionResult]::new('--read-only', '--read-only', [CompletionResultType]::ParameterName, 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            break
+        }
+        'rona;audit' {
+            [CompletionResult]::new('--since', '--since', [CompletionResultType]::ParameterName, 'Only audit the commits after this ref (e.g. the tag of the last release)')
+            [CompletionResult]::new('-f', '-f', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('--config-file', '--config-file', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('-C', '-C ', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--chdir', '--chdir', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--lang', '--lang', [CompletionResultType]::ParameterName, 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`')
+            [CompletionResult]::new('--template', '--template', [CompletionResultType]::ParameterName, 'Commit template to use: the one named NAME in `[templates]`')
+            [CompletionResult]::new('--scope', '--scope', [CompletionResultType]::ParameterName, 'Only consider the files under a directory: a name from `[scopes]` or a path')
+            [CompletionResult]::new('--output', '--output', [CompletionResultType]::ParameterName, 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`')
+            [CompletionResult]::new('--read-only', '--read-only', [CompletionResultType]::ParameterName, 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            break
+        }
+        'rona;backport' {
+            [CompletionResult]::new('--to', '--to', [CompletionResultType]::ParameterName, 'Target branches, comma-separated or repeated (`--to release/1.x,release/2.x`)')
+            [CompletionResult]::new('--remote', '--remote', [CompletionResultType]::ParameterName, 'Remote the target branches are fetched from and pushed to')
+            [CompletionResult]::new('-f', '-f', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('--config-file', '--config-file', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('-C', '-C ', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--chdir', '--chdir', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--lang', '--lang', [CompletionResultType]::ParameterName, 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`')
+            [CompletionResult]::new('--template', '--template', [CompletionResultType]::ParameterName, 'Commit template to use: the one named NAME in `[templates]`')
+            [CompletionResult]::new('--scope', '--scope', [CompletionResultType]::ParameterName, 'Only consider the files under a directory: a name from `[scopes]` or a path')
+            [CompletionResult]::new('--output', '--output', [CompletionResultType]::ParameterName, 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`')
+            [CompletionResult]::new('-p', '-p', [CompletionResultType]::ParameterName, 'Push each target branch after committing the backport')
+            [CompletionResult]::new('--push', '--push', [CompletionResultType]::ParameterName, 'Push each target branch after committing the backport')
+            [CompletionResult]::new('--pr', '--pr', [CompletionResultType]::ParameterName, 'Commit on a `backport/<sha>/<target>` branch, push it and open a pull request with the GitHub CLI (`gh`)')
+            [CompletionResult]::new('-u', '-u', [CompletionResultType]::ParameterName, 'Create unsigned commits')
+            [CompletionResult]::new('--unsigned', '--unsigned', [CompletionResultType]::ParameterName, 'Create unsigned commits')
+            [CompletionResult]::new('--dry-run', '--dry-run', [CompletionResultType]::ParameterName, 'Show what would be backported without changing any branch')
+            [CompletionResult]::new('--read-only', '--read-only', [CompletionResultType]::ParameterName, 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            break
+        }
         'rona;branch' {
             [CompletionResult]::new('-f', '-f', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
             [CompletionResult]::new('--config-file', '--config-file', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('-C', '-C ', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--chdir', '--chdir', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--lang', '--lang', [CompletionResultType]::ParameterName, 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`')
+            [CompletionResult]::new('--template', '--template', [CompletionResultType]::ParameterName, 'Commit template to use: the one named NAME in `[templates]`')
+            [CompletionResult]::new('--scope', '--scope', [CompletionResultType]::ParameterName, 'Only consider the files under a directory: a name from `[scopes]` or a path')
+            [CompletionResult]::new('--output', '--output', [CompletionResultType]::ParameterName, 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`')
             [CompletionResult]::new('--dry-run', '--dry-run', [CompletionResultType]::ParameterName, 'Show what would be created without actually creating the branch')
             [CompletionResult]::new('--no-switch', '--no-switch', [CompletionResultType]::ParameterName, 'Create the branch without switching to it')
-            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
-            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--read-only', '--read-only', [CompletionResultType]::ParameterName, 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('describe', 'describe', [CompletionResultType]::ParameterValue, 'Set the purpose of the current branch, exposed as `{branch_description}`')
+            [CompletionResult]::new('list', 'list', [CompletionResultType]::ParameterValue, 'List local branches with their descriptions')
+            [CompletionResult]::new('new', 'new', [CompletionResultType]::ParameterValue, 'Create a branch from `branch_template` without prompting for the description')
+            [CompletionResult]::new('rename', 'rename', [CompletionResultType]::ParameterValue, 'Rename the current branch, push it under the new name and track it')
+            [CompletionResult]::new('switch', 'switch', [CompletionResultType]::ParameterValue, 'Switch to a local branch, picked with a fuzzy finder when omitted')
+            [CompletionResult]::new('merge', 'merge', [CompletionResultType]::ParameterValue, 'Merge a local branch into the current one, picked with a fuzzy finder when omitted')
+            [CompletionResult]::new('rebase', 'rebase', [CompletionResultType]::ParameterValue, 'Rebase the current branch onto a local branch, picked with a fuzzy finder when omitted')
+            [CompletionResult]::new('pull', 'pull', [CompletionResultType]::ParameterValue, 'Pull the upstream changes of the current branch')
+            [CompletionResult]::new('help', 'help', [CompletionResultType]::ParameterValue, 'Print this message or the help of the given subcommand(s)')
+            break
+        }
+        'rona;branch;describe' {
+            [CompletionResult]::new('-f', '-f', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('--config-file', '--config-file', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('-C', '-C ', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--chdir', '--chdir', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--lang', '--lang', [CompletionResultType]::ParameterName, 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`')
+            [CompletionResult]::new('--template', '--template', [CompletionResultType]::ParameterName, 'Commit template to use: the one named NAME in `[templates]`')
+            [CompletionResult]::new('--scope', '--scope', [CompletionResultType]::ParameterName, 'Only consider the files under a directory: a name from `[scopes]` or a path')
+            [CompletionResult]::new('--output', '--output', [CompletionResultType]::ParameterName, 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`')
+            [CompletionResult]::new('--clear', '--clear', [CompletionResultType]::ParameterName, 'Remove the description of the current branch')
+            [CompletionResult]::new('--dry-run', '--dry-run', [CompletionResultType]::ParameterName, 'Show the description that would be stored without storing it')
+            [CompletionResult]::new('--read-only', '--read-only', [CompletionResultType]::ParameterName, 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            break
+        }
+        'rona;branch;list' {
+            [CompletionResult]::new('-f', '-f', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('--config-file', '--config-file', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('-C', '-C ', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--chdir', '--chdir', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--lang', '--lang', [CompletionResultType]::ParameterName, 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`')
+            [CompletionResult]::new('--template', '--template', [CompletionResultType]::ParameterName, 'Commit template to use: the one named NAME in `[templates]`')
+            [CompletionResult]::new('--scope', '--scope', [CompletionResultType]::ParameterName, 'Only consider the files under a directory: a name from `[scopes]` or a path')
+            [CompletionResult]::new('--output', '--output', [CompletionResultType]::ParameterName, 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`')
+            [CompletionResult]::new('--read-only', '--read-only', [CompletionResultType]::ParameterName, 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            break
+        }
+        'rona;branch;new' {
+            [CompletionResult]::new('-t', '-t', [CompletionResultType]::ParameterName, 'Ticket reference, available as `{ticket}` (e.g. `PROJ-1`)')
+            [CompletionResult]::new('--ticket', '--ticket', [CompletionResultType]::ParameterName, 'Ticket reference, available as `{ticket}` (e.g. `PROJ-1`)')
+            [CompletionResult]::new('--type', '--type', [CompletionResultType]::ParameterName, 'Branch type, available as `{branch_type}` or `{type}`; prompted when missing')
+            [CompletionResult]::new('-f', '-f', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('--config-file', '--config-file', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('-C', '-C ', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--chdir', '--chdir', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--lang', '--lang', [CompletionResultType]::ParameterName, 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`')
+            [CompletionResult]::new('--template', '--template', [CompletionResultType]::ParameterName, 'Commit template to use: the one named NAME in `[templates]`')
+            [CompletionResult]::new('--scope', '--scope', [CompletionResultType]::ParameterName, 'Only consider the files under a directory: a name from `[scopes]` or a path')
+            [CompletionResult]::new('--output', '--output', [CompletionResultType]::ParameterName, 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`')
+            [CompletionResult]::new('--no-switch', '--no-switch', [CompletionResultType]::ParameterName, 'Create the branch without switching to it')
+            [CompletionResult]::new('--dry-run', '--dry-run', [CompletionResultType]::ParameterName, 'Show the branch name without creating it')
+            [CompletionResult]::new('--read-only', '--read-only', [CompletionResultType]::ParameterName, 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            break
+        }
+        'rona;branch;rename' {
+            [CompletionResult]::new('-f', '-f', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('--config-file', '--config-file', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('-C', '-C ', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--chdir', '--chdir', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--lang', '--lang', [CompletionResultType]::ParameterName, 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`')
+            [CompletionResult]::new('--template', '--template', [CompletionResultType]::ParameterName, 'Commit template to use: the one named NAME in `[templates]`')
+            [CompletionResult]::new('--scope', '--scope', [CompletionResultType]::ParameterName, 'Only consider the files under a directory: a name from `[scopes]` or a path')
+            [CompletionResult]::new('--output', '--output', [CompletionResultType]::ParameterName, 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`')
+            [CompletionResult]::new('--local', '--local', [CompletionResultType]::ParameterName, 'Only rename the local branch; the remote branch and tracking are left as they are')
+            [CompletionResult]::new('-y', '-y', [CompletionResultType]::ParameterName, 'Delete the old remote branch without asking')
+            [CompletionResult]::new('--yes', '--yes', [CompletionResultType]::ParameterName, 'Delete the old remote branch without asking')
+            [CompletionResult]::new('--keep-old', '--keep-old', [CompletionResultType]::ParameterName, 'Keep the old remote branch')
+            [CompletionResult]::new('--dry-run', '--dry-run', [CompletionResultType]::ParameterName, 'Show what would be renamed, pushed and deleted without doing it')
+            [CompletionResult]::new('--read-only', '--read-only', [CompletionResultType]::ParameterName, 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            break
+        }
+        'rona;branch;switch' {
+            [CompletionResult]::new('-f', '-f', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('--config-file', '--config-file', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('-C', '-C ', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--chdir', '--chdir', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--lang', '--lang', [CompletionResultType]::ParameterName, 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`')
+            [CompletionResult]::new('--template', '--template', [CompletionResultType]::ParameterName, 'Commit template to use: the one named NAME in `[templates]`')
+            [CompletionResult]::new('--scope', '--scope', [CompletionResultType]::ParameterName, 'Only consider the files under a directory: a name from `[scopes]` or a path')
+            [CompletionResult]::new('--output', '--output', [CompletionResultType]::ParameterName, 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`')
+            [CompletionResult]::new('--dry-run', '--dry-run', [CompletionResultType]::ParameterName, 'Show the branch that would be checked out without switching')
+            [CompletionResult]::new('--read-only', '--read-only', [CompletionResultType]::ParameterName, 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            break
+        }
+        'rona;branch;merge' {
+            [CompletionResult]::new('-f', '-f', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('--config-file', '--config-file', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('-C', '-C ', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--chdir', '--chdir', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--lang', '--lang', [CompletionResultType]::ParameterName, 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`')
+            [CompletionResult]::new('--template', '--template', [CompletionResultType]::ParameterName, 'Commit template to use: the one named NAME in `[templates]`')
+            [CompletionResult]::new('--scope', '--scope', [CompletionResultType]::ParameterName, 'Only consider the files under a directory: a name from `[scopes]` or a path')
+            [CompletionResult]::new('--output', '--output', [CompletionResultType]::ParameterName, 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`')
+            [CompletionResult]::new('--dry-run', '--dry-run', [CompletionResultType]::ParameterName, 'Show the branch that would be merged without merging it')
+            [CompletionResult]::new('--read-only', '--read-only', [CompletionResultType]::ParameterName, 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            break
+        }
+        'rona;branch;rebase' {
+            [CompletionResult]::new('-f', '-f', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('--config-file', '--config-file', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('-C', '-C ', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--chdir', '--chdir', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--lang', '--lang', [CompletionResultType]::ParameterName, 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`')
+            [CompletionResult]::new('--template', '--template', [CompletionResultType]::ParameterName, 'Commit template to use: the one named NAME in `[templates]`')
+            [CompletionResult]::new('--scope', '--scope', [CompletionResultType]::ParameterName, 'Only consider the files under a directory: a name from `[scopes]` or a path')
+            [CompletionResult]::new('--output', '--output', [CompletionResultType]::ParameterName, 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`')
+            [CompletionResult]::new('--dry-run', '--dry-run', [CompletionResultType]::ParameterName, 'Show the branch that would be rebased onto without rebasing')
+            [CompletionResult]::new('--read-only', '--read-only', [CompletionResultType]::ParameterName, 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            break
+        }
+        'rona;branch;pull' {
+            [CompletionResult]::new('-f', '-f', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('--config-file', '--config-file', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('-C', '-C ', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--chdir', '--chdir', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--lang', '--lang', [CompletionResultType]::ParameterName, 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`')
+            [CompletionResult]::new('--template', '--template', [CompletionResultType]::ParameterName, 'Commit template to use: the one named NAME in `[templates]`')
+            [CompletionResult]::new('--scope', '--scope', [CompletionResultType]::ParameterName, 'Only consider the files under a directory: a name from `[scopes]` or a path')
+            [CompletionResult]::new('--output', '--output', [CompletionResultType]::ParameterName, 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`')
+            [CompletionResult]::new('--dry-run', '--dry-run', [CompletionResultType]::ParameterName, 'Show what would be pulled without pulling')
+            [CompletionResult]::new('--read-only', '--read-only', [CompletionResultType]::ParameterName, 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            break
+        }
+        'rona;branch;help' {
+            [CompletionResult]::new('describe', 'describe', [CompletionResultType]::ParameterValue, 'Set the purpose of the current branch, exposed as `{branch_description}`')
+            [CompletionResult]::new('list', 'list', [CompletionResultType]::ParameterValue, 'List local branches with their descriptions')
+            [CompletionResult]::new('new', 'new', [CompletionResultType]::ParameterValue, 'Create a branch from `branch_template` without prompting for the description')
+            [CompletionResult]::new('rename', 'rename', [CompletionResultType]::ParameterValue, 'Rename the current branch, push it under the new name and track it')
+            [CompletionResult]::new('switch', 'switch', [CompletionResultType]::ParameterValue, 'Switch to a local branch, picked with a fuzzy finder when omitted')
+            [CompletionResult]::new('merge', 'merge', [CompletionResultType]::ParameterValue, 'Merge a local branch into the current one, picked with a fuzzy finder when omitted')
+            [CompletionResult]::new('rebase', 'rebase', [CompletionResultType]::ParameterValue, 'Rebase the current branch onto a local branch, picked with a fuzzy finder when omitted')
+            [CompletionResult]::new('pull', 'pull', [CompletionResultType]::ParameterValue, 'Pull the upstream changes of the current branch')
+            [CompletionResult]::new('help', 'help', [CompletionResultType]::ParameterValue, 'Print this message or the help of the given subcommand(s)')
+            break
+        }
+        'rona;branch;help;describe' {
+            break
+        }
+        'rona;branch;help;list' {
+            break
+        }
+        'rona;branch;help;new' {
+            break
+        }
+        'rona;branch;help;rename' {
+            break
+        }
+        'rona;branch;help;switch' {
+            break
+        }
+        'rona;branch;help;merge' {
+            break
+        }
+        'rona;branch;help;rebase' {
+            break
+        }
+        'rona;branch;help;pull' {
+            break
+        }
+        'rona;branch;help;help' {
+            break
+        }
+        'rona;browse' {
+            [CompletionResult]::new('-n', '-n', [CompletionResultType]::ParameterName, 'Number of recent commits to list')
+            [CompletionResult]::new('--limit', '--limit', [CompletionResultType]::ParameterName, 'Number of recent commits to list')
+            [CompletionResult]::new('-f', '-f', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('--config-file', '--config-file', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('-C', '-C ', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--chdir', '--chdir', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--lang', '--lang', [CompletionResultType]::ParameterName, 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`')
+            [CompletionResult]::new('--template', '--template', [CompletionResultType]::ParameterName, 'Commit template to use: the one named NAME in `[templates]`')
+            [CompletionResult]::new('--scope', '--scope', [CompletionResultType]::ParameterName, 'Only consider the files under a directory: a name from `[scopes]` or a path')
+            [CompletionResult]::new('--output', '--output', [CompletionResultType]::ParameterName, 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`')
+            [CompletionResult]::new('--dry-run', '--dry-run', [CompletionResultType]::ParameterName, 'Show what a fixup or revert would do without running it')
+            [CompletionResult]::new('--read-only', '--read-only', [CompletionResultType]::ParameterName, 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            break
+        }
+        'rona;bundle' {
+            [CompletionResult]::new('-f', '-f', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('--config-file', '--config-file', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('-C', '-C ', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--chdir', '--chdir', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--lang', '--lang', [CompletionResultType]::ParameterName, 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`')
+            [CompletionResult]::new('--template', '--template', [CompletionResultType]::ParameterName, 'Commit template to use: the one named NAME in `[templates]`')
+            [CompletionResult]::new('--scope', '--scope', [CompletionResultType]::ParameterName, 'Only consider the files under a directory: a name from `[scopes]` or a path')
+            [CompletionResult]::new('--output', '--output', [CompletionResultType]::ParameterName, 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`')
+            [CompletionResult]::new('--read-only', '--read-only', [CompletionResultType]::ParameterName, 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('create', 'create', [CompletionResultType]::ParameterValue, 'Write branches to a bundle file')
+            [CompletionResult]::new('apply', 'apply', [CompletionResultType]::ParameterValue, 'Fast-forward branches from a bundle file, after checking it holds them')
+            [CompletionResult]::new('help', 'help', [CompletionResultType]::ParameterValue, 'Print this message or the help of the given subcommand(s)')
+            break
+        }
+        'rona;bundle;create' {
+            [CompletionResult]::new('--since', '--since', [CompletionResultType]::ParameterName, 'Leave out the commits reachable from this ref, which the receiving clone has')
+            [CompletionResult]::new('-b', '-b', [CompletionResultType]::ParameterName, 'Branch to bundle, can be repeated (default: the current branch)')
+            [CompletionResult]::new('--branch', '--branch', [CompletionResultType]::ParameterName, 'Branch to bundle, can be repeated (default: the current branch)')
+            [CompletionResult]::new('-f', '-f', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('--config-file', '--config-file', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('-C', '-C ', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--chdir', '--chdir', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--lang', '--lang', [CompletionResultType]::ParameterName, 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`')
+            [CompletionResult]::new('--template', '--template', [CompletionResultType]::ParameterName, 'Commit template to use: the one named NAME in `[templates]`')
+            [CompletionResult]::new('--scope', '--scope', [CompletionResultType]::ParameterName, 'Only consider the files under a directory: a name from `[scopes]` or a path')
+            [CompletionResult]::new('--output', '--output', [CompletionResultType]::ParameterName, 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`')
+            [CompletionResult]::new('--dry-run', '--dry-run', [CompletionResultType]::ParameterName, 'Show what would be bundled without writing the file')
+            [CompletionResult]::new('--read-only', '--read-only', [CompletionResultType]::ParameterName, 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            break
+        }
+        'rona;bundle;apply' {
+            [CompletionResult]::new('-b', '-b', [CompletionResultType]::ParameterName, 'Branch expected in the bundle and applied, can be repeated (default: the current branch)')
+            [CompletionResult]::new('--branch', '--branch', [CompletionResultType]::ParameterName, 'Branch expected in the bundle and applied, can be repeated (default: the current branch)')
+            [CompletionResult]::new('-f', '-f', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('--config-file', '--config-file', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('-C', '-C ', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--chdir', '--chdir', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--lang', '--lang', [CompletionResultType]::ParameterName, 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`')
+            [CompletionResult]::new('--template', '--template', [CompletionResultType]::ParameterName, 'Commit template to use: the one named NAME in `[templates]`')
+            [CompletionResult]::new('--scope', '--scope', [CompletionResultType]::ParameterName, 'Only consider the files under a directory: a name from `[scopes]` or a path')
+            [CompletionResult]::new('--output', '--output', [CompletionResultType]::ParameterName, 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`')
+            [CompletionResult]::new('--dry-run', '--dry-run', [CompletionResultType]::ParameterName, 'Show which branches would be updated without updating them')
+            [CompletionResult]::new('--read-only', '--read-only', [CompletionResultType]::ParameterName, 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            break
+        }
+        'rona;bundle;help' {
+            [CompletionResult]::new('create', 'create', [CompletionResultType]::ParameterValue, 'Write branches to a bundle file')
+            [CompletionResult]::new('apply', 'apply', [CompletionResultType]::ParameterValue, 'Fast-forward branches from a bundle file, after checking it holds them')
+            [CompletionResult]::new('help', 'help', [CompletionResultType]::ParameterValue, 'Print this message or the help of the given subcommand(s)')
+            break
+        }
+        'rona;bundle;help;create' {
+            break
+        }
+        'rona;bundle;help;apply' {
+            break
+        }
+        'rona;bundle;help;help' {
             break
         }
         'rona;add-with-exclude' {
+            [CompletionResult]::new('--filter', '--filter', [CompletionResultType]::ParameterName, 'Only offer files whose path fuzzy-matches this query (with `-i`)')
             [CompletionResult]::new('-f', '-f', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
             [CompletionResult]::new('--config-file', '--config-file', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
-            [CompletionResult]::new('-i', '-i', [CompletionResultType]::ParameterName, 'Interactively pick which changed files to stage (`MultiSelect` of git status)')
-            [CompletionResult]::new('--interactive', '--interactive', [CompletionResultType]::ParameterName, 'Interactively pick which changed files to stage (`MultiSelect` of git status)')
+            [CompletionResult]::new('-C', '-C ', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--chdir', '--chdir', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--lang', '--lang', [CompletionResultType]::ParameterName, 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`')
+            [CompletionResult]::new('--template', '--template', [CompletionResultType]::ParameterName, 'Commit template to use: the one named NAME in `[templates]`')
+            [CompletionResult]::new('--scope', '--scope', [CompletionResultType]::ParameterName, 'Only consider the files under a directory: a name from `[scopes]` or a path')
+            [CompletionResult]::new('--output', '--output', [CompletionResultType]::ParameterName, 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`')
+            [CompletionResult]::new('-i', '-i', [CompletionResultType]::ParameterName, 'Interactively pick which changed files to stage; excluded files start deselected')
+            [CompletionResult]::new('--interactive', '--interactive', [CompletionResultType]::ParameterName, 'Interactively pick which changed files to stage; excluded files start deselected')
             [CompletionResult]::new('--dry-run', '--dry-run', [CompletionResultType]::ParameterName, 'Show what would be added without actually adding files')
-            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
-            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--read-only', '--read-only', [CompletionResultType]::ParameterName, 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
             break
         }
         'rona;commit' {
+            [CompletionResult]::new('-m', '-m', [CompletionResultType]::ParameterName, 'Commit with this message run through the commit template, instead of `commit_message.md`')
+            [CompletionResult]::new('--message', '--message', [CompletionResultType]::ParameterName, 'Commit with this message run through the commit template, instead of `commit_message.md`')
+            [CompletionResult]::new('-t', '-t', [CompletionResultType]::ParameterName, 'Commit type for `--message` (inferred from the branch prefix by default)')
+            [CompletionResult]::new('--type', '--type', [CompletionResultType]::ParameterName, 'Commit type for `--message` (inferred from the branch prefix by default)')
             [CompletionResult]::new('-f', '-f', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
             [CompletionResult]::new('--config-file', '--config-file', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('-C', '-C ', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--chdir', '--chdir', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--lang', '--lang', [CompletionResultType]::ParameterName, 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`')
+            [CompletionResult]::new('--template', '--template', [CompletionResultType]::ParameterName, 'Commit template to use: the one named NAME in `[templates]`')
+            [CompletionResult]::new('--scope', '--scope', [CompletionResultType]::ParameterName, 'Only consider the files under a directory: a name from `[scopes]` or a path')
+            [CompletionResult]::new('--output', '--output', [CompletionResultType]::ParameterName, 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`')
             [CompletionResult]::new('-p', '-p', [CompletionResultType]::ParameterName, 'Whether to push the commit after committing')
             [CompletionResult]::new('--push', '--push', [CompletionResultType]::ParameterName, 'Whether to push the commit after committing')
             [CompletionResult]::new('-d', '-d', [CompletionResultType]::ParameterName, 'Show what would be committed without actually committing')
@@ -76,23 +453,70 @@ Register-ArgumentCompleter -Native -CommandName 'rona' -ScriptBlock {
             [CompletionResult]::new('-y', '-y', [CompletionResultType]::ParameterName, 'Skip confirmation prompt and commit directly')
             [CompletionResult]::new('--yes', '--yes', [CompletionResultType]::ParameterName, 'Skip confirmation prompt and commit directly')
             [CompletionResult]::new('--copy', '--copy', [CompletionResultType]::ParameterName, 'Copy commit message to clipboard instead of committing')
-            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
-            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('-n', '-n', [CompletionResultType]::ParameterName, 'Leave the commit number out of a `--message` commit')
+            [CompletionResult]::new('--no-commit-number', '--no-commit-number', [CompletionResultType]::ParameterName, 'Leave the commit number out of a `--message` commit')
+            [CompletionResult]::new('--read-only', '--read-only', [CompletionResultType]::ParameterName, 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
             break
         }
         'rona;completion' {
             [CompletionResult]::new('-f', '-f', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
             [CompletionResult]::new('--config-file', '--config-file', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
-            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
-            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('-C', '-C ', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--chdir', '--chdir', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--lang', '--lang', [CompletionResultType]::ParameterName, 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`')
+            [CompletionResult]::new('--template', '--template', [CompletionResultType]::ParameterName, 'Commit template to use: the one named NAME in `[templates]`')
+            [CompletionResult]::new('--scope', '--scope', [CompletionResultType]::ParameterName, 'Only consider the files under a directory: a name from `[scopes]` or a path')
+            [CompletionResult]::new('--output', '--output', [CompletionResultType]::ParameterName, 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`')
+            [CompletionResult]::new('--read-only', '--read-only', [CompletionResultType]::ParameterName, 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('check', 'check', [CompletionResultType]::ParameterValue, 'Check that the completions are installed, up to date and able to list files')
+            [CompletionResult]::new('help', 'help', [CompletionResultType]::ParameterValue, 'Print this message or the help of the given subcommand(s)')
+            break
+        }
+        'rona;completion;check' {
+            [CompletionResult]::new('-f', '-f', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('--config-file', '--config-file', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('-C', '-C ', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--chdir', '--chdir', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--lang', '--lang', [CompletionResultType]::ParameterName, 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`')
+            [CompletionResult]::new('--template', '--template', [CompletionResultType]::ParameterName, 'Commit template to use: the one named NAME in `[templates]`')
+            [CompletionResult]::new('--scope', '--scope', [CompletionResultType]::ParameterName, 'Only consider the files under a directory: a name from `[scopes]` or a path')
+            [CompletionResult]::new('--output', '--output', [CompletionResultType]::ParameterName, 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`')
+            [CompletionResult]::new('--read-only', '--read-only', [CompletionResultType]::ParameterName, 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            break
+        }
+        'rona;completion;help' {
+            [CompletionResult]::new('check', 'check', [CompletionResultType]::ParameterValue, 'Check that the completions are installed, up to date and able to list files')
+            [CompletionResult]::new('help', 'help', [CompletionResultType]::ParameterValue, 'Print this message or the help of the given subcommand(s)')
+            break
+        }
+        'rona;completion;help;check' {
+            break
+        }
+        'rona;completion;help;help' {
             break
         }
         'rona;config' {
             [CompletionResult]::new('-f', '-f', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
             [CompletionResult]::new('--config-file', '--config-file', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
-            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
-            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('-C', '-C ', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--chdir', '--chdir', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--lang', '--lang', [CompletionResultType]::ParameterName, 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`')
+            [CompletionResult]::new('--template', '--template', [CompletionResultType]::ParameterName, 'Commit template to use: the one named NAME in `[templates]`')
+            [CompletionResult]::new('--scope', '--scope', [CompletionResultType]::ParameterName, 'Only consider the files under a directory: a name from `[scopes]` or a path')
+            [CompletionResult]::new('--output', '--output', [CompletionResultType]::ParameterName, 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`')
+            [CompletionResult]::new('--read-only', '--read-only', [CompletionResultType]::ParameterName, 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
             [CompletionResult]::new('create', 'create', [CompletionResultType]::ParameterValue, 'Create or manage a local or global configuration file')
+            [CompletionResult]::new('edit', 'edit', [CompletionResultType]::ParameterValue, 'Edit a configuration file: in the editor, or key by key with `--interactive`')
+            [CompletionResult]::new('show', 'show', [CompletionResultType]::ParameterValue, 'Print the effective configuration, merged from every config file')
+            [CompletionResult]::new('sync', 'sync', [CompletionResultType]::ParameterValue, 'Compare the project `.rona.toml` with the team''s canonical one, set under `[config_sync]`, and apply the differences after confirmation')
             [CompletionResult]::new('which', 'which', [CompletionResultType]::ParameterValue, 'Show which configuration files would be used from a directory')
             [CompletionResult]::new('find', 'find', [CompletionResultType]::ParameterValue, 'Show which configuration files would be used from a directory')
             [CompletionResult]::new('help', 'help', [CompletionResultType]::ParameterValue, 'Print this message or the help of the given subcommand(s)')
@@ -101,9 +525,68 @@ Register-ArgumentCompleter -Native -CommandName 'rona' -ScriptBlock {
         'rona;config;create' {
             [CompletionResult]::new('-f', '-f', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
             [CompletionResult]::new('--config-file', '--config-file', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('-C', '-C ', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--chdir', '--chdir', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--lang', '--lang', [CompletionResultType]::ParameterName, 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`')
+            [CompletionResult]::new('--template', '--template', [CompletionResultType]::ParameterName, 'Commit template to use: the one named NAME in `[templates]`')
+            [CompletionResult]::new('--scope', '--scope', [CompletionResultType]::ParameterName, 'Only consider the files under a directory: a name from `[scopes]` or a path')
+            [CompletionResult]::new('--output', '--output', [CompletionResultType]::ParameterName, 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`')
             [CompletionResult]::new('-e', '-e', [CompletionResultType]::ParameterName, 'Add .rona.toml to .git/info/exclude (only applies to local scope)')
             [CompletionResult]::new('--exclude', '--exclude', [CompletionResultType]::ParameterName, 'Add .rona.toml to .git/info/exclude (only applies to local scope)')
             [CompletionResult]::new('--dry-run', '--dry-run', [CompletionResultType]::ParameterName, 'Show what would be created without actually creating the config file')
+            [CompletionResult]::new('--read-only', '--read-only', [CompletionResultType]::ParameterName, 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            break
+        }
+        'rona;config;edit' {
+            [CompletionResult]::new('-f', '-f', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('--config-file', '--config-file', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('-C', '-C ', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--chdir', '--chdir', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--lang', '--lang', [CompletionResultType]::ParameterName, 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`')
+            [CompletionResult]::new('--template', '--template', [CompletionResultType]::ParameterName, 'Commit template to use: the one named NAME in `[templates]`')
+            [CompletionResult]::new('--scope', '--scope', [CompletionResultType]::ParameterName, 'Only consider the files under a directory: a name from `[scopes]` or a path')
+            [CompletionResult]::new('--output', '--output', [CompletionResultType]::ParameterName, 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`')
+            [CompletionResult]::new('-i', '-i', [CompletionResultType]::ParameterName, 'Pick known keys from a list and edit them with prompts showing the current value, the default and a description. Comments in the file are kept')
+            [CompletionResult]::new('--interactive', '--interactive', [CompletionResultType]::ParameterName, 'Pick known keys from a list and edit them with prompts showing the current value, the default and a description. Comments in the file are kept')
+            [CompletionResult]::new('--read-only', '--read-only', [CompletionResultType]::ParameterName, 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            break
+        }
+        'rona;config;show' {
+            [CompletionResult]::new('-f', '-f', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('--config-file', '--config-file', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('-C', '-C ', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--chdir', '--chdir', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--lang', '--lang', [CompletionResultType]::ParameterName, 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`')
+            [CompletionResult]::new('--template', '--template', [CompletionResultType]::ParameterName, 'Commit template to use: the one named NAME in `[templates]`')
+            [CompletionResult]::new('--scope', '--scope', [CompletionResultType]::ParameterName, 'Only consider the files under a directory: a name from `[scopes]` or a path')
+            [CompletionResult]::new('--output', '--output', [CompletionResultType]::ParameterName, 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`')
+            [CompletionResult]::new('--origins', '--origins', [CompletionResultType]::ParameterName, 'Print each value with the file, environment variable or option it came from')
+            [CompletionResult]::new('--read-only', '--read-only', [CompletionResultType]::ParameterName, 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            break
+        }
+        'rona;config;sync' {
+            [CompletionResult]::new('--url', '--url', [CompletionResultType]::ParameterName, 'Download the canonical config from this URL (default: `[config_sync] url`)')
+            [CompletionResult]::new('--remote', '--remote', [CompletionResultType]::ParameterName, 'Remote name or repository URL of the branch holding the canonical config (default: `[config_sync] remote`, else `origin`)')
+            [CompletionResult]::new('--branch', '--branch', [CompletionResultType]::ParameterName, 'Branch holding the canonical config (default: `[config_sync] branch`, else `rona-config`)')
+            [CompletionResult]::new('-f', '-f', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('--config-file', '--config-file', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('-C', '-C ', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--chdir', '--chdir', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--lang', '--lang', [CompletionResultType]::ParameterName, 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`')
+            [CompletionResult]::new('--template', '--template', [CompletionResultType]::ParameterName, 'Commit template to use: the one named NAME in `[templates]`')
+            [CompletionResult]::new('--scope', '--scope', [CompletionResultType]::ParameterName, 'Only consider the files under a directory: a name from `[scopes]` or a path')
+            [CompletionResult]::new('--output', '--output', [CompletionResultType]::ParameterName, 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`')
+            [CompletionResult]::new('--check', '--check', [CompletionResultType]::ParameterName, 'Fail when the local config differs, without changing it (for CI)')
+            [CompletionResult]::new('-y', '-y', [CompletionResultType]::ParameterName, 'Apply the differences without asking')
+            [CompletionResult]::new('--yes', '--yes', [CompletionResultType]::ParameterName, 'Apply the differences without asking')
+            [CompletionResult]::new('--dry-run', '--dry-run', [CompletionResultType]::ParameterName, 'Show the differences without applying them')
+            [CompletionResult]::new('--read-only', '--read-only', [CompletionResultType]::ParameterName, 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)')
             [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
             [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
             break
@@ -111,23 +594,40 @@ Register-ArgumentCompleter -Native -CommandName 'rona' -ScriptBlock {
         'rona;config;which' {
             [CompletionResult]::new('-f', '-f', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
             [CompletionResult]::new('--config-file', '--config-file', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('-C', '-C ', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--chdir', '--chdir', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--lang', '--lang', [CompletionResultType]::ParameterName, 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`')
+            [CompletionResult]::new('--template', '--template', [CompletionResultType]::ParameterName, 'Commit template to use: the one named NAME in `[templates]`')
+            [CompletionResult]::new('--scope', '--scope', [CompletionResultType]::ParameterName, 'Only consider the files under a directory: a name from `[scopes]` or a path')
+            [CompletionResult]::new('--output', '--output', [CompletionResultType]::ParameterName, 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`')
             [CompletionResult]::new('-e', '-e', [CompletionResultType]::ParameterName, 'Show the effective (merged) configuration values')
             [CompletionResult]::new('--effective', '--effective', [CompletionResultType]::ParameterName, 'Show the effective (merged) configuration values')
-            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
-            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--read-only', '--read-only', [CompletionResultType]::ParameterName, 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
             break
         }
         'rona;config;find' {
             [CompletionResult]::new('-f', '-f', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
             [CompletionResult]::new('--config-file', '--config-file', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('-C', '-C ', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--chdir', '--chdir', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--lang', '--lang', [CompletionResultType]::ParameterName, 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`')
+            [CompletionResult]::new('--template', '--template', [CompletionResultType]::ParameterName, 'Commit template to use: the one named NAME in `[templates]`')
+            [CompletionResult]::new('--scope', '--scope', [CompletionResultType]::ParameterName, 'Only consider the files under a directory: a name from `[scopes]` or a path')
+            [CompletionResult]::new('--output', '--output', [CompletionResultType]::ParameterName, 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`')
             [CompletionResult]::new('-e', '-e', [CompletionResultType]::ParameterName, 'Show the effective (merged) configuration values')
             [CompletionResult]::new('--effective', '--effective', [CompletionResultType]::ParameterName, 'Show the effective (merged) configuration values')
-            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
-            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--read-only', '--read-only', [CompletionResultType]::ParameterName, 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
             break
         }
         'rona;config;help' {
             [CompletionResult]::new('create', 'create', [CompletionResultType]::ParameterValue, 'Create or manage a local or global configuration file')
+            [CompletionResult]::new('edit', 'edit', [CompletionResultType]::ParameterValue, 'Edit a configuration file: in the editor, or key by key with `--interactive`')
+            [CompletionResult]::new('show', 'show', [CompletionResultType]::ParameterValue, 'Print the effective configuration, merged from every config file')
+            [CompletionResult]::new('sync', 'sync', [CompletionResultType]::ParameterValue, 'Compare the project `.rona.toml` with the team''s canonical one, set under `[config_sync]`, and apply the differences after confirmation')
             [CompletionResult]::new('which', 'which', [CompletionResultType]::ParameterValue, 'Show which configuration files would be used from a directory')
             [CompletionResult]::new('help', 'help', [CompletionResultType]::ParameterValue, 'Print this message or the help of the given subcommand(s)')
             break
@@ -135,143 +635,1077 @@ Register-ArgumentCompleter -Native -CommandName 'rona' -ScriptBlock {
         'rona;config;help;create' {
             break
         }
+        'rona;config;help;edit' {
+            break
+        }
+        'rona;config;help;show' {
+            break
+        }
+        'rona;config;help;sync' {
+            break
+        }
         'rona;config;help;which' {
             break
         }
         'rona;config;help;help' {
             break
         }
-        'rona;generate' {
+        'rona;copy' {
+            [CompletionResult]::new('--format', '--format', [CompletionResultType]::ParameterName, '`reference`, `sha`, `short`, `subject`, a format of `[copy] formats`, or a template such as `{short_sha} {subject}` (default: `[copy] format`)')
             [CompletionResult]::new('-f', '-f', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
             [CompletionResult]::new('--config-file', '--config-file', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
-            [CompletionResult]::new('--dry-run', '--dry-run', [CompletionResultType]::ParameterName, 'Show what would be generated without creating files')
-            [CompletionResult]::new('-i', '-i', [CompletionResultType]::ParameterName, 'Interactive mode - input the commit message directly in the terminal')
-            [CompletionResult]::new('--interactive', '--interactive', [CompletionResultType]::ParameterName, 'Interactive mode - input the commit message directly in the terminal')
-            [CompletionResult]::new('-n', '-n', [CompletionResultType]::ParameterName, 'No commit number')
-            [CompletionResult]::new('--no-commit-number', '--no-commit-number', [CompletionResultType]::ParameterName, 'No commit number')
-            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
-            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('-C', '-C ', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--chdir', '--chdir', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--lang', '--lang', [CompletionResultType]::ParameterName, 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`')
+            [CompletionResult]::new('--template', '--template', [CompletionResultType]::ParameterName, 'Commit template to use: the one named NAME in `[templates]`')
+            [CompletionResult]::new('--scope', '--scope', [CompletionResultType]::ParameterName, 'Only consider the files under a directory: a name from `[scopes]` or a path')
+            [CompletionResult]::new('--output', '--output', [CompletionResultType]::ParameterName, 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`')
+            [CompletionResult]::new('--print', '--print', [CompletionResultType]::ParameterName, 'Print the text instead of copying it')
+            [CompletionResult]::new('--read-only', '--read-only', [CompletionResultType]::ParameterName, 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
             break
         }
-        'rona;init' {
+        'rona;daemon' {
             [CompletionResult]::new('-f', '-f', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
             [CompletionResult]::new('--config-file', '--config-file', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
-            [CompletionResult]::new('--dry-run', '--dry-run', [CompletionResultType]::ParameterName, 'Show what would be initialized without creating files')
-            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
-            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('-C', '-C ', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--chdir', '--chdir', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--lang', '--lang', [CompletionResultType]::ParameterName, 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`')
+            [CompletionResult]::new('--template', '--template', [CompletionResultType]::ParameterName, 'Commit template to use: the one named NAME in `[templates]`')
+            [CompletionResult]::new('--scope', '--scope', [CompletionResultType]::ParameterName, 'Only consider the files under a directory: a name from `[scopes]` or a path')
+            [CompletionResult]::new('--output', '--output', [CompletionResultType]::ParameterName, 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`')
+            [CompletionResult]::new('--stop', '--stop', [CompletionResultType]::ParameterName, 'Stop the daemon serving the current repository')
+            [CompletionResult]::new('--read-only', '--read-only', [CompletionResultType]::ParameterName, 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
             break
         }
-        'rona;list-status' {
+        'rona;check-msg' {
             [CompletionResult]::new('-f', '-f', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
             [CompletionResult]::new('--config-file', '--config-file', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
-            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
-            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('-C', '-C ', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--chdir', '--chdir', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--lang', '--lang', [CompletionResultType]::ParameterName, 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`')
+            [CompletionResult]::new('--template', '--template', [CompletionResultType]::ParameterName, 'Commit template to use: the one named NAME in `[templates]`')
+            [CompletionResult]::new('--scope', '--scope', [CompletionResultType]::ParameterName, 'Only consider the files under a directory: a name from `[scopes]` or a path')
+            [CompletionResult]::new('--output', '--output', [CompletionResultType]::ParameterName, 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`')
+            [CompletionResult]::new('--read-only', '--read-only', [CompletionResultType]::ParameterName, 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
             break
         }
-        'rona;push' {
+        'rona;env' {
             [CompletionResult]::new('-f', '-f', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
             [CompletionResult]::new('--config-file', '--config-file', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
-            [CompletionResult]::new('--dry-run', '--dry-run', [CompletionResultType]::ParameterName, 'Show what would be pushed without actually pushing')
-            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
-            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('-C', '-C ', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--chdir', '--chdir', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--lang', '--lang', [CompletionResultType]::ParameterName, 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`')
+            [CompletionResult]::new('--template', '--template', [CompletionResultType]::ParameterName, 'Commit template to use: the one named NAME in `[templates]`')
+            [CompletionResult]::new('--scope', '--scope', [CompletionResultType]::ParameterName, 'Only consider the files under a directory: a name from `[scopes]` or a path')
+            [CompletionResult]::new('--output', '--output', [CompletionResultType]::ParameterName, 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`')
+            [CompletionResult]::new('--json', '--json', [CompletionResultType]::ParameterName, 'Print the environment as JSON')
+            [CompletionResult]::new('--read-only', '--read-only', [CompletionResultType]::ParameterName, 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
             break
         }
-        'rona;reset' {
+        'rona;which' {
             [CompletionResult]::new('-f', '-f', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
             [CompletionResult]::new('--config-file', '--config-file', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
-            [CompletionResult]::new('-i', '-i', [CompletionResultType]::ParameterName, 'Interactively pick which staged files to unstage (`MultiSelect` of staged files)')
-            [CompletionResult]::new('--interactive', '--interactive', [CompletionResultType]::ParameterName, 'Interactively pick which staged files to unstage (`MultiSelect` of staged files)')
-            [CompletionResult]::new('--dry-run', '--dry-run', [CompletionResultType]::ParameterName, 'Show what would be unstaged without actually unstaging files')
-            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
-            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('-C', '-C ', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--chdir', '--chdir', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--lang', '--lang', [CompletionResultType]::ParameterName, 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`')
+            [CompletionResult]::new('--template', '--template', [CompletionResultType]::ParameterName, 'Commit template to use: the one named NAME in `[templates]`')
+            [CompletionResult]::new('--scope', '--scope', [CompletionResultType]::ParameterName, 'Only consider the files under a directory: a name from `[scopes]` or a path')
+            [CompletionResult]::new('--output', '--output', [CompletionResultType]::ParameterName, 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`')
+            [CompletionResult]::new('--json', '--json', [CompletionResultType]::ParameterName, 'Print the environment as JSON')
+            [CompletionResult]::new('--read-only', '--read-only', [CompletionResultType]::ParameterName, 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
             break
         }
-        'rona;restore' {
+        'rona;explain-status' {
             [CompletionResult]::new('-f', '-f', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
             [CompletionResult]::new('--config-file', '--config-file', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
-            [CompletionResult]::new('-i', '-i', [CompletionResultType]::ParameterName, 'Interactively pick which modified files to discard (`MultiSelect` of changed files)')
-            [CompletionResult]::new('--interactive', '--interactive', [CompletionResultType]::ParameterName, 'Interactively pick which modified files to discard (`MultiSelect` of changed files)')
-            [CompletionResult]::new('-y', '-y', [CompletionResultType]::ParameterName, 'Skip the confirmation prompt before discarding changes')
-            [CompletionResult]::new('--yes', '--yes', [CompletionResultType]::ParameterName, 'Skip the confirmation prompt before discarding changes')
-            [CompletionResult]::new('--dry-run', '--dry-run', [CompletionResultType]::ParameterName, 'Show what would be restored without actually discarding changes')
-            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
-            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('-C', '-C ', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--chdir', '--chdir', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--lang', '--lang', [CompletionResultType]::ParameterName, 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`')
+            [CompletionResult]::new('--template', '--template', [CompletionResultType]::ParameterName, 'Commit template to use: the one named NAME in `[templates]`')
+            [CompletionResult]::new('--scope', '--scope', [CompletionResultType]::ParameterName, 'Only consider the files under a directory: a name from `[scopes]` or a path')
+            [CompletionResult]::new('--output', '--output', [CompletionResultType]::ParameterName, 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`')
+            [CompletionResult]::new('--read-only', '--read-only', [CompletionResultType]::ParameterName, 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
             break
         }
-        'rona;set-editor' {
+        'rona;format-patch' {
+            [CompletionResult]::new('-n', '-n', [CompletionResultType]::ParameterName, 'Export the last N commits (default: 1 when no range is given)')
+            [CompletionResult]::new('--count', '--count', [CompletionResultType]::ParameterName, 'Export the last N commits (default: 1 when no range is given)')
+            [CompletionResult]::new('-o', '-o', [CompletionResultType]::ParameterName, 'Directory the patches are written to (default: `patches`)')
+            [CompletionResult]::new('--output-dir', '--output-dir', [CompletionResultType]::ParameterName, 'Directory the patches are written to (default: `patches`)')
             [CompletionResult]::new('-f', '-f', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
             [CompletionResult]::new('--config-file', '--config-file', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
-            [CompletionResult]::new('--dry-run', '--dry-run', [CompletionResultType]::ParameterName, 'Show what would be changed without modifying config')
-            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
-            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('-C', '-C ', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--chdir', '--chdir', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--lang', '--lang', [CompletionResultType]::ParameterName, 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`')
+            [CompletionResult]::new('--template', '--template', [CompletionResultType]::ParameterName, 'Commit template to use: the one named NAME in `[templates]`')
+            [CompletionResult]::new('--scope', '--scope', [CompletionResultType]::ParameterName, 'Only consider the files under a directory: a name from `[scopes]` or a path')
+            [CompletionResult]::new('--output', '--output', [CompletionResultType]::ParameterName, 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`')
+            [CompletionResult]::new('--cover-letter', '--cover-letter', [CompletionResultType]::ParameterName, 'Write a cover letter (`0000-cover-letter.patch`) from the cover letter template')
+            [CompletionResult]::new('--dry-run', '--dry-run', [CompletionResultType]::ParameterName, 'Show which commits would be exported without writing files')
+            [CompletionResult]::new('--read-only', '--read-only', [CompletionResultType]::ParameterName, 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
             break
         }
-        'rona;sync' {
-            [CompletionResult]::new('-b', '-b', [CompletionResultType]::ParameterName, 'Branch to sync from (default: main)')
-            [CompletionResult]::new('--branch', '--branch', [CompletionResultType]::ParameterName, 'Branch to sync from (default: main)')
-            [CompletionResult]::new('-n', '-n', [CompletionResultType]::ParameterName, 'Create a new branch before syncing')
-            [CompletionResult]::new('--new-branch', '--new-branch', [CompletionResultType]::ParameterName, 'Create a new branch before syncing')
+        'rona;fragment' {
             [CompletionResult]::new('-f', '-f', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
             [CompletionResult]::new('--config-file', '--config-file', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
-            [CompletionResult]::new('-r', '-r', [CompletionResultType]::ParameterName, 'Use rebase instead of merge')
-            [CompletionResult]::new('--rebase', '--rebase', [CompletionResultType]::ParameterName, 'Use rebase instead of merge')
-            [CompletionResult]::new('--dry-run', '--dry-run', [CompletionResultType]::ParameterName, 'Show what would be done without actually doing it')
-            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
-            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('-C', '-C ', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--chdir', '--chdir', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--lang', '--lang', [CompletionResultType]::ParameterName, 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`')
+            [CompletionResult]::new('--template', '--template', [CompletionResultType]::ParameterName, 'Commit template to use: the one named NAME in `[templates]`')
+            [CompletionResult]::new('--scope', '--scope', [CompletionResultType]::ParameterName, 'Only consider the files under a directory: a name from `[scopes]` or a path')
+            [CompletionResult]::new('--output', '--output', [CompletionResultType]::ParameterName, 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`')
+            [CompletionResult]::new('--read-only', '--read-only', [CompletionResultType]::ParameterName, 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('add', 'add', [CompletionResultType]::ParameterValue, 'Write a changelog fragment for the current branch')
+            [CompletionResult]::new('list', 'list', [CompletionResultType]::ParameterValue, 'List the fragments waiting for the next release')
+            [CompletionResult]::new('build', 'build', [CompletionResultType]::ParameterValue, 'Add the fragments to the changelog as a new release, then delete them')
+            [CompletionResult]::new('help', 'help', [CompletionResultType]::ParameterValue, 'Print this message or the help of the given subcommand(s)')
             break
         }
-        'rona;help' {
-            [CompletionResult]::new('branch', 'branch', [CompletionResultType]::ParameterValue, 'Create a new branch interactively using a branch name template')
-            [CompletionResult]::new('add-with-exclude', 'add-with-exclude', [CompletionResultType]::ParameterValue, 'Add all files to the `git add` command and exclude the patterns passed as positional arguments')
-            [CompletionResult]::new('commit', 'commit', [CompletionResultType]::ParameterValue, 'Directly commit the file with the text in `commit_message.md`')
-            [CompletionResult]::new('completion', 'completion', [CompletionResultType]::ParameterValue, 'Generate shell completions for your shell')
-            [CompletionResult]::new('config', 'config', [CompletionResultType]::ParameterValue, 'Manage configuration files (create or inspect)')
-            [CompletionResult]::new('generate', 'generate', [CompletionResultType]::ParameterValue, 'Directly generate the `commit_message.md` file')
-            [CompletionResult]::new('init', 'init', [CompletionResultType]::ParameterValue, 'Initialize the rona configuration file')
-            [CompletionResult]::new('list-status', 'list-status', [CompletionResultType]::ParameterValue, 'List files from git status (for shell completion on the -a)')
-            [CompletionResult]::new('push', 'push', [CompletionResultType]::ParameterValue, 'Push to a git repository')
-            [CompletionResult]::new('reset', 'reset', [CompletionResultType]::ParameterValue, 'Unstage files, moving them out of the staging area without losing changes')
-            [CompletionResult]::new('restore', 'restore', [CompletionResultType]::ParameterValue, 'Discard working-tree changes, restoring files to their staged or committed state')
-            [CompletionResult]::new('set-editor', 'set-editor', [CompletionResultType]::ParameterValue, 'Set the editor to use for editing the commit message')
-            [CompletionResult]::new('sync', 'sync', [CompletionResultType]::ParameterValue, 'Sync current branch with main (or another branch) by pulling and merging/rebasing')
-            [CompletionResult]::new('help', 'help', [CompletionResultType]::ParameterValue, 'Print this message or the help of the given subcommand(s)')
+        'rona;fragment;add' {
+            [CompletionResult]::new('--ticket', '--ticket', [CompletionResultType]::ParameterName, 'Ticket the change belongs to, added after the entry (e.g. `ABC-123`)')
+            [CompletionResult]::new('--package', '--package', [CompletionResultType]::ParameterName, 'Package of `[packages]` the fragment belongs to, in a monorepo')
+            [CompletionResult]::new('-f', '-f', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('--config-file', '--config-file', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('-C', '-C ', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--chdir', '--chdir', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--lang', '--lang', [CompletionResultType]::ParameterName, 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`')
+            [CompletionResult]::new('--template', '--template', [CompletionResultType]::ParameterName, 'Commit template to use: the one named NAME in `[templates]`')
+            [CompletionResult]::new('--scope', '--scope', [CompletionResultType]::ParameterName, 'Only consider the files under a directory: a name from `[scopes]` or a path')
+            [CompletionResult]::new('--output', '--output', [CompletionResultType]::ParameterName, 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`')
+            [CompletionResult]::new('--dry-run', '--dry-run', [CompletionResultType]::ParameterName, 'Show the fragment that would be written without writing it')
+            [CompletionResult]::new('--read-only', '--read-only', [CompletionResultType]::ParameterName, 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
             break
         }
-        'rona;help;branch' {
+        'rona;fragment;list' {
+            [CompletionResult]::new('--package', '--package', [CompletionResultType]::ParameterName, 'Package of `[packages]` to list the fragments of, in a monorepo')
+            [CompletionResult]::new('-f', '-f', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('--config-file', '--config-file', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('-C', '-C ', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--chdir', '--chdir', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--lang', '--lang', [CompletionResultType]::ParameterName, 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`')
+            [CompletionResult]::new('--template', '--template', [CompletionResultType]::ParameterName, 'Commit template to use: the one named NAME in `[templates]`')
+            [CompletionResult]::new('--scope', '--scope', [CompletionResultType]::ParameterName, 'Only consider the files under a directory: a name from `[scopes]` or a path')
+            [CompletionResult]::new('--output', '--output', [CompletionResultType]::ParameterName, 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`')
+            [CompletionResult]::new('--read-only', '--read-only', [CompletionResultType]::ParameterName, 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
             break
         }
-        'rona;help;add-with-exclude' {
+        'rona;fragment;build' {
+            [CompletionResult]::new('--date', '--date', [CompletionResultType]::ParameterName, 'Date of the release (defaults to today)')
+            [CompletionResult]::new('--package', '--package', [CompletionResultType]::ParameterName, 'Package of `[packages]` to build the changelog of, in a monorepo')
+            [CompletionResult]::new('-f', '-f', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('--config-file', '--config-file', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('-C', '-C ', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--chdir', '--chdir', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--lang', '--lang', [CompletionResultType]::ParameterName, 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`')
+            [CompletionResult]::new('--template', '--template', [CompletionResultType]::ParameterName, 'Commit template to use: the one named NAME in `[templates]`')
+            [CompletionResult]::new('--scope', '--scope', [CompletionResultType]::ParameterName, 'Only consider the files under a directory: a name from `[scopes]` or a path')
+            [CompletionResult]::new('--output', '--output', [CompletionResultType]::ParameterName, 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`')
+            [CompletionResult]::new('--keep', '--keep', [CompletionResultType]::ParameterName, 'Keep the fragment files')
+            [CompletionResult]::new('--dry-run', '--dry-run', [CompletionResultType]::ParameterName, 'Print the release section without changing any file')
+            [CompletionResult]::new('--read-only', '--read-only', [CompletionResultType]::ParameterName, 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
             break
         }
-        'rona;help;commit' {
+        'rona;fragment;help' {
+            [CompletionResult]::new('add', 'add', [CompletionResultType]::ParameterValue, 'Write a changelog fragment for the current branch')
+            [CompletionResult]::new('list', 'list', [CompletionResultType]::ParameterValue, 'List the fragments waiting for the next release')
+            [CompletionResult]::new('build', 'build', [CompletionResultType]::ParameterValue, 'Add the fragments to the changelog as a new release, then delete them')
+            [CompletionResult]::new('help', 'help', [CompletionResultType]::ParameterValue, 'Print this message or the help of the given subcommand(s)')
             break
         }
-        'rona;help;completion' {
+        'rona;fragment;help;add' {
             break
         }
-        'rona;help;config' {
-            [CompletionResult]::new('create', 'create', [CompletionResultType]::ParameterValue, 'Create or manage a local or global configuration file')
+        'rona;fragment;help;list' {
+            break
+        }
+        'rona;fragment;help;build' {
+            break
+        }
+        'rona;fragment;help;help' {
+            break
+        }
+        'rona;generate' {
+            [CompletionResult]::new('-f', '-f', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('--config-file', '--config-file', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('-C', '-C ', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--chdir', '--chdir', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--lang', '--lang', [CompletionResultType]::ParameterName, 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`')
+            [CompletionResult]::new('--template', '--template', [CompletionResultType]::ParameterName, 'Commit template to use: the one named NAME in `[templates]`')
+            [CompletionResult]::new('--scope', '--scope', [CompletionResultType]::ParameterName, 'Only consider the files under a directory: a name from `[scopes]` or a path')
+            [CompletionResult]::new('--output', '--output', [CompletionResultType]::ParameterName, 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`')
+            [CompletionResult]::new('--dry-run', '--dry-run', [CompletionResultType]::ParameterName, 'Show what would be generated without creating files')
+            [CompletionResult]::new('-i', '-i', [CompletionResultType]::ParameterName, 'Interactive mode - input the commit message directly in the terminal')
+            [CompletionResult]::new('--interactive', '--interactive', [CompletionResultType]::ParameterName, 'Interactive mode - input the commit message directly in the terminal')
+            [CompletionResult]::new('-n', '-n', [CompletionResultType]::ParameterName, 'No commit number')
+            [CompletionResult]::new('--no-commit-number', '--no-commit-number', [CompletionResultType]::ParameterName, 'No commit number')
+            [CompletionResult]::new('--print', '--print', [CompletionResultType]::ParameterName, 'Print the generated message to stdout instead of writing `commit_message.md` (safe to use from git hooks)')
+            [CompletionResult]::new('--stdin', '--stdin', [CompletionResultType]::ParameterName, 'Read the message from stdin and write `commit_message.md` without prompting (the commit type is inferred from the branch prefix)')
+            [CompletionResult]::new('--from-clipboard', '--from-clipboard', [CompletionResultType]::ParameterName, 'Read the message from the clipboard and write `commit_message.md` without prompting (the commit type is inferred from the branch prefix)')
+            [CompletionResult]::new('--read-only', '--read-only', [CompletionResultType]::ParameterName, 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            break
+        }
+        'rona;handoff' {
+            [CompletionResult]::new('-f', '-f', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('--config-file', '--config-file', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('-C', '-C ', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--chdir', '--chdir', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--lang', '--lang', [CompletionResultType]::ParameterName, 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`')
+            [CompletionResult]::new('--template', '--template', [CompletionResultType]::ParameterName, 'Commit template to use: the one named NAME in `[templates]`')
+            [CompletionResult]::new('--scope', '--scope', [CompletionResultType]::ParameterName, 'Only consider the files under a directory: a name from `[scopes]` or a path')
+            [CompletionResult]::new('--output', '--output', [CompletionResultType]::ParameterName, 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`')
+            [CompletionResult]::new('--read-only', '--read-only', [CompletionResultType]::ParameterName, 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('push', 'push', [CompletionResultType]::ParameterValue, 'Push the staged changes and `commit_message.md` of the current branch')
+            [CompletionResult]::new('pull', 'pull', [CompletionResultType]::ParameterValue, 'Check out a branch handed off from another clone and stage its changes')
+            [CompletionResult]::new('help', 'help', [CompletionResultType]::ParameterValue, 'Print this message or the help of the given subcommand(s)')
+            break
+        }
+        'rona;handoff;push' {
+            [CompletionResult]::new('--remote', '--remote', [CompletionResultType]::ParameterName, 'Remote to push to (default: `[handoff] remote`, else `origin`)')
+            [CompletionResult]::new('-f', '-f', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('--config-file', '--config-file', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('-C', '-C ', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--chdir', '--chdir', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--lang', '--lang', [CompletionResultType]::ParameterName, 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`')
+            [CompletionResult]::new('--template', '--template', [CompletionResultType]::ParameterName, 'Commit template to use: the one named NAME in `[templates]`')
+            [CompletionResult]::new('--scope', '--scope', [CompletionResultType]::ParameterName, 'Only consider the files under a directory: a name from `[scopes]` or a path')
+            [CompletionResult]::new('--output', '--output', [CompletionResultType]::ParameterName, 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`')
+            [CompletionResult]::new('--dry-run', '--dry-run', [CompletionResultType]::ParameterName, 'Show what would be handed off without pushing')
+            [CompletionResult]::new('--read-only', '--read-only', [CompletionResultType]::ParameterName, 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            break
+        }
+        'rona;handoff;pull' {
+            [CompletionResult]::new('--remote', '--remote', [CompletionResultType]::ParameterName, 'Remote to pull from (default: `[handoff] remote`, else `origin`)')
+            [CompletionResult]::new('-f', '-f', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('--config-file', '--config-file', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('-C', '-C ', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--chdir', '--chdir', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--lang', '--lang', [CompletionResultType]::ParameterName, 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`')
+            [CompletionResult]::new('--template', '--template', [CompletionResultType]::ParameterName, 'Commit template to use: the one named NAME in `[templates]`')
+            [CompletionResult]::new('--scope', '--scope', [CompletionResultType]::ParameterName, 'Only consider the files under a directory: a name from `[scopes]` or a path')
+            [CompletionResult]::new('--output', '--output', [CompletionResultType]::ParameterName, 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`')
+            [CompletionResult]::new('--dry-run', '--dry-run', [CompletionResultType]::ParameterName, 'Show the handed-off files without changing anything')
+            [CompletionResult]::new('--read-only', '--read-only', [CompletionResultType]::ParameterName, 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            break
+        }
+        'rona;handoff;help' {
+            [CompletionResult]::new('push', 'push', [CompletionResultType]::ParameterValue, 'Push the staged changes and `commit_message.md` of the current branch')
+            [CompletionResult]::new('pull', 'pull', [CompletionResultType]::ParameterValue, 'Check out a branch handed off from another clone and stage its changes')
+            [CompletionResult]::new('help', 'help', [CompletionResultType]::ParameterValue, 'Print this message or the help of the given subcommand(s)')
+            break
+        }
+        'rona;handoff;help;push' {
+            break
+        }
+        'rona;handoff;help;pull' {
+            break
+        }
+        'rona;handoff;help;help' {
+            break
+        }
+        'rona;health' {
+            [CompletionResult]::new('-f', '-f', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('--config-file', '--config-file', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('-C', '-C ', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--chdir', '--chdir', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--lang', '--lang', [CompletionResultType]::ParameterName, 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`')
+            [CompletionResult]::new('--template', '--template', [CompletionResultType]::ParameterName, 'Commit template to use: the one named NAME in `[templates]`')
+            [CompletionResult]::new('--scope', '--scope', [CompletionResultType]::ParameterName, 'Only consider the files under a directory: a name from `[scopes]` or a path')
+            [CompletionResult]::new('--output', '--output', [CompletionResultType]::ParameterName, 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`')
+            [CompletionResult]::new('--fix', '--fix', [CompletionResultType]::ParameterName, 'Offer to run the suggested git commands, asking before each one')
+            [CompletionResult]::new('--optimize', '--optimize', [CompletionResultType]::ParameterName, 'Write a commit-graph (with changed-path filters) and keep it updated on fetch, which speeds up commit counting and history walks')
+            [CompletionResult]::new('--read-only', '--read-only', [CompletionResultType]::ParameterName, 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            break
+        }
+        'rona;history' {
+            [CompletionResult]::new('-f', '-f', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('--config-file', '--config-file', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('-C', '-C ', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--chdir', '--chdir', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--lang', '--lang', [CompletionResultType]::ParameterName, 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`')
+            [CompletionResult]::new('--template', '--template', [CompletionResultType]::ParameterName, 'Commit template to use: the one named NAME in `[templates]`')
+            [CompletionResult]::new('--scope', '--scope', [CompletionResultType]::ParameterName, 'Only consider the files under a directory: a name from `[scopes]` or a path')
+            [CompletionResult]::new('--output', '--output', [CompletionResultType]::ParameterName, 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`')
+            [CompletionResult]::new('--read-only', '--read-only', [CompletionResultType]::ParameterName, 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('ops', 'ops', [CompletionResultType]::ParameterValue, 'List the state-changing operations rona performed, newest first')
+            [CompletionResult]::new('help', 'help', [CompletionResultType]::ParameterValue, 'Print this message or the help of the given subcommand(s)')
+            break
+        }
+        'rona;history;ops' {
+            [CompletionResult]::new('-n', '-n', [CompletionResultType]::ParameterName, 'Number of operations to show')
+            [CompletionResult]::new('--limit', '--limit', [CompletionResultType]::ParameterName, 'Number of operations to show')
+            [CompletionResult]::new('-f', '-f', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('--config-file', '--config-file', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('-C', '-C ', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--chdir', '--chdir', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--lang', '--lang', [CompletionResultType]::ParameterName, 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`')
+            [CompletionResult]::new('--template', '--template', [CompletionResultType]::ParameterName, 'Commit template to use: the one named NAME in `[templates]`')
+            [CompletionResult]::new('--scope', '--scope', [CompletionResultType]::ParameterName, 'Only consider the files under a directory: a name from `[scopes]` or a path')
+            [CompletionResult]::new('--output', '--output', [CompletionResultType]::ParameterName, 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`')
+            [CompletionResult]::new('--json', '--json', [CompletionResultType]::ParameterName, 'Print the operations as JSON lines')
+            [CompletionResult]::new('--read-only', '--read-only', [CompletionResultType]::ParameterName, 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            break
+        }
+        'rona;history;help' {
+            [CompletionResult]::new('ops', 'ops', [CompletionResultType]::ParameterValue, 'List the state-changing operations rona performed, newest first')
+            [CompletionResult]::new('help', 'help', [CompletionResultType]::ParameterValue, 'Print this message or the help of the given subcommand(s)')
+            break
+        }
+        'rona;history;help;ops' {
+            break
+        }
+        'rona;history;help;help' {
+            break
+        }
+        'rona;hooks' {
+            [CompletionResult]::new('-f', '-f', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('--config-file', '--config-file', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('-C', '-C ', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--chdir', '--chdir', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--lang', '--lang', [CompletionResultType]::ParameterName, 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`')
+            [CompletionResult]::new('--template', '--template', [CompletionResultType]::ParameterName, 'Commit template to use: the one named NAME in `[templates]`')
+            [CompletionResult]::new('--scope', '--scope', [CompletionResultType]::ParameterName, 'Only consider the files under a directory: a name from `[scopes]` or a path')
+            [CompletionResult]::new('--output', '--output', [CompletionResultType]::ParameterName, 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`')
+            [CompletionResult]::new('--read-only', '--read-only', [CompletionResultType]::ParameterName, 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('install', 'install', [CompletionResultType]::ParameterValue, 'Write managed hook scripts that call back into rona')
+            [CompletionResult]::new('uninstall', 'uninstall', [CompletionResultType]::ParameterValue, 'Remove the hook scripts rona installed, leaving other hooks alone')
+            [CompletionResult]::new('list', 'list', [CompletionResultType]::ParameterValue, 'Show which hooks are installed and whether rona manages them')
+            [CompletionResult]::new('run', 'run', [CompletionResultType]::ParameterValue, 'Run a hook''s checks; called by the installed hook scripts')
+            [CompletionResult]::new('help', 'help', [CompletionResultType]::ParameterValue, 'Print this message or the help of the given subcommand(s)')
+            break
+        }
+        'rona;hooks;install' {
+            [CompletionResult]::new('-f', '-f', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('--config-file', '--config-file', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('-C', '-C ', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--chdir', '--chdir', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--lang', '--lang', [CompletionResultType]::ParameterName, 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`')
+            [CompletionResult]::new('--template', '--template', [CompletionResultType]::ParameterName, 'Commit template to use: the one named NAME in `[templates]`')
+            [CompletionResult]::new('--scope', '--scope', [CompletionResultType]::ParameterName, 'Only consider the files under a directory: a name from `[scopes]` or a path')
+            [CompletionResult]::new('--output', '--output', [CompletionResultType]::ParameterName, 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`')
+            [CompletionResult]::new('--dry-run', '--dry-run', [CompletionResultType]::ParameterName, 'Show what would be installed without writing anything')
+            [CompletionResult]::new('--read-only', '--read-only', [CompletionResultType]::ParameterName, 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            break
+        }
+        'rona;hooks;uninstall' {
+            [CompletionResult]::new('-f', '-f', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('--config-file', '--config-file', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('-C', '-C ', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--chdir', '--chdir', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--lang', '--lang', [CompletionResultType]::ParameterName, 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`')
+            [CompletionResult]::new('--template', '--template', [CompletionResultType]::ParameterName, 'Commit template to use: the one named NAME in `[templates]`')
+            [CompletionResult]::new('--scope', '--scope', [CompletionResultType]::ParameterName, 'Only consider the files under a directory: a name from `[scopes]` or a path')
+            [CompletionResult]::new('--output', '--output', [CompletionResultType]::ParameterName, 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`')
+            [CompletionResult]::new('--dry-run', '--dry-run', [CompletionResultType]::ParameterName, 'Show what would be removed without removing anything')
+            [CompletionResult]::new('--read-only', '--read-only', [CompletionResultType]::ParameterName, 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            break
+        }
+        'rona;hooks;list' {
+            [CompletionResult]::new('-f', '-f', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('--config-file', '--config-file', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('-C', '-C ', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--chdir', '--chdir', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--lang', '--lang', [CompletionResultType]::ParameterName, 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`')
+            [CompletionResult]::new('--template', '--template', [CompletionResultType]::ParameterName, 'Commit template to use: the one named NAME in `[templates]`')
+            [CompletionResult]::new('--scope', '--scope', [CompletionResultType]::ParameterName, 'Only consider the files under a directory: a name from `[scopes]` or a path')
+            [CompletionResult]::new('--output', '--output', [CompletionResultType]::ParameterName, 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`')
+            [CompletionResult]::new('--read-only', '--read-only', [CompletionResultType]::ParameterName, 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            break
+        }
+        'rona;hooks;run' {
+            [CompletionResult]::new('-f', '-f', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('--config-file', '--config-file', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('-C', '-C ', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--chdir', '--chdir', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--lang', '--lang', [CompletionResultType]::ParameterName, 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`')
+            [CompletionResult]::new('--template', '--template', [CompletionResultType]::ParameterName, 'Commit template to use: the one named NAME in `[templates]`')
+            [CompletionResult]::new('--scope', '--scope', [CompletionResultType]::ParameterName, 'Only consider the files under a directory: a name from `[scopes]` or a path')
+            [CompletionResult]::new('--output', '--output', [CompletionResultType]::ParameterName, 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`')
+            [CompletionResult]::new('--read-only', '--read-only', [CompletionResultType]::ParameterName, 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            break
+        }
+        'rona;hooks;help' {
+            [CompletionResult]::new('install', 'install', [CompletionResultType]::ParameterValue, 'Write managed hook scripts that call back into rona')
+            [CompletionResult]::new('uninstall', 'uninstall', [CompletionResultType]::ParameterValue, 'Remove the hook scripts rona installed, leaving other hooks alone')
+            [CompletionResult]::new('list', 'list', [CompletionResultType]::ParameterValue, 'Show which hooks are installed and whether rona manages them')
+            [CompletionResult]::new('run', 'run', [CompletionResultType]::ParameterValue, 'Run a hook''s checks; called by the installed hook scripts')
+            [CompletionResult]::new('help', 'help', [CompletionResultType]::ParameterValue, 'Print this message or the help of the given subcommand(s)')
+            break
+        }
+        'rona;hooks;help;install' {
+            break
+        }
+        'rona;hooks;help;uninstall' {
+            break
+        }
+        'rona;hooks;help;list' {
+            break
+        }
+        'rona;hooks;help;run' {
+            break
+        }
+        'rona;hooks;help;help' {
+            break
+        }
+        'rona;init' {
+            [CompletionResult]::new('-f', '-f', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('--config-file', '--config-file', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('-C', '-C ', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--chdir', '--chdir', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--lang', '--lang', [CompletionResultType]::ParameterName, 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`')
+            [CompletionResult]::new('--template', '--template', [CompletionResultType]::ParameterName, 'Commit template to use: the one named NAME in `[templates]`')
+            [CompletionResult]::new('--scope', '--scope', [CompletionResultType]::ParameterName, 'Only consider the files under a directory: a name from `[scopes]` or a path')
+            [CompletionResult]::new('--output', '--output', [CompletionResultType]::ParameterName, 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`')
+            [CompletionResult]::new('--dry-run', '--dry-run', [CompletionResultType]::ParameterName, 'Show what would be initialized without creating files')
+            [CompletionResult]::new('--repo', '--repo', [CompletionResultType]::ParameterName, 'Prepare the current repository instead of creating a config file: create `commit_message.md` and `.commitignore`, with the patterns of the `preset` list (or of the ecosystems detected at the root when it is empty)')
+            [CompletionResult]::new('--read-only', '--read-only', [CompletionResultType]::ParameterName, 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            break
+        }
+        'rona;list-status' {
+            [CompletionResult]::new('--filter', '--filter', [CompletionResultType]::ParameterName, 'Only list files whose path fuzzy-matches this query, best matches first')
+            [CompletionResult]::new('-f', '-f', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('--config-file', '--config-file', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('-C', '-C ', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--chdir', '--chdir', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--lang', '--lang', [CompletionResultType]::ParameterName, 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`')
+            [CompletionResult]::new('--template', '--template', [CompletionResultType]::ParameterName, 'Commit template to use: the one named NAME in `[templates]`')
+            [CompletionResult]::new('--scope', '--scope', [CompletionResultType]::ParameterName, 'Only consider the files under a directory: a name from `[scopes]` or a path')
+            [CompletionResult]::new('--output', '--output', [CompletionResultType]::ParameterName, 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`')
+            [CompletionResult]::new('--read-only', '--read-only', [CompletionResultType]::ParameterName, 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            break
+        }
+        'rona;prepare-msg' {
+            [CompletionResult]::new('-f', '-f', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('--config-file', '--config-file', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('-C', '-C ', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--chdir', '--chdir', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--lang', '--lang', [CompletionResultType]::ParameterName, 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`')
+            [CompletionResult]::new('--template', '--template', [CompletionResultType]::ParameterName, 'Commit template to use: the one named NAME in `[templates]`')
+            [CompletionResult]::new('--scope', '--scope', [CompletionResultType]::ParameterName, 'Only consider the files under a directory: a name from `[scopes]` or a path')
+            [CompletionResult]::new('--output', '--output', [CompletionResultType]::ParameterName, 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`')
+            [CompletionResult]::new('--dry-run', '--dry-run', [CompletionResultType]::ParameterName, 'Show the message that would be written without changing the file')
+            [CompletionResult]::new('--read-only', '--read-only', [CompletionResultType]::ParameterName, 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            break
+        }
+        'rona;prune' {
+            [CompletionResult]::new('--max-age', '--max-age', [CompletionResultType]::ParameterName, 'Remove rona state files (under `.git/rona/`) older than this (units: s, m, h, d)')
+            [CompletionResult]::new('-f', '-f', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('--config-file', '--config-file', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('-C', '-C ', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--chdir', '--chdir', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--lang', '--lang', [CompletionResultType]::ParameterName, 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`')
+            [CompletionResult]::new('--template', '--template', [CompletionResultType]::ParameterName, 'Commit template to use: the one named NAME in `[templates]`')
+            [CompletionResult]::new('--scope', '--scope', [CompletionResultType]::ParameterName, 'Only consider the files under a directory: a name from `[scopes]` or a path')
+            [CompletionResult]::new('--output', '--output', [CompletionResultType]::ParameterName, 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`')
+            [CompletionResult]::new('--dry-run', '--dry-run', [CompletionResultType]::ParameterName, 'Show what would be removed without removing anything')
+            [CompletionResult]::new('--read-only', '--read-only', [CompletionResultType]::ParameterName, 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            break
+        }
+        'rona;push' {
+            [CompletionResult]::new('--target', '--target', [CompletionResultType]::ParameterName, 'Branch the review is for, the `{target}` of the review ref (default: the default branch)')
+            [CompletionResult]::new('-f', '-f', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('--config-file', '--config-file', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('-C', '-C ', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--chdir', '--chdir', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--lang', '--lang', [CompletionResultType]::ParameterName, 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`')
+            [CompletionResult]::new('--template', '--template', [CompletionResultType]::ParameterName, 'Commit template to use: the one named NAME in `[templates]`')
+            [CompletionResult]::new('--scope', '--scope', [CompletionResultType]::ParameterName, 'Only consider the files under a directory: a name from `[scopes]` or a path')
+            [CompletionResult]::new('--output', '--output', [CompletionResultType]::ParameterName, 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`')
+            [CompletionResult]::new('--dry-run', '--dry-run', [CompletionResultType]::ParameterName, 'Show what would be pushed without actually pushing')
+            [CompletionResult]::new('--review', '--review', [CompletionResultType]::ParameterName, 'Push HEAD to the `[review] ref` (default `refs/for/<target>`) instead of the branch')
+            [CompletionResult]::new('--read-only', '--read-only', [CompletionResultType]::ParameterName, 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            break
+        }
+        'rona;release' {
+            [CompletionResult]::new('--package', '--package', [CompletionResultType]::ParameterName, 'Release a package of `[packages]`, with its own tags and changelog')
+            [CompletionResult]::new('-f', '-f', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('--config-file', '--config-file', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('-C', '-C ', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--chdir', '--chdir', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--lang', '--lang', [CompletionResultType]::ParameterName, 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`')
+            [CompletionResult]::new('--template', '--template', [CompletionResultType]::ParameterName, 'Commit template to use: the one named NAME in `[templates]`')
+            [CompletionResult]::new('--scope', '--scope', [CompletionResultType]::ParameterName, 'Only consider the files under a directory: a name from `[scopes]` or a path')
+            [CompletionResult]::new('--output', '--output', [CompletionResultType]::ParameterName, 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`')
+            [CompletionResult]::new('--major', '--major', [CompletionResultType]::ParameterName, 'Bump the major version')
+            [CompletionResult]::new('--minor', '--minor', [CompletionResultType]::ParameterName, 'Bump the minor version')
+            [CompletionResult]::new('--patch', '--patch', [CompletionResultType]::ParameterName, 'Bump the patch version (the default)')
+            [CompletionResult]::new('--alpha', '--alpha', [CompletionResultType]::ParameterName, 'Release an alpha prerelease (e.g. `1.3.0-alpha.1`)')
+            [CompletionResult]::new('--beta', '--beta', [CompletionResultType]::ParameterName, 'Release a beta prerelease (e.g. `1.3.0-beta.1`)')
+            [CompletionResult]::new('--rc', '--rc', [CompletionResultType]::ParameterName, 'Release a release candidate (e.g. `1.3.0-rc.1`)')
+            [CompletionResult]::new('--dry-run', '--dry-run', [CompletionResultType]::ParameterName, 'Show the version, the tag and the changelog section without changing anything')
+            [CompletionResult]::new('--read-only', '--read-only', [CompletionResultType]::ParameterName, 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            break
+        }
+        'rona;reset' {
+            [CompletionResult]::new('-f', '-f', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('--config-file', '--config-file', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('-C', '-C ', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--chdir', '--chdir', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--lang', '--lang', [CompletionResultType]::ParameterName, 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`')
+            [CompletionResult]::new('--template', '--template', [CompletionResultType]::ParameterName, 'Commit template to use: the one named NAME in `[templates]`')
+            [CompletionResult]::new('--scope', '--scope', [CompletionResultType]::ParameterName, 'Only consider the files under a directory: a name from `[scopes]` or a path')
+            [CompletionResult]::new('--output', '--output', [CompletionResultType]::ParameterName, 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`')
+            [CompletionResult]::new('-i', '-i', [CompletionResultType]::ParameterName, 'Interactively pick which staged files to unstage (`MultiSelect` of staged files)')
+            [CompletionResult]::new('--interactive', '--interactive', [CompletionResultType]::ParameterName, 'Interactively pick which staged files to unstage (`MultiSelect` of staged files)')
+            [CompletionResult]::new('--dry-run', '--dry-run', [CompletionResultType]::ParameterName, 'Show what would be unstaged without actually unstaging files')
+            [CompletionResult]::new('--read-only', '--read-only', [CompletionResultType]::ParameterName, 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            break
+        }
+        'rona;restore' {
+            [CompletionResult]::new('-f', '-f', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('--config-file', '--config-file', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('-C', '-C ', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--chdir', '--chdir', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--lang', '--lang', [CompletionResultType]::ParameterName, 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`')
+            [CompletionResult]::new('--template', '--template', [CompletionResultType]::ParameterName, 'Commit template to use: the one named NAME in `[templates]`')
+            [CompletionResult]::new('--scope', '--scope', [CompletionResultType]::ParameterName, 'Only consider the files under a directory: a name from `[scopes]` or a path')
+            [CompletionResult]::new('--output', '--output', [CompletionResultType]::ParameterName, 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`')
+            [CompletionResult]::new('-i', '-i', [CompletionResultType]::ParameterName, 'Interactively pick which modified files to discard (`MultiSelect` of changed files)')
+            [CompletionResult]::new('--interactive', '--interactive', [CompletionResultType]::ParameterName, 'Interactively pick which modified files to discard (`MultiSelect` of changed files)')
+            [CompletionResult]::new('-y', '-y', [CompletionResultType]::ParameterName, 'Skip the confirmation prompt before discarding changes')
+            [CompletionResult]::new('--yes', '--yes', [CompletionResultType]::ParameterName, 'Skip the confirmation prompt before discarding changes')
+            [CompletionResult]::new('--dry-run', '--dry-run', [CompletionResultType]::ParameterName, 'Show what would be restored without actually discarding changes')
+            [CompletionResult]::new('--read-only', '--read-only', [CompletionResultType]::ParameterName, 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            break
+        }
+        'rona;set-editor' {
+            [CompletionResult]::new('-f', '-f', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('--config-file', '--config-file', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('-C', '-C ', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--chdir', '--chdir', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--lang', '--lang', [CompletionResultType]::ParameterName, 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`')
+            [CompletionResult]::new('--template', '--template', [CompletionResultType]::ParameterName, 'Commit template to use: the one named NAME in `[templates]`')
+            [CompletionResult]::new('--scope', '--scope', [CompletionResultType]::ParameterName, 'Only consider the files under a directory: a name from `[scopes]` or a path')
+            [CompletionResult]::new('--output', '--output', [CompletionResultType]::ParameterName, 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`')
+            [CompletionResult]::new('--dry-run', '--dry-run', [CompletionResultType]::ParameterName, 'Show what would be changed without modifying config')
+            [CompletionResult]::new('--read-only', '--read-only', [CompletionResultType]::ParameterName, 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            break
+        }
+        'rona;schema' {
+            [CompletionResult]::new('-f', '-f', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('--config-file', '--config-file', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('-C', '-C ', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--chdir', '--chdir', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--lang', '--lang', [CompletionResultType]::ParameterName, 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`')
+            [CompletionResult]::new('--template', '--template', [CompletionResultType]::ParameterName, 'Commit template to use: the one named NAME in `[templates]`')
+            [CompletionResult]::new('--scope', '--scope', [CompletionResultType]::ParameterName, 'Only consider the files under a directory: a name from `[scopes]` or a path')
+            [CompletionResult]::new('--output', '--output', [CompletionResultType]::ParameterName, 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`')
+            [CompletionResult]::new('--read-only', '--read-only', [CompletionResultType]::ParameterName, 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            break
+        }
+        'rona;show' {
+            [CompletionResult]::new('-f', '-f', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('--config-file', '--config-file', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('-C', '-C ', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--chdir', '--chdir', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--lang', '--lang', [CompletionResultType]::ParameterName, 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`')
+            [CompletionResult]::new('--template', '--template', [CompletionResultType]::ParameterName, 'Commit template to use: the one named NAME in `[templates]`')
+            [CompletionResult]::new('--scope', '--scope', [CompletionResultType]::ParameterName, 'Only consider the files under a directory: a name from `[scopes]` or a path')
+            [CompletionResult]::new('--output', '--output', [CompletionResultType]::ParameterName, 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`')
+            [CompletionResult]::new('--json', '--json', [CompletionResultType]::ParameterName, 'Print the commit as JSON')
+            [CompletionResult]::new('--read-only', '--read-only', [CompletionResultType]::ParameterName, 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            break
+        }
+        'rona;snippet' {
+            [CompletionResult]::new('-f', '-f', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('--config-file', '--config-file', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('-C', '-C ', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--chdir', '--chdir', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--lang', '--lang', [CompletionResultType]::ParameterName, 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`')
+            [CompletionResult]::new('--template', '--template', [CompletionResultType]::ParameterName, 'Commit template to use: the one named NAME in `[templates]`')
+            [CompletionResult]::new('--scope', '--scope', [CompletionResultType]::ParameterName, 'Only consider the files under a directory: a name from `[scopes]` or a path')
+            [CompletionResult]::new('--output', '--output', [CompletionResultType]::ParameterName, 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`')
+            [CompletionResult]::new('--read-only', '--read-only', [CompletionResultType]::ParameterName, 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('list', 'list', [CompletionResultType]::ParameterValue, 'List the configured snippets')
+            [CompletionResult]::new('insert', 'insert', [CompletionResultType]::ParameterValue, 'Append a rendered snippet to the commit message file')
+            [CompletionResult]::new('help', 'help', [CompletionResultType]::ParameterValue, 'Print this message or the help of the given subcommand(s)')
+            break
+        }
+        'rona;snippet;list' {
+            [CompletionResult]::new('-f', '-f', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('--config-file', '--config-file', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('-C', '-C ', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--chdir', '--chdir', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--lang', '--lang', [CompletionResultType]::ParameterName, 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`')
+            [CompletionResult]::new('--template', '--template', [CompletionResultType]::ParameterName, 'Commit template to use: the one named NAME in `[templates]`')
+            [CompletionResult]::new('--scope', '--scope', [CompletionResultType]::ParameterName, 'Only consider the files under a directory: a name from `[scopes]` or a path')
+            [CompletionResult]::new('--output', '--output', [CompletionResultType]::ParameterName, 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`')
+            [CompletionResult]::new('--read-only', '--read-only', [CompletionResultType]::ParameterName, 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            break
+        }
+        'rona;snippet;insert' {
+            [CompletionResult]::new('--file', '--file', [CompletionResultType]::ParameterName, 'Message file to insert into (defaults to `commit_message.md`)')
+            [CompletionResult]::new('-f', '-f', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('--config-file', '--config-file', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('-C', '-C ', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--chdir', '--chdir', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--lang', '--lang', [CompletionResultType]::ParameterName, 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`')
+            [CompletionResult]::new('--template', '--template', [CompletionResultType]::ParameterName, 'Commit template to use: the one named NAME in `[templates]`')
+            [CompletionResult]::new('--scope', '--scope', [CompletionResultType]::ParameterName, 'Only consider the files under a directory: a name from `[scopes]` or a path')
+            [CompletionResult]::new('--output', '--output', [CompletionResultType]::ParameterName, 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`')
+            [CompletionResult]::new('--dry-run', '--dry-run', [CompletionResultType]::ParameterName, 'Print the rendered snippet without changing the file')
+            [CompletionResult]::new('--read-only', '--read-only', [CompletionResultType]::ParameterName, 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            break
+        }
+        'rona;snippet;help' {
+            [CompletionResult]::new('list', 'list', [CompletionResultType]::ParameterValue, 'List the configured snippets')
+            [CompletionResult]::new('insert', 'insert', [CompletionResultType]::ParameterValue, 'Append a rendered snippet to the commit message file')
+            [CompletionResult]::new('help', 'help', [CompletionResultType]::ParameterValue, 'Print this message or the help of the given subcommand(s)')
+            break
+        }
+        'rona;snippet;help;list' {
+            break
+        }
+        'rona;snippet;help;insert' {
+            break
+        }
+        'rona;snippet;help;help' {
+            break
+        }
+        'rona;stats' {
+            [CompletionResult]::new('--export', '--export', [CompletionResultType]::ParameterName, 'Print the metrics in this format instead of a table')
+            [CompletionResult]::new('-f', '-f', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('--config-file', '--config-file', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('-C', '-C ', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--chdir', '--chdir', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--lang', '--lang', [CompletionResultType]::ParameterName, 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`')
+            [CompletionResult]::new('--template', '--template', [CompletionResultType]::ParameterName, 'Commit template to use: the one named NAME in `[templates]`')
+            [CompletionResult]::new('--scope', '--scope', [CompletionResultType]::ParameterName, 'Only consider the files under a directory: a name from `[scopes]` or a path')
+            [CompletionResult]::new('--output', '--output', [CompletionResultType]::ParameterName, 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`')
+            [CompletionResult]::new('--read-only', '--read-only', [CompletionResultType]::ParameterName, 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            break
+        }
+        'rona;status' {
+            [CompletionResult]::new('-f', '-f', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('--config-file', '--config-file', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('-C', '-C ', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--chdir', '--chdir', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--lang', '--lang', [CompletionResultType]::ParameterName, 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`')
+            [CompletionResult]::new('--template', '--template', [CompletionResultType]::ParameterName, 'Commit template to use: the one named NAME in `[templates]`')
+            [CompletionResult]::new('--scope', '--scope', [CompletionResultType]::ParameterName, 'Only consider the files under a directory: a name from `[scopes]` or a path')
+            [CompletionResult]::new('--output', '--output', [CompletionResultType]::ParameterName, 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`')
+            [CompletionResult]::new('--read-only', '--read-only', [CompletionResultType]::ParameterName, 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            break
+        }
+        'rona;sync' {
+            [CompletionResult]::new('-b', '-b', [CompletionResultType]::ParameterName, 'Branch to sync from (default: the default branch, from `origin/HEAD`)')
+            [CompletionResult]::new('--branch', '--branch', [CompletionResultType]::ParameterName, 'Branch to sync from (default: the default branch, from `origin/HEAD`)')
+            [CompletionResult]::new('-n', '-n', [CompletionResultType]::ParameterName, 'Create a new branch before syncing')
+            [CompletionResult]::new('--new-branch', '--new-branch', [CompletionResultType]::ParameterName, 'Create a new branch before syncing')
+            [CompletionResult]::new('-f', '-f', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('--config-file', '--config-file', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('-C', '-C ', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--chdir', '--chdir', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--lang', '--lang', [CompletionResultType]::ParameterName, 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`')
+            [CompletionResult]::new('--template', '--template', [CompletionResultType]::ParameterName, 'Commit template to use: the one named NAME in `[templates]`')
+            [CompletionResult]::new('--scope', '--scope', [CompletionResultType]::ParameterName, 'Only consider the files under a directory: a name from `[scopes]` or a path')
+            [CompletionResult]::new('--output', '--output', [CompletionResultType]::ParameterName, 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`')
+            [CompletionResult]::new('-r', '-r', [CompletionResultType]::ParameterName, 'Use rebase instead of merge')
+            [CompletionResult]::new('--rebase', '--rebase', [CompletionResultType]::ParameterName, 'Use rebase instead of merge')
+            [CompletionResult]::new('--dry-run', '--dry-run', [CompletionResultType]::ParameterName, 'Show what would be done without actually doing it')
+            [CompletionResult]::new('--read-only', '--read-only', [CompletionResultType]::ParameterName, 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            break
+        }
+        'rona;template' {
+            [CompletionResult]::new('-f', '-f', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('--config-file', '--config-file', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('-C', '-C ', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--chdir', '--chdir', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--lang', '--lang', [CompletionResultType]::ParameterName, 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`')
+            [CompletionResult]::new('--template', '--template', [CompletionResultType]::ParameterName, 'Commit template to use: the one named NAME in `[templates]`')
+            [CompletionResult]::new('--scope', '--scope', [CompletionResultType]::ParameterName, 'Only consider the files under a directory: a name from `[scopes]` or a path')
+            [CompletionResult]::new('--output', '--output', [CompletionResultType]::ParameterName, 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`')
+            [CompletionResult]::new('--read-only', '--read-only', [CompletionResultType]::ParameterName, 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('check', 'check', [CompletionResultType]::ParameterValue, 'Lint the configured templates for unknown variables and malformed blocks')
+            [CompletionResult]::new('help', 'help', [CompletionResultType]::ParameterValue, 'Print this message or the help of the given subcommand(s)')
+            break
+        }
+        'rona;template;check' {
+            [CompletionResult]::new('-f', '-f', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('--config-file', '--config-file', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('-C', '-C ', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--chdir', '--chdir', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--lang', '--lang', [CompletionResultType]::ParameterName, 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`')
+            [CompletionResult]::new('--template', '--template', [CompletionResultType]::ParameterName, 'Commit template to use: the one named NAME in `[templates]`')
+            [CompletionResult]::new('--scope', '--scope', [CompletionResultType]::ParameterName, 'Only consider the files under a directory: a name from `[scopes]` or a path')
+            [CompletionResult]::new('--output', '--output', [CompletionResultType]::ParameterName, 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`')
+            [CompletionResult]::new('--read-only', '--read-only', [CompletionResultType]::ParameterName, 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            break
+        }
+        'rona;template;help' {
+            [CompletionResult]::new('check', 'check', [CompletionResultType]::ParameterValue, 'Lint the configured templates for unknown variables and malformed blocks')
+            [CompletionResult]::new('help', 'help', [CompletionResultType]::ParameterValue, 'Print this message or the help of the given subcommand(s)')
+            break
+        }
+        'rona;template;help;check' {
+            break
+        }
+        'rona;template;help;help' {
+            break
+        }
+        'rona;todo' {
+            [CompletionResult]::new('-f', '-f', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('--config-file', '--config-file', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('-C', '-C ', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--chdir', '--chdir', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--lang', '--lang', [CompletionResultType]::ParameterName, 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`')
+            [CompletionResult]::new('--template', '--template', [CompletionResultType]::ParameterName, 'Commit template to use: the one named NAME in `[templates]`')
+            [CompletionResult]::new('--scope', '--scope', [CompletionResultType]::ParameterName, 'Only consider the files under a directory: a name from `[scopes]` or a path')
+            [CompletionResult]::new('--output', '--output', [CompletionResultType]::ParameterName, 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`')
+            [CompletionResult]::new('--check', '--check', [CompletionResultType]::ParameterName, 'Exit with an error when markers are found (for hooks and CI)')
+            [CompletionResult]::new('--read-only', '--read-only', [CompletionResultType]::ParameterName, 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            break
+        }
+        'rona;tour' {
+            [CompletionResult]::new('-f', '-f', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('--config-file', '--config-file', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('-C', '-C ', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--chdir', '--chdir', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--lang', '--lang', [CompletionResultType]::ParameterName, 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`')
+            [CompletionResult]::new('--template', '--template', [CompletionResultType]::ParameterName, 'Commit template to use: the one named NAME in `[templates]`')
+            [CompletionResult]::new('--scope', '--scope', [CompletionResultType]::ParameterName, 'Only consider the files under a directory: a name from `[scopes]` or a path')
+            [CompletionResult]::new('--output', '--output', [CompletionResultType]::ParameterName, 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`')
+            [CompletionResult]::new('-y', '-y', [CompletionResultType]::ParameterName, 'Run every step without pausing')
+            [CompletionResult]::new('--yes', '--yes', [CompletionResultType]::ParameterName, 'Run every step without pausing')
+            [CompletionResult]::new('--keep', '--keep', [CompletionResultType]::ParameterName, 'Keep the demo repository after the tour')
+            [CompletionResult]::new('--read-only', '--read-only', [CompletionResultType]::ParameterName, 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            break
+        }
+        'rona;usage' {
+            [CompletionResult]::new('-f', '-f', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('--config-file', '--config-file', [CompletionResultType]::ParameterName, 'Config file to use instead of the default global/project hierarchy')
+            [CompletionResult]::new('-C', '-C ', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--chdir', '--chdir', [CompletionResultType]::ParameterName, 'Run as if rona was started in PATH instead of the current directory (like `git -C`)')
+            [CompletionResult]::new('--lang', '--lang', [CompletionResultType]::ParameterName, 'Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`')
+            [CompletionResult]::new('--template', '--template', [CompletionResultType]::ParameterName, 'Commit template to use: the one named NAME in `[templates]`')
+            [CompletionResult]::new('--scope', '--scope', [CompletionResultType]::ParameterName, 'Only consider the files under a directory: a name from `[scopes]` or a path')
+            [CompletionResult]::new('--output', '--output', [CompletionResultType]::ParameterName, 'Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`')
+            [CompletionResult]::new('--json', '--json', [CompletionResultType]::ParameterName, 'Print the report as JSON')
+            [CompletionResult]::new('--read-only', '--read-only', [CompletionResultType]::ParameterName, 'Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help (see more with ''--help'')')
+            break
+        }
+        'rona;help' {
+            [CompletionResult]::new('am', 'am', [CompletionResultType]::ParameterValue, 'Apply mailbox patches, with a three-way fallback for patches that do not apply')
+            [CompletionResult]::new('audit', 'audit', [CompletionResultType]::ParameterValue, 'Report which existing commits follow the commit template and lint rules')
+            [CompletionResult]::new('backport', 'backport', [CompletionResultType]::ParameterValue, 'Cherry-pick a commit onto release branches with a re-templated message')
+            [CompletionResult]::new('branch', 'branch', [CompletionResultType]::ParameterValue, 'Create a new branch interactively using a branch name template')
+            [CompletionResult]::new('browse', 'browse', [CompletionResultType]::ParameterValue, 'Browse recent commits: filter by typing, then view, copy, fix up or revert one')
+            [CompletionResult]::new('bundle', 'bundle', [CompletionResultType]::ParameterValue, 'Carry branches to a clone without a shared remote in `git bundle` files')
+            [CompletionResult]::new('add-with-exclude', 'add-with-exclude', [CompletionResultType]::ParameterValue, 'Add all files to the `git add` command and exclude the patterns passed as positional arguments')
+            [CompletionResult]::new('commit', 'commit', [CompletionResultType]::ParameterValue, 'Directly commit the file with the text in `commit_message.md`')
+            [CompletionResult]::new('completion', 'completion', [CompletionResultType]::ParameterValue, 'Generate shell completions for your shell')
+            [CompletionResult]::new('config', 'config', [CompletionResultType]::ParameterValue, 'Manage configuration files (create or inspect)')
+            [CompletionResult]::new('copy', 'copy', [CompletionResultType]::ParameterValue, 'Copy a reference to a commit to the clipboard, e.g. `abc1234 ("fix: handle empty index")`, for pull request descriptions and review comments')
+            [CompletionResult]::new('daemon', 'daemon', [CompletionResultType]::ParameterValue, 'Keep the changed files warm for `rona -l`, served over a unix socket')
+            [CompletionResult]::new('check-msg', 'check-msg', [CompletionResultType]::ParameterValue, 'Check a commit message file against the commit template')
+            [CompletionResult]::new('env', 'env', [CompletionResultType]::ParameterValue, 'Show what rona resolved here: repository, config files, template, editor, signing, guards and scope. Paste it in bug reports')
+            [CompletionResult]::new('explain-status', 'explain-status', [CompletionResultType]::ParameterValue, 'Show git status with plain-language explanations of each state and the rona commands that act on it')
+            [CompletionResult]::new('format-patch', 'format-patch', [CompletionResultType]::ParameterValue, 'Export commits as mbox patch files for mailing-list review')
+            [CompletionResult]::new('fragment', 'fragment', [CompletionResultType]::ParameterValue, 'Write changelog fragments and assemble them into the changelog at release time')
+            [CompletionResult]::new('generate', 'generate', [CompletionResultType]::ParameterValue, 'Directly generate the `commit_message.md` file')
+            [CompletionResult]::new('handoff', 'handoff', [CompletionResultType]::ParameterValue, 'Continue a half-finished commit in another clone: push the staged changes and `commit_message.md` of a branch to a ref, then pull them on the other machine')
+            [CompletionResult]::new('health', 'health', [CompletionResultType]::ParameterValue, 'Check the repository for signs of missing maintenance (loose objects, missing commit-graph, large files in history, broken refs)')
+            [CompletionResult]::new('history', 'history', [CompletionResultType]::ParameterValue, 'Review what rona did in this repository (recorded in `.git/rona/oplog.jsonl`)')
+            [CompletionResult]::new('hooks', 'hooks', [CompletionResultType]::ParameterValue, 'Install, list or remove git hooks that run rona''s checks on plain `git commit` and `git push`')
+            [CompletionResult]::new('init', 'init', [CompletionResultType]::ParameterValue, 'Initialize the rona configuration file')
+            [CompletionResult]::new('list-status', 'list-status', [CompletionResultType]::ParameterValue, 'List files from git status (for shell completion on the -a)')
+            [CompletionResult]::new('prepare-msg', 'prepare-msg', [CompletionResultType]::ParameterValue, 'Prefill a commit message file from the project template')
+            [CompletionResult]::new('prune', 'prune', [CompletionResultType]::ParameterValue, 'Clean up the repository: prune deleted remote branches, expire reflogs, remove unreachable objects and stale rona state files')
+            [CompletionResult]::new('push', 'push', [CompletionResultType]::ParameterValue, 'Push to a git repository')
+            [CompletionResult]::new('release', 'release', [CompletionResultType]::ParameterValue, 'Tag the next version, adding its changelog section from the fragments')
+            [CompletionResult]::new('reset', 'reset', [CompletionResultType]::ParameterValue, 'Unstage files, moving them out of the staging area without losing changes')
+            [CompletionResult]::new('restore', 'restore', [CompletionResultType]::ParameterValue, 'Discard working-tree changes, restoring files to their staged or committed state')
+            [CompletionResult]::new('set-editor', 'set-editor', [CompletionResultType]::ParameterValue, 'Set the editor to use for editing the commit message')
+            [CompletionResult]::new('schema', 'schema', [CompletionResultType]::ParameterValue, 'Print the JSON Schema of the `--json` outputs, for tools that read them')
+            [CompletionResult]::new('show', 'show', [CompletionResultType]::ParameterValue, 'Show a commit: its template fields, trailers, notes, signature and diff stat')
+            [CompletionResult]::new('snippet', 'snippet', [CompletionResultType]::ParameterValue, 'Insert reusable message body text defined under `[snippets]`')
+            [CompletionResult]::new('stats', 'stats', [CompletionResultType]::ParameterValue, 'List the commits made with rona, with their size and lead time, or export them for dashboards. Computed locally from the operation log')
+            [CompletionResult]::new('status', 'status', [CompletionResultType]::ParameterValue, 'Show the current branch, how it compares to its upstream, and the changed files grouped by state')
+            [CompletionResult]::new('sync', 'sync', [CompletionResultType]::ParameterValue, 'Sync current branch with the default branch (or another one) by pulling and merging/rebasing')
+            [CompletionResult]::new('template', 'template', [CompletionResultType]::ParameterValue, 'Check the configured templates for unknown variables and malformed blocks')
+            [CompletionResult]::new('todo', 'todo', [CompletionResultType]::ParameterValue, 'List TODO, FIXME and HACK markers on the lines the staged changes add')
+            [CompletionResult]::new('tour', 'tour', [CompletionResultType]::ParameterValue, 'Walk through the rona workflow in a throwaway demo repository')
+            [CompletionResult]::new('usage', 'usage', [CompletionResultType]::ParameterValue, 'Summarize your own rona usage in this repository from the operation log. Computed locally; nothing is sent anywhere')
+            [CompletionResult]::new('help', 'help', [CompletionResultType]::ParameterValue, 'Print this message or the help of the given subcommand(s)')
+            break
+        }
+        'rona;help;am' {
+            break
+        }
+        'rona;help;audit' {
+            break
+        }
+        'rona;help;backport' {
+            break
+        }
+        'rona;help;branch' {
+            [CompletionResult]::new('describe', 'describe', [CompletionResultType]::ParameterValue, 'Set the purpose of the current branch, exposed as `{branch_description}`')
+            [CompletionResult]::new('list', 'list', [CompletionResultType]::ParameterValue, 'List local branches with their descriptions')
+            [CompletionResult]::new('new', 'new', [CompletionResultType]::ParameterValue, 'Create a branch from `branch_template` without prompting for the description')
+            [CompletionResult]::new('rename', 'rename', [CompletionResultType]::ParameterValue, 'Rename the current branch, push it under the new name and track it')
+            [CompletionResult]::new('switch', 'switch', [CompletionResultType]::ParameterValue, 'Switch to a local branch, picked with a fuzzy finder when omitted')
+            [CompletionResult]::new('merge', 'merge', [CompletionResultType]::ParameterValue, 'Merge a local branch into the current one, picked with a fuzzy finder when omitted')
+            [CompletionResult]::new('rebase', 'rebase', [CompletionResultType]::ParameterValue, 'Rebase the current branch onto a local branch, picked with a fuzzy finder when omitted')
+            [CompletionResult]::new('pull', 'pull', [CompletionResultType]::ParameterValue, 'Pull the upstream changes of the current branch')
+            break
+        }
+        'rona;help;branch;describe' {
+            break
+        }
+        'rona;help;branch;list' {
+            break
+        }
+        'rona;help;branch;new' {
+            break
+        }
+        'rona;help;branch;rename' {
+            break
+        }
+        'rona;help;branch;switch' {
+            break
+        }
+        'rona;help;branch;merge' {
+            break
+        }
+        'rona;help;branch;rebase' {
+            break
+        }
+        'rona;help;branch;pull' {
+            break
+        }
+        'rona;help;browse' {
+            break
+        }
+        'rona;help;bundle' {
+            [CompletionResult]::new('create', 'create', [CompletionResultType]::ParameterValue, 'Write branches to a bundle file')
+            [CompletionResult]::new('apply', 'apply', [CompletionResultType]::ParameterValue, 'Fast-forward branches from a bundle file, after checking it holds them')
+            break
+        }
+        'rona;help;bundle;create' {
+            break
+        }
+        'rona;help;bundle;apply' {
+            break
+        }
+        'rona;help;add-with-exclude' {
+            break
+        }
+        'rona;help;commit' {
+            break
+        }
+        'rona;help;completion' {
+            [CompletionResult]::new('check', 'check', [CompletionResultType]::ParameterValue, 'Check that the completions are installed, up to date and able to list files')
+            break
+        }
+        'rona;help;completion;check' {
+            break
+        }
+        'rona;help;config' {
+            [CompletionResult]::new('create', 'create', [CompletionResultType]::ParameterValue, 'Create or manage a local or global configuration file')
+            [CompletionResult]::new('edit', 'edit', [CompletionResultType]::ParameterValue, 'Edit a configuration file: in the editor, or key by key with `--interactive`')
+            [CompletionResult]::new('show', 'show', [CompletionResultType]::ParameterValue, 'Print the effective configuration, merged from every config file')
+            [CompletionResult]::new('sync', 'sync', [CompletionResultType]::ParameterValue, 'Compare the project `.rona.toml` with the team''s canonical one, set under `[config_sync]`, and apply the differences after confirmation')
             [CompletionResult]::new('which', 'which', [CompletionResultType]::ParameterValue, 'Show which configuration files would be used from a directory')
             break
         }
         'rona;help;config;create' {
             break
         }
+        'rona;help;config;edit' {
+            break
+        }
+        'rona;help;config;show' {
+            break
+        }
+        'rona;help;config;sync' {
+            break
+        }
         'rona;help;config;which' {
             break
         }
+        'rona;help;copy' {
+            break
+        }
+        'rona;help;daemon' {
+            break
+        }
+        'rona;help;check-msg' {
+            break
+        }
+        'rona;help;env' {
+            break
+        }
+        'rona;help;explain-status' {
+            break
+        }
+        'rona;help;format-patch' {
+            break
+        }
+        'rona;help;fragment' {
+            [CompletionResult]::new('add', 'add', [CompletionResultType]::ParameterValue, 'Write a changelog fragment for the current branch')
+            [CompletionResult]::new('list', 'list', [CompletionResultType]::ParameterValue, 'List the fragments waiting for the next release')
+            [CompletionResult]::new('build', 'build', [CompletionResultType]::ParameterValue, 'Add the fragments to the changelog as a new release, then delete them')
+            break
+        }
+        'rona;help;fragment;add' {
+            break
+        }
+        'rona;help;fragment;list' {
+            break
+        }
+        'rona;help;fragment;build' {
+            break
+        }
         'rona;help;generate' {
             break
         }
+        'rona;help;handoff' {
+            [CompletionResult]::new('push', 'push', [CompletionResultType]::ParameterValue, 'Push the staged changes and `commit_message.md` of the current branch')
+            [CompletionResult]::new('pull', 'pull', [CompletionResultType]::ParameterValue, 'Check out a branch handed off from another clone and stage its changes')
+            break
+        }
+        'rona;help;handoff;push' {
+            break
+        }
+        'rona;help;handoff;pull' {
+            break
+        }
+        'rona;help;health' {
+            break
+        }
+        'rona;help;history' {
+            [CompletionResult]::new('ops', 'ops', [CompletionResultType]::ParameterValue, 'List the state-changing operations rona performed, newest first')
+            break
+        }
+        'rona;help;history;ops' {
+            break
+        }
+        'rona;help;hooks' {
+            [CompletionResult]::new('install', 'install', [CompletionResultType]::ParameterValue, 'Write managed hook scripts that call back into rona')
+            [CompletionResult]::new('uninstall', 'uninstall', [CompletionResultType]::ParameterValue, 'Remove the hook scripts rona installed, leaving other hooks alone')
+            [CompletionResult]::new('list', 'list', [CompletionResultType]::ParameterValue, 'Show which hooks are installed and whether rona manages them')
+            [CompletionResult]::new('run', 'run', [CompletionResultType]::ParameterValue, 'Run a hook''s checks; called by the installed hook scripts')
+            break
+        }
+        'rona;help;hooks;install' {
+            break
+        }
+        'rona;help;hooks;uninstall' {
+            break
+        }
+        'rona;help;hooks;list' {
+            break
+        }
+        'rona;help;hooks;run' {
+            break
+        }
         'rona;help;init' {
             break
         }
         'rona;help;list-status' {
             break
         }
+        'rona;help;prepare-msg' {
+            break
+        }
+        'rona;help;prune' {
+            break
+        }
         'rona;help;push' {
             break
         }
+        'rona;help;release' {
+            break
+        }
         'rona;help;reset' {
             break
         }
@@ -281,9 +1715,48 @@ Register-ArgumentCompleter -Native -CommandName 'rona' -ScriptBlock {
         'rona;help;set-editor' {
             break
         }
+        'rona;help;schema' {
+            break
+        }
+        'rona;help;show' {
+            break
+        }
+        'rona;help;snippet' {
+            [CompletionResult]::new('list', 'list', [CompletionResultType]::ParameterValue, 'List the configured snippets')
+            [CompletionResult]::new('insert', 'insert', [CompletionResultType]::ParameterValue, 'Append a rendered snippet to the commit message file')
+            break
+        }
+        'rona;help;snippet;list' {
+            break
+        }
+        'rona;help;snippet;insert' {
+            break
+        }
+        'rona;help;stats' {
+            break
+        }
+        'rona;help;status' {
+            break
+        }
         'rona;help;sync' {
             break
         }
+        'rona;help;template' {
+            [CompletionResult]::new('check', 'check', [CompletionResultType]::ParameterValue, 'Lint the configured templates for unknown variables and malformed blocks')
+            break
+        }
+        'rona;help;template;check' {
+            break
+        }
+        'rona;help;todo' {
+            break
+        }
+        'rona;help;tour' {
+            break
+        }
+        'rona;help;usage' {
+            break
+        }
         'rona;help;help' {
             break
         }
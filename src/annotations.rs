@@ -0,0 +1,331 @@
+//! Diff Annotations
+//!
+//! With `file_bullet_annotate = true`, each file bullet of a generated
+//! `commit_message.md` is followed by a comment line summarizing its staged change:
+//! the net lines and the functions added, removed or changed, such as
+//! `# +12 -3; added parse_header(); changed lint_message()`. The comment gives any
+//! editor some context without the full diff, and is stripped before committing.
+//!
+//! Rust, JavaScript, TypeScript and Python files are parsed before and after the
+//! change (see [`crate::symbols`]), so types and classes are reported too, and a
+//! function is changed when any line of it is. In other files, functions are
+//! recognized in the staged hunks by the definition keywords of common languages
+//! (`fn`, `def`, `func`, `function`); a function whose definition line is both added
+//! and removed is reported as changed.
+
+use std::{collections::BTreeMap, fmt};
+
+use regex::Regex;
+
+use crate::symbols::{Language, symbol_changes};
+
+/// A function definition line: optional modifiers, a definition keyword, an optional
+/// Go receiver, then the name.
+const FUNCTION_DEFINITION: &str = r#"^\s*(?:(?:pub(?:\([^)]*\))?|async|const|unsafe|extern(?:\s+"[^"]*")?|static|export|default|private|public|protected)\s+)*(?:fn|def|func|function)\s+(?:\([^)]*\)\s*)?([A-Za-z_][A-Za-z0-9_]*)"#;
+
+/// What the staged hunks of one file change.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FileSummary {
+    pub insertions: usize,
+    pub deletions: usize,
+    /// Definitions the change adds, such as `parse_header()` or `Session`
+    pub added: Vec<String>,
+    /// Definitions the change removes
+    pub removed: Vec<String>,
+    /// Definitions the change edits
+    pub changed: Vec<String>,
+}
+
+impl FileSummary {
+    /// The definitions the change touches: added, changed, then removed.
+    pub fn symbols(&self) -> impl Iterator<Item = &str> {
+        self.added
+            .iter()
+            .chain(&self.changed)
+            .chain(&self.removed)
+            .map(String::as_str)
+    }
+}
+
+impl fmt::Display for FileSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "+{} -{}", self.insertions, self.deletions)?;
+        for (label, names) in [
+            ("added", &self.added),
+            ("removed", &self.removed),
+            ("changed", &self.changed),
+        ] {
+            if !names.is_empty() {
+                write!(f, "; {label} {}", names.join(", "))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Which version of a changed file a source is read from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Version {
+    /// Before the change, at its old path
+    Before,
+    /// After the change
+    After,
+}
+
+/// The file being read by [`summarize_diff`]: its paths, the lines the change removes
+/// and adds, and the functions of its definition lines.
+#[derive(Default)]
+struct FileDiff {
+    path: String,
+    old_path: Option<String>,
+    deleted: bool,
+    summary: FileSummary,
+    old_lines: Vec<usize>,
+    new_lines: Vec<usize>,
+    next_old: usize,
+    next_new: usize,
+    added: Vec<String>,
+    removed: Vec<String>,
+}
+
+/// Summarizes a unified diff written with `--no-prefix`, by file path. A deleted file
+/// is keyed by its old path.
+///
+/// `source` reads a version of a file, by path, for the languages whose definitions
+/// are parsed. When it gives neither version, the diff lines are used instead.
+#[must_use]
+pub fn summarize_diff(
+    diff: &str,
+    source: impl Fn(Version, &str) -> Option<String>,
+) -> BTreeMap<String, FileSummary> {
+    let Ok(definition) = Regex::new(FUNCTION_DEFINITION) else {
+        return BTreeMap::new();
+    };
+    let mut summaries = BTreeMap::new();
+    let mut current: Option<FileDiff> = None;
+    let mut in_hunk = false;
+
+    for line in diff.lines() {
+        if line.starts_with("diff --git ") {
+            finish(&mut summaries, current.take(), &source);
+            in_hunk = false;
+        } else if !in_hunk && let Some(path) = line.strip_prefix("--- ") {
+            if path != "/dev/null" {
+                current = Some(FileDiff {
+                    path: path.to_string(),
+                    old_path: Some(path.to_string()),
+                    ..FileDiff::default()
+                });
+            }
+        } else if !in_hunk && let Some(path) = line.strip_prefix("+++ ") {
+            let file = current.get_or_insert_default();
+            if path == "/dev/null" {
+                file.deleted = true;
+            } else {
+                file.path = path.to_string();
+            }
+        } else if let Some(ranges) = line.strip_prefix("@@ ") {
+            in_hunk = true;
+            if let Some(file) = current.as_mut() {
+                (file.next_old, file.next_new) = hunk_starts(ranges);
+            }
+        } else if in_hunk && let Some(file) = current.as_mut() {
+            if let Some(text) = line.strip_prefix('+') {
+                file.summary.insertions += 1;
+                file.new_lines.push(file.next_new);
+                file.next_new += 1;
+                file.added.extend(function_name(&definition, text));
+            } else if let Some(text) = line.strip_prefix('-') {
+                file.summary.deletions += 1;
+                file.old_lines.push(file.next_old);
+                file.next_old += 1;
+                file.removed.extend(function_name(&definition, text));
+            } else if line.starts_with(' ') {
+                file.next_old += 1;
+                file.next_new += 1;
+            }
+        }
+    }
+    finish(&mut summaries, current, &source);
+    summaries
+}
+
+/// The first old and new lines of a hunk, from its `-a,b +c,d @@` header.
+fn hunk_starts(ranges: &str) -> (usize, usize) {
+    let start = |prefix: char| {
+        ranges
+            .split_whitespace()
+            .find_map(|range| range.strip_prefix(prefix))
+            .and_then(|range| range.split(',').next()?.parse().ok())
+            .unwrap_or(0)
+    };
+    (start('-'), start('+'))
+}
+
+/// Sorts the definitions of a file into added, removed and changed, and stores its
+/// summary.
+fn finish(
+    summaries: &mut BTreeMap<String, FileSummary>,
+    file: Option<FileDiff>,
+    source: &impl Fn(Version, &str) -> Option<String>,
+) {
+    let Some(FileDiff {
+        path,
+        old_path,
+        deleted,
+        mut summary,
+        old_lines,
+        new_lines,
+        added,
+        removed,
+        ..
+    }) = file
+    else {
+        return;
+    };
+
+    let parsed = Language::of(&path).and_then(|language| {
+        let old = old_path
+            .as_deref()
+            .and_then(|old_path| source(Version::Before, old_path));
+        let new = if deleted {
+            None
+        } else {
+            source(Version::After, &path)
+        };
+        if old.is_none() && new.is_none() {
+            return None;
+        }
+        symbol_changes(
+            language,
+            old.as_deref(),
+            new.as_deref(),
+            &old_lines,
+            &new_lines,
+        )
+    });
+    if let Some(changes) = parsed {
+        summary.added = changes.added;
+        summary.removed = changes.removed;
+        summary.changed = changes.changed;
+        summaries.insert(path, summary);
+        return;
+    }
+
+    for name in &added {
+        let list = if removed.contains(name) {
+            &mut summary.changed
+        } else {
+            &mut summary.added
+        };
+        if !list.contains(name) {
+            list.push(name.clone());
+        }
+    }
+    for name in removed {
+        if !added.contains(&name) && !summary.removed.contains(&name) {
+            summary.removed.push(name);
+        }
+    }
+    summaries.insert(path, summary);
+}
+
+/// The label of the function a line defines, such as `login()`, if it is a definition
+/// line.
+fn function_name(definition: &Regex, line: &str) -> Option<String> {
+    definition
+        .captures(line)
+        .map(|captures| format!("{}()", &captures[1]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_summarize_diff() {
+        let diff = "\
+diff --git src/lint.rs src/lint.rs
+index 1111111..2222222 100644
+--- src/lint.rs
++++ src/lint.rs
+@@ -10,3 +10,5 @@
+-fn lint_message(content: &str) -> Vec<Diagnostic> {
++pub fn lint_message(content: &str, template: &str) -> Vec<Diagnostic> {
++    let _ = template;
+@@ -40,0 +42,3 @@
++pub(crate) async fn parse_header() {}
++
++// fn not_a_definition
+diff --git old.py old.py
+deleted file mode 100644
+--- old.py
++++ /dev/null
+@@ -1,2 +0,0 @@
+-def legacy(self):
+-    pass
+diff --git new.go new.go
+new file mode 100644
+--- /dev/null
++++ new.go
+@@ -0,0 +1,1 @@
++func (s *Server) Start() error { return nil }
+";
+        let summaries = summarize_diff(diff, |_, _| None);
+
+        let lint = &summaries["src/lint.rs"];
+        assert_eq!((lint.insertions, lint.deletions), (5, 1));
+        assert_eq!(lint.added, ["parse_header()"]);
+        assert_eq!(lint.changed, ["lint_message()"]);
+        assert!(lint.removed.is_empty());
+        assert_eq!(
+            lint.to_string(),
+            "+5 -1; added parse_header(); changed lint_message()"
+        );
+
+        assert_eq!(summaries["old.py"].to_string(), "+0 -2; removed legacy()");
+        assert_eq!(summaries["new.go"].to_string(), "+1 -0; added Start()");
+    }
+
+    #[test]
+    fn test_summarize_diff_parses_sources() {
+        let before = "\
+impl Session {
+    fn login(&self) {
+        check();
+    }
+}
+";
+        let after = "\
+impl Session {
+    fn login(&self) {
+        check_password();
+    }
+
+    fn refresh(&self) {}
+}
+";
+        let diff = "\
+diff --git src/session.rs src/session.rs
+index 1111111..2222222 100644
+--- src/session.rs
++++ src/session.rs
+@@ -3 +3 @@ impl Session {
+-        check();
++        check_password();
+@@ -4,0 +5,2 @@ impl Session {
++
++    fn refresh(&self) {}
+";
+        let summaries = summarize_diff(diff, |version, path| {
+            (path == "src/session.rs").then(|| match version {
+                Version::Before => before.to_string(),
+                Version::After => after.to_string(),
+            })
+        });
+        assert_eq!(
+            summaries["src/session.rs"].to_string(),
+            "+3 -1; added Session::refresh(); changed Session::login()"
+        );
+    }
+}
@@ -0,0 +1,143 @@
+//! Convention Audit
+//!
+//! `rona audit` lints the messages of existing commits with the rules of
+//! `rona check-msg`, to measure how much of the history follows the configured
+//! format. This helps when adopting Rona in an established repository, and to follow
+//! compliance over time with `--since`.
+
+use std::collections::BTreeMap;
+
+use crate::{
+    git::CommitMessage,
+    lint::{Diagnostic, lint_message},
+    utils::ratio,
+};
+
+/// A commit whose message does not follow the conventions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuditFinding {
+    pub short_sha: String,
+    pub author: String,
+    pub subject: String,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+/// How many commits of an author follow the conventions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuthorTally {
+    pub author: String,
+    pub conforming: usize,
+    pub total: usize,
+}
+
+/// The outcome of linting a range of commits.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AuditReport {
+    pub total: usize,
+    pub conforming: usize,
+    /// The commits that do not conform, newest first
+    pub findings: Vec<AuditFinding>,
+    /// One tally per author, by name
+    pub authors: Vec<AuthorTally>,
+}
+
+/// The share of `conforming` commits among `total`, in percent.
+#[must_use]
+pub fn compliance(conforming: usize, total: usize) -> f64 {
+    if total == 0 {
+        100.0
+    } else {
+        ratio(conforming, total) * 100.0
+    }
+}
+
+/// Lints the message of each commit against `template` and `commit_types`.
+#[must_use]
+pub fn audit_commits(
+    commits: &[CommitMessage],
+    template: &str,
+    commit_types: &[&str],
+) -> AuditReport {
+    let mut report = AuditReport {
+        total: commits.len(),
+        ..AuditReport::default()
+    };
+    let mut authors: BTreeMap<&str, (usize, usize)> = BTreeMap::new();
+    for commit in commits {
+        let diagnostics = lint_message(&commit.message, template, commit_types);
+        let tally = authors.entry(commit.author.as_str()).or_default();
+        tally.1 += 1;
+        if diagnostics.is_empty() {
+            tally.0 += 1;
+            report.conforming += 1;
+        } else {
+            report.findings.push(AuditFinding {
+                short_sha: commit.short_sha.clone(),
+                author: commit.author.clone(),
+                subject: commit
+                    .message
+                    .lines()
+                    .next()
+                    .unwrap_or_default()
+                    .to_string(),
+                diagnostics,
+            });
+        }
+    }
+    report.authors = authors
+        .into_iter()
+        .map(|(author, (conforming, total))| AuthorTally {
+            author: author.to_string(),
+            conforming,
+            total,
+        })
+        .collect();
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_audit_commits() {
+        let commit = |short_sha: &str, author: &str, message: &str| CommitMessage {
+            sha: format!("{short_sha}0000"),
+            short_sha: short_sha.to_string(),
+            author: author.to_string(),
+            message: message.to_string(),
+        };
+        let commits = [
+            commit("c3", "Jane", "(feat on login) add form\n"),
+            commit("b2", "John", "fixed stuff\n"),
+            commit("a1", "Jane", "(docs on main) explain setup\n\nBody.\n"),
+        ];
+
+        let report = audit_commits(
+            &commits,
+            "({commit_type} on {branch_name}) {message}",
+            &["feat", "fix", "docs"],
+        );
+        assert_eq!((report.total, report.conforming), (3, 2));
+        assert_eq!(report.findings.len(), 1);
+        assert_eq!(report.findings[0].short_sha, "b2");
+        assert_eq!(report.findings[0].subject, "fixed stuff");
+        assert_eq!(
+            report.authors,
+            [
+                AuthorTally {
+                    author: "Jane".to_string(),
+                    conforming: 2,
+                    total: 2
+                },
+                AuthorTally {
+                    author: "John".to_string(),
+                    conforming: 0,
+                    total: 1
+                },
+            ]
+        );
+        assert!((compliance(2, 3) - 66.67).abs() < 0.01);
+        assert!((compliance(0, 0) - 100.0).abs() < f64::EPSILON);
+    }
+}
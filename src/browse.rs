@@ -0,0 +1,576 @@
+//! History Browser
+//!
+//! The full-screen list of `rona browse`, drawn with `ratatui`. Typing filters the
+//! recent commits; Enter opens the commit inspector, with the details `rona show`
+//! prints. Keys copy the SHA of a commit, fix it up with the staged changes or revert
+//! it. A fixup or revert changes history, so it closes the browser and is left to the
+//! caller, whose git output then shows in the terminal as usual.
+//!
+//! In accessibility mode, `rona browse` uses prompts instead, which screen readers
+//! can follow.
+
+use std::collections::BTreeMap;
+
+use ratatui::{
+    DefaultTerminal, Frame,
+    crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    layout::{Constraint, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, List, ListItem, ListState, Paragraph},
+};
+
+use crate::{
+    errors::{Result, RonaError},
+    git::{CommitDetails, CommitSummary, commit_details, get_staged_files},
+    lint::commit_type_span,
+    theme::type_color,
+    utils::fuzzy_filter,
+};
+
+/// Rows a page key moves.
+const PAGE: usize = 10;
+
+/// How commit types are found in subjects and colored.
+#[derive(Debug, Clone, Copy)]
+pub struct TypeStyle<'a> {
+    /// The commit template the type is found with
+    pub template: &'a str,
+    /// The configured commit types
+    pub commit_types: &'a [&'a str],
+    /// The `[type_colors]` of the config
+    pub colors: &'a BTreeMap<String, String>,
+}
+
+/// What the user chose to do to a commit, by its index in the listed commits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Choice {
+    /// Commit the staged changes as a fixup of the commit
+    Fixup(usize),
+    /// Revert the commit
+    Revert(usize),
+}
+
+/// What a key asks the browser loop to do.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Step {
+    Stay,
+    Quit,
+    Inspect(usize),
+    Copy(usize),
+    Fixup(usize),
+    Revert(usize),
+}
+
+/// The state of the browser: the filter, the selected commit and the inspector.
+struct Browser<'a> {
+    commits: &'a [CommitSummary],
+    labels: Vec<String>,
+    filter: String,
+    /// Indexes of the commits matching the filter, best matches first
+    matches: Vec<usize>,
+    list: ListState,
+    /// The details of the inspected commit with its index, and the scroll offset
+    inspector: Option<(usize, Vec<Line<'static>>, u16)>,
+    /// Asks before reverting, except on dry runs
+    confirm_revert: bool,
+    /// The commit waiting for the revert to be confirmed
+    pending_revert: Option<usize>,
+    status: Option<String>,
+}
+
+impl<'a> Browser<'a> {
+    fn new(commits: &'a [CommitSummary], confirm_revert: bool) -> Self {
+        let labels = commits
+            .iter()
+            .map(|c| format!("{} {} ({})", c.short_sha, c.subject, c.relative_date))
+            .collect();
+        Self {
+            commits,
+            labels,
+            filter: String::new(),
+            matches: (0..commits.len()).collect(),
+            list: ListState::default().with_selected(Some(0)),
+            inspector: None,
+            confirm_revert,
+            pending_revert: None,
+            status: None,
+        }
+    }
+
+    /// The index of the selected commit, if any matches the filter.
+    fn selected(&self) -> Option<usize> {
+        self.list
+            .selected()
+            .and_then(|row| self.matches.get(row).copied())
+    }
+
+    /// The commit the action keys apply to: the inspected one, else the selected one.
+    fn current(&self) -> Option<usize> {
+        self.inspector
+            .as_ref()
+            .map(|(index, _, _)| *index)
+            .or_else(|| self.selected())
+    }
+
+    fn set_filter(&mut self, filter: String) {
+        self.filter = filter;
+        let labels = self.labels.iter().map(String::as_str).enumerate().collect();
+        self.matches = fuzzy_filter(labels, &self.filter, |(_, label)| label)
+            .into_iter()
+            .map(|(index, _)| index)
+            .collect();
+        self.list.select((!self.matches.is_empty()).then_some(0));
+    }
+
+    fn move_selection(&mut self, down: bool, rows: usize) {
+        let Some(row) = self.list.selected() else {
+            return;
+        };
+        let last = self.matches.len().saturating_sub(1);
+        let row = if down {
+            row.saturating_add(rows).min(last)
+        } else {
+            row.saturating_sub(rows)
+        };
+        self.list.select(Some(row));
+    }
+
+    fn scroll(&mut self, down: bool, rows: usize) {
+        if let Some((_, lines, offset)) = &mut self.inspector {
+            let rows = u16::try_from(rows).unwrap_or(u16::MAX);
+            let last = u16::try_from(lines.len().saturating_sub(1)).unwrap_or(u16::MAX);
+            *offset = if down {
+                offset.saturating_add(rows).min(last)
+            } else {
+                offset.saturating_sub(rows)
+            };
+        }
+    }
+
+    /// Asks to revert `index`, or reverts it right away when no confirmation is needed.
+    fn ask_revert(&mut self, index: usize) -> Step {
+        if !self.confirm_revert {
+            return Step::Revert(index);
+        }
+        let commit = &self.commits[index];
+        self.status = Some(format!(
+            "Revert {} {}? (y/N)",
+            commit.short_sha, commit.subject
+        ));
+        self.pending_revert = Some(index);
+        Step::Stay
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) -> Step {
+        if let Some(index) = self.pending_revert.take() {
+            if matches!(key.code, KeyCode::Char('y' | 'Y')) {
+                return Step::Revert(index);
+            }
+            self.status = Some("Revert cancelled".to_string());
+            return Step::Stay;
+        }
+        self.status = None;
+
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        match (key.code, ctrl) {
+            (KeyCode::Char('c'), true) => Step::Quit,
+            (KeyCode::Char('y'), true) => self.current().map_or(Step::Stay, Step::Copy),
+            (KeyCode::Char('f'), true) => self.current().map_or(Step::Stay, Step::Fixup),
+            (KeyCode::Char('r'), true) => self
+                .current()
+                .map_or(Step::Stay, |index| self.ask_revert(index)),
+            _ if self.inspector.is_some() => self.inspector_key(key.code),
+            _ => self.list_key(key.code, ctrl),
+        }
+    }
+
+    fn list_key(&mut self, code: KeyCode, ctrl: bool) -> Step {
+        match (code, ctrl) {
+            (KeyCode::Esc, _) => return Step::Quit,
+            (KeyCode::Enter, _) => return self.selected().map_or(Step::Stay, Step::Inspect),
+            (KeyCode::Up, _) | (KeyCode::Char('p'), true) => self.move_selection(false, 1),
+            (KeyCode::Down, _) | (KeyCode::Char('n'), true) => self.move_selection(true, 1),
+            (KeyCode::PageUp, _) => self.move_selection(false, PAGE),
+            (KeyCode::PageDown, _) => self.move_selection(true, PAGE),
+            (KeyCode::Backspace, _) => {
+                let mut filter = self.filter.clone();
+                filter.pop();
+                self.set_filter(filter);
+            }
+            (KeyCode::Char(c), false) => self.set_filter(format!("{}{c}", self.filter)),
+            _ => {}
+        }
+        Step::Stay
+    }
+
+    fn inspector_key(&mut self, code: KeyCode) -> Step {
+        match code {
+            KeyCode::Esc | KeyCode::Backspace | KeyCode::Char('q') => self.inspector = None,
+            KeyCode::Up | KeyCode::Char('k') => self.scroll(false, 1),
+            KeyCode::Down | KeyCode::Char('j') => self.scroll(true, 1),
+            KeyCode::PageUp => self.scroll(false, PAGE),
+            KeyCode::PageDown | KeyCode::Char(' ') => self.scroll(true, PAGE),
+            KeyCode::Char('y') => return self.current().map_or(Step::Stay, Step::Copy),
+            KeyCode::Char('f') => return self.current().map_or(Step::Stay, Step::Fixup),
+            KeyCode::Char('r') => {
+                return self
+                    .current()
+                    .map_or(Step::Stay, |index| self.ask_revert(index));
+            }
+            _ => {}
+        }
+        Step::Stay
+    }
+
+    fn draw(&mut self, frame: &mut Frame, style: TypeStyle) {
+        let [header, body, footer] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Min(1),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+
+        let count = format!("  {}/{}", self.matches.len(), self.commits.len());
+        frame.render_widget(
+            Paragraph::new(Line::from(vec![
+                Span::styled("Filter: ", Style::new().fg(Color::LightCyan)),
+                Span::raw(self.filter.clone()),
+                Span::styled(count, Style::new().add_modifier(Modifier::DIM)),
+            ])),
+            header,
+        );
+
+        if let Some((index, lines, offset)) = &self.inspector {
+            let commit = &self.commits[*index];
+            frame.render_widget(
+                Paragraph::new(lines.clone())
+                    .scroll((*offset, 0))
+                    .block(Block::bordered().title(format!(" {} ", commit.short_sha))),
+                body,
+            );
+        } else {
+            let items: Vec<ListItem> = self
+                .matches
+                .iter()
+                .map(|index| ListItem::new(commit_line(&self.commits[*index], style)))
+                .collect();
+            let list = List::new(items)
+                .block(Block::bordered().title(" Recent commits "))
+                .highlight_style(
+                    Style::new()
+                        .fg(Color::LightBlue)
+                        .add_modifier(Modifier::BOLD),
+                )
+                .highlight_symbol("> ");
+            frame.render_stateful_widget(list, body, &mut self.list);
+        }
+
+        let hints = if self.inspector.is_some() {
+            "Esc back  ↑↓ scroll  y copy SHA  f fix up  r revert"
+        } else {
+            "type to filter  Enter inspect  ^Y copy SHA  ^F fix up  ^R revert  Esc quit"
+        };
+        let footer_line = self.status.as_ref().map_or_else(
+            || Line::styled(hints, Style::new().add_modifier(Modifier::DIM)),
+            |status| Line::styled(status.clone(), Style::new().fg(Color::Yellow)),
+        );
+        frame.render_widget(Paragraph::new(footer_line), footer);
+    }
+}
+
+/// Shows `commits` in the browser until the user quits or picks a fixup or revert.
+///
+/// # Arguments
+/// * `commits` - The commits to list, newest first
+/// * `style` - How commit types are colored
+/// * `confirm_revert` - Whether a revert is confirmed first
+/// * `copy` - Copies a SHA to the clipboard
+///
+/// # Errors
+/// * If the terminal cannot be set up or read
+pub fn browse(
+    commits: &[CommitSummary],
+    style: TypeStyle,
+    confirm_revert: bool,
+    copy: fn(&str) -> Result<()>,
+) -> Result<Option<Choice>> {
+    let mut terminal = ratatui::try_init().map_err(RonaError::Io)?;
+    let result = run(&mut terminal, commits, style, confirm_revert, copy);
+    ratatui::restore();
+    result
+}
+
+fn run(
+    terminal: &mut DefaultTerminal,
+    commits: &[CommitSummary],
+    style: TypeStyle,
+    confirm_revert: bool,
+    copy: fn(&str) -> Result<()>,
+) -> Result<Option<Choice>> {
+    let mut browser = Browser::new(commits, confirm_revert);
+    loop {
+        terminal
+            .draw(|frame| browser.draw(frame, style))
+            .map_err(RonaError::Io)?;
+        let Event::Key(key) = event::read().map_err(RonaError::Io)? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+
+        match browser.handle_key(key) {
+            Step::Stay => {}
+            Step::Quit => return Ok(None),
+            Step::Inspect(index) => {
+                match commit_details(&commits[index].sha, style.template, style.commit_types) {
+                    Ok(details) => {
+                        browser.inspector = Some((index, details_lines(&details, style), 0));
+                    }
+                    Err(e) => browser.status = Some(e.to_string()),
+                }
+            }
+            Step::Copy(index) => {
+                let sha = &commits[index].sha;
+                browser.status = Some(match copy(sha) {
+                    Ok(()) => format!("Copied {sha} to clipboard"),
+                    Err(e) => e.to_string(),
+                });
+            }
+            Step::Fixup(index) => {
+                if get_staged_files()?.is_empty() {
+                    browser.status =
+                        Some("Nothing is staged; stage the fix first (e.g. `rona -a`)".to_string());
+                } else {
+                    return Ok(Some(Choice::Fixup(index)));
+                }
+            }
+            Step::Revert(index) => return Ok(Some(Choice::Revert(index))),
+        }
+    }
+}
+
+/// A list row: the short SHA, the subject with its type colored, and the date.
+fn commit_line(commit: &CommitSummary, style: TypeStyle) -> Line<'static> {
+    let mut spans = vec![Span::styled(
+        format!("{} ", commit.short_sha),
+        Style::new().fg(Color::Yellow),
+    )];
+    spans.extend(subject_spans(&commit.subject, style, Style::new()));
+    spans.push(Span::styled(
+        format!(" ({})", commit.relative_date),
+        Style::new().add_modifier(Modifier::DIM),
+    ));
+    Line::from(spans)
+}
+
+/// A subject with its commit type in color, found with the commit template.
+fn subject_spans(subject: &str, style: TypeStyle, base: Style) -> Vec<Span<'static>> {
+    let Some(span) = commit_type_span(subject, style.template, style.commit_types) else {
+        return vec![Span::styled(subject.to_string(), base)];
+    };
+    let commit_type = &subject[span.clone()];
+    let color = tui_color(type_color(commit_type, style.colors));
+    vec![
+        Span::styled(subject[..span.start].to_string(), base),
+        Span::styled(commit_type.to_string(), base.fg(color)),
+        Span::styled(subject[span.end..].to_string(), base),
+    ]
+}
+
+/// The inspector view of a commit, laid out like `rona show`.
+fn details_lines(details: &CommitDetails, style: TypeStyle) -> Vec<Line<'static>> {
+    let heading = Style::new().fg(Color::Cyan).add_modifier(Modifier::BOLD);
+    let mut lines = vec![
+        Line::styled(
+            format!("commit {}", details.sha),
+            Style::new().fg(Color::Yellow),
+        ),
+        Line::raw(format!("Author:    {} <{}>", details.author, details.email)),
+        Line::raw(format!("Date:      {}", details.date)),
+        Line::raw(format!(
+            "Signature: {}{}",
+            details.signature.status,
+            details
+                .signature
+                .signer
+                .as_ref()
+                .map(|signer| format!(" ({signer})"))
+                .unwrap_or_default()
+        )),
+        Line::raw(""),
+    ];
+
+    let mut subject = vec![Span::raw("    ")];
+    subject.extend(subject_spans(
+        &details.subject,
+        style,
+        Style::new().add_modifier(Modifier::BOLD),
+    ));
+    lines.push(Line::from(subject));
+    lines.extend(
+        details
+            .body
+            .lines()
+            .map(|line| Line::raw(format!("    {line}"))),
+    );
+
+    if let Some(fields) = details.header_fields.as_ref().filter(|f| !f.is_empty()) {
+        lines.extend([Line::raw(""), Line::styled("Header fields:", heading)]);
+        let width = fields.iter().map(|f| f.name.len()).max().unwrap_or(0);
+        lines.extend(
+            fields
+                .iter()
+                .map(|field| Line::raw(format!("  {:width$}  {}", field.name, field.value))),
+        );
+    }
+    if !details.trailers.is_empty() {
+        lines.extend([Line::raw(""), Line::styled("Trailers:", heading)]);
+        lines.extend(
+            details
+                .trailers
+                .iter()
+                .map(|trailer| Line::raw(format!("  {}: {}", trailer.key, trailer.value))),
+        );
+    }
+    if let Some(notes) = &details.notes {
+        lines.extend([Line::raw(""), Line::styled("Notes:", heading)]);
+        lines.extend(notes.lines().map(|line| Line::raw(format!("  {line}"))));
+    }
+    if !details.stat.is_empty() {
+        lines.push(Line::raw(""));
+        lines.extend(details.stat.lines().map(|line| Line::raw(line.to_string())));
+    }
+    lines
+}
+
+/// The `ratatui` color of a `colored` one, as used by `[type_colors]`.
+const fn tui_color(color: colored::Color) -> Color {
+    match color {
+        colored::Color::Black => Color::Black,
+        colored::Color::Red => Color::Red,
+        colored::Color::Green => Color::Green,
+        colored::Color::Yellow => Color::Yellow,
+        colored::Color::Blue => Color::Blue,
+        colored::Color::Magenta => Color::Magenta,
+        colored::Color::Cyan => Color::Cyan,
+        colored::Color::White => Color::Gray,
+        colored::Color::BrightBlack => Color::DarkGray,
+        colored::Color::BrightRed => Color::LightRed,
+        colored::Color::BrightGreen => Color::LightGreen,
+        colored::Color::BrightYellow => Color::LightYellow,
+        colored::Color::BrightBlue => Color::LightBlue,
+        colored::Color::BrightMagenta => Color::LightMagenta,
+        colored::Color::BrightCyan => Color::LightCyan,
+        colored::Color::BrightWhite => Color::White,
+        colored::Color::AnsiColor(index) => Color::Indexed(index),
+        colored::Color::TrueColor { r, g, b } => Color::Rgb(r, g, b),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn commits() -> Vec<CommitSummary> {
+        ["feat: add login", "fix: crash on start", "docs: readme"]
+            .iter()
+            .enumerate()
+            .map(|(index, subject)| CommitSummary {
+                sha: format!("{index}{}", "0".repeat(39)),
+                short_sha: format!("{index}000000"),
+                subject: (*subject).to_string(),
+                relative_date: "1 day ago".to_string(),
+            })
+            .collect()
+    }
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn ctrl(c: char) -> KeyEvent {
+        KeyEvent::new(KeyCode::Char(c), KeyModifiers::CONTROL)
+    }
+
+    #[test]
+    fn test_browser_filters_and_selects() {
+        let commits = commits();
+        let mut browser = Browser::new(&commits, true);
+        assert_eq!(browser.handle_key(key(KeyCode::Down)), Step::Stay);
+        assert_eq!(browser.selected(), Some(1));
+
+        for c in "crash".chars() {
+            browser.handle_key(key(KeyCode::Char(c)));
+        }
+        assert_eq!(browser.matches, [1]);
+        assert_eq!(browser.handle_key(key(KeyCode::Enter)), Step::Inspect(1));
+
+        browser.handle_key(key(KeyCode::Char('z')));
+        assert!(browser.matches.is_empty());
+        assert_eq!(browser.handle_key(key(KeyCode::Enter)), Step::Stay);
+        browser.handle_key(key(KeyCode::Backspace));
+        assert_eq!(browser.selected(), Some(1));
+
+        assert_eq!(browser.handle_key(ctrl('y')), Step::Copy(1));
+        assert_eq!(browser.handle_key(ctrl('f')), Step::Fixup(1));
+        assert_eq!(browser.handle_key(key(KeyCode::Esc)), Step::Quit);
+    }
+
+    #[test]
+    fn test_browser_confirms_revert() {
+        let commits = commits();
+        let mut browser = Browser::new(&commits, true);
+        assert_eq!(browser.handle_key(ctrl('r')), Step::Stay);
+        assert!(
+            browser
+                .status
+                .as_deref()
+                .is_some_and(|s| s.contains("(y/N)"))
+        );
+        assert_eq!(browser.handle_key(key(KeyCode::Char('n'))), Step::Stay);
+        assert_eq!(browser.status.as_deref(), Some("Revert cancelled"));
+
+        browser.handle_key(ctrl('r'));
+        assert_eq!(browser.handle_key(key(KeyCode::Char('y'))), Step::Revert(0));
+
+        // Dry runs do not ask
+        let mut browser = Browser::new(&commits, false);
+        assert_eq!(browser.handle_key(ctrl('r')), Step::Revert(0));
+    }
+
+    #[test]
+    fn test_browser_inspector_keys() {
+        let commits = commits();
+        let mut browser = Browser::new(&commits, false);
+        browser.inspector = Some((2, vec![Line::raw("a"), Line::raw("b")], 0));
+        browser.handle_key(key(KeyCode::PageDown));
+        assert!(matches!(browser.inspector, Some((2, _, 1))));
+
+        // Letters act on the inspected commit instead of filtering
+        assert_eq!(browser.handle_key(key(KeyCode::Char('y'))), Step::Copy(2));
+        assert_eq!(browser.handle_key(key(KeyCode::Char('r'))), Step::Revert(2));
+        assert!(browser.filter.is_empty());
+
+        assert_eq!(browser.handle_key(key(KeyCode::Esc)), Step::Stay);
+        assert!(browser.inspector.is_none());
+    }
+
+    #[test]
+    fn test_commit_line_colors_type() {
+        let colors = BTreeMap::new();
+        let style = TypeStyle {
+            template: "{commit_type}: {message}",
+            commit_types: &["feat", "fix"],
+            colors: &colors,
+        };
+        let line = commit_line(&commits()[1], style);
+        let texts: Vec<&str> = line.spans.iter().map(|s| s.content.as_ref()).collect();
+        assert_eq!(
+            texts,
+            ["1000000 ", "", "fix", ": crash on start", " (1 day ago)"]
+        );
+        assert_eq!(line.spans[2].style.fg, Some(Color::Red));
+    }
+}
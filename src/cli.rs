@@ -9,13 +9,31 @@
 //!
 //! The CLI supports several commands:
 //! - `add-with-exclude`: Add files to git while excluding specified patterns
+//! - `am`: Apply mailbox patches, optionally rewriting their subjects through the template
+//! - `backport`: Cherry-pick a commit onto release branches and push or open PRs
+//! - `browse`: Browse recent commits to view, copy, fix up or revert one
+//! - `check-msg`: Check a commit message file against the commit template
 //! - `commit`: Commit changes using the commit message from `commit_message.md`
 //! - `config`: Create or manage local/global configuration files
+//! - `daemon`: Serve the changed files for `rona -l` from a warm cache
+//! - `fragment`: Write changelog fragments and assemble them into the changelog
+//! - `format-patch`: Export commits as mbox patch files with an optional cover letter
 //! - `generate`: Generate a new commit message file
+//! - `health`: Check the repository for signs of missing maintenance
+//! - `history`: Review the operations rona performed in the repository
+//! - `hooks`: Install, list or remove the managed `pre-commit`, `commit-msg` and `pre-push` hooks
 //! - `init`: Initialize Rona configuration
 //! - `list-status`: List git status files (for shell completion)
+//! - `prepare-msg`: Prefill a commit message file from git's `prepare-commit-msg` hook
+//! - `prune`: Clean up stale remote refs, reflogs, unreachable objects and rona state
 //! - `push`: Push changes to remote repository
+//! - `release`: Tag the next version, with prerelease channels and the fragment changelog
 //! - `set-editor`: Configure the editor for commit messages
+//! - `show`: Show a commit with its template fields, trailers, notes and signature
+//! - `snippet`: List snippets or insert one into the commit message file
+//! - `template`: Lint the configured templates for unknown variables
+//! - `todo`: List TODO/FIXME/HACK markers added by the staged changes
+//! - `tour`: Walk through the workflow in a throwaway demo repository
 //!
 //! # Features
 //!
@@ -26,33 +44,132 @@
 //! - Handles configuration management
 //!
 
-use clap::{Command as ClapCommand, CommandFactory, Parser, Subcommand, ValueEnum, ValueHint};
+use clap::{
+    Command as ClapCommand, CommandFactory, Parser, Subcommand, ValueEnum, ValueHint,
+    builder::PossibleValuesParser,
+};
 use clap_complete::{Shell, generate};
-use colored::Colorize;
-use dialoguer::{Confirm, FuzzySelect, Input, MultiSelect};
+use colored::{Color, Colorize};
+use dialoguer::{
+    Confirm, Input, MultiSelect, Select,
+    console::{Key, Term},
+};
 use glob::Pattern;
-use std::{collections::HashMap, fs::read_to_string, io, process::Command};
+use serde::Serialize;
+use std::{
+    collections::HashMap,
+    fs::read_to_string,
+    io::Read,
+    path::{Path, PathBuf},
+    time::Duration,
+};
 
 use crate::{
-    config::{Config, find_config_sources},
-    errors::{Result, RonaError},
+    audit::{audit_commits, compliance},
+    browse::{Choice, TypeStyle, browse},
+    config::{
+        CommitType, Config, DEFAULT_TEMPLATE_NAME, PackageConfig, ProjectConfig, TodoAction,
+        denied_features, find_config_sources, validate_config_text,
+    },
+    config_editor::edit_interactively,
+    config_sync::{SyncSource, with_sync_section},
+    copy::{DEFAULT_COPY_FORMAT, copy_template, render_copy},
+    editor::{open_file_in_editor, open_in_editor},
+    errors::{GitError, Result, RonaError},
+    explain::print_explained_status,
     extra_fields::{
         BuiltInFieldConfig, ExtraField, MessagePrefetchConfig, prompt_extra_field,
         run_message_prefetch,
     },
+    forge::{PullRequest, RemoteRepo, open_pull_request},
+    fragments::{
+        DEFAULT_CHANGELOG, DEFAULT_FRAGMENT_DIR, Fragment, insert_release, next_fragment_path,
+        read_fragments, render_release,
+    },
     git::{
-        COMMIT_MESSAGE_FILE_PATH, COMMIT_TYPES, add_to_git_exclude, create_needed_files,
-        format_branch_name, generate_commit_message, get_current_branch, get_current_commit_nb,
-        get_restorable_files, get_stageable_files, get_staged_files, get_status_files,
-        get_top_level_path, git_add_files, git_add_with_exclude_patterns, git_branch_only,
-        git_commit, git_create_branch, git_push, git_restore_files, git_unstage_files,
-        sanitize_branch_name,
+        COMMIT_MESSAGE_FILE_PATH, COMMIT_TYPES, CommitNumberSource, CommitSummary, Feature,
+        FileBullet, FileState, ForcePush, NumberedCommit, PUSHING_ENV, PathScope, PushPreview,
+        PushSummary, Signing, StagedFileDiff, StatusReport, TODO_SECTION_HEADING,
+        add_to_commitignore, add_to_git_exclude,
+        am::{
+            AmOutcome, AmResume, am_in_progress, apply_messages, mail_info, remove_split_mailboxes,
+            resume_am, set_message_subject, split_mailboxes,
+        },
+        append_staged_diff, background_fetch_if_due,
+        backport::{
+            TargetBranch, backport_branch_name, backport_message, cherry_pick_no_commit,
+            commit_count, fetch_branch, has_staged_changes, is_worktree_clean,
+            prepare_target_branch, resolve_commit,
+        },
+        branch::slugify,
+        branch_description, branch_descriptions, branch_upstream,
+        bundle::{
+            BundleHead, apply_bundle, bundle_commit_count, bundle_heads, create_bundle,
+            verify_bundle,
+        },
+        change_id::with_change_id,
+        commit_details, commit_messages, commit_preview, commit_reference, count_caveats,
+        create_needed_files, current_head, ensure_allowed, ensure_outside_commit_hook,
+        ensure_writable, excluded_files_mask, file_arguments, force_push, format_branch_name,
+        generate_commit_message, get_all_branches, get_commit_nb, get_current_branch,
+        get_default_branch, get_file_states, get_out_of_cone_files, get_restorable_files,
+        get_stageable_files, get_staged_files, get_status_files, get_top_level_path, git_add_files,
+        git_add_with_exclude_patterns, git_branch_only, git_commit, git_commit_fixup,
+        git_commit_with_message, git_create_branch, git_delete_remote_branch, git_pull, git_push,
+        git_push_tracking, git_rename_branch, git_restore_files, git_revert, git_unstage_files,
+        handoff::{
+            DEFAULT_HANDOFF_NAMESPACE, create_handoff_commit, fetch_handoff, handoff_files,
+            handoff_ref, push_handoff, restore_handoff,
+        },
+        hooks::{
+            HookState, HookStatus, MANAGED_HOOKS, hooks_dir, install_hook, list_hooks,
+            uninstall_hook,
+        },
+        infer_commit_type, is_expected_remote, is_read_only, is_valid_ref_name,
+        message_log::{
+            DiffLine, archive_message, message_diff, save_generated_message, take_generated_message,
+        },
+        patch::{PatchSeries, fill_cover_letter, format_patches, series_changes, series_commits},
+        path_scope, pending_commit_message, push_divergence, push_remote, push_summary,
+        read_operations, recent_commits, record_operation,
+        release::{commits_touching, create_tag, merged_tags, stage_release_files, tag_exists},
+        remote_location, remote_url, render_commit_message, resolve_pattern, review_push_args,
+        run_pre_push_commands, sanitize_branch_name, set_branch_description, set_denied_features,
+        set_path_scope, set_read_only,
+        shared_config::config_diff,
+        show_staged_diff, staged_file_diffs, staged_size, staged_todos, staging_preview,
+        strip_message_comments,
+        todo::TodoMarker,
+        todo_section, unsigned_outgoing_commits, upstream_ahead, upstream_status,
+    },
+    impact::{impact_labels, invalid_globs},
+    lint::{Diagnostic, commit_type_span, lint_message, parse_header},
+    notify::{WebhookConfig, post_webhook, render_notification, render_release_notification},
+    presets::{commitignore_lines, detect_presets, resolve_presets, staging_patterns},
+    release::{
+        Bump, Channel, DEFAULT_RELEASE_MESSAGE, DEFAULT_TAG_PREFIX, Version, latest_version,
+        next_version,
     },
+    schema::{OUTPUT_NAMES, all_schemas, output_schema, to_versioned_json},
+    snippets::{insert_snippet, render_snippet, snippet_reference},
     template::{
-        BranchTemplateVariables, TemplateVariables, process_branch_template, process_template,
-        validate_branch_template, validate_template,
+        BRANCH_TEMPLATE_VARIABLES, BranchTemplateVariables, COMMIT_TEMPLATE_VARIABLES,
+        COPY_TEMPLATE_VARIABLES, COVER_LETTER_TEMPLATE_VARIABLES, CoverLetterTemplateVariables,
+        DEFAULT_REVIEW_REF, PUSH_TEMPLATE_VARIABLES, RELEASE_TEMPLATE_VARIABLES,
+        REVIEW_REF_TEMPLATE_VARIABLES, ReleaseTemplateVariables, ReviewRefVariables,
+        TemplateVariables, extract_ticket, infer_scope, parse_timezone, process_branch_template,
+        process_cover_letter_template, process_release_template, process_review_ref_template,
+        process_template, unknown_variables, uses_variable, validate_branch_template,
+        validate_cover_letter_template, validate_date_format, validate_release_template,
+        validate_review_ref_template, validate_template, validate_template_with_vars,
     },
-    theme::prompt_theme,
+    theme::{
+        TypeSelectTheme, commit_type_label, failure_mark, glyph, is_accessible, paint_subject_type,
+        paint_type, prompt_theme, select_item, set_accessible, success_mark,
+    },
+    tour::run_tour,
+    tracker::{TrackerCommand, run_tracker_commands, tracker_commands},
+    utils::{format_duration, format_list, fuzzy_filter, parse_duration},
 };
 
 /// Configuration scope for config command
@@ -64,6 +181,25 @@ pub(crate) enum ConfigScope {
     Global,
 }
 
+/// Export format of `rona stats`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub(crate) enum StatsFormat {
+    /// Prometheus text exposition format, e.g. for the node exporter's textfile collector
+    Prometheus,
+    /// One row per commit with a header row
+    Csv,
+}
+
+/// Output format set by the global `--output` flag
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub(crate) enum OutputFormat {
+    /// Human-readable text
+    #[default]
+    Text,
+    /// A JSON object with a `schema_version`, for scripts and editor plugins
+    Json,
+}
+
 /// Subcommands for the `config` command
 #[derive(Subcommand)]
 pub(crate) enum ConfigSubcommand {
@@ -83,6 +219,58 @@ pub(crate) enum ConfigSubcommand {
         dry_run: bool,
     },
 
+    /// Edit a configuration file: in the editor, or key by key with `--interactive`
+    #[command(name = "edit")]
+    Edit {
+        /// Scope of the configuration file to edit
+        #[arg(value_enum, default_value = "local")]
+        scope: ConfigScope,
+
+        /// Pick known keys from a list and edit them with prompts showing the current
+        /// value, the default and a description. Comments in the file are kept
+        #[arg(short = 'i', long, default_value_t = false)]
+        interactive: bool,
+    },
+
+    /// Print the effective configuration, merged from every config file
+    #[command(name = "show")]
+    Show {
+        /// Print each value with the file, environment variable or option it came from
+        #[arg(long, default_value_t = false)]
+        origins: bool,
+    },
+
+    /// Compare the project `.rona.toml` with the team's canonical one, set under
+    /// `[config_sync]`, and apply the differences after confirmation
+    #[command(name = "sync")]
+    Sync {
+        /// Download the canonical config from this URL (default: `[config_sync] url`)
+        #[arg(long, value_name = "URL", conflicts_with_all = ["remote", "branch"])]
+        url: Option<String>,
+
+        /// Remote name or repository URL of the branch holding the canonical config
+        /// (default: `[config_sync] remote`, else `origin`)
+        #[arg(long, value_name = "REMOTE")]
+        remote: Option<String>,
+
+        /// Branch holding the canonical config (default: `[config_sync] branch`, else
+        /// `rona-config`)
+        #[arg(long, value_name = "BRANCH")]
+        branch: Option<String>,
+
+        /// Fail when the local config differs, without changing it (for CI)
+        #[arg(long, default_value_t = false)]
+        check: bool,
+
+        /// Apply the differences without asking
+        #[arg(short = 'y', long, default_value_t = false)]
+        yes: bool,
+
+        /// Show the differences without applying them
+        #[arg(long, default_value_t = false)]
+        dry_run: bool,
+    },
+
     /// Show which configuration files would be used from a directory
     #[command(short_flag = 'w', name = "which", visible_alias = "find")]
     Which {
@@ -96,12 +284,447 @@ pub(crate) enum ConfigSubcommand {
     },
 }
 
+/// Subcommands for the `branch` command
+#[derive(Subcommand)]
+pub(crate) enum BranchSubcommand {
+    /// Set the purpose of the current branch, exposed as `{branch_description}`
+    #[command(name = "describe")]
+    Describe {
+        /// The description; prompts with the current one when omitted
+        #[arg(value_name = "DESCRIPTION", conflicts_with = "clear")]
+        description: Option<String>,
+
+        /// Remove the description of the current branch
+        #[arg(long, default_value_t = false)]
+        clear: bool,
+
+        /// Show the description that would be stored without storing it
+        #[arg(long, default_value_t = false)]
+        dry_run: bool,
+    },
+
+    /// List local branches with their descriptions
+    #[command(name = "list")]
+    List,
+
+    /// Create a branch from `branch_template` without prompting for the description
+    #[command(name = "new")]
+    New {
+        /// What the branch is for, available as `{description}` and slugified as `{slug}`
+        #[arg(value_name = "DESCRIPTION")]
+        description: String,
+
+        /// Ticket reference, available as `{ticket}` (e.g. `PROJ-1`)
+        #[arg(short = 't', long)]
+        ticket: Option<String>,
+
+        /// Branch type, available as `{branch_type}` or `{type}`; prompted when missing
+        #[arg(long = "type", value_name = "TYPE")]
+        branch_type: Option<String>,
+
+        /// Create the branch without switching to it
+        #[arg(long = "no-switch", default_value_t = false)]
+        no_switch: bool,
+
+        /// Show the branch name without creating it
+        #[arg(long, default_value_t = false)]
+        dry_run: bool,
+    },
+
+    /// Rename the current branch, push it under the new name and track it
+    #[command(name = "rename")]
+    Rename {
+        /// The new name of the branch
+        #[arg(value_name = "NEW_NAME")]
+        new_name: String,
+
+        /// Only rename the local branch; the remote branch and tracking are left as they are
+        #[arg(long, default_value_t = false, conflicts_with_all = ["yes", "keep_old"])]
+        local: bool,
+
+        /// Delete the old remote branch without asking
+        #[arg(
+            short = 'y',
+            long,
+            default_value_t = false,
+            conflicts_with = "keep_old"
+        )]
+        yes: bool,
+
+        /// Keep the old remote branch
+        #[arg(long = "keep-old", default_value_t = false)]
+        keep_old: bool,
+
+        /// Show what would be renamed, pushed and deleted without doing it
+        #[arg(long, default_value_t = false)]
+        dry_run: bool,
+    },
+
+    /// Switch to a local branch, picked with a fuzzy finder when omitted
+    #[command(name = "switch")]
+    Switch {
+        /// The branch to switch to
+        #[arg(value_name = "BRANCH")]
+        branch: Option<String>,
+
+        /// Show the branch that would be checked out without switching
+        #[arg(long, default_value_t = false)]
+        dry_run: bool,
+    },
+
+    /// Merge a local branch into the current one, picked with a fuzzy finder when omitted
+    #[command(name = "merge")]
+    Merge {
+        /// The branch to merge
+        #[arg(value_name = "BRANCH")]
+        branch: Option<String>,
+
+        /// Show the branch that would be merged without merging it
+        #[arg(long, default_value_t = false)]
+        dry_run: bool,
+    },
+
+    /// Rebase the current branch onto a local branch, picked with a fuzzy finder when omitted
+    #[command(name = "rebase")]
+    Rebase {
+        /// The branch to rebase onto
+        #[arg(value_name = "BRANCH")]
+        branch: Option<String>,
+
+        /// Show the branch that would be rebased onto without rebasing
+        #[arg(long, default_value_t = false)]
+        dry_run: bool,
+    },
+
+    /// Pull the upstream changes of the current branch
+    #[command(name = "pull")]
+    Pull {
+        /// Show what would be pulled without pulling
+        #[arg(long, default_value_t = false)]
+        dry_run: bool,
+    },
+}
+
+/// Subcommands for the `bundle` command
+#[derive(Subcommand)]
+pub(crate) enum BundleSubcommand {
+    /// Write branches to a bundle file
+    #[command(name = "create")]
+    Create {
+        /// The bundle file to write
+        #[arg(value_name = "FILE", value_hint = ValueHint::FilePath)]
+        file: String,
+
+        /// Leave out the commits reachable from this ref, which the receiving clone has
+        #[arg(long, value_name = "REF")]
+        since: Option<String>,
+
+        /// Branch to bundle, can be repeated (default: the current branch)
+        #[arg(short = 'b', long = "branch", value_name = "BRANCH")]
+        branches: Vec<String>,
+
+        /// Show what would be bundled without writing the file
+        #[arg(long, default_value_t = false)]
+        dry_run: bool,
+    },
+
+    /// Fast-forward branches from a bundle file, after checking it holds them
+    #[command(name = "apply")]
+    Apply {
+        /// The bundle file to apply
+        #[arg(value_name = "FILE", value_hint = ValueHint::FilePath)]
+        file: String,
+
+        /// Branch expected in the bundle and applied, can be repeated (default: the
+        /// current branch)
+        #[arg(short = 'b', long = "branch", value_name = "BRANCH")]
+        branches: Vec<String>,
+
+        /// Show which branches would be updated without updating them
+        #[arg(long, default_value_t = false)]
+        dry_run: bool,
+    },
+}
+
+/// Subcommands for the `handoff` command
+#[derive(Subcommand)]
+pub(crate) enum HandoffSubcommand {
+    /// Push the staged changes and `commit_message.md` of the current branch
+    #[command(name = "push")]
+    Push {
+        /// Remote to push to (default: `[handoff] remote`, else `origin`)
+        #[arg(long)]
+        remote: Option<String>,
+
+        /// Show what would be handed off without pushing
+        #[arg(long, default_value_t = false)]
+        dry_run: bool,
+    },
+
+    /// Check out a branch handed off from another clone and stage its changes
+    #[command(name = "pull")]
+    Pull {
+        /// Branch to restore (defaults to the current branch)
+        branch: Option<String>,
+
+        /// Remote to pull from (default: `[handoff] remote`, else `origin`)
+        #[arg(long)]
+        remote: Option<String>,
+
+        /// Show the handed-off files without changing anything
+        #[arg(long, default_value_t = false)]
+        dry_run: bool,
+    },
+}
+
+/// Subcommands for the `history` command
+#[derive(Subcommand)]
+pub(crate) enum HistorySubcommand {
+    /// List the state-changing operations rona performed, newest first
+    #[command(name = "ops")]
+    Ops {
+        /// Number of operations to show
+        #[arg(short = 'n', long, default_value_t = 20)]
+        limit: usize,
+
+        /// Print the operations as JSON lines
+        #[arg(long, default_value_t = false)]
+        json: bool,
+    },
+}
+
+/// Subcommands for the `fragment` command
+#[derive(Subcommand)]
+pub(crate) enum FragmentSubcommand {
+    /// Write a changelog fragment for the current branch
+    #[command(name = "add")]
+    Add {
+        /// Section of the changelog, one of the commit types (e.g. `feat`)
+        #[arg(value_name = "TYPE")]
+        fragment_type: String,
+
+        /// The changelog entry
+        text: String,
+
+        /// Ticket the change belongs to, added after the entry (e.g. `ABC-123`)
+        #[arg(long)]
+        ticket: Option<String>,
+
+        /// Package of `[packages]` the fragment belongs to, in a monorepo
+        #[arg(long, value_name = "NAME")]
+        package: Option<String>,
+
+        /// Show the fragment that would be written without writing it
+        #[arg(long, default_value_t = false)]
+        dry_run: bool,
+    },
+
+    /// List the fragments waiting for the next release
+    #[command(name = "list")]
+    List {
+        /// Package of `[packages]` to list the fragments of, in a monorepo
+        #[arg(long, value_name = "NAME")]
+        package: Option<String>,
+    },
+
+    /// Add the fragments to the changelog as a new release, then delete them
+    #[command(name = "build")]
+    Build {
+        /// Version of the release, used in its heading
+        version: String,
+
+        /// Date of the release (defaults to today)
+        #[arg(long)]
+        date: Option<String>,
+
+        /// Keep the fragment files
+        #[arg(long, default_value_t = false)]
+        keep: bool,
+
+        /// Package of `[packages]` to build the changelog of, in a monorepo
+        #[arg(long, value_name = "NAME")]
+        package: Option<String>,
+
+        /// Print the release section without changing any file
+        #[arg(long, default_value_t = false)]
+        dry_run: bool,
+    },
+}
+
+/// Subcommands for the `hooks` command
+#[derive(Subcommand)]
+pub(crate) enum HooksSubcommand {
+    /// Write managed hook scripts that call back into rona
+    #[command(name = "install")]
+    Install {
+        /// Hooks to install (defaults to all of them)
+        #[arg(value_parser = PossibleValuesParser::new(MANAGED_HOOKS))]
+        hooks: Vec<String>,
+
+        /// Show what would be installed without writing anything
+        #[arg(long, default_value_t = false)]
+        dry_run: bool,
+    },
+
+    /// Remove the hook scripts rona installed, leaving other hooks alone
+    #[command(name = "uninstall")]
+    Uninstall {
+        /// Hooks to remove (defaults to all of them)
+        #[arg(value_parser = PossibleValuesParser::new(MANAGED_HOOKS))]
+        hooks: Vec<String>,
+
+        /// Show what would be removed without removing anything
+        #[arg(long, default_value_t = false)]
+        dry_run: bool,
+    },
+
+    /// Show which hooks are installed and whether rona manages them
+    #[command(name = "list")]
+    List,
+
+    /// Run a hook's checks; called by the installed hook scripts
+    #[command(name = "run")]
+    Run {
+        /// The hook being run
+        #[arg(value_parser = PossibleValuesParser::new(MANAGED_HOOKS))]
+        hook: String,
+
+        /// Arguments git passed to the hook
+        #[arg(allow_hyphen_values = true)]
+        args: Vec<String>,
+    },
+}
+
+/// Subcommands for the `snippet` command
+#[derive(Subcommand)]
+pub(crate) enum SnippetSubcommand {
+    /// List the configured snippets
+    #[command(name = "list")]
+    List,
+
+    /// Append a rendered snippet to the commit message file
+    #[command(name = "insert")]
+    Insert {
+        /// Name of the snippet (a key under `[snippets]`)
+        name: String,
+
+        /// Message file to insert into (defaults to `commit_message.md`)
+        #[arg(long, value_hint = ValueHint::FilePath)]
+        file: Option<String>,
+
+        /// Print the rendered snippet without changing the file
+        #[arg(long, default_value_t = false)]
+        dry_run: bool,
+    },
+}
+
+/// Completion subcommands
+#[derive(Subcommand)]
+pub(crate) enum CompletionSubcommand {
+    /// Check that the completions are installed, up to date and able to list files
+    #[command(name = "check")]
+    Check {
+        /// The shell whose completions to check
+        #[arg(value_enum)]
+        shell: Shell,
+    },
+}
+
+/// Template subcommands
+#[derive(Subcommand)]
+pub(crate) enum TemplateSubcommand {
+    /// Lint the configured templates for unknown variables and malformed blocks
+    #[command(name = "check")]
+    Check,
+}
+
 /// CLI's commands
 #[derive(Subcommand)]
 pub(crate) enum CliCommand {
+    /// Apply mailbox patches, with a three-way fallback for patches that do not apply.
+    #[command(name = "am")]
+    Am {
+        /// Patch files or mailboxes, e.g. written by `rona format-patch`
+        #[arg(
+            value_name = "PATCH",
+            value_hint = ValueHint::FilePath,
+            required_unless_present_any = ["continue_am", "skip", "abort"]
+        )]
+        patches: Vec<String>,
+
+        /// Rewrite each subject through the commit template, numbered from the next commit
+        #[arg(long, default_value_t = false)]
+        retemplate: bool,
+
+        /// Apply the rest of the patches after resolving conflicts
+        #[arg(long = "continue", default_value_t = false, conflicts_with_all = ["patches", "skip", "abort"])]
+        continue_am: bool,
+
+        /// Drop the patch that stopped and apply the rest
+        #[arg(long, default_value_t = false, conflicts_with_all = ["patches", "abort"])]
+        skip: bool,
+
+        /// Stop applying and restore the branch to where it was
+        #[arg(long, default_value_t = false, conflicts_with = "patches")]
+        abort: bool,
+
+        /// Show which patches would be applied, with their subjects, without applying them
+        #[arg(long, default_value_t = false)]
+        dry_run: bool,
+    },
+
+    /// Report which existing commits follow the commit template and lint rules.
+    #[command(name = "audit")]
+    Audit {
+        /// Only audit the commits after this ref (e.g. the tag of the last release)
+        #[arg(long, value_name = "REF")]
+        since: Option<String>,
+    },
+
+    /// Cherry-pick a commit onto release branches with a re-templated message.
+    #[command(name = "backport")]
+    Backport {
+        /// The commit to backport (SHA, `HEAD`, a tag, ...)
+        #[arg(value_name = "COMMIT")]
+        commit: String,
+
+        /// Target branches, comma-separated or repeated (`--to release/1.x,release/2.x`)
+        #[arg(
+            long = "to",
+            value_name = "BRANCH",
+            value_delimiter = ',',
+            required = true
+        )]
+        targets: Vec<String>,
+
+        /// Push each target branch after committing the backport
+        #[arg(short = 'p', long, default_value_t = false, conflicts_with = "pr")]
+        push: bool,
+
+        /// Commit on a `backport/<sha>/<target>` branch, push it and open a pull request
+        /// with the GitHub CLI (`gh`)
+        #[arg(long, default_value_t = false)]
+        pr: bool,
+
+        /// Remote the target branches are fetched from and pushed to
+        #[arg(long, default_value = "origin")]
+        remote: String,
+
+        /// Create unsigned commits
+        #[arg(short = 'u', long = "unsigned", default_value_t = false)]
+        unsigned: bool,
+
+        /// Show what would be backported without changing any branch
+        #[arg(long, default_value_t = false)]
+        dry_run: bool,
+    },
+
     /// Create a new branch interactively using a branch name template.
-    #[command(name = "branch")]
+    #[command(name = "branch", args_conflicts_with_subcommands = true)]
     Branch {
+        #[command(subcommand)]
+        subcommand: Option<BranchSubcommand>,
+
         /// Show what would be created without actually creating the branch
         #[arg(long, default_value_t = false)]
         dry_run: bool,
@@ -111,6 +734,25 @@ pub(crate) enum CliCommand {
         no_switch: bool,
     },
 
+    /// Browse recent commits: filter by typing, then view, copy, fix up or revert one.
+    #[command(name = "browse")]
+    Browse {
+        /// Number of recent commits to list
+        #[arg(short = 'n', long, default_value_t = 100)]
+        limit: usize,
+
+        /// Show what a fixup or revert would do without running it
+        #[arg(long, default_value_t = false)]
+        dry_run: bool,
+    },
+
+    /// Carry branches to a clone without a shared remote in `git bundle` files.
+    #[command(name = "bundle")]
+    Bundle {
+        #[command(subcommand)]
+        subcommand: BundleSubcommand,
+    },
+
     /// Add all files to the `git add` command and exclude the patterns passed as positional arguments.
     #[command(short_flag = 'a', name = "add-with-exclude")]
     AddWithExclude {
@@ -118,10 +760,14 @@ pub(crate) enum CliCommand {
         #[arg(value_name = "PATTERNS", value_hint = ValueHint::AnyPath)]
         to_exclude: Vec<String>,
 
-        /// Interactively pick which changed files to stage (`MultiSelect` of git status)
+        /// Interactively pick which changed files to stage; excluded files start deselected
         #[arg(short = 'i', long = "interactive", default_value_t = false)]
         interactive: bool,
 
+        /// Only offer files whose path fuzzy-matches this query (with `-i`)
+        #[arg(long, value_name = "QUERY", requires = "interactive")]
+        filter: Option<String>,
+
         /// Show what would be added without actually adding files
         #[arg(long, default_value_t = false)]
         dry_run: bool,
@@ -150,17 +796,42 @@ pub(crate) enum CliCommand {
         #[arg(long = "copy", default_value_t = false)]
         copy: bool,
 
+        /// Commit with this message run through the commit template, instead of
+        /// `commit_message.md`
+        #[arg(short = 'm', long = "message", value_name = "MESSAGE")]
+        message: Option<String>,
+
+        /// Commit type for `--message` (inferred from the branch prefix by default)
+        #[arg(short = 't', long = "type", value_name = "TYPE", requires = "message")]
+        commit_type: Option<String>,
+
+        /// Leave the commit number out of a `--message` commit
+        #[arg(
+            short = 'n',
+            long = "no-commit-number",
+            default_value_t = false,
+            requires = "message"
+        )]
+        no_commit_number: bool,
+
         /// Additional arguments to pass to the commit command
         #[arg(allow_hyphen_values = true)]
         args: Vec<String>,
     },
 
     /// Generate shell completions for your shell
-    #[command(name = "completion")]
+    #[command(
+        name = "completion",
+        args_conflicts_with_subcommands = true,
+        subcommand_negates_reqs = true
+    )]
     Completion {
         /// The shell to generate completions for
-        #[arg(value_enum)]
-        shell: Shell,
+        #[arg(value_enum, required = true)]
+        shell: Option<Shell>,
+
+        #[command(subcommand)]
+        subcommand: Option<CompletionSubcommand>,
     },
 
     /// Manage configuration files (create or inspect)
@@ -170,6 +841,91 @@ pub(crate) enum CliCommand {
         subcommand: ConfigSubcommand,
     },
 
+    /// Copy a reference to a commit to the clipboard, e.g. `abc1234 ("fix: handle empty
+    /// index")`, for pull request descriptions and review comments.
+    #[command(name = "copy")]
+    Copy {
+        /// The commit to copy (branch, tag, SHA, `HEAD~2`, ...)
+        #[arg(value_name = "REF", default_value = "HEAD")]
+        reference: String,
+
+        /// `reference`, `sha`, `short`, `subject`, a format of `[copy] formats`, or a
+        /// template such as `{short_sha} {subject}` (default: `[copy] format`)
+        #[arg(long, value_name = "FORMAT")]
+        format: Option<String>,
+
+        /// Print the text instead of copying it
+        #[arg(long, default_value_t = false)]
+        print: bool,
+    },
+
+    /// Keep the changed files warm for `rona -l`, served over a unix socket.
+    ///
+    /// Runs in the foreground until stopped; `rona -l` falls back to `git status`
+    /// when no daemon is running.
+    #[command(name = "daemon")]
+    Daemon {
+        /// Stop the daemon serving the current repository
+        #[arg(long, default_value_t = false)]
+        stop: bool,
+    },
+
+    /// Check a commit message file against the commit template.
+    ///
+    /// Designed for git's `commit-msg` hook: `rona check-msg "$1"`.
+    #[command(name = "check-msg")]
+    CheckMsg {
+        /// Commit message file to check
+        #[arg(value_hint = ValueHint::FilePath)]
+        file: String,
+    },
+
+    /// Show what rona resolved here: repository, config files, template, editor,
+    /// signing, guards and scope. Paste it in bug reports.
+    #[command(name = "env", visible_alias = "which")]
+    Env {
+        /// Print the environment as JSON
+        #[arg(long, default_value_t = false)]
+        json: bool,
+    },
+
+    /// Show git status with plain-language explanations of each state and the rona
+    /// commands that act on it.
+    #[command(name = "explain-status")]
+    ExplainStatus,
+
+    /// Export commits as mbox patch files for mailing-list review.
+    #[command(name = "format-patch")]
+    FormatPatch {
+        /// Commits to export: a range such as `main..`, or a base revision (`v1.0`
+        /// exports `v1.0..HEAD`)
+        #[arg(value_name = "RANGE", conflicts_with = "count")]
+        range: Option<String>,
+
+        /// Export the last N commits (default: 1 when no range is given)
+        #[arg(short = 'n', long, value_name = "N")]
+        count: Option<usize>,
+
+        /// Directory the patches are written to (default: `patches`)
+        #[arg(short = 'o', long, value_name = "DIR", value_hint = ValueHint::DirPath)]
+        output_dir: Option<String>,
+
+        /// Write a cover letter (`0000-cover-letter.patch`) from the cover letter template
+        #[arg(long, default_value_t = false)]
+        cover_letter: bool,
+
+        /// Show which commits would be exported without writing files
+        #[arg(long, default_value_t = false)]
+        dry_run: bool,
+    },
+
+    /// Write changelog fragments and assemble them into the changelog at release time.
+    #[command(name = "fragment")]
+    Fragment {
+        #[command(subcommand)]
+        subcommand: FragmentSubcommand,
+    },
+
     /// Directly generate the `commit_message.md` file.
     #[command(short_flag = 'g')]
     Generate {
@@ -184,6 +940,60 @@ pub(crate) enum CliCommand {
         /// No commit number
         #[arg(short = 'n', long = "no-commit-number", default_value_t = false)]
         no_commit_number: bool,
+
+        /// Print the generated message to stdout instead of writing `commit_message.md`
+        /// (safe to use from git hooks)
+        #[arg(long, default_value_t = false, conflicts_with_all = ["interactive", "dry_run"])]
+        print: bool,
+
+        /// Read the message from stdin and write `commit_message.md` without prompting
+        /// (the commit type is inferred from the branch prefix)
+        #[arg(long, default_value_t = false, conflicts_with_all = ["interactive", "print"])]
+        stdin: bool,
+
+        /// Read the message from the clipboard and write `commit_message.md` without
+        /// prompting (the commit type is inferred from the branch prefix)
+        #[arg(long, default_value_t = false, conflicts_with_all = ["interactive", "print", "stdin"])]
+        from_clipboard: bool,
+    },
+
+    /// Continue a half-finished commit in another clone: push the staged changes and
+    /// `commit_message.md` of a branch to a ref, then pull them on the other machine.
+    #[command(name = "handoff")]
+    Handoff {
+        #[command(subcommand)]
+        subcommand: HandoffSubcommand,
+    },
+
+    /// Check the repository for signs of missing maintenance (loose objects,
+    /// missing commit-graph, large files in history, broken refs).
+    #[command(name = "health")]
+    Health {
+        /// Offer to run the suggested git commands, asking before each one
+        #[arg(long, default_value_t = false)]
+        fix: bool,
+
+        /// Write a commit-graph (with changed-path filters) and keep it updated on
+        /// fetch, which speeds up commit counting and history walks
+        #[arg(long, default_value_t = false)]
+        optimize: bool,
+    },
+
+    /// Review what rona did in this repository (recorded in `.git/rona/oplog.jsonl`).
+    #[command(name = "history")]
+    History {
+        #[command(subcommand)]
+        subcommand: HistorySubcommand,
+    },
+
+    /// Install, list or remove git hooks that run rona's checks on plain `git commit`
+    /// and `git push`.
+    ///
+    /// Hooks not written by rona are never overwritten or removed.
+    #[command(name = "hooks")]
+    Hooks {
+        #[command(subcommand)]
+        subcommand: HooksSubcommand,
     },
 
     /// Initialize the rona configuration file.
@@ -196,28 +1006,118 @@ pub(crate) enum CliCommand {
         /// Show what would be initialized without creating files
         #[arg(long, default_value_t = false)]
         dry_run: bool,
+
+        /// Prepare the current repository instead of creating a config file: create
+        /// `commit_message.md` and `.commitignore`, with the patterns of the `preset`
+        /// list (or of the ecosystems detected at the root when it is empty)
+        #[arg(long, default_value_t = false)]
+        repo: bool,
     },
 
     /// List files from git status (for shell completion on the -a)
     #[command(short_flag = 'l')]
-    ListStatus,
+    ListStatus {
+        /// Only list files whose path fuzzy-matches this query, best matches first
+        #[arg(long, value_name = "QUERY")]
+        filter: Option<String>,
+    },
 
-    /// Push to a git repository.
-    #[command(short_flag = 'p')]
+    /// Prefill a commit message file from the project template.
+    ///
+    /// Designed for git's `prepare-commit-msg` hook, which passes its arguments
+    /// straight through: `rona prepare-msg "$1" "$2" "$3"`.
+    #[command(name = "prepare-msg")]
+    PrepareMsg {
+        /// Commit message file provided by git
+        #[arg(value_hint = ValueHint::FilePath)]
+        msg_file: String,
+
+        /// Source of the message (`message`, `template`, `merge`, `squash` or `commit`)
+        source: Option<String>,
+
+        /// Commit object name, given by git when the source is `commit`
+        sha: Option<String>,
+
+        /// Show the message that would be written without changing the file
+        #[arg(long, default_value_t = false)]
+        dry_run: bool,
+    },
+
+    /// Clean up the repository: prune deleted remote branches, expire reflogs,
+    /// remove unreachable objects and stale rona state files.
+    #[command(name = "prune")]
+    Prune {
+        /// Remove rona state files (under `.git/rona/`) older than this (units: s, m, h, d)
+        #[arg(long, default_value = "30d")]
+        max_age: String,
+
+        /// Show what would be removed without removing anything
+        #[arg(long, default_value_t = false)]
+        dry_run: bool,
+    },
+
+    /// Push to a git repository.
+    #[command(short_flag = 'p')]
     Push {
         /// Show what would be pushed without actually pushing
         #[arg(long, default_value_t = false)]
         dry_run: bool,
 
+        /// Push HEAD to the `[review] ref` (default `refs/for/<target>`) instead of
+        /// the branch
+        #[arg(long, default_value_t = false)]
+        review: bool,
+
+        /// Branch the review is for, the `{target}` of the review ref (default: the
+        /// default branch)
+        #[arg(long, value_name = "BRANCH", requires = "review")]
+        target: Option<String>,
+
         /// Additional arguments to pass to the push command
         #[arg(allow_hyphen_values = true)]
         args: Vec<String>,
     },
 
+    /// Tag the next version, adding its changelog section from the fragments.
+    #[command(name = "release")]
+    Release {
+        /// Bump the major version
+        #[arg(long, default_value_t = false, conflicts_with_all = ["minor", "patch"])]
+        major: bool,
+
+        /// Bump the minor version
+        #[arg(long, default_value_t = false, conflicts_with = "patch")]
+        minor: bool,
+
+        /// Bump the patch version (the default)
+        #[arg(long, default_value_t = false)]
+        patch: bool,
+
+        /// Release an alpha prerelease (e.g. `1.3.0-alpha.1`)
+        #[arg(long, default_value_t = false, conflicts_with_all = ["beta", "rc"])]
+        alpha: bool,
+
+        /// Release a beta prerelease (e.g. `1.3.0-beta.1`)
+        #[arg(long, default_value_t = false, conflicts_with = "rc")]
+        beta: bool,
+
+        /// Release a release candidate (e.g. `1.3.0-rc.1`)
+        #[arg(long, default_value_t = false)]
+        rc: bool,
+
+        /// Release a package of `[packages]`, with its own tags and changelog
+        #[arg(long, value_name = "NAME")]
+        package: Option<String>,
+
+        /// Show the version, the tag and the changelog section without changing anything
+        #[arg(long, default_value_t = false)]
+        dry_run: bool,
+    },
+
     /// Unstage files, moving them out of the staging area without losing changes.
     #[command(name = "reset")]
     Reset {
-        /// Specific files to unstage (relative to the repo root). Unstages all staged files when omitted.
+        /// Specific files to unstage (relative to the current directory). Unstages all staged files when omitted.
         #[arg(value_name = "FILES", value_hint = ValueHint::AnyPath)]
         files: Vec<String>,
 
@@ -233,7 +1133,7 @@ pub(crate) enum CliCommand {
     /// Discard working-tree changes, restoring files to their staged or committed state.
     #[command(name = "restore")]
     Restore {
-        /// Specific files to restore (relative to the repo root). Required unless `--interactive` is used.
+        /// Specific files to restore (relative to the current directory). Required unless `--interactive` is used.
         #[arg(value_name = "FILES", value_hint = ValueHint::AnyPath)]
         files: Vec<String>,
 
@@ -262,12 +1162,53 @@ pub(crate) enum CliCommand {
         dry_run: bool,
     },
 
-    /// Sync current branch with main (or another branch) by pulling and merging/rebasing.
+    /// Print the JSON Schema of the `--json` outputs, for tools that read them.
+    #[command(name = "schema")]
+    Schema {
+        /// The output to print the schema of (all of them when omitted)
+        #[arg(value_name = "OUTPUT", value_parser = PossibleValuesParser::new(OUTPUT_NAMES))]
+        name: Option<String>,
+    },
+
+    /// Show a commit: its template fields, trailers, notes, signature and diff stat.
+    #[command(name = "show")]
+    Show {
+        /// The commit to show (branch, tag, SHA, `HEAD~2`, ...)
+        #[arg(value_name = "REF", default_value = "HEAD")]
+        reference: String,
+
+        /// Print the commit as JSON
+        #[arg(long, default_value_t = false)]
+        json: bool,
+    },
+
+    /// Insert reusable message body text defined under `[snippets]`.
+    #[command(name = "snippet")]
+    Snippet {
+        #[command(subcommand)]
+        subcommand: SnippetSubcommand,
+    },
+
+    /// List the commits made with rona, with their size and lead time, or export
+    /// them for dashboards. Computed locally from the operation log.
+    #[command(name = "stats")]
+    Stats {
+        /// Print the metrics in this format instead of a table
+        #[arg(long, value_enum, value_name = "FORMAT")]
+        export: Option<StatsFormat>,
+    },
+
+    /// Show the current branch, how it compares to its upstream, and the changed files
+    /// grouped by state.
+    #[command(name = "status")]
+    Status,
+
+    /// Sync current branch with the default branch (or another one) by pulling and merging/rebasing.
     #[command(name = "sync")]
     Sync {
-        /// Branch to sync from (default: main)
-        #[arg(short = 'b', long = "branch", default_value = "main")]
-        source_branch: String,
+        /// Branch to sync from (default: the default branch, from `origin/HEAD`)
+        #[arg(short = 'b', long = "branch")]
+        source_branch: Option<String>,
 
         /// Use rebase instead of merge
         #[arg(short = 'r', long = "rebase", default_value_t = false)]
@@ -281,6 +1222,42 @@ pub(crate) enum CliCommand {
         #[arg(long, default_value_t = false)]
         dry_run: bool,
     },
+
+    /// Check the configured templates for unknown variables and malformed blocks.
+    #[command(name = "template")]
+    Template {
+        #[command(subcommand)]
+        subcommand: TemplateSubcommand,
+    },
+
+    /// List TODO, FIXME and HACK markers on the lines the staged changes add.
+    #[command(name = "todo")]
+    Todo {
+        /// Exit with an error when markers are found (for hooks and CI)
+        #[arg(long, default_value_t = false)]
+        check: bool,
+    },
+
+    /// Walk through the rona workflow in a throwaway demo repository.
+    #[command(name = "tour")]
+    Tour {
+        /// Run every step without pausing
+        #[arg(short = 'y', long = "yes", default_value_t = false)]
+        yes: bool,
+
+        /// Keep the demo repository after the tour
+        #[arg(long, default_value_t = false)]
+        keep: bool,
+    },
+
+    /// Summarize your own rona usage in this repository from the operation log.
+    /// Computed locally; nothing is sent anywhere.
+    #[command(name = "usage")]
+    Usage {
+        /// Print the report as JSON
+        #[arg(long, default_value_t = false)]
+        json: bool,
+    },
 }
 
 #[derive(Parser)]
@@ -306,6 +1283,36 @@ pub(crate) struct Cli {
     /// Config file to use instead of the default global/project hierarchy
     #[arg(short = 'f', long = "config-file", value_name = "PATH", value_hint = ValueHint::FilePath, global = true)]
     config: Option<String>,
+
+    /// Run as if rona was started in PATH instead of the current directory (like `git -C`)
+    #[arg(short = 'C', long = "chdir", value_name = "PATH", value_hint = ValueHint::DirPath, global = true)]
+    chdir: Option<String>,
+
+    /// Refuse every operation that would write to the repository, the index or the config (also `RONA_READ_ONLY=1`)
+    #[arg(long = "read-only", global = true)]
+    read_only: bool,
+
+    /// Language of the commit message: use the commit template and date formats of `[templates.locales.<LANG>]`
+    #[arg(long = "lang", value_name = "LANG", global = true)]
+    lang: Option<String>,
+
+    /// Commit template to use: the one named NAME in `[templates]`
+    #[arg(long = "template", value_name = "NAME", global = true)]
+    template: Option<String>,
+
+    /// Only consider the files under a directory: a name from `[scopes]` or a path
+    #[arg(long = "scope", value_name = "SCOPE", global = true)]
+    path_scope: Option<String>,
+
+    /// Output format of `list-status`, `status` and the `--dry-run` of `add`, `commit` and `push`
+    #[arg(
+        long = "output",
+        value_enum,
+        value_name = "FORMAT",
+        default_value_t,
+        global = true
+    )]
+    output: OutputFormat,
 }
 
 /// Build the CLI command structure for generating completions
@@ -314,27 +1321,21 @@ fn build_cli() -> ClapCommand {
     Cli::command()
 }
 
-/// Print custom fish shell completions that enhance the auto-generated ones
-#[doc(hidden)]
-fn print_fish_custom_completions() {
-    println!();
-    println!("# === CUSTOM RONA COMPLETIONS ===");
-    println!("# Helper function to get git status files");
-    println!("function __rona_status_files");
-    println!("    rona -l");
-    println!("end");
-    println!();
-    println!("# Command-specific completions");
-    println!("# add-with-exclude: Complete with git status files");
-    println!(
-        "complete -c rona -n '__fish_seen_subcommand_from add-with-exclude -a' -xa '(__rona_status_files)'"
-    );
-    println!("# reset / restore: Complete with git status files");
-    println!("complete -c rona -n '__fish_seen_subcommand_from reset' -xa '(__rona_status_files)'");
-    println!(
-        "complete -c rona -n '__fish_seen_subcommand_from restore' -xa '(__rona_status_files)'"
-    );
-}
+/// Custom fish shell completions that enhance the auto-generated ones
+const FISH_CUSTOM_COMPLETIONS: &str = "
+# === CUSTOM RONA COMPLETIONS ===
+# Helper function to get git status files
+function __rona_status_files
+    rona -l
+end
+
+# Command-specific completions
+# add-with-exclude: Complete with git status files
+complete -c rona -n '__fish_seen_subcommand_from add-with-exclude -a' -xa '(__rona_status_files)'
+# reset / restore: Complete with git status files
+complete -c rona -n '__fish_seen_subcommand_from reset' -xa '(__rona_status_files)'
+complete -c rona -n '__fish_seen_subcommand_from restore' -xa '(__rona_status_files)'
+";
 
 /// Prompt for branch description and any configured branch extra fields in the configured order.
 ///
@@ -423,7 +1424,7 @@ fn prompt_branch_fields(
 fn branch_effective_types(config: &Config) -> Vec<String> {
     let commit: Vec<String> = config.project_config.commit_types.as_ref().map_or_else(
         || COMMIT_TYPES.iter().map(|s| (*s).to_string()).collect(),
-        Clone::clone,
+        |types| types.iter().map(|t| t.name.clone()).collect(),
     );
     match &config.project_config.branch_types {
         None => commit,
@@ -448,28 +1449,24 @@ fn branch_effective_types(config: &Config) -> Vec<String> {
 /// # Errors
 /// * If branch creation fails
 /// * If user cancels a prompt
-#[allow(clippy::literal_string_with_formatting_args)]
 fn handle_branch(no_switch: bool, config: &Config) -> Result<()> {
+    ensure_outside_commit_hook("branch")?;
+
     let effective_types = branch_effective_types(config);
     let types_for_branch: Vec<&str> = effective_types.iter().map(String::as_str).collect();
 
-    let default_template = "{branch_type}/{description}";
     let template = config
         .project_config
         .branch_template
         .as_deref()
-        .unwrap_or(default_template);
-
-    // Determine which built-in variables the template actually uses.
-    let needs_branch_type =
-        template.contains("{branch_type}") || template.contains("{?branch_type}");
-    let needs_description =
-        template.contains("{description}") || template.contains("{?description}");
+        .unwrap_or(DEFAULT_BRANCH_TEMPLATE);
 
     // A field is "referenced" when {name} or {?name} appears anywhere in the template.
-    let is_referenced = |name: &str| {
-        template.contains(&format!("{{{name}}}")) || template.contains(&format!("{{?{name}}}"))
-    };
+    let is_referenced = |name: &str| is_template_referenced(template, name);
+
+    // Determine which built-in variables the template actually uses.
+    let needs_branch_type = is_referenced("branch_type") || is_referenced("type");
+    let needs_description = is_referenced("description") || is_referenced("slug");
 
     // Build the effective field list for branch prompts. Only fields referenced in the template
     // are prompted: fields inherited from an extended config (or otherwise configured) but unused
@@ -508,13 +1505,7 @@ fn handle_branch(no_switch: bool, config: &Config) -> Result<()> {
     }
 
     let branch_type = if needs_branch_type {
-        let index = FuzzySelect::with_theme(&prompt_theme())
-            .with_prompt("Select branch type")
-            .items(&types_for_branch)
-            .default(0)
-            .interact_opt()
-            .map_err(|_| RonaError::UserCancelled)?
-            .ok_or(RonaError::UserCancelled)?;
+        let index = select_type("Select branch type", &types_for_branch, config)?;
         types_for_branch[index].to_string()
     } else {
         String::new()
@@ -535,10 +1526,99 @@ fn handle_branch(no_switch: bool, config: &Config) -> Result<()> {
         return Ok(());
     }
 
-    let variables = BranchTemplateVariables::new(branch_type, description.trim().to_owned())?;
+    let variables =
+        BranchTemplateVariables::new(branch_type, description.trim().to_owned(), String::new())?;
+
+    let raw_name = process_branch_template(
+        template,
+        &variables,
+        &extra_values,
+        config.project_config.strict_templates,
+    )?;
+    create_templated_branch(&raw_name, no_switch, config)
+}
+
+/// Whether `{name}` or `{?name}` appears in a template.
+fn is_template_referenced(template: &str, name: &str) -> bool {
+    template.contains(&format!("{{{name}}}")) || template.contains(&format!("{{?{name}}}"))
+}
+
+/// Handle `branch new`: create a branch from the template without prompting for the
+/// description or the ticket.
+///
+/// The type is prompted for only when `--type` is missing and the template uses it.
+/// Extra fields referenced by the template are left empty.
+///
+/// # Errors
+/// * If the type is not one of the branch types
+/// * If the template is invalid or the name does not match `branch_pattern`
+/// * If branch creation fails
+fn handle_branch_new(
+    description: &str,
+    ticket: Option<&str>,
+    branch_type: Option<&str>,
+    no_switch: bool,
+    config: &Config,
+) -> Result<()> {
+    ensure_outside_commit_hook("branch")?;
+
+    let template = config
+        .project_config
+        .branch_template
+        .as_deref()
+        .unwrap_or(DEFAULT_BRANCH_TEMPLATE);
+    let extra_values: HashMap<String, String> = config
+        .project_config
+        .branch_extra_fields
+        .iter()
+        .chain(&config.project_config.commit_extra_fields)
+        .filter(|field| is_template_referenced(template, &field.name))
+        .map(|field| (field.name.clone(), String::new()))
+        .collect();
+    let extra_names: Vec<&str> = extra_values.keys().map(String::as_str).collect();
+    validate_branch_template(template, &extra_names)
+        .map_err(|e| RonaError::InvalidInput(format!("Branch template validation error: {e}")))?;
+
+    let types = branch_effective_types(config);
+    let branch_type = match branch_type {
+        Some(branch_type) if types.iter().any(|t| t == branch_type) => branch_type.to_string(),
+        Some(branch_type) => {
+            return Err(RonaError::InvalidInput(format!(
+                "Unknown branch type '{branch_type}'. Valid types are: {}",
+                types.join(", ")
+            )));
+        }
+        None if is_template_referenced(template, "branch_type")
+            || is_template_referenced(template, "type") =>
+        {
+            let index = select_type("Select branch type", &types, config)?;
+            types[index].clone()
+        }
+        None => String::new(),
+    };
+
+    let variables = BranchTemplateVariables::new(
+        branch_type,
+        description.trim().to_string(),
+        ticket.unwrap_or_default().trim().to_string(),
+    )?;
+    let raw_name = process_branch_template(
+        template,
+        &variables,
+        &extra_values,
+        config.project_config.strict_templates,
+    )?;
+    create_templated_branch(&raw_name, no_switch, config)
+}
 
-    let raw_name = process_branch_template(template, &variables, &extra_values)?;
-    let branch_name = sanitize_branch_name(&raw_name);
+/// Sanitizes a rendered branch template, checks it against `branch_pattern`, then
+/// creates the branch and switches to it unless `no_switch` is set.
+///
+/// # Errors
+/// * If the name is empty after sanitization or does not match `branch_pattern`
+/// * If branch creation fails
+fn create_templated_branch(raw_name: &str, no_switch: bool, config: &Config) -> Result<()> {
+    let branch_name = sanitize_branch_name(raw_name);
 
     if branch_name.is_empty() {
         return Err(RonaError::InvalidInput(
@@ -546,6 +1626,17 @@ fn handle_branch(no_switch: bool, config: &Config) -> Result<()> {
         ));
     }
 
+    if let Some(pattern) = &config.project_config.branch_pattern {
+        let regex = regex::Regex::new(pattern).map_err(|e| {
+            RonaError::InvalidInput(format!("branch_pattern: invalid regex '{pattern}': {e}"))
+        })?;
+        if !regex.is_match(&branch_name) {
+            return Err(RonaError::InvalidInput(format!(
+                "Branch name '{branch_name}' does not match branch_pattern '{pattern}'"
+            )));
+        }
+    }
+
     if config.dry_run {
         println!("Would create branch: {branch_name}");
         if no_switch {
@@ -567,135 +1658,642 @@ fn handle_branch(no_switch: bool, config: &Config) -> Result<()> {
     Ok(())
 }
 
-/// Handle the `AddWithExclude` command which adds files to git while excluding specified patterns.
-///
-/// # Arguments
-/// * `exclude` - List of glob patterns for files to exclude from git add
-/// * `config` - Global configuration including verbose and dry-run settings
+/// Handle `branch describe`: store, prompt for or clear the purpose of the current branch.
 ///
 /// # Errors
-/// * If any glob pattern is invalid
-/// * If git add operation fails
-/// * If reading git status fails
-fn handle_add_with_exclude(exclude: &[String], interactive: bool, config: &Config) -> Result<()> {
-    if interactive {
-        return handle_add_interactive(exclude, config);
-    }
+/// * If the current branch cannot be determined
+/// * If the user cancels the prompt
+/// * If git config cannot be written
+fn handle_branch_describe(description: Option<String>, clear: bool, config: &Config) -> Result<()> {
+    let branch = get_current_branch()?;
 
-    let patterns: Vec<Pattern> = exclude
-        .iter()
-        .map(|p| {
-            Pattern::new(p)
-                .map_err(|e| RonaError::InvalidInput(format!("Invalid glob pattern '{p}': {e}")))
-        })
-        .collect::<Result<Vec<Pattern>>>()?;
+    let description = if clear {
+        None
+    } else {
+        let description = match description {
+            Some(description) => description,
+            None => Input::<String>::with_theme(&prompt_theme())
+                .with_prompt(format!("Purpose of {branch}"))
+                .with_initial_text(branch_description(&branch)?.unwrap_or_default())
+                .allow_empty(true)
+                .interact_text()
+                .map_err(|_| RonaError::UserCancelled)?,
+        };
+        Some(description.trim().to_string()).filter(|d| !d.is_empty())
+    };
 
-    git_add_with_exclude_patterns(&patterns, config.verbose, config.dry_run)?;
+    if config.dry_run {
+        match &description {
+            Some(description) => println!("Would describe {branch} as: {description}"),
+            None => println!("Would remove the description of {branch}"),
+        }
+        return Ok(());
+    }
+
+    set_branch_description(&branch, description.as_deref())?;
+    match description {
+        Some(description) => println!("{} Described {branch}: {description}", success_mark()),
+        None => println!("{} Removed the description of {branch}", success_mark()),
+    }
     Ok(())
 }
 
-/// Handle the interactive variant of the add command (`rona -a -i`).
-///
-/// Presents a `MultiSelect` of every file with unstaged changes and stages only
-/// the ones the user selects. Exclude patterns are not used in this mode.
+/// Handle `branch rename`: rename the current branch, then move its remote branch.
 ///
-/// # Arguments
-/// * `exclude` - Patterns passed on the command line (ignored, only used to warn)
-/// * `config` - Global configuration including dry-run settings
+/// When the branch tracks a remote branch, the renamed branch is pushed and set to
+/// track the new remote branch, then the old remote branch is deleted after
+/// confirmation. A remote branch already named like the new branch is never deleted.
 ///
 /// # Errors
-/// * If reading git status fails
-/// * If the user cancels the prompt
-/// * If staging the selected files fails
-fn handle_add_interactive(exclude: &[String], config: &Config) -> Result<()> {
-    if !exclude.is_empty() {
-        println!(
-            "{} Exclude patterns are ignored in interactive mode (-i).",
-            "WARNING:".yellow().bold()
-        );
+/// * If not on a branch, or the new name is the current one
+/// * If renaming, pushing or deleting fails
+#[allow(clippy::fn_params_excessive_bools)]
+fn handle_branch_rename(
+    new_name: &str,
+    local: bool,
+    yes: bool,
+    keep_old: bool,
+    config: &Config,
+) -> Result<()> {
+    ensure_outside_commit_hook("branch")?;
+
+    let old_name = get_current_branch()?;
+    if old_name == "HEAD" {
+        return Err(RonaError::InvalidInput(
+            "Not on a branch: switch to the branch to rename first".to_string(),
+        ));
     }
+    if old_name == new_name {
+        return Err(RonaError::InvalidInput(format!(
+            "The current branch is already named '{new_name}'"
+        )));
+    }
+    let upstream = if local {
+        None
+    } else {
+        branch_upstream(&old_name)?
+    };
+    let delete_old = upstream
+        .as_ref()
+        .filter(|(_, remote_branch)| !keep_old && remote_branch != new_name);
 
-    let entries = get_stageable_files()?;
-    if entries.is_empty() {
-        println!("No changes to stage.");
+    if config.dry_run {
+        println!("Would rename {old_name} to {new_name}");
+        if let Some((remote, _)) = &upstream {
+            println!("Would push {new_name} to {remote} and track {remote}/{new_name}");
+        }
+        if let Some((remote, remote_branch)) = delete_old {
+            println!("Would delete {remote}/{remote_branch}");
+        }
         return Ok(());
     }
 
-    let selected = MultiSelect::with_theme(&prompt_theme())
-        .with_prompt("Select files to stage")
-        .items(&entries)
-        .interact_opt()
-        .map_err(|_| RonaError::UserCancelled)?
-        .ok_or(RonaError::UserCancelled)?;
+    git_rename_branch(&old_name, new_name)?;
+    println!("{} Renamed {old_name} to {new_name}", success_mark());
 
-    let paths: Vec<String> = selected
-        .into_iter()
-        .map(|index| entries[index].path.clone())
-        .collect();
-    git_add_files(&paths, config.dry_run)?;
+    let Some((remote, _)) = &upstream else {
+        return Ok(());
+    };
+    git_push_tracking(remote, new_name)?;
+    println!(
+        "{} Pushed {new_name}, now tracking {remote}/{new_name}",
+        success_mark()
+    );
+
+    let Some((remote, remote_branch)) = delete_old else {
+        return Ok(());
+    };
+    let delete = yes
+        || Confirm::with_theme(&prompt_theme())
+            .with_prompt(format!("Delete {remote}/{remote_branch}?"))
+            .default(true)
+            .interact()
+            .unwrap_or(false);
+    if delete {
+        git_delete_remote_branch(remote, remote_branch)?;
+        println!("{} Deleted {remote}/{remote_branch}", success_mark());
+    } else {
+        println!(
+            "Kept {remote}/{remote_branch}. Delete it later with `git push {remote} --delete {remote_branch}`."
+        );
+    }
     Ok(())
 }
 
-/// Handle the Reset command (`rona reset`), unstaging files from the index.
-///
-/// In interactive mode (`-i`) a `MultiSelect` of staged files is shown and only
-/// the selected files are unstaged. Otherwise the explicitly listed files are
-/// unstaged, or every staged file when none are given. Unstaging never touches
-/// the working tree, so local edits are preserved.
-///
-/// # Arguments
-/// * `files` - Explicit files to unstage (ignored in interactive mode)
-/// * `interactive` - Whether to pick files from a checklist
-/// * `config` - Global configuration including dry-run settings
+/// Handle `branch list`: local branches, the current one marked, with the first line
+/// of their description.
 ///
 /// # Errors
-/// * If reading git status fails
-/// * If the user cancels the prompt
-/// * If unstaging the files fails
-fn handle_reset(files: &[String], interactive: bool, config: &Config) -> Result<()> {
-    if interactive {
-        return handle_reset_interactive(config);
+/// * If git cannot be run
+fn handle_branch_list() -> Result<()> {
+    let current = get_current_branch().ok();
+    let descriptions = branch_descriptions()?;
+
+    for branch in get_all_branches()? {
+        let marker = if current.as_deref() == Some(branch.as_str()) {
+            "*".green().to_string()
+        } else {
+            " ".to_string()
+        };
+        match descriptions
+            .get(&branch)
+            .and_then(|description| description.lines().next())
+        {
+            Some(summary) => println!("{marker} {branch}  {}", summary.dimmed()),
+            None => println!("{marker} {branch}"),
+        }
     }
+    Ok(())
+}
 
-    if !files.is_empty() {
-        return git_unstage_files(files, config.dry_run);
+/// Asks for a local branch other than the current one, with a fuzzy finder over
+/// the branch names and the first line of their description.
+///
+/// # Errors
+/// * If there is no other local branch
+/// * If the user cancels the prompt
+fn pick_branch(prompt: &str) -> Result<String> {
+    let current = get_current_branch().ok();
+    let descriptions = branch_descriptions()?;
+    let branches: Vec<String> = get_all_branches()?
+        .into_iter()
+        .filter(|branch| current.as_deref() != Some(branch.as_str()))
+        .collect();
+    if branches.is_empty() {
+        return Err(RonaError::InvalidInput(
+            "No other local branch to pick from".to_string(),
+        ));
     }
 
-    // No files given: unstage everything currently staged.
-    let staged: Vec<String> = get_staged_files()?
-        .into_iter()
-        .map(|entry| entry.path)
+    let labels: Vec<String> = branches
+        .iter()
+        .map(|branch| {
+            descriptions
+                .get(branch)
+                .and_then(|description| description.lines().next())
+                .map_or_else(
+                    || branch.clone(),
+                    |summary| format!("{branch}  {}", summary.dimmed()),
+                )
+        })
         .collect();
-    git_unstage_files(&staged, config.dry_run)
+    let index = select_item(&prompt_theme(), prompt, &labels)
+        .ok()
+        .flatten()
+        .ok_or(RonaError::UserCancelled)?;
+    Ok(branches[index].clone())
 }
 
-/// Handle the interactive variant of the reset command (`rona reset -i`).
-///
-/// Presents a `MultiSelect` of every staged file and unstages only the ones the
-/// user selects.
-///
-/// # Arguments
-/// * `config` - Global configuration including dry-run settings
+/// Handle `branch switch`: check out a local branch, picked when not given.
 ///
 /// # Errors
-/// * If reading git status fails
-/// * If the user cancels the prompt
-/// * If unstaging the selected files fails
-fn handle_reset_interactive(config: &Config) -> Result<()> {
-    let entries = get_staged_files()?;
-    if entries.is_empty() {
-        println!("No staged files to unstage.");
+/// * If the user cancels the picker
+/// * If `git switch` fails
+fn handle_branch_switch(branch: Option<String>, config: &Config) -> Result<()> {
+    ensure_outside_commit_hook("branch")?;
+
+    let branch = match branch {
+        Some(branch) => branch,
+        None => pick_branch("Switch to")?,
+    };
+    if config.dry_run {
+        println!("Would switch to: {branch}");
         return Ok(());
     }
 
-    let selected = MultiSelect::with_theme(&prompt_theme())
-        .with_prompt("Select files to unstage")
-        .items(&entries)
-        .interact_opt()
-        .map_err(|_| RonaError::UserCancelled)?
-        .ok_or(RonaError::UserCancelled)?;
+    crate::git::git_switch(&branch)?;
+    println!("{} Switched to {branch}", success_mark());
+    Ok(())
+}
 
-    let paths: Vec<String> = selected
+/// Handle `branch merge` and `branch rebase`: integrate a local branch, picked when
+/// not given, into the current one.
+///
+/// # Errors
+/// * If the user cancels the picker
+/// * If merging or rebasing is denied by a guard, or fails
+fn handle_branch_integrate(branch: Option<String>, rebase: bool, config: &Config) -> Result<()> {
+    use crate::git::{git_merge, git_rebase};
+
+    ensure_outside_commit_hook("branch")?;
+
+    let current = get_current_branch()?;
+    let branch = match branch {
+        Some(branch) => branch,
+        None if rebase => pick_branch(&format!("Rebase {current} onto"))?,
+        None => pick_branch(&format!("Merge into {current}"))?,
+    };
+
+    if rebase {
+        if config.dry_run {
+            println!("Would rebase {current} onto: {branch}");
+            return Ok(());
+        }
+        git_rebase(&branch, config.verbose)?;
+        println!("{} Rebased {current} onto {branch}", success_mark());
+    } else {
+        if config.dry_run {
+            println!("Would merge {branch} into: {current}");
+            return Ok(());
+        }
+        git_merge(&branch, config.verbose)?;
+        println!("{} Merged {branch} into {current}", success_mark());
+    }
+    Ok(())
+}
+
+/// Handle `branch pull`: pull the upstream changes of the current branch.
+///
+/// # Errors
+/// * If pulling is denied by a guard, or fails
+fn handle_branch_pull(config: &Config) -> Result<()> {
+    ensure_outside_commit_hook("branch")?;
+
+    let current = get_current_branch()?;
+    if config.dry_run {
+        println!("Would pull into: {current}");
+        return Ok(());
+    }
+
+    git_pull(config.verbose)?;
+    println!("{} Pulled into {current}", success_mark());
+    Ok(())
+}
+
+/// Asks for a commit or branch type, each drawn in its `[type_colors]` color.
+///
+/// # Errors
+/// * If the prompt is cancelled
+fn select_type<T: std::fmt::Display>(prompt: &str, types: &[T], config: &Config) -> Result<usize> {
+    select_item(
+        &TypeSelectTheme::new(&config.project_config.type_colors),
+        prompt,
+        types,
+    )
+    .map_err(|_| RonaError::UserCancelled)?
+    .ok_or(RonaError::UserCancelled)
+}
+
+/// Prompts for the commit type of `rona generate`.
+///
+/// When `commit_types` gives any type an emoji or a description, they are shown around
+/// the type names.
+///
+/// # Errors
+/// * If the user cancels the selection
+fn select_commit_type(config: &Config) -> Result<&str> {
+    let Some(types) = config.project_config.commit_types.as_ref().filter(|types| {
+        types
+            .iter()
+            .any(|t| t.emoji.is_some() || t.description.is_some())
+    }) else {
+        let commit_types_vec = config.project_config.commit_types.as_ref().map_or_else(
+            || COMMIT_TYPES.to_vec(),
+            |v| v.iter().map(CommitType::name).collect::<Vec<&str>>(),
+        );
+        let index = select_type("Select commit type", &commit_types_vec, config)?;
+        return Ok(commit_types_vec[index]);
+    };
+
+    let names: Vec<&str> = types.iter().map(CommitType::name).collect();
+    let width = names.iter().map(|name| name.len()).max().unwrap_or(0);
+    let labels: Vec<String> = types
+        .iter()
+        .map(|t| commit_type_label(&t.name, t.emoji.as_deref(), t.description.as_deref(), width))
+        .collect();
+    let theme = TypeSelectTheme::for_described_types(&config.project_config.type_colors, &names);
+    let index = select_item(&theme, "Select commit type", &labels)
+        .map_err(|_| RonaError::UserCancelled)?
+        .ok_or(RonaError::UserCancelled)?;
+    Ok(names[index])
+}
+
+/// Actions offered for the commit selected in `rona browse`.
+const BROWSE_ACTIONS: [&str; 5] = [
+    "View details",
+    "Copy SHA",
+    "Fix up with the staged changes",
+    "Revert",
+    "Back",
+];
+
+/// Handle the `browse` command: pick a commit from recent history and act on it.
+///
+/// The commits are shown in the full-screen browser of [`crate::browse`], or through
+/// prompts in accessibility mode. A fixup or revert changes history, so it ends the
+/// session.
+///
+/// # Errors
+/// * If run from inside a commit hook
+/// * If listing or showing commits fails
+/// * If the fixup or revert fails
+fn handle_browse(limit: usize, config: &Config) -> Result<()> {
+    ensure_outside_commit_hook("browse")?;
+
+    let commits = recent_commits(limit)?;
+    if commits.is_empty() {
+        println!("No commits yet.");
+        return Ok(());
+    }
+    let commit_types: Vec<&str> = config.project_config.commit_types.as_ref().map_or_else(
+        || COMMIT_TYPES.to_vec(),
+        |v| v.iter().map(CommitType::name).collect(),
+    );
+    let style = TypeStyle {
+        template: config
+            .project_config
+            .commit_template
+            .as_deref()
+            .unwrap_or(DEFAULT_COMMIT_TEMPLATE),
+        commit_types: &commit_types,
+        colors: &config.project_config.type_colors,
+    };
+
+    let choice = if is_accessible() {
+        browse_with_prompts(&commits, style, config)?
+    } else {
+        browse(&commits, style, !config.dry_run, copy_to_clipboard)?
+    };
+    match choice {
+        Some(Choice::Fixup(index)) => {
+            let commit = &commits[index];
+            git_commit_fixup(&commit.sha, config.dry_run)?;
+            if !config.dry_run {
+                println!(
+                    "Squash it with `git rebase -i --autosquash {}~1`.",
+                    commit.short_sha
+                );
+            }
+        }
+        Some(Choice::Revert(index)) => git_revert(&commits[index].sha, config.dry_run)?,
+        None => {}
+    }
+    Ok(())
+}
+
+/// `rona browse` in accessibility mode: a numbered list of the commits, then of the
+/// actions on the selected one. Viewing and copying return to the list.
+///
+/// # Errors
+/// * If showing a commit or copying its SHA fails
+fn browse_with_prompts(
+    commits: &[CommitSummary],
+    style: TypeStyle,
+    config: &Config,
+) -> Result<Option<Choice>> {
+    let items: Vec<String> = commits
+        .iter()
+        .map(|c| format!("{} {} ({})", c.short_sha, c.subject, c.relative_date))
+        .collect();
+    let theme = TypeSelectTheme::for_commits(style.colors, style.template, style.commit_types);
+
+    loop {
+        let Some(index) =
+            select_item(&theme, "Select a commit", &items).map_err(|_| RonaError::UserCancelled)?
+        else {
+            return Ok(None);
+        };
+        let commit = &commits[index];
+
+        let action = Select::with_theme(&prompt_theme())
+            .with_prompt(format!("{} {}", commit.short_sha, commit.subject))
+            .items(BROWSE_ACTIONS)
+            .default(0)
+            .interact_opt()
+            .map_err(|_| RonaError::UserCancelled)?;
+
+        match action {
+            Some(0) => {
+                handle_show(&commit.sha, false, config)?;
+                println!();
+            }
+            Some(1) => {
+                copy_to_clipboard(&commit.sha)?;
+                println!("Copied {} to clipboard", commit.sha);
+            }
+            Some(2) if get_staged_files()?.is_empty() => println!(
+                "{} Nothing is staged; stage the fix first (e.g. `rona -a`).",
+                "WARNING:".yellow().bold()
+            ),
+            Some(2) => return Ok(Some(Choice::Fixup(index))),
+            Some(3) => {
+                let confirmed = config.dry_run
+                    || Confirm::with_theme(&prompt_theme())
+                        .with_prompt(format!("Revert {} {}?", commit.short_sha, commit.subject))
+                        .default(false)
+                        .interact()
+                        .unwrap_or(false);
+                if confirmed {
+                    return Ok(Some(Choice::Revert(index)));
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Handle the `AddWithExclude` command which adds files to git while excluding specified patterns.
+///
+/// # Arguments
+/// * `exclude` - List of glob patterns for files to exclude from git add, on top of the
+///   build output of the configured presets
+/// * `config` - Global configuration including verbose and dry-run settings
+///
+/// # Errors
+/// * If any glob pattern is invalid
+/// * If a configured preset is unknown
+/// * If git add operation fails
+/// * If reading git status fails
+fn handle_add_with_exclude(exclude: &[String], config: &Config) -> Result<()> {
+    ensure_outside_commit_hook("add-with-exclude")?;
+
+    let patterns = exclude_patterns(exclude, config)?;
+    if config.json_output && config.dry_run {
+        println!(
+            "{}",
+            to_versioned_json(&staging_preview(&patterns)?, false)?
+        );
+        return Ok(());
+    }
+    git_add_with_exclude_patterns(&patterns, config.verbose, config.dry_run)?;
+    warn_out_of_cone_files();
+    Ok(())
+}
+
+/// The exclude patterns of `rona -a`: those given on the command line, then the build
+/// output of the configured presets.
+///
+/// # Errors
+/// * If any glob pattern is invalid
+/// * If a configured preset is unknown
+fn exclude_patterns(exclude: &[String], config: &Config) -> Result<Vec<Pattern>> {
+    let presets = resolve_presets(&config.project_config.preset)?;
+    exclude
+        .iter()
+        .map(|p| resolve_pattern(p))
+        .collect::<Result<Vec<String>>>()?
+        .into_iter()
+        .chain(staging_patterns(&presets))
+        .map(|p| {
+            Pattern::new(&p)
+                .map_err(|e| RonaError::InvalidInput(format!("Invalid glob pattern '{p}': {e}")))
+        })
+        .collect()
+}
+
+/// Lists the changes outside the sparse-checkout cone, which staging and message
+/// generation leave out. Printed to stderr so `generate --print` output stays clean.
+fn warn_out_of_cone_files() {
+    match get_out_of_cone_files() {
+        Ok(files) if !files.is_empty() => eprintln!(
+            "{} {} change(s) outside the sparse-checkout cone are left out:\n{}\nUse `git sparse-checkout add <dir>` to include them.",
+            "WARNING:".yellow().bold(),
+            files.len(),
+            format_list(&files)
+        ),
+        Ok(_) => {}
+        Err(e) => eprintln!("{} {e}", "WARNING:".yellow().bold()),
+    }
+}
+
+/// Above this many changed files, `rona -a -i` asks for a filter before listing them.
+const FILTER_PROMPT_THRESHOLD: usize = 20;
+
+/// Handle the interactive variant of the add command (`rona -a -i`).
+///
+/// Presents a `MultiSelect` of every file with unstaged changes and stages only
+/// the ones the user selects. Files matching the exclude patterns or the presets'
+/// build output are listed but start deselected. In dry-run mode the selection is
+/// summarized like `rona -a --dry-run`, unselected files counting as excluded.
+///
+/// The list can be narrowed with a fuzzy query, given with `--filter` or asked for
+/// first when there are more than [`FILTER_PROMPT_THRESHOLD`] files.
+///
+/// # Arguments
+/// * `exclude` - Patterns whose files start deselected
+/// * `filter` - Fuzzy query the offered paths must match
+/// * `config` - Global configuration including dry-run settings
+///
+/// # Errors
+/// * If any glob pattern is invalid, or a configured preset is unknown
+/// * If reading git status fails
+/// * If the user cancels the prompt
+/// * If staging the selected files fails
+fn handle_add_interactive(exclude: &[String], filter: Option<&str>, config: &Config) -> Result<()> {
+    ensure_outside_commit_hook("add-with-exclude")?;
+
+    let patterns = exclude_patterns(exclude, config)?;
+    warn_out_of_cone_files();
+    let entries = get_stageable_files()?;
+    if entries.is_empty() {
+        println!("No changes to stage.");
+        return Ok(());
+    }
+
+    let query = match filter {
+        Some(query) => query.to_string(),
+        None if entries.len() > FILTER_PROMPT_THRESHOLD => {
+            Input::<String>::with_theme(&prompt_theme())
+                .with_prompt(format!(
+                    "{} changed files. Filter (fuzzy, empty for all)",
+                    entries.len()
+                ))
+                .allow_empty(true)
+                .interact_text()
+                .map_err(|_| RonaError::UserCancelled)?
+        }
+        None => String::new(),
+    };
+    let entries = fuzzy_filter(entries, &query, |entry| &entry.path);
+    if entries.is_empty() {
+        println!("No changed files match '{}'.", query.trim());
+        return Ok(());
+    }
+
+    let paths: Vec<String> = entries.iter().map(|entry| entry.path.clone()).collect();
+    let defaults: Vec<bool> = excluded_files_mask(&patterns, &paths)?
+        .into_iter()
+        .map(|excluded| !excluded)
+        .collect();
+    let selected = MultiSelect::with_theme(&prompt_theme())
+        .with_prompt("Select files to stage")
+        .items(&entries)
+        .defaults(&defaults)
+        .interact_opt()
+        .map_err(|_| RonaError::UserCancelled)?
+        .ok_or(RonaError::UserCancelled)?;
+
+    let excluded_count = paths.len() - selected.len();
+    let paths: Vec<String> = selected
+        .into_iter()
+        .map(|index| paths[index].clone())
+        .collect();
+    git_add_files(&paths, excluded_count, config.dry_run)?;
+    Ok(())
+}
+
+/// Handle the Reset command (`rona reset`), unstaging files from the index.
+///
+/// In interactive mode (`-i`) a `MultiSelect` of staged files is shown and only
+/// the selected files are unstaged. Otherwise the explicitly listed files are
+/// unstaged, or every staged file when none are given. Unstaging never touches
+/// the working tree, so local edits are preserved.
+///
+/// # Arguments
+/// * `files` - Explicit files to unstage (ignored in interactive mode)
+/// * `interactive` - Whether to pick files from a checklist
+/// * `config` - Global configuration including dry-run settings
+///
+/// # Errors
+/// * If reading git status fails
+/// * If the user cancels the prompt
+/// * If unstaging the files fails
+fn handle_reset(files: &[String], interactive: bool, config: &Config) -> Result<()> {
+    ensure_outside_commit_hook("reset")?;
+
+    if interactive {
+        return handle_reset_interactive(config);
+    }
+
+    if !files.is_empty() {
+        return git_unstage_files(&file_arguments(files)?, config.dry_run);
+    }
+
+    // No files given: unstage everything currently staged.
+    let staged: Vec<String> = get_staged_files()?
+        .into_iter()
+        .map(|entry| entry.path)
+        .collect();
+    git_unstage_files(&staged, config.dry_run)
+}
+
+/// Handle the interactive variant of the reset command (`rona reset -i`).
+///
+/// Presents a `MultiSelect` of every staged file and unstages only the ones the
+/// user selects.
+///
+/// # Arguments
+/// * `config` - Global configuration including dry-run settings
+///
+/// # Errors
+/// * If reading git status fails
+/// * If the user cancels the prompt
+/// * If unstaging the selected files fails
+fn handle_reset_interactive(config: &Config) -> Result<()> {
+    let entries = get_staged_files()?;
+    if entries.is_empty() {
+        println!("No staged files to unstage.");
+        return Ok(());
+    }
+
+    let selected = MultiSelect::with_theme(&prompt_theme())
+        .with_prompt("Select files to unstage")
+        .items(&entries)
+        .interact_opt()
+        .map_err(|_| RonaError::UserCancelled)?
+        .ok_or(RonaError::UserCancelled)?;
+
+    let paths: Vec<String> = selected
         .into_iter()
         .map(|index| entries[index].path.clone())
         .collect();
@@ -722,6 +2320,8 @@ fn handle_reset_interactive(config: &Config) -> Result<()> {
 /// * If the user cancels the prompt
 /// * If restoring the files fails
 fn handle_restore(files: &[String], interactive: bool, yes: bool, config: &Config) -> Result<()> {
+    ensure_outside_commit_hook("restore")?;
+
     let paths: Vec<String> = if interactive {
         let entries = get_restorable_files()?;
         if entries.is_empty() {
@@ -747,7 +2347,7 @@ fn handle_restore(files: &[String], interactive: bool, yes: bool, config: &Confi
         );
         return Ok(());
     } else {
-        files.to_vec()
+        file_arguments(files)?
     };
 
     if paths.is_empty() {
@@ -776,6 +2376,41 @@ fn handle_restore(files: &[String], interactive: bool, yes: bool, config: &Confi
     git_restore_files(&paths, config.dry_run)
 }
 
+/// Copies text to the system clipboard.
+///
+/// # Errors
+/// * If the clipboard cannot be accessed or written
+fn copy_to_clipboard(text: &str) -> Result<()> {
+    use arboard::Clipboard;
+    let mut clipboard = Clipboard::new().map_err(|e| {
+        RonaError::Io(std::io::Error::other(format!(
+            "Failed to access clipboard: {e}"
+        )))
+    })?;
+
+    clipboard.set_text(text).map_err(|e| {
+        RonaError::Io(std::io::Error::other(format!(
+            "Failed to copy to clipboard: {e}"
+        )))
+    })
+}
+
+/// Reads text from the system clipboard.
+fn read_from_clipboard() -> Result<String> {
+    use arboard::Clipboard;
+    let mut clipboard = Clipboard::new().map_err(|e| {
+        RonaError::Io(std::io::Error::other(format!(
+            "Failed to access clipboard: {e}"
+        )))
+    })?;
+
+    clipboard.get_text().map_err(|e| {
+        RonaError::Io(std::io::Error::other(format!(
+            "Failed to read from clipboard: {e}"
+        )))
+    })
+}
+
 /// Handle the Commit command which commits changes using the message from `commit_message.md`.
 ///
 /// # Arguments
@@ -784,10 +2419,12 @@ fn handle_restore(files: &[String], interactive: bool, yes: bool, config: &Confi
 /// * `unsigned` - Whether to create an unsigned commit (skips -S flag)
 /// * `yes` - Whether to skip the confirmation prompt
 /// * `copy` - Whether to copy the commit message to clipboard instead of committing
+/// * `inline` - A message given on the command line, used instead of `commit_message.md`
 /// * `config` - Global configuration including verbose and dry-run settings
 ///
 /// # Errors
 /// * If git commit operation fails
+/// * If the inline message is empty, its type is unknown or the template is invalid
 /// * If push is true and git push operation fails
 /// * If commit message file doesn't exist or cannot be read
 /// * If user cancels the commit confirmation
@@ -799,38 +2436,56 @@ fn handle_commit(
     unsigned: bool,
     yes: bool,
     copy: bool,
+    inline: Option<InlineMessage<'_>>,
     config: &Config,
 ) -> Result<()> {
-    // Read the commit message file
-    let project_root = get_top_level_path()?;
-    let commit_file_path = project_root.join(COMMIT_MESSAGE_FILE_PATH);
-
-    if !commit_file_path.exists() {
-        return Err(crate::errors::RonaError::Git(
-            crate::errors::GitError::CommitMessageNotFound,
-        ));
+    ensure_outside_commit_hook("commit")?;
+    // Refuse before asking for confirmation.
+    if !config.dry_run {
+        ensure_writable("commit")?;
+        ensure_allowed(Feature::Commit)?;
     }
+    let signing = Signing::new(unsigned, config.project_config.require_signed)?;
 
-    let commit_message = read_to_string(&commit_file_path)?;
+    let from_file = inline.is_none();
+    let commit_message = if let Some(inline) = &inline {
+        inline_commit_message(inline, args, config)?
+    } else {
+        // Read the commit message file
+        let project_root = get_top_level_path()?;
+        let commit_file_path = project_root.join(COMMIT_MESSAGE_FILE_PATH);
+
+        if !commit_file_path.exists() {
+            return Err(crate::errors::RonaError::Git(
+                crate::errors::GitError::CommitMessageNotFound,
+            ));
+        }
+
+        strip_message_comments(
+            &read_to_string(&commit_file_path)?,
+            config.project_config.message_comments(),
+        )
+    };
 
     // If copy flag is set, copy to clipboard and exit
     if copy {
-        use arboard::Clipboard;
-        let mut clipboard = Clipboard::new().map_err(|e| {
-            crate::errors::RonaError::Io(std::io::Error::other(format!(
-                "Failed to access clipboard: {e}"
-            )))
-        })?;
-
-        clipboard.set_text(&commit_message).map_err(|e| {
-            crate::errors::RonaError::Io(std::io::Error::other(format!(
-                "Failed to copy to clipboard: {e}"
-            )))
-        })?;
-
+        copy_to_clipboard(&commit_message)?;
         println!("Commit message copied to clipboard");
         return Ok(());
     }
+    if config.json_output && config.dry_run {
+        return print_commit_preview(&commit_message, args, signing, push, config);
+    }
+
+    if !check_commit_size(yes, config)? || !check_upstream(yes, config)? {
+        println!("Commit cancelled.");
+        return Ok(());
+    }
+    let file_message = from_file.then(|| commit_message.clone());
+    let with_todos = apply_todo_policy(&commit_message, args, config)?;
+    let commit_message = with_todos.clone().unwrap_or(commit_message);
+    let with_change_id = add_change_id(&commit_message, args, config)?;
+    let commit_message = with_change_id.clone().unwrap_or(commit_message);
 
     // Show confirmation prompt unless --yes flag is set or in dry-run mode
     if !yes && !config.dry_run {
@@ -848,31 +2503,455 @@ fn handle_commit(
         }
     }
 
-    git_commit(args, unsigned, config.dry_run)?;
+    if inline.is_some() || with_todos.is_some() || with_change_id.is_some() {
+        git_commit_with_message(&commit_message, args, signing, config.dry_run)?;
+    } else {
+        git_commit(
+            args,
+            signing,
+            config.project_config.message_comments(),
+            config.dry_run,
+        )?;
+    }
+    if let Some(file_message) = file_message
+        && !config.dry_run
+    {
+        record_message_edits(&file_message, config)?;
+    }
 
     if push {
-        git_push(args, config.verbose, config.dry_run)?;
+        if !check_before_push(args, config) {
+            println!("Push cancelled.");
+            return Ok(());
+        }
+        push_and_notify(args, config)?;
     }
     Ok(())
 }
 
-/// Handle the Completion command
-#[doc(hidden)]
-fn handle_completion(shell: Shell) {
-    let mut cmd = build_cli();
-    generate(shell, &mut cmd, "rona", &mut io::stdout());
-
-    // Add custom completions for fish shell
-    if matches!(shell, Shell::Fish) {
-        print_fish_custom_completions();
-    }
-}
+/// Prints what `rona commit --dry-run` would commit as JSON, for `--output json`,
+/// followed by the push preview when `push` is set.
+///
+/// # Errors
+/// * If `on_commit = "block"` and the staged changes add TODO markers
+/// * If the staged changes cannot be read or measured
+/// * If the push preview fails
+fn print_commit_preview(
+    message: &str,
+    args: &[String],
+    signing: Signing,
+    push: bool,
+    config: &Config,
+) -> Result<()> {
+    let message = apply_todo_policy(message, args, config)?.unwrap_or_else(|| message.to_string());
+    let mut preview = commit_preview(&message, args, signing)?;
+    let exceeded = commit_size_excess(config)?;
+    if !exceeded.is_empty() {
+        preview
+            .warnings
+            .push(format!("This commit is large: {}", exceeded.join(", ")));
+    }
+    if let Some((upstream, ahead)) = upstream_ahead() {
+        preview
+            .warnings
+            .push(upstream_ahead_warning(&upstream, ahead));
+    }
+    println!("{}", to_versioned_json(&preview, false)?);
+
+    if push {
+        push_and_notify(args, config)?;
+    }
+    Ok(())
+}
+
+/// Compares the committed `commit_message.md` with the version `rona generate` wrote.
+/// When it was edited, both are archived and, in verbose mode, the edits are shown.
+///
+/// # Errors
+/// * If the generated copy or the archive cannot be read or written
+fn record_message_edits(committed: &str, config: &Config) -> Result<()> {
+    let Some(generated) = take_generated_message()? else {
+        return Ok(());
+    };
+    let diff = message_diff(&generated, committed);
+    if diff.is_empty() {
+        return Ok(());
+    }
+
+    if config.verbose {
+        println!("Edits to the generated message:");
+        for line in &diff {
+            match line {
+                DiffLine::Removed(text) => println!("{}", format!("- {text}").trim_end().red()),
+                DiffLine::Added(text) => println!("{}", format!("+ {text}").trim_end().green()),
+            }
+        }
+    }
+    archive_message(&generated, committed)
+}
+
+/// The message with a Gerrit `Change-Id:` trailer when `change_id` is enabled and the
+/// message has none. Not computed in dry runs, which must not write the index tree.
+///
+/// # Errors
+/// * If git cannot provide the inputs of the id
+fn add_change_id(message: &str, args: &[String], config: &Config) -> Result<Option<String>> {
+    if !config.project_config.change_id || config.dry_run {
+        return Ok(None);
+    }
+    with_change_id(message, args.iter().any(|arg| arg == "--amend"))
+}
+
+/// Checks the staged changes against the `[commit_size]` limits.
+///
+/// Over a limit, a warning suggests splitting the commit. With `confirm = true` the
+/// user is also asked whether to commit anyway, unless `--yes` or `--dry-run` is set.
+///
+/// # Errors
+/// * If an `exempt` pattern is not a valid glob
+/// * If the staged changes cannot be measured
+///
+/// # Returns
+/// * `false` when the user declined to commit
+fn check_commit_size(yes: bool, config: &Config) -> Result<bool> {
+    let exceeded = commit_size_excess(config)?;
+    if exceeded.is_empty() {
+        return Ok(true);
+    }
+
+    println!(
+        "{} This commit is large: {}.\nConsider splitting it: `rona reset -i` unstages files and `rona -a -i` stages a subset.",
+        "WARNING:".yellow().bold(),
+        exceeded.join(", ")
+    );
+    if !config.project_config.commit_size.confirm || yes || config.dry_run {
+        return Ok(true);
+    }
+    Ok(Confirm::with_theme(&prompt_theme())
+        .with_prompt("Commit anyway?")
+        .default(false)
+        .interact()
+        .unwrap_or(false))
+}
+
+/// The `[commit_size]` limits the staged changes exceed, e.g. `12 files (max 10)`.
+///
+/// # Errors
+/// * If an `exempt` pattern is not a valid glob
+/// * If the staged changes cannot be measured
+fn commit_size_excess(config: &Config) -> Result<Vec<String>> {
+    let limits = &config.project_config.commit_size;
+    if limits.max_files.is_none() && limits.max_insertions.is_none() {
+        return Ok(Vec::new());
+    }
+
+    let exempt = limits
+        .exempt
+        .iter()
+        .map(|p| {
+            Pattern::new(p).map_err(|e| {
+                RonaError::InvalidInput(format!("commit_size.exempt: invalid pattern '{p}': {e}"))
+            })
+        })
+        .collect::<Result<Vec<Pattern>>>()?;
+    let size = staged_size(&exempt)?;
+
+    let mut exceeded = Vec::new();
+    if let Some(max) = limits.max_files.filter(|max| size.files > *max) {
+        exceeded.push(format!("{} files (max {max})", size.files));
+    }
+    if let Some(max) = limits.max_insertions.filter(|max| size.insertions > *max) {
+        exceeded.push(format!("{} insertions (max {max})", size.insertions));
+    }
+    Ok(exceeded)
+}
+
+/// Choices offered when the upstream has commits the current branch does not.
+const UPSTREAM_ACTIONS: [&str; 3] = [
+    "Commit anyway",
+    "Sync first: pull the upstream commits, then commit",
+    "Cancel",
+];
+
+/// Warns when the upstream is ahead of the current branch, as the push following the
+/// commit would be rejected. Uses the remote-tracking branch from the last fetch.
+///
+/// Unless `--yes` or `--dry-run` is set, the user can sync right away or cancel.
+///
+/// # Errors
+/// * If syncing fails, e.g. when the staged changes conflict with the pulled commits
+///
+/// # Returns
+/// * `false` when the user cancelled the commit
+fn check_upstream(yes: bool, config: &Config) -> Result<bool> {
+    let Some((upstream, ahead)) = upstream_ahead() else {
+        return Ok(true);
+    };
+
+    println!(
+        "{} {}",
+        "WARNING:".yellow().bold(),
+        upstream_ahead_warning(&upstream, ahead)
+    );
+    if yes || config.dry_run {
+        return Ok(true);
+    }
+
+    let action = Select::with_theme(&prompt_theme())
+        .with_prompt("How to continue?")
+        .items(UPSTREAM_ACTIONS)
+        .default(0)
+        .interact_opt()
+        .unwrap_or(None);
+    match action {
+        Some(0) => Ok(true),
+        Some(1) => {
+            git_pull(config.verbose)?;
+            println!("{} Synced with {upstream}", success_mark());
+            Ok(true)
+        }
+        _ => Ok(false),
+    }
+}
+
+/// The warning shown when the upstream has `ahead` commits the branch does not.
+fn upstream_ahead_warning(upstream: &str, ahead: usize) -> String {
+    format!(
+        "{upstream} is {ahead} commit{} ahead — consider pulling first (`rona sync`).",
+        if ahead == 1 { "" } else { "s" }
+    )
+}
+
+/// Applies the `[todo]` `on_commit` policy to the markers the staged changes add.
+///
+/// `--no-verify` skips the check, as it skips the commit hooks.
+///
+/// # Errors
+/// * If `on_commit = "block"` and markers were added
+/// * If the staged changes cannot be read
+///
+/// # Returns
+/// * The message with a `TODOs:` section appended, when `on_commit = "append"` added one
+fn apply_todo_policy(message: &str, args: &[String], config: &Config) -> Result<Option<String>> {
+    let policy = &config.project_config.todo;
+    if policy.on_commit == TodoAction::Ignore || args.iter().any(|arg| arg == "--no-verify") {
+        return Ok(None);
+    }
+
+    let todos = staged_todos(&policy.markers)?;
+    if todos.is_empty() {
+        return Ok(None);
+    }
+    match policy.on_commit {
+        TodoAction::Ignore => Ok(None),
+        TodoAction::Block => {
+            print_todos(&todos);
+            Err(RonaError::InvalidInput(format!(
+                "{} new TODO marker(s) in the staged changes; resolve them or commit with --no-verify",
+                todos.len()
+            )))
+        }
+        TodoAction::Append if message.contains(TODO_SECTION_HEADING) => Ok(None),
+        TodoAction::Append => Ok(Some(format!(
+            "{}\n\n{}\n",
+            message.trim_end(),
+            todo_section(&todos)
+        ))),
+    }
+}
+
+/// Prints staged TODO markers as `file:line: text`.
+fn print_todos(todos: &[TodoMarker]) {
+    for todo in todos {
+        println!("  {}:{}: {}", todo.file.cyan(), todo.line, todo.text);
+    }
+}
+
+/// A one-line commit message given with `rona commit -m`.
+#[derive(Debug, Clone, Copy)]
+struct InlineMessage<'a> {
+    text: &'a str,
+    commit_type: Option<&'a str>,
+    no_commit_number: bool,
+}
+
+/// Runs an inline message through the commit template.
+///
+/// The commit number is the next one, or the current one when amending.
+///
+/// # Errors
+/// * If the message is empty or the commit type is not configured
+/// * If the template is invalid
+/// * If the branch or commit count cannot be read
+fn inline_commit_message(
+    inline: &InlineMessage<'_>,
+    args: &[String],
+    config: &Config,
+) -> Result<String> {
+    if inline.text.trim().is_empty() {
+        return Err(RonaError::InvalidInput(
+            "The commit message is empty".to_string(),
+        ));
+    }
+
+    let commit_types: Vec<&str> = config.project_config.commit_types.as_ref().map_or_else(
+        || COMMIT_TYPES.to_vec(),
+        |v| v.iter().map(CommitType::name).collect(),
+    );
+    let branch = get_current_branch()?;
+    let commit_type = match inline.commit_type {
+        Some(commit_type) if commit_types.contains(&commit_type) => commit_type,
+        Some(commit_type) => {
+            return Err(RonaError::InvalidInput(format!(
+                "Unknown commit type '{commit_type}'. Available: {}",
+                commit_types.join(", ")
+            )));
+        }
+        None => default_commit_type(&commit_types, &branch),
+    };
+    let commit_number = if inline.no_commit_number {
+        None
+    } else if args.iter().any(|arg| arg == "--amend") {
+        Some(commit_nb(commit_type, config)?)
+    } else {
+        next_commit_number(false, commit_type, config)?
+    };
+
+    render_commit_template(
+        commit_number,
+        commit_type.to_string(),
+        &commit_types,
+        &branch,
+        inline.text.trim().to_string(),
+        config,
+    )
+}
+
+/// Renders a message through the commit template, leaving extra fields empty.
+///
+/// # Errors
+/// * If the template is invalid
+fn render_commit_template(
+    commit_number: Option<u32>,
+    commit_type: String,
+    commit_types: &[&str],
+    branch: &str,
+    message: String,
+    config: &Config,
+) -> Result<String> {
+    let template = config
+        .project_config
+        .commit_template
+        .as_deref()
+        .unwrap_or(DEFAULT_COMMIT_TEMPLATE);
+    let extra_values: HashMap<String, String> = config
+        .project_config
+        .commit_extra_fields
+        .iter()
+        .map(|f| (f.name.clone(), String::new()))
+        .collect();
+    let extra_names: Vec<&str> = extra_values.keys().map(String::as_str).collect();
+    validate_template(template, &extra_names)
+        .map_err(|e| RonaError::InvalidInput(format!("Invalid commit template: {e}")))?;
+
+    let commit_emoji = config.project_config.commit_emoji(&commit_type);
+    let variables = TemplateVariables::new(
+        commit_number,
+        commit_type,
+        format_branch_name(commit_types, branch, &config.project_config.branch_name),
+        message,
+        config.project_config.ticket_pattern.as_deref(),
+    )?
+    .with_date_formats(
+        config.project_config.date_format.as_deref(),
+        config.project_config.time_format.as_deref(),
+        config.project_config.timezone.as_deref(),
+        config.project_config.lang.as_deref(),
+    )
+    .with_commit_emoji(commit_emoji)
+    .with_impact(&config.project_config.impact)
+    .with_changed_symbols(uses_variable(template, "changed_symbols"));
+    process_template(
+        template,
+        &variables,
+        &extra_values,
+        config.project_config.strict_templates,
+    )
+}
+
+/// The commit type used when none is picked: the one inferred from the branch
+/// prefix, falling back to the first configured type.
+fn default_commit_type<'a>(commit_types: &[&'a str], branch: &str) -> &'a str {
+    infer_commit_type(commit_types, branch)
+        .or_else(|| commit_types.first().copied())
+        .unwrap_or_default()
+}
+
+/// The completion script for `shell`, as `rona completion <shell>` prints it.
+fn completion_script(shell: Shell) -> String {
+    let mut cmd = build_cli();
+    let mut script = Vec::new();
+    generate(shell, &mut cmd, "rona", &mut script);
+    let mut script = String::from_utf8_lossy(&script).into_owned();
+
+    // Add custom completions for fish shell
+    if matches!(shell, Shell::Fish) {
+        script.push_str(FISH_CUSTOM_COMPLETIONS);
+    }
+    script
+}
+
+/// Handle `completion check`, which reports stale or missing completions with the
+/// commands that fix them.
+///
+/// # Errors
+/// * If a problem is found, after all of them are listed
+fn handle_completion_check(shell: Shell) -> Result<()> {
+    use crate::completion::{
+        ShellDirs, check_status_helper, find_installations, installation_findings,
+    };
+
+    let dirs = ShellDirs::from_env()
+        .ok_or_else(|| RonaError::InvalidInput("Could not find the home directory".to_string()))?;
+    let installations = find_installations(shell, &dirs, &completion_script(shell));
+    for installation in installations.iter().filter(|i| i.is_current()) {
+        println!(
+            "{} {} is up to date",
+            success_mark(),
+            installation.path().display()
+        );
+    }
+
+    let mut findings = installation_findings(shell, &dirs, &installations);
+    match check_status_helper() {
+        Ok(count) => println!(
+            "{} `rona -l` works in this directory ({count} files)",
+            success_mark()
+        ),
+        Err(finding) => findings.push(finding),
+    }
+
+    if findings.is_empty() {
+        return Ok(());
+    }
+    for finding in &findings {
+        println!("{} {}", "WARNING:".yellow().bold(), finding.problem);
+        if let Some(fix) = &finding.fix {
+            println!("  fix: {fix}");
+        }
+    }
+    Err(RonaError::InvalidInput(format!(
+        "{} {shell} completion problem(s) found",
+        findings.len()
+    )))
+}
 
 /// Prompt the commit message and any configured extra fields in the order defined by
 /// `field_order`.
 ///
 /// The reserved name `"message"` positions the built-in message prompt among the extra
-/// fields. Extra fields not listed in `field_order` are appended after all listed items.
+/// fields. The message prompt asks for the subject, then for optional body paragraphs;
+/// the returned message joins them with blank lines (see `{subject}` and `{body}`). Extra fields not listed in `field_order` are appended after all listed items.
 /// When `field_order` is empty the default order is: extra fields first, then message.
 ///
 /// # Errors
@@ -882,6 +2961,7 @@ fn prompt_interactive_fields(
     field_order: &[String],
     message_prefetch: Option<&MessagePrefetchConfig>,
     message_config: Option<&BuiltInFieldConfig>,
+    expand_snippet: &SnippetExpander<'_>,
 ) -> Result<(String, HashMap<String, String>)> {
     const MESSAGE_KEY: &str = "message";
 
@@ -954,7 +3034,12 @@ fn prompt_interactive_fields(
                     .interact_text()
                     .map_err(|_| RonaError::UserCancelled)?
             };
-            message = Some(value);
+            let body = prompt_body(expand_snippet, &extra_values)?;
+            message = Some(if body.is_empty() {
+                value
+            } else {
+                format!("{}\n\n{body}", value.trim())
+            });
         } else if let Some(field) = extra_fields.iter().find(|f| f.name == *name)
             && let Some(value) = prompt_extra_field(field)?
         {
@@ -968,6 +3053,49 @@ fn prompt_interactive_fields(
     Ok((message, extra_values))
 }
 
+/// Expands a `/name` snippet reference in the body prompt, given the extra field values
+/// entered so far. Returns `None` when the input is not a snippet reference.
+type SnippetExpander<'a> = dyn Fn(&str, &HashMap<String, String>) -> Result<Option<String>> + 'a;
+
+/// Prompt optional body paragraphs until an empty one is entered.
+///
+/// A paragraph consisting only of `/name` is replaced by the rendered snippet.
+///
+/// # Returns
+/// The paragraphs separated by blank lines, or an empty string when none was entered.
+///
+/// # Errors
+/// Returns an error if a prompt is cancelled or a snippet fails to render.
+fn prompt_body(
+    expand_snippet: &SnippetExpander<'_>,
+    extra_values: &HashMap<String, String>,
+) -> Result<String> {
+    let theme = prompt_theme();
+    let mut paragraphs = Vec::new();
+    loop {
+        let prompt = if paragraphs.is_empty() {
+            "Body (optional, Enter to skip)"
+        } else {
+            "Next paragraph (Enter to finish)"
+        };
+        let paragraph = Input::<String>::with_theme(&theme)
+            .with_prompt(prompt)
+            .allow_empty(true)
+            .interact_text()
+            .map_err(|_| RonaError::UserCancelled)?;
+        if paragraph.trim().is_empty() {
+            return Ok(paragraphs.join("\n\n"));
+        }
+        match expand_snippet(&paragraph, extra_values)? {
+            Some(snippet) => {
+                println!("{}", snippet.dimmed());
+                paragraphs.push(snippet);
+            }
+            None => paragraphs.push(paragraph.trim().to_string()),
+        }
+    }
+}
+
 /// The default commit-message template used when none is configured.
 ///
 /// The conditional block `{?commit_number}...{/commit_number}` is only included when
@@ -975,247 +3103,3546 @@ fn prompt_interactive_fields(
 const DEFAULT_COMMIT_TEMPLATE: &str =
     "{?commit_number}[{commit_number}] {/commit_number}({commit_type} on {branch_name}) {message}";
 
-/// Handle the Generate command which creates a new commit message file.
+/// The branch name template used when `branch_template` is not configured.
+const DEFAULT_BRANCH_TEMPLATE: &str = "{branch_type}/{description}";
+
+/// The directory `rona format-patch` writes to when `[format_patch]` sets none.
+const DEFAULT_PATCH_DIR: &str = "patches";
+
+/// The cover letter used when `[format_patch]` sets no template.
+const DEFAULT_COVER_LETTER_TEMPLATE: &str =
+    "{branch}\n\nThis series contains {count} patch(es).\n\n{changes}";
+
+/// Handle the `format-patch` command which exports commits as mbox patch files.
+///
+/// # Errors
+/// * If the cover letter template is invalid
+/// * If the range does not resolve or selects no commits
+/// * If `git format-patch` fails or the cover letter cannot be written
+fn handle_format_patch(
+    range: Option<&str>,
+    count: Option<usize>,
+    output_dir: Option<&str>,
+    cover_letter: bool,
+    config: &Config,
+) -> Result<()> {
+    let settings = &config.project_config.format_patch;
+    let output_dir = output_dir
+        .or(settings.output_dir.as_deref())
+        .unwrap_or(DEFAULT_PATCH_DIR);
+    let cover_template = settings
+        .cover_letter
+        .as_deref()
+        .unwrap_or(DEFAULT_COVER_LETTER_TEMPLATE);
+    if cover_letter {
+        validate_cover_letter_template(cover_template)
+            .map_err(|e| RonaError::InvalidInput(format!("format_patch.cover_letter: {e}")))?;
+    }
+
+    let series = range.map_or_else(|| PatchSeries::Last(count.unwrap_or(1)), PatchSeries::Range);
+    let commits = series_commits(series)?;
+    if commits.is_empty() {
+        return Err(RonaError::InvalidInput(
+            "No commits to export in the selected range".to_string(),
+        ));
+    }
+
+    let commit_types: Vec<&str> = config.project_config.commit_types.as_ref().map_or_else(
+        || COMMIT_TYPES.to_vec(),
+        |v| v.iter().map(CommitType::name).collect(),
+    );
+    let template = config
+        .project_config
+        .commit_template
+        .as_deref()
+        .unwrap_or(DEFAULT_COMMIT_TEMPLATE);
+    let details = commits
+        .iter()
+        .map(|sha| commit_details(sha, template, &commit_types))
+        .collect::<Result<Vec<_>>>()?;
+    let subjects: Vec<String> = details.iter().map(|d| d.subject.clone()).collect();
+
+    if config.dry_run {
+        println!("Would write {} patch(es) to {output_dir}:", subjects.len());
+        println!("{}", format_list(&subjects));
+        if cover_letter {
+            println!("Would write a cover letter (0000-cover-letter.patch)");
+        }
+        return Ok(());
+    }
+
+    let files = format_patches(series, output_dir, cover_letter)?;
+    if let Some(letter) = files.first().filter(|_| cover_letter) {
+        let variables = CoverLetterTemplateVariables::new(
+            get_current_branch()?,
+            &subjects,
+            series_changes(&details),
+        )?;
+        fill_cover_letter(
+            letter,
+            &process_cover_letter_template(cover_template, &variables)?,
+        )?;
+    }
+
+    let files: Vec<String> = files.iter().map(|f| f.display().to_string()).collect();
+    println!("Wrote {} file(s):\n{}", files.len(), format_list(&files));
+    println!("Apply them with `git am --keep-non-patch` to keep the commit headers.");
+    Ok(())
+}
+
+/// Handle the `am` command which applies mailbox patches or resumes a stopped session.
+///
+/// # Errors
+/// * If a session is already in progress when new patches are given
+/// * If a file is not a mailbox or a subject cannot be rewritten
+/// * If `git am` fails, or stops on a patch that does not apply
+fn handle_am(
+    patches: &[String],
+    retemplate: bool,
+    resume: Option<AmResume>,
+    config: &Config,
+) -> Result<()> {
+    if let Some(resume) = resume {
+        if config.dry_run {
+            println!("Would run `git am {}`", resume.flag());
+            return Ok(());
+        }
+        return report_am_outcome(resume_am(resume)?, resume == AmResume::Abort);
+    }
+    if am_in_progress()? {
+        return Err(RonaError::InvalidInput(
+            "A patch session is already in progress: finish it with `rona am --continue`, `--skip` or `--abort`".to_string(),
+        ));
+    }
+
+    let messages = split_mailboxes(patches)?;
+    // `git am` keeps its own copy of the patches, so the split files can go either way.
+    let outcome = prepare_and_apply(&messages, retemplate, config);
+    remove_split_mailboxes()?;
+    outcome?.map_or(Ok(()), |outcome| report_am_outcome(outcome, false))
+}
+
+/// Drops messages without a patch, rewrites subjects if asked, then applies the rest.
+///
+/// # Returns
+/// * `None` in dry-run mode, where nothing is applied
+fn prepare_and_apply(
+    messages: &[std::path::PathBuf],
+    retemplate: bool,
+    config: &Config,
+) -> Result<Option<AmOutcome>> {
+    let mut patches = Vec::new();
+    let mut subjects = Vec::new();
+    for message in messages {
+        let info = mail_info(message)?;
+        if info.has_patch {
+            patches.push(message.clone());
+            subjects.push(info.subject);
+        } else {
+            println!("Skipping '{}': it has no patch", info.subject);
+        }
+    }
+    if patches.is_empty() {
+        return Err(RonaError::InvalidInput(
+            "No patches found in the given files".to_string(),
+        ));
+    }
+
+    if retemplate {
+        let branch = get_current_branch()?;
+        warn_count_caveats()?;
+        let per_type = config.project_config.commit_number_source == CommitNumberSource::PerType;
+        // The commit types of the patches before, whose commits take the numbers in between
+        let mut numbered: Vec<String> = Vec::new();
+        for (patch, subject) in patches.iter().zip(&mut subjects) {
+            let number = |commit_type: &str| {
+                let earlier = numbered
+                    .iter()
+                    .filter(|numbered| !per_type || *numbered == commit_type)
+                    .count();
+                numbered.push(commit_type.to_string());
+                Ok(counted_commits(commit_type, config)?
+                    + 1
+                    + u32::try_from(earlier).unwrap_or(u32::MAX))
+            };
+            *subject = retemplate_subject(subject, &branch, number, config)?;
+            set_message_subject(patch, subject)?;
+        }
+    }
+
+    if config.dry_run {
+        println!(
+            "Would apply {} patch(es):\n{}",
+            patches.len(),
+            format_list(&subjects)
+        );
+        return Ok(None);
+    }
+    apply_messages(&patches).map(Some)
+}
+
+/// Renders a subject through the commit template, as committed on `branch`.
+///
+/// A subject that already matches the template keeps its type and message; any
+/// other subject becomes the message, with the type inferred from the branch.
+/// `commit_number` gives the number of a commit of that type.
+///
+/// # Errors
+/// * If the template is invalid
+/// * If `commit_number` fails
+fn retemplate_subject(
+    subject: &str,
+    branch: &str,
+    commit_number: impl FnOnce(&str) -> Result<u32>,
+    config: &Config,
+) -> Result<String> {
+    let commit_types: Vec<&str> = config.project_config.commit_types.as_ref().map_or_else(
+        || COMMIT_TYPES.to_vec(),
+        |v| v.iter().map(CommitType::name).collect(),
+    );
+    let template = config
+        .project_config
+        .commit_template
+        .as_deref()
+        .unwrap_or(DEFAULT_COMMIT_TEMPLATE);
+
+    let fields = parse_header(subject, template, &commit_types).unwrap_or_default();
+    let field = |name: &str| {
+        fields
+            .iter()
+            .find(|(field, _)| field == name)
+            .map(|(_, value)| value.as_str())
+    };
+    let commit_type = field("commit_type")
+        .unwrap_or_else(|| default_commit_type(&commit_types, branch))
+        .to_string();
+    let message = field("message").unwrap_or(subject).to_string();
+    let commit_number = commit_number(&commit_type)?;
+
+    let rendered = render_commit_template(
+        Some(commit_number),
+        commit_type,
+        &commit_types,
+        branch,
+        message,
+        config,
+    )?;
+    Ok(rendered.lines().next().unwrap_or_default().to_string())
+}
+
+/// Prints how a `git am` run ended.
+///
+/// # Errors
+/// * If a patch stopped the session, after printing how to resume it
+fn report_am_outcome(outcome: AmOutcome, aborted: bool) -> Result<()> {
+    match outcome {
+        AmOutcome::Done if aborted => {
+            println!("Patch session aborted.");
+            Ok(())
+        }
+        AmOutcome::Done => {
+            println!("Patches applied.");
+            Ok(())
+        }
+        AmOutcome::Stopped { reason, conflicted } => {
+            if conflicted.is_empty() {
+                println!(
+                    "{} The patch could not be merged.",
+                    "WARNING:".yellow().bold()
+                );
+            } else {
+                println!(
+                    "{} Conflicts to resolve:\n{}",
+                    "WARNING:".yellow().bold(),
+                    format_list(&conflicted)
+                );
+            }
+            println!(
+                "Resolve them and stage the result with `rona -a`, then run `rona am --continue`.\n\
+                 `rona am --skip` drops this patch; `rona am --abort` restores the branch."
+            );
+            Err(RonaError::Git(crate::errors::GitError::CommandFailed {
+                command: "git am".to_string(),
+                output: reason,
+            }))
+        }
+    }
+}
+
+/// How a backport reaches each target branch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BackportDelivery {
+    /// Commit on the local target branch only
+    Local,
+    /// Commit on the target branch and push it
+    Push,
+    /// Commit on a `backport/` branch, push it and open a pull request
+    PullRequest,
+}
+
+/// Handle the `backport` command which cherry-picks a commit onto release branches.
+///
+/// Every target is attempted; the ones that fail are listed at the end. The current
+/// branch is restored afterwards.
+///
+/// # Errors
+/// * If the commit does not resolve or tracked files have uncommitted changes
+/// * If the backport failed for any target
+fn handle_backport(
+    commit: &str,
+    targets: &[String],
+    remote: &str,
+    delivery: BackportDelivery,
+    unsigned: bool,
+    config: &Config,
+) -> Result<()> {
+    use crate::git::git_switch;
+
+    ensure_outside_commit_hook("backport")?;
+    let sha = resolve_commit(commit)?;
+    if !is_worktree_clean()? {
+        return Err(RonaError::Git(
+            crate::errors::GitError::DirtyWorkingDirectory,
+        ));
+    }
+
+    let commit_types: Vec<&str> = config.project_config.commit_types.as_ref().map_or_else(
+        || COMMIT_TYPES.to_vec(),
+        |v| v.iter().map(CommitType::name).collect(),
+    );
+    let template = config
+        .project_config
+        .commit_template
+        .as_deref()
+        .unwrap_or(DEFAULT_COMMIT_TEMPLATE);
+    let details = commit_details(&sha, template, &commit_types)?;
+    let original_branch = get_current_branch()?;
+
+    let mut failed = Vec::new();
+    for target in targets {
+        let result = backport_to(&details, target, remote, delivery, unsigned, config);
+        if !config.dry_run && get_current_branch()? != original_branch {
+            git_switch(&original_branch)?;
+        }
+        if let Err(e) = result {
+            println!("{} {target}: {e}", "WARNING:".yellow().bold());
+            failed.push(target.clone());
+        }
+    }
+
+    if failed.is_empty() {
+        Ok(())
+    } else {
+        Err(RonaError::InvalidInput(format!(
+            "Backport of {} failed for: {}",
+            &sha[..7],
+            failed.join(", ")
+        )))
+    }
+}
+
+/// Backports a commit onto one target branch.
+///
+/// # Errors
+/// * If the target branch cannot be found or updated
+/// * If the commit does not apply cleanly to it
+/// * If committing, pushing or opening the pull request fails
+fn backport_to(
+    details: &crate::git::show::CommitDetails,
+    target: &str,
+    remote: &str,
+    delivery: BackportDelivery,
+    unsigned: bool,
+    config: &Config,
+) -> Result<()> {
+    use crate::git::git_switch;
+
+    let short_sha = &details.sha[..7];
+    let work_branch = match delivery {
+        BackportDelivery::PullRequest => backport_branch_name(short_sha, target),
+        BackportDelivery::Local | BackportDelivery::Push => target.to_string(),
+    };
+
+    if config.dry_run {
+        let base = if resolve_commit(target).is_ok() {
+            target.to_string()
+        } else {
+            format!("{remote}/{target}")
+        };
+        let subject = retemplate_subject(
+            &details.subject,
+            target,
+            |_| Ok(commit_count(&base)? + 1),
+            config,
+        )?;
+        println!("Would backport {short_sha} to {target} as: {subject}");
+        match delivery {
+            BackportDelivery::Local => {}
+            BackportDelivery::Push => println!("Would push {target} to {remote}"),
+            BackportDelivery::PullRequest => println!(
+                "Would push {work_branch} to {remote} and open a pull request into {target}"
+            ),
+        }
+        return Ok(());
+    }
+
+    fetch_branch(remote, target)?;
+    match prepare_target_branch(remote, target)? {
+        TargetBranch::Created => println!("Created {target} from {remote}/{target}"),
+        TargetBranch::Updated => println!("Fast-forwarded {target} to {remote}/{target}"),
+        TargetBranch::Local => {}
+    }
+    git_switch(target)?;
+    if work_branch != target {
+        git_create_branch(&work_branch)?;
+    }
+
+    warn_count_caveats()?;
+    let subject = retemplate_subject(
+        &details.subject,
+        target,
+        |commit_type| Ok(counted_commits(commit_type, config)? + 1),
+        config,
+    )?;
+    if !cherry_pick_no_commit(&details.sha)? {
+        return Err(RonaError::InvalidInput(format!(
+            "{short_sha} does not apply cleanly; backport it by hand with `git cherry-pick {short_sha}`"
+        )));
+    }
+    if !has_staged_changes()? {
+        println!("{target} already contains the changes of {short_sha}, nothing to backport");
+        return Ok(());
+    }
+    let message = backport_message(&subject, &details.body, &details.sha);
+    let message = add_change_id(&message, &[], config)?.unwrap_or(message);
+    git_commit_with_message(
+        &message,
+        &[],
+        Signing::new(unsigned, config.project_config.require_signed)?,
+        false,
+    )?;
+
+    match delivery {
+        BackportDelivery::Local => {}
+        BackportDelivery::Push => {
+            push_and_notify(&[remote.to_string(), target.to_string()], config)?;
+        }
+        BackportDelivery::PullRequest => {
+            push_and_notify(
+                &["-u".to_string(), remote.to_string(), work_branch.clone()],
+                config,
+            )?;
+            let body = format!("Backport of {} to `{target}`.", details.sha);
+            let repo = remote_url(remote, &config.project_config.url_rewrites)
+                .and_then(|url| RemoteRepo::parse(&url));
+            match open_pull_request(repo.as_ref(), target, &work_branch, &subject, &body)? {
+                PullRequest::Opened(url) => println!("Opened {url}"),
+                PullRequest::CreatePage(url) => {
+                    println!("Create the pull request at {url}");
+                }
+            }
+        }
+    }
+    println!("{} Backported {short_sha} to {target}", success_mark());
+    Ok(())
+}
+
+/// Lets the user pick the commit template when `[templates]` names templates and none
+/// was given with `--template`. The active commit template is listed first as `default`.
+///
+/// # Errors
+/// * If the user cancels the picker
+fn pick_template(config: &mut Config) -> Result<()> {
+    if config.template_option().is_some() {
+        return Ok(());
+    }
+    let mut choices = vec![(DEFAULT_TEMPLATE_NAME, commit_template(config))];
+    choices.extend(
+        config
+            .project_config
+            .named_templates()
+            .filter(|(name, _)| *name != DEFAULT_TEMPLATE_NAME),
+    );
+    if choices.len() < 2 {
+        return Ok(());
+    }
+
+    let width = choices
+        .iter()
+        .map(|(name, _)| name.len())
+        .max()
+        .unwrap_or(0);
+    let labels: Vec<String> = choices
+        .iter()
+        .map(|(name, template)| format!("{name:<width$}  {}", template.dimmed()))
+        .collect();
+    let index = select_item(&prompt_theme(), "Select commit template", &labels)
+        .ok()
+        .flatten()
+        .ok_or(RonaError::UserCancelled)?;
+    if index == 0 {
+        return Ok(());
+    }
+    let name = choices[index].0.to_string();
+    config.apply_template(Some(&name))
+}
+
+/// Handle the Generate command which creates a new commit message file.
+///
+/// # Arguments
+/// * `interactive` - Whether to prompt for commit message in terminal
+/// * `no_commit_number` - Whether to include commit number in message
+/// * `print` - Whether to print the skeleton instead of writing the message file
+/// * `import` - Where to read a ready-made message from, skipping all prompts
+/// * `config` - Global configuration including verbose and dry-run settings
+///
+/// # Errors
+/// * If creating needed files fails
+/// * If generating commit message fails
+/// * If writing commit message fails
+/// * If launching editor fails (in non-interactive mode)
+/// * If the template picker is cancelled
+fn handle_generate(
+    interactive: bool,
+    no_commit_number: bool,
+    print: bool,
+    import: Option<MessageImport>,
+    config: &mut Config,
+) -> Result<()> {
+    if config.dry_run {
+        println!("Would create files: commit_message.md, .commitignore");
+        println!("Would add files to .git/info/exclude");
+        return Ok(());
+    }
+
+    let pending = check_before_generate(print, config)?;
+    if pending == PendingMessage::Cancel {
+        println!("Generate cancelled; {COMMIT_MESSAGE_FILE_PATH} is unchanged.");
+        return Ok(());
+    }
+
+    if !print {
+        create_needed_files()?;
+        if !interactive {
+            let staged: Vec<String> = get_staged_files()?
+                .into_iter()
+                .map(|entry| entry.path)
+                .collect();
+            print_impact(&staged, config);
+        }
+    }
+
+    if let Some(import) = import {
+        handle_message_import(import, no_commit_number, config)?;
+        return keep_pending_message(&pending, config);
+    }
+
+    pick_template(config)?;
+    let commit_type = select_commit_type(config)?;
+
+    if print {
+        warn_out_of_cone_files();
+        print!(
+            "{}",
+            render_commit_message(
+                commit_type,
+                &current_branch_name(config)?,
+                next_commit_number(no_commit_number, commit_type, config)?,
+                &file_bullet(config)?
+            )?
+        );
+        return Ok(());
+    }
+
+    if interactive {
+        show_diff_summary(config)?;
+
+        let referenced_fields = referenced_extra_fields(config);
+        let scope = prompt_scope(&referenced_fields, config)?;
+
+        // In interactive mode, prompt all fields (including message) in configured order
+        let snippets = &config.project_config.snippets;
+        let expand_snippet = |input: &str, values: &HashMap<String, String>| {
+            let Some(name) = snippet_reference(input, snippets) else {
+                return Ok(None);
+            };
+            let variables = snippet_variables(Some(commit_type), no_commit_number, config)?;
+            let extra_values = snippet_extra_values(config, values);
+            render_snippet(snippets, name, &variables, &extra_values).map(Some)
+        };
+        let (message, mut extra_values) = prompt_interactive_fields(
+            &referenced_fields,
+            &config.project_config.commit_fields_order,
+            config.project_config.message_prefetch.as_ref(),
+            config.project_config.commit_message.as_ref(),
+            &expand_snippet,
+        )?;
+        extra_values.extend(scope.map(|scope| ("scope".to_string(), scope)));
+        handle_interactive_mode(
+            commit_type,
+            no_commit_number,
+            &message,
+            &extra_values,
+            config,
+        )?;
+        keep_pending_message(&pending, config)?;
+    } else {
+        // In editor mode, generate the template file first, then open editor
+        warn_out_of_cone_files();
+        generate_commit_message(
+            commit_type,
+            &current_branch_name(config)?,
+            next_commit_number(no_commit_number, commit_type, config)?,
+            &file_bullet(config)?,
+        )?;
+        keep_pending_message(&pending, config)?;
+        handle_editor_mode(config)?;
+    }
+    Ok(())
+}
+
+/// The extra fields referenced in the commit template. Fields inherited from an extended
+/// config (or otherwise configured) but unused by this template are skipped rather than
+/// prompted for a value that would be discarded.
+fn referenced_extra_fields(config: &Config) -> Vec<ExtraField> {
+    let template = commit_template(config);
+    config
+        .project_config
+        .commit_extra_fields
+        .iter()
+        .filter(|f| {
+            let referenced = template.contains(&format!("{{{}}}", f.name))
+                || template.contains(&format!("{{?{}}}", f.name));
+            if !referenced {
+                println!(
+                    "[NOTE] Extra field '{}' is not referenced in the template; skipping.",
+                    f.name
+                );
+            }
+            referenced
+        })
+        .cloned()
+        .collect()
+}
+
+/// Asks for the `{scope}` of the message, offering the one inferred from the staged
+/// files, when the commit template uses it.
+///
+/// Not asked when an extra field named `scope` is prompted instead.
+///
+/// # Errors
+/// * If the staged files cannot be read
+/// * If the prompt is cancelled
+fn prompt_scope(extra_fields: &[ExtraField], config: &Config) -> Result<Option<String>> {
+    if !uses_variable(commit_template(config), "scope")
+        || extra_fields.iter().any(|field| field.name == "scope")
+    {
+        return Ok(None);
+    }
+
+    let staged: Vec<String> = get_staged_files()?
+        .into_iter()
+        .map(|entry| entry.path)
+        .collect();
+    let scope = Input::<String>::with_theme(&prompt_theme())
+        .with_prompt("Scope (empty for none)")
+        .with_initial_text(infer_scope(&staged))
+        .allow_empty(true)
+        .interact_text()
+        .map_err(|_| RonaError::UserCancelled)?;
+    Ok(Some(scope.trim().to_string()))
+}
+
+/// What `rona generate` does with a message left uncommitted in `commit_message.md`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum PendingMessage {
+    /// Nothing is pending, or the user chose to replace it
+    Overwrite,
+    /// Keep the pending message above the new one
+    Append(String),
+    /// Leave the file as it is and stop
+    Cancel,
+}
+
+/// Actions offered for a message left uncommitted in `commit_message.md`.
+const PENDING_MESSAGE_ACTIONS: [&str; 4] = [
+    "View it",
+    "Overwrite it",
+    "Keep it and append the new message below",
+    "Cancel",
+];
+
+/// The `check_before_generate` checks: something must be staged, and a message left in
+/// `commit_message.md` that was never committed is only replaced if the user says so.
+/// Skipped with `--print`, which writes no file.
+///
+/// # Errors
+/// * If nothing is staged
+/// * If a message is pending and no choice is made (for example without a terminal)
+fn check_before_generate(print: bool, config: &Config) -> Result<PendingMessage> {
+    if print || !config.project_config.check_before_generate {
+        return Ok(PendingMessage::Overwrite);
+    }
+    if get_staged_files()?.is_empty() {
+        return Err(RonaError::InvalidInput(
+            "Nothing is staged; stage changes with `rona -a` before generating a message"
+                .to_string(),
+        ));
+    }
+    let Some(message) = pending_commit_message(config.project_config.message_comments())? else {
+        return Ok(PendingMessage::Overwrite);
+    };
+
+    println!(
+        "{} {COMMIT_MESSAGE_FILE_PATH} holds a message that was not committed yet.",
+        "WARNING:".yellow().bold()
+    );
+    loop {
+        let choice = Select::with_theme(&prompt_theme())
+            .with_prompt("What should happen to it?")
+            .items(PENDING_MESSAGE_ACTIONS)
+            .default(0)
+            .interact_opt()
+            .map_err(|_| {
+                RonaError::InvalidInput(format!(
+                    "{COMMIT_MESSAGE_FILE_PATH} holds a message that was not committed yet; commit it or remove it first"
+                ))
+            })?;
+        match choice {
+            Some(0) => println!("\n{}\n", message.trim_end()),
+            Some(1) => return Ok(PendingMessage::Overwrite),
+            Some(2) => return Ok(PendingMessage::Append(message)),
+            _ => return Ok(PendingMessage::Cancel),
+        }
+    }
+}
+
+/// Puts the kept pending message back above the message generate just wrote. Nothing
+/// is done when generate wrote nothing, e.g. after an empty interactive message.
+///
+/// # Errors
+/// * If `commit_message.md` cannot be read or written
+fn keep_pending_message(pending: &PendingMessage, config: &Config) -> Result<()> {
+    let PendingMessage::Append(kept) = pending else {
+        return Ok(());
+    };
+    let path = get_top_level_path()?.join(COMMIT_MESSAGE_FILE_PATH);
+    let generated = read_to_string(&path)?;
+    if strip_message_comments(&generated, config.project_config.message_comments()) == *kept {
+        return Ok(());
+    }
+    std::fs::write(&path, format!("{}\n\n{generated}", kept.trim_end()))?;
+    Ok(())
+}
+
+/// Prints the `[impact]` labels of the staged `paths`, when a glob matches one.
+fn print_impact(paths: &[String], config: &Config) {
+    let labels = impact_labels(&config.project_config.impact, paths);
+    if !labels.is_empty() {
+        println!("{} {}", "Impact:".cyan().bold(), labels.join(", "));
+    }
+}
+
+/// Number of files listed by size in the diff summary of interactive `rona generate`.
+const SUMMARY_LARGEST_FILES: usize = 3;
+
+/// Shows what is staged before the prompts of interactive `rona generate`: the files
+/// by status, the total lines changed, the `[impact]` labels and the largest files. In
+/// a terminal, `d` opens the full diff in git's pager.
+///
+/// # Errors
+/// * If the staged changes cannot be read
+/// * If the diff cannot be shown
+fn show_diff_summary(config: &Config) -> Result<()> {
+    let files = staged_file_diffs()?;
+    if files.is_empty() {
+        return Ok(());
+    }
+
+    let mut statuses: Vec<(&str, usize)> = Vec::new();
+    for file in &files {
+        match statuses
+            .iter_mut()
+            .find(|(status, _)| *status == file.status)
+        {
+            Some((_, count)) => *count += 1,
+            None => statuses.push((file.status, 1)),
+        }
+    }
+    let by_status: Vec<String> = statuses
+        .iter()
+        .map(|(status, count)| {
+            let label = match *status {
+                "new file" => status.green(),
+                "deleted" => status.red(),
+                "renamed" | "copied" => status.cyan(),
+                _ => status.yellow(),
+            };
+            format!("{count} {label}")
+        })
+        .collect();
+    let insertions: usize = files.iter().map(|f| f.insertions).sum();
+    let deletions: usize = files.iter().map(|f| f.deletions).sum();
+    println!(
+        "\n{} {} file(s): {}, {} {}",
+        "Staged:".cyan().bold(),
+        files.len(),
+        by_status.join(", "),
+        format!("+{insertions}").green(),
+        format!("-{deletions}").red()
+    );
+    let paths: Vec<String> = files.iter().map(|f| f.path.clone()).collect();
+    print_impact(&paths, config);
+
+    let mut largest: Vec<&StagedFileDiff> = files.iter().filter(|f| !f.binary).collect();
+    largest.sort_by_key(|f| std::cmp::Reverse(f.insertions + f.deletions));
+    largest.truncate(SUMMARY_LARGEST_FILES);
+    let width = largest.iter().map(|f| f.path.len()).max().unwrap_or(0);
+    for file in largest {
+        println!(
+            "  {:width$}  {} {}",
+            file.path,
+            format!("+{}", file.insertions).green(),
+            format!("-{}", file.deletions).red()
+        );
+    }
+
+    let term = Term::stderr();
+    if !term.is_term() {
+        println!();
+        return Ok(());
+    }
+    loop {
+        println!(
+            "{}",
+            "Press d to view the full diff, any other key to write the message".dimmed()
+        );
+        if term.read_key()? != Key::Char('d') {
+            return Ok(());
+        }
+        show_staged_diff()?;
+    }
+}
+
+/// Builds the per-file bullet format for generated messages from the config.
+///
+/// # Errors
+/// * If `file_bullet` does not contain `{file}`
+fn file_bullet(config: &Config) -> Result<FileBullet> {
+    let project = &config.project_config;
+    let mut bullet = FileBullet {
+        compact: project.file_bullet_compact,
+        checkbox: project.file_bullet_checkbox,
+        annotate: project.file_bullet_annotate,
+        comment_char: project.message_comments().comment_char,
+        ..FileBullet::default()
+    };
+    if let Some(format) = &project.file_bullet {
+        if !format.contains("{file}") {
+            return Err(RonaError::InvalidInput(format!(
+                "file_bullet must contain {{file}}, got \"{format}\""
+            )));
+        }
+        bullet.format.clone_from(format);
+    }
+    Ok(bullet)
+}
+
+/// The `{commit_number}` of the next commit of type `commit_type`, counted per
+/// `commit_number_source`, or `None` with `--no-commit-number`.
+///
+/// # Errors
+/// * If the commit count cannot be read
+fn next_commit_number(
+    no_commit_number: bool,
+    commit_type: &str,
+    config: &Config,
+) -> Result<Option<u32>> {
+    if no_commit_number {
+        return Ok(None);
+    }
+    Ok(Some(commit_nb(commit_type, config)? + 1))
+}
+
+/// The commit count `{commit_number}` is based on, warning when shallow history,
+/// replace refs or grafts make it differ from the stored history.
+///
+/// # Errors
+/// * If the commit count cannot be read
+fn commit_nb(commit_type: &str, config: &Config) -> Result<u32> {
+    warn_count_caveats()?;
+    counted_commits(commit_type, config)
+}
+
+/// Warns when shallow history, replace refs or grafts make the commit count differ
+/// from the stored history.
+///
+/// # Errors
+/// * If the repository cannot be inspected
+fn warn_count_caveats() -> Result<()> {
+    for caveat in count_caveats()? {
+        eprintln!("{} {caveat}", "WARNING:".yellow().bold());
+    }
+    Ok(())
+}
+
+/// The commit count per `commit_number_source` for a commit of type `commit_type`,
+/// without warnings.
+///
+/// # Errors
+/// * If the commit count cannot be read
+fn counted_commits(commit_type: &str, config: &Config) -> Result<u32> {
+    let commit_types: Vec<&str> = config.project_config.commit_types.as_ref().map_or_else(
+        || COMMIT_TYPES.to_vec(),
+        |v| v.iter().map(CommitType::name).collect(),
+    );
+    get_commit_nb(
+        config.project_config.commit_number_source,
+        &NumberedCommit {
+            commit_type,
+            template: commit_template(config),
+            commit_types: &commit_types,
+        },
+    )
+}
+
+/// The `{branch_name}` value for the current branch: without its commit type prefix,
+/// formatted by the `[branch_name]` rules.
+///
+/// # Errors
+/// * If the current branch cannot be read
+fn current_branch_name(config: &Config) -> Result<String> {
+    let commit_types: Vec<&str> = config.project_config.commit_types.as_ref().map_or_else(
+        || COMMIT_TYPES.to_vec(),
+        |v| v.iter().map(CommitType::name).collect(),
+    );
+    Ok(format_branch_name(
+        &commit_types,
+        &get_current_branch()?,
+        &config.project_config.branch_name,
+    ))
+}
+
+/// Where `rona generate` reads a ready-made message from instead of prompting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MessageImport {
+    Stdin,
+    Clipboard,
+}
+
+/// Writes `commit_message.md` from a message read from stdin or the clipboard.
+///
+/// The commit type is inferred from the branch prefix, falling back to the first
+/// configured type. Extra fields are left empty.
+///
+/// # Errors
+/// * If the message cannot be read or is empty
+/// * If the branch or commit count cannot be read
+fn handle_message_import(
+    import: MessageImport,
+    no_commit_number: bool,
+    config: &Config,
+) -> Result<()> {
+    let message = match import {
+        MessageImport::Stdin => {
+            let mut message = String::new();
+            std::io::stdin().read_to_string(&mut message)?;
+            message
+        }
+        MessageImport::Clipboard => read_from_clipboard()?,
+    };
+    let message = message.replace("\r\n", "\n");
+    if message.trim().is_empty() {
+        return Err(RonaError::InvalidInput(match import {
+            MessageImport::Stdin => "No message on stdin".to_string(),
+            MessageImport::Clipboard => "The clipboard is empty".to_string(),
+        }));
+    }
+
+    let commit_types: Vec<&str> = config.project_config.commit_types.as_ref().map_or_else(
+        || COMMIT_TYPES.to_vec(),
+        |v| v.iter().map(CommitType::name).collect(),
+    );
+    let branch = get_current_branch()?;
+    let commit_type = default_commit_type(&commit_types, &branch);
+    let extra_values: HashMap<String, String> = config
+        .project_config
+        .commit_extra_fields
+        .iter()
+        .map(|f| (f.name.clone(), String::new()))
+        .collect();
+
+    handle_interactive_mode(
+        commit_type,
+        no_commit_number,
+        &message,
+        &extra_values,
+        config,
+    )
+}
+
+/// Handle interactive mode for generate command
+fn handle_interactive_mode(
+    commit_type: &str,
+    no_commit_number: bool,
+    message: &str,
+    extra_values: &HashMap<String, String>,
+    config: &Config,
+) -> Result<()> {
+    use std::fs;
+
+    let project_root = get_top_level_path()?;
+    let commit_file_path = project_root.join(COMMIT_MESSAGE_FILE_PATH);
+
+    if message.trim().is_empty() {
+        println!(
+            "{} Empty message provided. Exiting.",
+            "WARNING:".yellow().bold()
+        );
+        return Ok(());
+    }
+
+    let branch_name = current_branch_name(config)?;
+    let commit_number = next_commit_number(no_commit_number, commit_type, config)?;
+
+    // Get template from config or use default with conditional syntax
+    let template = config
+        .project_config
+        .commit_template
+        .as_deref()
+        .unwrap_or(DEFAULT_COMMIT_TEMPLATE);
+
+    // Validate template (including any extra field variable names)
+    let extra_names: Vec<&str> = extra_values.keys().map(String::as_str).collect();
+    if let Err(e) = validate_template(template, &extra_names) {
+        if config.project_config.strict_templates {
+            return Err(RonaError::InvalidInput(format!(
+                "Invalid commit template: {e}"
+            )));
+        }
+        println!(
+            "{} Template validation error: {e}",
+            "WARNING:".yellow().bold()
+        );
+        println!("Using fallback format...");
+        let formatted_message = if no_commit_number {
+            format!("({} on {}) {}", commit_type, branch_name, message.trim())
+        } else {
+            format!(
+                "[{}] ({} on {}) {}",
+                commit_number.unwrap_or(0),
+                commit_type,
+                branch_name,
+                message.trim()
+            )
+        };
+        fs::write(&commit_file_path, &formatted_message)?;
+        save_generated_message(&formatted_message)?;
+        println!("\n{} Commit message created!", success_mark());
+        println!("Message: {formatted_message}");
+        return Ok(());
+    }
+
+    // Create template variables
+    let variables = TemplateVariables::new(
+        commit_number,
+        commit_type.to_string(),
+        branch_name,
+        message.trim().to_string(),
+        config.project_config.ticket_pattern.as_deref(),
+    )?
+    .with_date_formats(
+        config.project_config.date_format.as_deref(),
+        config.project_config.time_format.as_deref(),
+        config.project_config.timezone.as_deref(),
+        config.project_config.lang.as_deref(),
+    )
+    .with_commit_emoji(config.project_config.commit_emoji(commit_type))
+    .with_impact(&config.project_config.impact)
+    .with_changed_symbols(uses_variable(template, "changed_symbols"));
+
+    // Process template (extra_values are substituted alongside built-in variables)
+    let formatted_message = process_template(
+        template,
+        &variables,
+        extra_values,
+        config.project_config.strict_templates,
+    )?;
+
+    // Write the formatted message to commit_message.md
+    fs::write(&commit_file_path, &formatted_message)?;
+    save_generated_message(&formatted_message)?;
+
+    println!("\n{} Commit message created!", success_mark());
+    println!("Message: {formatted_message}");
+    Ok(())
+}
+
+/// Handle editor mode for generate command, falling back to other editors or an
+/// inline prompt when the configured one cannot be started.
+///
+/// Once the editor closes, the message is linted like `rona check-msg` does. While it
+/// has problems, they are shown and the editor can be reopened, like `git rebase -i`
+/// does for an invalid todo list.
+///
+/// # Errors
+/// * If no editor can be started and the terminal is not interactive
+/// * If the commit template is invalid
+fn handle_editor_mode(config: &Config) -> Result<()> {
+    let commit_file_path = get_top_level_path()?.join(COMMIT_MESSAGE_FILE_PATH);
+    append_staged_diff(&commit_file_path, config.project_config.message_comments())?;
+
+    loop {
+        let in_editor = open_in_editor(&commit_file_path, config)?;
+        let diagnostics = message_diagnostics(&read_to_string(&commit_file_path)?, config)?;
+        if diagnostics.is_empty() {
+            return Ok(());
+        }
+
+        println!();
+        for diagnostic in &diagnostics {
+            println!("{} {COMMIT_MESSAGE_FILE_PATH}:{diagnostic}", failure_mark());
+        }
+        println!("Expected format: {}", commit_template(config));
+
+        let reopen = in_editor
+            && Confirm::with_theme(&prompt_theme())
+                .with_prompt("Reopen the editor to fix the message?")
+                .default(true)
+                .interact()
+                .unwrap_or(false);
+        if !reopen {
+            println!(
+                "{} {} problem(s) left in {COMMIT_MESSAGE_FILE_PATH}; fix them before committing",
+                "WARNING:".yellow().bold(),
+                diagnostics.len()
+            );
+            return Ok(());
+        }
+    }
+}
+
+/// Handle the `health` command which reports signs of an unmaintained repository.
+///
+/// # Errors
+/// * If inspecting the repository fails
+/// * If a confirmed fix fails
+/// * If writing the commit-graph fails when `optimize` is set
+fn handle_health(fix: bool, optimize: bool) -> Result<()> {
+    use crate::git::maintenance::{
+        health_findings, inspect_repository, optimize_revwalks, run_fix,
+    };
+
+    if optimize {
+        optimize_revwalks()?;
+        println!(
+            "{} Wrote commit-graph and enabled fetch.writeCommitGraph\n",
+            success_mark()
+        );
+    }
+
+    let report = inspect_repository()?;
+    let objects = report.objects;
+    println!(
+        "Repository size: {} MiB ({} loose objects, {} packs)",
+        (objects.loose_kib + objects.pack_kib) / 1024,
+        objects.loose,
+        objects.packs
+    );
+
+    let findings = health_findings(&report);
+    if findings.is_empty() {
+        println!("{} No maintenance needed.", success_mark());
+        return Ok(());
+    }
+
+    for finding in &findings {
+        println!("{} {}", "WARNING:".yellow().bold(), finding.problem);
+        if let Some(args) = &finding.fix {
+            println!("  fix: git {}", args.join(" "));
+        }
+    }
+    if findings.iter().any(|f| f.fix.is_none()) {
+        println!(
+            "\nLarge files, broken refs and replace refs need manual attention \
+             (e.g. `git filter-repo`, `git update-ref -d <ref>`, `git replace -d <commit>`)."
+        );
+    }
+    if !fix {
+        println!("\nRun `git maintenance start` to keep the repository maintained automatically.");
+        return Ok(());
+    }
+
+    for args in findings.iter().filter_map(|f| f.fix.as_ref()) {
+        let confirmed = Confirm::with_theme(&prompt_theme())
+            .with_prompt(format!("Run `git {}`?", args.join(" ")))
+            .default(true)
+            .interact()
+            .unwrap_or(false);
+        if confirmed {
+            run_fix(args)?;
+            println!("{} git {}", success_mark(), args.join(" "));
+        }
+    }
+    Ok(())
+}
+
+/// Handle `history ops` which lists the operation log, newest first.
+///
+/// # Errors
+/// * If the operation log cannot be read
+/// * If an operation cannot be serialized to JSON
+fn handle_history_ops(limit: usize, json: bool) -> Result<()> {
+    let operations = read_operations()?;
+    if operations.is_empty() {
+        println!("No operations recorded yet.");
+        return Ok(());
+    }
+
+    for operation in operations.iter().rev().take(limit) {
+        if json {
+            println!("{}", to_versioned_json(operation, false)?);
+            continue;
+        }
+
+        let short = |head: Option<&String>| {
+            head.map_or_else(|| "-".to_string(), |h| h.chars().take(7).collect())
+        };
+        let args: Vec<String> = operation
+            .args
+            .iter()
+            .map(|arg| {
+                if arg.is_empty() || arg.contains(char::is_whitespace) {
+                    format!("{arg:?}")
+                } else {
+                    arg.clone()
+                }
+            })
+            .collect();
+        println!(
+            "{}  {:<8} {:<20} {} -> {}  {}",
+            operation.time.replacen('T', " ", 1).dimmed(),
+            operation.command.cyan(),
+            operation.branch.as_deref().unwrap_or("(detached)"),
+            short(operation.head_before.as_ref()),
+            short(operation.head_after.as_ref()),
+            args.join(" ").dimmed()
+        );
+    }
+    Ok(())
+}
+
+/// Handle `env`, which prints what rona resolved for the current directory.
+///
+/// # Errors
+/// * If the environment cannot be serialized with `--json`
+fn handle_env(json: bool, config: &Config) -> Result<()> {
+    use crate::environment::Environment;
+
+    let env = Environment::read(config);
+    if json {
+        println!("{}", to_versioned_json(&env, true)?);
+        return Ok(());
+    }
+
+    let none = || "none".dimmed().to_string();
+    let yes_no = |value: bool| if value { "yes" } else { "no" };
+    let line = |label: &str, value: &str| println!("{:<11} {value}", label.bold());
+
+    line("rona", &env.version);
+    line("git", env.git_version.as_deref().unwrap_or("not found"));
+    line(
+        "Repository",
+        env.repo_root
+            .as_deref()
+            .unwrap_or("not in a git repository"),
+    );
+    if let Some(git_dir) = &env.git_dir {
+        line("Git dir", git_dir);
+    }
+    if env.config_files.is_empty() {
+        line("Config", &none());
+    }
+    for (i, file) in env.config_files.iter().enumerate() {
+        let label = if i == 0 { "Config" } else { "" };
+        line(label, &format!("{:<9} {}", file.origin, file.path));
+    }
+    let template = env.template.clone().unwrap_or_else(|| {
+        if config.project_config.commit_template.is_some() {
+            "commit_template".to_string()
+        } else {
+            "built-in".to_string()
+        }
+    });
+    line("Template", &template);
+    line("Language", &env.lang.clone().unwrap_or_else(none));
+    line(
+        "Editor",
+        &env.editor.as_ref().map_or_else(none, |editor| {
+            format!("{} ({})", editor.command, editor.source)
+        }),
+    );
+    let key = if env.signing_key {
+        "signing key configured"
+    } else {
+        "no signing key"
+    };
+    line("Signing", &format!("{}, {key}", env.signing));
+    line("Read-only", yes_no(env.read_only));
+    if env.denied.is_empty() {
+        line("Guards", &none());
+    }
+    for (i, denied) in env.denied.iter().enumerate() {
+        let label = if i == 0 { "Guards" } else { "" };
+        line(
+            label,
+            &format!("{} denied by {}", denied.feature, denied.guard),
+        );
+    }
+    line("Scope", &env.scope.clone().unwrap_or_else(none));
+    line("Sparse", yes_no(env.sparse_checkout));
+    line("Status", &env.status_source);
+    Ok(())
+}
+
+/// Handle `usage` which summarizes the operation log of the current repository.
+///
+/// # Errors
+/// * If the operation log cannot be read
+/// * If the report cannot be serialized with `--json`
+fn handle_usage(json: bool) -> Result<()> {
+    use crate::git::usage::{commit_size, usage_report};
+
+    let report = usage_report(&read_operations()?, commit_size);
+    if json {
+        println!("{}", to_versioned_json(&report, true)?);
+        return Ok(());
+    }
+    if report.operations == 0 {
+        println!("No operations recorded yet.");
+        return Ok(());
+    }
+
+    let day = |time: Option<&String>| {
+        time.map_or("?", |t| t.split('T').next().unwrap_or(t))
+            .to_string()
+    };
+    println!(
+        "{} operations from {} to {}",
+        report.operations,
+        day(report.first.as_ref()),
+        day(report.last.as_ref())
+    );
+
+    let mut commands: Vec<(&String, &usize)> = report.commands.iter().collect();
+    commands.sort_by(|a, b| b.1.cmp(a.1).then(a.0.cmp(b.0)));
+    println!("\nCommands:");
+    for (command, count) in commands {
+        println!("  {:<10} {count}", command.cyan());
+    }
+
+    println!();
+    if let (Some(files), Some(lines)) = (report.average_commit_files, report.average_commit_lines) {
+        println!(
+            "Average commit size:  {lines:.0} lines in {files:.1} files ({} commits)",
+            report.measured_commits
+        );
+    }
+    if let (Some(median), Some(average)) = (
+        report.median_generate_to_commit_secs,
+        report.average_generate_to_commit_secs,
+    ) {
+        let format = |secs: i64| format_duration(Duration::from_secs(secs.unsigned_abs()));
+        println!(
+            "Generate to commit:   median {}, average {} ({} commits)",
+            format(median),
+            format(average),
+            report.generated_commits
+        );
+    }
+
+    println!(
+        "\n{}",
+        "Computed locally from .git/rona/oplog.jsonl; nothing is sent anywhere.".dimmed()
+    );
+    Ok(())
+}
+
+/// Handle `stats` which lists the commits of the operation log, or exports them as
+/// Prometheus metrics or CSV.
+///
+/// # Errors
+/// * If the operation log cannot be read
+/// * If the repository root cannot be located (for the Prometheus label)
+fn handle_stats(export: Option<StatsFormat>) -> Result<()> {
+    use crate::git::usage::{commit_records, commit_size, commits_csv, prometheus_metrics};
+
+    let operations = read_operations()?;
+    let records = commit_records(&operations, commit_size);
+    match export {
+        Some(StatsFormat::Prometheus) => {
+            let root = get_top_level_path()?;
+            let repository = root
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            print!("{}", prometheus_metrics(&operations, &records, &repository));
+            return Ok(());
+        }
+        Some(StatsFormat::Csv) => {
+            print!("{}", commits_csv(&records));
+            return Ok(());
+        }
+        None => {}
+    }
+
+    if records.is_empty() {
+        println!("No commits recorded yet.");
+        return Ok(());
+    }
+    println!(
+        "{}",
+        format!(
+            "{:<16}  {:<7}  {:<20}  {:>5}  {:>6}  lead time",
+            "time", "commit", "branch", "files", "lines"
+        )
+        .bold()
+    );
+    for record in &records {
+        let size = |value: Option<usize>| value.map_or_else(|| "-".to_string(), |v| v.to_string());
+        println!(
+            "{:<16}  {:<7}  {:<20}  {:>5}  {:>6}  {}{}",
+            record
+                .time
+                .replacen('T', " ", 1)
+                .chars()
+                .take(16)
+                .collect::<String>(),
+            record
+                .commit
+                .as_deref()
+                .map_or_else(|| "-".to_string(), |c| c.chars().take(7).collect()),
+            record.branch.as_deref().unwrap_or("(detached)"),
+            size(record.size.map(|s| s.files)),
+            size(record.size.map(|s| s.lines)),
+            record.lead_time_secs.map_or_else(
+                || "-".to_string(),
+                |secs| format_duration(Duration::from_secs(secs.unsigned_abs()))
+            ),
+            if record.amend { " (amend)" } else { "" },
+        );
+    }
+    println!(
+        "\n{}",
+        "Export with --export prometheus or --export csv. Computed locally from .git/rona/oplog.jsonl.".dimmed()
+    );
+    Ok(())
+}
+
+/// Handle the Initialize command which creates the initial configuration file.
+///
+/// # Arguments
+/// * `editor` - The editor command to configure
+/// * `config` - Global configuration including verbose and dry-run settings
+///
+/// # Errors
+/// * If creating configuration file fails
+fn handle_initialize(editor: &str, config: &Config) -> Result<()> {
+    if config.dry_run {
+        println!("Would create config file with editor: {editor}");
+        return Ok(());
+    }
+    config.create_config_file(editor)?;
+    Ok(())
+}
+
+/// Handle `init --repo`, which prepares the current repository for rona.
+///
+/// Creates `commit_message.md` and `.commitignore`, and appends to `.commitignore` the
+/// patterns of the configured presets, or of the presets detected from marker files
+/// (`Cargo.toml`, `package.json`, ...) at the repository root when none is configured.
+///
+/// # Errors
+/// * If a configured preset is unknown
+/// * If the files cannot be created or written
+fn handle_initialize_repo(config: &Config) -> Result<()> {
+    let presets = if config.project_config.preset.is_empty() {
+        detect_presets(&get_top_level_path()?)
+    } else {
+        resolve_presets(&config.project_config.preset)?
+    };
+    let names = presets
+        .iter()
+        .map(|p| p.name)
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    if config.dry_run {
+        println!("Would create files: commit_message.md, .commitignore");
+        if !presets.is_empty() {
+            println!("Would add the {names} preset patterns to .commitignore");
+        }
+        return Ok(());
+    }
+
+    create_needed_files()?;
+    let added = add_to_commitignore(&commitignore_lines(&presets))?;
+    if presets.is_empty() {
+        println!("{} Repository ready (no preset detected)", success_mark());
+    } else {
+        println!(
+            "{} Repository ready: {added} pattern(s) from the {names} preset(s) added to .commitignore",
+            success_mark()
+        );
+    }
+    Ok(())
+}
+
+/// A package of `[packages]`, by name.
+///
+/// # Errors
+/// * If no package has that name
+fn find_package<'a>(name: &str, config: &'a Config) -> Result<&'a PackageConfig> {
+    let packages = &config.project_config.packages;
+    packages.get(name).ok_or_else(|| {
+        let names: Vec<&str> = packages.keys().map(String::as_str).collect();
+        RonaError::InvalidInput(if names.is_empty() {
+            format!("Unknown package '{name}': no [packages] are configured")
+        } else {
+            format!(
+                "Unknown package '{name}'. Configured packages: {}",
+                names.join(", ")
+            )
+        })
+    })
+}
+
+/// The fragment directory and the changelog of `[fragments]`, as absolute paths. For a
+/// package, both are inside its directory.
+///
+/// # Errors
+/// * If the repository root cannot be found
+/// * If the package is not configured
+fn fragment_paths(package: Option<&str>, config: &Config) -> Result<(PathBuf, PathBuf)> {
+    let mut root = get_top_level_path()?;
+    if let Some(name) = package {
+        root.push(&find_package(name, config)?.path);
+    }
+    let fragments = &config.project_config.fragments;
+    Ok((
+        root.join(fragments.dir.as_deref().unwrap_or(DEFAULT_FRAGMENT_DIR)),
+        root.join(fragments.changelog.as_deref().unwrap_or(DEFAULT_CHANGELOG)),
+    ))
+}
+
+/// Handle `release`: tags the next version after the latest version tag, of the
+/// repository or of one package.
+///
+/// When there are fragments, their changelog section is added and committed first.
+/// Prereleases keep the fragments, so the stable release lists every change again.
+///
+/// # Errors
+/// * If the `[release] message` template is invalid or the package is not configured
+/// * If the channel comes before the one of the current prerelease
+/// * If no commit touched the package since its last release
+/// * If the tag exists or tracked files have uncommitted changes
+/// * If writing the changelog, committing or tagging fails
+fn handle_release(
+    bump: Option<Bump>,
+    channel: Option<Channel>,
+    package: Option<&str>,
+    config: &Config,
+) -> Result<()> {
+    ensure_outside_commit_hook("release")?;
+    let release = &config.project_config.release;
+    let template = release
+        .message
+        .as_deref()
+        .unwrap_or(DEFAULT_RELEASE_MESSAGE);
+    validate_release_template(template)
+        .map_err(|e| RonaError::InvalidInput(format!("release.message: {e}")))?;
+    let prefix = match package {
+        Some(name) => find_package(name, config)?
+            .tag_prefix
+            .clone()
+            .unwrap_or_else(|| format!("{name}/v")),
+        None => release
+            .tag_prefix
+            .clone()
+            .unwrap_or_else(|| DEFAULT_TAG_PREFIX.to_string()),
+    };
+
+    let previous = latest_version(&merged_tags(&prefix)?, &prefix);
+    let previous_tag = previous.map(|v| format!("{prefix}{v}"));
+    let commits = match package {
+        Some(name) => {
+            let path = &find_package(name, config)?.path;
+            let commits = commits_touching(previous_tag.as_deref(), path)?;
+            if commits.is_empty() {
+                return Err(RonaError::InvalidInput(format!(
+                    "No commit touched {path} since {}; nothing to release",
+                    previous_tag.as_deref().unwrap_or("the first commit")
+                )));
+            }
+            commits
+        }
+        None => Vec::new(),
+    };
+    let version = next_version(previous, bump, channel)?;
+    let tag = format!("{prefix}{version}");
+    if tag_exists(&tag)? {
+        return Err(RonaError::InvalidInput(format!("Tag {tag} already exists")));
+    }
+    let variables = ReleaseTemplateVariables {
+        version: version.to_string(),
+        tag: tag.clone(),
+        channel: version
+            .channel()
+            .map(Channel::name)
+            .unwrap_or_default()
+            .to_string(),
+        previous: previous.map(|v| v.to_string()).unwrap_or_default(),
+        package: package.unwrap_or_default().to_string(),
+    };
+    let message = process_release_template(template, &variables)?;
+    let webhook = config.project_config.notify.webhook.as_ref();
+
+    let (dir, changelog_path) = fragment_paths(package, config)?;
+    let fragments = read_fragments(&dir)?;
+    let previous = previous.map_or_else(|| "none".to_string(), |v| v.to_string());
+    if config.dry_run {
+        println!("Would release {version} (previous: {previous}) as tag {tag}");
+        println!("Message: {message}");
+        for commit in &commits {
+            println!("  {commit}");
+        }
+        if !fragments.is_empty() {
+            println!("\n{}", release_section(&version, &fragments, config));
+            if version.pre.is_none() {
+                println!("Would remove {} fragment(s)", fragments.len());
+            }
+        }
+        if let Some(webhook) = webhook {
+            notify_release(webhook, &variables, true);
+        }
+        return Ok(());
+    }
+
+    if !is_worktree_clean()? {
+        return Err(RonaError::InvalidInput(
+            "Tracked files have uncommitted changes; commit or stash them before releasing"
+                .to_string(),
+        ));
+    }
+    if !fragments.is_empty() {
+        commit_release_changelog(&version, fragments, &changelog_path, &message, config)?;
+    }
+
+    create_tag(&tag, &message)?;
+    println!(
+        "{} Released {version} as tag {tag} (previous: {previous})",
+        success_mark()
+    );
+    println!("Publish it with `git push origin {tag}`.");
+    if let Some(webhook) = webhook {
+        notify_release(webhook, &variables, false);
+    }
+    Ok(())
+}
+
+/// Posts the release to the `[notify.webhook]` chat webhook, or says it would on a
+/// dry run. A failure only prints a warning, since the tag is already created.
+fn notify_release(webhook: &WebhookConfig, variables: &ReleaseTemplateVariables, dry_run: bool) {
+    if dry_run {
+        println!("Would post a release notification to {}", webhook.host());
+        return;
+    }
+    match render_release_notification(webhook, variables)
+        .and_then(|text| post_webhook(webhook, &text))
+    {
+        Ok(()) => println!("{} Notified {}", success_mark(), webhook.host()),
+        Err(e) => println!(
+            "{} Release notification failed: {e}",
+            "WARNING:".yellow().bold()
+        ),
+    }
+}
+
+/// Adds the changelog section of a release and commits it with the release message.
+/// A stable release also removes the fragments.
+///
+/// # Errors
+/// * If in read-only mode
+/// * If the changelog cannot be written, a fragment cannot be removed or committing
+///   fails
+fn commit_release_changelog(
+    version: &Version,
+    fragments: Vec<Fragment>,
+    changelog_path: &Path,
+    message: &str,
+    config: &Config,
+) -> Result<()> {
+    ensure_writable("update the changelog")?;
+    let changelog = if changelog_path.exists() {
+        read_to_string(changelog_path)?
+    } else {
+        String::new()
+    };
+    let section = release_section(version, &fragments, config);
+    std::fs::write(changelog_path, insert_release(&changelog, &section))?;
+    let mut removed = Vec::new();
+    if version.pre.is_none() {
+        for fragment in fragments {
+            std::fs::remove_file(&fragment.path)?;
+            removed.push(fragment.path);
+        }
+    }
+    stage_release_files(changelog_path, &removed)?;
+    git_commit_with_message(
+        message,
+        &[],
+        Signing::new(false, config.project_config.require_signed)?,
+        false,
+    )
+}
+
+/// The changelog section of a release, dated today.
+fn release_section(version: &Version, fragments: &[Fragment], config: &Config) -> String {
+    let commit_types: Vec<&str> = config.project_config.commit_types.as_ref().map_or_else(
+        || COMMIT_TYPES.to_vec(),
+        |types| types.iter().map(CommitType::name).collect(),
+    );
+    render_release(
+        &version.to_string(),
+        &chrono::Local::now().format("%Y-%m-%d").to_string(),
+        fragments,
+        &commit_types,
+        &config.project_config.fragments.titles,
+    )
+}
+
+/// The branches given with `--branch`, else the current branch.
+///
+/// # Errors
+/// * If none is given and `HEAD` is detached
+fn bundle_branches(branches: Vec<String>) -> Result<Vec<String>> {
+    if !branches.is_empty() {
+        return Ok(branches);
+    }
+    let current = get_current_branch()?;
+    if current == "HEAD" {
+        return Err(RonaError::InvalidInput(
+            "Not on a branch: name the branches to bundle with --branch".to_string(),
+        ));
+    }
+    Ok(vec![current])
+}
+
+/// Handle `bundle create`, which writes branches to a bundle file, leaving out the
+/// commits reachable from `--since`.
+///
+/// # Errors
+/// * If a branch or `--since` does not resolve
+/// * If there are no commits to bundle
+fn handle_bundle_create(
+    file: &str,
+    since: Option<&str>,
+    branches: Vec<String>,
+    config: &Config,
+) -> Result<()> {
+    let branches = bundle_branches(branches)?;
+    let count = bundle_commit_count(&branches, since)?;
+    if count == 0 {
+        return Err(RonaError::InvalidInput(format!(
+            "No commit on {} since {}; nothing to bundle",
+            branches.join(", "),
+            since.unwrap_or("the root")
+        )));
+    }
+    let since_text = since
+        .map(|since| format!(" since {since}"))
+        .unwrap_or_default();
+    if config.dry_run {
+        println!(
+            "Would bundle {count} commit(s) of {}{since_text} into {file}",
+            branches.join(", ")
+        );
+        return Ok(());
+    }
+
+    create_bundle(file, &branches, since)?;
+    println!(
+        "{} Bundled {count} commit(s) of {}{since_text} into {file}",
+        success_mark(),
+        branches.join(", ")
+    );
+    println!("Apply it in another clone with `rona bundle apply {file}`.");
+    Ok(())
+}
+
+/// Handle `bundle apply`, which checks that a bundle holds the expected branches and
+/// that the commits it was based on are here, then fast-forwards those branches.
+///
+/// # Errors
+/// * If the bundle lacks an expected branch, or commits it was based on
+/// * If a local branch has commits the bundle does not
+fn handle_bundle_apply(file: &str, branches: Vec<String>, config: &Config) -> Result<()> {
+    ensure_outside_commit_hook("bundle")?;
+    let branches = bundle_branches(branches)?;
+    let heads = bundle_heads(file)?;
+    let mut applied: Vec<BundleHead> = Vec::new();
+    for branch in &branches {
+        let Some(head) = heads.iter().find(|head| &head.branch == branch) else {
+            let contained: Vec<&str> = heads.iter().map(|head| head.branch.as_str()).collect();
+            return Err(RonaError::InvalidInput(format!(
+                "{file} does not contain {branch}; it contains: {}",
+                if contained.is_empty() {
+                    "no branch".to_string()
+                } else {
+                    contained.join(", ")
+                }
+            )));
+        };
+        applied.push(head.clone());
+    }
+    verify_bundle(file)?;
+
+    if config.dry_run {
+        for head in &applied {
+            println!(
+                "Would update {} to {}",
+                head.branch,
+                &head.commit[..head.commit.len().min(7)]
+            );
+        }
+        return Ok(());
+    }
+
+    apply_bundle(file, &applied)?;
+    for head in &applied {
+        println!(
+            "{} Updated {} to {}",
+            success_mark(),
+            head.branch,
+            &head.commit[..head.commit.len().min(7)]
+        );
+    }
+    Ok(())
+}
+
+/// The remote and ref namespace of a handoff: `--remote`, else `[handoff]`.
+///
+/// # Errors
+/// * If `[handoff] namespace` is not a valid ref namespace
+fn handoff_target(remote: Option<String>, config: &Config) -> Result<(String, String)> {
+    let handoff = &config.project_config.handoff;
+    let remote = remote
+        .or_else(|| handoff.remote.clone())
+        .unwrap_or_else(|| "origin".to_string());
+    let namespace = handoff
+        .namespace
+        .clone()
+        .unwrap_or_else(|| DEFAULT_HANDOFF_NAMESPACE.to_string());
+    if !namespace.starts_with("refs/") || !is_valid_ref_name(&handoff_ref(&namespace, "main")) {
+        return Err(RonaError::InvalidInput(format!(
+            "handoff.namespace: '{namespace}' is not a ref namespace such as {DEFAULT_HANDOFF_NAMESPACE}"
+        )));
+    }
+    Ok((remote, namespace))
+}
+
+/// Handle `handoff push`, which saves the staged changes and the pending
+/// `commit_message.md` of the current branch as a commit and pushes it to the
+/// branch's handoff ref, replacing the previous handoff.
+///
+/// # Errors
+/// * If there is nothing staged and no pending message
+/// * If the handoff commit cannot be created or pushed
+fn handle_handoff_push(remote: Option<String>, config: &Config) -> Result<()> {
+    ensure_outside_commit_hook("handoff")?;
+    let (remote, namespace) = handoff_target(remote, config)?;
+    let branch = get_current_branch()?;
+    let staged = get_staged_files()?.len();
+    let message = pending_commit_message(config.project_config.message_comments())?;
+    if staged == 0 && message.is_none() {
+        return Err(RonaError::InvalidInput(format!(
+            "Nothing to hand off: nothing is staged and {COMMIT_MESSAGE_FILE_PATH} has no pending message"
+        )));
+    }
+    let reference = handoff_ref(&namespace, &branch);
+    let with_message = if message.is_some() {
+        format!(" and {COMMIT_MESSAGE_FILE_PATH}")
+    } else {
+        String::new()
+    };
+    if config.dry_run {
+        println!("Would push {staged} staged file(s){with_message} to {reference} on '{remote}'");
+        return Ok(());
+    }
+
+    let commit = create_handoff_commit(&branch, message.as_deref())?;
+    push_handoff(&remote, &commit, &reference)?;
+    println!(
+        "{} Handed off {staged} staged file(s){with_message} to {reference} on '{remote}'",
+        success_mark()
+    );
+    println!("Continue in another clone with `rona handoff pull {branch}`.");
+    Ok(())
+}
+
+/// Handle `handoff pull`, which checks out the branch of a handoff at the commit it
+/// was based on, stages the handed-off changes and restores `commit_message.md`.
+///
+/// # Errors
+/// * If the remote has no handoff for the branch
+/// * If tracked files have uncommitted changes, or `commit_message.md` holds another
+///   pending message
+/// * If the local branch has commits the handoff was not based on
+fn handle_handoff_pull(
+    branch: Option<String>,
+    remote: Option<String>,
+    config: &Config,
+) -> Result<()> {
+    ensure_outside_commit_hook("handoff")?;
+    let (remote, namespace) = handoff_target(remote, config)?;
+    let branch = match branch {
+        Some(branch) => branch,
+        None => get_current_branch()?,
+    };
+    let handoff = fetch_handoff(&remote, &handoff_ref(&namespace, &branch))?;
+    let files = handoff_files(&handoff)?;
+    let base = &handoff.base[..handoff.base.len().min(7)];
+    if config.dry_run {
+        println!(
+            "Would check out {branch} at {base} and stage {} file(s):",
+            files.len()
+        );
+        for file in &files {
+            println!("  {file}");
+        }
+        if let Some(message) = &handoff.message {
+            println!(
+                "Would write {COMMIT_MESSAGE_FILE_PATH}:\n{}",
+                message.trim_end()
+            );
+        }
+        return Ok(());
+    }
+
+    if !is_worktree_clean()? {
+        return Err(RonaError::InvalidInput(
+            "Tracked files have uncommitted changes; commit or stash them before pulling a handoff"
+                .to_string(),
+        ));
+    }
+    if let Some(pending) = pending_commit_message(config.project_config.message_comments())?
+        && handoff.message.as_deref().map(str::trim) != Some(pending.trim())
+    {
+        return Err(RonaError::InvalidInput(format!(
+            "{COMMIT_MESSAGE_FILE_PATH} holds a message that was not committed yet; commit it or remove it first"
+        )));
+    }
+    restore_handoff(&handoff, &branch)?;
+    if let Some(message) = &handoff.message {
+        create_needed_files()?;
+        std::fs::write(
+            get_top_level_path()?.join(COMMIT_MESSAGE_FILE_PATH),
+            message,
+        )?;
+    }
+    println!(
+        "{} Restored the handoff of {branch}: {} staged file(s) on {base}{}",
+        success_mark(),
+        files.len(),
+        if handoff.message.is_some() {
+            format!(", with {COMMIT_MESSAGE_FILE_PATH}")
+        } else {
+            String::new()
+        }
+    );
+    Ok(())
+}
+
+/// Handle `fragment add`, which writes a fragment named after the current branch.
+///
+/// # Errors
+/// * If the type is not a commit type or the text is empty
+/// * If in read-only mode (unless dry-run)
+/// * If the fragment cannot be written
+fn handle_fragment_add(
+    fragment_type: &str,
+    text: &str,
+    ticket: Option<&str>,
+    package: Option<&str>,
+    config: &Config,
+) -> Result<()> {
+    let commit_types: Vec<&str> = config.project_config.commit_types.as_ref().map_or_else(
+        || COMMIT_TYPES.to_vec(),
+        |types| types.iter().map(CommitType::name).collect(),
+    );
+    if !commit_types.contains(&fragment_type) {
+        return Err(RonaError::InvalidInput(format!(
+            "Unknown fragment type '{fragment_type}'. Valid types: {}",
+            commit_types.join(", ")
+        )));
+    }
+    let text = text.trim();
+    if text.is_empty() {
+        return Err(RonaError::InvalidInput(
+            "The fragment text is empty".to_string(),
+        ));
+    }
+    let text = ticket.map_or_else(|| text.to_string(), |ticket| format!("{text} ({ticket})"));
+
+    let (dir, _) = fragment_paths(package, config)?;
+    let path = next_fragment_path(&dir, &slugify(&get_current_branch()?), fragment_type);
+    let shown = path
+        .strip_prefix(get_top_level_path()?)
+        .unwrap_or(&path)
+        .display()
+        .to_string();
+    if config.dry_run {
+        println!("Would write {shown}:\n{text}");
+        return Ok(());
+    }
+    ensure_writable("write a changelog fragment")?;
+    std::fs::create_dir_all(&dir)?;
+    std::fs::write(&path, format!("{text}\n"))?;
+    println!("{} Wrote {shown}", success_mark());
+    Ok(())
+}
+
+/// Handle `fragment list`, which lists the fragments waiting for the next release.
+///
+/// # Errors
+/// * If the fragments cannot be read
+fn handle_fragment_list(package: Option<&str>, config: &Config) -> Result<()> {
+    let (dir, _) = fragment_paths(package, config)?;
+    let fragments = read_fragments(&dir)?;
+    if fragments.is_empty() {
+        println!("No changelog fragments. Add one with `rona fragment add <type> \"<text>\"`.");
+        return Ok(());
+    }
+    let width = fragments
+        .iter()
+        .map(|fragment| fragment.fragment_type.len())
+        .max()
+        .unwrap_or(0);
+    for fragment in &fragments {
+        let first_line = fragment.text.lines().next().unwrap_or_default();
+        println!(
+            "{}  {first_line}  {}",
+            format!("{:<width$}", fragment.fragment_type).cyan(),
+            format!("({})", fragment.id).dimmed()
+        );
+    }
+    Ok(())
+}
+
+/// Handle `fragment build`, which adds the fragments to the changelog as a release
+/// and deletes them (unless `keep`).
+///
+/// # Errors
+/// * If there are no fragments
+/// * If in read-only mode (unless dry-run)
+/// * If the changelog cannot be written or a fragment cannot be removed
+fn handle_fragment_build(
+    version: &str,
+    date: Option<&str>,
+    keep: bool,
+    package: Option<&str>,
+    config: &Config,
+) -> Result<()> {
+    let (dir, changelog_path) = fragment_paths(package, config)?;
+    let fragments = read_fragments(&dir)?;
+    if fragments.is_empty() {
+        return Err(RonaError::InvalidInput(format!(
+            "No changelog fragments in {}",
+            dir.display()
+        )));
+    }
+
+    let commit_types: Vec<&str> = config.project_config.commit_types.as_ref().map_or_else(
+        || COMMIT_TYPES.to_vec(),
+        |types| types.iter().map(CommitType::name).collect(),
+    );
+    let date = date.map_or_else(
+        || chrono::Local::now().format("%Y-%m-%d").to_string(),
+        str::to_string,
+    );
+    let section = render_release(
+        version,
+        &date,
+        &fragments,
+        &commit_types,
+        &config.project_config.fragments.titles,
+    );
+    if config.dry_run {
+        print!("{section}");
+        if !keep {
+            println!("\nWould remove {} fragment(s)", fragments.len());
+        }
+        return Ok(());
+    }
+
+    ensure_writable("update the changelog")?;
+    let changelog = if changelog_path.exists() {
+        read_to_string(&changelog_path)?
+    } else {
+        String::new()
+    };
+    std::fs::write(&changelog_path, insert_release(&changelog, &section))?;
+    println!(
+        "{} Added {version} to {} ({} fragment(s))",
+        success_mark(),
+        changelog_path.display(),
+        fragments.len()
+    );
+    if !keep {
+        for fragment in &fragments {
+            std::fs::remove_file(&fragment.path)?;
+        }
+        println!("Removed the fragments; stage the changes with `rona -a`.");
+    }
+    Ok(())
+}
+
+/// The managed hooks named on the command line, or all of them when none is.
+///
+/// # Errors
+/// * If the hooks directory cannot be located
+fn selected_hooks(names: &[String]) -> Result<Vec<HookStatus>> {
+    let mut hooks = list_hooks()?;
+    if !names.is_empty() {
+        hooks.retain(|hook| names.iter().any(|name| name == hook.name));
+    }
+    Ok(hooks)
+}
+
+/// Handle `hooks install`, which writes the managed hook scripts. Hooks rona does not
+/// manage are left in place with a warning.
+///
+/// # Errors
+/// * If in read-only mode (unless dry-run)
+/// * If a hook script cannot be written
+fn handle_hooks_install(names: &[String], config: &Config) -> Result<()> {
+    let dry_run = config.dry_run;
+    if !dry_run {
+        ensure_writable("install git hooks")?;
+    }
+
+    for hook in selected_hooks(names)? {
+        let path = hook.path.display();
+        let verb = match install_hook(&hook, dry_run)? {
+            HookState::Unmanaged => {
+                eprintln!(
+                    "{} {path} was not written by rona; left it alone. Add `rona hooks run {} \"$@\"` to it to run rona's checks.",
+                    "WARNING:".yellow().bold(),
+                    hook.name
+                );
+                continue;
+            }
+            HookState::Managed if dry_run => "Would update",
+            HookState::Missing if dry_run => "Would install",
+            HookState::Managed => "Updated",
+            HookState::Missing => "Installed",
+        };
+        if dry_run {
+            println!("{verb} the {} hook ({path})", hook.name);
+        } else {
+            println!("{} {verb} the {} hook ({path})", success_mark(), hook.name);
+        }
+    }
+    Ok(())
+}
+
+/// Handle `hooks uninstall`, which removes the hook scripts rona installed.
+///
+/// # Errors
+/// * If in read-only mode (unless dry-run)
+/// * If a hook script cannot be removed
+fn handle_hooks_uninstall(names: &[String], config: &Config) -> Result<()> {
+    let dry_run = config.dry_run;
+    if !dry_run {
+        ensure_writable("remove git hooks")?;
+    }
+
+    for hook in selected_hooks(names)? {
+        let path = hook.path.display();
+        match uninstall_hook(&hook, dry_run)? {
+            HookState::Managed if dry_run => {
+                println!("Would remove the {} hook ({path})", hook.name);
+            }
+            HookState::Managed => {
+                println!("{} Removed the {} hook ({path})", success_mark(), hook.name);
+            }
+            HookState::Unmanaged => println!("Left {path}: it was not written by rona"),
+            HookState::Missing => println!("No {} hook installed", hook.name),
+        }
+    }
+    Ok(())
+}
+
+/// Handle `hooks list`, which shows the state of every hook rona can install.
+///
+/// # Errors
+/// * If the hooks directory cannot be located
+fn handle_hooks_list() -> Result<()> {
+    println!("Hooks directory: {}", hooks_dir()?.display());
+    for hook in list_hooks()? {
+        let state = match hook.state {
+            HookState::Managed => "installed".green(),
+            HookState::Unmanaged => "not managed by rona".yellow(),
+            HookState::Missing => "not installed".dimmed(),
+        };
+        println!("  {:<10}  {state}", hook.name);
+    }
+    Ok(())
+}
+
+/// Handle `hooks run`, called by the managed hook scripts with git's arguments.
+///
+/// * `pre-commit` refuses commits adding TODO markers when `[todo] on_commit = "block"`
+/// * `commit-msg` checks the message file like `rona check-msg`
+/// * `pre-push` runs the `[pre_push]` commands, unless `rona push` already ran them
+///
+/// # Errors
+/// * If the hook's check fails, so git aborts the commit or push
+fn handle_hooks_run(hook: &str, args: &[String], config: &Config) -> Result<()> {
+    match hook {
+        "pre-commit" if config.project_config.todo.on_commit == TodoAction::Block => {
+            apply_todo_policy("", &[], config).map(|_| ())
+        }
+        "commit-msg" => {
+            let file = args.first().ok_or_else(|| {
+                RonaError::InvalidInput("The commit-msg hook needs the message file".to_string())
+            })?;
+            handle_check_msg(file, config)
+        }
+        "pre-push" if std::env::var_os(PUSHING_ENV).is_none() => {
+            run_pre_push_commands(&config.project_config.pre_push, false)
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Handle the `check-msg` command, called from git's `commit-msg` hook.
+///
+/// Prints one `file:line:column: message` diagnostic per problem to stderr.
+///
+/// # Errors
+/// * If the message file cannot be read
+/// * If the commit template is invalid
+/// * If the message has any problem, so the hook rejects the commit
+fn handle_check_msg(file: &str, config: &Config) -> Result<()> {
+    let content = read_to_string(file)?;
+    let diagnostics = message_diagnostics(&content, config)?;
+    if diagnostics.is_empty() {
+        return Ok(());
+    }
+
+    for diagnostic in &diagnostics {
+        eprintln!("{file}:{diagnostic}");
+    }
+    eprintln!("Expected format: {}", commit_template(config));
+
+    Err(RonaError::InvalidInput(format!(
+        "{} problem(s) found in {file}",
+        diagnostics.len()
+    )))
+}
+
+/// The active commit template: the configured one, else the default.
+fn commit_template(config: &Config) -> &str {
+    config
+        .project_config
+        .commit_template
+        .as_deref()
+        .unwrap_or(DEFAULT_COMMIT_TEMPLATE)
+}
+
+/// Lints a commit message against the active commit template and commit types.
+///
+/// A message that does not follow the active template is linted against the valid
+/// named templates of `[templates]` too, and gets the diagnostics of the one it fits
+/// best, so a message generated with `--template` is accepted without it.
+///
+/// # Errors
+/// * If the commit template is invalid
+fn message_diagnostics(content: &str, config: &Config) -> Result<Vec<Diagnostic>> {
+    let (template, commit_types) = lint_rules(config)?;
+    let mut diagnostics = lint_message(content, template, &commit_types);
+    let extra_names: Vec<&str> = config
+        .project_config
+        .commit_extra_fields
+        .iter()
+        .map(|f| f.name.as_str())
+        .collect();
+    for (_, named) in config.project_config.named_templates() {
+        if diagnostics.is_empty() {
+            break;
+        }
+        if named == template || validate_template(named, &extra_names).is_err() {
+            continue;
+        }
+        let named_diagnostics = lint_message(content, named, &commit_types);
+        if named_diagnostics.len() < diagnostics.len() {
+            diagnostics = named_diagnostics;
+        }
+    }
+    Ok(diagnostics)
+}
+
+/// The commit template and commit types messages are linted against.
+///
+/// # Errors
+/// * If the commit template is invalid
+fn lint_rules(config: &Config) -> Result<(&str, Vec<&str>)> {
+    let commit_types: Vec<&str> = config.project_config.commit_types.as_ref().map_or_else(
+        || COMMIT_TYPES.to_vec(),
+        |v| v.iter().map(CommitType::name).collect(),
+    );
+    let template = commit_template(config);
+    let extra_names: Vec<&str> = config
+        .project_config
+        .commit_extra_fields
+        .iter()
+        .map(|f| f.name.as_str())
+        .collect();
+    validate_template(template, &extra_names)?;
+    Ok((template, commit_types))
+}
+
+/// Handle `audit`, which lints the messages of the commits since `since` (default:
+/// the whole history) and reports the ones that do not conform, with compliance per
+/// author.
+///
+/// # Errors
+/// * If the commit template is invalid
+/// * If `since` does not resolve
+fn handle_audit(since: Option<&str>, config: &Config) -> Result<()> {
+    let (template, commit_types) = lint_rules(config)?;
+    let range = since.map_or_else(|| "HEAD".to_string(), |since| format!("{since}..HEAD"));
+    let report = audit_commits(&commit_messages(&range)?, template, &commit_types);
+    let scope = since
+        .map(|since| format!(" since {since}"))
+        .unwrap_or_default();
+    if report.total == 0 {
+        println!("No commits to audit{scope}.");
+        return Ok(());
+    }
+
+    println!(
+        "Audited {} commit(s){scope}: {} conform ({:.0}%)",
+        report.total,
+        report.conforming,
+        compliance(report.conforming, report.total)
+    );
+    if !report.findings.is_empty() {
+        println!("\nNot conforming:");
+        for finding in &report.findings {
+            println!(
+                "  {} {} {}",
+                finding.short_sha.yellow(),
+                finding.author.cyan(),
+                finding.subject
+            );
+            for diagnostic in &finding.diagnostics {
+                println!("      {}", diagnostic.to_string().dimmed());
+            }
+        }
+    }
+    println!("\nBy author:");
+    let width = report
+        .authors
+        .iter()
+        .map(|tally| tally.author.chars().count())
+        .max()
+        .unwrap_or(0);
+    for tally in &report.authors {
+        println!(
+            "  {:<width$}  {}/{} ({:.0}%)",
+            tally.author,
+            tally.conforming,
+            tally.total,
+            compliance(tally.conforming, tally.total)
+        );
+    }
+    println!("\nExpected format: {template}");
+    Ok(())
+}
+
+/// Handle the `prepare-msg` command, called from git's `prepare-commit-msg` hook.
+///
+/// The commit template is rendered with an empty `{message}`, the commit type inferred
+/// from the branch prefix (falling back to the first configured type) and empty extra
+/// fields, then written above git's own comments. Messages that already have content
+/// (`-m`, `-F`, merges, squashes, amends or a `commit.template`) are left untouched.
+///
+/// # Errors
+/// * If reading or writing the message file fails
+/// * If reading the branch or commit count fails
+fn handle_prepare_msg(msg_file: &str, source: Option<&str>, config: &Config) -> Result<()> {
+    // Hooks forward `"$2"`, so a missing source arrives as an empty string.
+    if source.is_some_and(|s| !s.is_empty() && s != "template") {
+        return Ok(());
+    }
+
+    let existing = read_to_string(msg_file)?;
+    if existing
+        .lines()
+        .any(|line| !line.trim().is_empty() && !line.starts_with('#'))
+    {
+        return Ok(());
+    }
+
+    let commit_types: Vec<&str> = config.project_config.commit_types.as_ref().map_or_else(
+        || COMMIT_TYPES.to_vec(),
+        |v| v.iter().map(CommitType::name).collect(),
+    );
+    let branch = get_current_branch()?;
+    let commit_type = infer_commit_type(&commit_types, &branch)
+        .or_else(|| commit_types.first().copied())
+        .unwrap_or_default();
+
+    let template = config
+        .project_config
+        .commit_template
+        .as_deref()
+        .unwrap_or(DEFAULT_COMMIT_TEMPLATE);
+    let extra_values: HashMap<String, String> = config
+        .project_config
+        .commit_extra_fields
+        .iter()
+        .map(|f| (f.name.clone(), String::new()))
+        .collect();
+    let extra_names: Vec<&str> = extra_values.keys().map(String::as_str).collect();
+    if let Err(e) = validate_template(template, &extra_names) {
+        if config.project_config.strict_templates {
+            return Err(RonaError::InvalidInput(format!(
+                "Invalid commit template: {e}"
+            )));
+        }
+        eprintln!(
+            "{} Template validation error: {e}. Leaving the message untouched.",
+            "WARNING:".yellow().bold()
+        );
+        return Ok(());
+    }
+
+    let variables = TemplateVariables::new(
+        Some(commit_nb(commit_type, config)? + 1),
+        commit_type.to_string(),
+        format_branch_name(&commit_types, &branch, &config.project_config.branch_name),
+        String::new(),
+        config.project_config.ticket_pattern.as_deref(),
+    )?
+    .with_date_formats(
+        config.project_config.date_format.as_deref(),
+        config.project_config.time_format.as_deref(),
+        config.project_config.timezone.as_deref(),
+        config.project_config.lang.as_deref(),
+    )
+    .with_commit_emoji(config.project_config.commit_emoji(commit_type))
+    .with_impact(&config.project_config.impact)
+    .with_changed_symbols(uses_variable(template, "changed_symbols"));
+    let header = process_template(
+        template,
+        &variables,
+        &extra_values,
+        config.project_config.strict_templates,
+    )?;
+    let header = header.trim_end();
+
+    if config.dry_run {
+        println!("Would write to {msg_file}: {header}");
+        return Ok(());
+    }
+
+    ensure_writable("write the commit message")?;
+    std::fs::write(msg_file, format!("{header}\n{existing}"))?;
+    Ok(())
+}
+
+/// Starts a background fetch when `auto_fetch` is configured and the interval has elapsed.
+///
+/// Failures only produce a warning on stderr: freshness is a convenience and must never
+/// break the command (or pollute stdout, which shell completion reads).
+fn start_auto_fetch(config: &Config) {
+    let Some(interval) = config.project_config.auto_fetch.as_deref() else {
+        return;
+    };
+
+    let result = parse_duration(interval)
+        .map_err(|e| RonaError::InvalidInput(format!("auto_fetch: {e}")))
+        .and_then(background_fetch_if_due);
+    if let Err(e) = result {
+        eprintln!("{} {e}", "WARNING:".yellow().bold());
+    }
+}
+
+/// The changed files for `rona -l`, from the status daemon when one is running and
+/// no `--scope` narrows them.
+fn status_files() -> Result<Vec<String>> {
+    #[cfg(unix)]
+    if path_scope().is_none()
+        && let Some(files) = crate::git::daemon::cached_status_files()
+    {
+        tracing::debug!("Status served by the daemon");
+        return Ok(files);
+    }
+    get_status_files()
+}
+
+/// The changed files with their states for `rona status`, from the status daemon when
+/// one is running and no `--scope` narrows them.
+fn file_states() -> Result<Vec<(FileState, String)>> {
+    #[cfg(unix)]
+    if path_scope().is_none()
+        && let Some(states) = crate::git::daemon::cached_file_states()
+    {
+        tracing::debug!("Status served by the daemon");
+        return Ok(states);
+    }
+    get_file_states()
+}
+
+/// Handle `daemon`, which serves the changed files until stopped, or stops the
+/// running daemon with `--stop`.
+///
+/// # Errors
+/// * If the repository cannot be located or the socket cannot be created
+/// * If another daemon already serves the repository
+#[cfg(unix)]
+fn handle_daemon(stop: bool, config: &Config) -> Result<()> {
+    use crate::git::daemon::{daemon_socket, run_daemon, stop_daemon};
+
+    if stop {
+        if stop_daemon()? {
+            println!("{} Stopped the rona daemon", success_mark());
+            return Ok(());
+        }
+        return Err(RonaError::InvalidInput(
+            "No rona daemon is running for this repository".to_string(),
+        ));
+    }
+
+    let refresh_interval = config.project_config.daemon.refresh_interval()?;
+    let top_level = get_top_level_path()?;
+    std::env::set_current_dir(&top_level)?;
+    let socket = daemon_socket()?;
+    println!(
+        "Serving the status of {} on {} (stop with `rona daemon --stop`)",
+        top_level.display(),
+        socket.display()
+    );
+    run_daemon(&socket, refresh_interval)
+}
+
+/// Handle `daemon` on platforms without unix sockets.
+///
+/// # Errors
+/// * Always, since the daemon needs unix sockets
+#[cfg(not(unix))]
+fn handle_daemon(_stop: bool, _config: &Config) -> Result<()> {
+    Err(RonaError::InvalidInput(
+        "rona daemon needs unix sockets and is not available on this platform".to_string(),
+    ))
+}
+
+/// The files `rona list-status --output json` prints.
+#[derive(Debug, Serialize)]
+struct FileList {
+    files: Vec<String>,
+    count: usize,
+}
+
+/// Handle the `ListStatus` command, optionally narrowed to paths fuzzy-matching `filter`.
+fn handle_list_status(filter: Option<&str>, config: &Config) -> Result<()> {
+    start_auto_fetch(config);
+
+    let files = status_files()?;
+    let files = match filter {
+        Some(query) => fuzzy_filter(files, query, String::as_str),
+        None => files,
+    };
+    if config.json_output {
+        let list = FileList {
+            count: files.len(),
+            files,
+        };
+        println!("{}", to_versioned_json(&list, false)?);
+        return Ok(());
+    }
+    // Print each file on a new line for fish shell completion
+    for file in files {
+        println!("{file}");
+    }
+    Ok(())
+}
+
+/// Handle the `status` command: the branch and its upstream, then the changed files
+/// grouped into staged, modified, deleted, untracked and conflicted, with counts.
+///
+/// # Errors
+/// * If reading git status fails
+fn handle_status(config: &Config) -> Result<()> {
+    start_auto_fetch(config);
+
+    let branch = get_current_branch().ok().filter(|branch| branch != "HEAD");
+    if config.json_output {
+        let upstream = branch.as_ref().and_then(|_| upstream_status());
+        let report = StatusReport::new(branch, current_head(), upstream, file_states()?);
+        println!("{}", to_versioned_json(&report, false)?);
+        return Ok(());
+    }
+    match (&branch, upstream_status()) {
+        (None, _) => println!(
+            "{} {}",
+            "HEAD detached at".bold(),
+            current_head()
+                .map(|sha| sha.chars().take(7).collect::<String>())
+                .unwrap_or_default()
+                .cyan()
+        ),
+        (Some(branch), None) => {
+            println!(
+                "On branch {} {}",
+                branch.cyan().bold(),
+                "(no upstream)".dimmed()
+            );
+        }
+        (Some(branch), Some(status)) => {
+            let divergence = match (status.ahead, status.behind) {
+                (0, 0) => "up to date".green().to_string(),
+                (ahead, 0) => format!("{ahead} ahead").yellow().to_string(),
+                (0, behind) => format!("{behind} behind").yellow().to_string(),
+                (ahead, behind) => format!("{ahead} ahead, {behind} behind").red().to_string(),
+            };
+            println!(
+                "On branch {} tracking {}: {divergence}",
+                branch.cyan().bold(),
+                status.upstream
+            );
+        }
+    }
+
+    let states = file_states()?;
+    if states.is_empty() {
+        println!("\nNothing to commit, working tree clean");
+        warn_out_of_cone_files();
+        return Ok(());
+    }
+
+    let mut counts = Vec::new();
+    for files in states.chunk_by(|a, b| status_group(a.0).0 == status_group(b.0).0) {
+        let Some((first, _)) = files.first() else {
+            continue;
+        };
+        let (title, color) = status_group(*first);
+        println!("\n{} ({})", title.color(color).bold(), files.len());
+        for (state, path) in files {
+            println!("  {} {path}", format!("{:<10}", state.label()).color(color));
+        }
+        counts.push(format!("{} {}", files.len(), title.to_lowercase()));
+    }
+    println!("\n{}", counts.join(", "));
+    warn_out_of_cone_files();
+    Ok(())
+}
+
+/// The group of `rona status` a file state is listed under, and its color. Groups
+/// follow the order of [`FileState`], so sorted states stay grouped.
+const fn status_group(state: FileState) -> (&'static str, Color) {
+    match state {
+        FileState::Conflicted => ("Conflicted", Color::Red),
+        FileState::Modified => ("Modified", Color::Yellow),
+        FileState::Deleted => ("Deleted", Color::Red),
+        FileState::Untracked => ("Untracked", Color::BrightBlack),
+        FileState::StagedNew
+        | FileState::StagedModified
+        | FileState::StagedDeleted
+        | FileState::StagedRenamed => ("Staged", Color::Green),
+    }
+}
+
+/// Handle the `todo` command which lists markers added by the staged changes.
+///
+/// # Errors
+/// * If the staged changes cannot be read
+/// * If `check` is set and markers were found
+fn handle_todo(check: bool, config: &Config) -> Result<()> {
+    let todos = staged_todos(&config.project_config.todo.markers)?;
+    if todos.is_empty() {
+        println!("No new TODO markers in the staged changes.");
+        return Ok(());
+    }
+
+    println!("{} new TODO marker(s) in the staged changes:", todos.len());
+    print_todos(&todos);
+    if check {
+        return Err(RonaError::InvalidInput(format!(
+            "{} new TODO marker(s) in the staged changes",
+            todos.len()
+        )));
+    }
+    Ok(())
+}
+
+/// Handle the `prune` command which cleans up refs, reflogs, objects and rona state.
+///
+/// # Errors
+/// * If `max_age` is not a valid duration
+/// * If any of the underlying git commands fails
+fn handle_prune(max_age: &str, config: &Config) -> Result<()> {
+    use crate::git::maintenance::{
+        branches_with_gone_upstream, count_objects, expire_reflogs, expire_state_files,
+        prune_remote_tracking_refs, prune_unreachable_objects,
+    };
+
+    let max_age =
+        parse_duration(max_age).map_err(|e| RonaError::InvalidInput(format!("--max-age: {e}")))?;
+    let dry_run = config.dry_run;
+    let before = count_objects()?;
+
+    let pruned_refs = prune_remote_tracking_refs(dry_run)?;
+    let verb = if dry_run { "Would prune" } else { "Pruned" };
+    if pruned_refs.is_empty() {
+        println!("No stale remote-tracking refs.");
+    } else {
+        println!("{verb} {} remote-tracking refs:", pruned_refs.len());
+        println!("{}", format_list(&pruned_refs));
+    }
+
+    expire_reflogs(dry_run)?;
+    if dry_run {
+        println!("Would expire old reflog entries (gc.reflogExpire).");
+    } else {
+        println!("Expired old reflog entries.");
+    }
+
+    let unreachable = prune_unreachable_objects(dry_run)?;
+
+    let state_files = expire_state_files(max_age, dry_run)?;
+    if !state_files.is_empty() {
+        let verb = if dry_run { "Would remove" } else { "Removed" };
+        println!("{verb} {} stale rona state files:", state_files.len());
+        let names: Vec<String> = state_files
+            .iter()
+            .map(|p| p.display().to_string())
+            .collect();
+        println!("{}", format_list(&names));
+    }
+
+    if dry_run {
+        println!("Would remove {unreachable} unreachable loose objects.");
+    } else {
+        let after = count_objects()?;
+        println!(
+            "{} Reclaimed {} loose objects ({} KiB).",
+            success_mark(),
+            before.loose.saturating_sub(after.loose),
+            before.loose_kib.saturating_sub(after.loose_kib)
+        );
+    }
+
+    // The trunk is never offered for deletion, even when its upstream is gone.
+    let default_branch = get_default_branch()?;
+    let gone: Vec<String> = branches_with_gone_upstream()?
+        .into_iter()
+        .filter(|branch| *branch != default_branch)
+        .collect();
+    if !gone.is_empty() {
+        println!(
+            "\n{} Local branches whose upstream is gone (delete with `git branch -D <name>`):",
+            "NOTE:".cyan().bold()
+        );
+        println!("{}", format_list(&gone));
+    }
+
+    Ok(())
+}
+
+/// Handle the Push command which pushes changes to the remote repository.
+///
+/// # Arguments
+/// * `args` - Additional arguments to pass to git push
+/// * `config` - Global configuration including verbose and dry-run settings
+///
+/// # Errors
+/// * If git push operation fails
+fn handle_push(args: &[String], config: &Config) -> Result<()> {
+    if !check_before_push(args, config) {
+        println!("Push cancelled.");
+        return Ok(());
+    }
+    push_and_notify(args, config)
+}
+
+/// The `check_before_push` checks: the branch the push updates is fetched (compared as
+/// of the last fetch in a dry run) and a warning lists its commits `HEAD` does not
+/// have. A force push that would discard them asks first, since after the fetch even
+/// `--force-with-lease` would let it through.
+///
+/// Returns `false` when the user does not confirm the force push.
+fn check_before_push(args: &[String], config: &Config) -> bool {
+    if !config.project_config.check_before_push {
+        return true;
+    }
+    let remote = push_remote(args);
+    let Some(divergence) = push_divergence(&remote, !config.dry_run) else {
+        return true;
+    };
+    let tracking = &divergence.tracking;
+    if !divergence.fetched && !config.dry_run {
+        println!(
+            "{} Could not fetch {remote}; comparing with {tracking} as of the last fetch",
+            "WARNING:".yellow().bold()
+        );
+    }
+    if divergence.missing.is_empty() {
+        return true;
+    }
+
+    let count = divergence.missing.len();
+    let force = force_push(args);
+    let state = if divergence.ahead > 0 {
+        format!(
+            "has diverged: {count} commit(s) there and {} here",
+            divergence.ahead
+        )
+    } else {
+        format!("has {count} commit(s) HEAD does not")
+    };
+    println!("{} {tracking} {state}", "WARNING:".yellow().bold());
+    for commit in divergence.missing.iter().take(10) {
+        println!("  {commit}");
+    }
+    if count > 10 {
+        println!("  ... and {} more", count - 10);
+    }
+
+    if force == ForcePush::No {
+        println!("Integrate them first, e.g. with `rona branch pull`.");
+        return true;
+    }
+    if force == ForcePush::Force {
+        println!(
+            "Prefer --force-with-lease, which refuses to overwrite commits you have not fetched."
+        );
+    }
+    if config.dry_run {
+        println!("Would ask before force pushing over {tracking}");
+        return true;
+    }
+    Confirm::with_theme(&prompt_theme())
+        .with_prompt(format!(
+            "Force push anyway, discarding {count} commit(s) on {tracking}?"
+        ))
+        .default(false)
+        .interact()
+        .unwrap_or(false)
+}
+
+/// Handle `rona push --review`: pushes `HEAD` to the `[review] ref`.
+///
+/// # Errors
+/// * If the review ref template is invalid or renders an invalid ref
+/// * If the arguments name a refspec, or if the push fails
+fn handle_review_push(args: &[String], target: Option<String>, config: &Config) -> Result<()> {
+    let review = &config.project_config.review;
+    let template = review.ref_template.as_deref().unwrap_or(DEFAULT_REVIEW_REF);
+    validate_review_ref_template(template)
+        .map_err(|e| RonaError::InvalidInput(format!("review.ref: {e}")))?;
+
+    let target = match target {
+        Some(target) => target,
+        None => get_default_branch()?,
+    };
+    let variables = ReviewRefVariables::new(get_current_branch()?, target)?;
+    let review_ref = process_review_ref_template(template, &variables)?;
+    if !is_valid_ref_name(&review_ref) {
+        return Err(RonaError::InvalidInput(format!(
+            "review.ref renders `{review_ref}`, which is not a valid ref name"
+        )));
+    }
+
+    let push_args = review_push_args(
+        args,
+        review.remote.as_deref().unwrap_or("origin"),
+        &review_ref,
+    )?;
+    push_and_notify(&push_args, config)?;
+    if !config.dry_run {
+        println!("{} Pushed HEAD to {review_ref}", success_mark());
+    }
+    Ok(())
+}
+
+/// Pushes, then posts to the `[notify.webhook]` chat webhook when one is configured.
+///
+/// Warns first when the push remote is not one of the `expected_remotes`. A failed
+/// notification only prints a warning: the push itself succeeded. A dry run with
+/// `--output json` prints the [`PushPreview`] instead.
+///
+/// # Errors
+/// * If `require_signed` is set and an outgoing commit is not signed, or the outgoing
+///   commits cannot be listed
+/// * If a pre-push command or the push fails
+fn push_and_notify(args: &[String], config: &Config) -> Result<()> {
+    let project = &config.project_config;
+    let preview = config.json_output && config.dry_run;
+    let remote_warning = unexpected_remote_warning(args, project);
+    if let Some(warning) = remote_warning.as_ref().filter(|_| !preview) {
+        println!("{} {warning}", "WARNING:".yellow().bold());
+    }
+    if project.require_signed {
+        let unsigned = unsigned_outgoing_commits(args, &push_remote(args))?;
+        if !unsigned.is_empty() {
+            return Err(RonaError::Git(GitError::SigningRequired {
+                reason: format!(
+                    "{} outgoing commit(s) are not signed:\n{}",
+                    unsigned.len(),
+                    unsigned.join("\n")
+                ),
+            }));
+        }
+    }
+
+    if preview {
+        let mut preview = PushPreview {
+            summary: push_summary(args, &project.url_rewrites)?,
+            args: args.to_vec(),
+            pre_push: if args.iter().any(|arg| arg == "--no-verify") {
+                Vec::new()
+            } else {
+                project.pre_push.commands.clone()
+            },
+            notify: project
+                .notify
+                .webhook
+                .as_ref()
+                .map(|webhook| webhook.host().to_string()),
+            tracker: Vec::new(),
+            warnings: remote_warning.into_iter().collect(),
+        };
+        match pushed_tracker_commands(&preview.summary, config) {
+            Ok(tracker) => preview.tracker = tracker,
+            Err(e) => preview.warnings.push(format!("Tracker commands: {e}")),
+        }
+        println!("{}", to_versioned_json(&preview, false)?);
+        return Ok(());
+    }
+
+    let webhook = project.notify.webhook.as_ref();
+    let summary = if webhook.is_some() || !project.tracker.transitions.is_empty() {
+        push_summary(args, &project.url_rewrites).ok()
+    } else {
+        None
+    };
+
+    git_push(
+        args,
+        &config.project_config.pre_push,
+        config.verbose,
+        config.dry_run,
+    )?;
+
+    if let Some(webhook) = webhook {
+        notify_push(webhook, summary.as_ref(), config.dry_run);
+    }
+    if let Some(summary) = &summary {
+        run_tracker(summary, config);
+    }
+    Ok(())
+}
+
+/// Posts `summary` to the `[notify.webhook]` chat webhook, or says it would on a dry
+/// run. Nothing is posted when the push publishes no new commits, and a failure only
+/// prints a warning.
+fn notify_push(webhook: &WebhookConfig, summary: Option<&PushSummary>, dry_run: bool) {
+    if dry_run {
+        println!("Would post a push notification to {}", webhook.host());
+        return;
+    }
+    let Some(summary) = summary.filter(|s| !s.subjects.is_empty()) else {
+        return;
+    };
+    match render_notification(webhook, summary).and_then(|text| post_webhook(webhook, &text)) {
+        Ok(()) => println!("{} Notified {}", success_mark(), webhook.host()),
+        Err(e) => println!(
+            "{} Push notification failed: {e}",
+            "WARNING:".yellow().bold()
+        ),
+    }
+}
+
+/// The `[tracker]` commands pushing `summary` runs: the transitions of the commit
+/// types of the pushed commits, for the ticket `ticket_pattern` finds in the branch.
+/// None without a `ticket_pattern`.
+///
+/// # Errors
+/// * If `ticket_pattern` or the commit template is invalid
+fn pushed_tracker_commands(summary: &PushSummary, config: &Config) -> Result<Vec<TrackerCommand>> {
+    let project = &config.project_config;
+    let Some(pattern) = project
+        .ticket_pattern
+        .as_deref()
+        .filter(|_| !project.tracker.transitions.is_empty())
+    else {
+        return Ok(Vec::new());
+    };
+
+    let ticket = extract_ticket(&summary.branch, pattern)?;
+    let (template, commit_types) = lint_rules(config)?;
+    let subject_types: Vec<Option<String>> = summary
+        .subjects
+        .iter()
+        .map(|subject| {
+            commit_type_span(subject, template, &commit_types).map(|span| subject[span].to_string())
+        })
+        .collect();
+    Ok(tracker_commands(
+        &project.tracker,
+        &ticket,
+        &subject_types,
+        &summary.subjects,
+    ))
+}
+
+/// Runs the `[tracker]` commands after a push, or lists them on a dry run. A failure,
+/// including an invalid `ticket_pattern`, only prints a warning: the push itself
+/// succeeded.
+fn run_tracker(summary: &PushSummary, config: &Config) {
+    let commands = match pushed_tracker_commands(summary, config) {
+        Ok(commands) => commands,
+        Err(e) => {
+            println!(
+                "{} Tracker commands failed: {e}",
+                "WARNING:".yellow().bold()
+            );
+            return;
+        }
+    };
+    if config.dry_run {
+        for command in &commands {
+            println!(
+                "Would run tracker command for {} ({}): {}",
+                command.ticket, command.commit_type, command.command
+            );
+        }
+        return;
+    }
+    let Some(first) = commands.first() else {
+        return;
+    };
+
+    let failures = get_top_level_path().and_then(|root| {
+        run_tracker_commands(&commands, &config.project_config.tracker, summary, &root)
+    });
+    match failures {
+        Ok(failures) if failures.is_empty() => {
+            println!("{} Updated {}", success_mark(), first.ticket);
+        }
+        Ok(failures) => {
+            for (command, reason) in failures {
+                println!(
+                    "{} Tracker command failed: {command} ({reason})",
+                    "WARNING:".yellow().bold()
+                );
+            }
+        }
+        Err(e) => println!(
+            "{} Tracker commands failed: {e}",
+            "WARNING:".yellow().bold()
+        ),
+    }
+}
+
+/// The warning shown when the push remote points outside the `expected_remotes`, e.g.
+/// work code about to be pushed to a personal fork.
+fn unexpected_remote_warning(args: &[String], project: &ProjectConfig) -> Option<String> {
+    if project.expected_remotes.is_empty() {
+        return None;
+    }
+    let remote = push_remote(args);
+    let location =
+        remote_url(&remote, &project.url_rewrites).and_then(|url| remote_location(&url))?;
+    (!is_expected_remote(&location, &project.expected_remotes)).then(|| {
+        format!(
+            "{remote} points to {location}, not one of the expected remotes ({})",
+            project.expected_remotes.join(", ")
+        )
+    })
+}
+
+/// Handle the Set command which updates the editor in the configuration.
 ///
 /// # Arguments
-/// * `interactive` - Whether to prompt for commit message in terminal
-/// * `no_commit_number` - Whether to include commit number in message
+/// * `editor` - The editor command to set
 /// * `config` - Global configuration including verbose and dry-run settings
 ///
 /// # Errors
-/// * If creating needed files fails
-/// * If generating commit message fails
-/// * If writing commit message fails
-/// * If launching editor fails (in non-interactive mode)
-fn handle_generate(interactive: bool, no_commit_number: bool, config: &Config) -> Result<()> {
+/// * If updating configuration file fails
+fn handle_set(editor: &str, config: &Config) -> Result<()> {
     if config.dry_run {
-        println!("Would create files: commit_message.md, .commitignore");
-        println!("Would add files to .git/info/exclude");
+        println!("Would set editor to: {editor}");
         return Ok(());
     }
+    config.set_editor(editor)?;
+    Ok(())
+}
 
-    create_needed_files()?;
-
-    let commit_type = {
-        let commit_types_vec = config.project_config.commit_types.as_ref().map_or_else(
-            || COMMIT_TYPES.to_vec(),
-            |v| v.iter().map(String::as_str).collect::<Vec<&str>>(),
-        );
-
-        let index = FuzzySelect::with_theme(&prompt_theme())
-            .with_prompt("Select commit type")
-            .items(&commit_types_vec)
-            .default(0)
-            .interact_opt()
-            .map_err(|_| RonaError::UserCancelled)?
-            .ok_or(RonaError::UserCancelled)?;
-        commit_types_vec[index]
+/// Handle the `schema` command which prints the JSON Schema of one output, or of all
+/// of them.
+///
+/// # Errors
+/// * If the schema cannot be serialized
+fn handle_schema(name: Option<&str>) -> Result<()> {
+    let schema = match name {
+        Some(name) => output_schema(name).ok_or_else(|| {
+            RonaError::InvalidInput(format!(
+                "Unknown output '{name}'. Available outputs: {}",
+                OUTPUT_NAMES.join(", ")
+            ))
+        })?,
+        None => all_schemas(),
     };
-
-    if interactive {
-        // Only prompt for extra fields referenced in the commit template. Fields inherited from
-        // an extended config (or otherwise configured) but unused by this template are skipped
-        // rather than prompted for a value that would be discarded.
-        let commit_template = config
-            .project_config
-            .commit_template
-            .as_deref()
-            .unwrap_or(DEFAULT_COMMIT_TEMPLATE);
-        let referenced_fields: Vec<ExtraField> = config
-            .project_config
-            .commit_extra_fields
-            .iter()
-            .filter(|f| {
-                let referenced = commit_template.contains(&format!("{{{}}}", f.name))
-                    || commit_template.contains(&format!("{{?{}}}", f.name));
-                if !referenced {
-                    println!(
-                        "[NOTE] Extra field '{}' is not referenced in the template; skipping.",
-                        f.name
-                    );
-                }
-                referenced
-            })
-            .cloned()
-            .collect();
-
-        // In interactive mode, prompt all fields (including message) in configured order
-        let (message, extra_values) = prompt_interactive_fields(
-            &referenced_fields,
-            &config.project_config.commit_fields_order,
-            config.project_config.message_prefetch.as_ref(),
-            config.project_config.commit_message.as_ref(),
-        )?;
-        handle_interactive_mode(
-            commit_type,
-            no_commit_number,
-            &message,
-            &extra_values,
-            config,
-        )?;
-    } else {
-        // In editor mode, generate the template file first, then open editor
-        generate_commit_message(commit_type, no_commit_number)?;
-        handle_editor_mode(config)?;
-    }
+    let json = serde_json::to_string_pretty(&schema)
+        .map_err(|e| RonaError::InvalidInput(format!("Cannot serialize schema: {e}")))?;
+    println!("{json}");
     Ok(())
 }
 
-/// Handle interactive mode for generate command
-fn handle_interactive_mode(
-    commit_type: &str,
-    no_commit_number: bool,
-    message: &str,
-    extra_values: &HashMap<String, String>,
-    config: &Config,
-) -> Result<()> {
-    use std::fs;
-
-    let project_root = get_top_level_path()?;
-    let commit_file_path = project_root.join(COMMIT_MESSAGE_FILE_PATH);
-
-    if message.trim().is_empty() {
-        println!(
-            "{} Empty message provided. Exiting.",
-            "WARNING:".yellow().bold()
-        );
+/// Handle the `copy` command which copies a formatted reference to a commit.
+///
+/// # Errors
+/// * If the format is unknown or its template invalid
+/// * If the reference does not name a commit
+/// * If the clipboard cannot be written
+fn handle_copy(reference: &str, format: Option<&str>, print: bool, config: &Config) -> Result<()> {
+    let copy = &config.project_config.copy;
+    let format = format
+        .or(copy.format.as_deref())
+        .unwrap_or(DEFAULT_COPY_FORMAT);
+    let template = copy_template(format, &copy.formats)?;
+    let text = render_copy(template, &commit_reference(reference)?)?;
+
+    if print {
+        println!("{text}");
         return Ok(());
     }
+    copy_to_clipboard(&text)?;
+    println!("Copied {text} to clipboard");
+    Ok(())
+}
 
-    let branch_name = format_branch_name(&COMMIT_TYPES, &get_current_branch()?);
-    let commit_number = if no_commit_number {
-        None
-    } else {
-        Some(get_current_commit_nb()? + 1)
-    };
-
-    // Get template from config or use default with conditional syntax
+/// Handle the `show` command which displays a single commit.
+///
+/// # Errors
+/// * If the reference does not name a commit
+/// * If the commit cannot be serialized to JSON
+fn handle_show(reference: &str, json: bool, config: &Config) -> Result<()> {
+    let commit_types: Vec<&str> = config.project_config.commit_types.as_ref().map_or_else(
+        || COMMIT_TYPES.to_vec(),
+        |v| v.iter().map(CommitType::name).collect(),
+    );
     let template = config
         .project_config
         .commit_template
         .as_deref()
         .unwrap_or(DEFAULT_COMMIT_TEMPLATE);
 
-    // Validate template (including any extra field variable names)
-    let extra_names: Vec<&str> = extra_values.keys().map(String::as_str).collect();
-    if let Err(e) = validate_template(template, &extra_names) {
-        println!(
-            "{} Template validation error: {e}",
-            "WARNING:".yellow().bold()
-        );
-        println!("Using fallback format...");
-        let formatted_message = if no_commit_number {
-            format!("({} on {}) {}", commit_type, branch_name, message.trim())
-        } else {
-            format!(
-                "[{}] ({} on {}) {}",
-                commit_number.unwrap_or(0),
-                commit_type,
-                branch_name,
-                message.trim()
-            )
-        };
-        fs::write(&commit_file_path, &formatted_message)?;
-        println!("\n{} Commit message created!", "✓".green());
-        println!("Message: {formatted_message}");
+    let details = commit_details(reference, template, &commit_types)?;
+    if json {
+        println!("{}", to_versioned_json(&details, true)?);
         return Ok(());
     }
 
-    // Create template variables
-    let variables = TemplateVariables::new(
-        commit_number,
-        commit_type.to_string(),
-        branch_name,
-        message.trim().to_string(),
-    )?;
+    println!("{} {}", "commit".yellow(), details.sha.yellow());
+    println!("Author:    {} <{}>", details.author, details.email);
+    println!("Date:      {}", details.date);
+    let signature = match details.signature.status {
+        "good" => "good".green(),
+        "none" => "none".dimmed(),
+        "bad" | "revoked" => details.signature.status.red().bold(),
+        other => other.yellow(),
+    };
+    match &details.signature.signer {
+        Some(signer) => println!("Signature: {signature} ({signer})"),
+        None => println!("Signature: {signature}"),
+    }
 
-    // Process template (extra_values are substituted alongside built-in variables)
-    let formatted_message = process_template(template, &variables, extra_values)?;
+    let colors = &config.project_config.type_colors;
+    println!(
+        "\n    {}",
+        paint_subject_type(&details.subject, template, &commit_types, colors, true)
+    );
+    for line in details.body.lines() {
+        println!("    {line}");
+    }
 
-    // Write the formatted message to commit_message.md
-    fs::write(&commit_file_path, &formatted_message)?;
+    match &details.header_fields {
+        Some(fields) if !fields.is_empty() => {
+            println!("\n{}", "Header fields:".cyan().bold());
+            let width = fields.iter().map(|f| f.name.len()).max().unwrap_or(0);
+            for field in fields {
+                let value = if field.name == "commit_type" {
+                    paint_type(&field.value, colors)
+                } else {
+                    field.value.clone()
+                };
+                println!("  {:width$}  {value}", field.name);
+            }
+        }
+        Some(_) => {}
+        None => println!(
+            "\n{} Subject does not match the commit template: {template}",
+            "NOTE:".cyan().bold()
+        ),
+    }
 
-    println!("\n{} Commit message created!", "✓".green());
-    println!("Message: {formatted_message}");
+    if !details.trailers.is_empty() {
+        println!("\n{}", "Trailers:".cyan().bold());
+        for trailer in &details.trailers {
+            println!("  {}: {}", trailer.key, trailer.value);
+        }
+    }
+    if let Some(notes) = &details.notes {
+        println!("\n{}", "Notes:".cyan().bold());
+        for line in notes.lines() {
+            println!("  {line}");
+        }
+    }
+    if !details.stat.is_empty() {
+        println!("\n{}", details.stat);
+    }
     Ok(())
 }
 
-/// Handle editor mode for generate command
-fn handle_editor_mode(config: &Config) -> Result<()> {
-    let editor = config.get_editor()?;
-    let project_root = get_top_level_path()?;
-    let commit_file_path = project_root.join(COMMIT_MESSAGE_FILE_PATH);
+/// Handle `snippet list` which prints the configured snippets.
+fn handle_snippet_list(config: &Config) {
+    let snippets = &config.project_config.snippets;
+    if snippets.is_empty() {
+        println!("No snippets configured. Add them under [snippets] in .rona.toml.");
+        return;
+    }
+    for (name, text) in snippets {
+        let preview = text.lines().next().unwrap_or_default();
+        println!("{}  {}", format!("/{name}").cyan().bold(), preview.dimmed());
+    }
+}
 
-    Command::new(&editor)
-        .arg(&commit_file_path)
-        .spawn()
-        .map_err(|e| RonaError::CommandFailed {
-            command: format!("Failed to spawn editor '{editor}': {e}"),
-        })?
-        .wait()
-        .map_err(|e| RonaError::CommandFailed {
-            command: format!("Failed to wait for editor '{editor}': {e}"),
-        })?;
-    Ok(())
+/// Prints the `date_format`/`time_format` values (top-level and per language), the
+/// `timezone`, the `ticket_pattern` and the `[impact]` globs that do not parse,
+/// returning how many there are.
+fn check_formats(project: &ProjectConfig) -> usize {
+    let mut problems = 0;
+    let mut formats = vec![
+        ("date_format".to_string(), project.date_format.as_deref()),
+        ("time_format".to_string(), project.time_format.as_deref()),
+    ];
+    for (lang, locale) in project.locales() {
+        formats.push((
+            format!("templates.locales.{lang}.date_format"),
+            locale.date_format.as_deref(),
+        ));
+        formats.push((
+            format!("templates.locales.{lang}.time_format"),
+            locale.time_format.as_deref(),
+        ));
+    }
+    for (label, format) in formats {
+        if let Some(Err(e)) = format.map(validate_date_format) {
+            problems += 1;
+            println!("{} {}: {e}", failure_mark(), label.bold());
+        }
+    }
+    if let Some(Err(e)) = project.timezone.as_deref().map(parse_timezone) {
+        problems += 1;
+        println!("{} {}: {e}", failure_mark(), "timezone".bold());
+    }
+    if let Some(Err(e)) = project.ticket_pattern.as_deref().map(regex::Regex::new) {
+        problems += 1;
+        println!("{} {}: {e}", failure_mark(), "ticket_pattern".bold());
+    }
+    for (glob, e) in invalid_globs(&project.impact) {
+        problems += 1;
+        println!(
+            "{} {}: {e}",
+            failure_mark(),
+            format!("impact.\"{glob}\"").bold()
+        );
+    }
+    problems
 }
 
-/// Handle the Initialize command which creates the initial configuration file.
-///
-/// # Arguments
-/// * `editor` - The editor command to configure
-/// * `config` - Global configuration including verbose and dry-run settings
-///
-/// # Errors
-/// * If creating configuration file fails
-fn handle_initialize(editor: &str, config: &Config) -> Result<()> {
-    if config.dry_run {
-        println!("Would create config file with editor: {editor}");
-        return Ok(());
+/// Prints the unknown variables and malformed blocks of each labeled template, checked
+/// against its valid variables, returning how many problems there are.
+fn check_templates(checks: &[(String, &str, &[&str])]) -> usize {
+    let mut problems = 0;
+    for (label, template, valid) in checks {
+        let unknown = unknown_variables(template, valid);
+        if unknown.is_empty() {
+            if let Err(e) = validate_template_with_vars(template, valid) {
+                problems += 1;
+                println!("{} {}: {e}", failure_mark(), label.bold());
+            }
+            continue;
+        }
+        for variable in unknown {
+            problems += 1;
+            println!(
+                "{} {}: unknown variable {variable}",
+                failure_mark(),
+                label.bold()
+            );
+        }
     }
-    config.create_config_file(editor)?;
-    Ok(())
+    problems
 }
 
-/// Handle the `ListStatus` command
-fn handle_list_status() -> Result<()> {
-    let files = get_status_files()?;
-    // Print each file on a new line for fish shell completion
-    for file in files {
-        println!("{file}");
+/// The templates of the `[notify]`, `[format_patch]`, `[review]`, `[release]` and
+/// `[copy]` sections, with the variables they may use, for `template check`.
+fn section_templates(project: &ProjectConfig) -> Vec<(String, &str, &'static [&'static str])> {
+    let mut templates: Vec<(String, &str, &'static [&'static str])> = Vec::new();
+    if let Some(template) = project
+        .notify
+        .webhook
+        .as_ref()
+        .and_then(|w| w.template.as_deref())
+    {
+        templates.push((
+            "notify.webhook.template".to_string(),
+            template,
+            &PUSH_TEMPLATE_VARIABLES,
+        ));
     }
-    Ok(())
+    if let Some(template) = project
+        .notify
+        .webhook
+        .as_ref()
+        .and_then(|w| w.release_template.as_deref())
+    {
+        templates.push((
+            "notify.webhook.release_template".to_string(),
+            template,
+            &RELEASE_TEMPLATE_VARIABLES,
+        ));
+    }
+    if let Some(template) = project.format_patch.cover_letter.as_deref() {
+        templates.push((
+            "format_patch.cover_letter".to_string(),
+            template,
+            &COVER_LETTER_TEMPLATE_VARIABLES,
+        ));
+    }
+    if let Some(template) = project.review.ref_template.as_deref() {
+        templates.push((
+            "review.ref".to_string(),
+            template,
+            &REVIEW_REF_TEMPLATE_VARIABLES,
+        ));
+    }
+    if let Some(template) = project.release.message.as_deref() {
+        templates.push((
+            "release.message".to_string(),
+            template,
+            &RELEASE_TEMPLATE_VARIABLES,
+        ));
+    }
+    for (name, template) in &project.copy.formats {
+        templates.push((
+            format!("copy.formats.{name}"),
+            template,
+            &COPY_TEMPLATE_VARIABLES,
+        ));
+    }
+    if let Some(template) = project.copy.format.as_deref()
+        && template.contains('{')
+        && !project.copy.formats.contains_key(template)
+    {
+        templates.push((
+            "copy.format".to_string(),
+            template,
+            &COPY_TEMPLATE_VARIABLES,
+        ));
+    }
+    templates
 }
 
-/// Handle the Push command which pushes changes to the remote repository.
+/// Handle `template check` which lints every configured template.
 ///
-/// # Arguments
-/// * `args` - Additional arguments to pass to git push
-/// * `config` - Global configuration including verbose and dry-run settings
+/// Unknown variables are reported with their line and column, since with
+/// `strict_templates` they fail at render time instead of rendering as empty text.
 ///
 /// # Errors
-/// * If git push operation fails
-fn handle_push(args: &[String], config: &Config) -> Result<()> {
-    git_push(args, config.verbose, config.dry_run)?;
+/// * If any template has unknown variables or malformed conditional blocks
+fn handle_template_check(config: &Config) -> Result<()> {
+    let project = &config.project_config;
+    let commit_names: Vec<&str> = project
+        .commit_extra_fields
+        .iter()
+        .map(|f| f.name.as_str())
+        .collect();
+    let mut commit_vars = COMMIT_TEMPLATE_VARIABLES.to_vec();
+    commit_vars.extend_from_slice(&commit_names);
+    let mut branch_vars = BRANCH_TEMPLATE_VARIABLES.to_vec();
+    branch_vars.extend(
+        project
+            .branch_extra_fields
+            .iter()
+            .map(|f| f.name.as_str())
+            .chain(commit_names.iter().copied()),
+    );
+
+    let mut checks: Vec<(String, &str, &[&str])> = vec![(
+        "commit_template".to_string(),
+        project
+            .commit_template
+            .as_deref()
+            .unwrap_or(DEFAULT_COMMIT_TEMPLATE),
+        &commit_vars,
+    )];
+    if let Some(template) = project.branch_template.as_deref() {
+        checks.push(("branch_template".to_string(), template, &branch_vars));
+    }
+    for (name, template) in project.named_templates() {
+        checks.push((format!("templates.{name}"), template, &commit_vars));
+    }
+    for (lang, locale) in project.locales() {
+        if let Some(template) = locale.commit_template.as_deref() {
+            checks.push((
+                format!("templates.locales.{lang}.commit_template"),
+                template,
+                &commit_vars,
+            ));
+        }
+    }
+    for (name, text) in &project.snippets {
+        checks.push((format!("snippets.{name}"), text, &commit_vars));
+    }
+    checks.extend(section_templates(project));
+
+    let problems = check_templates(&checks) + check_formats(project);
+
+    if problems > 0 {
+        return Err(RonaError::InvalidInput(format!(
+            "{problems} template problem(s) found in {} template(s)",
+            checks.len()
+        )));
+    }
+    println!("{} {} template(s) OK", success_mark(), checks.len());
     Ok(())
 }
 
-/// Handle the Set command which updates the editor in the configuration.
-///
-/// # Arguments
-/// * `editor` - The editor command to set
-/// * `config` - Global configuration including verbose and dry-run settings
+/// Handle `snippet insert` which appends a rendered snippet to a message file.
 ///
 /// # Errors
-/// * If updating configuration file fails
-fn handle_set(editor: &str, config: &Config) -> Result<()> {
+/// * If the snippet does not exist or does not render
+/// * If the message file does not exist or cannot be written
+fn handle_snippet_insert(name: &str, file: Option<&str>, config: &Config) -> Result<()> {
+    let path = match file {
+        Some(file) => std::path::PathBuf::from(file),
+        None => get_top_level_path()?.join(COMMIT_MESSAGE_FILE_PATH),
+    };
+    if !path.exists() {
+        return Err(if file.is_some() {
+            RonaError::InvalidInput(format!("{} does not exist", path.display()))
+        } else {
+            RonaError::Git(crate::errors::GitError::CommitMessageNotFound)
+        });
+    }
+
+    let extra_values = snippet_extra_values(config, &HashMap::new());
+    let variables = snippet_variables(None, false, config)?;
+    let snippet = render_snippet(
+        &config.project_config.snippets,
+        name,
+        &variables,
+        &extra_values,
+    )?;
+
     if config.dry_run {
-        println!("Would set editor to: {editor}");
+        println!("Would insert into {}:\n{snippet}", path.display());
         return Ok(());
     }
-    config.set_editor(editor)?;
+
+    ensure_writable("write the commit message")?;
+    let content = read_to_string(&path)?;
+    std::fs::write(&path, insert_snippet(&content, &snippet))?;
+    println!(
+        "{} Inserted /{name} into {}",
+        success_mark(),
+        path.display()
+    );
     Ok(())
 }
 
+/// Builds the template variables snippets are rendered with.
+///
+/// The commit type defaults to the one inferred from the branch prefix, falling back to
+/// the first configured type; `{message}` is empty.
+///
+/// # Errors
+/// * If the branch, commit count or git author cannot be read
+fn snippet_variables(
+    commit_type: Option<&str>,
+    no_commit_number: bool,
+    config: &Config,
+) -> Result<TemplateVariables> {
+    let commit_types: Vec<&str> = config.project_config.commit_types.as_ref().map_or_else(
+        || COMMIT_TYPES.to_vec(),
+        |v| v.iter().map(CommitType::name).collect(),
+    );
+    let branch = get_current_branch()?;
+    let commit_type = commit_type.unwrap_or_else(|| default_commit_type(&commit_types, &branch));
+    let commit_number = next_commit_number(no_commit_number, commit_type, config)?;
+
+    TemplateVariables::new(
+        commit_number,
+        commit_type.to_string(),
+        format_branch_name(&commit_types, &branch, &config.project_config.branch_name),
+        String::new(),
+        config.project_config.ticket_pattern.as_deref(),
+    )
+    .map(|variables| {
+        variables
+            .with_date_formats(
+                config.project_config.date_format.as_deref(),
+                config.project_config.time_format.as_deref(),
+                config.project_config.timezone.as_deref(),
+                config.project_config.lang.as_deref(),
+            )
+            .with_commit_emoji(config.project_config.commit_emoji(commit_type))
+            .with_impact(&config.project_config.impact)
+            .with_changed_symbols(
+                config
+                    .project_config
+                    .snippets
+                    .values()
+                    .any(|text| uses_variable(text, "changed_symbols")),
+            )
+    })
+}
+
+/// The extra field values available to snippets: every configured commit extra field,
+/// empty unless a value is given.
+fn snippet_extra_values(
+    config: &Config,
+    values: &HashMap<String, String>,
+) -> HashMap<String, String> {
+    config
+        .project_config
+        .commit_extra_fields
+        .iter()
+        .map(|f| {
+            let value = values.get(&f.name).cloned().unwrap_or_default();
+            (f.name.clone(), value)
+        })
+        .collect()
+}
+
 /// Handle the Sync command which syncs the current branch with another branch.
 ///
 /// # Arguments
@@ -1234,7 +6661,9 @@ fn handle_sync(
     new_branch: Option<&str>,
     config: &Config,
 ) -> Result<()> {
-    use crate::git::{git_create_branch, git_merge, git_pull, git_rebase, git_switch};
+    use crate::git::{git_create_branch, git_merge, git_rebase, git_switch};
+
+    ensure_outside_commit_hook("sync")?;
 
     // Get current branch before any operations
     let original_branch = get_current_branch()?;
@@ -1323,11 +6752,12 @@ fn handle_which_config(path: Option<&str>, show_effective: bool) -> Result<()> {
         println!("Possible config locations (in loading order):");
         for source in &config_info.sources {
             println!(
-                "  ○ [priority {}] {}",
+                "  {} [priority {}] {}",
+                glyph("○", "-"),
                 source.priority,
                 source.path.display()
             );
-            println!("    └─ {}", source.description);
+            println!("    {} {}", glyph("└─", "-"), source.description);
         }
         println!();
         println!("Run 'rona init' or 'rona config local/global' to create a config file.");
@@ -1338,7 +6768,11 @@ fn handle_which_config(path: Option<&str>, show_effective: bool) -> Result<()> {
     println!();
 
     for source in &config_info.sources {
-        let status = if source.exists { "✓" } else { "○" };
+        let status = if source.exists {
+            glyph("✓", "+")
+        } else {
+            glyph("○", "-")
+        };
         let exists_text = if source.exists {
             "(active)"
         } else {
@@ -1351,7 +6785,12 @@ fn handle_which_config(path: Option<&str>, show_effective: bool) -> Result<()> {
             source.priority,
             source.path.display()
         );
-        println!("    └─ {} {}", source.description, exists_text);
+        println!(
+            "    {} {} {}",
+            glyph("└─", "-"),
+            source.description,
+            exists_text
+        );
     }
 
     // Show which config takes precedence
@@ -1371,7 +6810,8 @@ fn handle_which_config(path: Option<&str>, show_effective: bool) -> Result<()> {
                 println!("- editor = \"{editor}\"");
             }
             if let Some(commit_types) = &cfg.commit_types {
-                println!("- commit_types = {commit_types:?}");
+                let names: Vec<&str> = commit_types.iter().map(CommitType::name).collect();
+                println!("- commit_types = {names:?}");
             }
             if let Some(template) = &cfg.commit_template {
                 println!("- commit_template = \"{template}\"");
@@ -1387,15 +6827,22 @@ fn handle_which_config(path: Option<&str>, show_effective: bool) -> Result<()> {
 /// Handle the Config command which creates or manages configuration files.
 ///
 /// Generates a commented TOML config file content with all supported options documented.
+#[allow(clippy::too_many_lines)] // one commented entry per config option
 fn generate_commented_config() -> String {
     let default_commit_types = r#"["feat", "fix", "perf", "revert", "docs", "quality", "style", "chore", "refactor", "test", "build", "ci"]"#;
     format!(
         r#"# Editor used to open commit_message.md in non-interactive mode.
 editor = "nano"
 
-# Commit types shown in the selector.
+# Commit types shown in the selector. Entries can also be tables with an emoji
+# (exposed as {{commit_emoji}}) and a description shown in the selector:
+# commit_types = [{{ name = "feat", emoji = "✨", description = "A new feature" }}, "fix"]
 commit_types = {default_commit_types}
 
+# Start a background `git fetch` from status commands at most once per interval
+# (units: s, m, h, d), so ahead/behind information stays fresh.
+# auto_fetch = "15m"
+
 ##########
 # COMMIT #
 ##########
@@ -1404,16 +6851,63 @@ commit_types = {default_commit_types}
 # Built-in variables:
 #   {{commit_number}}  - sequential commit count on the current branch
 #   {{commit_type}}    - the type chosen in the selector
+#   {{commit_emoji}}   - the emoji of that type in commit_types (empty when none)
 #   {{branch_name}}    - current branch (prefix stripped, e.g. feat/x -> x)
-#   {{message}}        - the message entered by the user
-#   {{date}}           - YYYY-MM-DD
-#   {{time}}           - HH:MM:SS
+#   {{message}}        - the message entered by the user (subject, blank line, body)
+#   {{subject}}        - the first line of the message
+#   {{body}}           - the optional body paragraphs (empty when none were entered)
+#   {{date}}           - YYYY-MM-DD (see date_format)
+#   {{time}}           - HH:MM:SS (see time_format)
 #   {{author}}         - git user.name
 #   {{email}}          - git user.email
+#   {{branch_description}} - purpose of the current branch (rona branch describe)
+#   {{file_list}}      - the staged files, one "- path" line each
+#   {{file_count}}     - the number of staged files
+#   {{primary_path}}   - the deepest directory containing every staged file
+#   {{scope}}          - the last directory of primary_path (src/git -> git), or the
+#                        --scope name, asked with -i
+#   {{ticket}}         - the ticket ticket_pattern finds in the branch (empty when none)
+#   {{impact}}         - the [impact] labels of the staged files, comma-separated
+#   {{changed_symbols}} - the functions and types the staged change touches
 # Conditional blocks: {{?var}}...{{/var}} renders only when var has a value.
 # Extra variables: add with [[commit_extra_fields]].
 commit_template = "{{?commit_number}}[{{commit_number}}] {{/commit_number}}({{commit_type}} on {{branch_name}}) {{message}}"
 
+# Regex finding the ticket in the branch name for {{ticket}} (its first capture group
+# when it has one), e.g. PROJ-123 in feat/PROJ-123-add-login.
+# ticket_pattern = "[A-Z]+-\\d+"
+
+# Fail on variables that have no value at render time instead of rendering them empty.
+# Check templates with `rona template check`.
+# strict_templates = false
+
+# Before generating, require staged changes and ask before replacing a message in
+# commit_message.md that was not committed yet (view, overwrite or append).
+# check_before_generate = false
+
+# Before pushing, fetch the pushed branch, warn when it has commits HEAD does not,
+# and ask before a force push discards them.
+# check_before_push = false
+
+# chrono formats of {{date}} and {{time}}.
+# date_format = "%Y-%m-%d"
+# time_format = "%H:%M:%S"
+# Timezone of {{date}} and {{time}}: "UTC", "local" or an offset such as "+02:00".
+# timezone = "UTC"
+
+# Named commit templates, selected with --template NAME or picked by generate.
+# The one named default replaces commit_template.
+# [templates]
+# release = "release: {{message}}"
+
+# Language of the commit messages, a table under [templates.locales] (overridden by
+# --lang). Each [templates.locales.<lang>] may set commit_template, date_format and
+# time_format.
+# lang = "fr"
+# [templates.locales.fr]
+# commit_template = "({{commit_type}}) {{message}}"
+# date_format = "%d/%m/%Y"
+
 # Order of prompts in interactive mode (-i).
 # Use the reserved name "message" to position the built-in message prompt.
 # Fields not listed are appended after all listed items.
@@ -1444,14 +6938,201 @@ commit_template = "{{?commit_number}}[{{commit_number}}] {{/commit_number}}({{co
 # prefetch.source = "branch"
 # prefetch.extract_regex = "[A-Z]+-[0-9]+"
 
+# Reusable body text, inserted with /name in the interactive body prompt or with
+# `rona snippet insert <name>`. Snippets can use the commit template variables.
+# [snippets]
+# migration = "Migration: run `make migrate` after deploying {{branch_name}}."
+# review = "- [ ] Tests\n- [ ] Docs"
+
+# Line written for each file in a generated commit_message.md.
+# {{file}} is the path; {{placeholder}} is empty, or "deleted" for deleted files.
+# file_bullet = "- `{{file}}`: {{placeholder}}"
+# List files one per line, without the empty description line under each.
+# file_bullet_compact = false
+# Write file bullets as Markdown checkboxes ("- [ ] ...").
+# file_bullet_checkbox = false
+# Follow each file bullet with a comment summarizing its staged change, such as
+# +12 -3; added parse_header(). Comments are then stripped before committing.
+# file_bullet_annotate = false
+# Drop comment lines (core.commentChar, # by default) from commit_message.md
+# before committing. Off by default, so #123 references and headings are kept.
+# strip_comments = false
+
+# Warn before `rona commit` when the staged changes exceed these limits. With
+# `confirm = true`, ask before committing instead. Files matching `exempt` are not counted.
+# [commit_size]
+# max_files = 30
+# max_insertions = 800
+# confirm = false
+# exempt = ["Cargo.lock", "src/generated/**"]
+
+# Color of each commit type in the type selectors, `rona browse` and `rona show`.
+# Color names (green, bright blue, ...) or #rrggbb; unlisted types keep a built-in color.
+# [type_colors]
+# feat = "green"
+# fix = "red"
+# docs = "blue"
+
+# What {{commit_number}} counts: "head-count" (commits reachable from HEAD), "remote"
+# (the fetched upstream plus local commits, avoiding collisions between contributors),
+# "branch-only" (commits not on the default branch) or "per-type" (commits of the same type).
+# commit_number_source = "head-count"
+
+# Append a Gerrit Change-Id trailer to messages without one, like Gerrit's commit-msg
+# hook. `--amend` keeps the Change-Id of the amended commit.
+# change_id = false
+
+# Require signed commits: `rona commit` fails without a signing key (and refuses
+# --unsigned), and `rona push` refuses to push commits without a signature.
+# require_signed = false
+
+# How {{branch_name}} is rendered. The commit type prefix (feat/...) is always removed.
+# `separator` replaces - and _, `case` is preserve, lower or upper.
+# [branch_name]
+# max_length = 40
+# separator = " "
+# case = "preserve"
+
+# TODO markers on the lines the staged changes add, listed by `rona todo`.
+# `on_commit` is ignore, append (add a "TODOs:" section to the body) or block.
+# [todo]
+# markers = ["TODO", "FIXME", "HACK"]
+# on_commit = "ignore"
+
+# Patch export for `rona format-patch`. The cover letter's first line is its subject.
+# Template variables: {{branch}}, {{count}}, {{subjects}}, {{changes}}, {{author}}.
+# [format_patch]
+# output_dir = "patches"
+# cover_letter = "{{branch}}\n\nThis series contains {{count}} patch(es).\n\n{{changes}}"
+
+# Commands run from the repository root before `rona push` and `rona commit --push`.
+# The first failing command aborts the push; `--no-verify` skips them.
+# `timeout` kills a command running longer, `pass_env` lists the only environment
+# variables the commands see, and `output_lines` captures their output and shows its
+# last lines when a command fails.
+# [pre_push]
+# commands = ["cargo test", "cargo clippy -- -D warnings"]
+# timeout = "10m"
+# pass_env = ["PATH", "HOME", "CARGO_HOME"]
+# output_lines = 40
+
+# Chat webhook posted to after each successful push and release. `kind` is slack,
+# discord or teams (inferred from the URL when absent). Template variables: {{branch}},
+# {{remote}}, {{subjects}}, {{count}}, {{compare_url}}, {{author}}. The release
+# template takes the variables of the `[release]` message.
+# [notify.webhook]
+# url = "https://hooks.slack.com/services/..."
+# template = "{{author}} pushed {{count}} commit(s) to {{branch}}:\n{{subjects}}"
+# release_template = "Released {{version}} as {{tag}}"
+
+# Issue tracker commands run after each successful push, by commit type of the
+# pushed commits, for the ticket `ticket_pattern` finds in the branch. They get
+# $RONA_TICKET, $RONA_COMMIT_TYPE, $RONA_SUBJECTS, $RONA_BRANCH and $RONA_REMOTE.
+# [tracker]
+# timeout = "1m"
+# [tracker.transitions]
+# fix = ["jira issue comment add \"$RONA_TICKET\" \"Fix pushed to $RONA_BRANCH\"", "jira issue move \"$RONA_TICKET\" 'In Review'"]
+
+# The longest `rona daemon` serves the status without a refresh. Working tree edits
+# can show up in `rona -l` that late. Units: s, m, h, d.
+# [daemon]
+# refresh_interval = "2s"
+
+# Ref `rona push --review` pushes HEAD to, and the remote used when the command
+# line names none. Template variables: {{branch}}, {{target}} (--target, else the
+# default branch), {{user}} (user.email before the @).
+# [review]
+# ref = "refs/for/{{target}}"
+# remote = "origin"
+
+# Remote and ref namespace `rona handoff push` sends work in progress to, one ref
+# per branch, for `rona handoff pull` in another clone.
+# [handoff]
+# remote = "origin"
+# namespace = "refs/rona/handoff"
+
+# Where `rona config sync` reads the team's canonical config: a URL, or `path` on
+# `branch` of `remote` (a remote name or a repository URL).
+# [config_sync]
+# url = "https://example.com/acme/.rona.toml"
+# remote = "origin"
+# branch = "rona-config"
+# path = ".rona.toml"
+
+# Formats of `rona copy`: the one used without --format, and named templates added
+# to the built-in reference, sha, short and subject. Template variables: {{sha}},
+# {{short_sha}}, {{subject}}, {{body}}, {{author}}, {{email}}, {{date}}.
+# [copy]
+# format = "reference"
+# formats = {{ md = "[`{{short_sha}}`]: {{subject}}" }}
+
+# Version tags of `rona release`: the tag prefix and the message of the release
+# commit and tag. Template variables: {{version}}, {{tag}}, {{channel}} (empty for
+# a stable release), {{previous}}, {{package}} (the `--package` name).
+# [release]
+# tag_prefix = "v"
+# message = "Release {{version}}"
+
+# Impact labels of the areas a commit touches, by path glob from the repository root
+# (`*` stays within a directory). `rona generate` prints them; {{impact}} lists them.
+# [impact]
+# "src/git/**" = "core"
+# "docs/**" = "docs-only"
+
+# Monorepo directories, by scope name: `--scope <name>` narrows status, staging and
+# message generation to one of them and sets {{scope}} to its name.
+# [scopes]
+# api = "packages/api"
+
+# Monorepo packages released on their own with `rona release --package <name>`,
+# tagged `<tag_prefix><version>` (default `<name>/v`) from commits touching `path`.
+# [packages.api]
+# path = "packages/api"
+# tag_prefix = "api/v"
+
+# Changelog fragments written by `rona fragment add` and assembled into the
+# changelog by `rona fragment build <version>`. Titles name the changelog section
+# of each type (default: the type itself).
+# [fragments]
+# dir = "changelog.d"
+# changelog = "CHANGELOG.md"
+# titles = {{ feat = "Features", fix = "Bug Fixes" }}
+
+# Built-in exclusion presets: node, python, rust. Their build output is never staged
+# by `rona -a`; `rona init --repo` adds it and their lock files to .commitignore.
+# preset = ["rust"]
+
+# Screen reader friendly output: plain ASCII labels (OK:, ERROR:) instead of glyphs,
+# no colors, and numbered lists instead of fuzzy selectors.
+# accessibility = true
+
+# Hosts or host/owner prefixes the push remote should point to; pushing anywhere
+# else prints a warning. Best set per directory tree through [[overrides]].
+# expected_remotes = ["github.com/acme"]
+
+# Remote URL prefixes rewritten before rona uses a remote (push summaries, compare
+# links, pull requests), like git's insteadOf. The longest matching prefix wins.
+# [url_rewrites]
+# "https://github.com/acme/" = "git@github-work:acme/"
+
+# Features denied in the repositories a guard matches by root `path` or `remote`:
+# stage, commit, sign, push, fetch, pull, merge, rebase. Later guards can `allow`
+# them again. Read from the global config only.
+# [[guards]]
+# path = "~/experiments/**"
+# deny = ["push"]
+
 ##########
 # BRANCH #
 ##########
 
 # Template applied to the generated branch name.
 # Built-in variables:
-#   {{branch_type}}   - the type chosen in the selector
+#   {{branch_type}}   - the type chosen in the selector (short form: {{type}})
 #   {{description}}   - the description entered by the user
+#   {{slug}}          - the description lowercased, words joined with "-"
+#   {{ticket}}        - the ticket given with `rona branch new --ticket`
+#   {{scope}}         - the --scope name (empty without --scope)
 #   {{date}}          - YYYY-MM-DD
 #   {{time}}          - HH:MM:SS
 #   {{author}}        - git user.name
@@ -1460,6 +7141,9 @@ commit_template = "{{?commit_number}}[{{commit_number}}] {{/commit_number}}({{co
 # Commit extra fields (from [[commit_extra_fields]]) can also be referenced here.
 branch_template = "{{branch_type}}/{{description}}"
 
+# Regex new branch names must match (checked after sanitizing).
+# branch_pattern = "^(feat|fix|chore)/[A-Z]+-[0-9]+-[a-z0-9-]+$"
+
 # Dedicated branch types (when absent, commit_types is used).
 # branch_types = ["feat", "fix", "chore"]
 
@@ -1486,6 +7170,152 @@ branch_template = "{{branch_type}}/{{description}}"
     )
 }
 
+/// The config file of a scope: `.rona.toml` at the repository root, or
+/// `~/.config/rona.toml`.
+///
+/// # Errors
+/// * If the repository root or the home directory cannot be found
+fn config_file_path(scope: ConfigScope) -> Result<PathBuf> {
+    Ok(match scope {
+        ConfigScope::Local => get_top_level_path()?.join(".rona.toml"),
+        ConfigScope::Global => dirs::home_dir()
+            .ok_or(crate::errors::ConfigError::ConfigNotFound)?
+            .join(".config/rona.toml"),
+    })
+}
+
+/// Handle `config edit`: open the config file of `scope` in the editor, or edit its
+/// known keys with prompts when `interactive` is set.
+///
+/// # Errors
+/// * If the config file cannot be located, read or written
+/// * If no editor can be started
+fn handle_config_edit(scope: ConfigScope, interactive: bool, config: &Config) -> Result<()> {
+    let path = config_file_path(scope)?;
+    if interactive {
+        if edit_interactively(&path)? {
+            println!("{} Saved {}", success_mark(), path.display());
+        } else {
+            println!("No changes written.");
+        }
+        return Ok(());
+    }
+
+    if !path.exists() {
+        return Err(RonaError::InvalidInput(format!(
+            "{} does not exist; create it with `rona config create`",
+            path.display()
+        )));
+    }
+    open_file_in_editor(&path, config)
+}
+
+/// Handle `config show`: print the effective configuration as TOML, or with
+/// `origins`, every value next to where it was set.
+///
+/// # Errors
+/// * If the configuration cannot be serialized
+/// * If a config file cannot be read or parsed
+fn handle_config_show(origins: bool, config: &Config) -> Result<()> {
+    if !origins {
+        let content = toml::to_string_pretty(&config.project_config).map_err(|e| {
+            RonaError::InvalidInput(format!("Cannot serialize the configuration: {e}"))
+        })?;
+        print!("{content}");
+        return Ok(());
+    }
+
+    if config.layers.is_empty() {
+        println!("No configuration files loaded.");
+    } else {
+        println!("Configuration files (in loading order, later overrides earlier):");
+        for (index, layer) in config.layers.iter().enumerate() {
+            println!(
+                "  {}. {}: {}",
+                index + 1,
+                layer.origin,
+                layer.path.display()
+            );
+        }
+    }
+    println!();
+
+    let values = config.value_origins()?;
+    let width = values.iter().map(|v| v.key.len()).max().unwrap_or(0);
+    for value in values {
+        let origin = format!("# {}", value.origin);
+        println!(
+            "{:<width$} = {}  {}",
+            value.key,
+            value.value,
+            if value.origin == "default" {
+                origin.dimmed()
+            } else {
+                origin.cyan()
+            }
+        );
+    }
+    Ok(())
+}
+
+/// Handle `config sync`: show how the project `.rona.toml` differs from the canonical
+/// config of `source`, then replace it after confirmation.
+///
+/// # Errors
+/// * If the canonical config cannot be read or is not a valid config
+/// * With `check`, if the local config differs
+/// * If in read-only mode, or the file cannot be written
+fn handle_config_sync(source: &SyncSource, check: bool, yes: bool, config: &Config) -> Result<()> {
+    let path = get_top_level_path()?.join(".rona.toml");
+    let local = if path.exists() {
+        Some(read_to_string(&path)?)
+    } else {
+        None
+    };
+    let canonical = with_sync_section(local.as_deref(), &source.fetch()?)?;
+    validate_config_text(&source.to_string(), &canonical)?;
+
+    let diff = config_diff(&path, &canonical)?;
+    if diff.is_empty() {
+        println!("{} .rona.toml is up to date with {source}", success_mark());
+        return Ok(());
+    }
+    println!("{}", "--- .rona.toml".bold());
+    println!("{}", format!("+++ {source}").bold());
+    for line in diff.lines() {
+        match line.chars().next() {
+            Some('+') => println!("{}", line.green()),
+            Some('-') => println!("{}", line.red()),
+            Some('@') => println!("{}", line.cyan()),
+            _ => println!("{line}"),
+        }
+    }
+
+    if check {
+        return Err(RonaError::InvalidInput(format!(
+            ".rona.toml differs from {source}; update it with `rona config sync`"
+        )));
+    }
+    if config.dry_run {
+        println!("Would update {}", path.display());
+        return Ok(());
+    }
+    ensure_writable("write the config file")?;
+    let apply = yes
+        || Confirm::with_theme(&prompt_theme())
+            .with_prompt("Apply these changes to .rona.toml?")
+            .default(true)
+            .interact()
+            .unwrap_or(false);
+    if !apply {
+        println!("Config not changed.");
+        return Ok(());
+    }
+    std::fs::write(&path, canonical)?;
+    println!("{} Updated .rona.toml from {source}", success_mark());
+    Ok(())
+}
+
 /// # Arguments
 /// * `scope` - Whether to create local (.rona.toml) or global (~/.config/rona.toml) config
 /// * `config` - Global configuration including verbose and dry-run settings
@@ -1496,18 +7326,7 @@ branch_template = "{{branch_type}}/{{description}}"
 fn handle_config_command(scope: ConfigScope, exclude: bool, config: &Config) -> Result<()> {
     use std::io::Write;
 
-    let config_path = {
-        match scope {
-            ConfigScope::Local => {
-                let project_root = get_top_level_path()?;
-                project_root.join(".rona.toml")
-            }
-            ConfigScope::Global => {
-                let home = dirs::home_dir().ok_or(crate::errors::ConfigError::ConfigNotFound)?;
-                home.join(".config/rona.toml")
-            }
-        }
-    };
+    let config_path = config_file_path(scope)?;
 
     if config.dry_run {
         println!(
@@ -1537,6 +7356,8 @@ fn handle_config_command(scope: ConfigScope, exclude: bool, config: &Config) ->
         );
         println!("Use 'rona set-editor <editor>' to modify the editor setting.");
     } else {
+        ensure_writable("write the config file")?;
+
         // Create parent directory if it doesn't exist (for global config)
         if let Some(parent) = config_path.parent()
             && !parent.exists()
@@ -1585,6 +7406,59 @@ fn init_logging(verbose: bool) {
         .ok();
 }
 
+/// Changes the process working directory for the global `-C/--chdir` option.
+///
+/// Every later step (repository discovery, config lookup, relative `-f` paths and
+/// file arguments) then behaves exactly as if rona had been started in `path`.
+///
+/// # Errors
+/// * If `path` does not exist or is not a directory
+fn change_directory(path: &str) -> Result<()> {
+    std::env::set_current_dir(path).map_err(|e| {
+        RonaError::Io(std::io::Error::new(
+            e.kind(),
+            format!("Cannot change directory to '{path}': {e}"),
+        ))
+    })
+}
+
+/// Applies the global `-C` and `-f` options and loads the configuration.
+///
+/// The directory change happens first so a relative config path, like every
+/// other path argument, is resolved from the new working directory.
+///
+/// # Errors
+/// * If changing directory fails
+/// * If loading the configuration fails
+fn load_config(cli: &Cli) -> Result<Config> {
+    if let Some(ref dir) = cli.chdir {
+        change_directory(dir)?;
+    }
+
+    let mut config = if let Some(ref config_path) = cli.config {
+        Config::new_with_config_file(std::path::Path::new(config_path))?
+    } else {
+        Config::new()?
+    };
+    // Set before the package configs, which follow the staged files of the scope.
+    if let Some(ref scope) = cli.path_scope {
+        set_path_scope(Some(PathScope::resolve(
+            scope,
+            &config.project_config.scopes,
+        )?));
+    }
+    if cli.config.is_none() && cli.command.builds_message() {
+        config.apply_package_configs()?;
+    }
+    config.apply_locale(cli.lang.as_deref())?;
+    config.apply_template(cli.template.as_deref())?;
+
+    // Set the global flags in the config
+    config.set_verbose(cli.verbose);
+    config.set_json_output(cli.output == OutputFormat::Json);
+    Ok(config)
+}
+
 /// Runs the program by parsing command line arguments and executing the appropriate command.
 ///
 /// # Errors
@@ -1597,82 +7471,497 @@ fn init_logging(verbose: bool) {
 pub fn run() -> Result<()> {
     let cli = Cli::parse();
     init_logging(cli.verbose);
+    if cli.read_only {
+        set_read_only(true);
+    }
 
-    let mut config = if let Some(ref config_path) = cli.config {
-        Config::new_with_config_file(std::path::Path::new(config_path))?
-    } else {
-        Config::new()?
-    };
+    let mut config = load_config(&cli)?;
+    set_accessible(config.project_config.accessibility);
+    let root = get_top_level_path().or_else(|_| std::env::current_dir().map_err(RonaError::Io))?;
+    match denied_features(&root) {
+        Ok(denied) => set_denied_features(denied),
+        Err(e) => eprintln!("{} [[guards]] not applied: {e}", "WARNING:".yellow().bold()),
+    }
 
-    // Set the global flags in the config
-    config.set_verbose(cli.verbose);
+    let operation = cli.command.logged_operation();
+    let head_before = operation.and_then(|_| current_head());
+    execute(cli.command, &mut config)?;
+
+    if let Some(operation) = operation
+        && !config.dry_run
+        && !is_read_only()
+    {
+        // Browsing only changes the repository when a commit is reverted or fixed up.
+        if operation != "browse" || current_head() != head_before {
+            log_operation(operation, head_before);
+        }
+    }
+    Ok(())
+}
+
+/// Appends a finished operation to the operation log.
+///
+/// The command already succeeded, so a log that cannot be written only warns.
+fn log_operation(command: &str, head_before: Option<String>) {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if let Err(e) = record_operation(command, &args, head_before) {
+        println!(
+            "{} Could not record the operation: {e}",
+            "WARNING:".yellow().bold()
+        );
+    }
+}
+
+impl CliCommand {
+    /// Whether the command writes or checks a commit message, and so follows the
+    /// package `.rona.toml` of the staged files.
+    const fn builds_message(&self) -> bool {
+        matches!(
+            self,
+            Self::CheckMsg { .. }
+                | Self::Commit { .. }
+                | Self::Generate { .. }
+                | Self::PrepareMsg { .. }
+        )
+    }
+
+    /// The name recorded in the operation log, for commands that change the repository.
+    const fn logged_operation(&self) -> Option<&'static str> {
+        match self {
+            Self::AddWithExclude { .. } => Some("add"),
+            Self::Backport { .. } => Some("backport"),
+            Self::Branch {
+                subcommand:
+                    None
+                    | Some(
+                        BranchSubcommand::Describe { .. }
+                        | BranchSubcommand::Merge { .. }
+                        | BranchSubcommand::New { .. }
+                        | BranchSubcommand::Pull { .. }
+                        | BranchSubcommand::Rebase { .. }
+                        | BranchSubcommand::Rename { .. }
+                        | BranchSubcommand::Switch { .. },
+                    ),
+                ..
+            } => Some("branch"),
+            Self::Browse { .. } => Some("browse"),
+            Self::Bundle {
+                subcommand: BundleSubcommand::Apply { .. },
+            } => Some("bundle"),
+            Self::Commit { copy: false, .. } => Some("commit"),
+            Self::Generate { print: false, .. } => Some("generate"),
+            Self::Handoff { .. } => Some("handoff"),
+            Self::Health { fix, optimize } if *fix || *optimize => Some("health"),
+            Self::Prune { .. } => Some("prune"),
+            Self::Push { .. } => Some("push"),
+            Self::Reset { .. } => Some("reset"),
+            Self::Restore { .. } => Some("restore"),
+            Self::Sync { .. } => Some("sync"),
+            _ => None,
+        }
+    }
+}
+
+/// Runs a parsed command.
+#[allow(clippy::too_many_lines)] // one match arm per subcommand
+fn execute(command: CliCommand, config: &mut Config) -> Result<()> {
+    match command {
+        CliCommand::Am {
+            patches,
+            retemplate,
+            continue_am,
+            skip,
+            abort,
+            dry_run,
+        } => {
+            config.set_dry_run(dry_run);
+            let resume = if continue_am {
+                Some(AmResume::Continue)
+            } else if skip {
+                Some(AmResume::Skip)
+            } else if abort {
+                Some(AmResume::Abort)
+            } else {
+                None
+            };
+            handle_am(&patches, retemplate, resume, config)
+        }
+
+        CliCommand::Backport {
+            commit,
+            targets,
+            push,
+            pr,
+            remote,
+            unsigned,
+            dry_run,
+        } => {
+            config.set_dry_run(dry_run);
+            let delivery = if pr {
+                BackportDelivery::PullRequest
+            } else if push {
+                BackportDelivery::Push
+            } else {
+                BackportDelivery::Local
+            };
+            handle_backport(&commit, &targets, &remote, delivery, unsigned, config)
+        }
+
+        CliCommand::Branch {
+            subcommand,
+            dry_run,
+            no_switch,
+        } => match subcommand {
+            Some(BranchSubcommand::Describe {
+                description,
+                clear,
+                dry_run,
+            }) => {
+                config.set_dry_run(dry_run);
+                handle_branch_describe(description, clear, config)
+            }
+            Some(BranchSubcommand::List) => handle_branch_list(),
+            Some(BranchSubcommand::Merge { branch, dry_run }) => {
+                config.set_dry_run(dry_run);
+                handle_branch_integrate(branch, false, config)
+            }
+            Some(BranchSubcommand::Pull { dry_run }) => {
+                config.set_dry_run(dry_run);
+                handle_branch_pull(config)
+            }
+            Some(BranchSubcommand::Rebase { branch, dry_run }) => {
+                config.set_dry_run(dry_run);
+                handle_branch_integrate(branch, true, config)
+            }
+            Some(BranchSubcommand::Switch { branch, dry_run }) => {
+                config.set_dry_run(dry_run);
+                handle_branch_switch(branch, config)
+            }
+            Some(BranchSubcommand::Rename {
+                new_name,
+                local,
+                yes,
+                keep_old,
+                dry_run,
+            }) => {
+                config.set_dry_run(dry_run);
+                handle_branch_rename(&new_name, local, yes, keep_old, config)
+            }
+            Some(BranchSubcommand::New {
+                description,
+                ticket,
+                branch_type,
+                no_switch,
+                dry_run,
+            }) => {
+                config.set_dry_run(dry_run);
+                handle_branch_new(
+                    &description,
+                    ticket.as_deref(),
+                    branch_type.as_deref(),
+                    no_switch,
+                    config,
+                )
+            }
+            None => {
+                config.set_dry_run(dry_run);
+                handle_branch(no_switch, config)
+            }
+        },
+
+        CliCommand::Browse { limit, dry_run } => {
+            config.set_dry_run(dry_run);
+            handle_browse(limit, config)
+        }
+
+        CliCommand::AddWithExclude {
+            to_exclude: exclude,
+            interactive,
+            filter,
+            dry_run,
+        } => {
+            config.set_dry_run(dry_run);
+            if interactive {
+                handle_add_interactive(&exclude, filter.as_deref(), config)
+            } else {
+                handle_add_with_exclude(&exclude, config)
+            }
+        }
+
+        CliCommand::Commit {
+            args,
+            push,
+            dry_run,
+            unsigned,
+            yes,
+            copy,
+            message,
+            commit_type,
+            no_commit_number,
+        } => {
+            config.set_dry_run(dry_run);
+            let inline = message.as_deref().map(|text| InlineMessage {
+                text,
+                commit_type: commit_type.as_deref(),
+                no_commit_number,
+            });
+            handle_commit(&args, push, unsigned, yes, copy, inline, config)
+        }
+
+        CliCommand::CheckMsg { file } => handle_check_msg(&file, config),
+
+        CliCommand::Daemon { stop } => handle_daemon(stop, config),
+
+        CliCommand::Completion { shell, subcommand } => match (subcommand, shell) {
+            (Some(CompletionSubcommand::Check { shell }), _) => handle_completion_check(shell),
+            (None, Some(shell)) => {
+                print!("{}", completion_script(shell));
+                Ok(())
+            }
+            (None, None) => Err(RonaError::InvalidInput(
+                "A shell is required to generate completions".to_string(),
+            )),
+        },
+
+        CliCommand::Config { subcommand } => match subcommand {
+            ConfigSubcommand::Create {
+                scope,
+                exclude,
+                dry_run,
+            } => {
+                config.set_dry_run(dry_run);
+                handle_config_command(scope, exclude, config)
+            }
+            ConfigSubcommand::Which {
+                path,
+                show_effective,
+            } => handle_which_config(path.as_deref(), show_effective),
+            ConfigSubcommand::Edit { scope, interactive } => {
+                handle_config_edit(scope, interactive, config)
+            }
+            ConfigSubcommand::Show { origins } => handle_config_show(origins, config),
+            ConfigSubcommand::Sync {
+                url,
+                remote,
+                branch,
+                check,
+                yes,
+                dry_run,
+            } => {
+                config.set_dry_run(dry_run);
+                let source =
+                    SyncSource::new(&config.project_config.config_sync, url, remote, branch);
+                handle_config_sync(&source, check, yes, config)
+            }
+        },
+
+        CliCommand::Copy {
+            reference,
+            format,
+            print,
+        } => handle_copy(&reference, format.as_deref(), print, config),
+
+        CliCommand::Generate {
+            dry_run,
+            interactive,
+            no_commit_number,
+            print,
+            stdin,
+            from_clipboard,
+        } => {
+            config.set_dry_run(dry_run);
+            let import = if stdin {
+                Some(MessageImport::Stdin)
+            } else if from_clipboard {
+                Some(MessageImport::Clipboard)
+            } else {
+                None
+            };
+            handle_generate(interactive, no_commit_number, print, import, config)
+        }
+
+        CliCommand::Env { json } => handle_env(json, config),
+
+        CliCommand::ExplainStatus => {
+            print_explained_status()?;
+            warn_out_of_cone_files();
+            Ok(())
+        }
+
+        CliCommand::FormatPatch {
+            range,
+            count,
+            output_dir,
+            cover_letter,
+            dry_run,
+        } => {
+            config.set_dry_run(dry_run);
+            handle_format_patch(
+                range.as_deref(),
+                count,
+                output_dir.as_deref(),
+                cover_letter,
+                config,
+            )
+        }
+
+        CliCommand::Health { fix, optimize } => handle_health(fix, optimize),
+
+        CliCommand::History { subcommand } => match subcommand {
+            HistorySubcommand::Ops { limit, json } => handle_history_ops(limit, json),
+        },
+
+        CliCommand::Fragment { subcommand } => match subcommand {
+            FragmentSubcommand::Add {
+                fragment_type,
+                text,
+                ticket,
+                package,
+                dry_run,
+            } => {
+                config.set_dry_run(dry_run);
+                handle_fragment_add(
+                    &fragment_type,
+                    &text,
+                    ticket.as_deref(),
+                    package.as_deref(),
+                    config,
+                )
+            }
+            FragmentSubcommand::List { package } => {
+                handle_fragment_list(package.as_deref(), config)
+            }
+            FragmentSubcommand::Build {
+                version,
+                date,
+                keep,
+                package,
+                dry_run,
+            } => {
+                config.set_dry_run(dry_run);
+                handle_fragment_build(&version, date.as_deref(), keep, package.as_deref(), config)
+            }
+        },
+
+        CliCommand::Audit { since } => handle_audit(since.as_deref(), config),
+
+        CliCommand::Bundle { subcommand } => match subcommand {
+            BundleSubcommand::Create {
+                file,
+                since,
+                branches,
+                dry_run,
+            } => {
+                config.set_dry_run(dry_run);
+                handle_bundle_create(&file, since.as_deref(), branches, config)
+            }
+            BundleSubcommand::Apply {
+                file,
+                branches,
+                dry_run,
+            } => {
+                config.set_dry_run(dry_run);
+                handle_bundle_apply(&file, branches, config)
+            }
+        },
+
+        CliCommand::Handoff { subcommand } => match subcommand {
+            HandoffSubcommand::Push { remote, dry_run } => {
+                config.set_dry_run(dry_run);
+                handle_handoff_push(remote, config)
+            }
+            HandoffSubcommand::Pull {
+                branch,
+                remote,
+                dry_run,
+            } => {
+                config.set_dry_run(dry_run);
+                handle_handoff_pull(branch, remote, config)
+            }
+        },
 
-    match cli.command {
-        CliCommand::Branch { dry_run, no_switch } => {
-            config.set_dry_run(dry_run);
-            handle_branch(no_switch, &config)
-        }
+        CliCommand::Hooks { subcommand } => match subcommand {
+            HooksSubcommand::Install { hooks, dry_run } => {
+                config.set_dry_run(dry_run);
+                handle_hooks_install(&hooks, config)
+            }
+            HooksSubcommand::Uninstall { hooks, dry_run } => {
+                config.set_dry_run(dry_run);
+                handle_hooks_uninstall(&hooks, config)
+            }
+            HooksSubcommand::List => handle_hooks_list(),
+            HooksSubcommand::Run { hook, args } => handle_hooks_run(&hook, &args, config),
+        },
 
-        CliCommand::AddWithExclude {
-            to_exclude: exclude,
-            interactive,
+        CliCommand::Initialize {
+            editor,
             dry_run,
+            repo,
         } => {
             config.set_dry_run(dry_run);
-            handle_add_with_exclude(&exclude, interactive, &config)
+            if repo {
+                handle_initialize_repo(config)
+            } else {
+                handle_initialize(&editor, config)
+            }
         }
 
-        CliCommand::Commit {
-            args,
-            push,
+        CliCommand::ListStatus { filter } => handle_list_status(filter.as_deref(), config),
+
+        CliCommand::PrepareMsg {
+            msg_file,
+            source,
+            sha: _,
             dry_run,
-            unsigned,
-            yes,
-            copy,
         } => {
             config.set_dry_run(dry_run);
-            handle_commit(&args, push, unsigned, yes, copy, &config)
+            handle_prepare_msg(&msg_file, source.as_deref(), config)
         }
 
-        CliCommand::Completion { shell } => {
-            handle_completion(shell);
-            Ok(())
+        CliCommand::Prune { max_age, dry_run } => {
+            config.set_dry_run(dry_run);
+            handle_prune(&max_age, config)
         }
-
-        CliCommand::Config { subcommand } => match subcommand {
-            ConfigSubcommand::Create {
-                scope,
-                exclude,
-                dry_run,
-            } => {
-                config.set_dry_run(dry_run);
-                handle_config_command(scope, exclude, &config)
-            }
-            ConfigSubcommand::Which {
-                path,
-                show_effective,
-            } => handle_which_config(path.as_deref(), show_effective),
-        },
-
-        CliCommand::Generate {
+        CliCommand::Release {
+            major,
+            minor,
+            patch,
+            alpha,
+            beta,
+            rc,
+            package,
             dry_run,
-            interactive,
-            no_commit_number,
         } => {
             config.set_dry_run(dry_run);
-            handle_generate(interactive, no_commit_number, &config)
-        }
-
-        CliCommand::Initialize { editor, dry_run } => {
-            config.set_dry_run(dry_run);
-            handle_initialize(&editor, &config)
+            let bump = [
+                (major, Bump::Major),
+                (minor, Bump::Minor),
+                (patch, Bump::Patch),
+            ]
+            .into_iter()
+            .find_map(|(set, bump)| set.then_some(bump));
+            let channel = [
+                (alpha, Channel::Alpha),
+                (beta, Channel::Beta),
+                (rc, Channel::Rc),
+            ]
+            .into_iter()
+            .find_map(|(set, channel)| set.then_some(channel));
+            handle_release(bump, channel, package.as_deref(), config)
         }
 
-        CliCommand::ListStatus => handle_list_status(),
-
-        CliCommand::Push { args, dry_run } => {
+        CliCommand::Push {
+            args,
+            dry_run,
+            review,
+            target,
+        } => {
             config.set_dry_run(dry_run);
-            handle_push(&args, &config)
+            if review {
+                handle_review_push(&args, target, config)
+            } else {
+                handle_push(&args, config)
+            }
         }
 
         CliCommand::Reset {
@@ -1681,7 +7970,7 @@ pub fn run() -> Result<()> {
             dry_run,
         } => {
             config.set_dry_run(dry_run);
-            handle_reset(&files, interactive, &config)
+            handle_reset(&files, interactive, config)
         }
 
         CliCommand::Restore {
@@ -1691,14 +7980,36 @@ pub fn run() -> Result<()> {
             dry_run,
         } => {
             config.set_dry_run(dry_run);
-            handle_restore(&files, interactive, yes, &config)
+            handle_restore(&files, interactive, yes, config)
         }
 
         CliCommand::Set { editor, dry_run } => {
             config.set_dry_run(dry_run);
-            handle_set(&editor, &config)
+            handle_set(&editor, config)
         }
 
+        CliCommand::Schema { name } => handle_schema(name.as_deref()),
+
+        CliCommand::Show { reference, json } => handle_show(&reference, json, config),
+
+        CliCommand::Snippet { subcommand } => match subcommand {
+            SnippetSubcommand::List => {
+                handle_snippet_list(config);
+                Ok(())
+            }
+            SnippetSubcommand::Insert {
+                name,
+                file,
+                dry_run,
+            } => {
+                config.set_dry_run(dry_run);
+                handle_snippet_insert(&name, file.as_deref(), config)
+            }
+        },
+
+        CliCommand::Stats { export } => handle_stats(export),
+        CliCommand::Status => handle_status(config),
+
         CliCommand::Sync {
             source_branch,
             rebase,
@@ -1706,8 +8017,18 @@ pub fn run() -> Result<()> {
             dry_run,
         } => {
             config.set_dry_run(dry_run);
-            handle_sync(&source_branch, rebase, new_branch.as_deref(), &config)
+            let source_branch = source_branch.map_or_else(get_default_branch, Ok)?;
+            handle_sync(&source_branch, rebase, new_branch.as_deref(), config)
         }
+
+        CliCommand::Template { subcommand } => match subcommand {
+            TemplateSubcommand::Check => handle_template_check(config),
+        },
+
+        CliCommand::Todo { check } => handle_todo(check, config),
+
+        CliCommand::Tour { yes, keep } => run_tour(yes, keep),
+        CliCommand::Usage { json } => handle_usage(json),
     }
 }
 
@@ -1718,6 +8039,252 @@ mod cli_tests {
 
     type TestResult = std::result::Result<(), Box<dyn std::error::Error>>;
 
+    // === AM COMMAND TESTS ===
+
+    #[test]
+    fn test_am_command() -> TestResult {
+        let cli = Cli::try_parse_from(["rona", "am", "a.patch", "b.patch", "--retemplate"])?;
+        let CliCommand::Am {
+            patches,
+            retemplate,
+            continue_am,
+            ..
+        } = cli.command
+        else {
+            return Err("Wrong command parsed".into());
+        };
+        assert_eq!(patches, vec!["a.patch", "b.patch"]);
+        assert!(retemplate);
+        assert!(!continue_am);
+
+        let cli = Cli::try_parse_from(["rona", "am", "--continue"])?;
+        let CliCommand::Am {
+            patches,
+            continue_am,
+            ..
+        } = cli.command
+        else {
+            return Err("Wrong command parsed".into());
+        };
+        assert!(patches.is_empty());
+        assert!(continue_am);
+
+        // Patches are required unless resuming, and resuming takes no patches
+        assert!(Cli::try_parse_from(["rona", "am"]).is_err());
+        assert!(Cli::try_parse_from(["rona", "am", "a.patch", "--abort"]).is_err());
+        assert!(Cli::try_parse_from(["rona", "am", "--skip", "--abort"]).is_err());
+        Ok(())
+    }
+
+    // === BACKPORT COMMAND TESTS ===
+
+    #[test]
+    fn test_backport_command() -> TestResult {
+        let cli = Cli::try_parse_from([
+            "rona",
+            "backport",
+            "abc123",
+            "--to",
+            "release/1.x,release/2.x",
+            "--to",
+            "release/3.x",
+            "--pr",
+        ])?;
+        let CliCommand::Backport {
+            commit,
+            targets,
+            push,
+            pr,
+            remote,
+            ..
+        } = cli.command
+        else {
+            return Err("Wrong command parsed".into());
+        };
+        assert_eq!(commit, "abc123");
+        assert_eq!(targets, vec!["release/1.x", "release/2.x", "release/3.x"]);
+        assert!(!push);
+        assert!(pr);
+        assert_eq!(remote, "origin");
+
+        // A target is required, and a pull request already pushes its branch
+        assert!(Cli::try_parse_from(["rona", "backport", "HEAD"]).is_err());
+        assert!(
+            Cli::try_parse_from(["rona", "backport", "HEAD", "--to", "r", "--push", "--pr"])
+                .is_err()
+        );
+        Ok(())
+    }
+
+    // === BRANCH COMMAND TESTS ===
+
+    #[test]
+    fn test_branch_describe_command() -> TestResult {
+        let cli = Cli::try_parse_from(["rona", "branch", "describe", "Login form"])?;
+        let CliCommand::Branch {
+            subcommand:
+                Some(BranchSubcommand::Describe {
+                    description, clear, ..
+                }),
+            ..
+        } = cli.command
+        else {
+            return Err("Wrong command parsed".into());
+        };
+        assert_eq!(description.as_deref(), Some("Login form"));
+        assert!(!clear);
+
+        let cli = Cli::try_parse_from(["rona", "branch", "list"])?;
+        assert!(matches!(
+            cli.command,
+            CliCommand::Branch {
+                subcommand: Some(BranchSubcommand::List),
+                ..
+            }
+        ));
+
+        let cli = Cli::try_parse_from([
+            "rona",
+            "branch",
+            "new",
+            "--ticket",
+            "PROJ-1",
+            "--type",
+            "feat",
+            "Add login",
+        ])?;
+        let CliCommand::Branch {
+            subcommand:
+                Some(BranchSubcommand::New {
+                    description,
+                    ticket,
+                    branch_type,
+                    no_switch,
+                    ..
+                }),
+            ..
+        } = cli.command
+        else {
+            return Err("Wrong command parsed".into());
+        };
+        assert_eq!(description, "Add login");
+        assert_eq!(ticket.as_deref(), Some("PROJ-1"));
+        assert_eq!(branch_type.as_deref(), Some("feat"));
+        assert!(!no_switch);
+
+        let cli = Cli::try_parse_from(["rona", "branch", "rename", "feat/sign-in", "-y"])?;
+        let CliCommand::Branch {
+            subcommand:
+                Some(BranchSubcommand::Rename {
+                    new_name,
+                    local,
+                    yes,
+                    keep_old,
+                    ..
+                }),
+            ..
+        } = cli.command
+        else {
+            return Err("Wrong command parsed".into());
+        };
+        assert_eq!(new_name, "feat/sign-in");
+        assert!(yes && !local && !keep_old);
+        assert!(Cli::try_parse_from(["rona", "branch", "rename", "x", "--local", "-y"]).is_err());
+
+        let cli = Cli::try_parse_from(["rona", "branch", "merge", "main", "--dry-run"])?;
+        assert!(matches!(
+            cli.command,
+            CliCommand::Branch {
+                subcommand: Some(BranchSubcommand::Merge { branch: Some(ref branch), dry_run: true }),
+                ..
+            } if branch == "main"
+        ));
+        let cli = Cli::try_parse_from(["rona", "branch", "switch"])?;
+        assert!(matches!(
+            cli.command,
+            CliCommand::Branch {
+                subcommand: Some(BranchSubcommand::Switch { branch: None, .. }),
+                ..
+            }
+        ));
+        assert!(Cli::try_parse_from(["rona", "branch", "rebase", "main"]).is_ok());
+        assert!(Cli::try_parse_from(["rona", "branch", "pull", "main"]).is_err());
+
+        // Branch creation flags do not mix with subcommands
+        assert!(Cli::try_parse_from(["rona", "branch", "--no-switch", "list"]).is_err());
+        assert!(Cli::try_parse_from(["rona", "branch", "describe", "x", "--clear"]).is_err());
+        Ok(())
+    }
+
+    // === AUDIT COMMAND TESTS ===
+
+    #[test]
+    fn test_audit_command() -> TestResult {
+        let cli = Cli::try_parse_from(["rona", "audit", "--since", "v1.0"])?;
+        assert!(matches!(
+            cli.command,
+            CliCommand::Audit { since: Some(ref since) } if since == "v1.0"
+        ));
+        let cli = Cli::try_parse_from(["rona", "audit"])?;
+        assert!(matches!(cli.command, CliCommand::Audit { since: None }));
+        Ok(())
+    }
+
+    // === BUNDLE COMMAND TESTS ===
+
+    #[test]
+    fn test_bundle_command() -> TestResult {
+        let cli = Cli::try_parse_from([
+            "rona", "bundle", "create", "x.bundle", "--since", "v1.0", "-b", "main", "-b", "dev",
+        ])?;
+        let CliCommand::Bundle {
+            subcommand:
+                BundleSubcommand::Create {
+                    file,
+                    since,
+                    branches,
+                    dry_run,
+                },
+        } = cli.command
+        else {
+            return Err("Wrong command parsed".into());
+        };
+        assert_eq!(file, "x.bundle");
+        assert_eq!(since.as_deref(), Some("v1.0"));
+        assert_eq!(branches, ["main", "dev"]);
+        assert!(!dry_run);
+
+        let cli = Cli::try_parse_from(["rona", "bundle", "apply", "x.bundle", "--dry-run"])?;
+        assert!(matches!(
+            cli.command,
+            CliCommand::Bundle {
+                subcommand: BundleSubcommand::Apply { ref branches, dry_run: true, .. },
+            } if branches.is_empty()
+        ));
+        assert!(Cli::try_parse_from(["rona", "bundle", "apply"]).is_err());
+        Ok(())
+    }
+
+    // === BROWSE COMMAND TESTS ===
+
+    #[test]
+    fn test_browse_command() -> TestResult {
+        let cli = Cli::try_parse_from(["rona", "browse"])?;
+        let CliCommand::Browse { limit, dry_run } = cli.command else {
+            return Err("Wrong command parsed".into());
+        };
+        assert_eq!(limit, 100);
+        assert!(!dry_run);
+
+        let cli = Cli::try_parse_from(["rona", "browse", "-n", "20", "--dry-run"])?;
+        let CliCommand::Browse { limit, dry_run } = cli.command else {
+            return Err("Wrong command parsed".into());
+        };
+        assert_eq!(limit, 20);
+        assert!(dry_run);
+        Ok(())
+    }
+
     // === ADD COMMAND TESTS ===
 
     #[test]
@@ -1729,6 +8296,7 @@ mod cli_tests {
             to_exclude: exclude,
             interactive,
             dry_run,
+            ..
         } = cli.command
         else {
             return Err("Wrong command parsed".into());
@@ -1748,6 +8316,7 @@ mod cli_tests {
             to_exclude: exclude,
             interactive,
             dry_run,
+            ..
         } = cli.command
         else {
             return Err("Wrong command parsed".into());
@@ -1767,6 +8336,7 @@ mod cli_tests {
             to_exclude: exclude,
             interactive,
             dry_run,
+            ..
         } = cli.command
         else {
             return Err("Wrong command parsed".into());
@@ -1786,6 +8356,7 @@ mod cli_tests {
             to_exclude: exclude,
             interactive,
             dry_run,
+            ..
         } = cli.command
         else {
             return Err("Wrong command parsed".into());
@@ -1805,6 +8376,7 @@ mod cli_tests {
             to_exclude: exclude,
             interactive,
             dry_run,
+            ..
         } = cli.command
         else {
             return Err("Wrong command parsed".into());
@@ -1827,6 +8399,19 @@ mod cli_tests {
         Ok(())
     }
 
+    #[test]
+    fn test_add_interactive_filter() -> TestResult {
+        let cli = Cli::try_parse_from(["rona", "-a", "-i", "--filter", "clirs"])?;
+        let CliCommand::AddWithExclude { filter, .. } = cli.command else {
+            return Err("Wrong command parsed".into());
+        };
+        assert_eq!(filter.as_deref(), Some("clirs"));
+
+        // The filter only narrows the interactive selector
+        assert!(Cli::try_parse_from(["rona", "-a", "--filter", "clirs"]).is_err());
+        Ok(())
+    }
+
     // === RESET COMMAND TESTS ===
 
     #[test]
@@ -1837,6 +8422,7 @@ mod cli_tests {
             files,
             interactive,
             dry_run,
+            ..
         } = cli.command
         else {
             return Err("Wrong command parsed".into());
@@ -1942,6 +8528,7 @@ mod cli_tests {
             unsigned,
             yes,
             copy,
+            ..
         } = cli.command
         else {
             return Err("Wrong command parsed".into());
@@ -1967,6 +8554,7 @@ mod cli_tests {
             unsigned,
             yes,
             copy,
+            ..
         } = cli.command
         else {
             return Err("Wrong command parsed".into());
@@ -1992,6 +8580,7 @@ mod cli_tests {
             unsigned,
             yes,
             copy,
+            ..
         } = cli.command
         else {
             return Err("Wrong command parsed".into());
@@ -2017,6 +8606,7 @@ mod cli_tests {
             unsigned,
             yes,
             copy,
+            ..
         } = cli.command
         else {
             return Err("Wrong command parsed".into());
@@ -2042,6 +8632,7 @@ mod cli_tests {
             unsigned,
             yes,
             copy,
+            ..
         } = cli.command
         else {
             return Err("Wrong command parsed".into());
@@ -2067,6 +8658,7 @@ mod cli_tests {
             unsigned,
             yes,
             copy,
+            ..
         } = cli.command
         else {
             return Err("Wrong command parsed".into());
@@ -2092,6 +8684,7 @@ mod cli_tests {
             unsigned,
             yes,
             copy,
+            ..
         } = cli.command
         else {
             return Err("Wrong command parsed".into());
@@ -2105,6 +8698,101 @@ mod cli_tests {
         Ok(())
     }
 
+    #[test]
+    fn test_commit_inline_message() -> TestResult {
+        let cli = Cli::try_parse_from(["rona", "-c", "-m", "fix typo", "-t", "fix", "--amend"])?;
+
+        let CliCommand::Commit {
+            args,
+            message,
+            commit_type,
+            no_commit_number,
+            ..
+        } = cli.command
+        else {
+            return Err("Wrong command parsed".into());
+        };
+        assert_eq!(message.as_deref(), Some("fix typo"));
+        assert_eq!(commit_type.as_deref(), Some("fix"));
+        assert!(!no_commit_number);
+        assert_eq!(args, vec!["--amend"]);
+
+        // --type and --no-commit-number only apply to an inline message
+        assert!(Cli::try_parse_from(["rona", "-c", "-t", "fix"]).is_err());
+        assert!(Cli::try_parse_from(["rona", "-c", "-n"]).is_err());
+        Ok(())
+    }
+
+    // === HISTORY COMMAND TESTS ===
+
+    #[test]
+    fn test_history_ops_command() -> TestResult {
+        let cli = Cli::try_parse_from(["rona", "history", "ops", "-n", "5", "--json"])?;
+
+        let CliCommand::History {
+            subcommand: HistorySubcommand::Ops { limit, json },
+        } = cli.command
+        else {
+            return Err("Wrong command parsed".into());
+        };
+        assert_eq!(limit, 5);
+        assert!(json);
+        Ok(())
+    }
+
+    #[test]
+    fn test_logged_operations() -> TestResult {
+        let logged = |args: &[&str]| -> std::result::Result<_, clap::Error> {
+            Ok(Cli::try_parse_from(args)?.command.logged_operation())
+        };
+        assert_eq!(logged(&["rona", "-c", "-y"])?, Some("commit"));
+        assert_eq!(logged(&["rona", "-c", "--copy"])?, None);
+        assert_eq!(logged(&["rona", "health"])?, None);
+        assert_eq!(logged(&["rona", "health", "--fix"])?, Some("health"));
+        assert_eq!(logged(&["rona", "show"])?, None);
+        assert_eq!(logged(&["rona", "-g", "-i"])?, Some("generate"));
+        assert_eq!(logged(&["rona", "generate", "--print"])?, None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_env_command() -> TestResult {
+        let cli = Cli::try_parse_from(["rona", "env", "--json"])?;
+        assert!(matches!(cli.command, CliCommand::Env { json: true }));
+        let cli = Cli::try_parse_from(["rona", "which"])?;
+        assert!(matches!(cli.command, CliCommand::Env { json: false }));
+        Ok(())
+    }
+
+    #[test]
+    fn test_usage_command() -> TestResult {
+        let cli = Cli::try_parse_from(["rona", "usage", "--json"])?;
+
+        let CliCommand::Usage { json } = cli.command else {
+            return Err("Wrong command parsed".into());
+        };
+        assert!(json);
+        Ok(())
+    }
+
+    // === CHECK-MSG COMMAND TESTS ===
+
+    #[test]
+    fn test_check_msg_command() -> TestResult {
+        let cli = Cli::try_parse_from(["rona", "check-msg", ".git/COMMIT_EDITMSG"])?;
+
+        let CliCommand::CheckMsg { file } = cli.command else {
+            return Err("Wrong command parsed".into());
+        };
+        assert_eq!(file, ".git/COMMIT_EDITMSG");
+        Ok(())
+    }
+
+    #[test]
+    fn test_check_msg_requires_file() {
+        assert!(Cli::try_parse_from(["rona", "check-msg"]).is_err());
+    }
+
     // === PUSH COMMAND TESTS ===
 
     #[test]
@@ -2112,7 +8800,7 @@ mod cli_tests {
         let args = vec!["rona", "-p"];
         let cli = Cli::try_parse_from(args)?;
 
-        let CliCommand::Push { args, dry_run } = cli.command else {
+        let CliCommand::Push { args, dry_run, .. } = cli.command else {
             return Err("Wrong command parsed".into());
         };
         assert!(args.is_empty());
@@ -2125,7 +8813,7 @@ mod cli_tests {
         let args = vec!["rona", "-p", "--force"];
         let cli = Cli::try_parse_from(args)?;
 
-        let CliCommand::Push { args, dry_run } = cli.command else {
+        let CliCommand::Push { args, dry_run, .. } = cli.command else {
             return Err("Wrong command parsed".into());
         };
         assert_eq!(args, vec!["--force"]);
@@ -2138,7 +8826,7 @@ mod cli_tests {
         let args = vec!["rona", "-p", "--force", "--set-upstream", "origin", "main"];
         let cli = Cli::try_parse_from(args)?;
 
-        let CliCommand::Push { args, dry_run } = cli.command else {
+        let CliCommand::Push { args, dry_run, .. } = cli.command else {
             return Err("Wrong command parsed".into());
         };
         assert_eq!(args, vec!["--force", "--set-upstream", "origin", "main"]);
@@ -2151,7 +8839,7 @@ mod cli_tests {
         let args = vec!["rona", "-p", "origin", "feature/branch"];
         let cli = Cli::try_parse_from(args)?;
 
-        let CliCommand::Push { args, dry_run } = cli.command else {
+        let CliCommand::Push { args, dry_run, .. } = cli.command else {
             return Err("Wrong command parsed".into());
         };
         assert_eq!(args, vec!["origin", "feature/branch"]);
@@ -2164,7 +8852,7 @@ mod cli_tests {
         let args = vec!["rona", "-p", "-u", "origin", "main"];
         let cli = Cli::try_parse_from(args)?;
 
-        let CliCommand::Push { args, dry_run } = cli.command else {
+        let CliCommand::Push { args, dry_run, .. } = cli.command else {
             return Err("Wrong command parsed".into());
         };
         assert_eq!(args, vec!["-u", "origin", "main"]);
@@ -2172,6 +8860,28 @@ mod cli_tests {
         Ok(())
     }
 
+    #[test]
+    fn test_push_review() -> TestResult {
+        let cli =
+            Cli::try_parse_from(["rona", "push", "--review", "--target", "release", "gerrit"])?;
+
+        let CliCommand::Push {
+            args,
+            review,
+            target,
+            ..
+        } = cli.command
+        else {
+            return Err("Wrong command parsed".into());
+        };
+        assert!(review);
+        assert_eq!(target.as_deref(), Some("release"));
+        assert_eq!(args, vec!["gerrit"]);
+
+        assert!(Cli::try_parse_from(["rona", "push", "--target", "release"]).is_err());
+        Ok(())
+    }
+
     // === GENERATE COMMAND TESTS ===
 
     #[test]
@@ -2183,6 +8893,7 @@ mod cli_tests {
             dry_run,
             interactive,
             no_commit_number,
+            ..
         } = cli.command
         else {
             return Err("Wrong command parsed".into());
@@ -2202,6 +8913,7 @@ mod cli_tests {
             dry_run,
             interactive,
             no_commit_number,
+            ..
         } = cli.command
         else {
             return Err("Wrong command parsed".into());
@@ -2221,6 +8933,7 @@ mod cli_tests {
             dry_run,
             interactive,
             no_commit_number,
+            ..
         } = cli.command
         else {
             return Err("Wrong command parsed".into());
@@ -2232,21 +8945,65 @@ mod cli_tests {
     }
 
     #[test]
-    fn test_generate_no_commit_number() -> TestResult {
-        let args = vec!["rona", "-g", "-n"];
-        let cli = Cli::try_parse_from(args)?;
+    fn test_generate_no_commit_number() -> TestResult {
+        let args = vec!["rona", "-g", "-n"];
+        let cli = Cli::try_parse_from(args)?;
+
+        let CliCommand::Generate {
+            dry_run,
+            interactive,
+            no_commit_number,
+            ..
+        } = cli.command
+        else {
+            return Err("Wrong command parsed".into());
+        };
+        assert!(!dry_run);
+        assert!(!interactive);
+        assert!(no_commit_number);
+        Ok(())
+    }
+
+    #[test]
+    fn test_generate_print() -> TestResult {
+        let cli = Cli::try_parse_from(["rona", "-g", "--print", "-n"])?;
+
+        let CliCommand::Generate {
+            print,
+            no_commit_number,
+            ..
+        } = cli.command
+        else {
+            return Err("Wrong command parsed".into());
+        };
+        assert!(print);
+        assert!(no_commit_number);
+        Ok(())
+    }
+
+    #[test]
+    fn test_generate_print_conflicts_with_interactive() {
+        assert!(Cli::try_parse_from(["rona", "-g", "--print", "-i"]).is_err());
+        assert!(Cli::try_parse_from(["rona", "-g", "--print", "--dry-run"]).is_err());
+    }
 
+    #[test]
+    fn test_generate_import_flags() -> TestResult {
+        let cli = Cli::try_parse_from(["rona", "-g", "--stdin", "-n"])?;
         let CliCommand::Generate {
-            dry_run,
-            interactive,
-            no_commit_number,
+            stdin,
+            from_clipboard,
+            ..
         } = cli.command
         else {
             return Err("Wrong command parsed".into());
         };
-        assert!(!dry_run);
-        assert!(!interactive);
-        assert!(no_commit_number);
+        assert!(stdin);
+        assert!(!from_clipboard);
+
+        assert!(Cli::try_parse_from(["rona", "-g", "--stdin", "--from-clipboard"]).is_err());
+        assert!(Cli::try_parse_from(["rona", "-g", "--from-clipboard", "-i"]).is_err());
+        assert!(Cli::try_parse_from(["rona", "-g", "--stdin", "--print"]).is_err());
         Ok(())
     }
 
@@ -2259,6 +9016,7 @@ mod cli_tests {
             dry_run,
             interactive,
             no_commit_number,
+            ..
         } = cli.command
         else {
             return Err("Wrong command parsed".into());
@@ -2278,6 +9036,7 @@ mod cli_tests {
             dry_run,
             interactive,
             no_commit_number,
+            ..
         } = cli.command
         else {
             return Err("Wrong command parsed".into());
@@ -2295,9 +9054,127 @@ mod cli_tests {
         let args = vec!["rona", "-l"];
         let cli = Cli::try_parse_from(args)?;
 
-        let CliCommand::ListStatus = cli.command else {
+        let CliCommand::ListStatus { filter } = cli.command else {
+            return Err("Wrong command parsed".into());
+        };
+        assert_eq!(filter, None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_list_status_filter() -> TestResult {
+        let cli = Cli::try_parse_from(["rona", "-l", "--filter", "src/cli"])?;
+        let CliCommand::ListStatus { filter } = cli.command else {
+            return Err("Wrong command parsed".into());
+        };
+        assert_eq!(filter.as_deref(), Some("src/cli"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_prepare_msg_command() -> TestResult {
+        let args = vec![
+            "rona",
+            "prepare-msg",
+            ".git/COMMIT_EDITMSG",
+            "commit",
+            "HEAD",
+        ];
+        let cli = Cli::try_parse_from(args)?;
+
+        let CliCommand::PrepareMsg {
+            msg_file,
+            source,
+            sha,
+            dry_run,
+        } = cli.command
+        else {
+            return Err("Wrong command parsed".into());
+        };
+        assert_eq!(msg_file, ".git/COMMIT_EDITMSG");
+        assert_eq!(source.as_deref(), Some("commit"));
+        assert_eq!(sha.as_deref(), Some("HEAD"));
+        assert!(!dry_run);
+        Ok(())
+    }
+
+    // === EXPLAIN-STATUS COMMAND TESTS ===
+
+    #[test]
+    fn test_explain_status_command() -> TestResult {
+        let cli = Cli::try_parse_from(["rona", "explain-status"])?;
+        assert!(matches!(cli.command, CliCommand::ExplainStatus));
+        Ok(())
+    }
+
+    // === FORMAT-PATCH COMMAND TESTS ===
+
+    #[test]
+    fn test_format_patch_command() -> TestResult {
+        let cli = Cli::try_parse_from(["rona", "format-patch"])?;
+        let CliCommand::FormatPatch {
+            range,
+            count,
+            output_dir,
+            cover_letter,
+            dry_run,
+        } = cli.command
+        else {
+            return Err("Wrong command parsed".into());
+        };
+        assert_eq!(range, None);
+        assert_eq!(count, None);
+        assert_eq!(output_dir, None);
+        assert!(!cover_letter);
+        assert!(!dry_run);
+
+        let cli = Cli::try_parse_from([
+            "rona",
+            "format-patch",
+            "main..",
+            "-o",
+            "outgoing",
+            "--cover-letter",
+        ])?;
+        let CliCommand::FormatPatch {
+            range,
+            output_dir,
+            cover_letter,
+            ..
+        } = cli.command
+        else {
             return Err("Wrong command parsed".into());
         };
+        assert_eq!(range.as_deref(), Some("main.."));
+        assert_eq!(output_dir.as_deref(), Some("outgoing"));
+        assert!(cover_letter);
+
+        // A range and a count select the series in two different ways
+        assert!(Cli::try_parse_from(["rona", "format-patch", "main..", "-n", "2"]).is_err());
+        Ok(())
+    }
+
+    // === HEALTH COMMAND TESTS ===
+
+    #[test]
+    fn test_health_command() -> TestResult {
+        let cli = Cli::try_parse_from(["rona", "health"])?;
+        assert!(matches!(
+            cli.command,
+            CliCommand::Health {
+                fix: false,
+                optimize: false
+            }
+        ));
+
+        let cli = Cli::try_parse_from(["rona", "health", "--fix", "--optimize"])?;
+        assert!(matches!(
+            cli.command,
+            CliCommand::Health {
+                fix: true,
+                optimize: true
+            }
+        ));
         Ok(())
     }
 
@@ -2308,11 +9185,17 @@ mod cli_tests {
         let args = vec!["rona", "-i"];
         let cli = Cli::try_parse_from(args)?;
 
-        let CliCommand::Initialize { editor, dry_run } = cli.command else {
+        let CliCommand::Initialize {
+            editor,
+            dry_run,
+            repo,
+        } = cli.command
+        else {
             return Err("Wrong command parsed".into());
         };
         assert_eq!(editor, "nano");
         assert!(!dry_run);
+        assert!(!repo);
         Ok(())
     }
 
@@ -2321,7 +9204,10 @@ mod cli_tests {
         let args = vec!["rona", "-i", "zed"];
         let cli = Cli::try_parse_from(args)?;
 
-        let CliCommand::Initialize { editor, dry_run } = cli.command else {
+        let CliCommand::Initialize {
+            editor, dry_run, ..
+        } = cli.command
+        else {
             return Err("Wrong command parsed".into());
         };
         assert_eq!(editor, "zed");
@@ -2329,6 +9215,34 @@ mod cli_tests {
         Ok(())
     }
 
+    #[test]
+    fn test_init_repo() -> TestResult {
+        let cli = Cli::try_parse_from(["rona", "init", "--repo"])?;
+
+        let CliCommand::Initialize { repo, .. } = cli.command else {
+            return Err("Wrong command parsed".into());
+        };
+        assert!(repo);
+        Ok(())
+    }
+
+    // === SCHEMA COMMAND TESTS ===
+
+    #[test]
+    fn test_schema_command() -> TestResult {
+        let cli = Cli::try_parse_from(["rona", "schema"])?;
+        assert!(matches!(cli.command, CliCommand::Schema { name: None }));
+
+        let cli = Cli::try_parse_from(["rona", "schema", "show"])?;
+        let CliCommand::Schema { name } = cli.command else {
+            return Err("Wrong command parsed".into());
+        };
+        assert_eq!(name.as_deref(), Some("show"));
+
+        assert!(Cli::try_parse_from(["rona", "schema", "log"]).is_err());
+        Ok(())
+    }
+
     // === SET EDITOR COMMAND TESTS ===
 
     #[test]
@@ -2396,6 +9310,81 @@ mod cli_tests {
         Ok(())
     }
 
+    #[test]
+    fn test_chdir_short_flag() -> TestResult {
+        let cli = Cli::try_parse_from(["rona", "-C", "../other", "-l"])?;
+        assert_eq!(cli.chdir.as_deref(), Some("../other"));
+        assert!(matches!(cli.command, CliCommand::ListStatus { .. }));
+        Ok(())
+    }
+
+    #[test]
+    fn test_chdir_long_flag_after_subcommand() -> TestResult {
+        let cli = Cli::try_parse_from(["rona", "push", "--chdir", "/tmp/repo"])?;
+        assert_eq!(cli.chdir.as_deref(), Some("/tmp/repo"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_only_flag_is_global() -> TestResult {
+        let cli = Cli::try_parse_from(["rona", "commit", "--read-only"])?;
+        assert!(cli.read_only);
+        let cli = Cli::try_parse_from(["rona", "--read-only", "-l"])?;
+        assert!(cli.read_only);
+        let cli = Cli::try_parse_from(["rona", "-l"])?;
+        assert!(!cli.read_only);
+        Ok(())
+    }
+
+    #[test]
+    fn test_scope_flag_is_global() -> TestResult {
+        let cli = Cli::try_parse_from(["rona", "--scope", "api", "-a"])?;
+        assert_eq!(cli.path_scope.as_deref(), Some("api"));
+        let cli = Cli::try_parse_from(["rona", "list-status", "--scope", "packages/web"])?;
+        assert_eq!(cli.path_scope.as_deref(), Some("packages/web"));
+        let cli = Cli::try_parse_from(["rona", "-g"])?;
+        assert_eq!(cli.path_scope, None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_lang_flag_is_global() -> TestResult {
+        let cli = Cli::try_parse_from(["rona", "commit", "-m", "ajoute b", "--lang", "fr"])?;
+        assert_eq!(cli.lang.as_deref(), Some("fr"));
+        let cli = Cli::try_parse_from(["rona", "-g", "-i"])?;
+        assert_eq!(cli.lang, None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_template_flag_is_global() -> TestResult {
+        let cli = Cli::try_parse_from(["rona", "-g", "--template", "release"])?;
+        assert_eq!(cli.template.as_deref(), Some("release"));
+        let cli = Cli::try_parse_from(["rona", "--template", "hotfix", "commit", "-m", "fix"])?;
+        assert_eq!(cli.template.as_deref(), Some("hotfix"));
+        let cli = Cli::try_parse_from(["rona", "template", "check"])?;
+        assert_eq!(cli.template, None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_output_flag_is_global() -> TestResult {
+        let cli = Cli::try_parse_from(["rona", "status", "--output", "json"])?;
+        assert_eq!(cli.output, OutputFormat::Json);
+        let cli = Cli::try_parse_from(["rona", "--output", "json", "-c", "--dry-run"])?;
+        assert_eq!(cli.output, OutputFormat::Json);
+        let cli = Cli::try_parse_from(["rona", "-l"])?;
+        assert_eq!(cli.output, OutputFormat::Text);
+        assert!(Cli::try_parse_from(["rona", "-l", "--output", "yaml"]).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_chdir_missing_directory_errors() {
+        let result = change_directory("/definitely/not/a/rona/dir");
+        assert!(matches!(result, Err(RonaError::Io(_))));
+    }
+
     // === EDGE CASES AND ERROR TESTS ===
 
     #[test]
@@ -2410,6 +9399,7 @@ mod cli_tests {
             unsigned,
             yes,
             copy,
+            ..
         } = cli.command
         else {
             return Err("Wrong command parsed".into());
@@ -2435,6 +9425,7 @@ mod cli_tests {
             unsigned,
             yes,
             copy,
+            ..
         } = cli.command
         else {
             return Err("Wrong command parsed".into());
@@ -2473,6 +9464,7 @@ mod cli_tests {
             unsigned,
             yes,
             copy,
+            ..
         } = cli.command
         else {
             return Err("Wrong command parsed".into());
@@ -2498,6 +9490,7 @@ mod cli_tests {
             unsigned,
             yes,
             copy,
+            ..
         } = cli.command
         else {
             return Err("Wrong command parsed".into());
@@ -2523,6 +9516,7 @@ mod cli_tests {
             unsigned,
             yes,
             copy,
+            ..
         } = cli.command
         else {
             return Err("Wrong command parsed".into());
@@ -2548,6 +9542,7 @@ mod cli_tests {
             unsigned,
             yes,
             copy,
+            ..
         } = cli.command
         else {
             return Err("Wrong command parsed".into());
@@ -2573,6 +9568,7 @@ mod cli_tests {
             unsigned,
             yes,
             copy,
+            ..
         } = cli.command
         else {
             return Err("Wrong command parsed".into());
@@ -2598,6 +9594,7 @@ mod cli_tests {
             unsigned,
             yes,
             copy,
+            ..
         } = cli.command
         else {
             return Err("Wrong command parsed".into());
@@ -2623,6 +9620,7 @@ mod cli_tests {
             unsigned,
             yes,
             copy,
+            ..
         } = cli.command
         else {
             return Err("Wrong command parsed".into());
@@ -2650,6 +9648,7 @@ mod cli_tests {
             unsigned,
             yes,
             copy,
+            ..
         } = cli.command
         else {
             return Err("Wrong command parsed".into());
@@ -2675,6 +9674,7 @@ mod cli_tests {
             unsigned,
             yes,
             copy,
+            ..
         } = cli.command
         else {
             return Err("Wrong command parsed".into());
@@ -2784,6 +9784,81 @@ mod cli_tests {
         Ok(())
     }
 
+    #[test]
+    fn test_config_edit() -> TestResult {
+        let cli = Cli::try_parse_from(["rona", "config", "edit", "global", "-i"])?;
+        let CliCommand::Config {
+            subcommand: ConfigSubcommand::Edit { scope, interactive },
+        } = cli.command
+        else {
+            return Err("Wrong command parsed".into());
+        };
+        assert!(matches!(scope, ConfigScope::Global));
+        assert!(interactive);
+
+        let cli = Cli::try_parse_from(["rona", "config", "edit"])?;
+        let CliCommand::Config {
+            subcommand: ConfigSubcommand::Edit { scope, interactive },
+        } = cli.command
+        else {
+            return Err("Wrong command parsed".into());
+        };
+        assert!(matches!(scope, ConfigScope::Local));
+        assert!(!interactive);
+        Ok(())
+    }
+
+    #[test]
+    fn test_config_show() -> TestResult {
+        let cli = Cli::try_parse_from(["rona", "config", "show", "--origins"])?;
+        let CliCommand::Config {
+            subcommand: ConfigSubcommand::Show { origins },
+        } = cli.command
+        else {
+            return Err("Wrong command parsed".into());
+        };
+        assert!(origins);
+        Ok(())
+    }
+
+    #[test]
+    fn test_config_sync() -> TestResult {
+        let cli = Cli::try_parse_from(["rona", "config", "sync", "--branch", "conventions", "-y"])?;
+        let CliCommand::Config {
+            subcommand:
+                ConfigSubcommand::Sync {
+                    url,
+                    remote,
+                    branch,
+                    check,
+                    yes,
+                    dry_run,
+                },
+        } = cli.command
+        else {
+            return Err("Wrong command parsed".into());
+        };
+        assert_eq!(url, None);
+        assert_eq!(remote, None);
+        assert_eq!(branch.as_deref(), Some("conventions"));
+        assert!(yes && !check && !dry_run);
+
+        // A URL and a branch are two different sources
+        assert!(
+            Cli::try_parse_from([
+                "rona",
+                "config",
+                "sync",
+                "--url",
+                "https://example.com/rona.toml",
+                "--branch",
+                "main"
+            ])
+            .is_err()
+        );
+        Ok(())
+    }
+
     #[test]
     fn test_config_which() -> TestResult {
         let args = vec!["rona", "config", "which"];
@@ -2842,15 +9917,21 @@ mod cli_tests {
         let variables = TemplateVariables {
             commit_number: None,
             commit_type: "docs".to_string(),
+            commit_emoji: String::new(),
+            ticket: String::new(),
             branch_name: "main".to_string(),
             message: "Update docs".to_string(),
             date: "2024-01-15".to_string(),
             time: "14:30:00".to_string(),
             author: "Test User".to_string(),
             email: "test@example.com".to_string(),
+            branch_description: String::new(),
+            staged_files: Vec::new(),
+            impact: Vec::new(),
+            changed_symbols: Vec::new(),
         };
 
-        let result = process_template(default_template, &variables, &HashMap::new())?;
+        let result = process_template(default_template, &variables, &HashMap::new(), false)?;
 
         assert!(
             !result.contains("[]"),
@@ -2872,15 +9953,21 @@ mod cli_tests {
         let variables = TemplateVariables {
             commit_number: Some(42),
             commit_type: "feat".to_string(),
+            commit_emoji: String::new(),
+            ticket: String::new(),
             branch_name: "new-feature".to_string(),
             message: "Add feature".to_string(),
             date: "2024-01-15".to_string(),
             time: "14:30:00".to_string(),
             author: "Test User".to_string(),
             email: "test@example.com".to_string(),
+            branch_description: String::new(),
+            staged_files: Vec::new(),
+            impact: Vec::new(),
+            changed_symbols: Vec::new(),
         };
 
-        let result = process_template(default_template, &variables, &HashMap::new())?;
+        let result = process_template(default_template, &variables, &HashMap::new(), false)?;
 
         assert!(
             result.starts_with("[42]"),
@@ -2903,15 +9990,21 @@ mod cli_tests {
         let variables = TemplateVariables {
             commit_number: None,
             commit_type: "docs".to_string(),
+            commit_emoji: String::new(),
+            ticket: String::new(),
             branch_name: "main".to_string(),
             message: "Update docs".to_string(),
             date: "2024-01-15".to_string(),
             time: "14:30:00".to_string(),
             author: "Test User".to_string(),
             email: "test@example.com".to_string(),
+            branch_description: String::new(),
+            staged_files: Vec::new(),
+            impact: Vec::new(),
+            changed_symbols: Vec::new(),
         };
 
-        let result = process_template(wrong_template, &variables, &HashMap::new())?;
+        let result = process_template(wrong_template, &variables, &HashMap::new(), false)?;
 
         assert_eq!(result, "[] (docs on main) Update docs");
         assert!(result.contains("[]"), "This demonstrates the bug we fixed");
@@ -2962,6 +10055,214 @@ mod cli_tests {
         );
     }
 
+    // === PRUNE COMMAND TESTS ===
+
+    #[test]
+    fn test_prune_command() -> TestResult {
+        let cli = Cli::try_parse_from(["rona", "prune"])?;
+        let CliCommand::Prune { max_age, dry_run } = cli.command else {
+            return Err("Wrong command parsed".into());
+        };
+        assert_eq!(max_age, "30d");
+        assert!(!dry_run);
+
+        let cli = Cli::try_parse_from(["rona", "prune", "--max-age", "7d", "--dry-run"])?;
+        let CliCommand::Prune { max_age, dry_run } = cli.command else {
+            return Err("Wrong command parsed".into());
+        };
+        assert_eq!(max_age, "7d");
+        assert!(dry_run);
+        Ok(())
+    }
+
+    // === TODO COMMAND TESTS ===
+
+    #[test]
+    fn test_todo_command() -> TestResult {
+        let cli = Cli::try_parse_from(["rona", "todo"])?;
+        let CliCommand::Todo { check } = cli.command else {
+            return Err("Wrong command parsed".into());
+        };
+        assert!(!check);
+
+        let cli = Cli::try_parse_from(["rona", "todo", "--check"])?;
+        let CliCommand::Todo { check } = cli.command else {
+            return Err("Wrong command parsed".into());
+        };
+        assert!(check);
+        Ok(())
+    }
+
+    // === TOUR COMMAND TESTS ===
+
+    #[test]
+    fn test_tour_command() -> TestResult {
+        let cli = Cli::try_parse_from(["rona", "tour", "-y", "--keep"])?;
+
+        let CliCommand::Tour { yes, keep } = cli.command else {
+            return Err("Wrong command parsed".into());
+        };
+        assert!(yes);
+        assert!(keep);
+        Ok(())
+    }
+
+    // === SHOW COMMAND TESTS ===
+
+    #[test]
+    fn test_show_command() -> TestResult {
+        let cli = Cli::try_parse_from(["rona", "show"])?;
+        let CliCommand::Show { reference, json } = cli.command else {
+            return Err("Wrong command parsed".into());
+        };
+        assert_eq!(reference, "HEAD");
+        assert!(!json);
+
+        let cli = Cli::try_parse_from(["rona", "show", "HEAD~2", "--json"])?;
+        let CliCommand::Show { reference, json } = cli.command else {
+            return Err("Wrong command parsed".into());
+        };
+        assert_eq!(reference, "HEAD~2");
+        assert!(json);
+        Ok(())
+    }
+
+    // === COPY COMMAND TESTS ===
+
+    #[test]
+    fn test_copy_command() -> TestResult {
+        let cli = Cli::try_parse_from(["rona", "copy"])?;
+        let CliCommand::Copy {
+            reference,
+            format,
+            print,
+        } = cli.command
+        else {
+            return Err("Wrong command parsed".into());
+        };
+        assert_eq!(reference, "HEAD");
+        assert_eq!(format, None);
+        assert!(!print);
+
+        let cli = Cli::try_parse_from(["rona", "copy", "v1.0", "--format", "sha", "--print"])?;
+        let CliCommand::Copy {
+            reference,
+            format,
+            print,
+        } = cli.command
+        else {
+            return Err("Wrong command parsed".into());
+        };
+        assert_eq!(reference, "v1.0");
+        assert_eq!(format.as_deref(), Some("sha"));
+        assert!(print);
+        Ok(())
+    }
+
+    // === COMPLETION COMMAND TESTS ===
+
+    #[test]
+    fn test_completion_commands() -> TestResult {
+        let cli = Cli::try_parse_from(["rona", "completion", "zsh"])?;
+        assert!(matches!(
+            cli.command,
+            CliCommand::Completion {
+                shell: Some(Shell::Zsh),
+                subcommand: None
+            }
+        ));
+
+        let cli = Cli::try_parse_from(["rona", "completion", "check", "fish"])?;
+        assert!(matches!(
+            cli.command,
+            CliCommand::Completion {
+                shell: None,
+                subcommand: Some(CompletionSubcommand::Check { shell: Shell::Fish })
+            }
+        ));
+
+        assert!(Cli::try_parse_from(["rona", "completion"]).is_err());
+        Ok(())
+    }
+
+    // === TEMPLATE COMMAND TESTS ===
+
+    #[test]
+    fn test_template_check_command() -> TestResult {
+        let cli = Cli::try_parse_from(["rona", "template", "check"])?;
+        assert!(matches!(
+            cli.command,
+            CliCommand::Template {
+                subcommand: TemplateSubcommand::Check
+            }
+        ));
+        Ok(())
+    }
+
+    // === SNIPPET COMMAND TESTS ===
+
+    #[test]
+    fn test_snippet_commands() -> TestResult {
+        let cli = Cli::try_parse_from(["rona", "snippet", "list"])?;
+        assert!(matches!(
+            cli.command,
+            CliCommand::Snippet {
+                subcommand: SnippetSubcommand::List
+            }
+        ));
+
+        let cli = Cli::try_parse_from([
+            "rona",
+            "snippet",
+            "insert",
+            "review",
+            "--file",
+            "msg.txt",
+            "--dry-run",
+        ])?;
+        let CliCommand::Snippet {
+            subcommand:
+                SnippetSubcommand::Insert {
+                    name,
+                    file,
+                    dry_run,
+                },
+        } = cli.command
+        else {
+            return Err("Wrong command parsed".into());
+        };
+        assert_eq!(name, "review");
+        assert_eq!(file.as_deref(), Some("msg.txt"));
+        assert!(dry_run);
+        Ok(())
+    }
+
+    // === STATS COMMAND TESTS ===
+
+    #[test]
+    fn test_stats_command() -> TestResult {
+        let cli = Cli::try_parse_from(["rona", "stats"])?;
+        assert!(matches!(cli.command, CliCommand::Stats { export: None }));
+
+        let cli = Cli::try_parse_from(["rona", "stats", "--export", "prometheus"])?;
+        let CliCommand::Stats { export } = cli.command else {
+            return Err("Wrong command parsed".into());
+        };
+        assert_eq!(export, Some(StatsFormat::Prometheus));
+
+        assert!(Cli::try_parse_from(["rona", "stats", "--export", "xml"]).is_err());
+        Ok(())
+    }
+
+    // === STATUS COMMAND TESTS ===
+
+    #[test]
+    fn test_status_command() -> TestResult {
+        let cli = Cli::try_parse_from(["rona", "status"])?;
+        assert!(matches!(cli.command, CliCommand::Status));
+        Ok(())
+    }
+
     // === SYNC COMMAND TESTS ===
 
     #[test]
@@ -2978,7 +10279,7 @@ mod cli_tests {
         else {
             return Err("Wrong command parsed".into());
         };
-        assert_eq!(source_branch, "main");
+        assert!(source_branch.is_none());
         assert!(!rebase);
         assert!(new_branch.is_none());
         assert!(!dry_run);
@@ -2999,7 +10300,7 @@ mod cli_tests {
         else {
             return Err("Wrong command parsed".into());
         };
-        assert_eq!(source_branch, "develop");
+        assert_eq!(source_branch.as_deref(), Some("develop"));
         assert!(!rebase);
         assert!(new_branch.is_none());
         assert!(!dry_run);
@@ -3020,7 +10321,7 @@ mod cli_tests {
         else {
             return Err("Wrong command parsed".into());
         };
-        assert_eq!(source_branch, "staging");
+        assert_eq!(source_branch.as_deref(), Some("staging"));
         assert!(!rebase);
         assert!(new_branch.is_none());
         assert!(!dry_run);
@@ -3041,7 +10342,7 @@ mod cli_tests {
         else {
             return Err("Wrong command parsed".into());
         };
-        assert_eq!(source_branch, "main");
+        assert!(source_branch.is_none());
         assert!(rebase);
         assert!(new_branch.is_none());
         assert!(!dry_run);
@@ -3062,7 +10363,7 @@ mod cli_tests {
         else {
             return Err("Wrong command parsed".into());
         };
-        assert_eq!(source_branch, "main");
+        assert!(source_branch.is_none());
         assert!(rebase);
         assert!(new_branch.is_none());
         assert!(!dry_run);
@@ -3083,7 +10384,7 @@ mod cli_tests {
         else {
             return Err("Wrong command parsed".into());
         };
-        assert_eq!(source_branch, "main");
+        assert!(source_branch.is_none());
         assert!(!rebase);
         assert_eq!(new_branch, Some("feature/new-feature".to_string()));
         assert!(!dry_run);
@@ -3104,7 +10405,7 @@ mod cli_tests {
         else {
             return Err("Wrong command parsed".into());
         };
-        assert_eq!(source_branch, "main");
+        assert!(source_branch.is_none());
         assert!(!rebase);
         assert_eq!(new_branch, Some("bugfix/issue-123".to_string()));
         assert!(!dry_run);
@@ -3125,7 +10426,7 @@ mod cli_tests {
         else {
             return Err("Wrong command parsed".into());
         };
-        assert_eq!(source_branch, "main");
+        assert!(source_branch.is_none());
         assert!(!rebase);
         assert!(new_branch.is_none());
         assert!(dry_run);
@@ -3155,7 +10456,7 @@ mod cli_tests {
         else {
             return Err("Wrong command parsed".into());
         };
-        assert_eq!(source_branch, "develop");
+        assert_eq!(source_branch.as_deref(), Some("develop"));
         assert!(rebase);
         assert_eq!(new_branch, Some("feature/test".to_string()));
         assert!(dry_run);
@@ -3184,7 +10485,7 @@ mod cli_tests {
         else {
             return Err("Wrong command parsed".into());
         };
-        assert_eq!(source_branch, "staging");
+        assert_eq!(source_branch.as_deref(), Some("staging"));
         assert!(rebase);
         assert_eq!(new_branch, Some("hotfix/critical".to_string()));
         assert!(!dry_run);
@@ -0,0 +1,399 @@
+//! Shell Completion Checks
+//!
+//! Stale completion scripts are a recurring support issue: the script is generated
+//! once, rona is upgraded, and new commands or flags never complete. `rona completion
+//! check <shell>` looks for the script where the shell loads it from, compares it with
+//! the script the running rona generates, and runs the `rona -l` helper the fish
+//! completions call. Each problem becomes a [`CompletionFinding`] with the command
+//! that fixes it.
+
+use std::{
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use clap_complete::Shell;
+
+/// Where the shell looks for completions: the home directory and the XDG base
+/// directories, resolved once so the lookups can be tested with any layout.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShellDirs {
+    pub home: PathBuf,
+    /// `$XDG_CONFIG_HOME`, or `~/.config`
+    pub config_home: PathBuf,
+    /// `$XDG_DATA_HOME`, or `~/.local/share`
+    pub data_home: PathBuf,
+    /// `$ZDOTDIR`, or the home directory
+    pub zdotdir: PathBuf,
+}
+
+impl ShellDirs {
+    /// Resolves the directories from the environment, or `None` without a home
+    /// directory.
+    #[must_use]
+    pub fn from_env() -> Option<Self> {
+        let home = dirs::home_dir()?;
+        let env_dir = |name: &str| {
+            std::env::var_os(name)
+                .filter(|value| !value.is_empty())
+                .map(PathBuf::from)
+        };
+        Some(Self {
+            config_home: env_dir("XDG_CONFIG_HOME").unwrap_or_else(|| home.join(".config")),
+            data_home: env_dir("XDG_DATA_HOME").unwrap_or_else(|| home.join(".local/share")),
+            zdotdir: env_dir("ZDOTDIR").unwrap_or_else(|| home.clone()),
+            home,
+        })
+    }
+
+    /// The completion files `shell` loads on its own, most specific first.
+    #[must_use]
+    pub fn completion_files(&self, shell: Shell) -> Vec<PathBuf> {
+        let system = |dirs: &[&str], name: &str| -> Vec<PathBuf> {
+            dirs.iter().map(|dir| Path::new(dir).join(name)).collect()
+        };
+        let (mut files, system_files) = match shell {
+            Shell::Fish => (
+                vec![
+                    self.config_home.join("fish/completions/rona.fish"),
+                    self.data_home.join("fish/vendor_completions.d/rona.fish"),
+                ],
+                system(
+                    &[
+                        "/usr/local/share/fish/vendor_completions.d",
+                        "/opt/homebrew/share/fish/vendor_completions.d",
+                        "/usr/share/fish/vendor_completions.d",
+                    ],
+                    "rona.fish",
+                ),
+            ),
+            Shell::Bash => (
+                vec![self.data_home.join("bash-completion/completions/rona")],
+                system(
+                    &[
+                        "/usr/local/etc/bash_completion.d",
+                        "/opt/homebrew/etc/bash_completion.d",
+                        "/usr/share/bash-completion/completions",
+                        "/etc/bash_completion.d",
+                    ],
+                    "rona",
+                ),
+            ),
+            Shell::Zsh => (
+                vec![self.home.join(".zfunc/_rona")],
+                system(
+                    &[
+                        "/usr/local/share/zsh/site-functions",
+                        "/opt/homebrew/share/zsh/site-functions",
+                        "/usr/share/zsh/site-functions",
+                        "/usr/share/zsh/vendor-completions",
+                    ],
+                    "_rona",
+                ),
+            ),
+            _ => (Vec::new(), Vec::new()),
+        };
+        files.extend(system_files);
+        files
+    }
+
+    /// The startup files the script can be pasted into or generated from.
+    #[must_use]
+    pub fn startup_files(&self, shell: Shell) -> Vec<PathBuf> {
+        match shell {
+            Shell::Fish => vec![self.config_home.join("fish/config.fish")],
+            Shell::Bash => vec![self.home.join(".bashrc"), self.home.join(".bash_profile")],
+            Shell::Zsh => vec![self.zdotdir.join(".zshrc")],
+            Shell::PowerShell => vec![
+                self.config_home
+                    .join("powershell/Microsoft.PowerShell_profile.ps1"),
+                self.home
+                    .join("Documents/PowerShell/Microsoft.PowerShell_profile.ps1"),
+            ],
+            Shell::Elvish => vec![
+                self.config_home.join("elvish/rc.elv"),
+                self.home.join(".elvish/rc.elv"),
+            ],
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// How an installed completion script was found.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Installation {
+    /// A completion file of its own, such as `~/.config/fish/completions/rona.fish`
+    File { path: PathBuf, current: bool },
+    /// The script pasted into a startup file such as `~/.bashrc`
+    Embedded { path: PathBuf, current: bool },
+    /// A startup file running `rona completion <shell>`, which never goes stale
+    Generated { path: PathBuf },
+}
+
+impl Installation {
+    /// The file the script was found in.
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        match self {
+            Self::File { path, .. } | Self::Embedded { path, .. } | Self::Generated { path } => {
+                path
+            }
+        }
+    }
+
+    /// Whether the script matches the one the running rona generates.
+    #[must_use]
+    pub const fn is_current(&self) -> bool {
+        match self {
+            Self::File { current, .. } | Self::Embedded { current, .. } => *current,
+            Self::Generated { .. } => true,
+        }
+    }
+}
+
+/// A problem with the completion setup, with the command that fixes it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompletionFinding {
+    /// What is wrong
+    pub problem: String,
+    /// The shell command that fixes it, if there is one
+    pub fix: Option<String>,
+}
+
+/// A line of the generated script that identifies it inside a startup file.
+const fn script_marker(shell: Shell) -> Option<&'static str> {
+    match shell {
+        Shell::Bash => Some("complete -F _rona"),
+        Shell::Zsh => Some("compdef _rona rona"),
+        Shell::Fish => Some("complete -c rona"),
+        Shell::PowerShell => Some("-CommandName 'rona'"),
+        Shell::Elvish => Some("arg-completer[rona]"),
+        _ => None,
+    }
+}
+
+/// Finds every installed completion script for `shell`, comparing each with `script`,
+/// the script the running rona generates.
+#[must_use]
+pub fn find_installations(shell: Shell, dirs: &ShellDirs, script: &str) -> Vec<Installation> {
+    let script = script.trim();
+    let mut found: Vec<Installation> = dirs
+        .completion_files(shell)
+        .into_iter()
+        .filter_map(|path| {
+            let content = std::fs::read_to_string(&path).ok()?;
+            let current = content.trim() == script;
+            Some(Installation::File { path, current })
+        })
+        .collect();
+
+    let generator = format!("rona completion {shell}");
+    for path in dirs.startup_files(shell) {
+        let Ok(content) = std::fs::read_to_string(&path) else {
+            continue;
+        };
+        let generates = content
+            .lines()
+            .any(|line| !line.trim_start().starts_with('#') && line.contains(&generator));
+        if generates {
+            found.push(Installation::Generated { path });
+        } else if script_marker(shell).is_some_and(|marker| content.contains(marker)) {
+            let current = content.contains(script);
+            found.push(Installation::Embedded { path, current });
+        }
+    }
+    found
+}
+
+/// The line a startup file needs to generate the script on every start.
+const fn generator_line(shell: Shell) -> Option<&'static str> {
+    match shell {
+        Shell::Bash => Some(r#"eval "$(rona completion bash)""#),
+        Shell::Zsh => Some("source <(rona completion zsh)"),
+        Shell::Fish => Some("rona completion fish | source"),
+        Shell::PowerShell => Some("rona completion powershell | Out-String | Invoke-Expression"),
+        Shell::Elvish => Some("eval (rona completion elvish | slurp)"),
+        _ => None,
+    }
+}
+
+/// The command that installs the script where the shell loads it from.
+fn install_command(shell: Shell, dirs: &ShellDirs) -> Option<String> {
+    if shell == Shell::Fish {
+        return Some(format!(
+            "rona completion fish > {}",
+            dirs.config_home
+                .join("fish/completions/rona.fish")
+                .display()
+        ));
+    }
+    let startup = dirs.startup_files(shell).into_iter().next()?;
+    let line = generator_line(shell)?;
+    Some(format!("echo '{line}' >> {}", startup.display()))
+}
+
+/// Turns the installations found into findings.
+#[must_use]
+pub fn installation_findings(
+    shell: Shell,
+    dirs: &ShellDirs,
+    installations: &[Installation],
+) -> Vec<CompletionFinding> {
+    if installations.is_empty() {
+        return vec![CompletionFinding {
+            problem: format!("No {shell} completions are installed"),
+            fix: install_command(shell, dirs),
+        }];
+    }
+
+    installations
+        .iter()
+        .filter(|installation| !installation.is_current())
+        .map(|installation| match installation {
+            Installation::Embedded { path, .. } => CompletionFinding {
+                problem: format!(
+                    "{} contains completions from another rona version",
+                    path.display()
+                ),
+                fix: generator_line(shell).map(|line| {
+                    format!(
+                        "remove the pasted script from {}, then add: {line}",
+                        path.display()
+                    )
+                }),
+            },
+            other => CompletionFinding {
+                problem: format!("{} is from another rona version", other.path().display()),
+                fix: Some(format!(
+                    "rona completion {shell} > {}",
+                    other.path().display()
+                )),
+            },
+        })
+        .collect()
+}
+
+/// Runs `rona -l` the way the completions do, through `PATH` in the current
+/// directory, returning the number of files it lists.
+///
+/// # Errors
+/// * A finding when `rona` is not on `PATH` or the helper fails
+pub fn check_status_helper() -> std::result::Result<usize, CompletionFinding> {
+    let output = match Command::new("rona").arg("-l").output() {
+        Ok(output) => output,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            let fix = std::env::current_exe()
+                .ok()
+                .and_then(|exe| exe.parent().map(Path::to_path_buf))
+                .map(|dir| format!("export PATH=\"{}:$PATH\"", dir.display()));
+            return Err(CompletionFinding {
+                problem: "rona is not on PATH, so completions cannot run `rona -l`".to_string(),
+                fix,
+            });
+        }
+        Err(e) => {
+            return Err(CompletionFinding {
+                problem: format!("`rona -l` could not be run: {e}"),
+                fix: None,
+            });
+        }
+    };
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(CompletionFinding {
+            problem: format!(
+                "`rona -l` failed: {}",
+                stderr.lines().next().unwrap_or("no error output")
+            ),
+            fix: None,
+        });
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).lines().count())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use tempfile::TempDir;
+
+    use super::*;
+
+    const SCRIPT: &str = "_rona() {\n    :\n}\ncomplete -F _rona rona\n";
+
+    fn dirs(root: &Path) -> ShellDirs {
+        ShellDirs {
+            home: root.to_path_buf(),
+            config_home: root.join(".config"),
+            data_home: root.join(".local/share"),
+            zdotdir: root.to_path_buf(),
+        }
+    }
+
+    #[test]
+    fn test_missing_completions_suggest_install() {
+        let dirs = dirs(Path::new("/home/me"));
+        let findings = installation_findings(Shell::Fish, &dirs, &[]);
+        assert_eq!(
+            findings,
+            vec![CompletionFinding {
+                problem: "No fish completions are installed".to_string(),
+                fix: Some(
+                    "rona completion fish > /home/me/.config/fish/completions/rona.fish"
+                        .to_string()
+                ),
+            }]
+        );
+
+        let findings = installation_findings(Shell::Zsh, &dirs, &[]);
+        assert_eq!(
+            findings[0].fix.as_deref(),
+            Some("echo 'source <(rona completion zsh)' >> /home/me/.zshrc")
+        );
+    }
+
+    #[test]
+    fn test_find_installations() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let temp_dir = TempDir::new()?;
+        let dirs = dirs(temp_dir.path());
+
+        let file = temp_dir
+            .path()
+            .join(".local/share/bash-completion/completions/rona");
+        fs::create_dir_all(file.parent().ok_or("no parent")?)?;
+        fs::write(&file, SCRIPT)?;
+        fs::write(
+            temp_dir.path().join(".bashrc"),
+            "alias g=git\n_rona() {\n    old\n}\ncomplete -F _rona rona\n",
+        )?;
+
+        let found = find_installations(Shell::Bash, &dirs, SCRIPT);
+        assert_eq!(
+            found,
+            vec![
+                Installation::File {
+                    path: file,
+                    current: true
+                },
+                Installation::Embedded {
+                    path: temp_dir.path().join(".bashrc"),
+                    current: false
+                },
+            ]
+        );
+        let findings = installation_findings(Shell::Bash, &dirs, &found);
+        assert_eq!(findings.len(), 1);
+        assert!(findings[0].problem.contains("another rona version"));
+
+        fs::write(
+            temp_dir.path().join(".bashrc"),
+            "# eval \"$(rona completion bash)\"\neval \"$(rona completion bash)\"\n",
+        )?;
+        let found = find_installations(Shell::Bash, &dirs, SCRIPT);
+        assert!(found.iter().all(Installation::is_current));
+        assert!(matches!(found[1], Installation::Generated { .. }));
+
+        Ok(())
+    }
+}
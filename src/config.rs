@@ -21,19 +21,24 @@
 //! - Invalid configuration format
 //! - Home directory not found
 
-use dialoguer::FuzzySelect;
 use serde::{Deserialize, Serialize};
 use std::{
-    collections::HashSet,
+    collections::{BTreeMap, HashSet},
     env,
-    io::Write,
     path::{Path, PathBuf},
 };
 
 use crate::{
+    config_editor::set_file_value,
+    editor::fallback_editor,
     errors::{ConfigError, GitError, Result, RonaError},
-    git::get_top_level_path,
-    utils::print_error,
+    git::{
+        Comments, CommitNumberSource, Feature, branch::BranchNameFormat, common_staged_dir,
+        ensure_writable, get_all_staged_file_paths, get_top_level_path, remote_location,
+        remote_urls,
+    },
+    shell::Limits,
+    utils::{parse_duration, print_error},
 };
 
 /// Describes a configuration file source and its status
@@ -60,6 +65,36 @@ pub struct ConfigInfo {
     pub search_directory: PathBuf,
 }
 
+/// A config file merged into the effective configuration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigLayer {
+    /// Path to the config file
+    pub path: PathBuf,
+    /// Why the file is loaded, e.g. `global`, `project` or `extends`
+    pub origin: String,
+}
+
+impl ConfigLayer {
+    fn new(path: PathBuf, origin: impl Into<String>) -> Self {
+        Self {
+            path,
+            origin: origin.into(),
+        }
+    }
+}
+
+/// An effective config value and where it was set.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValueOrigin {
+    /// Dotted key, e.g. `commit_size.max_files`
+    pub key: String,
+    /// The value, as TOML
+    pub value: String,
+    /// The layer that set it (`project: ./.rona.toml`), `default`, an environment
+    /// variable (`env: $EDITOR`) or a command-line option (`cli: --lang`)
+    pub origin: String,
+}
+
 // Define your default commit types
 const DEFAULT_COMMIT_TYPES: &[&str] = &["feat", "fix", "docs", "test", "chore"];
 
@@ -80,6 +115,470 @@ pub struct ConfigOverride {
     pub config: String,
 }
 
+/// Features denied in matching repositories, declared as `[[guards]]` in the global
+/// config.
+///
+/// A guard with neither `path` nor `remote` matches every repository. Guards apply in
+/// order, so a later `allow` re-enables what an earlier guard denied.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct RepositoryGuard {
+    /// Glob pattern matched against the repository root, like the `path` of
+    /// `[[overrides]]`.
+    pub path: Option<String>,
+    /// Glob pattern matched against the URL of each remote, and its `host/path`
+    /// location (e.g. `github.com/acme/*`).
+    pub remote: Option<String>,
+    /// Features denied in matching repositories.
+    #[serde(default)]
+    pub deny: Vec<Feature>,
+    /// Features allowed again in matching repositories.
+    #[serde(default)]
+    pub allow: Vec<Feature>,
+}
+
+impl RepositoryGuard {
+    /// The guard as shown in error messages, e.g. `path = "~/oss/**"`.
+    fn describe(&self) -> String {
+        match (&self.path, &self.remote) {
+            (Some(path), Some(remote)) => format!("path = \"{path}\", remote = \"{remote}\""),
+            (Some(path), None) => format!("path = \"{path}\""),
+            (None, Some(remote)) => format!("remote = \"{remote}\""),
+            (None, None) => "without path or remote".to_string(),
+        }
+    }
+
+    /// Whether the guard applies to the repository at `root` with `remotes`.
+    fn matches(&self, root: &Path, remotes: &[String]) -> bool {
+        let path_matches = self
+            .path
+            .as_ref()
+            .is_none_or(|pattern| override_pattern_matches(pattern, root));
+        let remote_matches = self.remote.as_ref().is_none_or(|pattern| {
+            glob::Pattern::new(pattern).is_ok_and(|pattern| {
+                remotes.iter().any(|url| {
+                    pattern.matches(url)
+                        || remote_location(url).is_some_and(|location| pattern.matches(&location))
+                })
+            })
+        });
+        path_matches && remote_matches
+    }
+}
+
+/// Peeks at the `guards` key of a TOML config file without full deserialization.
+#[derive(Deserialize)]
+struct GuardsOnly {
+    #[serde(default)]
+    guards: Vec<RepositoryGuard>,
+}
+
+/// The features the `[[guards]]` of the global configs deny in the repository at
+/// `root`, each with the guard responsible.
+///
+/// Only global configs are read, so a repository cannot lift its own guards.
+///
+/// # Errors
+/// Returns `ConfigError::ParseError` if a global config is not valid TOML or a guard
+/// is malformed.
+pub fn denied_features(root: &Path) -> Result<Vec<(Feature, String)>> {
+    let home = dirs::home_dir().ok_or(ConfigError::ConfigNotFound)?;
+    let mut guards = Vec::new();
+    for path in [
+        home.join(".config/rona/config.toml"),
+        home.join(".config/rona.toml"),
+    ] {
+        let Ok(content) = std::fs::read_to_string(&path) else {
+            continue;
+        };
+        let parsed = toml::from_str::<GuardsOnly>(&content).map_err(|e| {
+            RonaError::Config(ConfigError::ParseError {
+                file: path.display().to_string(),
+                reason: e.to_string(),
+            })
+        })?;
+        guards.extend(parsed.guards);
+    }
+
+    let remotes = if guards.iter().any(|guard| guard.remote.is_some()) {
+        remote_urls()
+    } else {
+        Vec::new()
+    };
+
+    Ok(apply_guards(&guards, root, &remotes))
+}
+
+/// Applies `guards` in order to the repository at `root` with `remotes`.
+fn apply_guards(
+    guards: &[RepositoryGuard],
+    root: &Path,
+    remotes: &[String],
+) -> Vec<(Feature, String)> {
+    let mut denied: Vec<(Feature, String)> = Vec::new();
+    for guard in guards.iter().filter(|guard| guard.matches(root, remotes)) {
+        denied
+            .retain(|(feature, _)| !guard.allow.contains(feature) && !guard.deny.contains(feature));
+        denied.extend(
+            guard
+                .deny
+                .iter()
+                .map(|feature| (*feature, guard.describe())),
+        );
+    }
+    denied
+}
+
+/// Limits on the size of a commit, declared as `[commit_size]`.
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+pub struct CommitSizeConfig {
+    /// Warn when more files than this are staged.
+    pub max_files: Option<usize>,
+    /// Warn when the staged changes add more lines than this.
+    pub max_insertions: Option<usize>,
+    /// Ask for confirmation instead of only warning. Default: `false`.
+    #[serde(default)]
+    pub confirm: bool,
+    /// Glob patterns of files not counted, e.g. generated code or lock files.
+    #[serde(default)]
+    pub exempt: Vec<String>,
+}
+
+/// What `rona commit` does about TODO markers added by the staged changes.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TodoAction {
+    /// Commit as usual
+    #[default]
+    Ignore,
+    /// Append a `TODOs:` section listing them to the commit body
+    Append,
+    /// Refuse to commit (`--no-verify` commits anyway)
+    Block,
+}
+
+/// Staged TODO marker settings, declared as `[todo]`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct TodoConfig {
+    /// Words searched for on added lines. Default: `TODO`, `FIXME`, `HACK`.
+    #[serde(default = "default_todo_markers")]
+    pub markers: Vec<String>,
+    /// What `rona commit` does when markers are found. Default: `ignore`.
+    #[serde(default)]
+    pub on_commit: TodoAction,
+}
+
+impl Default for TodoConfig {
+    fn default() -> Self {
+        Self {
+            markers: default_todo_markers(),
+            on_commit: TodoAction::default(),
+        }
+    }
+}
+
+fn default_todo_markers() -> Vec<String> {
+    crate::git::todo::DEFAULT_TODO_MARKERS
+        .iter()
+        .map(ToString::to_string)
+        .collect()
+}
+
+/// Patch series export settings for `rona format-patch`, declared as `[format_patch]`.
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+pub struct FormatPatchConfig {
+    /// Directory the patches are written to. Default: `patches`.
+    pub output_dir: Option<String>,
+    /// Cover letter template: the first line is the subject, the rest the blurb.
+    /// Available variables: `{branch}`, `{count}`, `{subjects}`, `{changes}`, `{author}`.
+    pub cover_letter: Option<String>,
+}
+
+/// A commit type of `commit_types`.
+///
+/// Written as a plain name (`"feat"`) or as a table adding what the type selector shows:
+/// `{ name = "feat", emoji = "✨", description = "A new feature" }`, or `[[commit_types]]`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(from = "CommitTypeEntry", into = "CommitTypeEntry")]
+pub struct CommitType {
+    /// The type name, e.g. `feat`
+    pub name: String,
+    /// Shown before the type in the selector and exposed as `{commit_emoji}`
+    pub emoji: Option<String>,
+    /// Shown after the type in the selector
+    pub description: Option<String>,
+}
+
+impl CommitType {
+    /// The type name, for call sites mapping a list of types to names.
+    #[must_use]
+    pub const fn name(&self) -> &str {
+        self.name.as_str()
+    }
+}
+
+impl From<&str> for CommitType {
+    fn from(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            emoji: None,
+            description: None,
+        }
+    }
+}
+
+/// How a [`CommitType`] is written: a plain name when it has nothing else, else a table.
+#[derive(Deserialize, Serialize)]
+#[serde(untagged)]
+enum CommitTypeEntry {
+    Name(String),
+    Table {
+        name: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        emoji: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        description: Option<String>,
+    },
+}
+
+impl From<CommitTypeEntry> for CommitType {
+    fn from(entry: CommitTypeEntry) -> Self {
+        match entry {
+            CommitTypeEntry::Name(name) => Self::from(name.as_str()),
+            CommitTypeEntry::Table {
+                name,
+                emoji,
+                description,
+            } => Self {
+                name,
+                emoji,
+                description,
+            },
+        }
+    }
+}
+
+impl From<CommitType> for CommitTypeEntry {
+    fn from(commit_type: CommitType) -> Self {
+        if commit_type.emoji.is_none() && commit_type.description.is_none() {
+            Self::Name(commit_type.name)
+        } else {
+            Self::Table {
+                name: commit_type.name,
+                emoji: commit_type.emoji,
+                description: commit_type.description,
+            }
+        }
+    }
+}
+
+/// Where `rona push --review` pushes, declared as `[review]`.
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+pub struct ReviewConfig {
+    /// Ref template the current branch is pushed to, e.g. `refs/reviews/{user}/{branch}`.
+    /// Available variables: `{branch}`, `{target}`, `{user}`. Default: `refs/for/{target}`.
+    #[serde(rename = "ref")]
+    pub ref_template: Option<String>,
+    /// Remote pushed to when the command line names none. Default: `origin`.
+    pub remote: Option<String>,
+}
+
+/// Version tags and messages of `rona release`, declared as `[release]`.
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+pub struct ReleaseConfig {
+    /// Prefix of version tags, e.g. `release-`. Default: `v`.
+    pub tag_prefix: Option<String>,
+    /// Template of the release commit and tag message. Available variables:
+    /// `{version}`, `{tag}`, `{channel}`, `{previous}`, `{package}`. Default:
+    /// `Release {version}`, preceded by the package name when releasing a package.
+    pub message: Option<String>,
+}
+
+/// Where `rona handoff` pushes work in progress, declared as `[handoff]`.
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+pub struct HandoffConfig {
+    /// Remote handoffs are pushed to and pulled from. Default: `origin`.
+    pub remote: Option<String>,
+    /// Ref namespace of handoffs, one ref per branch. Default: `refs/rona/handoff`.
+    pub namespace: Option<String>,
+}
+
+/// Settings of `rona daemon`, declared as `[daemon]`.
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+pub struct DaemonConfig {
+    /// The longest the cached status goes without a refresh, e.g. `"10s"` (units: `s`,
+    /// `m`, `h`, `d`). Working tree edits can show up that late. Default: `2s`.
+    pub refresh_interval: Option<String>,
+}
+
+#[cfg(unix)]
+impl DaemonConfig {
+    /// The refresh interval of the daemon, [`crate::git::daemon::REFRESH_INTERVAL`]
+    /// when not set.
+    ///
+    /// # Errors
+    /// * If `refresh_interval` is not a valid duration
+    pub fn refresh_interval(&self) -> Result<std::time::Duration> {
+        use crate::git::daemon::REFRESH_INTERVAL;
+
+        self.refresh_interval
+            .as_deref()
+            .map_or(Ok(REFRESH_INTERVAL), |value| {
+                parse_duration(value)
+                    .map_err(|e| RonaError::InvalidInput(format!("[daemon] refresh_interval: {e}")))
+            })
+    }
+}
+
+/// Where `rona config sync` reads the team's canonical config, declared as
+/// `[config_sync]`. Without `url`, it is read from a branch of a remote.
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+pub struct ConfigSyncConfig {
+    /// URL the canonical `.rona.toml` is downloaded from, e.g. a raw file on a forge
+    pub url: Option<String>,
+    /// Remote name or repository URL of the branch. Default: `origin`.
+    pub remote: Option<String>,
+    /// Branch holding the canonical config. Default: `rona-config`.
+    pub branch: Option<String>,
+    /// Path of the config in the branch. Default: `.rona.toml`.
+    pub path: Option<String>,
+}
+
+/// Formats of `rona copy`, declared as `[copy]`.
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+pub struct CopyConfig {
+    /// Format used without `--format`: a format name or a template. Default: `reference`.
+    pub format: Option<String>,
+    /// Named format templates, e.g. `md = "[{short_sha}]: {subject}"`. Available
+    /// variables: `{sha}`, `{short_sha}`, `{subject}`, `{body}`, `{author}`, `{email}`,
+    /// `{date}`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub formats: BTreeMap<String, String>,
+}
+
+/// A package of a monorepo, released on its own, declared as `[packages.<name>]`.
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+pub struct PackageConfig {
+    /// Directory of the package, from the repository root, e.g. `packages/api`
+    pub path: String,
+    /// Prefix of the package's version tags. Default: `<name>/v`.
+    pub tag_prefix: Option<String>,
+}
+
+/// Changelog fragments written by `rona fragment add`, declared as `[fragments]`.
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+pub struct FragmentsConfig {
+    /// Directory of the fragments, from the repository root. Default: `changelog.d`.
+    pub dir: Option<String>,
+    /// Changelog `rona fragment build` adds releases to. Default: `CHANGELOG.md`.
+    pub changelog: Option<String>,
+    /// Section heading of each type, e.g. `feat = "Features"`. Default: the type.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub titles: BTreeMap<String, String>,
+}
+
+/// Commit template and date formats of one language, declared as
+/// `[templates.locales.<lang>]` and selected with `lang` or `--lang`. Keys that are set
+/// replace the top-level ones.
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+pub struct LocaleTemplates {
+    /// Replaces `commit_template`.
+    pub commit_template: Option<String>,
+    /// Replaces `date_format`.
+    pub date_format: Option<String>,
+    /// Replaces `time_format`.
+    pub time_format: Option<String>,
+}
+
+/// `[templates]`: the named commit templates, and the tables of the languages under
+/// `[templates.locales]`.
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+pub struct Templates {
+    /// `[templates.locales.<lang>]` tables, selected with `lang` or `--lang`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub locales: BTreeMap<String, LocaleTemplates>,
+    /// Commit templates selected with `--template <name>`, e.g. `release = "..."`.
+    /// The one named `default` replaces `commit_template`.
+    #[serde(flatten, deserialize_with = "named_templates")]
+    pub named: BTreeMap<String, String>,
+}
+
+/// Deserializes the named templates of `[templates]`, pointing a table that is not
+/// `locales` to `[templates.locales]`.
+fn named_templates<'de, D>(
+    deserializer: D,
+) -> std::result::Result<BTreeMap<String, String>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    BTreeMap::<String, toml::Value>::deserialize(deserializer)?
+        .into_iter()
+        .map(|(name, value)| match value {
+            toml::Value::String(template) => Ok((name, template)),
+            toml::Value::Table(_) => Err(serde::de::Error::custom(format!(
+                "[templates.{name}] is a table: language tables go under [templates.locales.{name}]"
+            ))),
+            _ => Err(serde::de::Error::custom(format!(
+                "templates.{name} must be a string"
+            ))),
+        })
+        .collect()
+}
+
+impl Templates {
+    /// Whether no named template nor language is declared.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.locales.is_empty() && self.named.is_empty()
+    }
+}
+
+/// The name of the `[templates]` entry used when no `--template` is given.
+pub const DEFAULT_TEMPLATE_NAME: &str = "default";
+
+/// Commands run before every push, declared as `[pre_push]`.
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+pub struct PrePushConfig {
+    /// Shell commands run in order from the repository root. A failing command
+    /// aborts the push.
+    #[serde(default)]
+    pub commands: Vec<String>,
+
+    /// Time limit of each command, e.g. `"5m"` (units: `s`, `m`, `h`, `d`). A command
+    /// still running is killed and the push aborted. No limit when absent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<String>,
+
+    /// The environment variables the commands see, e.g. `["PATH", "HOME"]`; the others
+    /// are cleared. All of them when absent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pass_env: Option<Vec<String>>,
+
+    /// Captures the output of each command and shows only its last `output_lines`
+    /// lines when it fails. Streamed as it comes when absent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output_lines: Option<usize>,
+}
+
+impl PrePushConfig {
+    /// The limits the commands run under.
+    ///
+    /// # Errors
+    /// * If `timeout` is not a valid duration
+    pub fn limits(&self) -> Result<Limits> {
+        let timeout = self
+            .timeout
+            .as_deref()
+            .map(parse_duration)
+            .transpose()
+            .map_err(|e| RonaError::InvalidInput(format!("[pre_push] timeout: {e}")))?;
+        Ok(Limits {
+            timeout,
+            pass_env: self.pass_env.clone(),
+            output_lines: self.output_lines,
+        })
+    }
+}
+
 /// Expands a leading `~/` to the user's home directory.
 fn expand_tilde(value: &str) -> String {
     value.strip_prefix("~/").map_or_else(
@@ -214,41 +713,107 @@ fn collect_override_sources(
 /// Builds the ordered list of config files to merge for `dir`, base-first.
 /// Global configs come first, then any matching `[[overrides]]` targets,
 /// then the project `.rona.toml` with its `extends` chain.
-fn config_paths_for_dir(dir: &Path) -> Result<Vec<PathBuf>> {
+fn config_layers_for_dir(dir: &Path) -> Result<Vec<ConfigLayer>> {
     let home = dirs::home_dir().ok_or(ConfigError::ConfigNotFound)?;
     let old_global = home.join(".config/rona/config.toml");
     let new_global = home.join(".config/rona.toml");
 
-    let globals: Vec<PathBuf> = [old_global, new_global]
+    let mut layers: Vec<ConfigLayer> = [(old_global, "legacy global"), (new_global, "global")]
         .into_iter()
-        .filter(|p| p.exists())
+        .filter(|(path, _)| path.exists())
+        .map(|(path, origin)| ConfigLayer::new(path, origin))
         .collect();
 
-    let mut paths = globals.clone();
-    paths.extend(
+    let globals = layer_paths(&layers);
+    layers.extend(
         collect_override_sources(&globals, dir)?
             .into_iter()
-            .map(|source| source.path),
+            .map(|source| {
+                ConfigLayer::new(
+                    source.path,
+                    format!("override (path = \"{}\")", source.pattern),
+                )
+            }),
     );
 
     let project_config_path = dir.join(".rona.toml");
     if project_config_path.exists() {
         let mut visited = HashSet::new();
-        paths.extend(collect_extends_chain(&project_config_path, &mut visited)?);
-        paths.push(project_config_path);
+        layers.extend(
+            collect_extends_chain(&project_config_path, &mut visited)?
+                .into_iter()
+                .map(|path| ConfigLayer::new(path, "extends")),
+        );
+        layers.push(ConfigLayer::new(project_config_path, "project"));
     }
 
+    Ok(layers)
+}
+
+/// The config file given with `--config-file`, after its `extends` chain.
+///
+/// # Errors
+/// * If the file does not exist
+/// * If its `extends` chain is broken or circular
+fn file_layers(path: &Path) -> Result<Vec<ConfigLayer>> {
+    if !path.exists() {
+        return Err(ConfigError::ConfigNotFound.into());
+    }
+
+    let abs_path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+
+    let mut visited = HashSet::new();
+    let mut layers: Vec<ConfigLayer> = collect_extends_chain(&abs_path, &mut visited)?
+        .into_iter()
+        .map(|path| ConfigLayer::new(path, "extends"))
+        .collect();
+    layers.push(ConfigLayer::new(abs_path, "config file"));
+    Ok(layers)
+}
+
+/// The paths of `layers`, in loading order.
+fn layer_paths(layers: &[ConfigLayer]) -> Vec<PathBuf> {
+    layers.iter().map(|layer| layer.path.clone()).collect()
+}
+
+/// Collects the package `.rona.toml` files that apply to the staged files, base-first.
+///
+/// Walks from the repository root down to the deepest directory containing every
+/// staged path. Each `.rona.toml` on the way below the root is layered in with its
+/// `extends` chain, so deeper packages override shallower ones. Files already in
+/// `loaded` (the configs for the current directory) are not layered twice.
+fn package_config_paths(
+    repo_root: &Path,
+    staged: &[String],
+    loaded: &[PathBuf],
+) -> Result<Vec<PathBuf>> {
+    let canonical = |path: &Path| path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    let loaded: HashSet<PathBuf> = loaded.iter().map(|p| canonical(p)).collect();
+
+    let mut paths = Vec::new();
+    let mut dir = repo_root.to_path_buf();
+    for component in common_staged_dir(staged).components() {
+        dir.push(component);
+        let package_config = dir.join(".rona.toml");
+        if !package_config.exists() || loaded.contains(&canonical(&package_config)) {
+            continue;
+        }
+        paths.extend(collect_extends_chain(&package_config, &mut HashSet::new())?);
+        paths.push(package_config);
+    }
     Ok(paths)
 }
 
 /// Project-specific configuration that can be defined in rona.toml
 #[derive(Debug, Deserialize, Serialize, Clone)]
+#[allow(clippy::struct_excessive_bools)]
 pub struct ProjectConfig {
     /// Editor command to use for commit messages
     pub editor: Option<String>,
 
-    /// Custom commit types for this project
-    pub commit_types: Option<Vec<String>>,
+    /// Custom commit types for this project, as names or tables with an emoji and a
+    /// description (see [`CommitType`])
+    pub commit_types: Option<Vec<CommitType>>,
 
     /// Template for interactive commit message generation
     /// Available variables: {`commit_number`}, {`commit_type`}, {`branch_name`}, {`message`}, {`date`}, {`time`}, {`author`}, {`email`}
@@ -268,10 +833,57 @@ pub struct ProjectConfig {
     pub commit_fields_order: Vec<String>,
 
     /// Template for branch name generation.
-    /// Available variables: `{commit_type}`, `{description}`, `{date}`, `{time}`, `{author}`.
+    /// Available variables: `{branch_type}` (or `{type}`), `{description}`, `{slug}`,
+    /// `{ticket}`, `{scope}`, `{date}`, `{time}`, `{author}`.
     /// Extra field names defined in `branch_extra_fields` are also available.
     pub branch_template: Option<String>,
 
+    /// Regex new branch names must match, checked by `rona branch` and `rona branch new`.
+    pub branch_pattern: Option<String>,
+
+    /// Regex finding the ticket in the current branch name, available as `{ticket}` in
+    /// commit templates, e.g. `[A-Z]+-\d+`. The first capture group is used when the
+    /// regex has one, else the whole match.
+    pub ticket_pattern: Option<String>,
+
+    /// When `true`, a template variable that is not defined (e.g. a typo such as
+    /// `{commit_typ}`) is an error instead of an empty string. Default: `false`.
+    #[serde(default)]
+    pub strict_templates: bool,
+
+    /// When `true`, `rona generate` refuses to run with nothing staged and asks what to
+    /// do with a message in `commit_message.md` that was not committed yet, instead of
+    /// overwriting it. Default: `false`.
+    #[serde(default)]
+    pub check_before_generate: bool,
+
+    /// When `true`, `rona push` and `rona commit --push` fetch the branch they update
+    /// and warn when it has commits `HEAD` does not, asking before a force push would
+    /// discard them. Default: `false`.
+    #[serde(default)]
+    pub check_before_push: bool,
+
+    /// chrono format of the `{date}` commit template variable, e.g. `"%d/%m/%Y"`.
+    /// Default: `"%Y-%m-%d"`.
+    pub date_format: Option<String>,
+
+    /// chrono format of the `{time}` commit template variable, e.g. `"%Hh%M"`.
+    /// Default: `"%H:%M:%S"`.
+    pub time_format: Option<String>,
+
+    /// Timezone of the `{date}` and `{time}` commit template variables: `"UTC"`,
+    /// `"local"` or a fixed offset such as `"+02:00"`. Default: `"local"`.
+    pub timezone: Option<String>,
+
+    /// Language of the commit messages: a key of `[templates.locales]` whose commit
+    /// template and date formats are used. Overridden by `--lang`.
+    pub lang: Option<String>,
+
+    /// Named commit templates (`release = "..."`) and per-language commit templates
+    /// and date formats (`[templates.locales.fr]`).
+    #[serde(default, skip_serializing_if = "Templates::is_empty")]
+    pub templates: Templates,
+
     /// Extra fields to prompt when generating a branch name.
     /// Each field becomes a template variable with the field's `name`.
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
@@ -307,6 +919,164 @@ pub struct ProjectConfig {
     /// Set `disabled = true` to skip the prompt (the `{description}` variable will be empty).
     pub branch_description: Option<crate::extra_fields::BuiltInFieldConfig>,
 
+    /// Reusable message body text, by name. Snippet text may use the commit template
+    /// variables. Inserted with `/name` in the interactive body prompt or with
+    /// `rona snippet insert <name>`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub snippets: BTreeMap<String, String>,
+
+    /// Color of each commit type in selectors, `rona browse` and `rona show`, by type
+    /// name: a color name such as `green` or `bright blue`, or `#rrggbb`. Types that
+    /// are not listed keep a built-in color.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub type_colors: BTreeMap<String, String>,
+
+    /// Line written for each file in a generated `commit_message.md`.
+    /// `{file}` is the path; `{placeholder}` is empty for changed files and `deleted`
+    /// for deleted ones. Defaults to ``"- `{file}`: {placeholder}"``.
+    pub file_bullet: Option<String>,
+
+    /// When `true`, file bullets are listed one per line, without the empty
+    /// description line under each changed file. Default: `false`.
+    #[serde(default)]
+    pub file_bullet_compact: bool,
+
+    /// When `true`, file bullets are written as Markdown checkboxes (`- [ ] ...`).
+    /// Default: `false`.
+    #[serde(default)]
+    pub file_bullet_checkbox: bool,
+
+    /// When `true`, each file bullet is followed by a comment summarizing the staged
+    /// change: net lines and the functions added, removed or changed. Turns on
+    /// `strip_comments`. Default: `false`.
+    #[serde(default)]
+    pub file_bullet_annotate: bool,
+
+    /// When `true`, lines of `commit_message.md` starting with git's comment character
+    /// (`core.commentChar`, `#` by default) are dropped before committing, like git does
+    /// for a message written in its editor. Everything below the scissors line is
+    /// dropped either way. Default: `false`.
+    #[serde(default)]
+    pub strip_comments: bool,
+
+    /// Size limits checked before `rona commit` (`[commit_size]`).
+    #[serde(default)]
+    pub commit_size: CommitSizeConfig,
+
+    /// What `{commit_number}` counts: `head-count` (default), `remote`, which counts
+    /// the fetched remote branch so concurrent contributors do not share a number,
+    /// `branch-only`, the commits not on the default branch, or `per-type`, the
+    /// commits of the same commit type.
+    #[serde(default)]
+    pub commit_number_source: CommitNumberSource,
+
+    /// When `true`, `rona commit` appends a Gerrit `Change-Id:` trailer to messages
+    /// that have none, reusing the id of the amended commit with `--amend`.
+    /// Default: `false`.
+    #[serde(default)]
+    pub change_id: bool,
+
+    /// When `true`, commits must be signed: `rona commit` fails instead of warning
+    /// when no signing key is configured, and `rona push` refuses to push commits
+    /// without a good signature. Default: `false`.
+    #[serde(default)]
+    pub require_signed: bool,
+
+    /// How the `{branch_name}` template variable is rendered (`[branch_name]`).
+    #[serde(default)]
+    pub branch_name: BranchNameFormat,
+
+    /// Patch series export settings for `rona format-patch` (`[format_patch]`).
+    #[serde(default)]
+    pub format_patch: FormatPatchConfig,
+
+    /// TODO markers reported by `rona todo` and checked by `rona commit` (`[todo]`).
+    #[serde(default)]
+    pub todo: TodoConfig,
+
+    /// Commands run before `rona push` and `rona commit --push` push (skipped with
+    /// `--no-verify`).
+    #[serde(default)]
+    pub pre_push: PrePushConfig,
+
+    /// Target of `rona push --review` (`[review]`).
+    #[serde(default)]
+    pub review: ReviewConfig,
+
+    /// Version tags of `rona release` (`[release]`).
+    #[serde(default)]
+    pub release: ReleaseConfig,
+
+    /// Settings of `rona daemon` (`[daemon]`).
+    #[serde(default)]
+    pub daemon: DaemonConfig,
+
+    /// Work in progress handed off between clones with `rona handoff` (`[handoff]`).
+    #[serde(default)]
+    pub handoff: HandoffConfig,
+
+    /// Source of the team's canonical config for `rona config sync` (`[config_sync]`).
+    #[serde(default)]
+    pub config_sync: ConfigSyncConfig,
+
+    /// Commit reference formats of `rona copy` (`[copy]`).
+    #[serde(default)]
+    pub copy: CopyConfig,
+
+    /// Changelog fragments (`[fragments]`).
+    #[serde(default)]
+    pub fragments: FragmentsConfig,
+
+    /// Monorepo packages released on their own with `rona release --package`, by name
+    /// (`[packages.<name>]`).
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub packages: BTreeMap<String, PackageConfig>,
+
+    /// Directories of a monorepo that `--scope <name>` narrows status, staging and
+    /// message generation to, by scope name (`[scopes]`), e.g. `api = "packages/api"`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub scopes: BTreeMap<String, String>,
+
+    /// Impact labels of the areas a commit touches, by path glob (`[impact]`), e.g.
+    /// `"src/git/**" = "core"`. Shown by `rona generate` and exposed as `{impact}`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub impact: BTreeMap<String, String>,
+
+    /// Notifications sent after a successful push or release (`[notify.webhook]`).
+    #[serde(default)]
+    pub notify: crate::notify::NotifyConfig,
+
+    /// Issue tracker commands run after a successful push (`[tracker]`).
+    #[serde(default)]
+    pub tracker: crate::tracker::TrackerConfig,
+
+    /// Remote URL prefixes and their replacements, like git's `url.<base>.insteadOf`,
+    /// applied when rona resolves a remote for push summaries, compare links and
+    /// pull requests. The longest matching prefix wins.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub url_rewrites: BTreeMap<String, String>,
+
+    /// Hosts or `host/owner` prefixes the push remote is expected to point to, e.g.
+    /// `["github.com/acme"]`. Pushing elsewhere prints a warning. Usually set per
+    /// directory tree through `[[overrides]]`. No check when empty.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub expected_remotes: Vec<String>,
+
+    /// Minimum interval between background `git fetch` runs started by status
+    /// commands, e.g. `"15m"` (units: `s`, `m`, `h`, `d`). Disabled when absent.
+    pub auto_fetch: Option<String>,
+
+    /// Built-in exclusion presets, e.g. `["node", "rust"]`. Their build output is never
+    /// staged by `rona -a`, and `rona init --repo` writes their patterns to
+    /// `.commitignore`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub preset: Vec<String>,
+
+    /// Plain ASCII labels instead of glyphs, no colors, and numbered lists instead of
+    /// fuzzy selectors, for screen readers. Default: `false`.
+    #[serde(default)]
+    pub accessibility: bool,
+
     /// Path-conditional config layers. Declared as `[[overrides]]`, typically in the
     /// global config, so that running rona under a given directory tree layers in
     /// another config file.
@@ -321,7 +1091,7 @@ impl Default for ProjectConfig {
             commit_types: Some(
                 DEFAULT_COMMIT_TYPES
                     .iter()
-                    .map(std::string::ToString::to_string)
+                    .map(|name| CommitType::from(*name))
                     .collect(),
             ),
             commit_template: Some(
@@ -337,6 +1107,48 @@ impl Default for ProjectConfig {
             message_prefetch: None,
             commit_message: None,
             branch_description: None,
+            branch_pattern: None,
+            ticket_pattern: None,
+            strict_templates: false,
+            date_format: None,
+            time_format: None,
+            timezone: None,
+            check_before_generate: false,
+            check_before_push: false,
+            lang: None,
+            templates: Templates::default(),
+            snippets: BTreeMap::new(),
+            type_colors: BTreeMap::new(),
+            file_bullet: None,
+            file_bullet_compact: false,
+            file_bullet_checkbox: false,
+            file_bullet_annotate: false,
+            strip_comments: false,
+            commit_size: CommitSizeConfig::default(),
+            commit_number_source: CommitNumberSource::default(),
+            change_id: false,
+            require_signed: false,
+            branch_name: BranchNameFormat::default(),
+            format_patch: FormatPatchConfig::default(),
+            todo: TodoConfig::default(),
+            pre_push: PrePushConfig::default(),
+            review: ReviewConfig::default(),
+            release: ReleaseConfig::default(),
+            daemon: DaemonConfig::default(),
+            handoff: HandoffConfig::default(),
+            config_sync: ConfigSyncConfig::default(),
+            copy: CopyConfig::default(),
+            fragments: FragmentsConfig::default(),
+            packages: BTreeMap::new(),
+            scopes: BTreeMap::new(),
+            impact: BTreeMap::new(),
+            notify: crate::notify::NotifyConfig::default(),
+            tracker: crate::tracker::TrackerConfig::default(),
+            url_rewrites: BTreeMap::new(),
+            expected_remotes: vec![],
+            auto_fetch: None,
+            preset: vec![],
+            accessibility: false,
             overrides: vec![],
         }
     }
@@ -348,7 +1160,7 @@ impl Default for ProjectConfig {
 #[derive(serde::Deserialize, Default)]
 struct RawProjectConfig {
     editor: Option<String>,
-    commit_types: Option<Vec<String>>,
+    commit_types: Option<Vec<CommitType>>,
     commit_template: Option<String>,
     template: Option<String>,
     commit_extra_fields: Option<Vec<crate::extra_fields::ExtraField>>,
@@ -365,6 +1177,48 @@ struct RawProjectConfig {
     message_prefetch: Option<crate::extra_fields::MessagePrefetchConfig>,
     commit_message: Option<crate::extra_fields::BuiltInFieldConfig>,
     branch_description: Option<crate::extra_fields::BuiltInFieldConfig>,
+    branch_pattern: Option<String>,
+    ticket_pattern: Option<String>,
+    strict_templates: Option<bool>,
+    date_format: Option<String>,
+    time_format: Option<String>,
+    timezone: Option<String>,
+    check_before_generate: Option<bool>,
+    check_before_push: Option<bool>,
+    lang: Option<String>,
+    templates: Option<Templates>,
+    snippets: Option<BTreeMap<String, String>>,
+    type_colors: Option<BTreeMap<String, String>>,
+    file_bullet: Option<String>,
+    file_bullet_compact: Option<bool>,
+    file_bullet_checkbox: Option<bool>,
+    file_bullet_annotate: Option<bool>,
+    strip_comments: Option<bool>,
+    commit_size: Option<CommitSizeConfig>,
+    commit_number_source: Option<CommitNumberSource>,
+    change_id: Option<bool>,
+    require_signed: Option<bool>,
+    branch_name: Option<BranchNameFormat>,
+    format_patch: Option<FormatPatchConfig>,
+    todo: Option<TodoConfig>,
+    pre_push: Option<PrePushConfig>,
+    review: Option<ReviewConfig>,
+    release: Option<ReleaseConfig>,
+    daemon: Option<DaemonConfig>,
+    handoff: Option<HandoffConfig>,
+    config_sync: Option<ConfigSyncConfig>,
+    copy: Option<CopyConfig>,
+    fragments: Option<FragmentsConfig>,
+    packages: Option<BTreeMap<String, PackageConfig>>,
+    scopes: Option<BTreeMap<String, String>>,
+    impact: Option<BTreeMap<String, String>>,
+    notify: Option<crate::notify::NotifyConfig>,
+    tracker: Option<crate::tracker::TrackerConfig>,
+    url_rewrites: Option<BTreeMap<String, String>>,
+    expected_remotes: Option<Vec<String>>,
+    auto_fetch: Option<String>,
+    preset: Option<Vec<String>>,
+    accessibility: Option<bool>,
     overrides: Option<Vec<ConfigOverride>>,
 }
 
@@ -384,6 +1238,48 @@ impl From<RawProjectConfig> for ProjectConfig {
             message_prefetch: raw.message_prefetch,
             commit_message: raw.commit_message,
             branch_description: raw.branch_description,
+            branch_pattern: raw.branch_pattern,
+            ticket_pattern: raw.ticket_pattern,
+            strict_templates: raw.strict_templates.unwrap_or(false),
+            date_format: raw.date_format,
+            time_format: raw.time_format,
+            timezone: raw.timezone,
+            check_before_generate: raw.check_before_generate.unwrap_or(false),
+            check_before_push: raw.check_before_push.unwrap_or(false),
+            lang: raw.lang,
+            templates: raw.templates.unwrap_or_default(),
+            snippets: raw.snippets.unwrap_or_default(),
+            type_colors: raw.type_colors.unwrap_or_default(),
+            file_bullet: raw.file_bullet,
+            file_bullet_compact: raw.file_bullet_compact.unwrap_or(false),
+            file_bullet_checkbox: raw.file_bullet_checkbox.unwrap_or(false),
+            file_bullet_annotate: raw.file_bullet_annotate.unwrap_or(false),
+            strip_comments: raw.strip_comments.unwrap_or(false),
+            commit_size: raw.commit_size.unwrap_or_default(),
+            commit_number_source: raw.commit_number_source.unwrap_or_default(),
+            change_id: raw.change_id.unwrap_or(false),
+            require_signed: raw.require_signed.unwrap_or(false),
+            branch_name: raw.branch_name.unwrap_or_default(),
+            format_patch: raw.format_patch.unwrap_or_default(),
+            todo: raw.todo.unwrap_or_default(),
+            pre_push: raw.pre_push.unwrap_or_default(),
+            review: raw.review.unwrap_or_default(),
+            release: raw.release.unwrap_or_default(),
+            daemon: raw.daemon.unwrap_or_default(),
+            handoff: raw.handoff.unwrap_or_default(),
+            config_sync: raw.config_sync.unwrap_or_default(),
+            copy: raw.copy.unwrap_or_default(),
+            fragments: raw.fragments.unwrap_or_default(),
+            packages: raw.packages.unwrap_or_default(),
+            scopes: raw.scopes.unwrap_or_default(),
+            impact: raw.impact.unwrap_or_default(),
+            notify: raw.notify.unwrap_or_default(),
+            tracker: raw.tracker.unwrap_or_default(),
+            url_rewrites: raw.url_rewrites.unwrap_or_default(),
+            expected_remotes: raw.expected_remotes.unwrap_or_default(),
+            auto_fetch: raw.auto_fetch,
+            preset: raw.preset.unwrap_or_default(),
+            accessibility: raw.accessibility.unwrap_or(false),
             overrides: raw.overrides.unwrap_or_default(),
         }
     }
@@ -431,8 +1327,37 @@ fn merge_named_fields(
     }
 }
 
+/// Merges two tables keyed by name: the child's entries override the base's.
+fn merge_maps<T>(
+    base: Option<BTreeMap<String, T>>,
+    child: Option<BTreeMap<String, T>>,
+) -> Option<BTreeMap<String, T>> {
+    match (base, child) {
+        (Some(mut base), Some(child)) => {
+            base.extend(child);
+            Some(base)
+        }
+        (base, child) => child.or(base),
+    }
+}
+
+/// Merges two `[templates]` tables: the child's named templates and languages override
+/// the base's.
+fn merge_templates(base: Option<Templates>, child: Option<Templates>) -> Option<Templates> {
+    match (base, child) {
+        (Some(mut base), Some(child)) => {
+            base.locales.extend(child.locales);
+            base.named.extend(child.named);
+            Some(base)
+        }
+        (base, child) => child.or(base),
+    }
+}
+
 /// Merges two raw configs: scalars use last-wins (child overrides base),
-/// array fields (`commit_extra_fields`, `branch_extra_fields`) are merged by name.
+/// array fields (`commit_extra_fields`, `branch_extra_fields`), `snippets`,
+/// `templates`, `type_colors`, `url_rewrites`, `packages`, `scopes` and `impact` are
+/// merged by name.
 fn merge_raw(base: RawProjectConfig, child: RawProjectConfig) -> RawProjectConfig {
     RawProjectConfig {
         editor: child.editor.or(base.editor),
@@ -459,6 +1384,48 @@ fn merge_raw(base: RawProjectConfig, child: RawProjectConfig) -> RawProjectConfi
         message_prefetch: child.message_prefetch.or(base.message_prefetch),
         commit_message: child.commit_message.or(base.commit_message),
         branch_description: child.branch_description.or(base.branch_description),
+        branch_pattern: child.branch_pattern.or(base.branch_pattern),
+        ticket_pattern: child.ticket_pattern.or(base.ticket_pattern),
+        strict_templates: child.strict_templates.or(base.strict_templates),
+        date_format: child.date_format.or(base.date_format),
+        time_format: child.time_format.or(base.time_format),
+        timezone: child.timezone.or(base.timezone),
+        check_before_generate: child.check_before_generate.or(base.check_before_generate),
+        check_before_push: child.check_before_push.or(base.check_before_push),
+        lang: child.lang.or(base.lang),
+        templates: merge_templates(base.templates, child.templates),
+        snippets: merge_maps(base.snippets, child.snippets),
+        type_colors: merge_maps(base.type_colors, child.type_colors),
+        file_bullet: child.file_bullet.or(base.file_bullet),
+        file_bullet_compact: child.file_bullet_compact.or(base.file_bullet_compact),
+        file_bullet_checkbox: child.file_bullet_checkbox.or(base.file_bullet_checkbox),
+        file_bullet_annotate: child.file_bullet_annotate.or(base.file_bullet_annotate),
+        strip_comments: child.strip_comments.or(base.strip_comments),
+        commit_size: child.commit_size.or(base.commit_size),
+        commit_number_source: child.commit_number_source.or(base.commit_number_source),
+        change_id: child.change_id.or(base.change_id),
+        require_signed: child.require_signed.or(base.require_signed),
+        branch_name: child.branch_name.or(base.branch_name),
+        format_patch: child.format_patch.or(base.format_patch),
+        todo: child.todo.or(base.todo),
+        pre_push: child.pre_push.or(base.pre_push),
+        review: child.review.or(base.review),
+        release: child.release.or(base.release),
+        daemon: child.daemon.or(base.daemon),
+        handoff: child.handoff.or(base.handoff),
+        config_sync: child.config_sync.or(base.config_sync),
+        copy: child.copy.or(base.copy),
+        fragments: child.fragments.or(base.fragments),
+        packages: merge_maps(base.packages, child.packages),
+        scopes: merge_maps(base.scopes, child.scopes),
+        impact: merge_maps(base.impact, child.impact),
+        notify: child.notify.or(base.notify),
+        tracker: child.tracker.or(base.tracker),
+        url_rewrites: merge_maps(base.url_rewrites, child.url_rewrites),
+        expected_remotes: child.expected_remotes.or(base.expected_remotes),
+        auto_fetch: child.auto_fetch.or(base.auto_fetch),
+        preset: child.preset.or(base.preset),
+        accessibility: child.accessibility.or(base.accessibility),
         overrides: child.overrides.or(base.overrides),
     }
 }
@@ -474,6 +1441,21 @@ fn load_single_raw_file(path: &Path) -> Result<RawProjectConfig> {
     })
 }
 
+/// Checks that `content` is a valid config file, named `file` in errors.
+///
+/// # Errors
+/// * If `content` is not TOML or has a value of the wrong type
+pub fn validate_config_text(file: &str, content: &str) -> Result<()> {
+    toml::from_str::<RawProjectConfig>(content)
+        .map(drop)
+        .map_err(|e| {
+            RonaError::Config(ConfigError::ParseError {
+                file: file.to_string(),
+                reason: e.to_string(),
+            })
+        })
+}
+
 /// Loads an ordered list of config files (base-first) and folds them with `merge_raw`.
 /// Files that do not exist are silently skipped.
 fn load_and_merge_files(paths: &[PathBuf]) -> Result<RawProjectConfig> {
@@ -488,26 +1470,73 @@ fn load_and_merge_files(paths: &[PathBuf]) -> Result<RawProjectConfig> {
 }
 
 impl ProjectConfig {
+    /// The emoji `commit_types` gives `commit_type`, if any.
+    #[must_use]
+    pub fn commit_emoji(&self, commit_type: &str) -> Option<&str> {
+        self.commit_types
+            .iter()
+            .flatten()
+            .find(|t| t.name == commit_type)
+            .and_then(|t| t.emoji.as_deref())
+    }
+
+    /// The comment lines of `commit_message.md`, read from git's `core.commentChar`.
+    /// They are stripped with `strip_comments`, or with `file_bullet_annotate`, whose
+    /// summaries are comments.
+    #[must_use]
+    pub fn message_comments(&self) -> Comments {
+        Comments::read(self.strip_comments || self.file_bullet_annotate)
+    }
+
+    /// The `[templates.locales.<lang>]` table of a language.
+    #[must_use]
+    pub fn locale(&self, lang: &str) -> Option<&LocaleTemplates> {
+        self.templates.locales.get(lang)
+    }
+
+    /// The `[templates.locales.<lang>]` tables, by language.
+    pub fn locales(&self) -> impl Iterator<Item = (&str, &LocaleTemplates)> {
+        self.templates
+            .locales
+            .iter()
+            .map(|(lang, locale)| (lang.as_str(), locale))
+    }
+
+    /// The named commit templates of `[templates]`, by name.
+    pub fn named_templates(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.templates
+            .named
+            .iter()
+            .map(|(name, template)| (name.as_str(), template.as_str()))
+    }
+
     /// Loads the project configuration, merging global and project config files.
+    /// Also returns the files merged, in loading order.
     ///
     /// # Errors
     /// Returns `ConfigError::ConfigNotFound` if the config files cannot be found or read.
     /// Returns `ConfigError::InvalidConfig` if deserialization fails.
-    ///
-    /// # Panics
-    /// Panics if the current working directory cannot be determined (i.e., if `std::env::current_dir()` fails).
-    pub fn load() -> Result<Self> {
+    pub fn load() -> Result<(Self, Vec<ConfigLayer>)> {
         // During tests, return default config to avoid dependency on external files
         if cfg!(test) {
-            return Ok(Self::default());
+            return Ok((Self::default(), Vec::new()));
         }
 
-        let paths = config_paths_for_dir(&env::current_dir()?)?;
+        let layers = config_layers_for_dir(&env::current_dir()?)?;
 
-        load_and_merge_files(&paths).map(Into::into).map_err(|e| {
+        let config = Self::from_layers(&layers).map_err(|e| {
             eprintln!("Failed to deserialize config: {e}");
             e
-        })
+        })?;
+        Ok((config, layers))
+    }
+
+    /// Merges the config files of `layers`, later layers overriding earlier ones.
+    ///
+    /// # Errors
+    /// Returns `ConfigError::ParseError` if a file cannot be parsed.
+    pub fn from_layers(layers: &[ConfigLayer]) -> Result<Self> {
+        load_and_merge_files(&layer_paths(layers)).map(Into::into)
     }
 
     /// Loads the project configuration from a specific file path, bypassing the default
@@ -520,17 +1549,7 @@ impl ProjectConfig {
     /// Returns `ConfigError::ConfigNotFound` if the file does not exist.
     /// Returns `ConfigError::InvalidConfig` if deserialization fails.
     pub fn load_from_file(path: &std::path::Path) -> Result<Self> {
-        if !path.exists() {
-            return Err(ConfigError::ConfigNotFound.into());
-        }
-
-        let abs_path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
-
-        let mut visited = HashSet::new();
-        let mut paths: Vec<PathBuf> = collect_extends_chain(&abs_path, &mut visited)?;
-        paths.push(abs_path);
-
-        load_and_merge_files(&paths).map(Into::into)
+        Self::from_layers(&file_layers(path)?)
     }
 
     /// Loads the project configuration from a specific directory.
@@ -542,9 +1561,7 @@ impl ProjectConfig {
     /// Returns `ConfigError::ConfigNotFound` if the config files cannot be found or read.
     /// Returns `ConfigError::InvalidConfig` if deserialization fails.
     pub fn load_from_dir(from_dir: &std::path::Path) -> Result<Self> {
-        let paths = config_paths_for_dir(from_dir)?;
-
-        load_and_merge_files(&paths).map(Into::into).map_err(|e| {
+        Self::from_layers(&config_layers_for_dir(from_dir)?).map_err(|e| {
             eprintln!("Failed to deserialize config: {e}");
             e
         })
@@ -688,7 +1705,7 @@ pub fn find_config_sources(from_dir: Option<&std::path::Path>) -> Result<ConfigI
         }
     }
 
-    // Project-local config (priority 5 - highest priority, overrides all)
+    // Project-local config (priority 5 - overrides global, overrides and extends)
     sources.push(ConfigSource {
         path: project_config.clone(),
         exists: project_config.exists(),
@@ -696,18 +1713,142 @@ pub fn find_config_sources(from_dir: Option<&std::path::Path>) -> Result<ConfigI
         priority: 5,
     });
 
-    // Try to load the effective configuration
-    let effective_config = if cfg!(test) {
-        Some(ProjectConfig::default())
+    // Package configs for the staged files (priority 6 - highest priority, overrides all)
+    let loaded: Vec<PathBuf> = sources.iter().map(|s| s.path.clone()).collect();
+    let package_paths = staged_package_config_paths(&loaded).unwrap_or_default();
+    for path in &package_paths {
+        sources.push(ConfigSource {
+            path: path.clone(),
+            exists: true,
+            description: "Package config (staged files)".to_string(),
+            priority: 6,
+        });
+    }
+
+    // Try to load the effective configuration
+    let effective_config = if cfg!(test) {
+        Some(ProjectConfig::default())
+    } else if package_paths.is_empty() {
+        ProjectConfig::load_from_dir(&search_dir).ok()
+    } else {
+        config_layers_for_dir(&search_dir)
+            .and_then(|layers| {
+                let mut paths = layer_paths(&layers);
+                paths.extend(package_paths);
+                load_and_merge_files(&paths)
+            })
+            .ok()
+            .map(Into::into)
+    };
+
+    Ok(ConfigInfo {
+        sources,
+        effective_config,
+        search_directory: search_dir,
+    })
+}
+
+/// The package configs that apply to the currently staged files. Empty when not in a
+/// git repository or nothing is staged.
+fn staged_package_config_paths(loaded: &[PathBuf]) -> Result<Vec<PathBuf>> {
+    if cfg!(test) {
+        return Ok(Vec::new());
+    }
+    let (Ok(repo_root), Ok(staged)) = (get_top_level_path(), get_all_staged_file_paths()) else {
+        return Ok(Vec::new());
+    };
+    package_config_paths(&repo_root, &staged, loaded)
+}
+
+/// Tables whose entries are merged one by one across config files.
+const MERGED_TABLES: &[&str] = &[
+    "templates",
+    "snippets",
+    "type_colors",
+    "url_rewrites",
+    "packages",
+    "scopes",
+    "impact",
+];
+
+/// Arrays whose entries are merged by name across config files.
+const MERGED_ARRAYS: &[&str] = &["commit_extra_fields", "branch_extra_fields"];
+
+/// Collects the leaf keys of a TOML table with their values. Arrays are leaves, and
+/// an empty table is a leaf of its own.
+fn flatten_toml(
+    prefix: &[String],
+    table: &toml::Table,
+    leaves: &mut Vec<(Vec<String>, toml::Value)>,
+) {
+    for (name, value) in table {
+        let mut key = prefix.to_vec();
+        key.push(name.clone());
+        match value {
+            toml::Value::Table(inner) if !inner.is_empty() => flatten_toml(&key, inner, leaves),
+            _ => leaves.push((key, value.clone())),
+        }
+    }
+}
+
+/// The leaf keys a config file sets, with the old key names renamed.
+///
+/// # Errors
+/// * If the file cannot be read or parsed
+fn layer_keys(layer: &ConfigLayer) -> Result<Vec<Vec<String>>> {
+    let content = std::fs::read_to_string(&layer.path)?;
+    let table: toml::Table = toml::from_str(&content).map_err(|e| {
+        RonaError::Config(ConfigError::ParseError {
+            file: layer.path.display().to_string(),
+            reason: e.to_string(),
+        })
+    })?;
+
+    let mut leaves = Vec::new();
+    flatten_toml(&[], &table, &mut leaves);
+    Ok(leaves
+        .into_iter()
+        .map(|(mut key, _)| {
+            key[0] = match key[0].as_str() {
+                "template" => "commit_template".to_string(),
+                "extra_fields" => "commit_extra_fields".to_string(),
+                "field_order" => "commit_fields_order".to_string(),
+                _ => key[0].clone(),
+            };
+            key
+        })
+        .collect())
+}
+
+/// The part of a key that `merge_raw` replaces as a whole: the top-level key, or the
+/// entry of a merged table.
+fn merge_unit(key: &[String]) -> &[String] {
+    let len = if key[0] == "templates" && key.get(1).is_some_and(|name| name == "locales") {
+        key.len().min(3)
+    } else if MERGED_TABLES.contains(&key[0].as_str()) {
+        key.len().min(2)
     } else {
-        ProjectConfig::load_from_dir(&search_dir).ok()
+        1
     };
+    &key[..len]
+}
 
-    Ok(ConfigInfo {
-        sources,
-        effective_config,
-        search_directory: search_dir,
-    })
+/// Formats a key path as a dotted TOML key, quoting the parts that need it.
+fn dotted_key(key: &[String]) -> String {
+    key.iter()
+        .map(|part| {
+            if !part.is_empty()
+                && part
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+            {
+                part.clone()
+            } else {
+                format!("{part:?}")
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(".")
 }
 
 /// Main configuration struct that handles all config operations.
@@ -718,12 +1859,22 @@ pub fn find_config_sources(from_dir: Option<&std::path::Path>) -> Result<ConfigI
 /// * `root` - The root path for configuration files
 /// * `verbose` - Whether to show detailed output
 /// * `dry_run` - Whether to simulate operations without making changes
+/// * `json_output` - Whether commands that support it print JSON instead of text
 #[derive(Debug)]
 pub struct Config {
     root: PathBuf,
     pub(crate) verbose: bool,
     pub(crate) dry_run: bool,
+    pub(crate) json_output: bool,
     pub project_config: ProjectConfig,
+    /// The files `project_config` was merged from, in loading order
+    pub layers: Vec<ConfigLayer>,
+    /// The language given with `--lang`
+    lang_option: Option<String>,
+    /// The named template given with `--template`
+    template_option: Option<String>,
+    /// The `[templates]` entry `commit_template` was taken from
+    named_template: Option<String>,
 }
 
 impl Config {
@@ -737,12 +1888,17 @@ impl Config {
     /// * `Result<Config>` - A new Config instance with default settings
     pub fn new() -> Result<Self> {
         let root = Self::get_config_root()?;
-        let project_config = ProjectConfig::load().unwrap_or_default();
+        let (project_config, layers) = ProjectConfig::load().unwrap_or_default();
         let config = Self {
             root,
             verbose: false,
             dry_run: false,
+            json_output: false,
             project_config,
+            layers,
+            lang_option: None,
+            template_option: None,
+            named_template: None,
         };
         Ok(config)
     }
@@ -757,13 +1913,18 @@ impl Config {
     /// * `Config` - A new Config instance with the specified root and default settings
     pub fn with_root(root: impl Into<PathBuf>) -> Self {
         let root = root.into();
-        let project_config = ProjectConfig::load().unwrap_or_default();
+        let (project_config, layers) = ProjectConfig::load().unwrap_or_default();
 
         Self {
             root,
             verbose: false,
             dry_run: false,
+            json_output: false,
             project_config,
+            layers,
+            lang_option: None,
+            template_option: None,
+            named_template: None,
         }
     }
 
@@ -781,15 +1942,258 @@ impl Config {
     /// * `Result<Config>` - A new Config instance using the provided file
     pub fn new_with_config_file(path: &std::path::Path) -> Result<Self> {
         let root = Self::get_config_root()?;
-        let project_config = ProjectConfig::load_from_file(path)?;
+        let layers = file_layers(path)?;
+        let project_config = ProjectConfig::from_layers(&layers)?;
         Ok(Self {
             root,
             verbose: false,
             dry_run: false,
+            json_output: false,
             project_config,
+            layers,
+            lang_option: None,
+            template_option: None,
+            named_template: None,
         })
     }
 
+    /// Layers in the package `.rona.toml` files that apply to the staged files.
+    ///
+    /// In a monorepo, a package directory can have its own `.rona.toml` with its own
+    /// commit types and templates. When every staged file is under such a directory,
+    /// its config overrides the one loaded for the current directory, and deeper
+    /// packages override shallower ones.
+    ///
+    /// # Errors
+    /// * If a package config or its `extends` chain cannot be read or parsed
+    pub fn apply_package_configs(&mut self) -> Result<()> {
+        let mut layers = config_layers_for_dir(&env::current_dir()?)?;
+        let package_paths = staged_package_config_paths(&layer_paths(&layers))?;
+        if package_paths.is_empty() {
+            return Ok(());
+        }
+
+        layers.extend(
+            package_paths
+                .into_iter()
+                .map(|path| ConfigLayer::new(path, "package")),
+        );
+        self.project_config = ProjectConfig::from_layers(&layers)?;
+        self.layers = layers;
+        Ok(())
+    }
+
+    /// Switches to the commit template and date formats of a language.
+    ///
+    /// `lang` (from `--lang`) takes precedence over the configured `lang`. The keys set
+    /// in its `[templates.locales.<lang>]` table replace the top-level ones.
+    ///
+    /// # Errors
+    /// * If the language has no `[templates.locales.<lang>]` table
+    pub fn apply_locale(&mut self, lang: Option<&str>) -> Result<()> {
+        let Some(lang) = lang
+            .map(str::to_string)
+            .or_else(|| self.project_config.lang.clone())
+        else {
+            return Ok(());
+        };
+        let Some(locale) = self.project_config.locale(&lang).cloned() else {
+            let known: Vec<&str> = self
+                .project_config
+                .locales()
+                .map(|(lang, _)| lang)
+                .collect();
+            return Err(RonaError::InvalidInput(format!(
+                "No [templates.locales.{lang}] table in the config (configured languages: {})",
+                if known.is_empty() {
+                    "none".to_string()
+                } else {
+                    known.join(", ")
+                }
+            )));
+        };
+
+        if self.project_config.lang.as_ref() != Some(&lang) {
+            self.lang_option = Some(lang.clone());
+        }
+        let config = &mut self.project_config;
+        config.lang = Some(lang);
+        if let Some(template) = locale.commit_template {
+            config.commit_template = Some(template);
+        }
+        if let Some(format) = locale.date_format {
+            config.date_format = Some(format);
+        }
+        if let Some(format) = locale.time_format {
+            config.time_format = Some(format);
+        }
+        Ok(())
+    }
+
+    /// Switches to a named commit template of `[templates]`.
+    ///
+    /// Without `name` (from `--template`), the template named `default` is used unless
+    /// the language from `apply_locale` sets its own commit template.
+    ///
+    /// # Errors
+    /// * If `[templates]` has no commit template named `name`
+    pub fn apply_template(&mut self, name: Option<&str>) -> Result<()> {
+        let Some(name) = name else {
+            let localized = self
+                .project_config
+                .lang
+                .as_deref()
+                .and_then(|lang| self.project_config.locale(lang))
+                .is_some_and(|locale| locale.commit_template.is_some());
+            if !localized
+                && let Some(template) = self
+                    .project_config
+                    .templates
+                    .named
+                    .get(DEFAULT_TEMPLATE_NAME)
+            {
+                self.project_config.commit_template = Some(template.clone());
+                self.named_template = Some(DEFAULT_TEMPLATE_NAME.to_string());
+            }
+            return Ok(());
+        };
+        let Some(template) = self.project_config.templates.named.get(name) else {
+            let known: Vec<&str> = self
+                .project_config
+                .named_templates()
+                .map(|(name, _)| name)
+                .collect();
+            return Err(RonaError::InvalidInput(format!(
+                "No template named '{name}' in [templates] (configured templates: {})",
+                if known.is_empty() {
+                    "none".to_string()
+                } else {
+                    known.join(", ")
+                }
+            )));
+        };
+
+        self.project_config.commit_template = Some(template.clone());
+        self.template_option = Some(name.to_string());
+        self.named_template = Some(name.to_string());
+        Ok(())
+    }
+
+    /// The named template given with `--template`, if any.
+    #[must_use]
+    pub fn template_option(&self) -> Option<&str> {
+        self.template_option.as_deref()
+    }
+
+    /// The `[templates]` entry the commit template was taken from, by `--template` or
+    /// as `default`.
+    #[must_use]
+    pub fn named_template(&self) -> Option<&str> {
+        self.named_template.as_deref()
+    }
+
+    /// Every effective config value with the layer that set it.
+    ///
+    /// Values no layer sets are `default`. An unset `editor` is reported from
+    /// `$VISUAL`, `$EDITOR` or git's `core.editor` when one of them is set, and a
+    /// `--lang` option as `cli: --lang`.
+    ///
+    /// # Errors
+    /// * If a config file cannot be read or parsed
+    pub fn value_origins(&self) -> Result<Vec<ValueOrigin>> {
+        let layers = self
+            .layers
+            .iter()
+            .map(|layer| Ok((layer, layer_keys(layer)?)))
+            .collect::<Result<Vec<_>>>()?;
+        let origin_of = |key: &[String]| -> String {
+            let unit = merge_unit(key);
+            let setters: Vec<_> = layers
+                .iter()
+                .filter(|(_, keys)| keys.iter().any(|k| k.starts_with(unit)))
+                .collect();
+            let describe =
+                |layer: &ConfigLayer| format!("{}: {}", layer.origin, layer.path.display());
+            if MERGED_ARRAYS.contains(&key[0].as_str()) && !setters.is_empty() {
+                return setters
+                    .iter()
+                    .map(|(layer, _)| describe(layer))
+                    .collect::<Vec<_>>()
+                    .join(" + ");
+            }
+            match setters.last() {
+                Some((layer, keys)) if keys.iter().any(|k| key.starts_with(k)) => describe(layer),
+                _ => "default".to_string(),
+            }
+        };
+
+        let effective = toml::Table::try_from(&self.project_config).map_err(|e| {
+            RonaError::InvalidInput(format!("Cannot serialize the configuration: {e}"))
+        })?;
+        let mut leaves = Vec::new();
+        flatten_toml(&[], &effective, &mut leaves);
+
+        let lang = self.project_config.lang.as_deref();
+        let mut origins: Vec<ValueOrigin> = leaves
+            .into_iter()
+            .map(|(key, value)| {
+                let localized = lang.filter(|lang| {
+                    self.project_config
+                        .locale(lang)
+                        .is_some_and(|locale| match key[0].as_str() {
+                            "commit_template" => locale.commit_template.is_some(),
+                            "date_format" => locale.date_format.is_some(),
+                            "time_format" => locale.time_format.is_some(),
+                            _ => false,
+                        })
+                });
+                let named = self
+                    .named_template
+                    .as_deref()
+                    .filter(|_| key[0] == "commit_template");
+                let origin = if key[0] == "lang" && self.lang_option.is_some() {
+                    "cli: --lang".to_string()
+                } else if let Some(name) = named {
+                    let template_key = ["templates", name].map(str::to_string);
+                    if self.template_option.is_some() {
+                        format!("{} (via --template {name})", origin_of(&template_key))
+                    } else {
+                        format!("{} (via templates.{name})", origin_of(&template_key))
+                    }
+                } else if let Some(lang) = localized {
+                    let locale_key = ["templates", "locales", lang, &key[0]].map(str::to_string);
+                    format!(
+                        "{} (via [templates.locales.{lang}])",
+                        origin_of(&locale_key)
+                    )
+                } else {
+                    origin_of(&key)
+                };
+                ValueOrigin {
+                    key: dotted_key(&key),
+                    value: value.to_string(),
+                    origin,
+                }
+            })
+            .collect();
+
+        if self.project_config.editor.is_none()
+            && let Some(fallback) = fallback_editor()
+        {
+            origins.push(ValueOrigin {
+                key: "editor".to_string(),
+                value: toml::Value::String(fallback.command).to_string(),
+                origin: if fallback.source.starts_with('$') {
+                    format!("env: {}", fallback.source)
+                } else {
+                    format!("git config: {}", fallback.source)
+                },
+            });
+        }
+        origins.sort_by(|a, b| a.key.cmp(&b.key));
+        Ok(origins)
+    }
+
     /// Sets the verbose flag which controls detailed output logging.
     ///
     /// # Arguments
@@ -807,6 +2211,15 @@ impl Config {
         self.dry_run = dry_run;
     }
 
+    /// Sets the `json_output` flag, set by `--output json`. Commands that support it
+    /// then print a JSON object instead of text.
+    ///
+    /// # Arguments
+    /// * `json_output` - Whether to print JSON
+    pub const fn set_json_output(&mut self, json_output: bool) {
+        self.json_output = json_output;
+    }
+
     /// Retrieves the editor from the configuration file.
     ///
     /// # Errors
@@ -852,6 +2265,8 @@ impl Config {
     /// * If the configuration file cannot be read or written
     /// * If the configuration file does not exist
     pub fn set_editor(&self, editor: &str) -> Result<()> {
+        ensure_writable("write the config file")?;
+
         // During tests, use the old behavior for compatibility
         if cfg!(test) {
             let config_file = self.get_config_file_path()?;
@@ -860,22 +2275,18 @@ impl Config {
                 return Err(ConfigError::ConfigNotFound.into());
             }
 
-            // Use old format for tests
-            let config_content = format!("editor = \"{editor}\"");
-            std::fs::write(&config_file, config_content)?;
-
-            return Ok(());
+            return set_file_value(&config_file, "editor", editor);
         }
 
         let options = vec!["Project (./.rona.toml)", "Global (~/.config/rona.toml)"];
 
-        let index = FuzzySelect::with_theme(&crate::theme::prompt_theme())
-            .with_prompt("Where do you want to set the editor?")
-            .items(&options)
-            .default(0)
-            .interact_opt()
-            .map_err(|_| ConfigError::InvalidConfig)?
-            .ok_or(ConfigError::InvalidConfig)?;
+        let index = crate::theme::select_item(
+            &crate::theme::prompt_theme(),
+            "Where do you want to set the editor?",
+            &options,
+        )
+        .map_err(|_| ConfigError::InvalidConfig)?
+        .ok_or(ConfigError::InvalidConfig)?;
 
         let config_path = match options[index] {
             "Project (./.rona.toml)" => get_top_level_path().map(|root| root.join(".rona.toml"))?,
@@ -886,13 +2297,7 @@ impl Config {
             _ => unreachable!(),
         };
 
-        let mut config = self.project_config.clone();
-        config.editor = Some(editor.to_string());
-
-        let toml_str = toml::to_string_pretty(&config).map_err(|_| ConfigError::InvalidConfig)?;
-        let mut file = std::fs::File::create(&config_path)?;
-
-        file.write_all(toml_str.as_bytes())?;
+        set_file_value(&config_path, "editor", editor)?;
 
         println!("Editor set in: {}", config_path.display());
 
@@ -909,6 +2314,8 @@ impl Config {
     /// * If writing the configuration file fails
     /// * If the configuration file already exists
     pub fn create_config_file(&self, editor: &str) -> Result<()> {
+        ensure_writable("write the config file")?;
+
         // During tests, use the old behavior for compatibility
         if cfg!(test) {
             let config_folder = self.get_config_folder_path()?;
@@ -918,25 +2325,22 @@ impl Config {
             }
 
             let config_file = self.get_config_file_path()?;
-            let config_content = format!("editor = \"{editor}\"");
 
             if config_file.exists() {
                 return Err(ConfigError::ConfigAlreadyExists.into());
             }
 
-            std::fs::write(&config_file, config_content)?;
-
-            return Ok(());
+            return set_file_value(&config_file, "editor", editor);
         }
 
         let options = vec!["Project (.rona.toml)", "Global (~/.config/rona.toml)"];
-        let index = FuzzySelect::with_theme(&crate::theme::prompt_theme())
-            .with_prompt("Where do you want to initialize the config?")
-            .items(&options)
-            .default(0)
-            .interact_opt()
-            .map_err(|_| ConfigError::InvalidConfig)?
-            .ok_or(ConfigError::InvalidConfig)?;
+        let index = crate::theme::select_item(
+            &crate::theme::prompt_theme(),
+            "Where do you want to initialize the config?",
+            &options,
+        )
+        .map_err(|_| ConfigError::InvalidConfig)?
+        .ok_or(ConfigError::InvalidConfig)?;
 
         let config_path = match options[index] {
             "Project (.rona.toml)" => env::current_dir()?.join(".rona.toml"),
@@ -966,13 +2370,7 @@ impl Config {
             return Err(ConfigError::ConfigAlreadyExists.into());
         }
 
-        let mut config = self.project_config.clone();
-        config.editor = Some(editor.to_string());
-
-        let toml_str = toml::to_string_pretty(&config).map_err(|_| ConfigError::InvalidConfig)?;
-        std::fs::write(&config_path, toml_str)?;
-
-        Ok(())
+        set_file_value(&config_path, "editor", editor)
     }
 
     /// Returns the path to the configuration folder.
@@ -1214,6 +2612,43 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_package_config_paths_layers_shallow_to_deep()
+    -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let temp_dir = TempDir::new()?;
+        let root = temp_dir.path();
+        std::fs::create_dir_all(root.join("packages/api/src"))?;
+        std::fs::write(root.join(".rona.toml"), "commit_types = [\"feat\"]\n")?;
+        std::fs::write(root.join("packages/.rona.toml"), "editor = \"vim\"\n")?;
+        std::fs::write(
+            root.join("packages/api/.rona.toml"),
+            "extends = \"../../api-base.toml\"\n",
+        )?;
+        std::fs::write(root.join("api-base.toml"), "commit_types = [\"api\"]\n")?;
+
+        let staged = vec!["packages/api/src/main.rs".to_string()];
+        let paths = package_config_paths(root, &staged, &[root.join(".rona.toml")])?;
+        assert_eq!(
+            paths,
+            vec![
+                root.join("packages/.rona.toml"),
+                root.join("packages/api/../../api-base.toml"),
+                root.join("packages/api/.rona.toml"),
+            ]
+        );
+
+        // Files spanning packages only get the configs they share
+        let staged = vec![
+            "packages/api/a.rs".to_string(),
+            "packages/web/b.ts".to_string(),
+        ];
+        assert_eq!(
+            package_config_paths(root, &staged, &[])?,
+            vec![root.join("packages/.rona.toml")]
+        );
+        Ok(())
+    }
+
     #[test]
     fn test_collect_override_paths_resolves_relative_config()
     -> std::result::Result<(), Box<dyn std::error::Error>> {
@@ -1285,7 +2720,7 @@ mod tests {
         assert!(config_file.exists());
 
         let content = std::fs::read_to_string(&config_file)?;
-        assert_eq!(content, format!("editor = \"{editor}\""));
+        assert_eq!(content, format!("editor = \"{editor}\"\n"));
 
         // Test error when a file already exists
         assert!(config.create_config_file(editor).is_err());
@@ -1329,6 +2764,128 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_set_editor_keeps_comments() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let temp_dir = TempDir::new()?;
+        let config = Config::with_root(temp_dir.path().to_path_buf());
+        let config_file = config.get_config_file_path()?;
+        std::fs::create_dir_all(config.get_config_folder_path()?)?;
+        std::fs::write(
+            &config_file,
+            "# Shared by the team\ncommit_types = [\"feat\", \"fix\"]\neditor = \"vim\" # default\n",
+        )?;
+
+        config.set_editor("zed")?;
+
+        assert_eq!(
+            std::fs::read_to_string(&config_file)?,
+            "# Shared by the team\ncommit_types = [\"feat\", \"fix\"]\neditor = \"zed\" # default\n"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_apply_guards() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let guards: GuardsOnly = toml::from_str(
+            r#"
+            [[guards]]
+            deny = ["push"]
+
+            [[guards]]
+            path = "/work/oss"
+            deny = ["sign", "commit"]
+
+            [[guards]]
+            path = "/work/oss/rona"
+            allow = ["commit"]
+
+            [[guards]]
+            remote = "github.com/acme/*"
+            allow = ["push"]
+            "#,
+        )?;
+        let acme = ["git@github.com:acme/app.git".to_string()];
+
+        let denied = apply_guards(&guards.guards, Path::new("/work/oss/tool"), &[]);
+        assert_eq!(
+            denied,
+            vec![
+                (Feature::Push, "without path or remote".to_string()),
+                (Feature::Sign, "path = \"/work/oss\"".to_string()),
+                (Feature::Commit, "path = \"/work/oss\"".to_string()),
+            ]
+        );
+
+        let denied = apply_guards(&guards.guards, Path::new("/work/oss/rona"), &acme);
+        assert_eq!(
+            denied,
+            vec![(Feature::Sign, "path = \"/work/oss\"".to_string())]
+        );
+
+        let denied = apply_guards(&guards.guards, Path::new("/home/me/app"), &acme);
+        assert!(denied.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_value_origins() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let temp_dir = TempDir::new()?;
+        let global = temp_dir.path().join("rona.toml");
+        let project = temp_dir.path().join(".rona.toml");
+        std::fs::write(
+            &global,
+            "editor = \"vim\"\ncommit_types = [\"feat\"]\n\n[snippets]\nreview = \"Reviewed\"\n\n\
+             [todo]\non_commit = \"block\"\n",
+        )?;
+        std::fs::write(
+            &project,
+            "editor = \"nano\"\n\n[snippets]\nticket = \"Refs\"\n\n[todo]\nmarkers = [\"XXX\"]\n",
+        )?;
+
+        let mut config = Config::with_root(temp_dir.path());
+        config.layers = vec![
+            ConfigLayer::new(global.clone(), "global"),
+            ConfigLayer::new(project.clone(), "project"),
+        ];
+        config.project_config = ProjectConfig::from_layers(&config.layers)?;
+
+        let origins = config.value_origins()?;
+        let origin = |key: &str| {
+            origins
+                .iter()
+                .find(|v| v.key == key)
+                .map(|v| (v.value.as_str(), v.origin.clone()))
+        };
+        let from = |layer: &str, path: &Path| format!("{layer}: {}", path.display());
+
+        assert_eq!(
+            origin("editor"),
+            Some(("\"nano\"", from("project", &project)))
+        );
+        assert_eq!(
+            origin("commit_types"),
+            Some(("[\"feat\"]", from("global", &global)))
+        );
+        assert_eq!(
+            origin("snippets.review"),
+            Some(("\"Reviewed\"", from("global", &global)))
+        );
+        assert_eq!(
+            origin("snippets.ticket"),
+            Some(("\"Refs\"", from("project", &project)))
+        );
+        // The project's [todo] table replaces the global one as a whole.
+        assert_eq!(
+            origin("todo.on_commit"),
+            Some(("\"ignore\"", "default".to_string()))
+        );
+        assert_eq!(
+            origin("strict_templates"),
+            Some(("false", "default".to_string()))
+        );
+        Ok(())
+    }
+
     #[test]
     fn test_get_editor_error_no_config() -> std::result::Result<(), Box<dyn std::error::Error>> {
         let temp_dir = TempDir::new()?;
@@ -1395,7 +2952,7 @@ mod tests {
         assert_eq!(cfg.editor.as_deref(), Some("vim"));
         assert_eq!(
             cfg.commit_types.as_deref(),
-            Some(["feat".to_string()].as_slice())
+            Some([CommitType::from("feat")].as_slice())
         );
 
         Ok(())
@@ -1438,7 +2995,7 @@ mod tests {
         assert_eq!(cfg.editor.as_deref(), Some("vim"));
         assert_eq!(
             cfg.commit_types.as_deref(),
-            Some(["fix".to_string()].as_slice())
+            Some([CommitType::from("fix")].as_slice())
         );
 
         Ok(())
@@ -1541,6 +3098,34 @@ prompt = "Scope (project)"
         Ok(())
     }
 
+    #[test]
+    fn test_snippets_merged_by_name() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let temp_dir = TempDir::new()?;
+        let base = temp_dir.path().join("base.toml");
+        let project = temp_dir.path().join(".rona.toml");
+
+        std::fs::write(
+            &base,
+            "[snippets]\nreview = \"base checklist\"\nmigration = \"run migrations\"\n",
+        )?;
+        std::fs::write(
+            &project,
+            "extends = \"base.toml\"\n\n[snippets]\nreview = \"project checklist\"\n",
+        )?;
+
+        let cfg = ProjectConfig::load_from_file(&project)?;
+        assert_eq!(
+            cfg.snippets.get("review").map(String::as_str),
+            Some("project checklist")
+        );
+        assert_eq!(
+            cfg.snippets.get("migration").map(String::as_str),
+            Some("run migrations")
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn test_branch_extra_fields_merged_by_name()
     -> std::result::Result<(), Box<dyn std::error::Error>> {
@@ -1587,4 +3172,178 @@ prompt = "Version (project)"
 
         Ok(())
     }
+
+    #[test]
+    fn test_named_templates() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let temp_dir = TempDir::new()?;
+        let project = temp_dir.path().join(".rona.toml");
+        std::fs::write(
+            &project,
+            "commit_template = \"{commit_type}: {message}\"\n\n\
+             [templates]\ndefault = \"[{commit_type}] {message}\"\n\
+             release = \"release: {message}\"\n\n\
+             [templates.locales.fr]\ncommit_template = \"{commit_type} : {message}\"\n",
+        )?;
+
+        let mut config = Config::new_with_config_file(&project)?;
+        assert_eq!(
+            config
+                .project_config
+                .named_templates()
+                .map(|(name, _)| name)
+                .collect::<Vec<_>>(),
+            ["default", "release"]
+        );
+        assert_eq!(
+            config
+                .project_config
+                .locales()
+                .map(|(lang, _)| lang)
+                .collect::<Vec<_>>(),
+            ["fr"]
+        );
+        assert!(config.project_config.locale("release").is_none());
+        toml::to_string_pretty(&config.project_config)?;
+
+        config.apply_template(None)?;
+        assert_eq!(
+            config.project_config.commit_template.as_deref(),
+            Some("[{commit_type}] {message}")
+        );
+        assert_eq!(config.template_option(), None);
+
+        config.apply_template(Some("release"))?;
+        assert_eq!(
+            config.project_config.commit_template.as_deref(),
+            Some("release: {message}")
+        );
+        assert_eq!(config.template_option(), Some("release"));
+
+        let error = config
+            .apply_template(Some("fr"))
+            .err()
+            .ok_or("expected an error")?;
+        assert!(
+            error
+                .to_string()
+                .contains("configured templates: default, release")
+        );
+
+        // The commit template of the language wins over `default`.
+        let mut config = Config::new_with_config_file(&project)?;
+        config.apply_locale(Some("fr"))?;
+        config.apply_template(None)?;
+        assert_eq!(
+            config.project_config.commit_template.as_deref(),
+            Some("{commit_type} : {message}")
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_locale_templates() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let temp_dir = TempDir::new()?;
+        let base = temp_dir.path().join("base.toml");
+        let project = temp_dir.path().join(".rona.toml");
+
+        std::fs::write(
+            &base,
+            "[templates.locales.fr]\ncommit_template = \"{commit_type} : {message}\"\n\n\
+             [templates.locales.de]\ndate_format = \"%d.%m.%Y\"\n",
+        )?;
+        std::fs::write(
+            &project,
+            "extends = \"base.toml\"\nlang = \"fr\"\n\n\
+             [templates.locales.fr]\ncommit_template = \"{commit_type} : {message}\"\n\
+             date_format = \"%d/%m/%Y\"\n",
+        )?;
+
+        let mut config = Config::new_with_config_file(&project)?;
+        assert_eq!(config.project_config.templates.locales.len(), 2);
+
+        config.apply_locale(None)?;
+        assert_eq!(
+            config.project_config.commit_template.as_deref(),
+            Some("{commit_type} : {message}")
+        );
+        assert_eq!(
+            config.project_config.date_format.as_deref(),
+            Some("%d/%m/%Y")
+        );
+
+        // `--lang` wins over `lang`; unset keys keep the current values.
+        config.apply_locale(Some("de"))?;
+        assert_eq!(config.project_config.lang.as_deref(), Some("de"));
+        assert_eq!(
+            config.project_config.date_format.as_deref(),
+            Some("%d.%m.%Y")
+        );
+        assert_eq!(
+            config.project_config.commit_template.as_deref(),
+            Some("{commit_type} : {message}")
+        );
+
+        let error = config
+            .apply_locale(Some("es"))
+            .err()
+            .ok_or("expected an error")?;
+        assert!(error.to_string().contains("configured languages: de, fr"));
+
+        // A language table directly under `[templates]` is pointed to `locales`.
+        let error = validate_config_text(".rona.toml", "[templates.fr]\ndate_format = \"%d\"\n")
+            .err()
+            .ok_or("expected an error")?;
+        assert!(
+            error
+                .to_string()
+                .contains("language tables go under [templates.locales.fr]")
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_commit_types_table_form() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let temp_dir = TempDir::new()?;
+        let project = temp_dir.path().join(".rona.toml");
+        std::fs::write(
+            &project,
+            "[[commit_types]]
+name = \"feat\"
+emoji = \"✨\"
+description = \"A new feature\"
+
+             [[commit_types]]
+name = \"chore\"
+",
+        )?;
+
+        let cfg = ProjectConfig::load_from_file(&project)?;
+        let names: Vec<&str> = cfg
+            .commit_types
+            .iter()
+            .flatten()
+            .map(CommitType::name)
+            .collect();
+        assert_eq!(names, ["feat", "chore"]);
+        assert_eq!(cfg.commit_emoji("feat"), Some("✨"));
+        assert_eq!(cfg.commit_emoji("chore"), None);
+
+        // Types without an emoji or description are written back as plain names.
+        let shown = toml::to_string(&cfg)?;
+        assert!(shown.contains("\"chore\""));
+        assert!(!shown.contains("name = \"chore\""));
+        let reloaded: ProjectConfig = toml::from_str(&shown)?;
+        assert_eq!(reloaded.commit_types, cfg.commit_types);
+
+        std::fs::write(
+            &project,
+            "commit_types = [\"fix\", { name = \"docs\", emoji = \"📝\" }]\n",
+        )?;
+        let cfg = ProjectConfig::load_from_file(&project)?;
+        assert_eq!(cfg.commit_emoji("docs"), Some("📝"));
+
+        Ok(())
+    }
 }
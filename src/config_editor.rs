@@ -0,0 +1,608 @@
+//! Interactive Config Editor
+//!
+//! `rona config edit --interactive` lists the known config keys with their current
+//! value, default and description, and edits the chosen ones with prompts. The file is
+//! edited in place with `toml_edit`, so comments, key order and the keys the editor
+//! does not know about are kept. Every other config write (`rona set-editor`, `rona
+//! init`) goes through [`set_file_value`] for the same reason.
+
+use std::path::Path;
+
+use colored::Colorize;
+use dialoguer::{Confirm, Input, MultiSelect, Select};
+use toml_edit::{Array, DocumentMut, Item, Table, Value};
+
+use crate::{
+    errors::{Result, RonaError},
+    git::ensure_writable,
+    theme::{failure_mark, prompt_theme, select_item},
+};
+
+/// How a config value is entered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValueKind {
+    /// Free text
+    Text,
+    /// `true` or `false`
+    Bool,
+    /// A non-negative integer
+    Integer,
+    /// One of a fixed set of strings
+    Choice(&'static [&'static str]),
+    /// A list of strings, entered comma-separated
+    List,
+    /// Any subset of a fixed set of strings
+    MultiChoice(&'static [&'static str]),
+}
+
+/// A config key the interactive editor knows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConfigKey {
+    /// Dotted path of the key, e.g. `todo.on_commit`
+    pub key: &'static str,
+    /// How the value is entered
+    pub kind: ValueKind,
+    /// The value used when the key is absent, as shown to the user
+    pub default: &'static str,
+    /// What the key does, in one line
+    pub description: &'static str,
+}
+
+/// The keys offered by the interactive editor, in menu order.
+pub const CONFIG_KEYS: &[ConfigKey] = &[
+    ConfigKey {
+        key: "editor",
+        kind: ValueKind::Text,
+        default: "nano",
+        description: "Editor command for commit messages, e.g. `code --wait`",
+    },
+    ConfigKey {
+        key: "commit_types",
+        kind: ValueKind::List,
+        default: "chore, feat, fix, test",
+        description: "Commit types offered by the type selector",
+    },
+    ConfigKey {
+        key: "commit_template",
+        kind: ValueKind::Text,
+        default: "{?commit_number}[{commit_number}] {/commit_number}({commit_type} on {branch_name}) {message}",
+        description: "Template of interactive commit messages",
+    },
+    ConfigKey {
+        key: "strict_templates",
+        kind: ValueKind::Bool,
+        default: "false",
+        description: "Treat unknown template variables as errors",
+    },
+    ConfigKey {
+        key: "check_before_generate",
+        kind: ValueKind::Bool,
+        default: "false",
+        description: "Require staged changes and keep uncommitted messages on generate",
+    },
+    ConfigKey {
+        key: "check_before_push",
+        kind: ValueKind::Bool,
+        default: "false",
+        description: "Fetch before pushing and confirm force pushes that discard commits",
+    },
+    ConfigKey {
+        key: "date_format",
+        kind: ValueKind::Text,
+        default: "%Y-%m-%d",
+        description: "chrono format of {date}",
+    },
+    ConfigKey {
+        key: "time_format",
+        kind: ValueKind::Text,
+        default: "%H:%M:%S",
+        description: "chrono format of {time}",
+    },
+    ConfigKey {
+        key: "timezone",
+        kind: ValueKind::Text,
+        default: "local",
+        description: "Timezone of {date} and {time}: UTC, local or +HH:MM",
+    },
+    ConfigKey {
+        key: "lang",
+        kind: ValueKind::Text,
+        default: "none",
+        description: "Language whose [templates.locales.<lang>] are used",
+    },
+    ConfigKey {
+        key: "commit_number_source",
+        kind: ValueKind::Choice(&["head-count", "remote", "branch-only", "per-type"]),
+        default: "head-count",
+        description: "What {commit_number} counts",
+    },
+    ConfigKey {
+        key: "file_bullet",
+        kind: ValueKind::Text,
+        default: "- `{file}`: {placeholder}",
+        description: "Line written for each file in commit_message.md",
+    },
+    ConfigKey {
+        key: "file_bullet_compact",
+        kind: ValueKind::Bool,
+        default: "false",
+        description: "List file bullets without the empty description lines",
+    },
+    ConfigKey {
+        key: "file_bullet_checkbox",
+        kind: ValueKind::Bool,
+        default: "false",
+        description: "Write file bullets as Markdown checkboxes",
+    },
+    ConfigKey {
+        key: "file_bullet_annotate",
+        kind: ValueKind::Bool,
+        default: "false",
+        description: "Summarize the staged change of each file in a comment",
+    },
+    ConfigKey {
+        key: "strip_comments",
+        kind: ValueKind::Bool,
+        default: "false",
+        description: "Drop comment lines from commit_message.md before committing",
+    },
+    ConfigKey {
+        key: "change_id",
+        kind: ValueKind::Bool,
+        default: "false",
+        description: "Append Gerrit Change-Id trailers to commit messages",
+    },
+    ConfigKey {
+        key: "require_signed",
+        kind: ValueKind::Bool,
+        default: "false",
+        description: "Refuse to commit or push unsigned commits",
+    },
+    ConfigKey {
+        key: "preset",
+        kind: ValueKind::MultiChoice(&["node", "python", "rust"]),
+        default: "none",
+        description: "Build output never staged by `rona -a`",
+    },
+    ConfigKey {
+        key: "branch_template",
+        kind: ValueKind::Text,
+        default: "{branch_type}/{description}",
+        description: "Template of generated branch names",
+    },
+    ConfigKey {
+        key: "branch_pattern",
+        kind: ValueKind::Text,
+        default: "none",
+        description: "Regex new branch names must match",
+    },
+    ConfigKey {
+        key: "branch_name.max_length",
+        kind: ValueKind::Integer,
+        default: "no limit",
+        description: "Longest {branch_name} kept, in characters",
+    },
+    ConfigKey {
+        key: "branch_name.case",
+        kind: ValueKind::Choice(&["preserve", "lower", "upper"]),
+        default: "preserve",
+        description: "Letter case of {branch_name}",
+    },
+    ConfigKey {
+        key: "commit_size.max_files",
+        kind: ValueKind::Integer,
+        default: "no limit",
+        description: "Files a commit may touch before a warning",
+    },
+    ConfigKey {
+        key: "commit_size.max_insertions",
+        kind: ValueKind::Integer,
+        default: "no limit",
+        description: "Lines a commit may add before a warning",
+    },
+    ConfigKey {
+        key: "commit_size.confirm",
+        kind: ValueKind::Bool,
+        default: "false",
+        description: "Ask before committing over a size limit",
+    },
+    ConfigKey {
+        key: "todo.on_commit",
+        kind: ValueKind::Choice(&["ignore", "append", "block"]),
+        default: "ignore",
+        description: "What `rona commit` does with added TODO markers",
+    },
+    ConfigKey {
+        key: "review.ref",
+        kind: ValueKind::Text,
+        default: "refs/for/{target}",
+        description: "Ref `rona push --review` pushes to",
+    },
+    ConfigKey {
+        key: "copy.format",
+        kind: ValueKind::Text,
+        default: "reference",
+        description: "Format `rona copy` uses without --format",
+    },
+    ConfigKey {
+        key: "daemon.refresh_interval",
+        kind: ValueKind::Text,
+        default: "2s",
+        description: "Longest `rona daemon` keeps its status without a refresh",
+    },
+    ConfigKey {
+        key: "expected_remotes",
+        kind: ValueKind::List,
+        default: "none",
+        description: "Hosts or host/owner prefixes pushes should go to",
+    },
+    ConfigKey {
+        key: "auto_fetch",
+        kind: ValueKind::Text,
+        default: "disabled",
+        description: "Interval between background fetches, e.g. 15m",
+    },
+    ConfigKey {
+        key: "accessibility",
+        kind: ValueKind::Bool,
+        default: "false",
+        description: "Plain ASCII output and numbered lists for screen readers",
+    },
+];
+
+/// The value of a dotted key in a document, as shown to the user: lists are joined
+/// with `, `. `None` when the key is absent.
+#[must_use]
+pub fn current_value(doc: &DocumentMut, key: &str) -> Option<String> {
+    let mut parts = key.split('.');
+    let mut item = doc.get(parts.next()?)?;
+    for part in parts {
+        item = item.as_table_like()?.get(part)?;
+    }
+    Some(match item.as_value()? {
+        Value::String(s) => s.value().clone(),
+        Value::Array(array) => array
+            .iter()
+            .map(|v| {
+                v.as_str()
+                    .map_or_else(|| v.to_string(), ToString::to_string)
+            })
+            .collect::<Vec<_>>()
+            .join(", "),
+        other => other.to_string().trim().to_string(),
+    })
+}
+
+/// Sets a dotted key to a value entered by the user, or removes it with `None`. Tables
+/// on the way are created as needed; comments and the other keys are left untouched.
+///
+/// # Errors
+/// * If the value does not fit the key's kind
+/// * If a table on the way is a value in the document
+pub fn set_value(doc: &mut DocumentMut, key: &ConfigKey, value: Option<&str>) -> Result<()> {
+    let mut parts: Vec<&str> = key.key.split('.').collect();
+    let Some(last) = parts.pop() else {
+        return Ok(());
+    };
+    let value = value.map(|v| parse_value(key, v)).transpose()?;
+
+    let mut table = doc.as_table_mut();
+    for part in parts {
+        table = table
+            .entry(part)
+            .or_insert_with(|| Item::Table(Table::new()))
+            .as_table_mut()
+            .ok_or_else(|| {
+                RonaError::InvalidInput(format!("`{part}` is not a table in the config file"))
+            })?;
+    }
+    match value {
+        Some(mut value) => {
+            // Keep the spacing and the trailing comment of the value being replaced.
+            if let Some(old) = table.get(last).and_then(Item::as_value) {
+                *value.decor_mut() = old.decor().clone();
+            }
+            table[last] = Item::Value(value);
+        }
+        None => {
+            table.remove(last);
+        }
+    }
+    Ok(())
+}
+
+/// Converts text entered by the user into the TOML value of a key.
+fn parse_value(key: &ConfigKey, input: &str) -> Result<Value> {
+    let input = input.trim();
+    let invalid = |expected: &str| {
+        RonaError::InvalidInput(format!(
+            "`{input}` is not a valid {}: expected {expected}",
+            key.key
+        ))
+    };
+    Ok(match key.kind {
+        ValueKind::Text => Value::from(input),
+        ValueKind::Bool => Value::from(
+            input
+                .parse::<bool>()
+                .map_err(|_| invalid("true or false"))?,
+        ),
+        ValueKind::Integer => Value::from(i64::from(
+            input
+                .parse::<u32>()
+                .map_err(|_| invalid("a non-negative number"))?,
+        )),
+        ValueKind::Choice(choices) => {
+            if !choices.contains(&input) {
+                return Err(invalid(&choices.join(", ")));
+            }
+            Value::from(input)
+        }
+        ValueKind::List | ValueKind::MultiChoice(_) => {
+            let items: Vec<&str> = input
+                .split(',')
+                .map(str::trim)
+                .filter(|item| !item.is_empty())
+                .collect();
+            if let ValueKind::MultiChoice(choices) = key.kind
+                && let Some(unknown) = items.iter().find(|item| !choices.contains(item))
+            {
+                return Err(RonaError::InvalidInput(format!(
+                    "`{unknown}` is not a valid {}: expected {}",
+                    key.key,
+                    choices.join(", ")
+                )));
+            }
+            Value::Array(items.into_iter().collect::<Array>())
+        }
+    })
+}
+
+/// Edits the config file at `path` interactively, creating it on save when missing.
+///
+/// # Errors
+/// * If the file cannot be read, parsed or written
+/// * If the terminal is not interactive
+///
+/// # Returns
+/// Whether changes were written
+pub fn edit_interactively(path: &Path) -> Result<bool> {
+    let mut doc = read_document(path)?;
+    let mut changed = false;
+
+    println!("Editing {}", path.display());
+    loop {
+        let mut items = vec!["Save and quit".to_string()];
+        items.extend(CONFIG_KEYS.iter().map(|key| {
+            current_value(&doc, key.key).map_or_else(
+                || {
+                    format!(
+                        "{} {}",
+                        key.key,
+                        format!("(default: {})", key.default).dimmed()
+                    )
+                },
+                |value| format!("{} = {value}", key.key),
+            )
+        }));
+        items.push("Quit without saving".to_string());
+
+        let selection = select_item(&prompt_theme(), "Setting to change", &items)
+            .map_err(|_| RonaError::UserCancelled)?;
+        let key = match selection {
+            Some(0) => break,
+            Some(index) if index <= CONFIG_KEYS.len() => &CONFIG_KEYS[index - 1],
+            _ => {
+                if changed
+                    && !Confirm::with_theme(&prompt_theme())
+                        .with_prompt("Discard the changes?")
+                        .default(false)
+                        .interact()
+                        .unwrap_or(false)
+                {
+                    continue;
+                }
+                return Ok(false);
+            }
+        };
+
+        println!("{}", key.description.dimmed());
+        let value = prompt_value(key, current_value(&doc, key.key))?;
+        if value.as_deref().map(str::trim) == current_value(&doc, key.key).as_deref() {
+            continue;
+        }
+        match set_value(&mut doc, key, value.as_deref()) {
+            Ok(()) => changed = true,
+            Err(e) => println!("{} {e}", failure_mark()),
+        }
+    }
+
+    if changed {
+        write_document(path, &doc)?;
+    }
+    Ok(changed)
+}
+
+/// Sets one known key in a config file, in place. The file is created when missing.
+///
+/// # Errors
+/// * If `key` is not in [`CONFIG_KEYS`] or the value does not fit it
+/// * If the file cannot be read, parsed or written
+pub fn set_file_value(path: &Path, key: &str, value: &str) -> Result<()> {
+    let key = CONFIG_KEYS
+        .iter()
+        .find(|known| known.key == key)
+        .ok_or_else(|| RonaError::InvalidInput(format!("Unknown config key `{key}`")))?;
+    let mut doc = read_document(path)?;
+    set_value(&mut doc, key, Some(value))?;
+    write_document(path, &doc)
+}
+
+/// Parses a config file for editing; an empty document when the file is missing.
+fn read_document(path: &Path) -> Result<DocumentMut> {
+    let content = if path.exists() {
+        std::fs::read_to_string(path)?
+    } else {
+        String::new()
+    };
+    content
+        .parse()
+        .map_err(|e| RonaError::InvalidInput(format!("Cannot parse {}: {e}", path.display())))
+}
+
+/// Writes an edited config document, creating the parent directory when needed.
+fn write_document(path: &Path, doc: &DocumentMut) -> Result<()> {
+    ensure_writable("write the config file")?;
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    std::fs::write(path, doc.to_string())?;
+    Ok(())
+}
+
+/// Prompts for the new value of a key. `None` removes the key, so the default applies.
+fn prompt_value(key: &ConfigKey, current: Option<String>) -> Result<Option<String>> {
+    let default_item = format!("default ({})", key.default);
+    let cancelled = |_| RonaError::UserCancelled;
+    match key.kind {
+        ValueKind::Bool | ValueKind::Choice(_) => {
+            let choices: &[&str] = match key.kind {
+                ValueKind::Choice(choices) => choices,
+                _ => &["true", "false"],
+            };
+            let mut items = vec![default_item.as_str()];
+            items.extend(choices);
+            let selected = current
+                .as_deref()
+                .and_then(|c| choices.iter().position(|choice| *choice == c))
+                .map_or(0, |index| index + 1);
+            let index = Select::with_theme(&prompt_theme())
+                .with_prompt(key.key)
+                .items(&items)
+                .default(selected)
+                .interact()
+                .map_err(cancelled)?;
+            Ok((index > 0).then(|| items[index].to_string()))
+        }
+        ValueKind::MultiChoice(choices) => {
+            let current = current.unwrap_or_default();
+            let checked: Vec<bool> = choices
+                .iter()
+                .map(|choice| current.split(", ").any(|c| c == *choice))
+                .collect();
+            let picked = MultiSelect::with_theme(&prompt_theme())
+                .with_prompt(key.key)
+                .items(choices)
+                .defaults(&checked)
+                .interact()
+                .map_err(cancelled)?;
+            Ok((!picked.is_empty()).then(|| {
+                picked
+                    .iter()
+                    .map(|&index| choices[index])
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            }))
+        }
+        ValueKind::Text | ValueKind::Integer | ValueKind::List => {
+            let input: String = Input::with_theme(&prompt_theme())
+                .with_prompt(format!(
+                    "{} (empty for the default: {})",
+                    key.key, key.default
+                ))
+                .with_initial_text(current.unwrap_or_default())
+                .allow_empty(true)
+                .interact_text()
+                .map_err(cancelled)?;
+            Ok((!input.trim().is_empty()).then_some(input))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(name: &str) -> Result<&'static ConfigKey> {
+        CONFIG_KEYS
+            .iter()
+            .find(|key| key.key == name)
+            .ok_or_else(|| RonaError::InvalidInput(format!("no key {name}")))
+    }
+
+    fn parse(content: &str) -> Result<DocumentMut> {
+        content
+            .parse()
+            .map_err(|e: toml_edit::TomlError| RonaError::InvalidInput(e.to_string()))
+    }
+
+    #[test]
+    fn test_set_value_keeps_comments() -> Result<()> {
+        let mut doc = parse(concat!(
+            "# Team settings\n",
+            "editor = \"vim\" # everyone has vim\n",
+            "strict_templates = true\n",
+            "\n",
+            "[todo]\n",
+            "# Be strict\n",
+            "on_commit = \"block\"\n",
+        ))?;
+
+        set_value(&mut doc, key("editor")?, Some("zed"))?;
+        set_value(&mut doc, key("strict_templates")?, None)?;
+        set_value(&mut doc, key("todo.on_commit")?, Some("append"))?;
+        set_value(&mut doc, key("commit_types")?, Some("feat, fix,, docs"))?;
+        set_value(&mut doc, key("commit_size.max_files")?, Some("30"))?;
+
+        let written = doc.to_string();
+        assert!(
+            written.starts_with("# Team settings\neditor = \"zed\" # everyone has vim\n"),
+            "{written}"
+        );
+        assert!(!written.contains("strict_templates"), "{written}");
+        assert!(
+            written.contains("[todo]\n# Be strict\non_commit = \"append\"\n"),
+            "{written}"
+        );
+        assert!(
+            written.contains("[commit_size]\nmax_files = 30\n"),
+            "{written}"
+        );
+
+        let doc = parse(&written)?;
+        assert_eq!(
+            current_value(&doc, "commit_types").as_deref(),
+            Some("feat, fix, docs")
+        );
+        assert_eq!(
+            current_value(&doc, "commit_size.max_files").as_deref(),
+            Some("30")
+        );
+        assert_eq!(current_value(&doc, "lang"), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_preset_choices_match_presets() -> Result<()> {
+        let ValueKind::MultiChoice(choices) = key("preset")?.kind else {
+            return Err(RonaError::InvalidInput(
+                "preset is not a multi-choice".into(),
+            ));
+        };
+        let names: Vec<&str> = crate::presets::PRESETS.iter().map(|p| p.name).collect();
+        assert_eq!(choices, names.as_slice());
+        Ok(())
+    }
+
+    #[test]
+    fn test_set_value_rejects_invalid_input() -> Result<()> {
+        let mut doc = parse("")?;
+        assert!(set_value(&mut doc, key("strict_templates")?, Some("yes")).is_err());
+        assert!(set_value(&mut doc, key("commit_size.max_files")?, Some("-1")).is_err());
+        assert!(set_value(&mut doc, key("todo.on_commit")?, Some("warn")).is_err());
+        assert!(set_value(&mut doc, key("preset")?, Some("rust, cobol")).is_err());
+        set_value(&mut doc, key("preset")?, Some("rust"))?;
+        assert_eq!(doc.to_string(), "preset = [\"rust\"]\n");
+        Ok(())
+    }
+}
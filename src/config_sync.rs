@@ -0,0 +1,234 @@
+//! Shared Configuration Sync
+//!
+//! `rona config sync` keeps the project `.rona.toml` in line with the canonical one a
+//! team maintains, downloaded from `[config_sync] url` or read from a branch of a
+//! remote (see [`crate::git::shared_config`]).
+//!
+//! The local `[config_sync]` table is kept when the canonical file has none, so the
+//! next sync still knows where to look.
+
+use std::{
+    fmt,
+    io::Write,
+    process::{Command, Stdio},
+};
+
+use toml_edit::DocumentMut;
+
+use crate::{
+    config::ConfigSyncConfig,
+    errors::{Result, RonaError},
+    git::shared_config::{DEFAULT_CONFIG_BRANCH, fetch_branch_file},
+    notify::curl_quote,
+};
+
+/// Where the canonical config is read from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SyncSource {
+    /// Downloaded from a URL
+    Url(String),
+    /// Read from `path` in `branch` of `remote`, a remote name or URL
+    Branch {
+        remote: String,
+        branch: String,
+        path: String,
+    },
+}
+
+impl SyncSource {
+    /// The source of `[config_sync]`, with the given values overriding it. A URL wins
+    /// over a branch.
+    #[must_use]
+    pub fn new(
+        config: &ConfigSyncConfig,
+        url: Option<String>,
+        remote: Option<String>,
+        branch: Option<String>,
+    ) -> Self {
+        if let Some(url) = url.or_else(|| {
+            // A branch asked for on the command line wins over a configured URL.
+            (remote.is_none() && branch.is_none())
+                .then(|| config.url.clone())
+                .flatten()
+        }) {
+            return Self::Url(url);
+        }
+        Self::Branch {
+            remote: remote
+                .or_else(|| config.remote.clone())
+                .unwrap_or_else(|| "origin".to_string()),
+            branch: branch
+                .or_else(|| config.branch.clone())
+                .unwrap_or_else(|| DEFAULT_CONFIG_BRANCH.to_string()),
+            path: config
+                .path
+                .clone()
+                .unwrap_or_else(|| ".rona.toml".to_string()),
+        }
+    }
+
+    /// Reads the canonical config.
+    ///
+    /// # Errors
+    /// * If the download or the fetch fails
+    pub fn fetch(&self) -> Result<String> {
+        match self {
+            Self::Url(url) => download(url),
+            Self::Branch {
+                remote,
+                branch,
+                path,
+            } => fetch_branch_file(remote, branch, path),
+        }
+    }
+}
+
+impl fmt::Display for SyncSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Url(url) => write!(f, "{url}"),
+            Self::Branch {
+                remote,
+                branch,
+                path,
+            } => write!(f, "{path} on branch '{branch}' of '{remote}'"),
+        }
+    }
+}
+
+/// Downloads a file with `curl`, the same way push notifications are posted: the
+/// URL goes through a config on stdin, so it is never read as an option. Only
+/// `http://` and `https://` URLs are fetched, redirects included.
+///
+/// # Errors
+/// * If the URL starts with `-` or is not an `http(s)://` URL
+/// * If `curl` cannot be started or the request fails
+fn download(url: &str) -> Result<String> {
+    if url.starts_with('-') {
+        return Err(RonaError::InvalidInput(format!(
+            "[config_sync] url cannot start with '-': {url}"
+        )));
+    }
+    let scheme = url
+        .split_once("://")
+        .map(|(scheme, _)| scheme.to_ascii_lowercase());
+    if !matches!(scheme.as_deref(), Some("http" | "https")) {
+        return Err(RonaError::InvalidInput(format!(
+            "[config_sync] url must be an http:// or https:// URL: {url}"
+        )));
+    }
+
+    let mut child = Command::new("curl")
+        .args([
+            "--silent",
+            "--show-error",
+            "--fail",
+            "--location",
+            "--proto",
+            "=http,https",
+            "--proto-redir",
+            "=http,https",
+            "--max-time",
+            "10",
+            "--config",
+            "-",
+        ])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(format!("url = {}\n", curl_quote(url)).as_bytes())?;
+    }
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        return Err(RonaError::InvalidInput(format!(
+            "Cannot download {url}: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// The canonical config with the `[config_sync]` table of `local` added when it has
+/// none. The canonical config is returned as is when it has its own, or when `local`
+/// has none or does not parse.
+///
+/// # Errors
+/// * If the canonical config is not valid TOML
+pub fn with_sync_section(local: Option<&str>, canonical: &str) -> Result<String> {
+    let mut doc: DocumentMut = canonical.parse().map_err(|e| {
+        RonaError::InvalidInput(format!("The canonical config is not valid TOML: {e}"))
+    })?;
+    if doc.contains_key("config_sync") {
+        return Ok(canonical.to_string());
+    }
+    let Some(section) = local
+        .and_then(|local| local.parse::<DocumentMut>().ok())
+        .and_then(|local| local.get("config_sync").cloned())
+    else {
+        return Ok(canonical.to_string());
+    };
+    doc.insert("config_sync", section);
+    Ok(doc.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sync_source() {
+        let config = ConfigSyncConfig {
+            url: Some("https://example.com/rona.toml".to_string()),
+            ..ConfigSyncConfig::default()
+        };
+        assert_eq!(
+            SyncSource::new(&config, None, None, None),
+            SyncSource::Url("https://example.com/rona.toml".to_string())
+        );
+        assert_eq!(
+            SyncSource::new(&config, None, None, Some("main".to_string())).to_string(),
+            ".rona.toml on branch 'main' of 'origin'"
+        );
+        assert_eq!(
+            SyncSource::new(&ConfigSyncConfig::default(), None, None, None).to_string(),
+            ".rona.toml on branch 'rona-config' of 'origin'"
+        );
+    }
+
+    #[test]
+    fn test_download_rejects_other_urls() {
+        for url in [
+            "-K/etc/passwd",
+            "--config=/tmp/x",
+            "file:///etc/passwd",
+            "ftp://example.com/rona.toml",
+            "example.com/rona.toml",
+        ] {
+            assert!(
+                matches!(download(url), Err(RonaError::InvalidInput(_))),
+                "{url}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_with_sync_section() -> Result<()> {
+        let local = "editor = \"vim\"\n\n[config_sync]\nbranch = \"conventions\"\n";
+        let canonical = "commit_types = [\"feat\", \"fix\"]\n";
+        assert_eq!(
+            with_sync_section(Some(local), canonical)?,
+            "commit_types = [\"feat\", \"fix\"]\n\n[config_sync]\nbranch = \"conventions\"\n"
+        );
+
+        let canonical_with_section = "[config_sync]\nurl = \"https://example.com\"\n";
+        assert_eq!(
+            with_sync_section(Some(local), canonical_with_section)?,
+            canonical_with_section
+        );
+        assert_eq!(with_sync_section(None, canonical)?, canonical);
+        assert!(with_sync_section(None, "commit_types = [").is_err());
+        Ok(())
+    }
+}
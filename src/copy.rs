@@ -0,0 +1,130 @@
+//! Commit References
+//!
+//! `rona copy` puts a reference to a commit on the clipboard, for pull request
+//! descriptions and review comments. A format is a template over the commit's
+//! `{sha}`, `{short_sha}`, `{subject}`, `{body}`, `{author}`, `{email}` and `{date}`.
+//!
+//! Four formats are built in: `reference` (`abc1234 ("fix: handle empty index")`),
+//! `sha`, `short` and `subject`. `[copy] formats` adds named ones or replaces the
+//! built-in ones, and `--format` also takes a template directly.
+
+use std::collections::BTreeMap;
+
+use crate::{
+    errors::{Result, RonaError},
+    git::CommitReference,
+    template::{CopyTemplateVariables, process_copy_template, validate_copy_template},
+};
+
+/// Format `rona copy` uses when neither `--format` nor `[copy] format` is given.
+pub const DEFAULT_COPY_FORMAT: &str = "reference";
+
+/// The built-in formats, by name.
+pub const BUILTIN_COPY_FORMATS: [(&str, &str); 4] = [
+    ("reference", "{short_sha} (\"{subject}\")"),
+    ("sha", "{sha}"),
+    ("short", "{short_sha}"),
+    ("subject", "{subject}"),
+];
+
+/// The names of the built-in and configured formats, sorted.
+#[must_use]
+pub fn copy_format_names(formats: &BTreeMap<String, String>) -> Vec<&str> {
+    let mut names: Vec<&str> = BUILTIN_COPY_FORMATS
+        .iter()
+        .map(|(name, _)| *name)
+        .chain(formats.keys().map(String::as_str))
+        .collect();
+    names.sort_unstable();
+    names.dedup();
+    names
+}
+
+/// The template of `format`: a configured format, else a built-in one, else `format`
+/// itself when it is a template (contains a `{`).
+///
+/// # Errors
+/// * If `format` is neither a known format name nor a template
+pub fn copy_template<'a>(
+    format: &'a str,
+    formats: &'a BTreeMap<String, String>,
+) -> Result<&'a str> {
+    if let Some(template) = formats.get(format) {
+        return Ok(template);
+    }
+    if let Some((_, template)) = BUILTIN_COPY_FORMATS
+        .iter()
+        .find(|(name, _)| *name == format)
+    {
+        return Ok(template);
+    }
+    if format.contains('{') {
+        return Ok(format);
+    }
+    Err(RonaError::InvalidInput(format!(
+        "Unknown copy format '{format}' (formats: {}); pass a template such as \"{{short_sha}} {{subject}}\" instead",
+        copy_format_names(formats).join(", ")
+    )))
+}
+
+/// Renders a commit with a format template.
+///
+/// # Errors
+/// * If the template uses unknown variables or malformed conditional blocks
+pub fn render_copy(template: &str, commit: &CommitReference) -> Result<String> {
+    validate_copy_template(template)?;
+    let variables = CopyTemplateVariables {
+        sha: commit.sha.clone(),
+        short_sha: commit.short_sha.clone(),
+        subject: commit.subject.clone(),
+        body: commit.body.clone(),
+        author: commit.author.clone(),
+        email: commit.email.clone(),
+        date: commit.date.clone(),
+    };
+    process_copy_template(template, &variables)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_copy_template() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let commit = CommitReference {
+            sha: "abc1234def".to_string(),
+            short_sha: "abc1234".to_string(),
+            subject: "fix: handle empty index".to_string(),
+            body: String::new(),
+            author: "Jane".to_string(),
+            email: "jane@example.com".to_string(),
+            date: "2026-01-02".to_string(),
+        };
+        let formats = BTreeMap::from([
+            ("short".to_string(), "{short_sha} by {author}".to_string()),
+            ("md".to_string(), "[`{short_sha}`] {subject}".to_string()),
+        ]);
+
+        let render = |format| render_copy(copy_template(format, &formats)?, &commit);
+        assert_eq!(
+            render(DEFAULT_COPY_FORMAT)?,
+            "abc1234 (\"fix: handle empty index\")"
+        );
+        assert_eq!(render("sha")?, "abc1234def");
+        // A configured format replaces the built-in one of the same name
+        assert_eq!(render("short")?, "abc1234 by Jane");
+        assert_eq!(render("md")?, "[`abc1234`] fix: handle empty index");
+        assert_eq!(
+            render("{date} {subject}")?,
+            "2026-01-02 fix: handle empty index"
+        );
+
+        assert!(copy_template("long", &formats).is_err());
+        assert!(render("{title}").is_err());
+        assert_eq!(
+            copy_format_names(&formats),
+            ["md", "reference", "sha", "short", "subject"]
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,316 @@
+//! Editor
+//!
+//! Opens the commit message file in an editor. When the configured editor cannot be
+//! started, for example because it is not installed on this machine, rona falls back
+//! to `$VISUAL`, `$EDITOR` and git's `core.editor`, then to a message prompt in the
+//! terminal. An editor found that way can be saved to the config.
+
+use std::{path::Path, process::Command};
+
+use colored::Colorize;
+use dialoguer::{Confirm, Input};
+
+use crate::{
+    config::Config,
+    errors::{Result, RonaError},
+    git::is_read_only,
+    theme::{prompt_theme, success_mark},
+};
+
+/// An editor command and where it was found.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EditorCandidate {
+    /// Where the command comes from, e.g. `$EDITOR`
+    pub source: &'static str,
+    /// The command, possibly with arguments, e.g. `code --wait`
+    pub command: String,
+}
+
+/// The editors to try, in order: the configured one, `$VISUAL`, `$EDITOR`, then git's
+/// `core.editor`. Empty values and repeated commands are left out.
+#[must_use]
+pub fn editor_candidates(
+    configured: Option<String>,
+    visual: Option<String>,
+    editor: Option<String>,
+    core_editor: Option<String>,
+) -> Vec<EditorCandidate> {
+    let mut candidates: Vec<EditorCandidate> = Vec::new();
+    for (source, command) in [
+        ("config", configured),
+        ("$VISUAL", visual),
+        ("$EDITOR", editor),
+        ("core.editor", core_editor),
+    ] {
+        let Some(command) = command.map(|c| c.trim().to_string()) else {
+            continue;
+        };
+        if !command.is_empty() && candidates.iter().all(|c| c.command != command) {
+            candidates.push(EditorCandidate { source, command });
+        }
+    }
+    candidates
+}
+
+/// The editor used when none is configured: the first of `$VISUAL`, `$EDITOR` and
+/// git's `core.editor` that is set.
+#[must_use]
+pub fn fallback_editor() -> Option<EditorCandidate> {
+    editor_candidates(
+        None,
+        env_value("VISUAL"),
+        env_value("EDITOR"),
+        core_editor(),
+    )
+    .into_iter()
+    .next()
+}
+
+/// Opens the commit message file in the first editor of [`editor_candidates`] that can
+/// be started and waits for it to exit. Returns `false` when the message was prompted
+/// for instead.
+///
+/// When the configured editor could not be started but a fallback could, the user is
+/// asked whether to save the fallback as the editor. When none can be started, the
+/// message is asked for in the terminal and appended to the first line of the file.
+///
+/// # Errors
+/// * If the editor cannot be waited for or exits with an error
+/// * If no editor can be started and the terminal is not interactive
+pub fn open_in_editor(path: &Path, config: &Config) -> Result<bool> {
+    if let Err(tried) = try_editors(path, config)? {
+        println!(
+            "{} No editor could be started{tried}; asking for the message here instead",
+            "WARNING:".yellow().bold(),
+        );
+        prompt_inline_message(path)?;
+        return Ok(false);
+    }
+    Ok(true)
+}
+
+/// Opens any file in the first editor of [`editor_candidates`] that can be started and
+/// waits for it to exit, offering to save a fallback editor like [`open_in_editor`].
+///
+/// # Errors
+/// * If no editor can be started, it cannot be waited for or it exits with an error
+pub fn open_file_in_editor(path: &Path, config: &Config) -> Result<()> {
+    try_editors(path, config)?.map_err(|tried| {
+        RonaError::InvalidInput(format!(
+            "No editor could be started{tried}; run `rona set-editor <editor>`"
+        ))
+    })
+}
+
+/// Tries each editor candidate on `path` in turn. The inner `Err` lists the editors
+/// tried (` (tried `a`, `b`)`, empty when there was none) when none could be started.
+///
+/// # Errors
+/// * If an editor was started but cannot be waited for or exits with an error
+/// * If a fallback editor cannot be saved to the config
+fn try_editors(path: &Path, config: &Config) -> Result<std::result::Result<(), String>> {
+    let configured = config.get_editor().ok();
+    let candidates = editor_candidates(
+        configured.clone(),
+        env_value("VISUAL"),
+        env_value("EDITOR"),
+        core_editor(),
+    );
+
+    for (index, candidate) in candidates.iter().enumerate() {
+        if spawn_editor(&candidate.command, path)? {
+            if configured.is_some() && index > 0 {
+                offer_to_save(candidate, config)?;
+            }
+            return Ok(Ok(()));
+        }
+        println!(
+            "{} Could not start editor `{}` ({}), trying the next one",
+            "WARNING:".yellow().bold(),
+            candidate.command,
+            candidate.source
+        );
+    }
+
+    let tried = candidates
+        .iter()
+        .map(|c| format!("`{}`", c.command))
+        .collect::<Vec<_>>()
+        .join(", ");
+    Ok(Err(if tried.is_empty() {
+        String::new()
+    } else {
+        format!(" (tried {tried})")
+    }))
+}
+
+/// Starts an editor on `path` and waits for it. `Ok(false)` when it cannot be started.
+///
+/// Like git, the command runs as `sh -c '<command> "$@"'` with the path as argument,
+/// so it may quote its arguments or a program path with spaces. The shell exits with
+/// 126 or 127 when the program cannot be run, which counts as not started.
+///
+/// # Errors
+/// * If the editor was started but cannot be waited for
+/// * If the editor exits with an error, e.g. `:cq` in vim, so the edit is not used
+fn spawn_editor(command: &str, path: &Path) -> Result<bool> {
+    if command.trim().is_empty() {
+        return Ok(false);
+    }
+    let Ok(mut child) = Command::new("sh")
+        .arg("-c")
+        .arg(format!("{command} \"$@\""))
+        .arg(command)
+        .arg(path)
+        .spawn()
+    else {
+        return Ok(false);
+    };
+    let status = child.wait().map_err(|e| RonaError::CommandFailed {
+        command: format!("Failed to wait for editor '{command}': {e}"),
+    })?;
+    match status.code() {
+        Some(0) => Ok(true),
+        Some(126 | 127) => Ok(false),
+        _ => Err(RonaError::CommandFailed {
+            command: format!("Editor '{command}' exited with {status}; the edit was not used"),
+        }),
+    }
+}
+
+/// Asks whether to save a fallback editor to the config, and saves it on consent.
+///
+/// # Errors
+/// * If the config file cannot be written
+fn offer_to_save(candidate: &EditorCandidate, config: &Config) -> Result<()> {
+    if is_read_only() || config.dry_run {
+        return Ok(());
+    }
+    let save = Confirm::with_theme(&prompt_theme())
+        .with_prompt(format!(
+            "Use `{}` (from {}) as the editor from now on?",
+            candidate.command, candidate.source
+        ))
+        .default(true)
+        .interact()
+        .unwrap_or(false);
+    if save {
+        config.set_editor(&candidate.command)?;
+        println!("{} Editor set to {}", success_mark(), candidate.command);
+    } else {
+        println!(
+            "Run `rona set-editor \"{}\"` to keep using it.",
+            candidate.command
+        );
+    }
+    Ok(())
+}
+
+/// Asks for the commit message in the terminal and appends it to the first line of
+/// the file, where it would have been typed in the editor.
+///
+/// # Errors
+/// * If the terminal is not interactive
+/// * If the file cannot be read or written
+fn prompt_inline_message(path: &Path) -> Result<()> {
+    let message: String = Input::with_theme(&prompt_theme())
+        .with_prompt("Commit message")
+        .interact_text()
+        .map_err(|e| {
+            RonaError::InvalidInput(format!(
+                "No editor could be started and the message cannot be prompted for ({e}); \
+                 install an editor or run `rona set-editor <editor>`"
+            ))
+        })?;
+
+    let content = std::fs::read_to_string(path)?;
+    let (first, rest) = content.split_once('\n').unwrap_or((&content, ""));
+    std::fs::write(
+        path,
+        format!("{} {}\n{rest}", first.trim_end(), message.trim()),
+    )?;
+    Ok(())
+}
+
+/// An environment variable, `None` when unset or empty.
+fn env_value(name: &str) -> Option<String> {
+    std::env::var(name)
+        .ok()
+        .filter(|value| !value.trim().is_empty())
+}
+
+/// git's `core.editor`, `None` when unset.
+fn core_editor() -> Option<String> {
+    let output = Command::new("git")
+        .args(["config", "--get", "core.editor"])
+        .output()
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_editor_candidates() {
+        let candidates = editor_candidates(
+            Some("zed".to_string()),
+            Some(String::new()),
+            Some("code --wait".to_string()),
+            Some("zed".to_string()),
+        );
+        assert_eq!(
+            candidates,
+            vec![
+                EditorCandidate {
+                    source: "config",
+                    command: "zed".to_string()
+                },
+                EditorCandidate {
+                    source: "$EDITOR",
+                    command: "code --wait".to_string()
+                },
+            ]
+        );
+        assert!(editor_candidates(None, None, None, None).is_empty());
+    }
+
+    #[test]
+    fn test_spawn_editor_missing_program() -> Result<()> {
+        let path = Path::new("commit_message.md");
+        assert!(!spawn_editor("rona-no-such-editor --wait", path)?);
+        assert!(!spawn_editor("   ", path)?);
+        assert!(spawn_editor("true", path)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_spawn_editor_failing_exit() {
+        let path = Path::new("commit_message.md");
+        let error = spawn_editor("false", path).err().map(|e| e.to_string());
+        assert!(
+            error.is_some_and(|e| e.contains("Editor 'false' exited with")),
+            "a failing editor must not count as an accepted edit"
+        );
+    }
+
+    #[test]
+    fn test_spawn_editor_quoted_command() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let dir = dir.path().join("my files");
+        std::fs::create_dir(&dir)?;
+        let source = dir.join("edited message.md");
+        std::fs::write(&source, "feat: edited\n")?;
+        let path = dir.join("commit message.md");
+
+        // Quoted arguments and the path with spaces reach the program whole.
+        let command = format!("cp '{}'", source.display());
+        assert!(spawn_editor(&command, &path)?);
+        assert_eq!(std::fs::read_to_string(&path)?, "feat: edited\n");
+        Ok(())
+    }
+}
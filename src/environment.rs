@@ -0,0 +1,158 @@
+//! Environment
+//!
+//! `rona env` prints what rona resolved for the current directory: the repository
+//! and git directories, the config files in effect, the commit template and language,
+//! the editor, how commits are signed, and the guards, scope and status source that
+//! apply. It is a quick reference to paste in bug reports, and with `--json` a stable
+//! document for scripts.
+
+use std::process::Command;
+
+use serde::Serialize;
+
+use crate::{
+    config::Config,
+    editor::fallback_editor,
+    git::{
+        Feature, Signing, commit::is_gpg_signing_available, denied_by, find_git_root,
+        get_top_level_path, is_read_only, path_scope, sparse::sparse_cone,
+    },
+};
+
+/// A config file in effect, in loading order.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ConfigFile {
+    pub path: String,
+    /// Why the file is loaded, e.g. `global`, `project` or `extends`
+    pub origin: String,
+}
+
+/// The editor `rona generate` opens, and where it was found.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct EditorChoice {
+    pub command: String,
+    /// `config`, `$VISUAL`, `$EDITOR` or `core.editor`
+    pub source: String,
+}
+
+/// A feature a `[[guards]]` entry denies here.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DeniedFeature {
+    pub feature: String,
+    pub guard: String,
+}
+
+/// What rona resolved for the current directory.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize)]
+pub struct Environment {
+    /// Version of rona
+    pub version: String,
+    /// Output of `git --version`, `None` when git cannot be run
+    pub git_version: Option<String>,
+    /// Root of the working tree, `None` outside a repository
+    pub repo_root: Option<String>,
+    /// The git directory, `None` outside a repository
+    pub git_dir: Option<String>,
+    pub config_files: Vec<ConfigFile>,
+    /// The `[templates]` entry the commit template comes from, `None` for
+    /// `commit_template` or the built-in one
+    pub template: Option<String>,
+    /// The language of `--lang` or `lang`
+    pub lang: Option<String>,
+    /// `None` when no editor is configured or found
+    pub editor: Option<EditorChoice>,
+    /// `auto`, `required`, `off` (a guard denies signing) or `denied` (required, but
+    /// a guard denies it)
+    pub signing: String,
+    /// Whether git has a `user.signingkey`
+    pub signing_key: bool,
+    pub read_only: bool,
+    pub denied: Vec<DeniedFeature>,
+    /// The `--scope` name and directory, `None` without one
+    pub scope: Option<String>,
+    /// Whether the checkout is a cone-mode sparse checkout
+    pub sparse_checkout: bool,
+    /// Where `rona -l` reads the changed files: `daemon` or `git status`
+    pub status_source: String,
+}
+
+impl Environment {
+    /// Reads the environment of the current directory under `config`. Anything that
+    /// cannot be read is reported as missing instead of failing.
+    #[must_use]
+    pub fn read(config: &Config) -> Self {
+        let path = |path: std::path::PathBuf| path.display().to_string();
+        let signing = match Signing::new(false, config.project_config.require_signed) {
+            Ok(Signing::Auto) => "auto",
+            Ok(Signing::Required) => "required",
+            Ok(Signing::Off) => "off",
+            Err(_) => "denied",
+        };
+        let editor = config
+            .project_config
+            .editor
+            .clone()
+            .filter(|editor| !editor.trim().is_empty())
+            .map(|command| EditorChoice {
+                command,
+                source: "config".to_string(),
+            })
+            .or_else(|| {
+                fallback_editor().map(|candidate| EditorChoice {
+                    command: candidate.command,
+                    source: candidate.source.to_string(),
+                })
+            });
+
+        Self {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            git_version: git_version(),
+            repo_root: get_top_level_path().ok().map(path),
+            git_dir: find_git_root().ok().map(path),
+            config_files: config
+                .layers
+                .iter()
+                .map(|layer| ConfigFile {
+                    path: path(layer.path.clone()),
+                    origin: layer.origin.clone(),
+                })
+                .collect(),
+            template: config.named_template().map(ToString::to_string),
+            lang: config.project_config.lang.clone(),
+            editor,
+            signing: signing.to_string(),
+            signing_key: is_gpg_signing_available(),
+            read_only: is_read_only(),
+            denied: Feature::ALL
+                .iter()
+                .filter_map(|&feature| {
+                    denied_by(feature).map(|guard| DeniedFeature {
+                        feature: feature.name().to_string(),
+                        guard,
+                    })
+                })
+                .collect(),
+            scope: path_scope().map(|scope| format!("{} ({})", scope.name, scope.dir)),
+            sparse_checkout: sparse_cone().is_ok_and(|cone| cone.is_some()),
+            status_source: status_source().to_string(),
+        }
+    }
+}
+
+/// The output of `git --version`, without its trailing newline.
+fn git_version() -> Option<String> {
+    let output = Command::new("git").arg("--version").output().ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Where `rona -l` reads the changed files from.
+fn status_source() -> &'static str {
+    #[cfg(unix)]
+    if path_scope().is_none() && crate::git::daemon::cached_status_files().is_some() {
+        return "daemon";
+    }
+    "git status"
+}
@@ -20,6 +20,16 @@ pub enum RonaError {
 
     #[error("Command execution failed: {command}")]
     CommandFailed { command: String },
+
+    #[error(
+        "Read-only mode: refusing to {action} (unset --read-only / RONA_READ_ONLY to allow it)"
+    )]
+    ReadOnly { action: String },
+
+    #[error(
+        "Refusing to {feature} in this repository: denied by the guard {guard} in the global config"
+    )]
+    Denied { feature: String, guard: String },
 }
 
 /// Configuration-related errors
@@ -88,6 +98,14 @@ pub enum GitError {
 
     #[error("Remote repository not configured - add a remote with 'git remote add origin <url>'")]
     NoRemoteConfigured,
+
+    #[error(
+        "'rona {command}' cannot run inside a git commit hook - it would modify the commit in progress"
+    )]
+    InsideCommitHook { command: String },
+
+    #[error("Signed commits are required (require_signed): {reason}")]
+    SigningRequired { reason: String },
 }
 
 /// Type alias for Result using `RonaError`
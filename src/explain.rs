@@ -0,0 +1,168 @@
+//! Status Explanations
+//!
+//! `rona explain-status` prints the current `git status` grouped by state. Each group
+//! says in plain words what the state means and lists the rona commands that act on
+//! it, for people who are new to git or to rona.
+
+use colored::Colorize;
+
+use crate::{
+    errors::Result,
+    git::{FileState, get_current_branch, get_file_states},
+};
+
+/// What a [`FileState`] means and what can be done about it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StateHelp {
+    /// Group heading
+    pub title: &'static str,
+    /// Plain-language explanation of the state
+    pub meaning: &'static str,
+    /// Commands acting on files in this state, with what each one does
+    pub commands: &'static [(&'static str, &'static str)],
+}
+
+/// The commands acting on staged changes, shared by every staged state.
+const STAGED_COMMANDS: &[(&str, &str)] = &[
+    ("rona reset <file>", "unstage it, keeping your edits"),
+    (
+        "rona -g",
+        "write commit_message.md describing the staged files",
+    ),
+    ("rona -c", "commit everything staged"),
+];
+
+/// The explanation shown for a state.
+#[must_use]
+pub const fn state_help(state: FileState) -> StateHelp {
+    match state {
+        FileState::Conflicted => StateHelp {
+            title: "Conflicted",
+            meaning: "A merge, rebase or cherry-pick changed these files on both sides and git could not combine the changes. The files contain conflict markers (<<<<<<<, =======, >>>>>>>): edit them to keep the right version and remove the markers.",
+            commands: &[
+                (
+                    "rona -a",
+                    "mark the files resolved once the markers are gone",
+                ),
+                (
+                    "git merge --abort",
+                    "give up a merge and go back to before it",
+                ),
+            ],
+        },
+        FileState::StagedNew => StateHelp {
+            title: "Staged: new files",
+            meaning: "Files git did not track before, added to the staging area (the index). The next commit adds them to the repository.",
+            commands: STAGED_COMMANDS,
+        },
+        FileState::StagedModified => StateHelp {
+            title: "Staged: modified",
+            meaning: "Changes in the staging area. The next commit records these files as they were when staged; edits made since are listed under \"Modified\" too.",
+            commands: STAGED_COMMANDS,
+        },
+        FileState::StagedDeleted => StateHelp {
+            title: "Staged: deleted",
+            meaning: "Deletions in the staging area. The next commit removes these files from the repository.",
+            commands: &[
+                ("rona reset <file>", "unstage the deletion"),
+                ("rona -c", "commit everything staged"),
+            ],
+        },
+        FileState::StagedRenamed => StateHelp {
+            title: "Staged: renamed",
+            meaning: "Files moved or renamed (old -> new). Git recognizes a rename when the content stayed mostly the same.",
+            commands: STAGED_COMMANDS,
+        },
+        FileState::Modified => StateHelp {
+            title: "Modified (not staged)",
+            meaning: "Tracked files edited since the last commit, or since they were staged. These edits are not part of the next commit until you stage them.",
+            commands: &[
+                ("rona -a", "stage every change, except patterns you pass"),
+                ("rona -a -i", "pick the files to stage from a checklist"),
+                (
+                    "rona restore <file>",
+                    "throw the edits away (cannot be undone)",
+                ),
+            ],
+        },
+        FileState::Deleted => StateHelp {
+            title: "Deleted (not staged)",
+            meaning: "Tracked files missing from disk. The deletion is not part of the next commit until you stage it.",
+            commands: &[
+                ("rona -a", "stage the deletion"),
+                ("rona restore <file>", "bring the file back"),
+            ],
+        },
+        FileState::Untracked => StateHelp {
+            title: "Untracked",
+            meaning: "New files git does not track yet. They stay out of every commit until staged; add a pattern to .gitignore for files that should never be committed.",
+            commands: &[
+                ("rona -a", "stage every change, except patterns you pass"),
+                ("rona -a -i", "pick the files to stage from a checklist"),
+            ],
+        },
+    }
+}
+
+/// Prints the status of the repository grouped by state, with explanations.
+///
+/// # Errors
+/// * If reading git status fails
+pub fn print_explained_status() -> Result<()> {
+    match get_current_branch() {
+        Ok(branch) if branch != "HEAD" => println!(
+            "On branch {}. New commits are added to this branch.",
+            branch.bold()
+        ),
+        _ => println!(
+            "Not on a branch (detached HEAD). Commits made now belong to no branch; create one with `rona branch new`."
+        ),
+    }
+
+    let states = get_file_states()?;
+    if states.is_empty() {
+        println!("\nNothing to commit: every file matches the last commit.");
+        println!("Edit some files, then run `rona -a` to stage them.");
+        return Ok(());
+    }
+
+    let mut groups: Vec<(FileState, Vec<&str>)> = Vec::new();
+    for (state, path) in &states {
+        match groups.last_mut() {
+            Some((last, paths)) if last == state => paths.push(path),
+            _ => groups.push((*state, vec![path])),
+        }
+    }
+
+    for (state, paths) in &groups {
+        let help = state_help(*state);
+        println!("\n{} ({})", help.title.bold(), paths.len());
+        println!("  {}", help.meaning);
+        for path in paths {
+            println!("    {path}");
+        }
+        let width = help
+            .commands
+            .iter()
+            .map(|(c, _)| c.len())
+            .max()
+            .unwrap_or(0);
+        for (command, what) in help.commands {
+            println!("  {}  {what}", format!("{command:<width$}").cyan());
+        }
+    }
+
+    let staged = groups.iter().any(|(state, _)| state.is_staged());
+    println!();
+    if groups
+        .iter()
+        .any(|(state, _)| *state == FileState::Conflicted)
+    {
+        println!("Next: resolve the conflicts, then stage the files with `rona -a`.");
+    } else if staged {
+        println!("Next: `rona -g` to write the commit message, then `rona -c` to commit.");
+    } else {
+        println!("Next: nothing is staged yet, so stage changes with `rona -a` or `rona -a -i`.");
+    }
+    Ok(())
+}
@@ -5,7 +5,7 @@
 
 use std::collections::{HashMap, HashSet};
 
-use dialoguer::{FuzzySelect, Input};
+use dialoguer::Input;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 
@@ -227,11 +227,7 @@ fn prompt_as_select(
     }
     options.push(OTHER_OPTION.to_string());
 
-    let index = FuzzySelect::with_theme(&crate::theme::prompt_theme())
-        .with_prompt(prompt_text)
-        .items(&options)
-        .default(0)
-        .interact_opt()
+    let index = crate::theme::select_item(&crate::theme::prompt_theme(), prompt_text, &options)
         .map_err(|_| RonaError::UserCancelled)?
         .ok_or(RonaError::UserCancelled)?;
 
@@ -0,0 +1,226 @@
+//! Changelog Fragments
+//!
+//! Towncrier-style news fragments: instead of deriving the changelog from commit
+//! subjects, each branch adds small files under `changelog.d/` describing its
+//! user-facing changes, with `rona fragment add <type> "<text>"`. At release time,
+//! `rona fragment build <version>` assembles them into a new section of `CHANGELOG.md`
+//! and deletes them.
+//!
+//! A fragment is named `<id>.<type>.md`, where `<id>` is the slug of the branch it was
+//! written on, so fragments of different branches never conflict. Further fragments of
+//! the same branch and type get a counter: `<id>.<type>.2.md`.
+
+use std::{
+    collections::BTreeMap,
+    fmt::Write,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::errors::Result;
+
+/// Directory of the fragments, from the repository root, when `[fragments]` sets none.
+pub const DEFAULT_FRAGMENT_DIR: &str = "changelog.d";
+
+/// Changelog releases are added to when `[fragments]` sets none.
+pub const DEFAULT_CHANGELOG: &str = "CHANGELOG.md";
+
+/// A fragment file waiting for the next release.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fragment {
+    /// Path of the fragment file
+    pub path: PathBuf,
+    /// The slug of the branch the fragment was written on
+    pub id: String,
+    /// The commit type the fragment is listed under
+    pub fragment_type: String,
+    /// The entry text, trimmed
+    pub text: String,
+}
+
+/// Splits a fragment file name into its id, type and counter: `login.feat.2.md` gives
+/// `("login", "feat", 2)`, and `login.feat.md` has counter 1. Other files, such as a
+/// `README.md`, give `None`.
+#[must_use]
+pub fn parse_fragment_name(name: &str) -> Option<(&str, &str, u32)> {
+    let stem = name.strip_suffix(".md")?;
+    let mut parts = stem.split('.');
+    let (id, fragment_type) = (parts.next()?, parts.next()?);
+    let counter = match (parts.next(), parts.next()) {
+        (None, None) => 1,
+        (Some(counter), None) => counter.parse().ok()?,
+        _ => return None,
+    };
+    (!id.is_empty() && !fragment_type.is_empty()).then_some((id, fragment_type, counter))
+}
+
+/// Reads the fragments of `dir`, sorted by id and type, then in the order they were
+/// written. A missing directory has none.
+///
+/// # Errors
+/// * If the directory or a fragment cannot be read
+pub fn read_fragments(dir: &Path) -> Result<Vec<Fragment>> {
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+    let mut fragments = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let Some((id, fragment_type, counter)) = parse_fragment_name(&name) else {
+            continue;
+        };
+        let text = fs::read_to_string(&path)?.trim().to_string();
+        if text.is_empty() {
+            continue;
+        }
+        let fragment = Fragment {
+            id: id.to_string(),
+            fragment_type: fragment_type.to_string(),
+            text,
+            path,
+        };
+        fragments.push((counter, fragment));
+    }
+    fragments.sort_by(|(a_counter, a), (b_counter, b)| {
+        (&a.id, &a.fragment_type, a_counter).cmp(&(&b.id, &b.fragment_type, b_counter))
+    });
+    Ok(fragments
+        .into_iter()
+        .map(|(_, fragment)| fragment)
+        .collect())
+}
+
+/// The first free fragment path for `id` and `fragment_type` in `dir`.
+#[must_use]
+pub fn next_fragment_path(dir: &Path, id: &str, fragment_type: &str) -> PathBuf {
+    let first = dir.join(format!("{id}.{fragment_type}.md"));
+    if !first.exists() {
+        return first;
+    }
+    (2..=u16::MAX)
+        .map(|counter| dir.join(format!("{id}.{fragment_type}.{counter}.md")))
+        .find(|path| !path.exists())
+        .unwrap_or(first)
+}
+
+/// Renders the changelog section of a release: a `## <version> - <date>` heading, then
+/// one `###` section per type with a bullet per fragment.
+///
+/// Types are listed in `type_order` (the commit types), then any others alphabetically.
+/// A type's heading is its entry in `titles`, else the type itself.
+#[must_use]
+pub fn render_release(
+    version: &str,
+    date: &str,
+    fragments: &[Fragment],
+    type_order: &[&str],
+    titles: &BTreeMap<String, String>,
+) -> String {
+    let mut by_type: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+    for fragment in fragments {
+        by_type
+            .entry(fragment.fragment_type.as_str())
+            .or_default()
+            .push(fragment.text.as_str());
+    }
+    let mut types: Vec<&str> = type_order
+        .iter()
+        .copied()
+        .filter(|t| by_type.contains_key(t))
+        .collect();
+    types.extend(by_type.keys().filter(|t| !type_order.contains(t)));
+
+    let mut section = format!("## {version} - {date}\n");
+    for fragment_type in types {
+        let title = titles
+            .get(fragment_type)
+            .map_or(fragment_type, String::as_str);
+        let _ = write!(section, "\n### {title}\n\n");
+        for text in &by_type[fragment_type] {
+            let _ = writeln!(section, "- {}", text.replace('\n', "\n  "));
+        }
+    }
+    section
+}
+
+/// Adds a release section to a changelog, newest first: below the `# ` title when the
+/// changelog starts with one, else at the top.
+#[must_use]
+pub fn insert_release(changelog: &str, section: &str) -> String {
+    if changelog.trim().is_empty() {
+        return format!("# Changelog\n\n{section}");
+    }
+    let (title, rest) = if changelog.starts_with("# ") {
+        changelog.split_once('\n').unwrap_or((changelog, ""))
+    } else {
+        return format!("{section}\n{changelog}");
+    };
+    let rest = rest.trim_start_matches('\n');
+    if rest.is_empty() {
+        format!("{title}\n\n{section}")
+    } else {
+        format!("{title}\n\n{section}\n{rest}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fragment(id: &str, fragment_type: &str, text: &str) -> Fragment {
+        Fragment {
+            path: PathBuf::from(format!("changelog.d/{id}.{fragment_type}.md")),
+            id: id.to_string(),
+            fragment_type: fragment_type.to_string(),
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_parse_fragment_name() {
+        assert_eq!(
+            parse_fragment_name("login.feat.md"),
+            Some(("login", "feat", 1))
+        );
+        assert_eq!(
+            parse_fragment_name("login.fix.2.md"),
+            Some(("login", "fix", 2))
+        );
+        assert_eq!(parse_fragment_name("README.md"), None);
+        assert_eq!(parse_fragment_name("login.fix.two.md"), None);
+        assert_eq!(parse_fragment_name("login.feat.txt"), None);
+    }
+
+    #[test]
+    fn test_render_and_insert_release() {
+        let fragments = [
+            fragment("crash", "fix", "Handle an empty index"),
+            fragment("login", "feat", "Add login\nwith two lines"),
+            fragment("deps", "build", "Bump clap"),
+        ];
+        let titles = BTreeMap::from([("feat".to_string(), "Features".to_string())]);
+        let section = render_release("1.2.0", "2026-10-18", &fragments, &["feat", "fix"], &titles);
+        assert_eq!(
+            section,
+            "## 1.2.0 - 2026-10-18\n\n### Features\n\n- Add login\n  with two lines\n\n\
+             ### fix\n\n- Handle an empty index\n\n### build\n\n- Bump clap\n"
+        );
+
+        assert_eq!(
+            insert_release("", "## 1.0.0\n"),
+            "# Changelog\n\n## 1.0.0\n"
+        );
+        assert_eq!(
+            insert_release("# Changelog\n\n## 0.9.0\n\n- Old\n", "## 1.0.0\n"),
+            "# Changelog\n\n## 1.0.0\n\n## 0.9.0\n\n- Old\n"
+        );
+        assert_eq!(
+            insert_release("## 0.9.0\n", "## 1.0.0\n"),
+            "## 1.0.0\n\n## 0.9.0\n"
+        );
+    }
+}
@@ -0,0 +1,312 @@
+//! Mailbox Patches
+//!
+//! Applies mailbox patches with `git am --3way --keep-non-patch`, so patches that do
+//! not apply cleanly fall back to a three-way merge and `[N] (type on branch)`
+//! subject headers survive. The mailboxes are first split with `git mailsplit` into
+//! one file per message, which lets `rona am` rewrite each subject before applying.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::{Command, Output},
+};
+
+use crate::errors::{GitError, Result, RonaError};
+
+use super::{
+    repository::{ensure_writable, git_path},
+    status::get_conflicted_files,
+};
+
+/// The directory under `.git` that holds the split messages while applying.
+const SPLIT_DIR: &str = "rona-am";
+
+/// How to resume a `git am` session stopped on a patch that did not apply.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AmResume {
+    /// Commit the resolved patch and apply the rest
+    Continue,
+    /// Drop the current patch and apply the rest
+    Skip,
+    /// Restore the branch to where it was before `rona am`
+    Abort,
+}
+
+impl AmResume {
+    /// The `git am` option that resumes this way.
+    #[must_use]
+    pub const fn flag(self) -> &'static str {
+        match self {
+            Self::Continue => "--continue",
+            Self::Skip => "--skip",
+            Self::Abort => "--abort",
+        }
+    }
+}
+
+/// The result of applying patches.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AmOutcome {
+    /// Every patch was applied, or the session was aborted
+    Done,
+    /// A patch stopped the session
+    Stopped {
+        /// git's reason, e.g. `Patch failed at 0002 add form`
+        reason: String,
+        /// Files with conflicts to resolve; empty when git could not merge at all
+        conflicted: Vec<String>,
+    },
+}
+
+/// What `git mailinfo` reads from a message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MailInfo {
+    /// The subject as `git am --keep-non-patch` would commit it: decoded, unfolded,
+    /// and without the `[PATCH n/m]` prefix
+    pub subject: String,
+    /// Whether the message carries a diff; cover letters do not
+    pub has_patch: bool,
+}
+
+/// Whether a `git am` session is waiting for `--continue`, `--skip` or `--abort`.
+///
+/// # Errors
+/// * If the git directory cannot be located
+pub fn am_in_progress() -> Result<bool> {
+    Ok(git_path("rebase-apply/applying")?.exists())
+}
+
+/// Splits mailboxes into one file per message, in order.
+///
+/// Files from a previous run are removed first.
+///
+/// # Errors
+/// * If the split directory cannot be prepared
+/// * If `git mailsplit` fails, e.g. on a file that is not a mailbox
+pub fn split_mailboxes(mailboxes: &[String]) -> Result<Vec<PathBuf>> {
+    ensure_writable("split mailboxes")?;
+
+    let dir = split_dir()?;
+    if dir.exists() {
+        fs::remove_dir_all(&dir)?;
+    }
+    fs::create_dir_all(&dir)?;
+
+    let output = Command::new("git")
+        .arg("mailsplit")
+        .arg("-b")
+        .arg(format!("-o{}", dir.display()))
+        .arg("--")
+        .args(mailboxes)
+        .output()
+        .map_err(RonaError::Io)?;
+    check("git mailsplit", &output)?;
+
+    // `git mailsplit` numbers the messages with fixed-width names, so they sort in order.
+    let mut messages: Vec<PathBuf> = fs::read_dir(&dir)?
+        .map(|entry| entry.map(|e| e.path()))
+        .collect::<std::io::Result<_>>()?;
+    messages.sort();
+    Ok(messages)
+}
+
+/// Removes the split messages.
+///
+/// # Errors
+/// * If the directory exists but cannot be removed
+pub fn remove_split_mailboxes() -> Result<()> {
+    let dir = split_dir()?;
+    if dir.exists() {
+        fs::remove_dir_all(dir)?;
+    }
+    Ok(())
+}
+
+/// Reads the subject of a message and whether it carries a patch.
+///
+/// # Errors
+/// * If the message cannot be read
+/// * If `git mailinfo` fails
+pub fn mail_info(message: &Path) -> Result<MailInfo> {
+    let dir = message.parent().unwrap_or_else(|| Path::new("."));
+    let (msg, patch) = (dir.join("mailinfo-msg"), dir.join("mailinfo-patch"));
+    let output = Command::new("git")
+        .args(["mailinfo", "-b"])
+        .arg(&msg)
+        .arg(&patch)
+        .stdin(fs::File::open(message)?)
+        .output()
+        .map_err(RonaError::Io)?;
+    check("git mailinfo", &output)?;
+    let has_patch = fs::metadata(&patch).is_ok_and(|meta| meta.len() > 0);
+    fs::remove_file(msg)?;
+    fs::remove_file(patch)?;
+
+    let subject = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .find_map(|line| line.strip_prefix("Subject: "))
+        .unwrap_or_default()
+        .to_string();
+    Ok(MailInfo { subject, has_patch })
+}
+
+/// Replaces the `Subject:` header of a message.
+///
+/// # Errors
+/// * If the message cannot be read or written
+pub fn set_message_subject(message: &Path, subject: &str) -> Result<()> {
+    ensure_writable("edit a patch subject")?;
+
+    let content = fs::read_to_string(message)?;
+    fs::write(message, replace_subject(&content, subject))?;
+    Ok(())
+}
+
+/// Applies messages with `git am --3way --keep-non-patch`.
+///
+/// # Errors
+/// * If `git am` fails without leaving a session to resume
+pub fn apply_messages(messages: &[PathBuf]) -> Result<AmOutcome> {
+    ensure_writable("apply patches")?;
+
+    let output = Command::new("git")
+        .args(["am", "--3way", "--keep-non-patch", "--"])
+        .args(messages)
+        .output()
+        .map_err(RonaError::Io)?;
+    am_outcome(&output)
+}
+
+/// Resumes a stopped `git am` session.
+///
+/// # Errors
+/// * If `git am` fails without leaving a session to resume, e.g. when conflicts
+///   are still unresolved on `--continue`
+pub fn resume_am(resume: AmResume) -> Result<AmOutcome> {
+    ensure_writable("resume git am")?;
+
+    let output = Command::new("git")
+        .args(["am", resume.flag()])
+        .output()
+        .map_err(RonaError::Io)?;
+    am_outcome(&output)
+}
+
+/// Reads the outcome of a `git am` run: done, stopped on a patch, or failed.
+fn am_outcome(output: &Output) -> Result<AmOutcome> {
+    if output.status.success() {
+        return Ok(AmOutcome::Done);
+    }
+    let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+    if !am_in_progress()? {
+        return Err(RonaError::Git(GitError::CommandFailed {
+            command: "git am".to_string(),
+            output: stderr,
+        }));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let reason = stdout
+        .lines()
+        .chain(stderr.lines())
+        .find(|line| line.starts_with("Patch failed at"))
+        .or_else(|| stderr.lines().next())
+        .unwrap_or_default()
+        .to_string();
+    Ok(AmOutcome::Stopped {
+        reason,
+        conflicted: get_conflicted_files()?,
+    })
+}
+
+fn split_dir() -> Result<PathBuf> {
+    git_path(SPLIT_DIR)
+}
+
+fn check(command: &str, output: &Output) -> Result<()> {
+    if output.status.success() {
+        return Ok(());
+    }
+    Err(RonaError::Git(GitError::CommandFailed {
+        command: command.to_string(),
+        output: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+    }))
+}
+
+/// Replaces the (possibly folded) `Subject:` header in the header block of a message.
+fn replace_subject(message: &str, subject: &str) -> String {
+    let mut result = String::with_capacity(message.len());
+    let mut in_headers = true;
+    let mut in_subject = false;
+
+    for line in message.split_inclusive('\n') {
+        if in_headers {
+            if line.trim_end().is_empty() {
+                in_headers = false;
+            } else if in_subject && line.starts_with([' ', '\t']) {
+                // Continuation of the folded subject being replaced
+                continue;
+            } else {
+                in_subject = line
+                    .get(..8)
+                    .is_some_and(|name| name.eq_ignore_ascii_case("subject:"));
+                if in_subject {
+                    result.push_str("Subject: ");
+                    result.push_str(&encode_header(subject));
+                    result.push('\n');
+                    continue;
+                }
+            }
+        }
+        result.push_str(line);
+    }
+    result
+}
+
+/// Encodes a header value as an RFC 2047 `Q` encoded word when it is not plain ASCII.
+fn encode_header(value: &str) -> String {
+    if value.is_ascii() {
+        return value.to_string();
+    }
+    let encoded: String = value
+        .bytes()
+        .map(|byte| match byte {
+            b' ' => "_".to_string(),
+            b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'-' | b'.' => (byte as char).to_string(),
+            _ => format!("={byte:02X}"),
+        })
+        .collect();
+    format!("=?UTF-8?q?{encoded}?=")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_replace_subject() {
+        let message = "From 1234 Mon Sep 17 00:00:00 2001\n\
+                       From: Jane <jane@example.com>\n\
+                       Subject: [PATCH 1/2] a long subject that git\n folded over two lines\n\
+                       \n\
+                       Subject: in the body is kept\n";
+
+        assert_eq!(
+            replace_subject(message, "[3] (feat on main) add form"),
+            "From 1234 Mon Sep 17 00:00:00 2001\n\
+             From: Jane <jane@example.com>\n\
+             Subject: [3] (feat on main) add form\n\
+             \n\
+             Subject: in the body is kept\n"
+        );
+    }
+
+    #[test]
+    fn test_encode_header() {
+        assert_eq!(encode_header("plain subject"), "plain subject");
+        assert_eq!(
+            encode_header("café au lait"),
+            "=?UTF-8?q?caf=C3=A9_au_lait?="
+        );
+    }
+}
@@ -0,0 +1,115 @@
+//! Git Attributes
+//!
+//! Reads `.gitattributes` through `git check-attr`, so macros such as `binary`,
+//! nested attribute files and `.git/info/attributes` resolve exactly as they do for
+//! git. Rona honors:
+//! - `-diff`: the file is listed in generated messages without a description line
+//! - `rona-ignore`: the file is left out of generated messages, like `.commitignore`
+//! - `merge=binary` (or `-merge`): staging the file while resolving a conflict warns,
+//!   since git kept one side whole instead of merging
+
+use std::{
+    collections::HashMap,
+    io::Write,
+    process::{Command, Stdio},
+};
+
+use crate::errors::{GitError, Result, RonaError};
+
+use super::repository::get_top_level_path;
+
+/// The custom attribute that leaves a file out of generated commit messages.
+pub const IGNORE_ATTRIBUTE: &str = "rona-ignore";
+
+/// The state of an attribute on a path, as reported by `git check-attr`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AttributeValue {
+    /// `attr` in `.gitattributes`
+    Set,
+    /// `-attr`
+    Unset,
+    /// Not mentioned for the path
+    Unspecified,
+    /// `attr=value`
+    Value(String),
+}
+
+impl AttributeValue {
+    fn parse(value: &str) -> Self {
+        match value {
+            "set" => Self::Set,
+            "unset" => Self::Unset,
+            "unspecified" => Self::Unspecified,
+            other => Self::Value(other.to_string()),
+        }
+    }
+}
+
+/// Reads one attribute for each of the given repository-relative paths.
+///
+/// # Errors
+/// * If the repository root cannot be located
+/// * If `git check-attr` fails
+pub fn attribute_values(
+    paths: &[String],
+    attribute: &str,
+) -> Result<HashMap<String, AttributeValue>> {
+    if paths.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let mut child = Command::new("git")
+        .args(["check-attr", "-z", "--stdin", attribute])
+        .current_dir(get_top_level_path()?)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(RonaError::Io)?;
+    if let Some(mut stdin) = child.stdin.take() {
+        for path in paths {
+            stdin.write_all(path.as_bytes())?;
+            stdin.write_all(b"\0")?;
+        }
+    }
+    let output = child.wait_with_output().map_err(RonaError::Io)?;
+
+    if !output.status.success() {
+        return Err(RonaError::Git(GitError::CommandFailed {
+            command: format!("git check-attr {attribute}"),
+            output: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        }));
+    }
+    Ok(parse_check_attr(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Parses `git check-attr -z` output: `path`, attribute and value, NUL-separated.
+fn parse_check_attr(output: &str) -> HashMap<String, AttributeValue> {
+    let fields: Vec<&str> = output.split('\0').collect();
+    fields
+        .chunks_exact(3)
+        .map(|entry| (entry[0].to_string(), AttributeValue::parse(entry[2])))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_check_attr() {
+        let output = "logo.png\0diff\0unset\0src/a.rs\0diff\0unspecified\0\
+                      data.bin\0merge\0binary\0docs/gen.md\0rona-ignore\0set\0";
+        let values = parse_check_attr(output);
+
+        assert_eq!(values.len(), 4);
+        assert_eq!(values["logo.png"], AttributeValue::Unset);
+        assert_eq!(values["src/a.rs"], AttributeValue::Unspecified);
+        assert_eq!(
+            values["data.bin"],
+            AttributeValue::Value("binary".to_string())
+        );
+        assert_eq!(values["docs/gen.md"], AttributeValue::Set);
+        assert!(parse_check_attr("").is_empty());
+    }
+}
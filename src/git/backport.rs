@@ -0,0 +1,192 @@
+//! Backports
+//!
+//! Git plumbing for `rona backport`: bringing release branches up to date with the
+//! remote, applying a commit without committing it so its message can be rewritten
+//! for the target branch. Pull requests are opened by [`crate::forge`].
+
+use crate::errors::{GitError, Result, RonaError};
+
+use super::{
+    branch::get_current_branch,
+    repository::{Feature, ensure_allowed, ensure_writable, git_stdout, run_git},
+};
+
+/// Where a target branch of a backport comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TargetBranch {
+    /// The local branch exists and is not behind the remote
+    Local,
+    /// The local branch was fast-forwarded to the remote one
+    Updated,
+    /// The local branch was created from the remote one
+    Created,
+}
+
+/// Resolves a revision to the full SHA of a commit.
+///
+/// # Errors
+/// * If the revision does not name a commit
+pub fn resolve_commit(revision: &str) -> Result<String> {
+    let output = run_git(&[
+        "rev-parse",
+        "--verify",
+        "--quiet",
+        &format!("{revision}^{{commit}}"),
+    ])?;
+    if !output.status.success() {
+        return Err(RonaError::InvalidInput(format!(
+            "'{revision}' is not a commit"
+        )));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Whether tracked files have no staged or unstaged changes.
+///
+/// # Errors
+/// * If `git status` fails
+pub fn is_worktree_clean() -> Result<bool> {
+    let output = git_stdout(&["status", "--porcelain", "--untracked-files=no"])?;
+    Ok(output.trim().is_empty())
+}
+
+/// Fetches a branch from the remote, ignoring branches the remote does not have.
+///
+/// # Errors
+/// * If git cannot be run
+pub fn fetch_branch(remote: &str, branch: &str) -> Result<()> {
+    ensure_writable("fetch")?;
+    ensure_allowed(Feature::Fetch)?;
+
+    // A missing remote branch is reported when the target is prepared.
+    run_git(&["fetch", "--quiet", remote, branch])?;
+    Ok(())
+}
+
+/// Makes sure a local target branch exists and is not behind the remote one.
+///
+/// The local branch is created from `<remote>/<branch>` when missing, and
+/// fast-forwarded when it is strictly behind. A local branch that diverged is left
+/// as it is.
+///
+/// # Errors
+/// * If neither the local nor the remote branch exists
+/// * If creating or fast-forwarding the branch fails
+pub fn prepare_target_branch(remote: &str, branch: &str) -> Result<TargetBranch> {
+    ensure_writable("prepare the backport branch")?;
+
+    let remote_ref = format!("refs/remotes/{remote}/{branch}");
+    let has_remote = ref_exists(&remote_ref)?;
+
+    if !ref_exists(&format!("refs/heads/{branch}"))? {
+        if !has_remote {
+            return Err(RonaError::InvalidInput(format!(
+                "Branch '{branch}' exists neither locally nor on '{remote}'"
+            )));
+        }
+        git_stdout(&["branch", "--track", branch, &format!("{remote}/{branch}")])?;
+        return Ok(TargetBranch::Created);
+    }
+
+    let behind = has_remote
+        && run_git(&["merge-base", "--is-ancestor", branch, &remote_ref])?
+            .status
+            .success()
+        && resolve_commit(branch)? != resolve_commit(&remote_ref)?;
+    if !behind {
+        return Ok(TargetBranch::Local);
+    }
+    if get_current_branch()? == branch {
+        git_stdout(&["merge", "--ff-only", "--quiet", &remote_ref])?;
+    } else {
+        // Updates the ref without a checkout, refusing anything but a fast-forward.
+        git_stdout(&["fetch", ".", &format!("{remote_ref}:refs/heads/{branch}")])?;
+    }
+    Ok(TargetBranch::Updated)
+}
+
+/// Counts the commits reachable from a revision.
+///
+/// # Errors
+/// * If `git rev-list` fails
+pub fn commit_count(revision: &str) -> Result<u32> {
+    let output = git_stdout(&["rev-list", "--count", revision])?;
+    output.trim().parse().map_err(|_| {
+        RonaError::Git(GitError::InvalidStatus {
+            output: format!("Unexpected commit count for {revision}: {output}"),
+        })
+    })
+}
+
+/// Applies a commit to the index and working tree without committing it.
+///
+/// On conflicts the changes are rolled back with `git reset --merge`.
+///
+/// # Errors
+/// * If git cannot be run
+///
+/// # Returns
+/// * `false` when the commit did not apply cleanly
+pub fn cherry_pick_no_commit(commit: &str) -> Result<bool> {
+    ensure_writable("cherry-pick")?;
+
+    let output = run_git(&["cherry-pick", "--no-commit", commit])?;
+    if output.status.success() {
+        return Ok(true);
+    }
+    git_stdout(&["reset", "--merge"])?;
+    Ok(false)
+}
+
+/// Whether the index differs from `HEAD`.
+///
+/// # Errors
+/// * If git cannot be run
+pub fn has_staged_changes() -> Result<bool> {
+    Ok(!run_git(&["diff", "--cached", "--quiet"])?.status.success())
+}
+
+/// The branch a backport is committed on when it goes through a pull request.
+#[must_use]
+pub fn backport_branch_name(short_sha: &str, target: &str) -> String {
+    format!("backport/{short_sha}/{target}")
+}
+
+/// The message of a backport commit: the re-templated subject, the original body,
+/// and a `(backport of <sha>)` footer.
+#[must_use]
+pub fn backport_message(subject: &str, body: &str, original_sha: &str) -> String {
+    let footer = format!("(backport of {original_sha})");
+    if body.trim().is_empty() {
+        format!("{subject}\n\n{footer}")
+    } else {
+        format!("{subject}\n\n{}\n\n{footer}", body.trim_end())
+    }
+}
+
+fn ref_exists(reference: &str) -> Result<bool> {
+    Ok(run_git(&["show-ref", "--verify", "--quiet", reference])?
+        .status
+        .success())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backport_message() {
+        assert_eq!(
+            backport_message("[12] (fix on release/1.x) guard", "", "abc123"),
+            "[12] (fix on release/1.x) guard\n\n(backport of abc123)"
+        );
+        assert_eq!(
+            backport_message("[12] (fix on release/1.x) guard", "Details.\n", "abc123"),
+            "[12] (fix on release/1.x) guard\n\nDetails.\n\n(backport of abc123)"
+        );
+        assert_eq!(
+            backport_branch_name("abc1234", "release/1.x"),
+            "backport/abc1234/release/1.x"
+        );
+    }
+}
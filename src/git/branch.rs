@@ -5,9 +5,12 @@
 
 use crate::{
     errors::{Result, RonaError},
-    git::handle_output,
+    git::{Feature, ensure_allowed, ensure_writable, handle_output},
+    theme::is_accessible,
 };
 use indicatif::{ProgressBar, ProgressDrawTarget};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::io::IsTerminal;
 use std::process::Command;
 use std::time::Duration;
@@ -37,6 +40,38 @@ fn try_get_default_branch() -> Result<String> {
     Ok("main".to_string())
 }
 
+/// Gets the repository's default branch, the one the remote's `HEAD` points to.
+///
+/// Reads `refs/remotes/origin/HEAD` (set by `git clone` or `git remote set-head origin
+/// --auto`), so repositories whose trunk is `master` or `develop` are handled. Without
+/// it, falls back to `init.defaultBranch`, then to "main".
+///
+/// # Errors
+/// Returns an error only if the git process cannot be spawned.
+pub fn get_default_branch() -> Result<String> {
+    let output = Command::new("git")
+        .args([
+            "symbolic-ref",
+            "--quiet",
+            "--short",
+            "refs/remotes/origin/HEAD",
+        ])
+        .output()
+        .map_err(RonaError::Io)?;
+
+    if output.status.success() {
+        let remote_head = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        if let Some(branch) = remote_head
+            .strip_prefix("origin/")
+            .filter(|branch| !branch.is_empty())
+        {
+            return Ok(branch.to_string());
+        }
+    }
+
+    try_get_default_branch()
+}
+
 /// Gets the current branch name.
 ///
 /// This function returns the name of the currently checked out branch.
@@ -126,75 +161,202 @@ pub fn get_all_branches() -> Result<Vec<String>> {
         .collect())
 }
 
-/// Formats a branch name by removing commit type prefixes.
+/// Reads the purpose of a branch, stored in `branch.<name>.description`.
 ///
-/// This function cleans up branch names that follow conventional naming patterns
-/// like `feat/feature-name`, `fix/bug-name`, etc., by removing the commit type
-/// prefix and slash, leaving just the descriptive part of the branch name.
+/// This is the same setting `git branch --edit-description` writes.
 ///
-/// # Arguments
+/// # Errors
+/// Returns an error only if the git process cannot be spawned.
+pub fn branch_description(branch: &str) -> Result<Option<String>> {
+    let output = Command::new("git")
+        .args(["config", "--get", &format!("branch.{branch}.description")])
+        .output()
+        .map_err(RonaError::Io)?;
+
+    let description = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    Ok((output.status.success() && !description.is_empty()).then_some(description))
+}
+
+/// Stores the purpose of a branch, or removes it when `description` is `None`.
 ///
-/// * `commit_types` - A slice of commit type prefixes to remove (e.g., `&["feat", "fix", "chore", "test"]`)
-/// * `branch` - The branch name to format
+/// # Errors
+/// * If git config cannot be written
+pub fn set_branch_description(branch: &str, description: Option<&str>) -> Result<()> {
+    ensure_writable("set a branch description")?;
+
+    let key = format!("branch.{branch}.description");
+    let args = description.map_or_else(
+        || vec!["config", "--unset", &key],
+        |description| vec!["config", &key, description],
+    );
+    let output = Command::new("git")
+        .args(args)
+        .output()
+        .map_err(RonaError::Io)?;
+
+    // `--unset` exits with 5 when there was nothing to remove.
+    if description.is_none() && output.status.code() == Some(5) {
+        return Ok(());
+    }
+    handle_output("config", &output)
+}
+
+/// Reads the descriptions of every branch that has one, keyed by branch name.
 ///
-/// # Returns
+/// # Errors
+/// Returns an error only if the git process cannot be spawned.
+pub fn branch_descriptions() -> Result<HashMap<String, String>> {
+    let output = Command::new("git")
+        .args(["config", "-z", "--get-regexp", r"^branch\..*\.description$"])
+        .output()
+        .map_err(RonaError::Io)?;
+
+    // Exits with 1 when no branch has a description.
+    if !output.status.success() {
+        return Ok(HashMap::new());
+    }
+    Ok(parse_branch_descriptions(&String::from_utf8_lossy(
+        &output.stdout,
+    )))
+}
+
+/// Parses `git config -z --get-regexp` output: `key\nvalue` entries, NUL-terminated.
+fn parse_branch_descriptions(output: &str) -> HashMap<String, String> {
+    output
+        .split('\0')
+        .filter_map(|entry| {
+            let (key, value) = entry.split_once('\n')?;
+            let branch = key.strip_prefix("branch.")?.strip_suffix(".description")?;
+            let value = value.trim();
+            (!value.is_empty()).then(|| (branch.to_string(), value.to_string()))
+        })
+        .collect()
+}
+
+/// Letter case applied to the formatted branch name.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BranchNameCase {
+    /// Keep the branch name as it is
+    #[default]
+    Preserve,
+    /// `Add-Login` becomes `add-login`
+    Lower,
+    /// `add-login` becomes `ADD-LOGIN`
+    Upper,
+}
+
+/// How the branch name is rendered as `{branch_name}`, declared as `[branch_name]`.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct BranchNameFormat {
+    /// Longest name kept, in characters; longer names are cut. Default: no limit.
+    #[serde(default)]
+    pub max_length: Option<usize>,
+    /// Replaces the `-` and `_` word separators, e.g. `" "` turns `add-login` into
+    /// `add login`. Default: kept as they are.
+    #[serde(default)]
+    pub separator: Option<String>,
+    /// Letter case of the name. Default: `preserve`.
+    #[serde(default)]
+    pub case: BranchNameCase,
+}
+
+/// Formats a branch name for templates by removing its commit type prefix.
+///
+/// The prefix is the first `/`-separated segment, and is removed only when it is
+/// exactly one of the commit types: `feat/login` becomes `login`, while
+/// `feature/test-fixtures` and `latest/docs` are kept whole. Only one prefix is
+/// removed, and a branch that is nothing but a type (such as `fix`) is unchanged.
+///
+/// The `format` rules then replace separators, change the case and cut the length,
+/// in that order.
+///
+/// # Arguments
 ///
-/// A formatted branch name with commit type prefixes removed
+/// * `commit_types` - The configured commit types (e.g., `&["feat", "fix", "chore", "test"]`)
+/// * `branch` - The branch name to format
+/// * `format` - The `[branch_name]` rules
 ///
 /// # Examples
 ///
 /// ```
-/// use rona::git::branch::format_branch_name;
+/// use rona::git::branch::{BranchNameFormat, format_branch_name};
 ///
 /// let commit_types = ["feat", "fix", "chore", "test"];
+/// let format = BranchNameFormat::default();
 ///
 /// assert_eq!(
-///     format_branch_name(&commit_types, "feat/user-authentication"),
+///     format_branch_name(&commit_types, "feat/user-authentication", &format),
 ///     "user-authentication"
 /// );
 ///
+/// // Only a whole first segment is a prefix
 /// assert_eq!(
-///     format_branch_name(&commit_types, "fix/memory-leak"),
-///     "memory-leak"
+///     format_branch_name(&commit_types, "feature/test-fixtures", &format),
+///     "feature/test-fixtures"
 /// );
 ///
-/// // Branch names without prefixes are unchanged
+/// // Only the first prefix is removed
 /// assert_eq!(
-///     format_branch_name(&commit_types, "main"),
-///     "main"
-/// );
-///
-/// // Multiple prefixes are handled
-/// assert_eq!(
-///     format_branch_name(&commit_types, "feat/fix/complex-branch"),
-///     "fix/complex-branch"  // Only first matching prefix is removed
-/// );
-///
-/// // Works with any number of commit types
-/// assert_eq!(
-///     format_branch_name(&["feat", "fix"], "fix/bug"),
-///     "bug"
+///     format_branch_name(&commit_types, "feat/fix/complex-branch", &format),
+///     "fix/complex-branch"
 /// );
 /// ```
-///
-/// # Use Cases
-///
-/// This is particularly useful for:
-/// - Generating clean commit messages
-/// - Creating readable branch displays in UI
-/// - Normalizing branch names for processing
 #[must_use]
-pub fn format_branch_name(commit_types: &[&str], branch: &str) -> String {
-    let mut formatted_branch = branch.to_owned();
-
-    for commit_type in commit_types {
-        if formatted_branch.contains(commit_type) {
-            // Remove the `/commit_type` from the branch name
-            formatted_branch = formatted_branch.replace(&format!("{commit_type}/"), "");
+pub fn format_branch_name(
+    commit_types: &[&str],
+    branch: &str,
+    format: &BranchNameFormat,
+) -> String {
+    let name = branch
+        .split_once('/')
+        .filter(|(prefix, rest)| !rest.is_empty() && commit_types.contains(prefix))
+        .map_or(branch, |(_, rest)| rest);
+
+    let name = format.separator.as_ref().map_or_else(
+        || name.to_string(),
+        |separator| name.replace(['-', '_'], separator),
+    );
+    let name = match format.case {
+        BranchNameCase::Preserve => name,
+        BranchNameCase::Lower => name.to_lowercase(),
+        BranchNameCase::Upper => name.to_uppercase(),
+    };
+    match format.max_length {
+        Some(max) if name.chars().count() > max => {
+            let cut: String = name.chars().take(max).collect();
+            // Do not end on a dangling separator.
+            let separator = format.separator.as_deref().unwrap_or_default();
+            cut.trim_end_matches(|c: char| {
+                matches!(c, '-' | '_' | '/') || c.is_whitespace() || separator.contains(c)
+            })
+            .to_string()
         }
+        _ => name,
     }
+}
 
-    formatted_branch
+/// Infers the commit type from a branch's first path segment.
+///
+/// `feat/login` yields `feat`; a branch without a known type prefix yields `None`.
+///
+/// # Arguments
+/// * `commit_types` - The configured commit types
+/// * `branch` - The branch name to inspect
+#[must_use]
+pub fn infer_commit_type<'a>(commit_types: &[&'a str], branch: &str) -> Option<&'a str> {
+    let (prefix, _) = branch.split_once('/')?;
+    commit_types.iter().copied().find(|t| *t == prefix)
+}
+
+/// Whether `name` is a valid full ref name (e.g. `refs/for/main`) according to
+/// `git check-ref-format`.
+#[must_use]
+pub fn is_valid_ref_name(name: &str) -> bool {
+    Command::new("git")
+        .args(["check-ref-format", name])
+        .output()
+        .is_ok_and(|output| output.status.success())
 }
 
 /// Sanitizes a string into a valid git branch name segment.
@@ -258,6 +420,20 @@ fn clean_branch_segment(seg: &str) -> &str {
     seg
 }
 
+/// Turns a free-form description into a branch name slug.
+///
+/// Letters are lowercased, runs of anything but ASCII letters and digits become a
+/// single `-`, and leading and trailing dashes are dropped: `"Add login (v2)!"`
+/// becomes `add-login-v2`.
+#[must_use]
+pub fn slugify(text: &str) -> String {
+    text.to_lowercase()
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
 /// Creates a branch without switching to it using `git branch`.
 ///
 /// # Arguments
@@ -270,6 +446,8 @@ fn clean_branch_segment(seg: &str) -> &str {
 pub fn git_branch_only(branch_name: &str) -> Result<()> {
     tracing::debug!("Creating branch without switching: {branch_name}");
 
+    ensure_writable("create a branch")?;
+
     let output = Command::new("git")
         .args(["branch", branch_name])
         .output()
@@ -278,6 +456,83 @@ pub fn git_branch_only(branch_name: &str) -> Result<()> {
     handle_output("branch", &output)
 }
 
+/// The remote and remote branch a local branch tracks, from `branch.<name>.remote`
+/// and `branch.<name>.merge`.
+///
+/// # Errors
+/// Returns an error only if the git process cannot be spawned.
+///
+/// # Returns
+/// * `None` when the branch tracks nothing, or tracks a local branch (remote `.`)
+pub fn branch_upstream(branch: &str) -> Result<Option<(String, String)>> {
+    let config = |key: &str| -> Result<Option<String>> {
+        let output = Command::new("git")
+            .args(["config", "--get", &format!("branch.{branch}.{key}")])
+            .output()
+            .map_err(RonaError::Io)?;
+        let value = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        Ok((output.status.success() && !value.is_empty()).then_some(value))
+    };
+
+    let (Some(remote), Some(merge)) = (config("remote")?, config("merge")?) else {
+        return Ok(None);
+    };
+    if remote == "." {
+        return Ok(None);
+    }
+    let remote_branch = merge.strip_prefix("refs/heads/").unwrap_or(&merge);
+    Ok(Some((remote, remote_branch.to_string())))
+}
+
+/// Renames a local branch with `git branch -m`.
+///
+/// git moves the `branch.<name>.*` settings along, so the description is kept and
+/// the renamed branch still tracks the old remote branch until it is pushed.
+///
+/// # Errors
+/// * If the new name is invalid or already taken
+pub fn git_rename_branch(old: &str, new: &str) -> Result<()> {
+    ensure_writable("rename a branch")?;
+
+    let output = Command::new("git")
+        .args(["branch", "-m", old, new])
+        .output()
+        .map_err(RonaError::Io)?;
+
+    handle_output("branch -m", &output)
+}
+
+/// Pushes a branch under the same name and makes it track the pushed branch.
+///
+/// # Errors
+/// * If the push fails
+pub fn git_push_tracking(remote: &str, branch: &str) -> Result<()> {
+    ensure_writable("push")?;
+    ensure_allowed(Feature::Push)?;
+
+    let output = Command::new("git")
+        .args(["push", "--quiet", "--set-upstream", remote, branch])
+        .output()
+        .map_err(RonaError::Io)?;
+
+    handle_output("push", &output)
+}
+
+/// Deletes a branch from a remote.
+///
+/// # Errors
+/// * If the push fails, e.g. when the branch is protected on the remote
+pub fn git_delete_remote_branch(remote: &str, branch: &str) -> Result<()> {
+    ensure_writable("delete a remote branch")?;
+
+    let output = Command::new("git")
+        .args(["push", "--quiet", remote, "--delete", branch])
+        .output()
+        .map_err(RonaError::Io)?;
+
+    handle_output("push --delete", &output)
+}
+
 /// Switches to a different branch using `git switch`.
 ///
 /// # Arguments
@@ -291,6 +546,8 @@ pub fn git_branch_only(branch_name: &str) -> Result<()> {
 pub fn git_switch(branch_name: &str) -> Result<()> {
     tracing::debug!("Switching to branch: {branch_name}");
 
+    ensure_writable("switch branches")?;
+
     let output = Command::new("git")
         .args(["switch", branch_name])
         .output()
@@ -315,6 +572,8 @@ pub fn git_switch(branch_name: &str) -> Result<()> {
 pub fn git_create_branch(branch_name: &str) -> Result<()> {
     tracing::debug!("Creating new branch: {branch_name}");
 
+    ensure_writable("create a branch")?;
+
     let output = Command::new("git")
         .args(["switch", "-c", branch_name])
         .output()
@@ -338,7 +597,10 @@ pub fn git_create_branch(branch_name: &str) -> Result<()> {
 pub fn git_pull(verbose: bool) -> Result<()> {
     tracing::debug!("Pulling latest changes...");
 
-    let show_spinner = !verbose && std::io::stderr().is_terminal();
+    ensure_writable("pull")?;
+    ensure_allowed(Feature::Pull)?;
+
+    let show_spinner = !verbose && std::io::stderr().is_terminal() && !is_accessible();
     let output = if show_spinner {
         let pb = ProgressBar::new_spinner();
         pb.set_draw_target(ProgressDrawTarget::stderr());
@@ -372,7 +634,10 @@ pub fn git_pull(verbose: bool) -> Result<()> {
 pub fn git_merge(branch_name: &str, verbose: bool) -> Result<()> {
     tracing::debug!("Merging {branch_name} into current branch...");
 
-    let show_spinner = !verbose && std::io::stderr().is_terminal();
+    ensure_writable("merge")?;
+    ensure_allowed(Feature::Merge)?;
+
+    let show_spinner = !verbose && std::io::stderr().is_terminal() && !is_accessible();
     let branch_owned = branch_name.to_string();
     let output = if show_spinner {
         let pb = ProgressBar::new_spinner();
@@ -409,7 +674,10 @@ pub fn git_merge(branch_name: &str, verbose: bool) -> Result<()> {
 pub fn git_rebase(branch_name: &str, verbose: bool) -> Result<()> {
     tracing::debug!("Rebasing onto {branch_name}...");
 
-    let show_spinner = !verbose && std::io::stderr().is_terminal();
+    ensure_writable("rebase")?;
+    ensure_allowed(Feature::Rebase)?;
+
+    let show_spinner = !verbose && std::io::stderr().is_terminal() && !is_accessible();
     let branch_owned = branch_name.to_string();
     let output = if show_spinner {
         let pb = ProgressBar::new_spinner();
@@ -439,7 +707,75 @@ pub fn git_rebase(branch_name: &str, verbose: bool) -> Result<()> {
 
 #[cfg(test)]
 mod tests {
-    use super::sanitize_branch_name;
+    use super::{
+        BranchNameCase, BranchNameFormat, format_branch_name, infer_commit_type,
+        parse_branch_descriptions, sanitize_branch_name, slugify,
+    };
+
+    #[test]
+    fn slugifies_descriptions() {
+        assert_eq!(slugify("Add login (v2)!"), "add-login-v2");
+        assert_eq!(slugify("  Fix  the_API  "), "fix-the-api");
+        assert_eq!(slugify("!!!"), "");
+    }
+
+    const TYPES: [&str; 4] = ["chore", "feat", "fix", "test"];
+
+    #[test]
+    fn strips_only_a_whole_type_segment() {
+        let format = BranchNameFormat::default();
+        assert_eq!(format_branch_name(&TYPES, "feat/login", &format), "login");
+        assert_eq!(
+            format_branch_name(&TYPES, "feature/test-fixtures", &format),
+            "feature/test-fixtures"
+        );
+        assert_eq!(
+            format_branch_name(&TYPES, "docs/latest/test", &format),
+            "docs/latest/test"
+        );
+        assert_eq!(
+            format_branch_name(&TYPES, "feat/fix/complex", &format),
+            "fix/complex"
+        );
+        assert_eq!(format_branch_name(&TYPES, "fix", &format), "fix");
+        assert_eq!(format_branch_name(&TYPES, "fix/", &format), "fix/");
+        assert_eq!(format_branch_name(&TYPES, "main", &format), "main");
+    }
+
+    #[test]
+    fn applies_branch_name_format() {
+        let format = BranchNameFormat {
+            max_length: Some(14),
+            separator: Some(" ".to_string()),
+            case: BranchNameCase::Upper,
+        };
+        assert_eq!(
+            format_branch_name(&TYPES, "feat/add-user_login-form", &format),
+            "ADD USER LOGIN"
+        );
+        assert_eq!(
+            format_branch_name(&TYPES, "feat/add-user_login", &format),
+            "ADD USER LOGIN"
+        );
+
+        let format = BranchNameFormat {
+            max_length: Some(4),
+            ..BranchNameFormat::default()
+        };
+        assert_eq!(format_branch_name(&TYPES, "fix/abc-def", &format), "abc");
+    }
+
+    #[test]
+    fn parses_branch_descriptions() {
+        let output = "branch.feat/login.description\nLogin form for the\nmobile app\n\0\
+                      branch.release/1.2.description\nPatch releases\0\
+                      branch.empty.description\n\0";
+        let descriptions = parse_branch_descriptions(output);
+
+        assert_eq!(descriptions.len(), 2);
+        assert_eq!(descriptions["feat/login"], "Login form for the\nmobile app");
+        assert_eq!(descriptions["release/1.2"], "Patch releases");
+    }
 
     #[test]
     fn preserves_dotted_version() {
@@ -473,4 +809,19 @@ mod tests {
         assert_eq!(sanitize_branch_name("feature.lock"), "feature");
         assert_eq!(sanitize_branch_name("release/1.2.3.lock"), "release/1.2.3");
     }
+
+    #[test]
+    fn infers_commit_type_from_branch_prefix() {
+        let types = ["chore", "feat", "fix", "test"];
+        assert_eq!(infer_commit_type(&types, "feat/login"), Some("feat"));
+        assert_eq!(infer_commit_type(&types, "fix/nested/path"), Some("fix"));
+    }
+
+    #[test]
+    fn infers_no_commit_type_without_known_prefix() {
+        let types = ["chore", "feat", "fix", "test"];
+        assert_eq!(infer_commit_type(&types, "main"), None);
+        assert_eq!(infer_commit_type(&types, "feature/login"), None);
+        assert_eq!(infer_commit_type(&types, "user/feat/login"), None);
+    }
 }
@@ -0,0 +1,122 @@
+//! Bundles
+//!
+//! Git side of `rona bundle`: branches are written to a `git bundle` file to carry
+//! them to a clone without a shared remote, and applied from one after checking the
+//! bundle holds the expected branches and its prerequisite commits are present.
+
+use crate::errors::{Result, RonaError};
+
+use super::{
+    branch::get_current_branch,
+    repository::{Feature, ensure_allowed, ensure_writable, git_stdout, run_git},
+};
+
+/// A branch recorded in a bundle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BundleHead {
+    /// The commit the branch points to
+    pub commit: String,
+    /// The branch name, without `refs/heads/`
+    pub branch: String,
+}
+
+/// Writes `branches` to a bundle at `file`. With `since`, commits reachable from it
+/// are left out and become prerequisites of the bundle.
+///
+/// # Errors
+/// * If in read-only mode
+/// * If a branch or `since` does not resolve
+/// * If there are no commits to bundle
+pub fn create_bundle(file: &str, branches: &[String], since: Option<&str>) -> Result<()> {
+    ensure_writable("write a bundle")?;
+
+    let mut args = vec!["bundle", "create", "--quiet", file];
+    let excluded = since.map(|since| format!("^{since}"));
+    args.extend(excluded.as_deref());
+    let refs: Vec<String> = branches
+        .iter()
+        .map(|branch| format!("refs/heads/{branch}"))
+        .collect();
+    args.extend(refs.iter().map(String::as_str));
+    git_stdout(&args)?;
+    Ok(())
+}
+
+/// The number of commits `create_bundle` would write.
+///
+/// # Errors
+/// * If a branch or `since` does not resolve
+pub fn bundle_commit_count(branches: &[String], since: Option<&str>) -> Result<usize> {
+    let mut args = vec!["rev-list".to_string(), "--count".to_string()];
+    args.extend(since.map(|since| format!("^{since}")));
+    args.extend(branches.iter().map(|branch| format!("refs/heads/{branch}")));
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    Ok(git_stdout(&args)?.trim().parse().unwrap_or(0))
+}
+
+/// The branches recorded in the bundle at `file`.
+///
+/// # Errors
+/// * If `file` is not a bundle
+pub fn bundle_heads(file: &str) -> Result<Vec<BundleHead>> {
+    let output = run_git(&["bundle", "list-heads", file])?;
+    if !output.status.success() {
+        return Err(RonaError::InvalidInput(format!(
+            "{file} is not a git bundle"
+        )));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let (commit, reference) = line.split_once(' ')?;
+            Some(BundleHead {
+                commit: commit.to_string(),
+                branch: reference.strip_prefix("refs/heads/")?.to_string(),
+            })
+        })
+        .collect())
+}
+
+/// Checks that the commits the bundle at `file` was based on are in the repository.
+///
+/// # Errors
+/// * If prerequisite commits are missing, with the ones git reports
+pub fn verify_bundle(file: &str) -> Result<()> {
+    let output = run_git(&["bundle", "verify", "--quiet", file])?;
+    if !output.status.success() {
+        return Err(RonaError::InvalidInput(format!(
+            "{file} cannot be applied to this repository:\n{}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(())
+}
+
+/// Fast-forwards local branches to the heads of the bundle at `file`, creating the
+/// missing ones. The current branch is fast-forwarded in the working tree too.
+///
+/// # Errors
+/// * If in read-only mode, or fetching or merging is denied by a guard
+/// * If a branch has commits the bundle does not
+pub fn apply_bundle(file: &str, heads: &[BundleHead]) -> Result<()> {
+    ensure_writable("apply a bundle")?;
+    ensure_allowed(Feature::Fetch)?;
+
+    let current = get_current_branch().ok();
+    for head in heads {
+        let reference = format!("refs/heads/{}", head.branch);
+        if current.as_deref() == Some(head.branch.as_str()) {
+            ensure_allowed(Feature::Merge)?;
+            git_stdout(&["fetch", "--quiet", file, &reference])?;
+            git_stdout(&["merge", "--ff-only", "--quiet", &head.commit])?;
+        } else {
+            git_stdout(&[
+                "fetch",
+                "--quiet",
+                file,
+                &format!("{reference}:{reference}"),
+            ])?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,188 @@
+//! Gerrit Change-Id
+//!
+//! Gerrit groups the patch sets of one change by the `Change-Id:` trailer of the
+//! commit message. Its `commit-msg` hook derives the id from the tree, parent, author,
+//! committer and message being committed; this module computes it the same way, so
+//! commits made by rona are accepted without installing the hook. An amend keeps the
+//! id of the commit it replaces.
+
+use std::{
+    io::Write,
+    process::{Command, Stdio},
+};
+
+use crate::errors::{GitError, Result, RonaError};
+
+use super::repository::git_stdout;
+
+/// The trailer key Gerrit reads.
+pub const CHANGE_ID_TRAILER: &str = "Change-Id";
+
+/// The message with a `Change-Id:` trailer, or `None` when it already has one.
+///
+/// With `amend`, the id of the `HEAD` commit is reused when it has one, so that the
+/// amended commit stays a new patch set of the same change.
+///
+/// Computing a new id writes the trees of the index to the object database (see
+/// [`compute_change_id`]), so this is meant for commits about to be made, not for
+/// previews or dry runs.
+///
+/// # Errors
+/// * If the tree, parent or identities of the commit cannot be read from git
+pub fn with_change_id(message: &str, amend: bool) -> Result<Option<String>> {
+    if change_id_of(message).is_some() {
+        return Ok(None);
+    }
+
+    let id = match amend.then(head_change_id).flatten() {
+        Some(id) => id,
+        None => compute_change_id(message)?,
+    };
+    Ok(Some(append_trailer(
+        message,
+        &format!("{CHANGE_ID_TRAILER}: {id}"),
+    )))
+}
+
+/// The Change-Id of a message: the value of a `Change-Id:` line in its trailer block.
+#[must_use]
+pub fn change_id_of(message: &str) -> Option<&str> {
+    trailer_block(message)?
+        .lines()
+        .filter_map(parse_trailer)
+        .find(|(key, _)| *key == CHANGE_ID_TRAILER)
+        .map(|(_, value)| value)
+        .filter(|id| is_change_id(id))
+}
+
+/// Whether `id` is `I` followed by a 40-digit lowercase hexadecimal sha1.
+fn is_change_id(id: &str) -> bool {
+    id.strip_prefix('I').is_some_and(|sha| {
+        sha.len() == 40 && sha.bytes().all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'))
+    })
+}
+
+/// Splits a `Key: value` trailer line; `None` for any other line.
+fn parse_trailer(line: &str) -> Option<(&str, &str)> {
+    let (key, value) = line.split_once(':')?;
+    let is_key = !key.is_empty() && key.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-');
+    (is_key && value.starts_with([' ', '\t'])).then(|| (key, value.trim()))
+}
+
+/// The last paragraph of a message when every line of it is a `Key: value` trailer.
+/// The subject is never a trailer block, even when it looks like one (`fix: typo`).
+fn trailer_block(message: &str) -> Option<&str> {
+    let (_, last) = message.trim_end().rsplit_once("\n\n")?;
+    let last = last.trim_start_matches('\n');
+    last.lines()
+        .all(|line| parse_trailer(line).is_some())
+        .then_some(last)
+}
+
+/// Appends a trailer line: to the trailer block when the message ends with one, else
+/// as a new paragraph.
+#[must_use]
+pub fn append_trailer(message: &str, trailer: &str) -> String {
+    let message = message.trim_end();
+    if trailer_block(message).is_some() {
+        format!("{message}\n{trailer}\n")
+    } else {
+        format!("{message}\n\n{trailer}\n")
+    }
+}
+
+/// The Change-Id of the `HEAD` commit, if it has one.
+fn head_change_id() -> Option<String> {
+    let message = git_stdout(&["log", "-1", "--format=%B", "HEAD"]).ok()?;
+    change_id_of(&message).map(ToString::to_string)
+}
+
+/// Computes a Change-Id like Gerrit's `commit-msg` hook: `I` followed by the sha1 of
+/// the staged tree, the parent, the author and committer identities and the message.
+///
+/// The staged tree is read with `git write-tree`, which writes the tree objects of the
+/// index, as the commit itself would. When no commit follows, they stay unreferenced
+/// until `git gc` prunes them.
+///
+/// # Errors
+/// * If git cannot write the index tree or report the identities
+fn compute_change_id(message: &str) -> Result<String> {
+    let tree = git_stdout(&["write-tree"])?;
+    let parent = git_stdout(&["rev-parse", "--verify", "--quiet", "HEAD^0"])
+        .map(|parent| format!("parent {}\n", parent.trim()))
+        .unwrap_or_default();
+    let input = format!(
+        "tree {}\n{parent}author {}\ncommitter {}\n\n{message}",
+        tree.trim(),
+        git_stdout(&["var", "GIT_AUTHOR_IDENT"])?.trim(),
+        git_stdout(&["var", "GIT_COMMITTER_IDENT"])?.trim(),
+    );
+
+    let mut child = Command::new("git")
+        .args(["hash-object", "-t", "blob", "--stdin"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(RonaError::Io)?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(input.as_bytes())?;
+    }
+    let output = child.wait_with_output().map_err(RonaError::Io)?;
+    if !output.status.success() {
+        return Err(RonaError::Git(GitError::CommandFailed {
+            command: "git hash-object".to_string(),
+            output: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        }));
+    }
+    Ok(format!(
+        "I{}",
+        String::from_utf8_lossy(&output.stdout).trim()
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ID: &str = "I0123456789abcdef0123456789abcdef01234567";
+
+    #[test]
+    fn test_change_id_of() {
+        assert_eq!(
+            change_id_of(&format!("fix: typo\n\nBody.\n\nChange-Id: {ID}\n")),
+            Some(ID)
+        );
+        assert_eq!(
+            change_id_of(&format!(
+                "fix: typo\n\nSigned-off-by: Jane <j@x.io>\nChange-Id: {ID}"
+            )),
+            Some(ID)
+        );
+        // Not in the trailer block
+        assert_eq!(
+            change_id_of(&format!("fix: typo\n\nChange-Id: {ID}\n\nMore text.")),
+            None
+        );
+        assert_eq!(change_id_of("fix: typo"), None);
+        assert_eq!(change_id_of("fix: typo\n\nChange-Id: I123"), None);
+    }
+
+    #[test]
+    fn test_append_trailer() {
+        let trailer = format!("Change-Id: {ID}");
+
+        assert_eq!(
+            append_trailer("fix: typo\n\nBody.\n\n", &trailer),
+            format!("fix: typo\n\nBody.\n\n{trailer}\n")
+        );
+        assert_eq!(
+            append_trailer("fix: typo", &trailer),
+            format!("fix: typo\n\n{trailer}\n")
+        );
+        assert_eq!(
+            append_trailer("fix: typo\n\nRefs: #12\n", &trailer),
+            format!("fix: typo\n\nRefs: #12\n{trailer}\n")
+        );
+    }
+}
@@ -4,38 +4,187 @@
 //! and commit execution operations.
 
 use std::{
-    fs::{File, OpenOptions, read_to_string, write},
-    io::Write,
+    collections::{BTreeMap, HashMap},
+    fmt::Write,
+    fs::{read_to_string, write},
     path::Path,
     process::Command,
 };
 
 use colored::Colorize;
+use glob::Pattern;
+use serde::{Deserialize, Serialize};
 
 use crate::{
+    annotations::{FileSummary, Version, summarize_diff},
     errors::{GitError, Result, RonaError},
-    git::branch::{format_branch_name, get_current_branch},
+    lint::parse_header,
 };
 
 use super::{
-    files::get_ignore_patterns,
+    attributes::{AttributeValue, IGNORE_ATTRIBUTE, attribute_values},
+    branch::{branch_upstream, get_current_branch, get_default_branch},
+    files::{get_ignore_patterns, is_ignored},
     get_top_level_path,
-    status::{process_deleted_files_for_commit_message, process_git_status},
+    message_log::save_generated_message,
+    repository::{
+        Feature, denied_by, ensure_allowed, ensure_writable, git_path, git_stdout, is_read_only,
+    },
+    status::{StatusEntry, StatusSnapshot, get_staged_files},
 };
 
 pub const COMMIT_MESSAGE_FILE_PATH: &str = "commit_message.md";
 pub const COMMIT_TYPES: [&str; 4] = ["chore", "feat", "fix", "test"];
 
+/// A graft file path that never exists, used to count the history without grafts.
+const NO_GRAFTS_FILE: &str = "info/rona-no-grafts";
+
+/// Git's scissors line, after the comment character. In `commit_message.md`, the
+/// staged diff is shown below it.
+const SCISSORS: &str = "------------------------ >8 ------------------------";
+
+/// The default line written for each file in a generated commit message.
+pub const DEFAULT_FILE_BULLET: &str = "- `{file}`: {placeholder}";
+
+/// How a generated commit message lists each changed file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileBullet {
+    /// Line format. `{file}` is the path and `{placeholder}` is empty for changed
+    /// files and `deleted` for deleted ones.
+    pub format: String,
+    /// Drops the empty, indented description line under changed files and the blank
+    /// lines between bullets.
+    pub compact: bool,
+    /// Writes bullets as Markdown checkboxes (`- [ ] ...`).
+    pub checkbox: bool,
+    /// Follows each bullet with a comment summarizing the staged change of the file
+    /// (see [`crate::annotations`]).
+    pub annotate: bool,
+    /// The comment character of the annotations.
+    pub comment_char: char,
+}
+
+impl Default for FileBullet {
+    fn default() -> Self {
+        Self {
+            format: DEFAULT_FILE_BULLET.to_string(),
+            compact: false,
+            checkbox: false,
+            annotate: false,
+            comment_char: '#',
+        }
+    }
+}
+
+/// The comment lines of `commit_message.md`.
+///
+/// Lines starting with the comment character are only dropped when `strip` is set,
+/// so `#123` references and Markdown headings stay in the message otherwise.
+/// Everything from the scissors line on is always dropped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Comments {
+    /// The comment character, git's `core.commentChar` (`#` by default)
+    pub comment_char: char,
+    /// Drops every comment line, not only those from the scissors line on
+    pub strip: bool,
+}
+
+impl Default for Comments {
+    fn default() -> Self {
+        Self {
+            comment_char: '#',
+            strip: false,
+        }
+    }
+}
+
+impl Comments {
+    /// The comments of the current repository, with its `core.commentChar`. `auto`,
+    /// which lets git pick a character per message, is read as `#`.
+    #[must_use]
+    pub fn read(strip: bool) -> Self {
+        let comment_char = git_stdout(&["config", "--get", "core.commentChar"])
+            .ok()
+            .and_then(|value| {
+                let mut chars = value.trim().chars();
+                chars.next().filter(|_| chars.next().is_none())
+            })
+            .unwrap_or('#');
+        Self {
+            comment_char,
+            strip,
+        }
+    }
+
+    /// Git's scissors line with this comment character.
+    #[must_use]
+    pub fn scissors(self) -> String {
+        format!("{} {SCISSORS}", self.comment_char)
+    }
+}
+
+/// How a file listed in a generated commit message changed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileChange {
+    /// Added or modified; followed by a line for its description
+    Changed,
+    /// Changed, but marked `-diff` in `.gitattributes`, so listed without a
+    /// description line
+    NoDiff,
+    /// Deleted; the placeholder reads `deleted`
+    Deleted,
+}
+
+impl FileBullet {
+    /// Renders the bullet for one file, including the lines that follow it.
+    #[must_use]
+    #[allow(clippy::literal_string_with_formatting_args)]
+    pub fn render(&self, file: &str, change: FileChange) -> String {
+        let placeholder = if change == FileChange::Deleted {
+            "deleted"
+        } else {
+            ""
+        };
+        let line = self
+            .format
+            .replace("{file}", file)
+            .replace("{placeholder}", placeholder);
+        let line = line.trim_end();
+        let line = if self.checkbox {
+            let item = line
+                .strip_prefix("- ")
+                .or_else(|| line.strip_prefix("* "))
+                .unwrap_or(line);
+            format!("- [ ] {item}")
+        } else {
+            line.to_string()
+        };
+
+        if self.compact {
+            format!("{line}\n")
+        } else if change != FileChange::Changed {
+            format!("{line}\n\n")
+        } else {
+            format!("{line}\n\n\t\n\n")
+        }
+    }
+}
+
 /// Gets the total number of commits in the current branch.
 ///
 /// This function counts all commits reachable from the current HEAD.
 /// Returns 0 for a fresh repository with no commits.
 ///
+/// Replace refs and grafts are honored, like `git log` does. When one of them breaks
+/// the walk (e.g. a graft naming a commit that was never fetched), the stored history
+/// is counted instead; [`count_caveats`] reports both cases.
+///
 /// # Errors
 ///
 /// Returns an error if:
 /// - Not currently in a git repository
 /// - The commit count output cannot be parsed
+/// - The history cannot be walked even without replacements
 ///
 /// # Returns
 ///
@@ -55,19 +204,292 @@ pub const COMMIT_TYPES: [&str; 4] = ["chore", "feat", "fix", "test"];
 /// # Ok::<(), Box<dyn std::error::Error>>(())
 /// ```
 pub fn get_current_commit_nb() -> Result<u32> {
-    let output = Command::new("git")
-        .args(["rev-list", "--count", "HEAD"])
+    if !has_head()? {
+        // A fresh repository with no commits
+        return Ok(0);
+    }
+    if let Some(count) = rev_count("HEAD")? {
+        return Ok(count);
+    }
+    stored_rev_count("HEAD")?.ok_or_else(|| {
+        RonaError::Git(GitError::CommandFailed {
+            command: "git rev-list --count HEAD".to_string(),
+            output: "The history of HEAD cannot be walked".to_string(),
+        })
+    })
+}
+
+/// Something that makes `{commit_number}` differ from the commits actually stored.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CountCaveat {
+    /// The repository is a shallow clone, so commits before the cut are not counted
+    Shallow,
+    /// Replace refs or `.git/info/grafts` change the history that is counted
+    Rewritten {
+        /// Number of refs under `refs/replace/`
+        replace_refs: usize,
+        /// Whether a graft file is in use
+        grafts: bool,
+        /// The count with the replacements, or `None` when they break the walk
+        counted: Option<u32>,
+        /// The count of the stored history, without replacements
+        stored: u32,
+    },
+}
+
+impl std::fmt::Display for CountCaveat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Shallow => write!(
+                f,
+                "Shallow clone: commit numbers only count the fetched commits \
+                 (git fetch --unshallow fetches the rest)"
+            ),
+            Self::Rewritten {
+                replace_refs,
+                grafts,
+                counted,
+                stored,
+            } => {
+                let source = match (*replace_refs > 0, grafts) {
+                    (true, true) => format!("{replace_refs} replace ref(s) and info/grafts"),
+                    (true, false) => format!("{replace_refs} replace ref(s)"),
+                    _ => "info/grafts".to_string(),
+                };
+                match counted {
+                    Some(counted) => write!(
+                        f,
+                        "{source} change the commit count: {counted} with them, \
+                         {stored} in the stored history; commit numbers use {counted}"
+                    ),
+                    None => write!(
+                        f,
+                        "{source} break the history walk; commit numbers count the \
+                         {stored} stored commits"
+                    ),
+                }
+            }
+        }
+    }
+}
+
+/// Finds what makes the commit count of `HEAD` differ from the stored history.
+///
+/// Replace refs and grafts are only reported when they actually change the count,
+/// which takes a second walk of the history without them.
+///
+/// # Errors
+/// * If git cannot be run or a commit count cannot be parsed
+pub fn count_caveats() -> Result<Vec<CountCaveat>> {
+    let mut caveats = Vec::new();
+    if git_stdout(&["rev-parse", "--is-shallow-repository"])?.trim() == "true" {
+        caveats.push(CountCaveat::Shallow);
+    }
+
+    let replace_refs = git_stdout(&["for-each-ref", "--format=%(refname)", "refs/replace/"])?
+        .lines()
+        .count();
+    let grafts = std::fs::metadata(git_path("info/grafts")?).is_ok_and(|meta| meta.len() > 0);
+    if (replace_refs > 0 || grafts) && has_head()? {
+        let counted = rev_count("HEAD")?;
+        let stored = stored_rev_count("HEAD")?.unwrap_or_default();
+        if counted != Some(stored) {
+            caveats.push(CountCaveat::Rewritten {
+                replace_refs,
+                grafts,
+                counted,
+                stored,
+            });
+        }
+    }
+    Ok(caveats)
+}
+
+/// Whether `HEAD` points to a commit.
+fn has_head() -> Result<bool> {
+    Ok(Command::new("git")
+        .args(["rev-parse", "--verify", "--quiet", "HEAD"])
         .output()
-        .map_err(RonaError::Io)?;
+        .map_err(RonaError::Io)?
+        .status
+        .success())
+}
 
-    if !output.status.success() {
-        // Likely a fresh repository with no commits
+/// What `{commit_number}` counts, set with `commit_number_source`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CommitNumberSource {
+    /// The commits reachable from `HEAD`
+    #[default]
+    HeadCount,
+    /// The commits of the remote target branch, freshly fetched, plus the local
+    /// commits it does not have yet
+    Remote,
+    /// The commits of the current branch that are not on the default branch
+    BranchOnly,
+    /// The commits reachable from `HEAD` with the same commit type
+    PerType,
+}
+
+/// The commit a number is wanted for, as `per-type` counts it: its commit type, and
+/// how to read the type of earlier subjects.
+#[derive(Debug, Clone, Copy)]
+pub struct NumberedCommit<'a> {
+    pub commit_type: &'a str,
+    /// The commit template the earlier subjects were written with
+    pub template: &'a str,
+    /// Allowed values for `{commit_type}`
+    pub commit_types: &'a [&'a str],
+}
+
+/// Gets the number of commits `{commit_number}` counts from; the next commit is this
+/// plus one.
+///
+/// With [`CommitNumberSource::Remote`], two contributors who have not pulled each
+/// other's work do not claim the same number. The count is resolved as follows:
+///
+/// 1. The target is the upstream of the current branch, else the default branch on
+///    `origin` (see [`get_default_branch`]).
+/// 2. The target branch is fetched first. A failed fetch (offline, no credentials) is
+///    ignored and the remote-tracking branch from the last fetch is used.
+/// 3. The count is the commits of the target plus those of `HEAD` it does not have
+///    (`rev-list --count <target>` plus `rev-list --count <target>..HEAD`). Commits
+///    pushed by others are counted even though `HEAD` lacks them, and local commits
+///    already pushed are counted once.
+/// 4. Without a target, e.g. before the first push, the `HEAD` count is used.
+///
+/// With [`CommitNumberSource::BranchOnly`], the count is the commits of `HEAD` not
+/// on the default branch. With [`CommitNumberSource::PerType`], it is the commits
+/// whose subject has the commit type of `commit`.
+///
+/// # Errors
+/// * If a commit count cannot be parsed
+pub fn get_commit_nb(source: CommitNumberSource, commit: &NumberedCommit<'_>) -> Result<u32> {
+    match source {
+        CommitNumberSource::HeadCount => get_current_commit_nb(),
+        CommitNumberSource::Remote => remote_commit_nb(),
+        CommitNumberSource::BranchOnly => branch_commit_nb(),
+        CommitNumberSource::PerType => type_commit_nb(commit),
+    }
+}
+
+/// The `remote` count described at [`get_commit_nb`]. The fetch is skipped in
+/// read-only mode and when a guard denies fetching, which counts against the last
+/// fetched state.
+///
+/// # Errors
+/// * If the upstream remote or branch starts with `-`
+/// * If a commit count cannot be parsed
+fn remote_commit_nb() -> Result<u32> {
+    let branch = get_current_branch()?;
+    let (remote, remote_branch) = match branch_upstream(&branch)? {
+        Some(upstream) => upstream,
+        None => ("origin".to_string(), get_default_branch()?),
+    };
+    for (key, value) in [("remote", &remote), ("branch", &remote_branch)] {
+        if value.starts_with('-') {
+            return Err(RonaError::InvalidInput(format!(
+                "Upstream {key} cannot start with '-': {value}"
+            )));
+        }
+    }
+    let target = format!("refs/remotes/{remote}/{remote_branch}");
+
+    if !is_read_only() && denied_by(Feature::Fetch).is_none() {
+        // Deliberately ignored: an offline fetch falls back to the last fetched state.
+        let _ = Command::new("git")
+            .args(["fetch", "--quiet", "--", &remote, &remote_branch])
+            .env("GIT_TERMINAL_PROMPT", "0")
+            .output();
+    }
+
+    let (Some(remote_count), Some(ahead)) =
+        (rev_count(&target)?, rev_count(&format!("{target}..HEAD"))?)
+    else {
+        return get_current_commit_nb();
+    };
+    Ok(remote_count + ahead)
+}
+
+/// Counts the commits of `HEAD` that are not on the default branch, so numbering
+/// starts over on each branch. The default branch is compared as last fetched from
+/// `origin`, else as the local branch. On the default branch itself, or when it
+/// does not exist, the `HEAD` count is used.
+///
+/// # Errors
+/// * If the default or current branch cannot be determined
+/// * If a commit count cannot be parsed
+fn branch_commit_nb() -> Result<u32> {
+    let default_branch = get_default_branch()?;
+    if get_current_branch()? == default_branch {
+        return get_current_commit_nb();
+    }
+    for base in [
+        format!("refs/remotes/origin/{default_branch}"),
+        format!("refs/heads/{default_branch}"),
+    ] {
+        if let Some(count) = rev_count(&format!("{base}..HEAD"))? {
+            return Ok(count);
+        }
+    }
+    get_current_commit_nb()
+}
+
+/// Counts the commits reachable from `HEAD` whose subject, read with the commit
+/// template, has the commit type of `commit`. Subjects that do not match the
+/// template are not counted.
+///
+/// # Errors
+/// * If the commit subjects cannot be read
+fn type_commit_nb(commit: &NumberedCommit<'_>) -> Result<u32> {
+    if !has_head()? {
         return Ok(0);
     }
+    let subjects = git_stdout(&["log", "--format=%s", "HEAD"])?;
+    let count = subjects
+        .lines()
+        .filter(|subject| {
+            parse_header(subject, commit.template, commit.commit_types).is_some_and(|fields| {
+                fields
+                    .iter()
+                    .any(|(name, value)| name == "commit_type" && value == commit.commit_type)
+            })
+        })
+        .count();
+    Ok(u32::try_from(count).unwrap_or(u32::MAX))
+}
 
-    let count_str = String::from_utf8_lossy(&output.stdout).trim().to_string();
+/// Counts the commits of a revision range, or `None` when it does not resolve.
+fn rev_count(range: &str) -> Result<Option<u32>> {
+    parse_rev_count(
+        &Command::new("git")
+            .args(["rev-list", "--count", range])
+            .output()
+            .map_err(RonaError::Io)?,
+    )
+}
+
+/// Counts the commits of a revision range as stored, ignoring replace refs and grafts.
+fn stored_rev_count(range: &str) -> Result<Option<u32>> {
+    // `--no-replace-objects` leaves grafts in effect; a graft file that does not
+    // exist turns them off.
+    let no_grafts = git_path(NO_GRAFTS_FILE)?;
+    parse_rev_count(
+        &Command::new("git")
+            .args(["--no-replace-objects", "rev-list", "--count", range])
+            .env("GIT_GRAFT_FILE", no_grafts)
+            .output()
+            .map_err(RonaError::Io)?,
+    )
+}
 
-    count_str.parse::<u32>().map_err(|_| {
+fn parse_rev_count(output: &std::process::Output) -> Result<Option<u32>> {
+    if !output.status.success() {
+        return Ok(None);
+    }
+
+    let count_str = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    count_str.parse::<u32>().map(Some).map_err(|_| {
         RonaError::Git(GitError::InvalidStatus {
             output: format!("Failed to parse commit count: {count_str}"),
         })
@@ -106,16 +528,115 @@ pub fn is_gpg_signing_available() -> bool {
     }
 }
 
+/// How a commit is signed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Signing {
+    /// Signed as git's own configuration says; warns when no signing key is configured
+    Auto,
+    /// Never signed (`--no-gpg-sign`)
+    Off,
+    /// Always signed (`-S`); committing fails when no signing key is configured
+    Required,
+}
+
+impl Signing {
+    /// The signing of a commit made with or without `--unsigned`, under the
+    /// `require_signed` policy. A guard denying `sign` turns signing off.
+    ///
+    /// # Errors
+    /// * If signing is required and `--unsigned` was given
+    /// * If signing is required and a guard denies it
+    pub fn new(unsigned: bool, required: bool) -> Result<Self> {
+        if let Some(guard) = denied_by(Feature::Sign) {
+            if required {
+                return Err(RonaError::Denied {
+                    feature: Feature::Sign.name().to_string(),
+                    guard,
+                });
+            }
+            return Ok(Self::Off);
+        }
+        match (unsigned, required) {
+            (true, true) => Err(RonaError::Git(GitError::SigningRequired {
+                reason: "--unsigned is not allowed".to_string(),
+            })),
+            (true, false) => Ok(Self::Off),
+            (false, true) => Ok(Self::Required),
+            (false, false) => Ok(Self::Auto),
+        }
+    }
+}
+
+/// What `rona commit --dry-run` would commit, for `--output json`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CommitPreview {
+    /// The commit message, trimmed
+    pub message: String,
+    /// The current branch, `None` when `HEAD` is detached
+    pub branch: Option<String>,
+    /// Whether the previous commit would be amended
+    pub amend: bool,
+    /// Whether the commit would be signed
+    pub signed: bool,
+    /// The arguments passed on to `git commit`
+    pub args: Vec<String>,
+    /// The staged files
+    pub files: Vec<StatusEntry>,
+    /// Warnings the text output would print, without colors
+    pub warnings: Vec<String>,
+}
+
+/// Describes the commit [`git_commit`] would create with these arguments, without
+/// committing.
+///
+/// # Errors
+/// * If reading the staged files fails
+pub fn commit_preview(message: &str, args: &[String], signing: Signing) -> Result<CommitPreview> {
+    let (amend, args) = commit_args(args);
+    let gpg_available = is_gpg_signing_available();
+    let mut warnings = Vec::new();
+    if signing == Signing::Required && !gpg_available {
+        warnings.push(
+            "Would refuse to commit: signing is required but no signing key is configured"
+                .to_string(),
+        );
+    } else if signing == Signing::Auto && !gpg_available {
+        warnings.push("GPG signing not available or not configured".to_string());
+    }
+
+    Ok(CommitPreview {
+        message: message.trim().to_string(),
+        branch: get_current_branch().ok().filter(|branch| branch != "HEAD"),
+        amend,
+        signed: signing != Signing::Off && gpg_available,
+        args,
+        files: get_staged_files()?,
+        warnings,
+    })
+}
+
+/// Splits the arguments of `rona commit` into whether `--amend` was given and the
+/// arguments passed on to `git commit`, without rona's own flags.
+fn commit_args(args: &[String]) -> (bool, Vec<String>) {
+    let is_amend = args.iter().any(|arg| arg == "--amend");
+    let filtered_args = args
+        .iter()
+        .filter(|arg| !arg.starts_with("-c") && !arg.starts_with("--commit") && *arg != "--amend")
+        .cloned()
+        .collect();
+    (is_amend, filtered_args)
+}
+
 /// Handles dry run output for commit operations.
 ///
 /// # Arguments
 /// * `file_content` - The commit message content
-/// * `unsigned` - Whether the commit should be unsigned
+/// * `signing` - How the commit would be signed
 /// * `filtered_args` - Additional git arguments
 /// * `is_amend` - Whether this is an amend operation
 fn handle_dry_run_output(
     file_content: &str,
-    unsigned: bool,
+    signing: Signing,
     filtered_args: &[String],
     is_amend: bool,
 ) {
@@ -129,12 +650,17 @@ fn handle_dry_run_output(
     }
 
     let gpg_available = is_gpg_signing_available();
-    let would_sign = !unsigned && gpg_available;
+    let would_sign = signing != Signing::Off && gpg_available;
 
-    if unsigned {
+    if signing == Signing::Off {
         println!("Would create unsigned commit");
     } else if would_sign {
         println!("Would sign commit with GPG");
+    } else if signing == Signing::Required {
+        println!(
+            "{} Would refuse to commit: signing is required but no signing key is configured",
+            "WARNING:".yellow().bold()
+        );
     } else {
         println!("Would create unsigned commit (GPG signing not available)");
         if !gpg_available {
@@ -151,48 +677,56 @@ fn handle_dry_run_output(
     }
 }
 
-/// Commits files to the git repository using `git commit -F`.
+/// Commits files to the git repository with the message in `commit_message.md`.
 ///
-/// This function reads the commit message from `commit_message.md` and creates
-/// a git commit with that message. By using the git CLI directly, all git hooks
+/// This function reads the commit message from `commit_message.md`, strips the
+/// staged diff below the scissors line and, when `comments.strip`, the comment lines
+/// (see [`strip_message_comments`]), and creates a git commit with that message. By using the git CLI directly, all git hooks
 /// (pre-commit, commit-msg, post-commit, etc.) are triggered naturally.
 ///
 /// GPG signing is handled by git's own configuration (`commit.gpgsign`,
-/// `user.signingkey`). Pass `unsigned = true` to disable signing via
-/// `--no-gpg-sign`.
+/// `user.signingkey`) with [`Signing::Auto`]. [`Signing::Off`] disables it via
+/// `--no-gpg-sign`, and [`Signing::Required`] forces it with `-S`.
 ///
 /// # Arguments
 /// * `args` - Additional arguments (supports `--amend` to amend the previous commit)
-/// * `unsigned` - If true, creates an unsigned commit (passes `--no-gpg-sign`)
+/// * `signing` - How the commit is signed
+/// * `comments` - The comment lines dropped from the message
 /// * `dry_run` - If true, only show what would be committed without actually committing
 ///
 /// # Errors
 /// * If the commit message file doesn't exist
 /// * If reading the commit message file fails
+/// * If signing is required and no signing key is configured
 /// * If the git commit command fails
 /// * If not in a git repository
 ///
 /// # Examples
 ///
 /// ```no_run
-/// use rona::git::commit::git_commit;
+/// use rona::git::commit::{Comments, Signing, git_commit};
 ///
 /// // Commit with automatic GPG detection (default)
-/// git_commit(&[], false, false)?;
+/// git_commit(&[], Signing::Auto, Comments::default(), false)?;
 ///
 /// // Unsigned commit
-/// git_commit(&[], true, false)?;
+/// git_commit(&[], Signing::Off, Comments::default(), false)?;
 ///
 /// // Amend the previous commit
-/// git_commit(&["--amend".to_string()], false, false)?;
+/// git_commit(&["--amend".to_string()], Signing::Auto, Comments::default(), false)?;
 ///
 /// // Dry run to preview the commit
-/// git_commit(&[], false, true)?;
+/// git_commit(&[], Signing::Auto, Comments::default(), true)?;
 /// # Ok::<(), Box<dyn std::error::Error>>(())
 /// ```
 #[tracing::instrument(skip_all)]
-pub fn git_commit(args: &[String], unsigned: bool, dry_run: bool) -> Result<()> {
-    tracing::debug!(unsigned, dry_run, "Committing files...");
+pub fn git_commit(
+    args: &[String],
+    signing: Signing,
+    comments: Comments,
+    dry_run: bool,
+) -> Result<()> {
+    tracing::debug!(?signing, dry_run, "Committing files...");
 
     let project_root = get_top_level_path()?;
     let commit_file_path = project_root.join(COMMIT_MESSAGE_FILE_PATH);
@@ -201,23 +735,55 @@ pub fn git_commit(args: &[String], unsigned: bool, dry_run: bool) -> Result<()>
         return Err(RonaError::Git(GitError::CommitMessageNotFound));
     }
 
-    let file_content = read_to_string(&commit_file_path)?;
+    let message = strip_message_comments(&read_to_string(&commit_file_path)?, comments);
+    run_commit(&message, &["-m", &message], args, signing, dry_run)
+}
 
+/// Commits with the given message instead of `commit_message.md`, using `git commit -m`.
+///
+/// Hooks, signing and `--amend` behave as in [`git_commit`].
+///
+/// # Errors
+/// * If the git commit command fails
+#[tracing::instrument(skip_all)]
+pub fn git_commit_with_message(
+    message: &str,
+    args: &[String],
+    signing: Signing,
+    dry_run: bool,
+) -> Result<()> {
+    tracing::debug!(?signing, dry_run, "Committing with an inline message...");
+    run_commit(message, &["-m", message], args, signing, dry_run)
+}
+
+/// Runs `git commit` with the given message arguments (`-F <file>` or `-m <text>`).
+///
+/// `message` is only used for the dry-run preview.
+fn run_commit(
+    message: &str,
+    message_args: &[&str],
+    args: &[String],
+    signing: Signing,
+    dry_run: bool,
+) -> Result<()> {
     // Detect --amend and filter out flags that don't apply to git commit -F
-    let is_amend = args.iter().any(|arg| arg == "--amend");
-    let filtered_args: Vec<String> = args
-        .iter()
-        .filter(|arg| !arg.starts_with("-c") && !arg.starts_with("--commit") && *arg != "--amend")
-        .cloned()
-        .collect();
+    let (is_amend, filtered_args) = commit_args(args);
 
     if dry_run {
-        handle_dry_run_output(&file_content, unsigned, &filtered_args, is_amend);
+        handle_dry_run_output(message, signing, &filtered_args, is_amend);
         return Ok(());
     }
 
-    // Warn if user expects signing but no key is configured
-    if !unsigned && !is_gpg_signing_available() {
+    ensure_writable("commit")?;
+    ensure_allowed(Feature::Commit)?;
+
+    // Refuse, or warn, when signing is expected but no key is configured
+    if signing == Signing::Required && !is_gpg_signing_available() {
+        return Err(RonaError::Git(GitError::SigningRequired {
+            reason: "no signing key is configured (git config user.signingkey)".to_string(),
+        }));
+    }
+    if signing == Signing::Auto && !is_gpg_signing_available() {
         println!(
             "{} GPG signing not available or not configured. Creating unsigned commit.",
             "WARNING:".yellow().bold()
@@ -225,13 +791,6 @@ pub fn git_commit(args: &[String], unsigned: bool, dry_run: bool) -> Result<()>
         println!("   To suppress this warning, use the --unsigned (-u) flag.");
     }
 
-    let commit_file_str = commit_file_path.to_str().ok_or_else(|| {
-        RonaError::Git(GitError::CommandFailed {
-            command: "commit".to_string(),
-            output: "Invalid path to commit message file".to_string(),
-        })
-    })?;
-
     let mut cmd = Command::new("git");
     cmd.arg("commit");
 
@@ -239,11 +798,17 @@ pub fn git_commit(args: &[String], unsigned: bool, dry_run: bool) -> Result<()>
         cmd.arg("--amend");
     }
 
-    if unsigned {
-        cmd.arg("--no-gpg-sign");
+    match signing {
+        Signing::Auto => {}
+        Signing::Off => {
+            cmd.arg("--no-gpg-sign");
+        }
+        Signing::Required => {
+            cmd.arg("-S");
+        }
     }
 
-    cmd.args(["-F", commit_file_str]);
+    cmd.args(message_args);
 
     // Use .status() so git inherits stdin/stdout/stderr.
     // This allows hooks to run and interactive GPG prompts to work.
@@ -273,113 +838,396 @@ pub fn git_commit(args: &[String], unsigned: bool, dry_run: bool) -> Result<()>
 ///
 /// # Arguments
 /// * `commit_type` - `&str` - The commit type
-/// * `no_commit_number` - `bool` - Whether to include the commit number in the header
+/// * `branch_name` - `&str` - The branch name shown in the header, already formatted
+/// * `commit_number` - `Option<u32>` - The number shown in the header, if any
+/// * `bullet` - `&FileBullet` - How each file is listed
 #[tracing::instrument(skip_all)]
-pub fn generate_commit_message(commit_type: &str, no_commit_number: bool) -> Result<()> {
+pub fn generate_commit_message(
+    commit_type: &str,
+    branch_name: &str,
+    commit_number: Option<u32>,
+    bullet: &FileBullet,
+) -> Result<()> {
+    ensure_writable("write commit_message.md")?;
+
     let project_root = get_top_level_path()?;
     let commit_message_path = project_root.join(COMMIT_MESSAGE_FILE_PATH);
 
-    // Empty the file if it exists
-    if commit_message_path.exists() {
-        write(&commit_message_path, "")?;
-    }
+    let message = render_commit_message(commit_type, branch_name, commit_number, bullet)?;
+    write(&commit_message_path, &message)?;
+    save_generated_message(&message)?;
 
-    // Get git status info
-    let modified_files = process_git_status()?;
-    let deleted_files = process_deleted_files_for_commit_message()?;
+    tracing::debug!("{} created", commit_message_path.display());
+
+    Ok(())
+}
 
-    // Open the commit file for writing
-    let mut commit_file = OpenOptions::new()
-        .append(true)
-        .create(true)
-        .open(&commit_message_path)?;
+/// Renders the commit message skeleton: the header followed by one bullet per changed file.
+///
+/// This only reads the repository, so it is safe to call from git hooks
+/// (see `rona generate --print`).
+///
+/// # Errors
+/// * If we cannot read the git status
+/// * If we cannot process either git status or deleted files from the git status
+/// * If we cannot read the commitignore file
+///
+/// # Arguments
+/// * `commit_type` - `&str` - The commit type
+/// * `branch_name` - `&str` - The branch name shown in the header, already formatted
+/// * `commit_number` - `Option<u32>` - The number shown in the header, if any
+/// * `bullet` - `&FileBullet` - How each file is listed
+pub fn render_commit_message(
+    commit_type: &str,
+    branch_name: &str,
+    commit_number: Option<u32>,
+    bullet: &FileBullet,
+) -> Result<String> {
+    // Get git status info
+    let status = StatusSnapshot::read()?;
+    let modified_files = status.staged_changes();
+    let deleted_files = status.staged_deletions();
 
     // Write header
-    write_commit_header(&mut commit_file, commit_type, no_commit_number)?;
+    let mut sections = vec![commit_header(commit_type, branch_name, commit_number)];
 
     // Get files to ignore
     let ignore_patterns = get_ignore_patterns()?;
+    let all_files: Vec<String> = modified_files
+        .iter()
+        .chain(&deleted_files)
+        .cloned()
+        .collect();
+    let ignored = attribute_values(&all_files, IGNORE_ATTRIBUTE)?;
+    let diff = attribute_values(&modified_files, "diff")?;
+    let has_attribute = |values: &HashMap<String, AttributeValue>, file: &str, value| {
+        values.get(file) == Some(&value)
+    };
+    let summaries = if bullet.annotate {
+        staged_summaries()?
+    } else {
+        BTreeMap::new()
+    };
+    let annotated = |section: String, file: &str| match summaries.get(file) {
+        Some(summary) => match section.split_once('\n') {
+            Some((line, rest)) => format!("{line}\n{} {summary}\n{rest}", bullet.comment_char),
+            None => section,
+        },
+        None => section,
+    };
 
     // Process modified files
-    for file in modified_files {
-        if !should_ignore_file(&file, &ignore_patterns)? {
-            writeln!(commit_file, "- `{file}`:\n\n\t\n")?;
+    for file in &modified_files {
+        if is_ignored(file, &ignore_patterns) || has_attribute(&ignored, file, AttributeValue::Set)
+        {
+            continue;
+        }
+        if has_attribute(&diff, file, AttributeValue::Unset) {
+            sections.push(bullet.render(file, FileChange::NoDiff));
+        } else {
+            sections.push(annotated(bullet.render(file, FileChange::Changed), file));
         }
     }
 
     // Process deleted files
-    for file in deleted_files {
-        writeln!(commit_file, "- `{file}`: deleted\n")?;
+    for file in &deleted_files {
+        if !has_attribute(&ignored, file, AttributeValue::Set) {
+            sections.push(annotated(bullet.render(file, FileChange::Deleted), file));
+        }
     }
 
-    // Close the file
-    commit_file.flush()?;
-
-    tracing::debug!("{} created", commit_message_path.display());
+    Ok(sections.concat())
+}
 
-    Ok(())
+/// Summarizes the staged change of each file (see [`crate::annotations`]), reading
+/// the `HEAD` and staged versions of the files whose definitions are parsed.
+///
+/// # Errors
+/// * If `git diff` fails
+pub fn staged_summaries() -> Result<BTreeMap<String, FileSummary>> {
+    let diff = git_stdout(&[
+        "diff",
+        "--cached",
+        "--unified=0",
+        "--no-color",
+        "--no-ext-diff",
+        "--no-prefix",
+    ])?;
+    Ok(summarize_diff(&diff, |version, path| {
+        let object = match version {
+            Version::Before => format!("HEAD:{path}"),
+            Version::After => format!(":{path}"),
+        };
+        git_stdout(&["show", &object]).ok()
+    }))
 }
 
-/// Writes the commit header to the commit file.
+/// Builds the commit header line, followed by the blank lines separating it from the body.
 ///
 /// # Arguments
-/// * `commit_file` - The file to write to
 /// * `commit_type` - The type of commit
-/// * `no_commit_number` - Whether to include the commit number in the header
+/// * `branch_name` - The formatted branch name
+/// * `commit_number` - The number shown in the header, if any
+fn commit_header(commit_type: &str, branch_name: &str, commit_number: Option<u32>) -> String {
+    let number = commit_number.map_or_else(String::new, |number| format!("[{number}] "));
+    format!("{number}({commit_type} on {branch_name})\n\n\n")
+}
+
+/// The size of the staged changes, as checked against `[commit_size]`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StagedSize {
+    /// Number of staged files
+    pub files: usize,
+    /// Lines added by the staged changes (binary files count none)
+    pub insertions: usize,
+}
+
+/// Measures the staged changes, leaving out files matching an `exempt` pattern.
+///
+/// A pattern matches either the repository-relative path or the file name.
 ///
 /// # Errors
-/// * If writing to the file fails
-fn write_commit_header(
-    commit_file: &mut File,
-    commit_type: &str,
-    no_commit_number: bool,
-) -> Result<()> {
-    let branch_name = format_branch_name(&COMMIT_TYPES, &get_current_branch()?);
+/// * If `git diff --cached --numstat` fails
+pub fn staged_size(exempt: &[Pattern]) -> Result<StagedSize> {
+    Ok(parse_numstat(&staged_numstat()?, exempt))
+}
 
-    if no_commit_number {
-        writeln!(commit_file, "({commit_type} on {branch_name})\n\n")?;
-    } else {
-        let commit_number = get_current_commit_nb()? + 1;
-        writeln!(
-            commit_file,
-            "[{commit_number}] ({commit_type} on {branch_name})\n\n"
-        )?;
+/// A staged file with the lines it changes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StagedFileDiff {
+    /// Repository-relative path; the new path of a rename
+    pub path: String,
+    /// Short status label, e.g. "new file" or "modified" (see [`get_staged_files`])
+    pub status: &'static str,
+    /// Lines added
+    pub insertions: usize,
+    /// Lines removed
+    pub deletions: usize,
+    /// Whether git considers the file binary, which counts no lines
+    pub binary: bool,
+}
+
+/// Lists the staged files with their status and line counts, in path order.
+///
+/// # Errors
+/// * If reading git status or `git diff --cached --numstat` fails
+pub fn staged_file_diffs() -> Result<Vec<StagedFileDiff>> {
+    let lines = numstat_files(&staged_numstat()?);
+    Ok(get_staged_files()?
+        .into_iter()
+        .map(|entry| {
+            let counts = lines.iter().find(|file| file.path == entry.path);
+            StagedFileDiff {
+                insertions: counts.map_or(0, |file| file.insertions),
+                deletions: counts.map_or(0, |file| file.deletions),
+                binary: counts.is_some_and(|file| file.binary),
+                path: entry.path,
+                status: entry.status,
+            }
+        })
+        .collect())
+}
+
+/// Shows the full staged diff in git's pager.
+///
+/// # Errors
+/// * If `git diff --cached` fails
+pub fn show_staged_diff() -> Result<()> {
+    run_interactive_git(&["diff", "--cached"], "diff")
+}
+
+/// Appends the staged diff to a commit message file, commented out below git's scissors
+/// line, so it can be read while writing the message (like `git commit -v`).
+///
+/// A diff appended before is replaced; nothing is appended when nothing is staged.
+/// Its lines start with the comment character of `comments`.
+///
+/// # Errors
+/// * If the file cannot be read or written
+/// * If `git diff --cached` fails
+pub fn append_staged_diff(path: &Path, comments: Comments) -> Result<()> {
+    let content = read_to_string(path)?;
+    let scissors = comments.scissors();
+    let comment = comments.comment_char;
+    let (message, previous) = content
+        .find(&scissors)
+        .map_or((content.as_str(), false), |index| (&content[..index], true));
+    let diff = git_stdout(&["diff", "--cached", "--no-color", "--no-ext-diff"])?;
+    if diff.trim().is_empty() && !previous {
+        return Ok(());
     }
 
+    let mut result = format!("{}\n", message.trim_end());
+    if !diff.trim().is_empty() {
+        let _ = writeln!(
+            result,
+            "\n{scissors}\n{comment} Do not modify or remove the line above.\n{comment} Everything below it is removed before committing."
+        );
+        for line in diff.lines() {
+            let _ = writeln!(result, "{}", format!("{comment} {line}").trim_end());
+        }
+    }
+    write(path, result)?;
     Ok(())
 }
 
-/// Checks if a file should be ignored based on ignored patterns.
+/// Strips everything from the scissors line on and, when `comments.strip`, the lines
+/// starting with the comment character. Trailing blank lines are removed.
+#[must_use]
+pub fn strip_message_comments(content: &str, comments: Comments) -> String {
+    let scissors = comments.scissors();
+    let lines: Vec<&str> = content
+        .lines()
+        .take_while(|line| *line != scissors)
+        .filter(|line| !(comments.strip && line.starts_with(comments.comment_char)))
+        .collect();
+    format!("{}\n", lines.join("\n").trim_end())
+}
+
+/// The message `commit_message.md` holds when it is not committed yet.
 ///
-/// # Arguments
-/// * `file` - The file to check
-/// * `ignore_patterns` - Patterns to check against
+/// A message is pending when it is not empty once comments are stripped, and is not the
+/// start of the `HEAD` message, which commits can extend with trailers or a `TODOs:`
+/// section.
 ///
 /// # Errors
-/// * If checking file paths fails
-///
-/// # Returns
-/// * `true` if the file should be ignored, `false` otherwise
-fn should_ignore_file(file: &str, ignore_patterns: &[String]) -> Result<bool> {
-    use crate::utils::check_for_file_in_folder;
-
-    // Check if the file is directly in the ignore list
-    if ignore_patterns.contains(&file.to_string()) {
-        return Ok(true);
+/// * If the repository root cannot be found or the file cannot be read
+pub fn pending_commit_message(comments: Comments) -> Result<Option<String>> {
+    let path = get_top_level_path()?.join(COMMIT_MESSAGE_FILE_PATH);
+    if !path.exists() {
+        return Ok(None);
     }
+    let message = strip_message_comments(&read_to_string(&path)?, comments);
+    if message.trim().is_empty() {
+        return Ok(None);
+    }
+    // An unborn branch has no HEAD message, so everything in the file is pending.
+    let head = git_stdout(&["log", "-1", "--format=%B"]).unwrap_or_default();
+    Ok((!is_committed_message(&message, &head)).then_some(message))
+}
 
-    // Check if the file is in a folder that's in the ignore list
-    let file_path = Path::new(file);
+/// Whether `head` starts with `message`, ignoring trailing whitespace on each line.
+fn is_committed_message(message: &str, head: &str) -> bool {
+    let normalize = |text: &str| {
+        text.lines()
+            .map(str::trim_end)
+            .collect::<Vec<_>>()
+            .join("\n")
+            .trim()
+            .to_string()
+    };
+    normalize(head).starts_with(&normalize(message))
+}
 
-    for item in ignore_patterns {
-        let item_path = Path::new(item);
+/// Runs `git diff --cached --numstat -z` and returns its output.
+fn staged_numstat() -> Result<String> {
+    let output = Command::new("git")
+        .args(["diff", "--cached", "--numstat", "-z"])
+        .output()
+        .map_err(RonaError::Io)?;
+    if !output.status.success() {
+        return Err(RonaError::Git(GitError::CommandFailed {
+            command: "git diff --cached --numstat".to_string(),
+            output: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        }));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
 
-        if check_for_file_in_folder(file_path, item_path)? {
-            return Ok(true);
+/// Sums `git diff --numstat -z` output, leaving out files matching an `exempt` pattern.
+fn parse_numstat(output: &str, exempt: &[Pattern]) -> StagedSize {
+    let mut size = StagedSize::default();
+    for file in numstat_files(output) {
+        let name = Path::new(&file.path)
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or(&file.path);
+        if exempt
+            .iter()
+            .any(|p| p.matches(&file.path) || p.matches(name))
+        {
+            continue;
         }
+        size.files += 1;
+        size.insertions += file.insertions;
     }
+    size
+}
 
-    Ok(false)
+/// Reads the files of `git diff --numstat -z` output. Renames are records with an
+/// empty path followed by the old and new paths; binary files show `-` counts.
+fn numstat_files(output: &str) -> Vec<StagedFileDiff> {
+    let mut files = Vec::new();
+    let mut fields = output.split('\0');
+    while let Some(record) = fields.next() {
+        let mut parts = record.splitn(3, '\t');
+        let (Some(insertions), Some(deletions), Some(path)) =
+            (parts.next(), parts.next(), parts.next())
+        else {
+            continue;
+        };
+        let path = if path.is_empty() {
+            fields.next();
+            fields.next().unwrap_or_default()
+        } else {
+            path
+        };
+
+        files.push(StagedFileDiff {
+            path: path.to_string(),
+            status: "modified",
+            insertions: insertions.parse().unwrap_or(0),
+            deletions: deletions.parse().unwrap_or(0),
+            binary: insertions == "-",
+        });
+    }
+    files
+}
+
+/// Reverts a commit with git's default revert message.
+///
+/// Runs `git revert --no-edit`, inheriting stdio so hooks and signing prompts work.
+///
+/// # Errors
+/// * If `git revert` fails (for example on conflicts)
+pub fn git_revert(commit: &str, dry_run: bool) -> Result<()> {
+    if dry_run {
+        println!("Would revert {commit}");
+        return Ok(());
+    }
+    ensure_writable("revert a commit")?;
+    run_interactive_git(&["revert", "--no-edit", commit], "revert")
+}
+
+/// Commits the staged changes as a `fixup!` of an earlier commit.
+///
+/// The fixup is squashed into its target by `git rebase --autosquash`.
+///
+/// # Errors
+/// * If `git commit --fixup` fails (for example when nothing is staged)
+pub fn git_commit_fixup(commit: &str, dry_run: bool) -> Result<()> {
+    if dry_run {
+        println!("Would commit the staged changes as a fixup of {commit}");
+        return Ok(());
+    }
+    ensure_writable("commit")?;
+    ensure_allowed(Feature::Commit)?;
+    run_interactive_git(&["commit", &format!("--fixup={commit}")], "commit --fixup")
+}
+
+/// Runs a git command with inherited stdio, so hooks and prompts reach the terminal.
+fn run_interactive_git(args: &[&str], command: &str) -> Result<()> {
+    let status = Command::new("git")
+        .args(args)
+        .status()
+        .map_err(RonaError::Io)?;
+    if !status.success() {
+        return Err(RonaError::Git(GitError::CommandFailed {
+            command: command.to_string(),
+            output: format!("git {command} failed"),
+        }));
+    }
+    Ok(())
 }
 
 #[cfg(test)]
@@ -431,7 +1279,7 @@ mod tests {
         let original_dir = std::env::current_dir()?;
         std::env::set_current_dir(temp_path)?;
 
-        let result = git_commit(&[], true, true);
+        let result = git_commit(&[], Signing::Off, Comments::default(), true);
 
         std::env::set_current_dir(original_dir)?;
 
@@ -439,6 +1287,47 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_file_bullet_default_layout() {
+        let bullet = FileBullet::default();
+        assert_eq!(
+            bullet.render("src/a.rs", FileChange::Changed),
+            "- `src/a.rs`:\n\n\t\n\n"
+        );
+        assert_eq!(
+            bullet.render("old.rs", FileChange::Deleted),
+            "- `old.rs`: deleted\n\n"
+        );
+        assert_eq!(
+            bullet.render("logo.png", FileChange::NoDiff),
+            "- `logo.png`:\n\n"
+        );
+    }
+
+    #[test]
+    fn test_file_bullet_custom_compact_checkbox() {
+        let bullet = FileBullet {
+            format: "* {file} {placeholder}".to_string(),
+            compact: true,
+            checkbox: true,
+            ..FileBullet::default()
+        };
+        assert_eq!(
+            bullet.render("src/a.rs", FileChange::Changed),
+            "- [ ] src/a.rs\n"
+        );
+        assert_eq!(
+            bullet.render("old.rs", FileChange::Deleted),
+            "- [ ] old.rs deleted\n"
+        );
+
+        let plain = FileBullet {
+            format: "{file}:".to_string(),
+            ..FileBullet::default()
+        };
+        assert_eq!(plain.render("a.rs", FileChange::Changed), "a.rs:\n\n\t\n\n");
+    }
+
     /// Verifies that a `pre-commit` hook is triggered when `git_commit` runs.
     ///
     /// The hook writes a marker file. If it fires, the file exists after the commit.
@@ -479,7 +1368,7 @@ mod tests {
         let original_dir = std::env::current_dir()?;
         std::env::set_current_dir(temp_path)?;
 
-        let result = git_commit(&[], true, false);
+        let result = git_commit(&[], Signing::Off, Comments::default(), false);
 
         std::env::set_current_dir(&original_dir)?;
 
@@ -529,7 +1418,7 @@ mod tests {
         let original_dir = std::env::current_dir()?;
         std::env::set_current_dir(temp_path)?;
 
-        let result = git_commit(&[], true, false);
+        let result = git_commit(&[], Signing::Off, Comments::default(), false);
 
         std::env::set_current_dir(&original_dir)?;
 
@@ -539,4 +1428,245 @@ mod tests {
         );
         Ok(())
     }
+
+    /// Verifies that generated messages follow `.gitattributes`: `-diff` files get no
+    /// description line and `rona-ignore` files are left out.
+    #[test]
+    #[cfg(unix)]
+    fn test_render_commit_message_honors_attributes()
+    -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let _guard = DIR_MUTEX.lock().map_err(|e| e.to_string())?;
+
+        let temp_dir = TempDir::new()?;
+        let temp_path = temp_dir.path();
+        init_git_repo(temp_path)?;
+
+        write(
+            temp_path.join(".gitattributes"),
+            "*.png -diff\ngen/** rona-ignore\n",
+        )?;
+        std::fs::create_dir_all(temp_path.join("gen"))?;
+        for file in ["src.rs", "logo.png", "gen/api.rs"] {
+            write(temp_path.join(file), "x")?;
+        }
+        Command::new("git")
+            .current_dir(temp_path)
+            .args(["add", "src.rs", "logo.png", "gen/api.rs"])
+            .output()?;
+
+        let original_dir = std::env::current_dir()?;
+        std::env::set_current_dir(temp_path)?;
+        let result = render_commit_message("feat", "main", None, &FileBullet::default());
+        std::env::set_current_dir(original_dir)?;
+
+        let message = result?;
+        assert!(message.contains("- `src.rs`:\n\n\t\n\n"));
+        assert!(message.contains("- `logo.png`:\n\n"));
+        assert!(!message.contains("- `logo.png`:\n\n\t"));
+        assert!(!message.contains("gen/api.rs"));
+        Ok(())
+    }
+
+    /// Verifies that `annotate` follows each bullet with a comment summarizing the
+    /// staged change, which is stripped before committing.
+    #[test]
+    #[cfg(unix)]
+    fn test_render_commit_message_annotates_bullets()
+    -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let _guard = DIR_MUTEX.lock().map_err(|e| e.to_string())?;
+
+        let temp_dir = TempDir::new()?;
+        let temp_path = temp_dir.path();
+        init_git_repo(temp_path)?;
+
+        write(
+            temp_path.join("login.rs"),
+            "fn login() {}\n\nfn form() {}\n",
+        )?;
+        Command::new("git")
+            .current_dir(temp_path)
+            .args(["add", "login.rs"])
+            .output()?;
+
+        let original_dir = std::env::current_dir()?;
+        std::env::set_current_dir(temp_path)?;
+        let bullet = FileBullet {
+            annotate: true,
+            ..FileBullet::default()
+        };
+        let result = render_commit_message("feat", "main", None, &bullet);
+        std::env::set_current_dir(original_dir)?;
+
+        let message = result?;
+        assert!(message.contains("- `login.rs`:\n# +3 -0; added login(), form()\n\n\t\n\n"));
+        let comments = Comments {
+            strip: true,
+            ..Comments::default()
+        };
+        assert!(!strip_message_comments(&message, comments).contains("added"));
+        Ok(())
+    }
+
+    /// Verifies that `.commitignore` is read from the repository root when rona runs
+    /// from a nested directory, and that listed paths stay relative to the root.
+    #[test]
+    #[cfg(unix)]
+    fn test_render_commit_message_from_subdirectory()
+    -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let _guard = DIR_MUTEX.lock().map_err(|e| e.to_string())?;
+
+        let temp_dir = TempDir::new()?;
+        let temp_path = temp_dir.path();
+        init_git_repo(temp_path)?;
+
+        write(temp_path.join(".commitignore"), "api/Cargo.lock\n")?;
+        std::fs::create_dir_all(temp_path.join("api/src"))?;
+        for file in ["api/src/lib.rs", "api/Cargo.lock"] {
+            write(temp_path.join(file), "x")?;
+        }
+        Command::new("git")
+            .current_dir(temp_path)
+            .args(["add", "api"])
+            .output()?;
+
+        let original_dir = std::env::current_dir()?;
+        std::env::set_current_dir(temp_path.join("api/src"))?;
+        let result = render_commit_message("feat", "main", None, &FileBullet::default());
+        std::env::set_current_dir(original_dir)?;
+
+        let message = result?;
+        assert!(message.contains("- `api/src/lib.rs`:"));
+        assert!(!message.contains("Cargo.lock"));
+        Ok(())
+    }
+
+    /// Verifies that `.commitignore` files of subdirectories apply to their own
+    /// directory, and that a `!` line re-includes a file an earlier line ignored.
+    #[test]
+    #[cfg(unix)]
+    fn test_render_commit_message_nested_commitignore()
+    -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let _guard = DIR_MUTEX.lock().map_err(|e| e.to_string())?;
+
+        let temp_dir = TempDir::new()?;
+        let temp_path = temp_dir.path();
+        init_git_repo(temp_path)?;
+
+        std::fs::create_dir_all(temp_path.join("web/dist"))?;
+        write(temp_path.join(".commitignore"), "web/dist/\n")?;
+        write(
+            temp_path.join("web/.commitignore"),
+            "!dist/keep.js\nyarn.lock\n",
+        )?;
+        for file in [
+            "web/dist/app.js",
+            "web/dist/keep.js",
+            "web/yarn.lock",
+            "yarn.lock",
+        ] {
+            write(temp_path.join(file), "x")?;
+        }
+        Command::new("git")
+            .current_dir(temp_path)
+            .args(["add", "web", "yarn.lock"])
+            .output()?;
+
+        let original_dir = std::env::current_dir()?;
+        std::env::set_current_dir(temp_path)?;
+        let result = render_commit_message("feat", "main", None, &FileBullet::default());
+        std::env::set_current_dir(original_dir)?;
+
+        let message = result?;
+        assert!(!message.contains("web/dist/app.js"));
+        assert!(message.contains("- `web/dist/keep.js`:"));
+        assert!(!message.contains("web/yarn.lock"));
+        // A nested `.commitignore` does not reach outside its directory
+        assert!(message.contains("- `yarn.lock`:"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_strip_message_comments() {
+        let content = format!(
+            "[1] (feat on main) Add login\n# a comment\n\n- `a.rs`: done\n\n\n# {SCISSORS}\n# diff --git a/a.rs b/a.rs\n+fn a() {{}}\n"
+        );
+        let strip = Comments {
+            strip: true,
+            ..Comments::default()
+        };
+        assert_eq!(
+            strip_message_comments(&content, strip),
+            "[1] (feat on main) Add login\n\n- `a.rs`: done\n"
+        );
+        assert_eq!(
+            strip_message_comments("fix typo", Comments::default()),
+            "fix typo\n"
+        );
+
+        // Only the diff below the scissors line goes by default
+        let content = format!("fix: crash\n\nFixes\n#123\n\n# {SCISSORS}\n# diff\n");
+        assert_eq!(
+            strip_message_comments(&content, Comments::default()),
+            "fix: crash\n\nFixes\n#123\n"
+        );
+
+        // The scissors line follows `core.commentChar`
+        let semicolon = Comments {
+            comment_char: ';',
+            strip: true,
+        };
+        let content = format!("fix: crash\n#123\n; a comment\n; {SCISSORS}\ndiff\n");
+        assert_eq!(
+            strip_message_comments(&content, semicolon),
+            "fix: crash\n#123\n"
+        );
+    }
+
+    #[test]
+    fn test_is_committed_message() {
+        let message = "[1] (feat on main) Add login\n\n- `a.rs`: done\n";
+        assert!(is_committed_message(message, message));
+        assert!(is_committed_message(
+            message,
+            "[1] (feat on main) Add login  \n\n- `a.rs`: done\n\nChange-Id: I123\n"
+        ));
+        assert!(!is_committed_message(message, "[0] (chore on main) Init\n"));
+        assert!(!is_committed_message(message, ""));
+    }
+
+    #[test]
+    fn test_parse_numstat() -> std::result::Result<(), glob::PatternError> {
+        let output = "10\t2\tsrc/main.rs\u{0}-\t-\tlogo.png\u{0}300\t0\tCargo.lock\u{0}\
+                      0\t0\t\u{0}old/name.rs\u{0}src/generated/name.rs\u{0}";
+
+        assert_eq!(
+            parse_numstat(output, &[]),
+            StagedSize {
+                files: 4,
+                insertions: 310
+            }
+        );
+        let exempt = [
+            Pattern::new("Cargo.lock")?,
+            Pattern::new("src/generated/**")?,
+        ];
+        assert_eq!(
+            parse_numstat(output, &exempt),
+            StagedSize {
+                files: 2,
+                insertions: 10
+            }
+        );
+        assert_eq!(parse_numstat("", &exempt), StagedSize::default());
+
+        let files = numstat_files(output);
+        assert_eq!(files.len(), 4);
+        assert_eq!(
+            (files[0].insertions, files[0].deletions, files[0].binary),
+            (10, 2, false)
+        );
+        assert!(files[1].binary);
+        assert_eq!(files[3].path, "src/generated/name.rs");
+        Ok(())
+    }
 }
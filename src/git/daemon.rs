@@ -0,0 +1,395 @@
+//! Status Daemon
+//!
+//! `rona -l` is called by shell completions on every Tab press, and each call runs a
+//! full `git status`, which takes seconds in a very large repository. `rona daemon`
+//! keeps the list of changed files and their states warm instead and serves them over
+//! a unix socket in the git directory, to `rona -l`, the prompt and `rona status`.
+//!
+//! The cache is refreshed as soon as the index or `HEAD` changes (staging, committing,
+//! switching branches), checked on every request and every [`POLL_INTERVAL`], and at
+//! least every refresh interval (`[daemon] refresh_interval`, [`REFRESH_INTERVAL`] by
+//! default) to pick up working tree edits. Those are not watched, so a file edited
+//! without touching the index can be missing from the answer for up to that interval,
+//! plus the time `git status` takes. Clients that get no answer fall back to running
+//! `git status` themselves.
+
+use std::{
+    fs,
+    io::{BufRead, BufReader, Read, Write},
+    os::unix::net::{UnixListener, UnixStream},
+    path::{Path, PathBuf},
+    sync::{
+        Arc, Mutex, PoisonError,
+        atomic::{AtomicBool, Ordering},
+    },
+    thread,
+    time::{Duration, Instant, SystemTime},
+};
+
+use crate::errors::{Result, RonaError};
+
+use super::{
+    repository::git_path,
+    status::{FileState, StatusSnapshot},
+};
+
+/// Socket the daemon listens on, under the git directory.
+pub const DAEMON_SOCKET: &str = "rona/daemon.sock";
+
+/// How often the daemon checks the index and `HEAD` for changes.
+pub const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// The longest the cache goes without a refresh by default, bounding how late working
+/// tree edits show up.
+pub const REFRESH_INTERVAL: Duration = Duration::from_secs(2);
+
+/// How long a client waits for the daemon before falling back to `git status`.
+const CLIENT_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// First line of a successful reply, so an empty status is told apart from a failure.
+const REPLY_OK: &str = "ok";
+
+/// The modification times that change whenever git rewrites the index or moves `HEAD`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Stamp {
+    index: Option<SystemTime>,
+    head: Option<SystemTime>,
+}
+
+/// The files whose modification times make up the [`Stamp`].
+#[derive(Debug)]
+struct Watched {
+    index: PathBuf,
+    head: PathBuf,
+}
+
+impl Watched {
+    fn stamp(&self) -> Stamp {
+        let modified = |path: &Path| fs::metadata(path).and_then(|m| m.modified()).ok();
+        Stamp {
+            index: modified(&self.index),
+            head: modified(&self.head),
+        }
+    }
+}
+
+/// Changed files with their states, as `rona status` lists them.
+type FileStates = Vec<(FileState, String)>;
+
+/// The cached `rona -l` and `rona status` answers and when they were computed.
+#[derive(Debug)]
+struct Cache {
+    files: Vec<String>,
+    states: FileStates,
+    stamp: Stamp,
+    refreshed: Instant,
+}
+
+impl Cache {
+    /// Stores the files and states read from `git status` at `stamp`. A failing
+    /// `git status` keeps the previous answers.
+    fn store(&mut self, stamp: Stamp, read: Result<(Vec<String>, FileStates)>) {
+        match read {
+            Ok((mut files, states)) => {
+                files.sort();
+                self.files = files;
+                self.states = states;
+            }
+            Err(e) => eprintln!("rona daemon: git status failed: {e}"),
+        }
+        self.stamp = stamp;
+        self.refreshed = Instant::now();
+    }
+}
+
+/// Recomputes the cache. The stamp is read first, so a change made while `git status`
+/// runs triggers another refresh.
+fn refresh(cache: &Mutex<Cache>, watched: &Watched) {
+    let stamp = watched.stamp();
+    let status = StatusSnapshot::read().map(|status| (status.status_files(), status.file_states()));
+    cache
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .store(stamp, status);
+}
+
+/// Refreshes the cache before answering when the index or `HEAD` changed since it was
+/// computed, so staging or committing shows up at once.
+fn refresh_if_changed(cache: &Mutex<Cache>, watched: &Watched) {
+    if watched.stamp() != cache.lock().unwrap_or_else(PoisonError::into_inner).stamp {
+        refresh(cache, watched);
+    }
+}
+
+/// Whether the index or `HEAD` changed since the cache was computed, or the cache is
+/// older than `refresh_interval`.
+fn is_stale(cache: &Mutex<Cache>, watched: &Watched, refresh_interval: Duration) -> bool {
+    let stamp = watched.stamp();
+    let cache = cache.lock().unwrap_or_else(PoisonError::into_inner);
+    cache.stamp != stamp || cache.refreshed.elapsed() >= refresh_interval
+}
+
+/// The socket path of the current repository's daemon.
+///
+/// # Errors
+/// * If the git directory cannot be located
+pub fn daemon_socket() -> Result<PathBuf> {
+    git_path(DAEMON_SOCKET)
+}
+
+/// Runs the daemon in the foreground until `rona daemon --stop` asks it to exit,
+/// refreshing the cache at least every `refresh_interval`.
+///
+/// # Errors
+/// * If another daemon already serves this repository
+/// * If the socket cannot be created
+pub fn run_daemon(socket: &Path, refresh_interval: Duration) -> Result<()> {
+    if socket.exists() {
+        if UnixStream::connect(socket).is_ok() {
+            return Err(RonaError::InvalidInput(format!(
+                "A rona daemon is already running on {}",
+                socket.display()
+            )));
+        }
+        // Left behind by a daemon that was killed.
+        fs::remove_file(socket)?;
+    }
+    if let Some(dir) = socket.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    let watched = Arc::new(Watched {
+        index: git_path("index")?,
+        head: git_path("HEAD")?,
+    });
+    let cache = Arc::new(Mutex::new(Cache {
+        files: Vec::new(),
+        states: Vec::new(),
+        stamp: watched.stamp(),
+        refreshed: Instant::now(),
+    }));
+    refresh(&cache, &watched);
+
+    let listener = UnixListener::bind(socket)?;
+    let stopping = Arc::new(AtomicBool::new(false));
+    {
+        let (cache, watched, stopping) = (cache.clone(), watched.clone(), stopping.clone());
+        thread::spawn(move || {
+            while !stopping.load(Ordering::Relaxed) {
+                thread::sleep(POLL_INTERVAL);
+                if is_stale(&cache, &watched, refresh_interval) {
+                    refresh(&cache, &watched);
+                }
+            }
+        });
+    }
+
+    for stream in listener.incoming() {
+        let Ok(stream) = stream else {
+            continue;
+        };
+        match serve(stream, &cache, &watched) {
+            Ok(true) => break,
+            Ok(false) => {}
+            Err(e) => eprintln!("rona daemon: {e}"),
+        }
+    }
+
+    stopping.store(true, Ordering::Relaxed);
+    fs::remove_file(socket)?;
+    Ok(())
+}
+
+/// Answers one request. Returns `true` when the daemon was asked to stop.
+fn serve(stream: UnixStream, cache: &Mutex<Cache>, watched: &Watched) -> Result<bool> {
+    stream.set_read_timeout(Some(CLIENT_TIMEOUT))?;
+    let mut request = String::new();
+    BufReader::new(&stream).read_line(&mut request)?;
+    let mut stream = stream;
+
+    match request.trim() {
+        "status" => {
+            refresh_if_changed(cache, watched);
+            let mut reply = format!("{REPLY_OK}\n");
+            for file in &cache.lock().unwrap_or_else(PoisonError::into_inner).files {
+                reply.push_str(file);
+                reply.push('\n');
+            }
+            stream.write_all(reply.as_bytes())?;
+            Ok(false)
+        }
+        "states" => {
+            refresh_if_changed(cache, watched);
+            let states =
+                serde_json::to_string(&cache.lock().unwrap_or_else(PoisonError::into_inner).states)
+                    .map_err(std::io::Error::other)?;
+            stream.write_all(format!("{REPLY_OK}\n{states}\n").as_bytes())?;
+            Ok(false)
+        }
+        "stop" => {
+            stream.write_all(format!("{REPLY_OK}\n").as_bytes())?;
+            Ok(true)
+        }
+        other => {
+            stream.write_all(format!("unknown request: {other}\n").as_bytes())?;
+            Ok(false)
+        }
+    }
+}
+
+/// Sends a request to the daemon and returns the reply after its `ok` line.
+fn request(socket: &Path, request: &str) -> std::io::Result<String> {
+    let mut stream = UnixStream::connect(socket)?;
+    stream.set_read_timeout(Some(CLIENT_TIMEOUT))?;
+    stream.set_write_timeout(Some(CLIENT_TIMEOUT))?;
+    stream.write_all(format!("{request}\n").as_bytes())?;
+    stream.shutdown(std::net::Shutdown::Write)?;
+
+    let mut reply = String::new();
+    stream.read_to_string(&mut reply)?;
+    reply
+        .strip_prefix(REPLY_OK)
+        .and_then(|rest| rest.strip_prefix('\n'))
+        .map(ToString::to_string)
+        .ok_or_else(|| std::io::Error::other(reply.trim().to_string()))
+}
+
+/// The changed files, as `rona -l` lists them, from the daemon serving the current
+/// repository. `None` when no daemon answers, and the caller runs `git status` itself.
+#[must_use]
+pub fn cached_status_files() -> Option<Vec<String>> {
+    let socket = daemon_socket().ok()?;
+    if !socket.exists() {
+        return None;
+    }
+    let reply = request(&socket, "status").ok()?;
+    Some(reply.lines().map(String::from).collect())
+}
+
+/// The changed files with their states, as `rona status` lists them, from the daemon
+/// serving the current repository. `None` when no daemon answers.
+#[must_use]
+pub fn cached_file_states() -> Option<FileStates> {
+    let socket = daemon_socket().ok()?;
+    if !socket.exists() {
+        return None;
+    }
+    let reply = request(&socket, "states").ok()?;
+    serde_json::from_str(reply.trim()).ok()
+}
+
+/// Asks the daemon of the current repository to exit.
+///
+/// # Errors
+/// * If the git directory cannot be located
+/// * If a stale socket cannot be removed
+///
+/// # Returns
+/// * `true` if a running daemon was stopped
+pub fn stop_daemon() -> Result<bool> {
+    let socket = daemon_socket()?;
+    if !socket.exists() {
+        return Ok(false);
+    }
+    if request(&socket, "stop").is_ok() {
+        return Ok(true);
+    }
+    // Nobody listens: the daemon was killed without cleaning up.
+    fs::remove_file(&socket)?;
+    Ok(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+
+    use super::*;
+
+    /// Watched files in a temporary directory, and a cache computed from their stamp.
+    fn setup(dir: &Path) -> Result<(Watched, Mutex<Cache>)> {
+        let watched = Watched {
+            index: dir.join("index"),
+            head: dir.join("HEAD"),
+        };
+        fs::write(&watched.index, "index")?;
+        fs::write(&watched.head, "ref: refs/heads/main\n")?;
+        let cache = Mutex::new(Cache {
+            files: vec!["a.txt".to_string()],
+            states: vec![(FileState::Modified, "a.txt".to_string())],
+            stamp: watched.stamp(),
+            refreshed: Instant::now(),
+        });
+        Ok((watched, cache))
+    }
+
+    #[test]
+    fn test_serve() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let (watched, cache) = setup(dir.path())?;
+        let socket = dir.path().join("daemon.sock");
+        let listener = UnixListener::bind(&socket)?;
+
+        let server = thread::spawn(move || -> Result<Vec<bool>> {
+            let mut stops = Vec::new();
+            for stream in listener.incoming().take(4) {
+                stops.push(serve(stream?, &cache, &watched)?);
+            }
+            Ok(stops)
+        });
+        assert_eq!(request(&socket, "status")?, "a.txt\n");
+        assert_eq!(request(&socket, "states")?, "[[\"modified\",\"a.txt\"]]\n");
+        assert!(request(&socket, "files").is_err());
+        assert_eq!(request(&socket, "stop")?, "");
+
+        let stops = server
+            .join()
+            .map_err(|_| RonaError::InvalidInput("the daemon thread panicked".to_string()))??;
+        assert_eq!(stops, [false, false, false, true]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_is_stale() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let (watched, cache) = setup(dir.path())?;
+        assert!(!is_stale(&cache, &watched, REFRESH_INTERVAL));
+        assert!(is_stale(&cache, &watched, Duration::ZERO));
+
+        // Staging rewrites the index.
+        File::options()
+            .write(true)
+            .open(&watched.index)?
+            .set_modified(SystemTime::UNIX_EPOCH)?;
+        assert!(is_stale(&cache, &watched, REFRESH_INTERVAL));
+        Ok(())
+    }
+
+    #[test]
+    fn test_refresh() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let (watched, cache) = setup(dir.path())?;
+        fs::remove_file(&watched.head)?;
+        let stamp = watched.stamp();
+        let mut cache = cache.into_inner().unwrap_or_else(PoisonError::into_inner);
+
+        cache.store(
+            stamp,
+            Ok((
+                vec!["z.txt".to_string(), "b.txt".to_string()],
+                vec![(FileState::Untracked, "z.txt".to_string())],
+            )),
+        );
+        assert_eq!(cache.files, ["b.txt", "z.txt"]);
+        assert_eq!(cache.states, [(FileState::Untracked, "z.txt".to_string())]);
+        assert_eq!(cache.stamp, stamp);
+
+        let before = cache.refreshed;
+        cache.store(
+            stamp,
+            Err(RonaError::InvalidInput("no repository".to_string())),
+        );
+        assert_eq!(cache.files, ["b.txt", "z.txt"]);
+        assert!(cache.refreshed >= before);
+        Ok(())
+    }
+}
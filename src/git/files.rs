@@ -10,9 +10,14 @@ use std::{
     path::Path,
 };
 
+use glob::{MatchOptions, Pattern};
+
 use crate::{
-    errors::Result,
-    git::{COMMIT_MESSAGE_FILE_PATH, find_git_root, get_top_level_path},
+    errors::{GitError, Result, RonaError},
+    git::{
+        COMMIT_MESSAGE_FILE_PATH, get_top_level_path,
+        repository::{ensure_writable, git_command_in, git_path},
+    },
 };
 
 const COMMITIGNORE_FILE_PATH: &str = ".commitignore";
@@ -20,6 +25,9 @@ const GITIGNORE_FILE_PATH: &str = ".gitignore";
 
 /// Add paths to the `.git/info/exclude` file.
 ///
+/// The file is located with [`git_path`], so `GIT_DIR` and linked worktrees are
+/// respected.
+///
 /// # Arguments
 /// * `paths` - List of paths to add to the exclude file.
 ///
@@ -29,13 +37,15 @@ const GITIGNORE_FILE_PATH: &str = ".gitignore";
 /// # Returns
 /// * `Result<(), std::io::Error>` - Result of the operation.
 pub fn add_to_git_exclude(paths: &[&str]) -> Result<()> {
-    let git_root_path = find_git_root()?;
-    let info_dir = git_root_path.join("info");
-    let exclude_file = info_dir.join("exclude");
+    ensure_writable("edit .git/info/exclude")?;
+
+    let exclude_file = git_path("info/exclude")?;
 
     // Ensure the info directory exists
-    if !info_dir.exists() {
-        std::fs::create_dir_all(&info_dir)?;
+    if let Some(info_dir) = exclude_file.parent()
+        && !info_dir.exists()
+    {
+        std::fs::create_dir_all(info_dir)?;
     }
 
     // Read existing content to avoid duplicates
@@ -90,6 +100,8 @@ pub fn add_to_git_exclude(paths: &[&str]) -> Result<()> {
 /// * If the files cannot be created.
 /// * If the git add command fails.
 pub fn create_needed_files() -> Result<()> {
+    ensure_writable("create commit_message.md and .commitignore")?;
+
     let project_root = get_top_level_path()?;
 
     let commit_file_path = Path::new(&project_root).join(COMMIT_MESSAGE_FILE_PATH);
@@ -108,47 +120,280 @@ pub fn create_needed_files() -> Result<()> {
     Ok(())
 }
 
-/// Gets all patterns from commitignore and gitignore files.
+/// Appends lines to `.commitignore` at the repository root, skipping the ones it
+/// already has. The file is created when missing.
 ///
 /// # Errors
-/// * If reading the ignored files fails
+/// * If the file cannot be read or written
 ///
 /// # Returns
-/// * A vector of patterns to ignore
-pub fn get_ignore_patterns() -> Result<Vec<String>> {
-    let commitignore_path = Path::new(COMMITIGNORE_FILE_PATH);
+/// * The number of lines added
+pub fn add_to_commitignore(lines: &[&str]) -> Result<usize> {
+    ensure_writable("edit .commitignore")?;
 
-    if !commitignore_path.exists() {
-        return Ok(Vec::new());
+    let path = get_top_level_path()?.join(COMMITIGNORE_FILE_PATH);
+    let content = if path.exists() {
+        read_to_string(&path)?
+    } else {
+        String::new()
+    };
+    let existing: HashSet<&str> = content.lines().map(str::trim).collect();
+    let mut new_lines: Vec<&str> = Vec::new();
+    for line in lines {
+        if !existing.contains(line) && !new_lines.contains(line) {
+            new_lines.push(line);
+        }
+    }
+    if new_lines.is_empty() {
+        return Ok(0);
+    }
+
+    let mut file = OpenOptions::new().create(true).append(true).open(&path)?;
+    if !content.is_empty() && !content.ends_with('\n') {
+        writeln!(file)?;
+    }
+    for line in &new_lines {
+        writeln!(file, "{line}")?;
     }
+    Ok(new_lines.len())
+}
 
-    let mut patterns = process_gitignore_file()?;
-    patterns.append(&mut process_gitignore_file()?);
+/// `*` stays within one directory, as in `.gitignore`; `**` crosses directories.
+const IGNORE_MATCH_OPTIONS: MatchOptions = MatchOptions {
+    case_sensitive: true,
+    require_literal_separator: true,
+    require_literal_leading_dot: false,
+};
 
-    Ok(patterns)
+/// A line of a `.commitignore` or `.gitignore` file, such as `Cargo.lock`, `*.lock`,
+/// `docs/**` or `**/*.snap`.
+///
+/// The glob is compiled once when the file is read and reused for every path.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IgnorePattern {
+    /// Directory of the ignore file relative to the repository root, empty at the root
+    pub dir: String,
+    /// The line without `!` and surrounding `/`. A line that is not a valid glob
+    /// matches literally.
+    pub glob: Pattern,
+    /// Whether the line starts with `!`, re-including what an earlier line ignored
+    pub negated: bool,
+    /// Whether the line ends with `/`, so it only matches folders
+    pub folder_only: bool,
+    /// Whether the line has a `/` before its end, so it matches paths from `dir`
+    /// instead of names at any depth
+    pub anchored: bool,
 }
 
-/// Processes the gitignore file.
+impl IgnorePattern {
+    /// Whether the pattern matches `file`, a path relative to the repository root,
+    /// or one of its folders.
+    #[must_use]
+    pub fn matches(&self, file: &str) -> bool {
+        let relative = if self.dir.is_empty() {
+            Some(file)
+        } else {
+            file.strip_prefix(&self.dir)
+                .and_then(|rest| rest.strip_prefix('/'))
+        };
+        let Some(relative) = relative else {
+            return false;
+        };
+
+        let components: Vec<&str> = relative.trim_end_matches('/').split('/').collect();
+        let last = if self.folder_only {
+            components.len() - 1
+        } else {
+            components.len()
+        };
+        (1..=last).any(|len| {
+            if self.anchored {
+                self.glob
+                    .matches_with(&components[..len].join("/"), IGNORE_MATCH_OPTIONS)
+            } else {
+                self.glob
+                    .matches_with(components[len - 1], IGNORE_MATCH_OPTIONS)
+            }
+        })
+    }
+}
+
+/// Gets the patterns of every `.commitignore` and `.gitignore` file of the working
+/// tree, wherever rona is run from. Nothing is ignored without a `.commitignore`.
+///
+/// Files are read from the root down, a `.gitignore` before the `.commitignore` of
+/// the same directory, so the last matching pattern decides (see
+/// [`is_ignored`]).
 ///
 /// # Errors
-/// * If the gitignore file is not found
-/// * If the gitignore file cannot be read
-/// * If the gitignore file contains invalid patterns
+/// * If the repository root cannot be found
+/// * If listing or reading the ignore files fails
 ///
 /// # Returns
-/// * `Result<Vec<String>, Error>` - The files and folders to ignore or an error message
-pub fn process_gitignore_file() -> Result<Vec<String>> {
-    // look for the gitignore file
-    let gitignore_file_path = Path::new(GITIGNORE_FILE_PATH);
-    //
-    if !gitignore_file_path.exists() {
+/// * The patterns in the order they apply
+pub fn get_ignore_patterns() -> Result<Vec<IgnorePattern>> {
+    let repo_root = get_top_level_path()?;
+    let mut files = ignore_files(&repo_root)?;
+    if !files
+        .iter()
+        .any(|file| file.ends_with(COMMITIGNORE_FILE_PATH))
+    {
         return Ok(Vec::new());
     }
+    files.sort_by_key(|file| {
+        let (dir, name) = file.rsplit_once('/').unwrap_or(("", file));
+        (
+            dir.matches('/').count() + usize::from(!dir.is_empty()),
+            dir.to_string(),
+            name == COMMITIGNORE_FILE_PATH,
+        )
+    });
+
+    let mut patterns = Vec::new();
+    for file in files {
+        let dir = file.rsplit_once('/').map_or("", |(dir, _)| dir);
+        patterns.extend(parse_ignore_file(
+            &read_to_string(repo_root.join(&file))?,
+            dir,
+        ));
+    }
+    Ok(patterns)
+}
+
+/// Whether `file` is ignored by `patterns`: the last pattern matching it decides,
+/// and a negated one re-includes it.
+#[must_use]
+pub fn is_ignored(file: &str, patterns: &[IgnorePattern]) -> bool {
+    patterns
+        .iter()
+        .rev()
+        .find(|pattern| pattern.matches(file))
+        .is_some_and(|pattern| !pattern.negated)
+}
+
+/// Lists the `.commitignore` and `.gitignore` files of the working tree, relative to
+/// the repository root. Untracked `.commitignore` files are listed even though
+/// `create_needed_files` excludes them from git.
+fn ignore_files(repo_root: &Path) -> Result<Vec<String>> {
+    let list = |args: &[&str], names: &[&str]| -> Result<Vec<String>> {
+        let pathspecs = names.iter().map(|name| format!(":(glob)**/{name}"));
+        let output = git_command_in(repo_root)?
+            .args(["ls-files", "-z", "--exclude-standard"])
+            .args(args)
+            .arg("--")
+            .args(pathspecs)
+            .output()
+            .map_err(RonaError::Io)?;
+        if !output.status.success() {
+            return Err(RonaError::Git(GitError::CommandFailed {
+                command: "git ls-files".to_string(),
+                output: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+            }));
+        }
+        Ok(String::from_utf8_lossy(&output.stdout)
+            .split('\0')
+            .filter(|path| !path.is_empty())
+            .map(ToString::to_string)
+            .collect())
+    };
 
-    let git_ignore_file_contents = read_to_string(gitignore_file_path)?;
+    let mut files = list(
+        &["--cached", "--others"],
+        &[COMMITIGNORE_FILE_PATH, GITIGNORE_FILE_PATH],
+    )?;
+    files.extend(list(&["--others", "--ignored"], &[COMMITIGNORE_FILE_PATH])?);
+    files.sort();
+    files.dedup();
+    files.retain(|file| repo_root.join(file).is_file());
+    Ok(files)
+}
 
-    extract_filenames(&git_ignore_file_contents, r"^([^#]\S*)$")
+/// Parses the lines of an ignore file found in `dir`, relative to the repository
+/// root.
+///
+/// Blank lines and `#` comments are skipped, `!` negates a line, and `\\` escapes a
+/// leading `!` or `#`. As in `.gitignore`, a line without a `/` (but a trailing one)
+/// matches names at any depth below `dir`, and any other line matches paths from
+/// `dir`.
+#[must_use]
+pub fn parse_ignore_file(content: &str, dir: &str) -> Vec<IgnorePattern> {
+    content
+        .lines()
+        .map(str::trim_end)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| {
+            let (negated, line) = line
+                .strip_prefix('!')
+                .map_or((false, line), |rest| (true, rest));
+            let line = line.strip_prefix('\\').unwrap_or(line);
+            let folder_only = line.ends_with('/');
+            let glob = line.trim_matches('/');
+            if glob.is_empty() {
+                return None;
+            }
+            Some(IgnorePattern {
+                dir: dir.to_string(),
+                glob: Pattern::new(glob)
+                    .or_else(|_| Pattern::new(&Pattern::escape(glob)))
+                    .ok()?,
+                negated,
+                folder_only,
+                anchored: line.trim_end_matches('/').contains('/'),
+            })
+        })
+        .collect()
 }
 
-// Use the shared extract_filenames function from the parent module
-use super::extract_filenames;
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_ignore_file() {
+        let patterns =
+            parse_ignore_file("# lock files\nCargo.lock\n\n/dist/\n!dist/keep.js\n", "web");
+        assert_eq!(patterns.len(), 3);
+        assert_eq!(patterns[0].glob.as_str(), "Cargo.lock");
+        assert!(!patterns[0].anchored);
+        assert_eq!(patterns[1].glob.as_str(), "dist");
+        assert!(patterns[1].anchored && patterns[1].folder_only);
+        assert!(patterns[2].negated && patterns[2].anchored);
+
+        // Escapes, and lines that name nothing
+        assert_eq!(
+            parse_ignore_file("\\#notes.md\n", "")[0].glob.as_str(),
+            "#notes.md"
+        );
+        assert!(parse_ignore_file("/\n!\n", "").is_empty());
+        // An invalid glob matches literally
+        assert!(is_ignored("[draft", &parse_ignore_file("[draft\n", "")));
+    }
+
+    #[test]
+    fn test_is_ignored_globs() {
+        let patterns = parse_ignore_file("*.lock\ndocs/**\n**/*.snap\nbuild/\n", "");
+        // A name without `/` matches at any depth
+        assert!(is_ignored("Cargo.lock", &patterns));
+        assert!(is_ignored("web/yarn.lock", &patterns));
+        assert!(is_ignored("docs/guide/intro.md", &patterns));
+        assert!(!is_ignored("src/docs/intro.md", &patterns));
+        assert!(is_ignored("tests/snapshots/cli.snap", &patterns));
+        assert!(is_ignored("web/build/app.js", &patterns));
+        // A trailing `/` only matches folders
+        assert!(!is_ignored("build", &patterns));
+        assert!(!is_ignored("src/main.rs", &patterns));
+    }
+
+    #[test]
+    fn test_is_ignored_nested_and_negated() {
+        let mut patterns = parse_ignore_file("dist/\n", "");
+        patterns.extend(parse_ignore_file("!/dist/keep.js\n*.lock\n", "web"));
+        assert!(is_ignored("dist/bundle.js", &patterns));
+        assert!(is_ignored("web/dist/bundle.js", &patterns));
+        assert!(!is_ignored("web/dist/keep.js", &patterns));
+        assert!(is_ignored("web/yarn.lock", &patterns));
+        // Patterns of a nested file do not reach outside its directory
+        assert!(!is_ignored("yarn.lock", &patterns));
+        assert!(!is_ignored("webapp/yarn.lock", &patterns));
+    }
+}
@@ -0,0 +1,218 @@
+//! Handoffs
+//!
+//! Git side of `rona handoff`: the work in progress of a branch, its staged changes
+//! and the pending `commit_message.md`, is saved as a commit on top of `HEAD` and
+//! pushed to `<namespace>/<branch>` on a remote, from which another clone restores it.
+//!
+//! The tree of the handoff commit is the index, so only staged changes travel. Its
+//! message is `rona handoff of <branch>`, followed by the pending commit message.
+
+use crate::errors::{Result, RonaError};
+
+use super::{
+    branch::get_current_branch,
+    repository::{Feature, ensure_allowed, ensure_writable, git_stdout, run_git},
+};
+
+/// Namespace of handoff refs when `[handoff] namespace` is not set.
+pub const DEFAULT_HANDOFF_NAMESPACE: &str = "refs/rona/handoff";
+
+/// First line of the message of a handoff commit, before the branch name.
+const HANDOFF_SUBJECT: &str = "rona handoff of ";
+
+/// Work in progress fetched from a remote.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Handoff {
+    /// The handoff commit, whose tree is the staged state
+    pub commit: String,
+    /// The commit the branch was at, parent of the handoff commit
+    pub base: String,
+    /// The branch the work was handed off from
+    pub branch: String,
+    /// The pending commit message, if there was one
+    pub message: Option<String>,
+}
+
+/// The ref a branch's handoff is pushed to.
+#[must_use]
+pub fn handoff_ref(namespace: &str, branch: &str) -> String {
+    format!("{}/{branch}", namespace.trim_end_matches('/'))
+}
+
+/// The message of a handoff commit.
+#[must_use]
+pub fn handoff_message(branch: &str, message: Option<&str>) -> String {
+    let mut text = format!("{HANDOFF_SUBJECT}{branch}\n");
+    if let Some(message) = message.map(str::trim).filter(|m| !m.is_empty()) {
+        text.push('\n');
+        text.push_str(message);
+        text.push('\n');
+    }
+    text
+}
+
+/// Splits the message of a handoff commit into its branch and pending message.
+#[must_use]
+pub fn parse_handoff_message(text: &str) -> Option<(String, Option<String>)> {
+    let (subject, rest) = text.split_once('\n').unwrap_or((text, ""));
+    let branch = subject.trim().strip_prefix(HANDOFF_SUBJECT)?;
+    let message = rest.trim();
+    Some((
+        branch.to_string(),
+        (!message.is_empty()).then(|| format!("{message}\n")),
+    ))
+}
+
+/// Saves the index as a handoff commit on top of `HEAD`, without moving any ref.
+///
+/// # Errors
+/// * If the index has conflicts or the commit cannot be created
+pub fn create_handoff_commit(branch: &str, message: Option<&str>) -> Result<String> {
+    let tree = git_stdout(&["write-tree"])?;
+    let commit = git_stdout(&[
+        "commit-tree",
+        "--no-gpg-sign",
+        tree.trim(),
+        "-p",
+        "HEAD",
+        "-m",
+        &handoff_message(branch, message),
+    ])?;
+    Ok(commit.trim().to_string())
+}
+
+/// Pushes a handoff commit to `reference` on `remote`, replacing the previous one.
+///
+/// # Errors
+/// * If in read-only mode, or pushing is denied by a guard
+/// * If `git push` fails
+pub fn push_handoff(remote: &str, commit: &str, reference: &str) -> Result<()> {
+    ensure_writable("push a handoff")?;
+    ensure_allowed(Feature::Push)?;
+
+    git_stdout(&[
+        "push",
+        "--quiet",
+        "--force",
+        remote,
+        &format!("{commit}:{reference}"),
+    ])?;
+    Ok(())
+}
+
+/// Fetches the handoff at `reference` on `remote`.
+///
+/// # Errors
+/// * If fetching is denied by a guard
+/// * If the remote has no such ref, or it is not a handoff commit
+pub fn fetch_handoff(remote: &str, reference: &str) -> Result<Handoff> {
+    ensure_allowed(Feature::Fetch)?;
+
+    let output = run_git(&["fetch", "--quiet", "--no-tags", remote, reference])?;
+    if !output.status.success() {
+        return Err(RonaError::InvalidInput(format!(
+            "No handoff at {reference} on '{remote}'"
+        )));
+    }
+    let commit = git_stdout(&["rev-parse", "--verify", "FETCH_HEAD^0"])?
+        .trim()
+        .to_string();
+    let details = git_stdout(&["log", "-1", "--format=%P%n%B", &commit])?;
+    let (parents, text) = details.split_once('\n').unwrap_or((&details, ""));
+    let mut parents = parents.split_whitespace();
+    let (Some(base), None, Some((branch, message))) =
+        (parents.next(), parents.next(), parse_handoff_message(text))
+    else {
+        return Err(RonaError::InvalidInput(format!(
+            "{reference} on '{remote}' is not a rona handoff"
+        )));
+    };
+    Ok(Handoff {
+        commit,
+        base: base.to_string(),
+        branch,
+        message,
+    })
+}
+
+/// The files a handoff changes compared to its base.
+///
+/// # Errors
+/// * If `git diff` fails
+pub fn handoff_files(handoff: &Handoff) -> Result<Vec<String>> {
+    Ok(
+        git_stdout(&["diff", "--name-only", &handoff.base, &handoff.commit])?
+            .lines()
+            .map(ToString::to_string)
+            .collect(),
+    )
+}
+
+/// Checks out `branch` at the base of a handoff and stages the handed-off changes,
+/// in the index and the working tree. The branch is created when missing and
+/// fast-forwarded when it is behind the base.
+///
+/// # Errors
+/// * If in read-only mode
+/// * If the branch has commits the base does not
+/// * If checking out or reading the tree fails
+pub fn restore_handoff(handoff: &Handoff, branch: &str) -> Result<()> {
+    ensure_writable("restore a handoff")?;
+
+    let local = format!("refs/heads/{branch}");
+    if run_git(&["show-ref", "--verify", "--quiet", &local])?
+        .status
+        .success()
+    {
+        let is_ancestor = run_git(&["merge-base", "--is-ancestor", &local, &handoff.base])?
+            .status
+            .success();
+        if !is_ancestor {
+            return Err(RonaError::InvalidInput(format!(
+                "Branch '{branch}' has commits the handoff was not based on; \
+                 rename it or reset it to {}",
+                &handoff.base[..handoff.base.len().min(7)]
+            )));
+        }
+        if get_current_branch().ok().as_deref() != Some(branch) {
+            git_stdout(&["switch", "--quiet", branch])?;
+        }
+        git_stdout(&["merge", "--ff-only", "--quiet", &handoff.base])?;
+    } else {
+        git_stdout(&["switch", "--quiet", "--create", branch, &handoff.base])?;
+    }
+
+    // A two-tree merge from HEAD to the handoff tree also applies deletions.
+    git_stdout(&["read-tree", "-u", "-m", "HEAD", &handoff.commit])?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_handoff_message() {
+        let text = handoff_message("feat/login", Some("(feat on login) add form\n\nBody.\n"));
+        assert_eq!(
+            text,
+            "rona handoff of feat/login\n\n(feat on login) add form\n\nBody.\n"
+        );
+        assert_eq!(
+            parse_handoff_message(&text),
+            Some((
+                "feat/login".to_string(),
+                Some("(feat on login) add form\n\nBody.\n".to_string())
+            ))
+        );
+        assert_eq!(
+            parse_handoff_message(&handoff_message("main", Some("  "))),
+            Some(("main".to_string(), None))
+        );
+        assert_eq!(parse_handoff_message("WIP on main"), None);
+        assert_eq!(
+            handoff_ref("refs/rona/handoff/", "feat/login"),
+            "refs/rona/handoff/feat/login"
+        );
+    }
+}
@@ -0,0 +1,156 @@
+//! Managed Git Hooks
+//!
+//! `rona hooks install` writes `pre-commit`, `commit-msg` and `pre-push` scripts into
+//! the hooks directory (`core.hooksPath`, else `.git/hooks`). Each script only calls
+//! back into `rona hooks run <hook>`, so upgrading rona upgrades the hooks too.
+//!
+//! Every script rona writes carries [`HOOK_SENTINEL`]. Rona only overwrites or removes
+//! scripts that carry it, so hooks written by hand or by other tools are never touched.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::errors::Result;
+
+use super::repository::git_path;
+
+/// The hooks rona can install, in the order git runs them.
+pub const MANAGED_HOOKS: [&str; 3] = ["pre-commit", "commit-msg", "pre-push"];
+
+/// The line marking a hook script as written by rona. Deleting it from a script hands
+/// the script over to the user: rona leaves it alone from then on.
+pub const HOOK_SENTINEL: &str = "# Managed by rona: `rona hooks uninstall` removes this hook.";
+
+/// Who owns a hook script.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HookState {
+    /// No script for the hook
+    Missing,
+    /// A script written by `rona hooks install`
+    Managed,
+    /// A script rona did not write, or one whose sentinel line was removed
+    Unmanaged,
+}
+
+/// A hook and its script in the hooks directory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HookStatus {
+    /// The hook name, e.g. `pre-commit`
+    pub name: &'static str,
+    /// Where git looks for the script
+    pub path: PathBuf,
+    /// Who owns the script
+    pub state: HookState,
+}
+
+/// The directory git runs hooks from, honoring `core.hooksPath` and worktrees.
+///
+/// # Errors
+/// * If not in a git repository
+pub fn hooks_dir() -> Result<PathBuf> {
+    git_path("hooks")
+}
+
+/// The script rona installs for `hook`.
+#[must_use]
+pub fn hook_script(hook: &str) -> String {
+    format!(
+        "#!/bin/sh\n\
+         {HOOK_SENTINEL}\n\
+         command -v rona >/dev/null 2>&1 || {{\n\
+         \techo \"rona not found in PATH; skipping the {hook} hook\" >&2\n\
+         \texit 0\n\
+         }}\n\
+         exec rona hooks run {hook} \"$@\"\n"
+    )
+}
+
+/// Who owns the script at `path`.
+fn hook_state(path: &Path) -> HookState {
+    if !path.exists() {
+        return HookState::Missing;
+    }
+    match fs::read_to_string(path) {
+        Ok(content) if content.lines().any(|line| line == HOOK_SENTINEL) => HookState::Managed,
+        _ => HookState::Unmanaged,
+    }
+}
+
+/// The state of every hook rona can install.
+///
+/// # Errors
+/// * If the hooks directory cannot be located
+pub fn list_hooks() -> Result<Vec<HookStatus>> {
+    let dir = hooks_dir()?;
+    Ok(MANAGED_HOOKS
+        .into_iter()
+        .map(|name| {
+            let path = dir.join(name);
+            HookStatus {
+                name,
+                state: hook_state(&path),
+                path,
+            }
+        })
+        .collect())
+}
+
+/// Writes the managed script of `hook` unless a script rona does not manage is there.
+///
+/// # Errors
+/// * If the script cannot be written or made executable
+///
+/// # Returns
+/// * The state before installing: the script is written unless it is
+///   [`HookState::Unmanaged`]
+pub fn install_hook(hook: &HookStatus, dry_run: bool) -> Result<HookState> {
+    if hook.state == HookState::Unmanaged || dry_run {
+        return Ok(hook.state);
+    }
+    if let Some(dir) = hook.path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(&hook.path, hook_script(hook.name))?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&hook.path, fs::Permissions::from_mode(0o755))?;
+    }
+    Ok(hook.state)
+}
+
+/// Removes the script of `hook` if rona manages it.
+///
+/// # Errors
+/// * If the script cannot be removed
+///
+/// # Returns
+/// * The state before uninstalling: the script is removed only if it was
+///   [`HookState::Managed`]
+pub fn uninstall_hook(hook: &HookStatus, dry_run: bool) -> Result<HookState> {
+    if hook.state == HookState::Managed && !dry_run {
+        fs::remove_file(&hook.path)?;
+    }
+    Ok(hook.state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hook_state() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("pre-commit");
+        assert_eq!(hook_state(&path), HookState::Missing);
+
+        fs::write(&path, hook_script("pre-commit"))?;
+        assert_eq!(hook_state(&path), HookState::Managed);
+
+        fs::write(&path, "#!/bin/sh\nnpx lint-staged\n")?;
+        assert_eq!(hook_state(&path), HookState::Unmanaged);
+        Ok(())
+    }
+}
@@ -0,0 +1,621 @@
+//! Repository Maintenance
+//!
+//! Housekeeping operations behind `rona prune`: pruning deleted remote branches,
+//! expiring reflogs, removing unreachable loose objects and cleaning up rona's own
+//! state files under `.git/rona/`. Everything goes through the git CLI, using the
+//! same expiry defaults as `git gc`.
+//!
+//! It also gathers the [`HealthReport`] behind `rona health`, which turns signs of
+//! an unmaintained repository into [`Finding`]s with the git command that fixes them.
+
+use std::{
+    collections::HashMap,
+    fs,
+    io::Write,
+    path::PathBuf,
+    process::{Command, Stdio},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use crate::errors::{GitError, Result, RonaError};
+
+use super::{
+    commit::{CountCaveat, count_caveats},
+    message_log::MESSAGE_ARCHIVE_FILE,
+    oplog::OPLOG_FILE,
+    repository::{Feature, ensure_allowed, ensure_writable, git_path, git_stdout, run_git},
+};
+
+/// Unreachable loose objects younger than this are kept, matching `git gc`'s default,
+/// so objects another process is still writing are never removed.
+const PRUNE_EXPIRE: &str = "2.weeks.ago";
+
+/// Loose object count above which `git gc --auto` would repack (git's `gc.auto` default).
+const LOOSE_OBJECT_LIMIT: u64 = 6700;
+
+/// Pack count above which `git gc --auto` would consolidate packs (`gc.autoPackLimit` default).
+const PACK_LIMIT: u64 = 50;
+
+/// Blobs at least this large anywhere in history are reported as oversized files.
+pub const LARGE_FILE_BYTES: u64 = 5 * 1024 * 1024;
+
+/// Maximum number of oversized files listed in a health report.
+const LARGE_FILE_LIMIT: usize = 5;
+
+/// Loose object statistics, as reported by `git count-objects -v`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ObjectCounts {
+    /// Number of loose objects
+    pub loose: u64,
+    /// Disk space used by loose objects, in KiB
+    pub loose_kib: u64,
+    /// Number of pack files
+    pub packs: u64,
+    /// Disk space used by pack files, in KiB
+    pub pack_kib: u64,
+}
+
+/// A file whose content is stored in history as a large blob.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LargeFile {
+    /// Path of the file in the commit that introduced the blob
+    pub path: String,
+    /// Size of the blob in bytes
+    pub size: u64,
+}
+
+/// Facts about the repository gathered by [`inspect_repository`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HealthReport {
+    /// Loose and packed object statistics
+    pub objects: ObjectCounts,
+    /// Whether a commit-graph file exists
+    pub has_commit_graph: bool,
+    /// The configured `gc.auto` value, if any
+    pub gc_auto: Option<String>,
+    /// The largest blobs in history, largest first
+    pub large_files: Vec<LargeFile>,
+    /// Refs that are unreadable or point to missing objects
+    pub broken_refs: Vec<String>,
+    /// What makes commit numbers differ from the stored history
+    pub count_caveats: Vec<CountCaveat>,
+}
+
+/// A problem found by a health check, with the git command that addresses it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Finding {
+    /// What is wrong
+    pub problem: String,
+    /// Arguments of the git command that fixes it, if it can be fixed automatically
+    pub fix: Option<Vec<&'static str>>,
+}
+
+/// Reads the loose object statistics of the repository.
+///
+/// # Errors
+/// * If `git count-objects` fails
+pub fn count_objects() -> Result<ObjectCounts> {
+    Ok(parse_count_objects(&git_stdout(&["count-objects", "-v"])?))
+}
+
+/// Inspects the repository for the signs of missing maintenance.
+///
+/// # Errors
+/// * If any of the underlying git commands fails
+pub fn inspect_repository() -> Result<HealthReport> {
+    let graph = git_path("objects/info/commit-graph")?;
+    let graph_chain = git_path("objects/info/commit-graphs/commit-graph-chain")?;
+    let (tips, broken_refs) = ref_tips()?;
+
+    Ok(HealthReport {
+        objects: count_objects()?,
+        has_commit_graph: graph.exists() || graph_chain.exists(),
+        gc_auto: config_value("gc.auto")?,
+        large_files: large_files(&tips, LARGE_FILE_BYTES)?,
+        broken_refs,
+        count_caveats: count_caveats()?,
+    })
+}
+
+/// Turns a health report into findings, each suggesting a fix where there is one.
+#[must_use]
+pub fn health_findings(report: &HealthReport) -> Vec<Finding> {
+    let mut findings = Vec::new();
+
+    if report.objects.loose > LOOSE_OBJECT_LIMIT {
+        findings.push(Finding {
+            problem: format!(
+                "{} loose objects (more than {LOOSE_OBJECT_LIMIT})",
+                report.objects.loose
+            ),
+            fix: Some(vec!["maintenance", "run", "--task=gc"]),
+        });
+    }
+    if report.objects.packs > PACK_LIMIT {
+        findings.push(Finding {
+            problem: format!(
+                "{} pack files (more than {PACK_LIMIT})",
+                report.objects.packs
+            ),
+            fix: Some(vec!["maintenance", "run", "--task=incremental-repack"]),
+        });
+    }
+    if !report.has_commit_graph {
+        findings.push(Finding {
+            problem: "No commit-graph: history walks (log, merge-base, ...) are slower".to_string(),
+            fix: Some(vec!["commit-graph", "write", "--reachable"]),
+        });
+    }
+    if report.gc_auto.as_deref() == Some("0") {
+        findings.push(Finding {
+            problem: "Automatic garbage collection is disabled (gc.auto = 0)".to_string(),
+            fix: Some(vec!["config", "--local", "--unset", "gc.auto"]),
+        });
+    }
+    for file in &report.large_files {
+        findings.push(Finding {
+            problem: format!(
+                "Large file in history: {} ({} MiB)",
+                file.path,
+                file.size / (1024 * 1024)
+            ),
+            fix: None,
+        });
+    }
+    for reference in &report.broken_refs {
+        findings.push(Finding {
+            problem: format!("Broken ref: {reference}"),
+            fix: None,
+        });
+    }
+    for caveat in &report.count_caveats {
+        let fix = match caveat {
+            CountCaveat::Shallow => Some(vec!["fetch", "--unshallow"]),
+            // The graft file is deprecated; replace refs keep the same history.
+            CountCaveat::Rewritten { grafts: true, .. } => {
+                Some(vec!["replace", "--convert-graft-file"])
+            }
+            CountCaveat::Rewritten { .. } => None,
+        };
+        findings.push(Finding {
+            problem: caveat.to_string(),
+            fix,
+        });
+    }
+
+    findings
+}
+
+/// Writes a commit-graph with changed-path Bloom filters and keeps it updated on fetch.
+///
+/// Git reads the commit-graph automatically, so history walks such as commit counting
+/// (`rev-list --count`) and path-limited `git log` get faster without other changes.
+///
+/// # Errors
+/// * If writing the commit-graph or setting the config fails
+pub fn optimize_revwalks() -> Result<()> {
+    ensure_writable("write the commit-graph")?;
+
+    git_stdout(&["commit-graph", "write", "--reachable", "--changed-paths"])?;
+    git_stdout(&["config", "--local", "fetch.writeCommitGraph", "true"])?;
+    Ok(())
+}
+
+/// Runs the git command suggested by a [`Finding`].
+///
+/// # Errors
+/// * If the git command fails
+pub fn run_fix(args: &[&str]) -> Result<()> {
+    ensure_writable("apply a fix")?;
+
+    git_stdout(args)?;
+    Ok(())
+}
+
+/// Removes remote-tracking refs whose branch no longer exists on the remote.
+///
+/// Runs `git fetch --all --prune`, or `git remote prune --dry-run` for each remote
+/// when `dry_run` is set.
+///
+/// # Errors
+/// * If a guard denies fetching
+/// * If listing remotes or pruning fails
+///
+/// # Returns
+/// The pruned (or prunable) remote-tracking refs, e.g. `origin/old-feature`
+pub fn prune_remote_tracking_refs(dry_run: bool) -> Result<Vec<String>> {
+    if !dry_run {
+        ensure_writable("prune remote-tracking refs")?;
+        ensure_allowed(Feature::Fetch)?;
+        // Compare the refs before and after rather than parsing fetch's progress output.
+        let before = remote_tracking_refs()?;
+        git_stdout(&["fetch", "--all", "--prune", "--quiet"])?;
+        let after = remote_tracking_refs()?;
+        return Ok(before.into_iter().filter(|r| !after.contains(r)).collect());
+    }
+
+    let remotes = git_stdout(&["remote"])?;
+    let mut refs = Vec::new();
+    for remote in remotes.lines() {
+        let output = git_stdout(&["remote", "prune", "--dry-run", remote.trim()])?;
+        refs.extend(parse_pruned_refs(&output));
+    }
+    Ok(refs)
+}
+
+/// Expires reflog entries using git's configured defaults (`gc.reflogExpire`, ...).
+///
+/// # Errors
+/// * If `git reflog expire` fails
+pub fn expire_reflogs(dry_run: bool) -> Result<()> {
+    if dry_run {
+        return Ok(());
+    }
+    ensure_writable("expire reflogs")?;
+    git_stdout(&["reflog", "expire", "--all"])?;
+    Ok(())
+}
+
+/// Removes unreachable loose objects older than two weeks.
+///
+/// # Errors
+/// * If `git prune` fails
+///
+/// # Returns
+/// The number of objects that would be removed when `dry_run` is set, otherwise `0`
+/// (use [`count_objects`] before and after to measure what was reclaimed).
+pub fn prune_unreachable_objects(dry_run: bool) -> Result<usize> {
+    let expire = format!("--expire={PRUNE_EXPIRE}");
+    if dry_run {
+        return Ok(git_stdout(&["prune", "--dry-run", &expire])?
+            .lines()
+            .count());
+    }
+    ensure_writable("prune objects")?;
+    git_stdout(&["prune", &expire])?;
+    Ok(0)
+}
+
+/// Deletes rona state files under `.git/rona/` that were not modified within `max_age`.
+/// The operation log and the message archive are kept.
+///
+/// # Errors
+/// * If the git directory cannot be located
+/// * If a stale file cannot be removed
+///
+/// # Returns
+/// The removed (or removable, when `dry_run` is set) files
+pub fn expire_state_files(max_age: Duration, dry_run: bool) -> Result<Vec<PathBuf>> {
+    if !dry_run {
+        ensure_writable("delete state files")?;
+    }
+    let dir = git_path("rona")?;
+    let kept = [git_path(OPLOG_FILE)?, git_path(MESSAGE_ARCHIVE_FILE)?];
+    let Ok(entries) = fs::read_dir(&dir) else {
+        return Ok(Vec::new());
+    };
+
+    let now = SystemTime::now();
+    let mut expired = Vec::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let Ok(metadata) = entry.metadata() else {
+            continue;
+        };
+        let age = metadata
+            .modified()
+            .ok()
+            .and_then(|modified| now.duration_since(modified).ok());
+        // The operation log and the message archive are records, not caches.
+        if kept.contains(&path) {
+            continue;
+        }
+        if metadata.is_file() && age.is_some_and(|age| age >= max_age) {
+            if !dry_run {
+                fs::remove_file(&path)?;
+            }
+            expired.push(path);
+        }
+    }
+    expired.sort();
+    Ok(expired)
+}
+
+/// Lists local branches whose upstream branch was deleted from the remote.
+///
+/// # Errors
+/// * If `git for-each-ref` fails
+pub fn branches_with_gone_upstream() -> Result<Vec<String>> {
+    Ok(git_stdout(&[
+        "for-each-ref",
+        "--format=%(refname:short)|%(upstream:track)",
+        "refs/heads",
+    ])?
+    .lines()
+    .filter_map(|line| line.split_once('|'))
+    .filter(|(_, track)| *track == "[gone]")
+    .map(|(branch, _)| branch.to_string())
+    .collect())
+}
+
+/// Reads a git config value, returning `None` when it is not set.
+fn config_value(key: &str) -> Result<Option<String>> {
+    let output = run_git(&["config", "--get", key])?;
+    // `git config --get` exits with 1 when the key is missing.
+    if output.status.code() == Some(1) {
+        return Ok(None);
+    }
+    if !output.status.success() {
+        return Err(RonaError::Git(GitError::CommandFailed {
+            command: format!("git config --get {key}"),
+            output: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        }));
+    }
+    Ok(Some(
+        String::from_utf8_lossy(&output.stdout).trim().to_string(),
+    ))
+}
+
+/// Finds the largest blobs reachable from `tips` that are at least `threshold` bytes.
+fn large_files(tips: &[String], threshold: u64) -> Result<Vec<LargeFile>> {
+    let input = tips
+        .iter()
+        .flat_map(|t| format!("{t}\n").into_bytes())
+        .collect();
+    let objects = run_git_with_input(&["rev-list", "--objects", "--stdin"], input)?;
+    let checked = run_git_with_input(
+        &[
+            "cat-file",
+            "--batch-check=%(objecttype) %(objectsize) %(rest)",
+        ],
+        objects.into_bytes(),
+    )?;
+    Ok(parse_large_files(&checked, threshold))
+}
+
+/// Splits the refs into the object ids they point to and the names of broken refs:
+/// refs git cannot read, and refs pointing to objects that do not exist.
+///
+/// History walks like `rev-list --all` abort on a broken ref, so only the valid tips
+/// are walked.
+fn ref_tips() -> Result<(Vec<String>, Vec<String>)> {
+    let args = ["for-each-ref", "--format=%(objectname) %(refname)"];
+    let output = run_git(&args)?;
+    if !output.status.success() {
+        return Err(RonaError::Git(GitError::CommandFailed {
+            command: format!("git {}", args.join(" ")),
+            output: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        }));
+    }
+
+    // Unreadable refs are skipped by for-each-ref with a warning rather than an error.
+    let mut broken: Vec<String> = String::from_utf8_lossy(&output.stderr)
+        .lines()
+        .filter_map(|line| {
+            line.strip_prefix("warning: ignoring broken ref ")
+                .or_else(|| line.strip_prefix("warning: ignoring dangling symref "))
+        })
+        .map(|r| r.trim().to_string())
+        .collect();
+
+    let refs = String::from_utf8_lossy(&output.stdout).into_owned();
+    let refs: Vec<(&str, &str)> = refs.lines().filter_map(|l| l.split_once(' ')).collect();
+
+    // `cat-file` answers `<sha> missing` for objects that do not exist.
+    let input = refs
+        .iter()
+        .flat_map(|(sha, _)| format!("{sha}\n").into_bytes())
+        .collect();
+    let checked = run_git_with_input(&["cat-file", "--batch-check=%(objectname)"], input)?;
+    let missing: Vec<&str> = checked
+        .lines()
+        .filter_map(|line| line.strip_suffix(" missing"))
+        .collect();
+
+    let mut tips = Vec::new();
+    for (sha, name) in refs {
+        if missing.contains(&sha) {
+            broken.push(name.to_string());
+        } else {
+            tips.push(sha.to_string());
+        }
+    }
+    tips.sort();
+    tips.dedup();
+    broken.sort();
+    broken.dedup();
+    Ok((tips, broken))
+}
+
+/// Runs a git command with `input` on its standard input.
+fn run_git_with_input(args: &[&str], input: Vec<u8>) -> Result<String> {
+    let mut child = Command::new("git")
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(RonaError::Io)?;
+
+    // Write from another thread so a large output cannot block the input.
+    let mut stdin = child
+        .stdin
+        .take()
+        .ok_or_else(|| RonaError::Io(std::io::Error::other("git stdin unavailable")))?;
+    let writer = thread::spawn(move || stdin.write_all(&input));
+    let output = child.wait_with_output().map_err(RonaError::Io)?;
+    writer
+        .join()
+        .map_err(|_| RonaError::Io(std::io::Error::other("git stdin writer panicked")))??;
+
+    if !output.status.success() {
+        return Err(RonaError::Git(GitError::CommandFailed {
+            command: format!("git {}", args.join(" ")),
+            output: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        }));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Lists remote-tracking refs by short name, e.g. `origin/main`.
+fn remote_tracking_refs() -> Result<Vec<String>> {
+    Ok(
+        git_stdout(&["for-each-ref", "--format=%(refname:short)", "refs/remotes"])?
+            .lines()
+            .map(str::to_string)
+            .collect(),
+    )
+}
+
+/// Extracts ref names from `git remote prune --dry-run` output (`* [would prune] origin/x`).
+fn parse_pruned_refs(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("* [would prune]"))
+        .map(|r| r.trim().to_string())
+        .filter(|r| !r.is_empty())
+        .collect()
+}
+
+/// Picks blobs of at least `threshold` bytes from `cat-file` output
+/// (`<type> <size> <path>`), one entry per path, largest first.
+fn parse_large_files(output: &str, threshold: u64) -> Vec<LargeFile> {
+    let mut sizes: HashMap<&str, u64> = HashMap::new();
+    for line in output.lines() {
+        let mut parts = line.splitn(3, ' ');
+        let (Some("blob"), Some(size), Some(path)) = (parts.next(), parts.next(), parts.next())
+        else {
+            continue;
+        };
+        let Ok(size) = size.parse::<u64>() else {
+            continue;
+        };
+        if size >= threshold && !path.is_empty() {
+            let largest = sizes.entry(path).or_default();
+            *largest = (*largest).max(size);
+        }
+    }
+
+    let mut files: Vec<LargeFile> = sizes
+        .into_iter()
+        .map(|(path, size)| LargeFile {
+            path: path.to_string(),
+            size,
+        })
+        .collect();
+    files.sort_by(|a, b| b.size.cmp(&a.size).then_with(|| a.path.cmp(&b.path)));
+    files.truncate(LARGE_FILE_LIMIT);
+    files
+}
+
+/// Parses the object and pack lines of `git count-objects -v`.
+fn parse_count_objects(output: &str) -> ObjectCounts {
+    let mut counts = ObjectCounts::default();
+    for line in output.lines() {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim().parse().unwrap_or(0);
+        match key.trim() {
+            "count" => counts.loose = value,
+            "size" => counts.loose_kib = value,
+            "packs" => counts.packs = value,
+            "size-pack" => counts.pack_kib = value,
+            _ => {}
+        }
+    }
+    counts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_pruned_refs() {
+        let dry_run = "Pruning origin\nURL: ../remote\n * [would prune] origin/old\n * [would prune] origin/feat/x\n";
+        assert_eq!(
+            parse_pruned_refs(dry_run),
+            vec!["origin/old", "origin/feat/x"]
+        );
+
+        assert!(parse_pruned_refs("").is_empty());
+    }
+
+    #[test]
+    fn test_parse_count_objects() {
+        let output = "count: 12\nsize: 48\nin-pack: 300\npacks: 1\nsize-pack: 512\n";
+        assert_eq!(
+            parse_count_objects(output),
+            ObjectCounts {
+                loose: 12,
+                loose_kib: 48,
+                packs: 1,
+                pack_kib: 512,
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_large_files() {
+        let output = "commit 250 \n\
+                      tree 90 \n\
+                      blob 100 README.md\n\
+                      blob 7000 assets/video.mp4\n\
+                      blob 9000 assets/video.mp4\n\
+                      blob 8000 dump.sql\n";
+        assert_eq!(
+            parse_large_files(output, 5000),
+            vec![
+                LargeFile {
+                    path: "assets/video.mp4".to_string(),
+                    size: 9000
+                },
+                LargeFile {
+                    path: "dump.sql".to_string(),
+                    size: 8000
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_health_findings() {
+        let healthy = HealthReport {
+            has_commit_graph: true,
+            ..HealthReport::default()
+        };
+        assert!(health_findings(&healthy).is_empty());
+
+        let neglected = HealthReport {
+            objects: ObjectCounts {
+                loose: 10_000,
+                ..ObjectCounts::default()
+            },
+            has_commit_graph: false,
+            gc_auto: Some("0".to_string()),
+            large_files: vec![LargeFile {
+                path: "dump.sql".to_string(),
+                size: 12 * 1024 * 1024,
+            }],
+            broken_refs: vec!["refs/heads/bad".to_string()],
+            count_caveats: vec![CountCaveat::Shallow],
+        };
+        let findings = health_findings(&neglected);
+        assert_eq!(findings.len(), 6);
+        assert_eq!(
+            findings[0].fix,
+            Some(vec!["maintenance", "run", "--task=gc"])
+        );
+        assert!(findings[1].problem.contains("commit-graph"));
+        assert!(findings[2].problem.contains("gc.auto"));
+        assert_eq!(
+            findings[3].problem,
+            "Large file in history: dump.sql (12 MiB)"
+        );
+        assert_eq!(findings[4].problem, "Broken ref: refs/heads/bad");
+        assert!(findings[4].fix.is_none());
+        assert_eq!(findings[5].fix, Some(vec!["fetch", "--unshallow"]));
+    }
+}
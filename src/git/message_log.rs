@@ -0,0 +1,160 @@
+//! Message Archive
+//!
+//! `rona generate` keeps a copy of the `commit_message.md` it writes. When `rona
+//! commit` uses a file that was edited since, the generated and committed versions are
+//! appended to `.git/rona/messages.jsonl`. Comparing the two shows what people actually
+//! change in a generated message, which helps refine the commit template. The archive
+//! stays in the local git directory.
+
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+
+use chrono::{Local, SecondsFormat};
+use serde::{Deserialize, Serialize};
+
+use crate::errors::{Result, RonaError};
+
+use super::{oplog::current_head, repository::git_path};
+
+/// The last generated message, relative to the git directory.
+pub const GENERATED_MESSAGE_FILE: &str = "rona/generated_message.md";
+
+/// The message archive, relative to the git directory.
+pub const MESSAGE_ARCHIVE_FILE: &str = "rona/messages.jsonl";
+
+/// A committed message that was edited after it was generated.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArchivedMessage {
+    /// Local time of the commit, in RFC 3339
+    pub time: String,
+    /// The commit made with the message
+    pub commit: Option<String>,
+    /// `commit_message.md` as `rona generate` wrote it
+    pub generated: String,
+    /// `commit_message.md` as it was committed
+    pub committed: String,
+}
+
+/// Keeps a copy of a generated `commit_message.md`, replacing the previous one.
+///
+/// # Errors
+/// * If the git directory cannot be located or the copy cannot be written
+pub fn save_generated_message(message: &str) -> Result<()> {
+    let path = git_path(GENERATED_MESSAGE_FILE)?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, message)?;
+    Ok(())
+}
+
+/// Removes the copy of the generated message and returns it, if there is one.
+///
+/// # Errors
+/// * If the git directory cannot be located
+/// * If the copy exists but cannot be read or removed
+pub fn take_generated_message() -> Result<Option<String>> {
+    let path = git_path(GENERATED_MESSAGE_FILE)?;
+    if !path.exists() {
+        return Ok(None);
+    }
+    let message = fs::read_to_string(&path)?;
+    fs::remove_file(path)?;
+    Ok(Some(message))
+}
+
+/// Appends a generated message and the version committed from it to the archive,
+/// with the commit `HEAD` now points to.
+///
+/// # Errors
+/// * If the git directory cannot be located
+/// * If the archive cannot be written
+pub fn archive_message(generated: &str, committed: &str) -> Result<()> {
+    let entry = ArchivedMessage {
+        time: Local::now().to_rfc3339_opts(SecondsFormat::Secs, false),
+        commit: current_head(),
+        generated: generated.to_string(),
+        committed: committed.to_string(),
+    };
+    let line =
+        serde_json::to_string(&entry).map_err(|e| RonaError::Io(std::io::Error::other(e)))?;
+
+    let path = git_path(MESSAGE_ARCHIVE_FILE)?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(&path)?;
+    writeln!(file, "{line}")?;
+    Ok(())
+}
+
+/// One line of a [`message_diff`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiffLine<'a> {
+    /// Only in the generated message
+    Removed(&'a str),
+    /// Only in the committed message
+    Added(&'a str),
+}
+
+/// The lines removed from and added to a message, in order. Unchanged lines are left
+/// out; trailing whitespace is ignored.
+#[must_use]
+pub fn message_diff<'a>(generated: &'a str, committed: &'a str) -> Vec<DiffLine<'a>> {
+    let old: Vec<&str> = generated.trim_end().lines().map(str::trim_end).collect();
+    let new: Vec<&str> = committed.trim_end().lines().map(str::trim_end).collect();
+
+    // Longest common subsequence lengths of the suffixes
+    let mut lcs = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut diff = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            i += 1;
+            j += 1;
+        } else if i < old.len() && (j == new.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            diff.push(DiffLine::Removed(old[i]));
+            i += 1;
+        } else {
+            diff.push(DiffLine::Added(new[j]));
+            j += 1;
+        }
+    }
+    diff
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_message_diff() {
+        let generated = "[3] (feat on main) \n\n\n- `src/app.rs`: \n";
+        let committed = "[3] (feat on main) Add login\n\n\n- `src/app.rs`: form and session\n";
+
+        assert_eq!(
+            message_diff(generated, committed),
+            vec![
+                DiffLine::Removed("[3] (feat on main)"),
+                DiffLine::Added("[3] (feat on main) Add login"),
+                DiffLine::Removed("- `src/app.rs`:"),
+                DiffLine::Added("- `src/app.rs`: form and session"),
+            ]
+        );
+        assert!(message_diff(generated, "[3] (feat on main)\n\n\n- `src/app.rs`:").is_empty());
+        assert_eq!(
+            message_diff("a\nb\nc", "a\nc\nd"),
+            vec![DiffLine::Removed("b"), DiffLine::Added("d")]
+        );
+    }
+}
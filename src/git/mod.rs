@@ -12,47 +12,115 @@
 //!
 //! ## Submodules
 //!
-//! - [`repository`] - Core repository operations (finding git root, top level path)
+//! - [`am`] - Applying mailbox patches with `git am`, subjects rewritten on request
+//! - [`backport`] - Cherry-picking onto release branches and opening pull requests
+//! - [`attributes`] - `.gitattributes` lookups (`-diff`, `rona-ignore`, `merge=binary`)
+//! - [`repository`] - Core repository operations (finding git root, top level path, git env overrides)
+//! - [`bundle`] - Branches carried between clones in `git bundle` files
 //! - [`branch`] - Branch operations (current branch, branch name formatting, switch, create)
-//! - [`commit`] - Commit operations (commit counting, committing, commit message generation)
+//! - [`change_id`] - Gerrit `Change-Id:` trailers computed like Gerrit's `commit-msg` hook
+//! - [`daemon`] - Unix socket daemon keeping the changed files warm for `rona -l`
+//! - [`commit`] - Commit operations (commit counting, committing, reverting, fixups, commit message generation)
 //! - [`status`] - Git status parsing and processing
 //! - [`staging`] - File staging operations with pattern exclusion
-//! - [`remote`] - Remote operations (git push)
+//! - [`remote`] - Remote operations (git push, background fetch)
 //! - [`files`] - File and exclusion handling utilities
+//! - [`handoff`] - Work in progress pushed to a ref and restored in another clone
+//! - [`hooks`] - Managed `pre-commit`, `commit-msg` and `pre-push` hook scripts
+//! - [`scope`] - Directory of a monorepo that `--scope` narrows status and staging to
+//! - [`shared_config`] - The team's canonical `.rona.toml` read from a branch and diffed with the local one
+//! - [`show`] - Commit inspection (header fields, trailers, notes, signature) and recent history
+//! - [`maintenance`] - Repository housekeeping (pruning refs, reflogs, objects and rona state)
+//! - [`message_log`] - Generated messages and the edits made to them before committing
+//! - [`oplog`] - Log of the state-changing operations rona performed
+//! - [`release`] - Version tags and the release commit of `rona release`
+//! - [`patch`] - Patch series export with `git format-patch` and cover letters
+//! - [`paths`] - File and pattern arguments resolved inside the repository
+//! - [`todo`] - TODO markers on the lines added by the staged changes
+//! - [`sparse`] - Cone of a sparse checkout, which status and staging stay inside
+//! - [`usage`] - Local usage report computed from the operation log
 
 use crate::errors::{GitError, Result, RonaError};
 use regex::Regex;
 use std::process::Output;
 
+pub mod am;
+pub mod attributes;
+pub mod backport;
 pub mod branch;
+pub mod bundle;
+pub mod change_id;
 pub mod commit;
+#[cfg(unix)]
+pub mod daemon;
 pub mod files;
+pub mod handoff;
+pub mod hooks;
+pub mod maintenance;
+pub mod message_log;
+pub mod oplog;
+pub mod patch;
+pub mod paths;
+pub mod release;
 pub mod remote;
 pub mod repository;
+pub mod scope;
+pub mod shared_config;
+pub mod show;
+pub mod sparse;
 pub mod staging;
 pub mod status;
+pub mod todo;
+pub mod usage;
 
 use colored::Colorize;
 
 // Re-export commonly used functions for convenience
 pub use branch::{
-    format_branch_name, get_all_branches, get_current_branch, git_branch_only, git_create_branch,
-    git_merge, git_pull, git_rebase, git_switch, sanitize_branch_name,
+    branch_description, branch_descriptions, branch_upstream, format_branch_name, get_all_branches,
+    get_current_branch, get_default_branch, git_branch_only, git_create_branch,
+    git_delete_remote_branch, git_merge, git_pull, git_push_tracking, git_rebase,
+    git_rename_branch, git_switch, infer_commit_type, is_valid_ref_name, sanitize_branch_name,
+    set_branch_description,
 };
 pub use commit::{
-    COMMIT_MESSAGE_FILE_PATH, COMMIT_TYPES, generate_commit_message, get_current_commit_nb,
-    git_commit,
+    COMMIT_MESSAGE_FILE_PATH, COMMIT_TYPES, Comments, CommitNumberSource, CommitPreview,
+    CountCaveat, FileBullet, NumberedCommit, Signing, StagedFileDiff, append_staged_diff,
+    commit_preview, count_caveats, generate_commit_message, get_commit_nb, get_current_commit_nb,
+    git_commit, git_commit_fixup, git_commit_with_message, git_revert, pending_commit_message,
+    render_commit_message, show_staged_diff, staged_file_diffs, staged_size, staged_summaries,
+    strip_message_comments,
+};
+pub use files::{add_to_commitignore, add_to_git_exclude, create_needed_files};
+pub use oplog::{Operation, current_head, read_operations, record_operation};
+pub use paths::{file_arguments, resolve_pattern};
+pub use remote::{
+    ForcePush, PUSHING_ENV, PushDivergence, PushPreview, PushSummary, UpstreamStatus,
+    background_fetch_if_due, force_push, git_push, is_expected_remote, push_divergence,
+    push_remote, push_summary, remote_location, remote_url, remote_urls, review_push_args,
+    rewrite_url, run_pre_push_commands, unsigned_outgoing_commits, upstream_ahead, upstream_status,
+    url_host,
+};
+pub use repository::{
+    Feature, READ_ONLY_ENV, denied_by, ensure_allowed, ensure_outside_commit_hook, ensure_writable,
+    find_git_root, get_top_level_path, git_command_in, git_path, is_inside_commit_hook,
+    is_read_only, set_denied_features, set_read_only,
+};
+pub use scope::{PathScope, in_scope, path_scope, set_path_scope};
+pub use show::{
+    CommitDetails, CommitMessage, CommitReference, CommitSummary, commit_details, commit_messages,
+    commit_reference, recent_commits,
 };
-pub use files::{add_to_git_exclude, create_needed_files};
-pub use remote::git_push;
-pub use repository::{find_git_root, get_top_level_path};
 pub use staging::{
-    git_add_files, git_add_with_exclude_patterns, git_restore_files, git_unstage_files,
+    StagingPreview, excluded_files_mask, git_add_files, git_add_with_exclude_patterns,
+    git_restore_files, git_unstage_files, staging_preview,
 };
 pub use status::{
-    StatusEntry, get_all_staged_file_paths, get_restorable_files, get_stageable_files,
+    FileState, StatusEntry, StatusReport, common_staged_dir, get_all_staged_file_paths,
+    get_file_states, get_out_of_cone_files, get_restorable_files, get_stageable_files,
     get_staged_files, get_status_files,
 };
+pub use todo::{TODO_SECTION_HEADING, staged_todos, todo_section};
 
 /// Handles the output of `Command`-based git operations (push, pull, merge, rebase).
 ///
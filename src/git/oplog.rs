@@ -0,0 +1,126 @@
+//! Operation Log
+//!
+//! Every state-changing rona command (add, generate, commit, push, branch, sync,
+//! reset, ...) appends one JSON line to `.git/rona/oplog.jsonl` with its time,
+//! arguments, branch and `HEAD` before and after. `rona history ops` lists the entries
+//! and `rona usage` summarizes them; the recorded `HEAD` values are what an undo needs
+//! to restore the previous state.
+
+use std::{
+    fs::{self, OpenOptions},
+    io::Write,
+    process::Command,
+};
+
+use chrono::{Local, SecondsFormat};
+use serde::{Deserialize, Serialize};
+
+use crate::errors::{Result, RonaError};
+
+use super::repository::git_path;
+
+/// The operation log, relative to the git directory.
+pub const OPLOG_FILE: &str = "rona/oplog.jsonl";
+
+/// One recorded rona operation.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Operation {
+    /// Local time the operation finished, in RFC 3339
+    pub time: String,
+    /// The rona command, e.g. `commit`
+    pub command: String,
+    /// The command-line arguments after the program name
+    pub args: Vec<String>,
+    /// The branch checked out after the operation, `None` when detached
+    pub branch: Option<String>,
+    /// `HEAD` before the operation, `None` in a repository without commits
+    pub head_before: Option<String>,
+    /// `HEAD` after the operation
+    pub head_after: Option<String>,
+}
+
+/// Returns the commit `HEAD` points to, or `None` when there is none yet.
+#[must_use]
+pub fn current_head() -> Option<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--verify", "--quiet", "HEAD"])
+        .output()
+        .ok()?;
+    let head = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (output.status.success() && !head.is_empty()).then_some(head)
+}
+
+/// Appends an operation to the log, reading the branch and `HEAD` after it.
+///
+/// # Errors
+/// * If the git directory cannot be located
+/// * If the log cannot be written
+pub fn record_operation(command: &str, args: &[String], head_before: Option<String>) -> Result<()> {
+    let branch = Command::new("git")
+        .args(["branch", "--show-current"])
+        .output()
+        .ok()
+        .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+        .filter(|b| !b.is_empty());
+    let operation = Operation {
+        time: Local::now().to_rfc3339_opts(SecondsFormat::Secs, false),
+        command: command.to_string(),
+        args: args.to_vec(),
+        branch,
+        head_before,
+        head_after: current_head(),
+    };
+    let line =
+        serde_json::to_string(&operation).map_err(|e| RonaError::Io(std::io::Error::other(e)))?;
+
+    let path = git_path(OPLOG_FILE)?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(&path)?;
+    writeln!(file, "{line}")?;
+    Ok(())
+}
+
+/// Reads the operation log, oldest first. Lines that do not parse are skipped.
+///
+/// # Errors
+/// * If the git directory cannot be located
+/// * If the log exists but cannot be read
+pub fn read_operations() -> Result<Vec<Operation>> {
+    let path = git_path(OPLOG_FILE)?;
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    Ok(parse_operations(&fs::read_to_string(path)?))
+}
+
+/// Parses the JSON lines of the log, skipping malformed ones.
+fn parse_operations(content: &str) -> Vec<Operation> {
+    content
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_operations_skips_malformed_lines() {
+        let content = concat!(
+            r#"{"time":"2026-01-02T03:04:05+01:00","command":"commit","args":["-c","-y"],"branch":"main","head_before":"aaa","head_after":"bbb"}"#,
+            "\n",
+            "{truncated\n",
+            r#"{"time":"2026-01-02T03:05:00+01:00","command":"push","args":["push"],"branch":null,"head_before":null,"head_after":null}"#,
+            "\n",
+        );
+        let operations = parse_operations(content);
+
+        assert_eq!(operations.len(), 2);
+        assert_eq!(operations[0].command, "commit");
+        assert_eq!(operations[0].head_after.as_deref(), Some("bbb"));
+        assert_eq!(operations[1].branch, None);
+    }
+}
@@ -0,0 +1,186 @@
+//! Patch Series
+//!
+//! Exports commits as mbox patch files with `git format-patch`, for mailing-list
+//! workflows. Subjects are written as committed, so the `[N] (type on branch)`
+//! header survives; `git am --keep-non-patch` keeps it when the series is applied.
+//!
+//! The optional cover letter is git's own `0000-cover-letter.patch`, with its
+//! `*** SUBJECT HERE ***` and `*** BLURB HERE ***` placeholders filled from the
+//! `[format_patch]` cover letter template.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::errors::Result;
+
+use super::{
+    repository::{ensure_writable, git_stdout},
+    show::CommitDetails,
+};
+
+/// The commit type heading used for commits whose subject does not match the template.
+const UNTYPED_HEADING: &str = "other";
+
+/// The commits a patch series is made of.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PatchSeries<'a> {
+    /// The last `n` commits on `HEAD`
+    Last(usize),
+    /// A revision range such as `main..`, or a base revision meaning `<base>..HEAD`
+    Range(&'a str),
+}
+
+impl PatchSeries<'_> {
+    /// The revision arguments for `git format-patch`.
+    fn format_patch_args(&self) -> Vec<String> {
+        match self {
+            Self::Last(count) => vec![format!("-{count}"), "HEAD".to_string()],
+            Self::Range(range) => vec![(*range).to_string()],
+        }
+    }
+
+    /// The arguments for `git rev-list` that select the same commits, oldest first.
+    fn rev_list_args(&self) -> Vec<String> {
+        match self {
+            Self::Last(count) => vec![format!("--max-count={count}"), "HEAD".to_string()],
+            // Like `git format-patch`, a single revision is the base of the series.
+            Self::Range(range) if range.contains("..") => vec![(*range).to_string()],
+            Self::Range(base) => vec![format!("{base}..HEAD")],
+        }
+    }
+}
+
+/// Lists the SHAs of the commits in a series, oldest first.
+///
+/// # Errors
+/// * If the range does not resolve
+pub fn series_commits(series: PatchSeries<'_>) -> Result<Vec<String>> {
+    let mut args = vec!["rev-list".to_string(), "--reverse".to_string()];
+    args.extend(series.rev_list_args());
+    let stdout = git_stdout(&args.iter().map(String::as_str).collect::<Vec<_>>())?;
+    Ok(stdout.lines().map(str::to_string).collect())
+}
+
+/// Writes the series as patch files into `output_dir`.
+///
+/// # Errors
+/// * If `git format-patch` fails
+///
+/// # Returns
+/// * The paths of the written files, the cover letter first when requested
+pub fn format_patches(
+    series: PatchSeries<'_>,
+    output_dir: &str,
+    cover_letter: bool,
+) -> Result<Vec<PathBuf>> {
+    ensure_writable("write patch files")?;
+
+    let mut args = vec![
+        "format-patch".to_string(),
+        "--output-directory".to_string(),
+        output_dir.to_string(),
+    ];
+    if cover_letter {
+        args.push("--cover-letter".to_string());
+    }
+    args.extend(series.format_patch_args());
+    let stdout = git_stdout(&args.iter().map(String::as_str).collect::<Vec<_>>())?;
+    Ok(stdout.lines().map(PathBuf::from).collect())
+}
+
+/// Replaces the placeholders of a cover letter written by `git format-patch`.
+///
+/// The first line of `text` becomes the subject, the rest the blurb.
+///
+/// # Errors
+/// * If the cover letter cannot be read or written
+pub fn fill_cover_letter(path: &Path, text: &str) -> Result<()> {
+    let (subject, blurb) = text.split_once('\n').unwrap_or((text, ""));
+    let letter = fs::read_to_string(path)?
+        .replacen("*** SUBJECT HERE ***", subject.trim(), 1)
+        .replacen("*** BLURB HERE ***", blurb.trim(), 1);
+    fs::write(path, letter)?;
+    Ok(())
+}
+
+/// Renders the series messages grouped by commit type, for the `{changes}` variable.
+///
+/// Groups keep the order in which their type first appears. Commits whose subject
+/// does not match the template are listed under `other` with their full subject.
+#[must_use]
+pub fn series_changes(commits: &[CommitDetails]) -> String {
+    changes_by_type(commits.iter().map(|commit| {
+        let field = |name: &str| {
+            commit
+                .header_fields
+                .as_ref()?
+                .iter()
+                .find(|field| field.name == name)
+                .map(|field| field.value.as_str())
+        };
+        (
+            field("commit_type"),
+            field("message").unwrap_or(&commit.subject),
+        )
+    }))
+}
+
+/// Groups `(type, message)` pairs under `type:` headings.
+fn changes_by_type<'a>(entries: impl IntoIterator<Item = (Option<&'a str>, &'a str)>) -> String {
+    let mut groups: Vec<(&str, Vec<&str>)> = Vec::new();
+    for (commit_type, message) in entries {
+        let heading = commit_type.unwrap_or(UNTYPED_HEADING);
+        match groups.iter_mut().find(|(name, _)| *name == heading) {
+            Some((_, messages)) => messages.push(message),
+            None => groups.push((heading, vec![message])),
+        }
+    }
+
+    groups
+        .iter()
+        .map(|(heading, messages)| {
+            let lines: Vec<String> = messages.iter().map(|m| format!("- {m}")).collect();
+            format!("{heading}:\n{}", lines.join("\n"))
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_series_args() {
+        assert_eq!(PatchSeries::Last(3).format_patch_args(), vec!["-3", "HEAD"]);
+        assert_eq!(
+            PatchSeries::Last(3).rev_list_args(),
+            vec!["--max-count=3", "HEAD"]
+        );
+        assert_eq!(PatchSeries::Range("main..").rev_list_args(), vec!["main.."]);
+        assert_eq!(
+            PatchSeries::Range("v1.0").rev_list_args(),
+            vec!["v1.0..HEAD"]
+        );
+    }
+
+    #[test]
+    fn test_changes_by_type() {
+        let changes = changes_by_type([
+            (Some("feat"), "add login form"),
+            (Some("fix"), "handle empty password"),
+            (None, "Merge branch 'main'"),
+            (Some("feat"), "remember the user"),
+        ]);
+
+        assert_eq!(
+            changes,
+            "feat:\n- add login form\n- remember the user\n\n\
+             fix:\n- handle empty password\n\n\
+             other:\n- Merge branch 'main'"
+        );
+        assert_eq!(changes_by_type([]), "");
+    }
+}
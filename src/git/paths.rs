@@ -0,0 +1,151 @@
+//! Path Arguments
+//!
+//! Files and patterns given on the command line, possibly by scripts passing file
+//! lists they did not write, are resolved against the repository before use: `.` and
+//! `..` are resolved without touching the file system, absolute paths are made
+//! relative to the root, and anything pointing outside the repository is refused.
+//!
+//! Files are always passed to git after `--`. A file starting with `-` also gets a
+//! `./` prefix, so git cannot read it as an option even where `--` is missing.
+
+use std::path::{Component, Path, PathBuf};
+
+use crate::errors::{Result, RonaError};
+
+use super::repository::get_top_level_path;
+
+/// Resolves `arg` to a path relative to `repo_root`, with `/` separators. A relative
+/// `arg` starts from `base`, a directory inside the repository.
+///
+/// Only the path is resolved, so glob patterns resolve like paths and the file does
+/// not need to exist.
+///
+/// # Errors
+/// * If `arg` is empty, or resolves to the repository root itself or outside of it
+pub fn resolve_in_repo(arg: &str, base: &Path, repo_root: &Path) -> Result<String> {
+    let path = Path::new(arg);
+    let joined = if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        base.join(path)
+    };
+
+    let outside = || {
+        RonaError::InvalidInput(format!(
+            "'{arg}' is outside the repository ({})",
+            repo_root.display()
+        ))
+    };
+    let mut resolved = PathBuf::new();
+    for component in joined.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if !resolved.pop() {
+                    return Err(outside());
+                }
+            }
+            other => resolved.push(other),
+        }
+    }
+
+    let relative: Vec<String> = resolved
+        .strip_prefix(repo_root)
+        .map_err(|_| outside())?
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy().into_owned())
+        .collect();
+    if relative.is_empty() {
+        return Err(RonaError::InvalidInput(format!(
+            "'{arg}' is the repository root, not a file in it"
+        )));
+    }
+    Ok(relative.join("/"))
+}
+
+/// Resolves files given relative to the current directory to paths relative to the
+/// repository root, for commands passing them to git from the root. Files starting
+/// with `-` are prefixed with `./`.
+///
+/// # Errors
+/// * If the repository root or the current directory cannot be read
+/// * If a file is outside the repository (see [`resolve_in_repo`])
+pub fn file_arguments(files: &[String]) -> Result<Vec<String>> {
+    let repo_root = get_top_level_path()?;
+    let base = current_base()?;
+    files
+        .iter()
+        .map(|file| {
+            let path = resolve_in_repo(file, &base, &repo_root)?;
+            Ok(if path.starts_with('-') {
+                format!("./{path}")
+            } else {
+                path
+            })
+        })
+        .collect()
+}
+
+/// Resolves an exclude pattern written as a path from the current directory.
+///
+/// Only absolute patterns and those with `.` or `..` components are resolved. Others
+/// are returned unchanged, since `rona -a` matches them from the root, the current
+/// directory and file names.
+///
+/// # Errors
+/// * If the repository root or the current directory cannot be read
+/// * If the pattern points outside the repository (see [`resolve_in_repo`])
+pub fn resolve_pattern(pattern: &str) -> Result<String> {
+    let path = Path::new(pattern);
+    let path_like = path.is_absolute()
+        || path
+            .components()
+            .any(|c| matches!(c, Component::CurDir | Component::ParentDir));
+    if !path_like {
+        return Ok(pattern.to_string());
+    }
+
+    let repo_root = get_top_level_path()?;
+    resolve_in_repo(pattern, &current_base()?, &repo_root)
+}
+
+/// The current directory, with symlinks resolved since git reports the root that way.
+fn current_base() -> Result<PathBuf> {
+    let current_dir = std::env::current_dir().map_err(RonaError::Io)?;
+    Ok(current_dir.canonicalize().unwrap_or(current_dir))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_in_repo() -> Result<()> {
+        let root = Path::new("/work/repo");
+        let src = root.join("src");
+        assert_eq!(resolve_in_repo("a.rs", &src, root)?, "src/a.rs");
+        assert_eq!(resolve_in_repo("./../README.md", &src, root)?, "README.md");
+        assert_eq!(resolve_in_repo("../*.log", &src, root)?, "*.log");
+        assert_eq!(
+            resolve_in_repo("/work/repo/docs/x.md", &src, root)?,
+            "docs/x.md"
+        );
+        assert_eq!(resolve_in_repo("-rf", root, root)?, "-rf");
+
+        for escape in [
+            "../../other/a.rs",
+            "/etc/passwd",
+            "/work/repository/a",
+            "../../../../..",
+        ] {
+            let result = resolve_in_repo(escape, &src, root);
+            assert!(
+                matches!(&result, Err(RonaError::InvalidInput(m)) if m.contains("outside the repository")),
+                "{escape}: {result:?}"
+            );
+        }
+        assert!(resolve_in_repo("..", &src, root).is_err());
+        assert!(resolve_in_repo("", root, root).is_err());
+        Ok(())
+    }
+}
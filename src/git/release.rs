@@ -0,0 +1,94 @@
+//! Release Plumbing
+//!
+//! Git side of `rona release`: listing and creating the annotated tags versions are
+//! marked with, the commits touching a package, and staging the changelog of the
+//! release commit.
+
+use std::path::{Path, PathBuf};
+
+use crate::errors::Result;
+
+use super::repository::{Feature, ensure_allowed, ensure_writable, git_stdout, run_git};
+
+/// The tags starting with `prefix` whose commit is reachable from `HEAD`.
+///
+/// # Errors
+/// * If git cannot be run
+pub fn merged_tags(prefix: &str) -> Result<Vec<String>> {
+    let output = run_git(&["tag", "--list", &format!("{prefix}*"), "--merged", "HEAD"])?;
+    if !output.status.success() {
+        // A repository without commits has no tags to list.
+        return Ok(Vec::new());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(str::trim)
+        .filter(|tag| !tag.is_empty())
+        .map(ToString::to_string)
+        .collect())
+}
+
+/// Subjects of the commits after `since` (all of `HEAD` when `None`) touching `path`,
+/// newest first.
+///
+/// # Errors
+/// * If `git log` fails
+pub fn commits_touching(since: Option<&str>, path: &str) -> Result<Vec<String>> {
+    let range = since.map_or_else(|| "HEAD".to_string(), |since| format!("{since}..HEAD"));
+    Ok(git_stdout(&["log", "--format=%s", &range, "--", path])?
+        .lines()
+        .map(ToString::to_string)
+        .collect())
+}
+
+/// Whether a tag of that name exists.
+///
+/// # Errors
+/// * If git cannot be run
+pub fn tag_exists(name: &str) -> Result<bool> {
+    Ok(run_git(&[
+        "show-ref",
+        "--verify",
+        "--quiet",
+        &format!("refs/tags/{name}"),
+    ])?
+    .status
+    .success())
+}
+
+/// Stages the changelog of a release and the removal of its fragments. Fragments
+/// that were never committed are skipped.
+///
+/// # Errors
+/// * If in read-only mode, or staging is denied by a guard
+/// * If `git add` or `git rm` fails
+pub fn stage_release_files(changelog: &Path, removed_fragments: &[PathBuf]) -> Result<()> {
+    ensure_writable("stage the changelog")?;
+    ensure_allowed(Feature::Stage)?;
+
+    let changelog = changelog.to_string_lossy();
+    git_stdout(&["add", "--", &changelog])?;
+    if !removed_fragments.is_empty() {
+        let paths: Vec<String> = removed_fragments
+            .iter()
+            .map(|path| path.to_string_lossy().into_owned())
+            .collect();
+        let mut args = vec!["rm", "--quiet", "--cached", "--ignore-unmatch", "--"];
+        args.extend(paths.iter().map(String::as_str));
+        git_stdout(&args)?;
+    }
+    Ok(())
+}
+
+/// Creates an annotated tag on `HEAD`.
+///
+/// # Errors
+/// * If in read-only mode, or committing is denied by a guard
+/// * If `git tag` fails
+pub fn create_tag(name: &str, message: &str) -> Result<()> {
+    ensure_writable("create a tag")?;
+    ensure_allowed(Feature::Commit)?;
+
+    git_stdout(&["tag", "--annotate", name, "--message", message])?;
+    Ok(())
+}
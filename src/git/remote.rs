@@ -1,15 +1,439 @@
 //! Git Remote Operations
 //!
-//! Remote repository operations including push functionality with dry-run support.
+//! Remote repository operations including push functionality with dry-run support,
+//! the `[pre_push]` commands run before each push, the summary of what a push
+//! publishes (for push notifications), how far the upstream is ahead before a commit,
+//! whether the branch a push updates has diverged (for `check_before_push`), and the
+//! rate-limited background fetch behind the `auto_fetch` setting.
 
+use std::collections::BTreeMap;
+use std::fs;
 use std::io::IsTerminal;
-use std::process::Command;
-use std::time::Duration;
+use std::process::{Command, Stdio};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use indicatif::ProgressBar;
 use indicatif::ProgressDrawTarget;
+use serde::Serialize;
 
-use crate::errors::{Result, RonaError};
+use crate::{
+    config::PrePushConfig,
+    errors::{GitError, Result, RonaError},
+    shell::run_shell,
+    theme::is_accessible,
+    tracker::TrackerCommand,
+    utils::format_duration,
+};
+
+use super::repository::{
+    Feature, denied_by, ensure_allowed, ensure_writable, get_top_level_path, git_path, git_stdout,
+    is_read_only,
+};
+
+/// File under the git directory recording when the last background fetch started.
+const LAST_FETCH_FILE: &str = "rona/last-fetch";
+
+/// Set on the `git push` that `rona push` runs, which has already run the `[pre_push]`
+/// commands, so the managed `pre-push` hook does not run them again.
+pub const PUSHING_ENV: &str = "RONA_PUSHING";
+
+/// What pushing the current branch publishes, as reported by push notifications.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PushSummary {
+    /// The remote pushed to
+    pub remote: String,
+    /// The remote's URL, when it has one
+    pub remote_url: Option<String>,
+    /// The current branch
+    pub branch: String,
+    /// The remote branch tip before the push, when the branch already exists there
+    pub old_tip: Option<String>,
+    /// The pushed commit (`HEAD`)
+    pub new_tip: String,
+    /// Subjects of the commits the remote does not have yet, newest first
+    pub subjects: Vec<String>,
+}
+
+/// What `rona push --dry-run` would do, for `--output json`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PushPreview {
+    /// What the push would publish
+    #[serde(flatten)]
+    pub summary: PushSummary,
+    /// The arguments passed on to `git push`
+    pub args: Vec<String>,
+    /// The `[pre_push]` commands that would run first, none with `--no-verify`
+    pub pre_push: Vec<String>,
+    /// The host of the webhook that would be notified
+    pub notify: Option<String>,
+    /// The `[tracker]` commands that would run after the push
+    pub tracker: Vec<TrackerCommand>,
+    /// Warnings the text output would print, without colors
+    pub warnings: Vec<String>,
+}
+
+/// Describes what pushing the current branch will publish. Call it before pushing.
+///
+/// The remote is the one [`push_remote`] resolves, and its URL has the `rewrites`
+/// applied (see [`remote_url`]). The commits listed are those reachable from `HEAD`
+/// but from no remote-tracking branch of that remote.
+///
+/// # Errors
+/// * If `HEAD` does not point to a commit
+pub fn push_summary(args: &[String], rewrites: &BTreeMap<String, String>) -> Result<PushSummary> {
+    let new_tip = git_value(&["rev-parse", "--verify", "--quiet", "HEAD"]).ok_or_else(|| {
+        RonaError::Git(GitError::InvalidStatus {
+            output: "HEAD does not point to a commit".to_string(),
+        })
+    })?;
+    let remote = push_remote(args);
+    let subjects = git_value(&[
+        "log",
+        "--format=%s",
+        "HEAD",
+        "--not",
+        &format!("--remotes={remote}"),
+    ])
+    .map(|log| log.lines().map(str::to_string).collect())
+    .unwrap_or_default();
+
+    Ok(PushSummary {
+        remote_url: remote_url(&remote, rewrites),
+        branch: git_value(&["branch", "--show-current"]).unwrap_or_else(|| "HEAD".to_string()),
+        old_tip: git_value(&["rev-parse", "--verify", "--quiet", "@{push}"]),
+        remote,
+        new_tip,
+        subjects,
+    })
+}
+
+/// The commits pushing with `args` to `remote` would publish without a good
+/// signature, as `short-sha subject (reason)`.
+///
+/// The commits are those of the refs being pushed (see [`pushed_revisions`]) that no
+/// remote-tracking branch of `remote` has. Each one is verified like `%G?` does, so a
+/// bad signature, or one git cannot check as good (unknown or untrusted key, expired
+/// or revoked key), counts as unsigned.
+///
+/// # Errors
+/// * If `git log` fails, e.g. on a refspec naming no commit
+pub fn unsigned_outgoing_commits(args: &[String], remote: &str) -> Result<Vec<String>> {
+    let revisions = pushed_revisions(args);
+    let remotes = format!("--remotes={remote}");
+    let mut log_args = vec!["log", "--format=%h%x1f%G?%x1f%s"];
+    log_args.extend(revisions.iter().map(String::as_str));
+    log_args.extend(["--not", &remotes, "--"]);
+
+    Ok(unsigned_commits(&git_stdout(&log_args)?))
+}
+
+/// The revisions a `git push` with `args` publishes: the source of each refspec after
+/// the repository argument, `--branches` for `--all`/`--branches`/`--mirror`, `--tags`
+/// for `--tags`, and `HEAD` when none is given. Deletions (`:branch`) publish nothing.
+fn pushed_revisions(args: &[String]) -> Vec<String> {
+    const VALUE_OPTIONS: &[&str] = &["-o", "--push-option", "--repo", "--receive-pack", "--exec"];
+
+    let mut revisions = Vec::new();
+    let mut positional = Vec::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--all" | "--branches" | "--mirror" => revisions.push("--branches".to_string()),
+            "--tags" => revisions.push("--tags".to_string()),
+            option if VALUE_OPTIONS.contains(&option) => {
+                args.next();
+            }
+            option if option.starts_with('-') => {}
+            refspec => positional.push(refspec),
+        }
+    }
+
+    let mut refspecs = positional.into_iter().skip(1);
+    while let Some(refspec) = refspecs.next() {
+        if refspec == "tag" {
+            if let Some(tag) = refspecs.next() {
+                revisions.push(format!("refs/tags/{tag}"));
+            }
+            continue;
+        }
+        let refspec = refspec.strip_prefix('+').unwrap_or(refspec);
+        let source = refspec
+            .split_once(':')
+            .map_or(refspec, |(source, _)| source);
+        if source.contains('*') {
+            revisions.push(format!("--glob={source}"));
+        } else if !source.is_empty() {
+            revisions.push(source.to_string());
+        }
+    }
+
+    if revisions.is_empty() {
+        revisions.push("HEAD".to_string());
+    }
+    revisions
+}
+
+/// The commits of `git log --format=%h%x1f%G?%x1f%s` output whose signature is not
+/// good, with the reason.
+fn unsigned_commits(log: &str) -> Vec<String> {
+    log.lines()
+        .filter_map(|line| {
+            let mut fields = line.splitn(3, '\x1f');
+            let (sha, status, subject) = (fields.next()?, fields.next()?, fields.next()?);
+            let reason = match status {
+                "G" => return None,
+                "N" => "no signature git can verify",
+                "B" => "bad signature",
+                "U" => "signed by a key of unknown validity",
+                "X" => "expired signature",
+                "Y" => "signed by an expired key",
+                "R" => "signed by a revoked key",
+                _ => "signature cannot be checked",
+            };
+            Some(format!("{sha} {subject} ({reason})"))
+        })
+        .collect()
+}
+
+/// The remote a push goes to: the one `@{push}` resolves to, else the first
+/// non-option push argument, else `origin`.
+#[must_use]
+pub fn push_remote(args: &[String]) -> String {
+    git_value(&[
+        "rev-parse",
+        "--abbrev-ref",
+        "--symbolic-full-name",
+        "@{push}",
+    ])
+    .as_deref()
+    .and_then(|r| r.split_once('/'))
+    .map(|(remote, _)| remote.to_string())
+    .or_else(|| args.iter().find(|arg| !arg.starts_with('-')).cloned())
+    .unwrap_or_else(|| "origin".to_string())
+}
+
+/// The URL of a remote with the `[url_rewrites]` applied, `None` when there is no
+/// such remote.
+#[must_use]
+pub fn remote_url(remote: &str, rewrites: &BTreeMap<String, String>) -> Option<String> {
+    git_value(&["remote", "get-url", remote]).map(|url| rewrite_url(&url, rewrites))
+}
+
+/// The URLs of every remote of the repository.
+#[must_use]
+pub fn remote_urls() -> Vec<String> {
+    git_value(&["remote"])
+        .unwrap_or_default()
+        .lines()
+        .filter_map(|remote| git_value(&["remote", "get-url", remote]))
+        .collect()
+}
+
+/// Replaces the longest prefix of `url` found in `rewrites` with its value, like git's
+/// `url.<base>.insteadOf`. URLs without a matching prefix are returned unchanged.
+#[must_use]
+pub fn rewrite_url(url: &str, rewrites: &BTreeMap<String, String>) -> String {
+    rewrites
+        .iter()
+        .filter(|(prefix, _)| url.starts_with(prefix.as_str()))
+        .max_by_key(|(prefix, _)| prefix.len())
+        .map_or_else(
+            || url.to_string(),
+            |(prefix, replacement)| format!("{replacement}{}", &url[prefix.len()..]),
+        )
+}
+
+/// The `host/path` a remote URL points to, without scheme, credentials, port or
+/// `.git` suffix, e.g. `github.com/rona-rs/rona` for `git@github.com:rona-rs/rona.git`.
+/// `None` for local paths.
+#[must_use]
+pub fn remote_location(remote_url: &str) -> Option<String> {
+    let remote_url = remote_url.trim().trim_end_matches('/');
+    let (host, path) = if let Some(rest) = remote_url
+        .strip_prefix("https://")
+        .or_else(|| remote_url.strip_prefix("http://"))
+        .or_else(|| remote_url.strip_prefix("ssh://"))
+    {
+        let (authority, path) = rest.split_once('/')?;
+        let host = authority.rsplit('@').next()?;
+        // An SSH port is not part of the location.
+        let host = if remote_url.starts_with("ssh://") {
+            host.split(':').next()?
+        } else {
+            host
+        };
+        (host, path)
+    } else {
+        // scp-like syntax: `git@github.com:owner/repo.git`
+        let (authority, path) = remote_url.split_once(':')?;
+        if authority.contains('/') {
+            return None;
+        }
+        (authority.rsplit('@').next()?, path)
+    };
+
+    let path = path.trim_end_matches(".git");
+    (!host.is_empty() && !path.is_empty()).then(|| format!("{host}/{path}"))
+}
+
+/// The host part of a URL, without scheme, credentials, port or path.
+#[must_use]
+pub fn url_host(url: &str) -> &str {
+    let rest = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let authority = rest.split('/').next().unwrap_or_default();
+    let host = authority.rsplit('@').next().unwrap_or_default();
+    host.split(':').next().unwrap_or_default()
+}
+
+/// Whether a remote location (see [`remote_location`]) is covered by one of the
+/// `expected` hosts or `host/owner` prefixes. Hosts compare case-insensitively.
+#[must_use]
+pub fn is_expected_remote(location: &str, expected: &[String]) -> bool {
+    let location = location.to_ascii_lowercase();
+    expected.iter().any(|entry| {
+        let entry = entry.trim().trim_end_matches('/').to_ascii_lowercase();
+        location == entry || location.starts_with(&format!("{entry}/"))
+    })
+}
+
+/// How far the upstream of the current branch is ahead of `HEAD`.
+///
+/// Reads the remote-tracking branch as of the last fetch (e.g. the `auto_fetch`
+/// background fetch); the remote itself is never contacted.
+///
+/// # Returns
+/// * The upstream, e.g. `origin/main`, and the number of its commits `HEAD` does not
+///   have; `None` when the branch has no upstream or is not behind it
+#[must_use]
+#[allow(clippy::literal_string_with_formatting_args)]
+pub fn upstream_ahead() -> Option<(String, usize)> {
+    let upstream = git_value(&[
+        "rev-parse",
+        "--abbrev-ref",
+        "--symbolic-full-name",
+        "@{upstream}",
+    ])?;
+    let ahead: usize = git_value(&["rev-list", "--count", "HEAD..@{upstream}"])?
+        .parse()
+        .ok()?;
+    (ahead > 0).then_some((upstream, ahead))
+}
+
+/// How the current branch compares to its upstream.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UpstreamStatus {
+    /// The upstream, e.g. `origin/main`
+    pub upstream: String,
+    /// Commits on `HEAD` the upstream does not have
+    pub ahead: usize,
+    /// Commits on the upstream `HEAD` does not have
+    pub behind: usize,
+}
+
+/// How far the current branch and its upstream have diverged, as of the last fetch.
+/// `None` when the branch has no upstream.
+#[must_use]
+#[allow(clippy::literal_string_with_formatting_args)]
+pub fn upstream_status() -> Option<UpstreamStatus> {
+    let upstream = git_value(&[
+        "rev-parse",
+        "--abbrev-ref",
+        "--symbolic-full-name",
+        "@{upstream}",
+    ])?;
+    let counts = git_value(&["rev-list", "--left-right", "--count", "HEAD...@{upstream}"])?;
+    let (ahead, behind) = counts.split_once('\t')?;
+    Some(UpstreamStatus {
+        upstream,
+        ahead: ahead.trim().parse().ok()?,
+        behind: behind.trim().parse().ok()?,
+    })
+}
+
+/// How a push may overwrite the remote branch, from its arguments.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ForcePush {
+    /// A fast-forward push
+    No,
+    /// `--force-with-lease`, which fails if the remote branch moved since the last fetch
+    Lease,
+    /// `--force`, `-f` or a `+` refspec
+    Force,
+}
+
+/// How the arguments of `git push` may overwrite the remote branch.
+/// `--force-with-lease` wins over `--force`, like in git.
+#[must_use]
+pub fn force_push(args: &[String]) -> ForcePush {
+    if args.iter().any(|arg| arg.starts_with("--force-with-lease")) {
+        return ForcePush::Lease;
+    }
+    let forced = args.iter().any(|arg| {
+        arg == "--force"
+            || arg.starts_with('+')
+            || (arg.starts_with('-') && !arg.starts_with("--") && arg.contains('f'))
+    });
+    if forced {
+        ForcePush::Force
+    } else {
+        ForcePush::No
+    }
+}
+
+/// How the branch a push updates compares to `HEAD`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PushDivergence {
+    /// The remote-tracking branch of the pushed branch, e.g. `origin/main`
+    pub tracking: String,
+    /// Commits on `HEAD` the remote branch does not have
+    pub ahead: usize,
+    /// Commits on the remote branch `HEAD` does not have, as `<short sha> <subject>`
+    pub missing: Vec<String>,
+    /// Whether the remote branch was fetched first; when not, it is compared as of
+    /// the last fetch
+    pub fetched: bool,
+}
+
+/// Compares `HEAD` to the branch of the same name on `remote`.
+///
+/// The branch is fetched first when `fetch` is set, unless in read-only mode or a
+/// guard denies fetching. `None` when `HEAD` is detached or the remote has no such
+/// branch.
+#[must_use]
+pub fn push_divergence(remote: &str, fetch: bool) -> Option<PushDivergence> {
+    let branch = git_value(&["symbolic-ref", "--quiet", "--short", "HEAD"])?;
+    let fetched = fetch
+        && !is_read_only()
+        && denied_by(Feature::Fetch).is_none()
+        && Command::new("git")
+            .args(["fetch", "--quiet", "--no-tags", remote, &branch])
+            .env("GIT_TERMINAL_PROMPT", "0")
+            .stdin(Stdio::null())
+            .output()
+            .is_ok_and(|output| output.status.success());
+
+    let tracking = format!("{remote}/{branch}");
+    let reference = format!("refs/remotes/{tracking}");
+    git_value(&["rev-parse", "--verify", "--quiet", &reference])?;
+    let ahead = git_value(&["rev-list", "--count", &format!("{reference}..HEAD")])?
+        .parse()
+        .ok()?;
+    let missing = git_value(&["log", "--format=%h %s", &format!("HEAD..{reference}")])
+        .map(|log| log.lines().map(ToString::to_string).collect())
+        .unwrap_or_default();
+    Some(PushDivergence {
+        tracking,
+        ahead,
+        missing,
+        fetched,
+    })
+}
+
+/// The trimmed stdout of a git command, or `None` when it fails or prints nothing.
+fn git_value(args: &[&str]) -> Option<String> {
+    let stdout = git_stdout(args).ok()?.trim().to_string();
+    (!stdout.is_empty()).then_some(stdout)
+}
 
 /// Pushes committed changes to the remote repository.
 ///
@@ -19,12 +443,18 @@ use crate::errors::{Result, RonaError};
 /// Note: Uses the git command to properly handle authentication (SSH keys, credentials, etc.)
 /// rather than git2's push API which requires complex callback setup.
 ///
+/// The `pre_push` commands run first, from the repository root; a failing command
+/// aborts the push. Passing `--no-verify` skips them, as it skips git's own
+/// `pre-push` hook.
+///
 /// # Arguments
 /// * `args` - Additional arguments to pass to the git push command (e.g., `--force`, `origin main`)
+/// * `pre_push` - Shell commands to run before pushing, and their limits
 /// * `verbose` - Whether to print verbose output during the operation
 /// * `dry_run` - If true, only show what would be pushed without actually pushing
 ///
 /// # Errors
+/// * If a pre-push command fails
 /// * If the git push command fails
 /// * If not in a git repository
 /// * If no remote repository is configured
@@ -36,25 +466,49 @@ use crate::errors::{Result, RonaError};
 /// # Examples
 ///
 /// ```no_run
-/// use rona::git::remote::git_push;
+/// use rona::{config::PrePushConfig, git::remote::git_push};
+///
+/// let none = PrePushConfig::default();
 ///
 /// // Basic push
-/// git_push(&vec![], false, false)?;
+/// git_push(&vec![], &none, false, false)?;
 ///
 /// // Push with force
-/// git_push(&vec!["--force".to_string()], true, false)?;
+/// git_push(&vec!["--force".to_string()], &none, true, false)?;
 ///
 /// // Push to specific remote and branch
-/// git_push(&vec!["origin".to_string(), "main".to_string()], false, false)?;
+/// git_push(&vec!["origin".to_string(), "main".to_string()], &none, false, false)?;
+///
+/// // Run the tests first, for at most ten minutes
+/// let tests = PrePushConfig {
+///     commands: vec!["cargo test".to_string()],
+///     timeout: Some("10m".to_string()),
+///     ..PrePushConfig::default()
+/// };
+/// git_push(&vec![], &tests, false, false)?;
 ///
 /// // Dry run to preview the push
-/// git_push(&vec![], false, true)?;
+/// git_push(&vec![], &none, false, true)?;
 /// # Ok::<(), Box<dyn std::error::Error>>(())
 /// ```
-#[tracing::instrument(skip(args))]
-pub fn git_push(args: &[String], verbose: bool, dry_run: bool) -> Result<()> {
+#[tracing::instrument(skip(args, pre_push))]
+pub fn git_push(
+    args: &[String],
+    pre_push: &PrePushConfig,
+    verbose: bool,
+    dry_run: bool,
+) -> Result<()> {
     tracing::debug!(args = ?args, dry_run, "Running git push");
 
+    if !dry_run {
+        ensure_writable("push")?;
+        ensure_allowed(Feature::Push)?;
+    }
+
+    if !args.iter().any(|arg| arg == "--no-verify") {
+        run_pre_push_commands(pre_push, dry_run)?;
+    }
+
     if dry_run {
         println!("Would push to remote repository");
         if !args.is_empty() {
@@ -63,7 +517,7 @@ pub fn git_push(args: &[String], verbose: bool, dry_run: bool) -> Result<()> {
         return Ok(());
     }
 
-    let show_spinner = !verbose && std::io::stderr().is_terminal();
+    let show_spinner = !verbose && std::io::stderr().is_terminal() && !is_accessible();
     let args_vec: Vec<String> = args.to_vec();
 
     let output = if show_spinner {
@@ -72,20 +526,181 @@ pub fn git_push(args: &[String], verbose: bool, dry_run: bool) -> Result<()> {
         pb.set_message("Pushing...");
         pb.enable_steady_tick(Duration::from_millis(80));
 
-        let handle =
-            std::thread::spawn(move || Command::new("git").arg("push").args(&args_vec).output());
+        let handle = std::thread::spawn(move || {
+            Command::new("git")
+                .arg("push")
+                .args(&args_vec)
+                .env(PUSHING_ENV, "1")
+                .output()
+        });
         let result = handle.join().map_err(|_| RonaError::CommandFailed {
             command: "git push".to_string(),
         })?;
         pb.finish_and_clear();
         result?
     } else {
-        Command::new("git").arg("push").args(args).output()?
+        Command::new("git")
+            .arg("push")
+            .args(args)
+            .env(PUSHING_ENV, "1")
+            .output()?
     };
 
     handle_output("push", &output)
 }
 
+/// `git push` options whose value is the next argument.
+const PUSH_OPTIONS_WITH_VALUE: [&str; 5] =
+    ["-o", "--push-option", "--repo", "--receive-pack", "--exec"];
+
+/// Push arguments that publish `HEAD` to `review_ref` on a remote: the options of
+/// `args`, then the remote (the first non-option argument, else `default_remote`),
+/// then `HEAD:<review_ref>`.
+///
+/// # Errors
+/// * If `args` name a refspec besides the remote
+pub fn review_push_args(
+    args: &[String],
+    default_remote: &str,
+    review_ref: &str,
+) -> Result<Vec<String>> {
+    let mut options = Vec::new();
+    let mut positional = Vec::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        if !arg.starts_with('-') {
+            positional.push(arg.clone());
+            continue;
+        }
+        options.push(arg.clone());
+        if PUSH_OPTIONS_WITH_VALUE.contains(&arg.as_str())
+            && let Some(value) = args.next()
+        {
+            options.push(value.clone());
+        }
+    }
+    if positional.len() > 1 {
+        return Err(RonaError::InvalidInput(format!(
+            "--review pushes HEAD to {review_ref}; name only the remote, not a refspec"
+        )));
+    }
+
+    let remote = positional
+        .pop()
+        .unwrap_or_else(|| default_remote.to_string());
+    options.push(remote);
+    options.push(format!("HEAD:{review_ref}"));
+    Ok(options)
+}
+
+/// Runs the pre-push commands through `sh -c`, in order, from the repository root.
+///
+/// They run under the `[pre_push]` limits (see [`crate::shell`]). Their output is
+/// streamed, or with `output_lines` only shown when a command fails.
+///
+/// # Errors
+/// * If `timeout` is invalid
+/// * If a command cannot be started, exits with a non-zero status or times out
+pub fn run_pre_push_commands(pre_push: &PrePushConfig, dry_run: bool) -> Result<()> {
+    let commands = &pre_push.commands;
+    if commands.is_empty() {
+        return Ok(());
+    }
+    let limits = pre_push.limits()?;
+    if dry_run {
+        for command in commands {
+            println!("Would run pre-push command: {command}");
+        }
+        return Ok(());
+    }
+
+    let root = get_top_level_path()?;
+    for command in commands {
+        println!("Running pre-push command: {command}");
+        let outcome = run_shell(command, &root, &limits, &[])?;
+        if outcome.success() {
+            continue;
+        }
+        for line in &outcome.tail {
+            println!("  {line}");
+        }
+        println!("Push aborted. Use --no-verify to push anyway.");
+        let reason = match (outcome.status, limits.timeout) {
+            (Some(status), _) => status.to_string(),
+            (None, Some(timeout)) => format!("timed out after {}", format_duration(timeout)),
+            (None, None) => "killed".to_string(),
+        };
+        return Err(RonaError::CommandFailed {
+            command: format!("{command} ({reason})"),
+        });
+    }
+    Ok(())
+}
+
+/// Starts `git fetch --quiet` in the background if the last one is older than `interval`.
+///
+/// The start time is recorded in `.git/rona/last-fetch` before spawning, so concurrent
+/// invocations do not pile up fetches. The fetch is not waited for and its output is
+/// discarded; credential prompts are disabled so it can never block on input.
+/// Repositories without remotes, and read-only mode, are skipped.
+///
+/// # Arguments
+/// * `interval` - Minimum time between two background fetches
+///
+/// # Errors
+/// * If the git directory cannot be located
+/// * If the timestamp file cannot be written
+///
+/// # Returns
+/// * `true` if a fetch was started
+pub fn background_fetch_if_due(interval: Duration) -> Result<bool> {
+    if is_read_only() || denied_by(Feature::Fetch).is_some() {
+        return Ok(false);
+    }
+
+    let stamp = git_path(LAST_FETCH_FILE)?;
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+    let last = fs::read_to_string(&stamp)
+        .ok()
+        .and_then(|s| s.trim().parse::<u64>().ok());
+
+    if !is_fetch_due(last, now, interval) {
+        return Ok(false);
+    }
+
+    let has_remote = Command::new("git")
+        .arg("remote")
+        .output()
+        .is_ok_and(|o| o.status.success() && !o.stdout.is_empty());
+    if !has_remote {
+        return Ok(false);
+    }
+
+    if let Some(dir) = stamp.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(&stamp, now.to_string())?;
+
+    tracing::debug!("Starting background fetch");
+    // Deliberately not waited for: the fetch outlives this process.
+    let spawned = Command::new("git")
+        .args(["fetch", "--quiet"])
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn();
+
+    Ok(spawned.is_ok())
+}
+
+/// Whether a fetch is due, given the last fetch time and now (both in Unix seconds).
+fn is_fetch_due(last: Option<u64>, now: u64, interval: Duration) -> bool {
+    last.is_none_or(|last| now.saturating_sub(last) >= interval.as_secs())
+}
+
 /// Handles the output of git commands, providing consistent error handling and success messaging.
 ///
 /// This function processes the output of git commands and:
@@ -102,3 +717,152 @@ pub fn git_push(args: &[String], verbose: bool, dry_run: bool) -> Result<()> {
 /// * `Result<()>` - `Ok(())` if the command succeeded, `Err(RonaError)` if it failed
 // Use the shared handle_output function from the parent module
 use super::handle_output;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_fetch_due() {
+        let interval = Duration::from_mins(15);
+
+        assert!(is_fetch_due(None, 1_000, interval));
+        assert!(!is_fetch_due(Some(1_000), 1_000 + 60, interval));
+        assert!(is_fetch_due(Some(1_000), 1_000 + 15 * 60, interval));
+        // A clock that went backwards must not fetch on every run
+        assert!(!is_fetch_due(Some(5_000), 1_000, interval));
+    }
+
+    #[test]
+    fn test_force_push() {
+        let args = |args: &[&str]| args.iter().map(ToString::to_string).collect::<Vec<_>>();
+        assert_eq!(force_push(&args(&["origin", "main"])), ForcePush::No);
+        assert_eq!(force_push(&args(&["-u", "origin"])), ForcePush::No);
+        assert_eq!(force_push(&args(&["--force"])), ForcePush::Force);
+        assert_eq!(force_push(&args(&["-fu", "origin"])), ForcePush::Force);
+        assert_eq!(force_push(&args(&["origin", "+main"])), ForcePush::Force);
+        assert_eq!(
+            force_push(&args(&["--force-with-lease=main", "-f"])),
+            ForcePush::Lease
+        );
+    }
+
+    #[test]
+    fn test_unsigned_commits() {
+        let log = "9cf14c9\x1fN\x1fadd logout\n\
+                   0756e28\x1fG\x1fadd login\n\
+                   1b2c3d4\x1fB\x1ftamper\n\
+                   5e6f7a8\x1fE\x1fsigned elsewhere\n";
+        assert_eq!(
+            unsigned_commits(log),
+            [
+                "9cf14c9 add logout (no signature git can verify)",
+                "1b2c3d4 tamper (bad signature)",
+                "5e6f7a8 signed elsewhere (signature cannot be checked)",
+            ]
+        );
+        assert!(unsigned_commits("").is_empty());
+    }
+
+    #[test]
+    fn test_pushed_revisions() {
+        let revisions = |values: &[&str]| {
+            pushed_revisions(&values.iter().map(ToString::to_string).collect::<Vec<_>>())
+        };
+
+        assert_eq!(revisions(&[]), ["HEAD"]);
+        assert_eq!(revisions(&["origin"]), ["HEAD"]);
+        assert_eq!(revisions(&["-u", "origin", "main"]), ["main"]);
+        assert_eq!(
+            revisions(&["origin", "+feat:refs/heads/feat", ":old", "tag", "v1.0"]),
+            ["feat", "refs/tags/v1.0"]
+        );
+        assert_eq!(
+            revisions(&["-o", "ci.skip", "origin", "--all"]),
+            ["--branches"]
+        );
+        assert_eq!(
+            revisions(&["origin", "refs/heads/*:refs/heads/*"]),
+            ["--glob=refs/heads/*"]
+        );
+    }
+
+    #[test]
+    fn test_review_push_args() -> Result<()> {
+        let args = |values: &[&str]| values.iter().map(ToString::to_string).collect::<Vec<_>>();
+
+        assert_eq!(
+            review_push_args(&[], "origin", "refs/for/main")?,
+            args(&["origin", "HEAD:refs/for/main"])
+        );
+        assert_eq!(
+            review_push_args(
+                &args(&["-o", "topic=login", "gerrit"]),
+                "origin",
+                "refs/for/main"
+            )?,
+            args(&["-o", "topic=login", "gerrit", "HEAD:refs/for/main"])
+        );
+        assert!(review_push_args(&args(&["origin", "main"]), "origin", "refs/for/main").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_rewrite_url() {
+        let rewrites = BTreeMap::from([
+            (
+                "https://github.com/".to_string(),
+                "git@github.com:".to_string(),
+            ),
+            (
+                "https://github.com/acme/".to_string(),
+                "git@github-work:acme/".to_string(),
+            ),
+        ]);
+
+        assert_eq!(
+            rewrite_url("https://github.com/acme/app.git", &rewrites),
+            "git@github-work:acme/app.git"
+        );
+        assert_eq!(
+            rewrite_url("https://github.com/me/dotfiles", &rewrites),
+            "git@github.com:me/dotfiles"
+        );
+        assert_eq!(
+            rewrite_url("git@gitlab.com:me/x.git", &rewrites),
+            "git@gitlab.com:me/x.git"
+        );
+    }
+
+    #[test]
+    fn test_remote_location() {
+        assert_eq!(
+            remote_location("git@github.com:rona-rs/rona.git").as_deref(),
+            Some("github.com/rona-rs/rona")
+        );
+        assert_eq!(
+            remote_location("ssh://git@gitlab.example.com:2222/team/app.git").as_deref(),
+            Some("gitlab.example.com/team/app")
+        );
+        assert_eq!(
+            remote_location("https://user@github.com/acme/app/").as_deref(),
+            Some("github.com/acme/app")
+        );
+        assert_eq!(remote_location("/srv/git/app.git"), None);
+        assert_eq!(remote_location("../app.git"), None);
+    }
+
+    #[test]
+    fn test_is_expected_remote() {
+        let expected = vec!["github.com/acme".to_string(), "gitlab.acme.io".to_string()];
+
+        assert!(is_expected_remote("github.com/acme/app", &expected));
+        assert!(is_expected_remote("GitLab.acme.io/team/app", &expected));
+        assert!(!is_expected_remote(
+            "github.com/acme-personal/app",
+            &expected
+        ));
+        assert!(!is_expected_remote("github.com/me/app", &expected));
+        assert!(!is_expected_remote("github.com/acme/app", &[]));
+    }
+}
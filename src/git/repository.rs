@@ -2,8 +2,40 @@
 //!
 //! Core repository-level operations for Git repositories including repository detection
 //! and path resolution using the git CLI.
+//!
+//! ## Environment overrides
+//!
+//! Git's standard location overrides (`GIT_DIR`, `GIT_WORK_TREE`, `GIT_INDEX_FILE`, ...)
+//! are honored by every git invocation. They are set by git itself when running hooks,
+//! and by tools like pre-commit. Relative values are resolved against the directory
+//! rona was started in, so they keep pointing at the same repository when a command
+//! runs from the top level of the working tree (see [`git_command_in`]).
+//!
+//! ## Read-only mode
+//!
+//! `--read-only` (or `RONA_READ_ONLY=1`) makes every operation that writes to the
+//! repository, the index or the config refuse to run (see [`ensure_writable`]), so
+//! rona can be pointed at a repository it must not touch.
+//!
+//! ## Guards
+//!
+//! `[[guards]]` in the global config deny single features (staging, committing,
+//! signing, pushing, ...) in the repositories they match. The CLI resolves them once
+//! with [`set_denied_features`], and each operation checks its feature with
+//! [`ensure_allowed`] before running.
+
+use std::{
+    env,
+    ffi::{OsStr, OsString},
+    path::{Path, PathBuf},
+    process::{Command, Output},
+    sync::{
+        Mutex, PoisonError,
+        atomic::{AtomicBool, Ordering},
+    },
+};
 
-use std::{path::PathBuf, process::Command};
+use serde::{Deserialize, Serialize};
 
 use crate::errors::{GitError, Result, RonaError};
 
@@ -89,3 +121,313 @@ pub fn get_top_level_path() -> Result<PathBuf> {
     let path_str = String::from_utf8_lossy(&output.stdout).trim().to_string();
     Ok(PathBuf::from(path_str))
 }
+
+/// Resolves a path inside the git directory, as `git rev-parse --git-path` does.
+///
+/// Unlike joining onto [`find_git_root`], this honors `GIT_DIR`, `GIT_COMMON_DIR`
+/// and linked worktrees, where files like `info/exclude` live in the common
+/// directory rather than the worktree's own git directory.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - Not currently in a git repository
+/// - Unable to determine the current directory
+pub fn git_path(relative: &str) -> Result<PathBuf> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--git-path", relative])
+        .output()
+        .map_err(RonaError::Io)?;
+
+    if !output.status.success() {
+        return Err(RonaError::Git(GitError::RepositoryNotFound));
+    }
+
+    let path = PathBuf::from(String::from_utf8_lossy(&output.stdout).trim());
+    if path.is_absolute() {
+        Ok(path)
+    } else {
+        Ok(env::current_dir().map_err(RonaError::Io)?.join(path))
+    }
+}
+
+/// Git environment variables holding paths that git resolves against its working directory.
+const GIT_PATH_ENV_VARS: [&str; 5] = [
+    "GIT_DIR",
+    "GIT_WORK_TREE",
+    "GIT_INDEX_FILE",
+    "GIT_COMMON_DIR",
+    "GIT_OBJECT_DIRECTORY",
+];
+
+/// Creates a `git` command that runs in `dir` against the same repository as the
+/// current process.
+///
+/// Running git from another directory would otherwise reinterpret relative
+/// `GIT_DIR`, `GIT_WORK_TREE` or `GIT_INDEX_FILE` values, so these are passed to the
+/// child as absolute paths.
+///
+/// # Errors
+///
+/// Returns an error if the current directory cannot be determined.
+pub fn git_command_in(dir: &Path) -> Result<Command> {
+    let cwd = env::current_dir().map_err(RonaError::Io)?;
+
+    let mut cmd = Command::new("git");
+    cmd.current_dir(dir).envs(absolute_git_env(
+        GIT_PATH_ENV_VARS
+            .iter()
+            .filter_map(|&name| env::var_os(name).map(|value| (name, value))),
+        &cwd,
+    ));
+    Ok(cmd)
+}
+
+/// Runs a git command and returns its output, whatever its status.
+///
+/// # Errors
+///
+/// Returns an error if git cannot be started.
+pub(crate) fn run_git(args: &[&str]) -> Result<Output> {
+    Command::new("git")
+        .args(args)
+        .output()
+        .map_err(RonaError::Io)
+}
+
+/// Runs a git command and returns its stdout, failing on a non-zero status.
+///
+/// # Errors
+///
+/// Returns an error if git cannot be started or exits with a non-zero status.
+pub(crate) fn git_stdout(args: &[&str]) -> Result<String> {
+    let output = run_git(args)?;
+    if !output.status.success() {
+        return Err(RonaError::Git(GitError::CommandFailed {
+            command: format!("git {}", args.join(" ")),
+            output: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        }));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Rewrites relative git path variables as absolute paths under `cwd`.
+fn absolute_git_env<'a>(
+    vars: impl Iterator<Item = (&'a str, OsString)>,
+    cwd: &Path,
+) -> Vec<(&'a str, PathBuf)> {
+    vars.filter(|(_, value)| !value.is_empty())
+        .map(|(name, value)| (name, cwd.join(value)))
+        .collect()
+}
+
+/// Returns `true` when rona runs from a hook of an in-progress `git commit`.
+///
+/// Git exports `GIT_EXEC_PATH` to every process it spawns, and sets `GIT_INDEX_FILE`
+/// for `pre-commit`, `prepare-commit-msg` and `commit-msg` to the index being
+/// committed (`index.lock`, or a temporary `next-index-*` file for `git commit <paths>`).
+/// Staging or committing from there would rewrite that index, while read-only
+/// commands like `rona generate --print` are safe.
+#[must_use]
+pub fn is_inside_commit_hook() -> bool {
+    is_commit_hook_env(
+        env::var_os("GIT_EXEC_PATH").as_deref(),
+        env::var_os("GIT_INDEX_FILE").as_deref(),
+    )
+}
+
+/// Refuses to run an index-modifying command from inside a commit hook.
+///
+/// # Errors
+///
+/// Returns `GitError::InsideCommitHook` if [`is_inside_commit_hook`] is `true`.
+pub fn ensure_outside_commit_hook(command: &str) -> Result<()> {
+    if is_inside_commit_hook() {
+        return Err(RonaError::Git(GitError::InsideCommitHook {
+            command: command.to_string(),
+        }));
+    }
+    Ok(())
+}
+
+/// Set by the global `--read-only` flag.
+static READ_ONLY: AtomicBool = AtomicBool::new(false);
+
+/// Environment variable that enables read-only mode like `--read-only`.
+pub const READ_ONLY_ENV: &str = "RONA_READ_ONLY";
+
+/// Enables read-only mode for the rest of the process.
+pub fn set_read_only(read_only: bool) {
+    READ_ONLY.store(read_only, Ordering::Relaxed);
+}
+
+/// Whether mutating operations are refused, because of `--read-only` or a
+/// non-empty `RONA_READ_ONLY` other than `0` or `false`.
+#[must_use]
+pub fn is_read_only() -> bool {
+    READ_ONLY.load(Ordering::Relaxed) || is_read_only_env(env::var_os(READ_ONLY_ENV).as_deref())
+}
+
+/// Refuses to run a mutating operation in read-only mode.
+///
+/// `action` completes "refusing to ...", e.g. `"stage files"`.
+///
+/// # Errors
+///
+/// Returns `RonaError::ReadOnly` if [`is_read_only`] is `true`.
+pub fn ensure_writable(action: &str) -> Result<()> {
+    if is_read_only() {
+        return Err(RonaError::ReadOnly {
+            action: action.to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// A rona feature that `[[guards]]` can deny in a repository.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Feature {
+    /// Staging and unstaging files
+    Stage,
+    /// Committing, including fixups
+    Commit,
+    /// Signing commits; denied, commits are made with `--no-gpg-sign`
+    Sign,
+    /// Pushing
+    Push,
+    /// Fetching, including the `auto_fetch` background fetch
+    Fetch,
+    /// Pulling
+    Pull,
+    /// Merging branches
+    Merge,
+    /// Rebasing branches
+    Rebase,
+}
+
+impl Feature {
+    /// Every feature, in the order of the config documentation.
+    pub const ALL: [Self; 8] = [
+        Self::Stage,
+        Self::Commit,
+        Self::Sign,
+        Self::Push,
+        Self::Fetch,
+        Self::Pull,
+        Self::Merge,
+        Self::Rebase,
+    ];
+
+    /// The name used in the config, e.g. `push`.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Stage => "stage",
+            Self::Commit => "commit",
+            Self::Sign => "sign",
+            Self::Push => "push",
+            Self::Fetch => "fetch",
+            Self::Pull => "pull",
+            Self::Merge => "merge",
+            Self::Rebase => "rebase",
+        }
+    }
+}
+
+/// The features denied in this repository, each with the guard that denied it.
+static DENIED: Mutex<Vec<(Feature, String)>> = Mutex::new(Vec::new());
+
+/// Denies features for the rest of the process. `guard` describes the `[[guards]]`
+/// entry responsible, e.g. `path = "~/oss/**"`.
+pub fn set_denied_features(denied: Vec<(Feature, String)>) {
+    *DENIED.lock().unwrap_or_else(PoisonError::into_inner) = denied;
+}
+
+/// The guard denying `feature` in this repository, `None` when it is allowed.
+#[must_use]
+pub fn denied_by(feature: Feature) -> Option<String> {
+    DENIED
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .iter()
+        .find(|(denied, _)| *denied == feature)
+        .map(|(_, guard)| guard.clone())
+}
+
+/// Refuses to run an operation whose feature a guard denies in this repository.
+///
+/// # Errors
+///
+/// Returns `RonaError::Denied` if [`denied_by`] names a guard.
+pub fn ensure_allowed(feature: Feature) -> Result<()> {
+    denied_by(feature).map_or(Ok(()), |guard| {
+        Err(RonaError::Denied {
+            feature: feature.name().to_string(),
+            guard,
+        })
+    })
+}
+
+fn is_read_only_env(value: Option<&OsStr>) -> bool {
+    value.is_some_and(|v| !v.is_empty() && v != "0" && !v.eq_ignore_ascii_case("false"))
+}
+
+/// A commit hook is a git-spawned process with an index override.
+fn is_commit_hook_env(exec_path: Option<&OsStr>, index_file: Option<&OsStr>) -> bool {
+    exec_path.is_some_and(|p| !p.is_empty()) && index_file.is_some_and(|i| !i.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_only_env_values() {
+        assert!(is_read_only_env(Some(OsStr::new("1"))));
+        assert!(is_read_only_env(Some(OsStr::new("true"))));
+        assert!(!is_read_only_env(Some(OsStr::new("0"))));
+        assert!(!is_read_only_env(Some(OsStr::new("FALSE"))));
+        assert!(!is_read_only_env(Some(OsStr::new(""))));
+        assert!(!is_read_only_env(None));
+    }
+
+    #[test]
+    fn test_absolute_git_env_resolves_relative_paths() {
+        let cwd = Path::new("/work/hooks");
+        let vars = vec![
+            ("GIT_DIR", OsString::from("../repo/.git")),
+            ("GIT_INDEX_FILE", OsString::from("/tmp/index.lock")),
+        ];
+
+        let resolved = absolute_git_env(vars.into_iter(), cwd);
+
+        assert_eq!(
+            resolved,
+            vec![
+                ("GIT_DIR", PathBuf::from("/work/hooks/../repo/.git")),
+                ("GIT_INDEX_FILE", PathBuf::from("/tmp/index.lock")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_absolute_git_env_skips_empty_values() {
+        let vars = vec![("GIT_WORK_TREE", OsString::new())];
+
+        assert!(absolute_git_env(vars.into_iter(), Path::new("/work")).is_empty());
+    }
+
+    #[test]
+    fn test_is_commit_hook_env() {
+        let exec = Some(OsStr::new("/usr/lib/git-core"));
+        let index = Some(OsStr::new(".git/index.lock"));
+
+        assert!(is_commit_hook_env(exec, index));
+        // Spawned by git but not during a commit (e.g. an alias or `pre-push`)
+        assert!(!is_commit_hook_env(exec, None));
+        // Alternate index set by the user, not by git
+        assert!(!is_commit_hook_env(None, index));
+        assert!(!is_commit_hook_env(Some(OsStr::new("")), index));
+    }
+}
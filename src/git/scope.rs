@@ -0,0 +1,151 @@
+//! Path Scope
+//!
+//! `--scope <SCOPE>` narrows status, staging and message generation to one directory
+//! of a monorepo, such as `packages/api`, so one package's changes are committed on
+//! their own. The scope is a name from the `[scopes]` table or a path, and its name
+//! becomes the `{scope}` of commit messages and branch names.
+//!
+//! Like the sparse-checkout cone (see [`super::sparse`]), it is set once for the
+//! process and applied wherever git status is read.
+
+use std::{
+    collections::BTreeMap,
+    sync::{Mutex, PoisonError},
+};
+
+use crate::errors::{Result, RonaError};
+
+use super::{paths::resolve_in_repo, repository::get_top_level_path};
+
+/// A directory of the repository that rona is narrowed to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PathScope {
+    /// The `{scope}` of messages and branch names
+    pub name: String,
+    /// Path relative to the repository root, with `/` separators
+    pub dir: String,
+}
+
+impl PathScope {
+    /// Resolves `--scope`: a name from `scopes`, else a path from the current
+    /// directory. A path is named after the `scopes` entry pointing to it, else after
+    /// its last directory.
+    ///
+    /// # Errors
+    /// * If the repository root or the current directory cannot be found
+    /// * If the path is the repository root, outside of it, or not a directory
+    pub fn resolve(arg: &str, scopes: &BTreeMap<String, String>) -> Result<Self> {
+        if let Some(dir) = scopes.get(arg) {
+            return Ok(Self {
+                name: arg.to_string(),
+                dir: normalize_dir(dir),
+            });
+        }
+
+        let repo_root = get_top_level_path()?;
+        let current_dir = std::env::current_dir().map_err(RonaError::Io)?;
+        let unknown = |reason: String| {
+            RonaError::InvalidInput(format!(
+                "--scope: {reason}; known scopes: {}",
+                scope_names(scopes)
+            ))
+        };
+        let dir =
+            resolve_in_repo(arg, &current_dir, &repo_root).map_err(|e| unknown(e.to_string()))?;
+        if !repo_root.join(&dir).is_dir() {
+            return Err(unknown(format!(
+                "'{arg}' is neither a scope name nor a directory"
+            )));
+        }
+        let name = scopes
+            .iter()
+            .find(|(_, configured)| normalize_dir(configured) == dir)
+            .map_or_else(
+                || dir.rsplit('/').next().unwrap_or(&dir).to_string(),
+                |(name, _)| name.clone(),
+            );
+        Ok(Self { name, dir })
+    }
+
+    /// Whether a repository-relative path is inside the scope directory.
+    #[must_use]
+    pub fn contains(&self, path: &str) -> bool {
+        let path = path.trim_end_matches('/');
+        path == self.dir
+            || path
+                .strip_prefix(&self.dir)
+                .is_some_and(|rest| rest.starts_with('/'))
+    }
+}
+
+/// A configured directory without `./` or surrounding slashes.
+fn normalize_dir(dir: &str) -> String {
+    dir.trim_start_matches("./").trim_matches('/').to_string()
+}
+
+/// The configured scope names, for error messages.
+fn scope_names(scopes: &BTreeMap<String, String>) -> String {
+    if scopes.is_empty() {
+        "none".to_string()
+    } else {
+        scopes.keys().cloned().collect::<Vec<_>>().join(", ")
+    }
+}
+
+/// Set by the global `--scope` flag.
+static SCOPE: Mutex<Option<PathScope>> = Mutex::new(None);
+
+/// Narrows rona to `scope` for the rest of the process.
+pub fn set_path_scope(scope: Option<PathScope>) {
+    *SCOPE.lock().unwrap_or_else(PoisonError::into_inner) = scope;
+}
+
+/// The scope set by `--scope`, if any.
+#[must_use]
+pub fn path_scope() -> Option<PathScope> {
+    SCOPE.lock().unwrap_or_else(PoisonError::into_inner).clone()
+}
+
+/// Whether a repository-relative path is inside the `--scope` directory; always true
+/// without one.
+#[must_use]
+pub fn in_scope(path: &str) -> bool {
+    SCOPE
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .as_ref()
+        .is_none_or(|scope| scope.contains(path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scope_contains() {
+        let scope = PathScope {
+            name: "api".to_string(),
+            dir: "packages/api".to_string(),
+        };
+        assert!(scope.contains("packages/api/src/main.rs"));
+        assert!(scope.contains("packages/api"));
+        // Collapsed untracked directories
+        assert!(scope.contains("packages/api/new/"));
+        assert!(!scope.contains("packages/api-client/lib.rs"));
+        assert!(!scope.contains("packages/package.json"));
+        assert!(!scope.contains("README.md"));
+    }
+
+    #[test]
+    fn test_resolve_configured_scope() -> Result<()> {
+        let scopes = BTreeMap::from([("api".to_string(), "./packages/api/".to_string())]);
+        assert_eq!(
+            PathScope::resolve("api", &scopes)?,
+            PathScope {
+                name: "api".to_string(),
+                dir: "packages/api".to_string(),
+            }
+        );
+        Ok(())
+    }
+}
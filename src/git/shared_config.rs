@@ -0,0 +1,110 @@
+//! Shared Configuration
+//!
+//! Git side of `rona config sync`: the canonical `.rona.toml` of a team is read from a
+//! branch of a remote, such as a `rona-config` branch of `origin` or of a dedicated
+//! repository, and compared with the local file through `git diff --no-index`.
+
+use std::{
+    io::Write,
+    path::Path,
+    process::{Command, Stdio},
+};
+
+use crate::errors::{GitError, Result, RonaError};
+
+use super::repository::{Feature, ensure_allowed, run_git};
+
+/// Branch the canonical config is read from when `[config_sync]` names none.
+pub const DEFAULT_CONFIG_BRANCH: &str = "rona-config";
+
+/// Fetches `branch` of `remote`, a remote name or URL, and reads the file at `path`
+/// in it.
+///
+/// # Errors
+/// * If `remote` or `branch` starts with `-`, so git would read it as an option
+/// * If fetching is denied by a guard
+/// * If the branch cannot be fetched, or has no file at `path`
+pub fn fetch_branch_file(remote: &str, branch: &str, path: &str) -> Result<String> {
+    for (key, value) in [("remote", remote), ("branch", branch)] {
+        if value.starts_with('-') {
+            return Err(RonaError::InvalidInput(format!(
+                "[config_sync] {key} cannot start with '-': {value}"
+            )));
+        }
+    }
+    ensure_allowed(Feature::Fetch)?;
+
+    let output = Command::new("git")
+        .args(["fetch", "--quiet", "--no-tags", "--", remote, branch])
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .stdin(Stdio::null())
+        .output()
+        .map_err(RonaError::Io)?;
+    if !output.status.success() {
+        return Err(RonaError::InvalidInput(format!(
+            "Cannot fetch branch '{branch}' of '{remote}': {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+
+    let output = run_git(&["show", &format!("FETCH_HEAD:{path}")])?;
+    if !output.status.success() {
+        return Err(RonaError::InvalidInput(format!(
+            "Branch '{branch}' of '{remote}' has no {path}"
+        )));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// The hunks turning the file at `path` (empty when missing) into `text`, as a
+/// uncolored unified diff without its file headers. Empty when they are the same.
+///
+/// # Errors
+/// * If `git diff` cannot be run or fails
+pub fn config_diff(path: &Path, text: &str) -> Result<String> {
+    let old = if path.exists() {
+        path.as_os_str()
+    } else {
+        "/dev/null".as_ref()
+    };
+    let mut child = Command::new("git")
+        .args(["diff", "--no-index", "--no-color", "--no-ext-diff", "--"])
+        .arg(old)
+        .arg("-")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(text.as_bytes())?;
+    }
+    let output = child.wait_with_output()?;
+
+    // `--no-index` exits with 1 when the files differ.
+    if output.status.code().is_some_and(|code| code > 1) {
+        return Err(RonaError::Git(GitError::CommandFailed {
+            command: "git diff --no-index".to_string(),
+            output: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        }));
+    }
+    let diff = String::from_utf8_lossy(&output.stdout);
+    Ok(diff
+        .find("\n@@")
+        .map(|start| diff[start + 1..].to_string())
+        .unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fetch_branch_file_rejects_options() {
+        for (remote, branch) in [("--upload-pack=touch pwned", "main"), ("origin", "-v")] {
+            assert!(matches!(
+                fetch_branch_file(remote, branch, ".rona.toml"),
+                Err(RonaError::InvalidInput(message)) if message.contains("cannot start with '-'")
+            ));
+        }
+    }
+}
@@ -0,0 +1,403 @@
+//! Commit Inspection
+//!
+//! Reads a single commit for `rona show`: author, message, the values of the
+//! template variables in its subject, trailers, notes, signature status and diff
+//! stat. Everything is read with one `git show` format string plus a diff stat, so
+//! signature verification follows the user's git and gpg setup.
+//!
+//! [`recent_commits`] lists the one-line summaries that `rona browse` picks from,
+//! [`commit_messages`] the full messages that `rona audit` lints, and
+//! [`commit_reference`] the fields `rona copy` formats.
+
+use std::process::Command;
+
+use serde::Serialize;
+
+use crate::{
+    errors::{GitError, Result, RonaError},
+    lint::parse_header,
+};
+
+use super::repository::git_stdout;
+
+/// Separates the fields of the `git show` format string.
+const FIELD_SEPARATOR: char = '\u{1e}';
+
+/// Separates trailers within the trailers field.
+const TRAILER_SEPARATOR: char = '\u{1f}';
+
+/// The `git show` format: one field per line of [`CommitDetails`], in order.
+const FORMAT: &str = "%H%x1e%an%x1e%ae%x1e%aI%x1e%G?%x1e%GS%x1e%s%x1e%b%x1e%(trailers:only,unfold,separator=%x1f)%x1e%N";
+
+/// A template variable and its value, as found in a commit subject.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct HeaderField {
+    pub name: String,
+    pub value: String,
+}
+
+/// A `Key: value` trailer from the end of a commit message.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Trailer {
+    pub key: String,
+    pub value: String,
+}
+
+/// The signature verification result, from git's `%G?` placeholder.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Signature {
+    /// `good`, `bad`, `unknown-validity`, `expired`, `expired-key`, `revoked`,
+    /// `unverifiable` or `none`
+    pub status: &'static str,
+    /// The signer, when git could determine it
+    pub signer: Option<String>,
+}
+
+/// Everything `rona show` displays about a commit.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CommitDetails {
+    pub sha: String,
+    pub author: String,
+    pub email: String,
+    /// Author date, in strict ISO 8601
+    pub date: String,
+    pub subject: String,
+    pub body: String,
+    /// The template variables in the subject, or `None` when it does not match the template
+    pub header_fields: Option<Vec<HeaderField>>,
+    pub trailers: Vec<Trailer>,
+    pub notes: Option<String>,
+    pub signature: Signature,
+    /// Output of `git show --stat` (first parent for merges)
+    pub stat: String,
+}
+
+/// One line of history, as listed by `rona browse`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommitSummary {
+    pub sha: String,
+    pub short_sha: String,
+    pub subject: String,
+    /// Author date relative to now, e.g. `3 days ago`
+    pub relative_date: String,
+}
+
+/// Lists the most recent commits reachable from `HEAD`, newest first.
+///
+/// # Errors
+/// * If `git log` fails for another reason than an empty repository
+pub fn recent_commits(limit: usize) -> Result<Vec<CommitSummary>> {
+    let output = Command::new("git")
+        .args([
+            "log",
+            &format!("--max-count={limit}"),
+            "--format=%H%x1e%h%x1e%s%x1e%ar",
+        ])
+        .output()
+        .map_err(RonaError::Io)?;
+    if !output.status.success() {
+        // A repository without commits has no history to list.
+        return Ok(Vec::new());
+    }
+    Ok(parse_summaries(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// A commit message with its author, as linted by `rona audit`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommitMessage {
+    pub sha: String,
+    pub short_sha: String,
+    pub author: String,
+    pub message: String,
+}
+
+/// The fields of a commit that `rona copy` formats.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommitReference {
+    pub sha: String,
+    pub short_sha: String,
+    pub subject: String,
+    pub body: String,
+    pub author: String,
+    pub email: String,
+    /// Author date, as `YYYY-MM-DD`
+    pub date: String,
+}
+
+/// Reads the fields `rona copy` formats of a commit.
+///
+/// # Errors
+/// * If the reference does not name a commit
+pub fn commit_reference(reference: &str) -> Result<CommitReference> {
+    let output = git_stdout(&[
+        "show",
+        "-s",
+        "--format=%H%x1e%h%x1e%an%x1e%ae%x1e%as%x1e%s%x1e%b",
+        &format!("{reference}^{{commit}}"),
+        "--",
+    ])
+    .map_err(|_| RonaError::InvalidInput(format!("'{reference}' is not a commit")))?;
+    parse_reference(&output).ok_or_else(|| {
+        RonaError::Git(GitError::InvalidStatus {
+            output: format!("Unexpected `git show` output for {reference}"),
+        })
+    })
+}
+
+/// Lists the non-merge commits of `range` (e.g. `v1.0..HEAD`), newest first, with
+/// their full message.
+///
+/// # Errors
+/// * If `range` does not resolve, or `git log` fails for another reason than an
+///   empty repository
+pub fn commit_messages(range: &str) -> Result<Vec<CommitMessage>> {
+    if git_stdout(&["rev-parse", "--verify", "--quiet", "HEAD"]).is_err() {
+        return Ok(Vec::new());
+    }
+    let output = git_stdout(&[
+        "log",
+        "-z",
+        "--no-merges",
+        "--format=%H%x1e%h%x1e%an%x1e%B",
+        range,
+        "--",
+    ])?;
+    Ok(parse_messages(&output))
+}
+
+/// Reads the details of a commit.
+///
+/// # Arguments
+/// * `reference` - Any revision git understands (`HEAD~2`, a SHA, a tag, ...)
+/// * `template` - The commit template used to parse the subject
+/// * `commit_types` - Allowed values for `{commit_type}`
+///
+/// # Errors
+/// * If the reference does not name a commit
+/// * If a git command fails
+pub fn commit_details(
+    reference: &str,
+    template: &str,
+    commit_types: &[&str],
+) -> Result<CommitDetails> {
+    let commit = git_stdout(&[
+        "rev-parse",
+        "--verify",
+        "--quiet",
+        &format!("{reference}^{{commit}}"),
+    ])
+    .map_err(|_| RonaError::InvalidInput(format!("'{reference}' is not a commit")))?;
+    let commit = commit.trim();
+
+    let output = git_stdout(&["show", "-s", &format!("--format={FORMAT}"), commit, "--"])?;
+    let stat = git_stdout(&[
+        "show",
+        "--stat",
+        "--format=",
+        "--first-parent",
+        commit,
+        "--",
+    ])?;
+
+    let mut details = parse_details(&output).ok_or_else(|| {
+        RonaError::Git(GitError::InvalidStatus {
+            output: format!("Unexpected `git show` output for {reference}"),
+        })
+    })?;
+    details.header_fields = parse_header(&details.subject, template, commit_types).map(|f| {
+        f.into_iter()
+            .map(|(name, value)| HeaderField { name, value })
+            .collect()
+    });
+    details.stat = stat.trim_end().to_string();
+    Ok(details)
+}
+
+/// Parses the output of [`FORMAT`], leaving the header fields and stat empty.
+fn parse_details(output: &str) -> Option<CommitDetails> {
+    let fields: Vec<&str> = output.splitn(10, FIELD_SEPARATOR).collect();
+    let [
+        sha,
+        author,
+        email,
+        date,
+        status,
+        signer,
+        subject,
+        body,
+        trailers,
+        notes,
+    ] = fields.as_slice()
+    else {
+        return None;
+    };
+
+    let trailers = trailers
+        .split(TRAILER_SEPARATOR)
+        .filter_map(|t| t.split_once(':'))
+        .map(|(key, value)| Trailer {
+            key: key.trim().to_string(),
+            value: value.trim().to_string(),
+        })
+        .collect();
+    let signer = signer.trim();
+    let notes = notes.trim();
+
+    Some(CommitDetails {
+        sha: sha.trim().to_string(),
+        author: (*author).to_string(),
+        email: (*email).to_string(),
+        date: (*date).to_string(),
+        subject: (*subject).to_string(),
+        body: body.trim_end().to_string(),
+        header_fields: None,
+        trailers,
+        notes: (!notes.is_empty()).then(|| notes.to_string()),
+        signature: Signature {
+            status: signature_status(status),
+            signer: (!signer.is_empty()).then(|| signer.to_string()),
+        },
+        stat: String::new(),
+    })
+}
+
+/// Parses `git log` lines of `sha`, short sha, subject and relative date.
+fn parse_summaries(output: &str) -> Vec<CommitSummary> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.splitn(4, FIELD_SEPARATOR);
+            Some(CommitSummary {
+                sha: fields.next()?.to_string(),
+                short_sha: fields.next()?.to_string(),
+                subject: fields.next()?.to_string(),
+                relative_date: fields.next()?.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Parses a `git show` record of sha, short sha, author, email, date, subject and body.
+fn parse_reference(output: &str) -> Option<CommitReference> {
+    let mut fields = output.splitn(7, FIELD_SEPARATOR);
+    Some(CommitReference {
+        sha: fields.next()?.to_string(),
+        short_sha: fields.next()?.to_string(),
+        author: fields.next()?.to_string(),
+        email: fields.next()?.to_string(),
+        date: fields.next()?.to_string(),
+        subject: fields.next()?.to_string(),
+        body: fields.next()?.trim_end().to_string(),
+    })
+}
+
+/// Parses NUL-separated `git log` records of sha, short sha, author and message.
+fn parse_messages(output: &str) -> Vec<CommitMessage> {
+    output
+        .split('\0')
+        .filter_map(|record| {
+            let mut fields = record.splitn(4, FIELD_SEPARATOR);
+            Some(CommitMessage {
+                sha: fields.next()?.to_string(),
+                short_sha: fields.next()?.to_string(),
+                author: fields.next()?.to_string(),
+                message: fields.next()?.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Names a `%G?` signature code.
+fn signature_status(code: &str) -> &'static str {
+    match code {
+        "G" => "good",
+        "B" => "bad",
+        "U" => "unknown-validity",
+        "X" => "expired",
+        "Y" => "expired-key",
+        "R" => "revoked",
+        "E" => "unverifiable",
+        _ => "none",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type TestResult = std::result::Result<(), Box<dyn std::error::Error>>;
+
+    #[test]
+    fn test_parse_messages() {
+        let output = "abc123\u{1e}abc\u{1e}Jane Doe\u{1e}(feat on login) add form\n\nBody.\n\0def456\u{1e}def\u{1e}John\u{1e}wip\n\0";
+        let messages = parse_messages(output);
+
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0].author, "Jane Doe");
+        assert_eq!(messages[0].message, "(feat on login) add form\n\nBody.\n");
+        assert_eq!(messages[1].sha, "def456");
+        assert_eq!(messages[1].short_sha, "def");
+        assert_eq!(messages[1].message, "wip\n");
+    }
+
+    #[test]
+    fn test_parse_details() -> TestResult {
+        let output = "abc123\u{1e}Jane\u{1e}jane@example.com\u{1e}2026-01-02T03:04:05+00:00\u{1e}G\u{1e}Jane <jane@example.com>\u{1e}[3] (feat on login) add form\u{1e}- `src/form.rs`: new\n\nSigned-off-by: Jane <jane@example.com>\n\u{1e}Signed-off-by: Jane <jane@example.com>\u{1f}Refs: #12\u{1e}reviewed\n\n";
+        let details = parse_details(output).ok_or("unparsed output")?;
+
+        assert_eq!(details.sha, "abc123");
+        assert_eq!(details.subject, "[3] (feat on login) add form");
+        assert_eq!(
+            details.body,
+            "- `src/form.rs`: new\n\nSigned-off-by: Jane <jane@example.com>"
+        );
+        assert_eq!(
+            details.trailers,
+            vec![
+                Trailer {
+                    key: "Signed-off-by".to_string(),
+                    value: "Jane <jane@example.com>".to_string()
+                },
+                Trailer {
+                    key: "Refs".to_string(),
+                    value: "#12".to_string()
+                },
+            ]
+        );
+        assert_eq!(details.notes.as_deref(), Some("reviewed"));
+        assert_eq!(details.signature.status, "good");
+        assert_eq!(
+            details.signature.signer.as_deref(),
+            Some("Jane <jane@example.com>")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_details_unsigned_without_extras() -> TestResult {
+        let output = "abc\u{1e}A\u{1e}a@b\u{1e}d\u{1e}N\u{1e}\u{1e}subject\u{1e}\u{1e}\u{1e}\n";
+        let details = parse_details(output).ok_or("unparsed output")?;
+
+        assert!(details.trailers.is_empty());
+        assert_eq!(details.notes, None);
+        assert_eq!(details.signature.status, "none");
+        assert_eq!(details.signature.signer, None);
+
+        assert_eq!(parse_details("truncated"), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_summaries() {
+        let output = "abc123\u{1e}abc\u{1e}[2] (feat on x) add\u{1e}2 hours ago\nbroken line\n";
+        assert_eq!(
+            parse_summaries(output),
+            vec![CommitSummary {
+                sha: "abc123".to_string(),
+                short_sha: "abc".to_string(),
+                subject: "[2] (feat on x) add".to_string(),
+                relative_date: "2 hours ago".to_string(),
+            }]
+        );
+    }
+}
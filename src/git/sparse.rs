@@ -0,0 +1,127 @@
+//! Sparse Checkout
+//!
+//! Reads the cone of a cone-mode sparse checkout (`git sparse-checkout set <dirs>`),
+//! so status, staging and message generation can stay inside the materialized part
+//! of the tree. Git refuses to `git add` paths outside the cone, and listing them
+//! as if they could be committed is misleading.
+//!
+//! Non-cone sparse checkouts use gitignore-style patterns and are not narrowed.
+
+use std::process::Command;
+
+use crate::errors::{GitError, Result, RonaError};
+
+use super::status::unquote_git_path;
+
+/// The directories of a cone-mode sparse checkout.
+///
+/// A cone contains every file at the root, every file directly inside an ancestor
+/// of a listed directory, and everything below a listed directory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseCone {
+    dirs: Vec<String>,
+}
+
+impl SparseCone {
+    /// Creates a cone from the directories `git sparse-checkout list` prints.
+    #[must_use]
+    pub fn new(dirs: Vec<String>) -> Self {
+        let dirs = dirs
+            .into_iter()
+            .map(|dir| dir.trim_matches('/').to_string())
+            .filter(|dir| !dir.is_empty())
+            .collect();
+        Self { dirs }
+    }
+
+    /// Whether a repository-relative path is inside the cone.
+    ///
+    /// Collapsed untracked directories (`new/dir/`) are judged by the directory itself.
+    #[must_use]
+    pub fn contains(&self, path: &str) -> bool {
+        let dir = path
+            .strip_suffix('/')
+            .unwrap_or_else(|| path.rsplit_once('/').map_or("", |(parent, _)| parent));
+        self.contains_dir(dir)
+    }
+
+    /// Whether a directory is in the cone: the root, a listed directory, one of
+    /// their descendants, or an ancestor of a listed directory.
+    fn contains_dir(&self, dir: &str) -> bool {
+        dir.is_empty()
+            || self
+                .dirs
+                .iter()
+                .any(|listed| listed == dir || is_below(dir, listed) || is_below(listed, dir))
+    }
+}
+
+/// Whether `path` is strictly below the directory `dir`.
+fn is_below(path: &str, dir: &str) -> bool {
+    path.strip_prefix(dir)
+        .is_some_and(|rest| rest.starts_with('/'))
+}
+
+/// Returns the cone when the repository is a cone-mode sparse checkout.
+///
+/// # Errors
+/// * If git cannot be run
+/// * If `git sparse-checkout list` fails
+pub fn sparse_cone() -> Result<Option<SparseCone>> {
+    if !git_config_enabled("core.sparseCheckout")?
+        || !git_config_enabled("core.sparseCheckoutCone")?
+    {
+        return Ok(None);
+    }
+
+    let output = Command::new("git")
+        .args(["sparse-checkout", "list"])
+        .output()
+        .map_err(RonaError::Io)?;
+    if !output.status.success() {
+        return Err(RonaError::Git(GitError::CommandFailed {
+            command: "git sparse-checkout list".to_string(),
+            output: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        }));
+    }
+
+    let dirs = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(unquote_git_path)
+        .collect();
+    Ok(Some(SparseCone::new(dirs)))
+}
+
+/// Whether a boolean git config key is set to true, worktree config included.
+fn git_config_enabled(key: &str) -> Result<bool> {
+    let output = Command::new("git")
+        .args(["config", "--get", "--bool", key])
+        .output()
+        .map_err(RonaError::Io)?;
+    Ok(output.status.success() && String::from_utf8_lossy(&output.stdout).trim() == "true")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cone_contains() {
+        let cone = SparseCone::new(vec!["packages/api/".to_string(), "docs".to_string()]);
+
+        // Root files are always in the cone
+        assert!(cone.contains("README.md"));
+        // Everything below a listed directory
+        assert!(cone.contains("packages/api/src/main.rs"));
+        assert!(cone.contains("docs/guide.md"));
+        // Files directly inside an ancestor, but not its other subdirectories
+        assert!(cone.contains("packages/package.json"));
+        assert!(!cone.contains("packages/web/index.ts"));
+        assert!(!cone.contains("packages/api-client/lib.rs"));
+        assert!(!cone.contains("tools/build.sh"));
+        // Collapsed untracked directories
+        assert!(cone.contains("packages/api/new/"));
+        assert!(!cone.contains("tools/"));
+        assert!(!cone.contains("packages/web/"));
+    }
+}
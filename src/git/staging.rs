@@ -2,18 +2,25 @@
 //!
 //! File staging functionality with pattern exclusion and dry-run capabilities.
 
-use std::{io::IsTerminal, process::Command, time::Duration};
+use std::{io::IsTerminal, path::Path, time::Duration};
 
+use colored::Colorize;
 use glob::Pattern;
 use indicatif::{ProgressBar, ProgressDrawTarget};
+use serde::Serialize;
 
-use crate::errors::{GitError, Result, RonaError};
+use crate::{
+    errors::{GitError, Result, RonaError},
+    theme::is_accessible,
+};
 
 use super::{
-    repository::get_top_level_path,
+    attributes::{AttributeValue, attribute_values},
+    repository::{Feature, ensure_allowed, ensure_writable, get_top_level_path, git_command_in},
+    scope::path_scope,
+    sparse::sparse_cone,
     status::{
-        count_renamed_files, get_all_staged_file_paths, get_status_files,
-        process_deleted_files_for_staging,
+        StatusSnapshot, count_renamed_files, get_all_staged_file_paths, get_conflicted_files,
     },
 };
 
@@ -92,8 +99,7 @@ fn pattern_matches_file(
 /// in a repo with no commits yet, where all staged entries are brand-new.
 fn unstage_files(repo_root: &std::path::Path, files: &[String]) -> Result<()> {
     // `git restore --staged` requires HEAD; fall back for initial-commit repos.
-    let head_exists = Command::new("git")
-        .current_dir(repo_root)
+    let head_exists = git_command_in(repo_root)?
         .args(["rev-parse", "--verify", "HEAD"])
         .output()
         .is_ok_and(|o| o.status.success());
@@ -104,8 +110,7 @@ fn unstage_files(repo_root: &std::path::Path, files: &[String]) -> Result<()> {
         (&["rm", "--cached", "--"], "git rm --cached")
     };
 
-    let output = Command::new("git")
-        .current_dir(repo_root)
+    let output = git_command_in(repo_root)?
         .args(unstage_args)
         .args(files)
         .output()
@@ -191,49 +196,29 @@ pub fn git_add_with_exclude_patterns(
 ) -> Result<()> {
     tracing::debug!("Adding files...");
 
-    // Get current directory relative to repo root
     let repo_root = get_top_level_path()?;
-    let current_dir_rel_to_repo = {
-        use std::env;
-
-        let current_dir = env::current_dir().map_err(RonaError::Io)?;
+    let current_dir_rel_to_repo = current_dir_in_repo(&repo_root)?;
 
-        // Calculate relative path from repo root to current directory
-        current_dir
-            .strip_prefix(&repo_root)
-            .ok()
-            .and_then(|p| p.to_str())
-            .map(String::from)
-    };
+    let conflicted: Vec<String> = get_conflicted_files()?
+        .into_iter()
+        .filter(|f| {
+            !exclude_patterns
+                .iter()
+                .any(|p| pattern_matches_file(p, f, current_dir_rel_to_repo.as_deref()))
+        })
+        .collect();
+    warn_binary_merges(&conflicted)?;
 
     if dry_run {
-        let deleted_files = process_deleted_files_for_staging()?;
-        let all_files = get_status_files()?;
-        let total_len = all_files.len() + deleted_files.len();
-
-        let files_to_add: Vec<String> = all_files
-            .into_iter()
-            .filter(|f| {
-                !exclude_patterns
-                    .iter()
-                    .any(|p| pattern_matches_file(p, f, current_dir_rel_to_repo.as_deref()))
-            })
-            .collect();
-        let deleted_to_stage: Vec<String> = deleted_files
-            .into_iter()
-            .filter(|f| {
-                !exclude_patterns
-                    .iter()
-                    .any(|p| pattern_matches_file(p, f, current_dir_rel_to_repo.as_deref()))
-            })
-            .collect();
-
-        let excluded_count = total_len - files_to_add.len() - deleted_to_stage.len();
-        print_dry_run_summary(&files_to_add, &deleted_to_stage, excluded_count);
+        let preview = staging_preview(exclude_patterns)?;
+        print_dry_run_summary(&preview.add, &preview.delete, preview.excluded.len());
         return Ok(());
     }
 
-    let show_progress = std::io::stderr().is_terminal() && !verbose;
+    ensure_writable("stage files")?;
+    ensure_allowed(Feature::Stage)?;
+
+    let show_progress = std::io::stderr().is_terminal() && !verbose && !is_accessible();
     let pb = if show_progress {
         let bar = ProgressBar::new_spinner();
         bar.set_draw_target(ProgressDrawTarget::stderr());
@@ -245,21 +230,11 @@ pub fn git_add_with_exclude_patterns(
     };
 
     // Stage everything at once
-    let output = Command::new("git")
-        .current_dir(&repo_root)
-        .args(["add", "-A"])
-        .output()
-        .map_err(RonaError::Io)?;
-
-    if !output.status.success() {
+    if let Err(e) = stage_all(&repo_root) {
         if let Some(bar) = &pb {
             bar.finish_and_clear();
         }
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(RonaError::Git(GitError::CommandFailed {
-            command: "git add -A".to_string(),
-            output: stderr.trim().to_string(),
-        }));
+        return Err(e);
     }
 
     // Unstage files matching exclude patterns
@@ -299,6 +274,136 @@ pub fn git_add_with_exclude_patterns(
     Ok(())
 }
 
+/// Warns about conflicted files that `.gitattributes` marks `merge=binary` (or `-merge`).
+///
+/// Git does not merge such files; it leaves the current side in place. Staging one
+/// marks the conflict resolved, so the user should check it holds the version they want.
+///
+/// # Errors
+/// * If the attributes cannot be read
+fn warn_binary_merges(conflicted: &[String]) -> Result<()> {
+    let merge = attribute_values(conflicted, "merge")?;
+    for file in conflicted {
+        let binary = match merge.get(file) {
+            Some(AttributeValue::Unset) => true,
+            Some(AttributeValue::Value(driver)) => driver == "binary",
+            _ => false,
+        };
+        if binary {
+            println!(
+                "{} {file} is marked merge=binary in .gitattributes: git kept one side without merging. Check it holds the version you want before committing.",
+                "WARNING:".yellow().bold()
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Runs `git add -A`, limited to the changes inside the sparse-checkout cone and the
+/// `--scope` directory if any.
+///
+/// # Errors
+/// * If reading git status or the sparse-checkout cone fails
+/// * If `git add` fails
+fn stage_all(repo_root: &Path) -> Result<()> {
+    let mut add = git_command_in(repo_root)?;
+    add.args(["add", "-A"]);
+    // Git refuses to stage paths outside the cone, so only the changes inside it are named.
+    if sparse_cone()?.is_some() || path_scope().is_some() {
+        let status = StatusSnapshot::read()?;
+        let mut paths = status.status_files();
+        paths.extend(status.unstaged_deletions());
+        if paths.is_empty() {
+            return Ok(());
+        }
+        add.arg("--").args(paths);
+    }
+
+    let output = add.output().map_err(RonaError::Io)?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(RonaError::Git(GitError::CommandFailed {
+            command: "git add -A".to_string(),
+            output: stderr.trim().to_string(),
+        }));
+    }
+    Ok(())
+}
+
+/// The current directory relative to the repository root, `None` outside of it.
+///
+/// Git reports the root with symlinks resolved, so the current directory is resolved
+/// too: entering the repository through a symlink (or macOS's `/tmp`) still places
+/// it inside.
+///
+/// # Errors
+/// * If the current directory cannot be read
+fn current_dir_in_repo(repo_root: &Path) -> Result<Option<String>> {
+    let current_dir = std::env::current_dir().map_err(RonaError::Io)?;
+    let current_dir = current_dir.canonicalize().unwrap_or(current_dir);
+    let repo_root = repo_root
+        .canonicalize()
+        .unwrap_or_else(|_| repo_root.to_path_buf());
+    Ok(current_dir
+        .strip_prefix(&repo_root)
+        .ok()
+        .and_then(|p| p.to_str())
+        .map(String::from))
+}
+
+/// What `rona -a` would stage, as shown by `--dry-run`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct StagingPreview {
+    /// Changed files that would be staged
+    pub add: Vec<String>,
+    /// Deleted files whose deletion would be staged
+    pub delete: Vec<String>,
+    /// Changed files the exclude patterns leave out
+    pub excluded: Vec<String>,
+}
+
+/// Lists what `rona -a` would stage with these exclude patterns, without staging.
+///
+/// # Errors
+/// * If reading git status fails
+/// * If the repository root or the current directory cannot be read
+pub fn staging_preview(exclude_patterns: &[Pattern]) -> Result<StagingPreview> {
+    let status = StatusSnapshot::read()?;
+    let mut preview = StagingPreview::default();
+    for (files, staged) in [
+        (status.status_files(), &mut preview.add),
+        (status.unstaged_deletions(), &mut preview.delete),
+    ] {
+        let excluded = excluded_files_mask(exclude_patterns, &files)?;
+        for (file, excluded) in files.into_iter().zip(excluded) {
+            if excluded {
+                preview.excluded.push(file);
+            } else {
+                staged.push(file);
+            }
+        }
+    }
+    Ok(preview)
+}
+
+/// Which of `files` the exclude patterns match, the way `rona -a` applies them:
+/// against the path from the repository root, from the current directory, or the
+/// file name.
+///
+/// # Errors
+/// * If the repository root or the current directory cannot be read
+pub fn excluded_files_mask(exclude_patterns: &[Pattern], files: &[String]) -> Result<Vec<bool>> {
+    let current_dir = current_dir_in_repo(&get_top_level_path()?)?;
+    Ok(files
+        .iter()
+        .map(|f| {
+            exclude_patterns
+                .iter()
+                .any(|p| pattern_matches_file(p, f, current_dir.as_deref()))
+        })
+        .collect())
+}
+
 /// Stages an explicit list of files via `git add -- <files>`.
 ///
 /// Used by the interactive add mode (`rona -a -i`) after the user has selected
@@ -307,28 +412,38 @@ pub fn git_add_with_exclude_patterns(
 ///
 /// # Arguments
 /// * `files` - Paths (relative to the repository root) to stage
+/// * `excluded_count` - Number of files left out of the selection, for the dry-run summary
 /// * `dry_run` - If true, only print what would be staged without staging anything
 ///
 /// # Errors
 /// * If locating the repository root fails
 /// * If the `git add` command fails
-pub fn git_add_files(files: &[String], dry_run: bool) -> Result<()> {
+pub fn git_add_files(files: &[String], excluded_count: usize, dry_run: bool) -> Result<()> {
     if files.is_empty() {
         println!("No files selected.");
         return Ok(());
     }
 
+    let conflicted: Vec<String> = get_conflicted_files()?
+        .into_iter()
+        .filter(|f| files.contains(f))
+        .collect();
+    warn_binary_merges(&conflicted)?;
+
+    let repo_root = get_top_level_path()?;
     if dry_run {
-        println!("Would stage {} files:", files.len());
-        for file in files {
-            println!("  + {file}");
-        }
+        let (files_to_add, deleted_files): (Vec<String>, Vec<String>) = files
+            .iter()
+            .cloned()
+            .partition(|f| repo_root.join(f).symlink_metadata().is_ok());
+        print_dry_run_summary(&files_to_add, &deleted_files, excluded_count);
         return Ok(());
     }
 
-    let repo_root = get_top_level_path()?;
-    let output = Command::new("git")
-        .current_dir(&repo_root)
+    ensure_writable("stage files")?;
+    ensure_allowed(Feature::Stage)?;
+
+    let output = git_command_in(&repo_root)?
         .args(["add", "--"])
         .args(files)
         .output()
@@ -373,6 +488,8 @@ pub fn git_unstage_files(files: &[String], dry_run: bool) -> Result<()> {
         return Ok(());
     }
 
+    ensure_writable("unstage files")?;
+
     let repo_root = get_top_level_path()?;
     unstage_files(&repo_root, files)?;
 
@@ -407,9 +524,10 @@ pub fn git_restore_files(files: &[String], dry_run: bool) -> Result<()> {
         return Ok(());
     }
 
+    ensure_writable("restore files")?;
+
     let repo_root = get_top_level_path()?;
-    let output = Command::new("git")
-        .current_dir(&repo_root)
+    let output = git_command_in(&repo_root)?
         .args(["restore", "--"])
         .args(files)
         .output()
@@ -448,12 +566,11 @@ pub fn git_restore_files(files: &[String], dry_run: bool) -> Result<()> {
 /// # Arguments
 /// * `files_to_add` - List of files that would be added to the staging area
 /// * `deleted_files` - List of files that would be marked as deleted
-/// * `staged_files_len` - Total number of files that would be staged (including excluded ones)
-/// ```
+/// * `excluded_files_len` - Number of files that would be left unstaged
 fn print_dry_run_summary(
     files_to_add: &[String],
     deleted_files: &[String],
-    staged_files_len: usize,
+    excluded_files_len: usize,
 ) {
     println!("Would add {} files:", files_to_add.len());
     for file in files_to_add {
@@ -465,7 +582,6 @@ fn print_dry_run_summary(
         println!("  - {file}");
     }
 
-    let excluded_files_len = staged_files_len - files_to_add.len();
     println!("Would exclude {excluded_files_len} files");
 }
 
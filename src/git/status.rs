@@ -3,16 +3,28 @@
 //! Git status processing functionality using the git CLI for handling different
 //! file states and contexts.
 
-use std::{collections::HashSet, process::Command};
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use serde::{Deserialize, Serialize};
 
 use crate::errors::{GitError, Result, RonaError};
 
+use super::{
+    remote::UpstreamStatus,
+    scope::{in_scope, path_scope},
+    sparse::sparse_cone,
+};
+
 /// Unquotes a git path.
 ///
 /// When a path contains special characters (spaces, non-ASCII bytes, etc.),
 /// git wraps it in double quotes and uses C-style escape sequences. This
 /// function strips the surrounding quotes and unescapes the content.
-fn unquote_git_path(path: &str) -> String {
+pub(super) fn unquote_git_path(path: &str) -> String {
     if path.starts_with('"') && path.ends_with('"') && path.len() >= 2 {
         let inner = &path[1..path.len() - 1];
         // Collect raw bytes so that multi-byte UTF-8 octal sequences (e.g. \303\242 -> â)
@@ -65,13 +77,69 @@ fn unquote_git_path(path: &str) -> String {
 /// working-tree status. For renamed files, the path may include ` -> ` separating
 /// the old and new names.
 ///
+/// In a cone-mode sparse checkout, entries outside the cone are left out (see
+/// [`get_out_of_cone_files`]), and so are entries outside the `--scope` directory.
+///
 /// # Errors
 /// * If the git command fails or we are not in a git repository
 fn run_git_status() -> Result<Vec<String>> {
-    let output = Command::new("git")
-        .args(["status", "--porcelain=v1"])
-        .output()
-        .map_err(RonaError::Io)?;
+    let lines: Vec<String> = run_full_git_status()?
+        .into_iter()
+        .filter(|line| line.len() < 4 || in_scope(&status_line_path(line)))
+        .collect();
+    Ok(match sparse_cone()? {
+        Some(cone) => lines
+            .into_iter()
+            .filter(|line| line.len() < 4 || cone.contains(&status_line_path(line)))
+            .collect(),
+        None => lines,
+    })
+}
+
+/// Returns the changed paths outside the sparse-checkout cone, sorted.
+///
+/// Rona's status, staging and message generation ignore these, so commands list them
+/// to make clear they were left out. Empty when the repository is not a cone-mode
+/// sparse checkout.
+///
+/// # Errors
+/// * If reading git status or the sparse-checkout cone fails
+pub fn get_out_of_cone_files() -> Result<Vec<String>> {
+    let Some(cone) = sparse_cone()? else {
+        return Ok(Vec::new());
+    };
+    let mut files: Vec<String> = run_full_git_status()?
+        .iter()
+        .filter(|line| line.len() >= 4)
+        .map(|line| status_line_path(line))
+        .filter(|path| !cone.contains(path))
+        .collect();
+    files.sort();
+    files.dedup();
+    Ok(files)
+}
+
+/// The path of a porcelain status line, the new path for renames.
+fn status_line_path(line: &str) -> String {
+    let raw_path = &line[3..];
+    unquote_git_path(raw_path.rsplit(" -> ").next().unwrap_or(raw_path))
+}
+
+/// Runs `git status --porcelain=v1` without narrowing it to the sparse-checkout cone.
+///
+/// Paths are relative to the repository root wherever rona is run from. With
+/// `--scope`, git is given the scope directory as pathspec, from the root, so an
+/// untracked directory above it is not collapsed into one entry.
+///
+/// # Errors
+/// * If the git command fails or we are not in a git repository
+fn run_full_git_status() -> Result<Vec<String>> {
+    let mut status = Command::new("git");
+    status.args(["status", "--porcelain=v1"]);
+    if let Some(scope) = path_scope() {
+        status.args(["--", &format!(":(top){}", scope.dir)]);
+    }
+    let output = status.output().map_err(RonaError::Io)?;
 
     if output.status.success() {
         let stdout = String::from_utf8_lossy(&output.stdout);
@@ -92,13 +160,23 @@ fn run_git_status() -> Result<Vec<String>> {
 /// Returns the new paths of all staged renamed files.
 ///
 /// Uses `git diff --cached --name-status --diff-filter=R` which outputs lines like:
-/// `R100\told_name\tnew_name`
+/// `R100\told_name\tnew_name`, relative to the repository root even when
+/// `diff.relative` is set.
+///
+/// Like [`run_git_status`], this stays inside the sparse-checkout cone and the
+/// `--scope` directory.
 ///
 /// # Errors
 /// * If the git command fails
 fn get_renamed_new_paths() -> Result<Vec<String>> {
     let output = Command::new("git")
-        .args(["diff", "--cached", "--name-status", "--diff-filter=R"])
+        .args([
+            "diff",
+            "--cached",
+            "--no-relative",
+            "--name-status",
+            "--diff-filter=R",
+        ])
         .output()
         .map_err(RonaError::Io)?;
 
@@ -107,7 +185,7 @@ fn get_renamed_new_paths() -> Result<Vec<String>> {
     }
 
     let stdout = String::from_utf8_lossy(&output.stdout);
-    let paths = stdout
+    let paths: Vec<String> = stdout
         .lines()
         .filter_map(|line| {
             let parts: Vec<&str> = line.splitn(3, '\t').collect();
@@ -117,9 +195,68 @@ fn get_renamed_new_paths() -> Result<Vec<String>> {
                 None
             }
         })
+        .filter(|path| in_scope(path))
         .collect();
 
-    Ok(paths)
+    Ok(match sparse_cone()? {
+        Some(cone) => paths.into_iter().filter(|p| cone.contains(p)).collect(),
+        None => paths,
+    })
+}
+
+/// The output of one `git status` walk, for commands needing several lists from it.
+///
+/// Staging and message generation each read two lists from `git status`; taking both
+/// from one snapshot runs the walk once instead of once per list. A snapshot does not
+/// follow later changes to the index.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StatusSnapshot {
+    lines: Vec<String>,
+    renamed: Vec<String>,
+}
+
+impl StatusSnapshot {
+    /// Runs `git status` and lists the new paths of the staged renames.
+    ///
+    /// # Errors
+    /// * If reading git status fails
+    pub fn read() -> Result<Self> {
+        Ok(Self {
+            lines: run_git_status()?,
+            renamed: get_renamed_new_paths()?,
+        })
+    }
+
+    /// The files [`get_status_files`] returns.
+    #[must_use]
+    pub fn status_files(&self) -> Vec<String> {
+        status_files_in(&self.lines, &self.renamed)
+    }
+
+    /// The file states [`get_file_states`] returns.
+    #[must_use]
+    pub fn file_states(&self) -> Vec<(FileState, String)> {
+        file_states_in(&self.lines)
+    }
+
+    /// Files deleted in the working directory but not yet staged for deletion.
+    #[must_use]
+    pub fn unstaged_deletions(&self) -> Vec<String> {
+        unstaged_deletions_in(&self.lines)
+    }
+
+    /// Files modified, added, renamed or type-changed in the index, for the commit
+    /// message. Renames are listed by their new path.
+    #[must_use]
+    pub fn staged_changes(&self) -> Vec<String> {
+        staged_changes_in(&self.lines, &self.renamed)
+    }
+
+    /// Files staged for deletion, for the commit message.
+    #[must_use]
+    pub fn staged_deletions(&self) -> Vec<String> {
+        staged_deletions_in(&self.lines)
+    }
 }
 
 /// Returns a list of all files that appear in git status
@@ -131,10 +268,17 @@ fn get_renamed_new_paths() -> Result<Vec<String>> {
 /// # Returns
 /// * `Vec<String>` - List of files from git status
 pub fn get_status_files() -> Result<Vec<String>> {
-    let lines = run_git_status()?;
+    Ok(status_files_in(
+        &run_git_status()?,
+        &get_renamed_new_paths()?,
+    ))
+}
+
+/// The files of [`get_status_files`], from `git status` lines and the staged renames.
+fn status_files_in(lines: &[String], renamed: &[String]) -> Vec<String> {
     let mut files: HashSet<String> = HashSet::new();
 
-    for line in &lines {
+    for line in lines {
         if line.len() < 4 {
             continue;
         }
@@ -163,11 +307,9 @@ pub fn get_status_files() -> Result<Vec<String>> {
     }
 
     // Add new paths for renamed files
-    for path in get_renamed_new_paths()? {
-        files.insert(path);
-    }
+    files.extend(renamed.iter().cloned());
 
-    Ok(files.into_iter().collect())
+    files.into_iter().collect()
 }
 
 /// A single entry from `git status` that has unstaged changes and can be staged.
@@ -175,7 +317,7 @@ pub fn get_status_files() -> Result<Vec<String>> {
 /// Used by the interactive add mode (`rona -a -i`) to present a `MultiSelect` of
 /// changed files. The [`Display`] implementation renders a human-readable status
 /// label followed by the path, e.g. `modified    src/main.rs`.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub struct StatusEntry {
     /// Path to the file, relative to the repository root.
     pub path: String,
@@ -341,19 +483,207 @@ pub fn get_restorable_files() -> Result<Vec<StatusEntry>> {
     Ok(entries)
 }
 
-/// Processes deleted files that need to be staged for deletion.
-/// Only returns files that are deleted in the working directory but not yet staged.
+/// The state of a changed file, as `rona explain-status` groups them. A file can be in
+/// two states at once, e.g. staged and modified again since.
+///
+/// Variants are ordered as they are listed: conflicts first, then the staging area,
+/// then the working tree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FileState {
+    /// Changed on both sides of a merge, rebase or cherry-pick
+    Conflicted,
+    /// New file (or copy) in the index
+    StagedNew,
+    /// Modified or type-changed in the index
+    StagedModified,
+    /// Deletion in the index
+    StagedDeleted,
+    /// Rename in the index
+    StagedRenamed,
+    /// Modified or type-changed in the working tree, not staged
+    Modified,
+    /// Deleted from the working tree, not staged
+    Deleted,
+    /// Not tracked by git
+    Untracked,
+}
+
+impl FileState {
+    /// Short label, as `git status` words it.
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Conflicted => "conflicted",
+            Self::StagedNew => "new file",
+            Self::StagedModified | Self::Modified => "modified",
+            Self::StagedDeleted | Self::Deleted => "deleted",
+            Self::StagedRenamed => "renamed",
+            Self::Untracked => "untracked",
+        }
+    }
+
+    /// Whether the state is a change in the index, part of the next commit.
+    #[must_use]
+    pub const fn is_staged(self) -> bool {
+        matches!(
+            self,
+            Self::StagedNew | Self::StagedModified | Self::StagedDeleted | Self::StagedRenamed
+        )
+    }
+}
+
+/// The states of a `git status --porcelain=v1` line, from its two status letters.
+fn line_states(index_char: char, wt_char: char) -> Vec<FileState> {
+    let code = format!("{index_char}{wt_char}");
+    if matches!(
+        code.as_str(),
+        "DD" | "AU" | "UD" | "UA" | "DU" | "AA" | "UU"
+    ) {
+        return vec![FileState::Conflicted];
+    }
+    if code == "??" {
+        return vec![FileState::Untracked];
+    }
+
+    let staged = match index_char {
+        'A' | 'C' => Some(FileState::StagedNew),
+        'M' | 'T' => Some(FileState::StagedModified),
+        'D' => Some(FileState::StagedDeleted),
+        'R' => Some(FileState::StagedRenamed),
+        _ => None,
+    };
+    let unstaged = match wt_char {
+        'M' | 'T' => Some(FileState::Modified),
+        'D' => Some(FileState::Deleted),
+        _ => None,
+    };
+    staged.into_iter().chain(unstaged).collect()
+}
+
+/// Returns every changed file with its states, sorted by state then path.
+///
+/// Staged renames are shown as `old -> new`; every other state uses the current path.
+/// Ignored files are left out.
 ///
 /// # Errors
 /// * If reading git status fails
-///
-/// # Returns
-/// * `Result<Vec<String>>` - Files that need to be staged for deletion
-pub fn process_deleted_files_for_staging() -> Result<Vec<String>> {
-    let lines = run_git_status()?;
+pub fn get_file_states() -> Result<Vec<(FileState, String)>> {
+    Ok(file_states_in(&run_git_status()?))
+}
+
+/// The states of [`get_file_states`], from `git status` lines.
+fn file_states_in(lines: &[String]) -> Vec<(FileState, String)> {
+    let mut states = Vec::new();
+    for line in lines {
+        if line.len() < 4 {
+            continue;
+        }
+
+        let mut chars = line.chars();
+        let index_char = chars.next().unwrap_or(' ');
+        let wt_char = chars.next().unwrap_or(' ');
+        let raw_path = &line[3..];
+        let path = status_line_path(line);
+
+        for state in line_states(index_char, wt_char) {
+            let shown = match (state, raw_path.split_once(" -> ")) {
+                (FileState::StagedRenamed, Some((old, _))) => {
+                    format!("{} -> {path}", unquote_git_path(old))
+                }
+                _ => path.clone(),
+            };
+            states.push((state, shown));
+        }
+    }
+
+    states.sort();
+    states
+}
+
+/// A changed file in one of its states, as `rona status --output json` lists it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FileStatus {
+    /// The path, `old -> new` for a staged rename
+    pub path: String,
+    /// The state, e.g. `staged_new` or `untracked`
+    pub state: FileState,
+}
+
+/// How many changed files `rona status` lists in each group. A file staged and
+/// modified again since counts in both groups.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct StatusCounts {
+    pub staged: usize,
+    pub modified: usize,
+    pub deleted: usize,
+    pub untracked: usize,
+    pub conflicted: usize,
+}
+
+/// The branch, its upstream and the changed files, for `rona status --output json`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct StatusReport {
+    /// The current branch, `None` when `HEAD` is detached
+    pub branch: Option<String>,
+    /// The commit `HEAD` points to, `None` in a repository without commits
+    pub head: Option<String>,
+    /// The upstream of the branch, e.g. `origin/main`
+    pub upstream: Option<String>,
+    /// Commits on `HEAD` the upstream does not have
+    pub ahead: Option<usize>,
+    /// Commits on the upstream `HEAD` does not have
+    pub behind: Option<usize>,
+    /// The changed files, as sorted by [`get_file_states`]
+    pub files: Vec<FileStatus>,
+    pub counts: StatusCounts,
+}
+
+impl StatusReport {
+    /// Builds the report from the file states returned by [`get_file_states`].
+    #[must_use]
+    pub fn new(
+        branch: Option<String>,
+        head: Option<String>,
+        upstream: Option<UpstreamStatus>,
+        states: Vec<(FileState, String)>,
+    ) -> Self {
+        let mut counts = StatusCounts::default();
+        for (state, _) in &states {
+            let count = match state {
+                FileState::Conflicted => &mut counts.conflicted,
+                FileState::Modified => &mut counts.modified,
+                FileState::Deleted => &mut counts.deleted,
+                FileState::Untracked => &mut counts.untracked,
+                FileState::StagedNew
+                | FileState::StagedModified
+                | FileState::StagedDeleted
+                | FileState::StagedRenamed => &mut counts.staged,
+            };
+            *count += 1;
+        }
+
+        Self {
+            branch,
+            head,
+            ahead: upstream.as_ref().map(|u| u.ahead),
+            behind: upstream.as_ref().map(|u| u.behind),
+            upstream: upstream.map(|u| u.upstream),
+            files: states
+                .into_iter()
+                .map(|(state, path)| FileStatus { path, state })
+                .collect(),
+            counts,
+        }
+    }
+}
+
+/// Files deleted in the working directory but not yet staged for deletion, from
+/// `git status` lines.
+fn unstaged_deletions_in(lines: &[String]) -> Vec<String> {
     let mut deleted_files = Vec::new();
 
-    for line in &lines {
+    for line in lines {
         if line.len() < 4 {
             continue;
         }
@@ -369,22 +699,14 @@ pub fn process_deleted_files_for_staging() -> Result<Vec<String>> {
         }
     }
 
-    Ok(deleted_files)
+    deleted_files
 }
 
-/// Processes deleted files for commit message generation.
-/// Returns all deleted files that are staged for deletion.
-///
-/// # Errors
-/// * If reading git status fails
-///
-/// # Returns
-/// * `Result<Vec<String>>` - All deleted files for the commit message
-pub fn process_deleted_files_for_commit_message() -> Result<Vec<String>> {
-    let lines = run_git_status()?;
+/// Files staged for deletion, from `git status` lines.
+fn staged_deletions_in(lines: &[String]) -> Vec<String> {
     let mut deleted_files = Vec::new();
 
-    for line in &lines {
+    for line in lines {
         if line.len() < 4 {
             continue;
         }
@@ -398,23 +720,15 @@ pub fn process_deleted_files_for_commit_message() -> Result<Vec<String>> {
         }
     }
 
-    Ok(deleted_files)
+    deleted_files
 }
 
-/// Processes the git status.
-/// Returns the modified/added/renamed/type-changed files in the index,
-/// to prepare the git commit message.
-///
-/// # Errors
-/// * If reading git status fails
-///
-/// # Returns
-/// * `Result<Vec<String>>` - The modified/added files
-pub fn process_git_status() -> Result<Vec<String>> {
-    let lines = run_git_status()?;
+/// Files modified, added, renamed or type-changed in the index, from `git status`
+/// lines and the staged renames.
+fn staged_changes_in(lines: &[String], renamed: &[String]) -> Vec<String> {
     let mut files = Vec::new();
 
-    for line in &lines {
+    for line in lines {
         if line.len() < 4 {
             continue;
         }
@@ -429,9 +743,9 @@ pub fn process_git_status() -> Result<Vec<String>> {
     }
 
     // Add new paths for renamed files
-    files.extend(get_renamed_new_paths()?);
+    files.extend(renamed.iter().cloned());
 
-    Ok(files)
+    files
 }
 
 /// Returns all file paths currently staged in the index.
@@ -478,6 +792,40 @@ pub fn get_all_staged_file_paths() -> Result<Vec<String>> {
     Ok(files.into_iter().collect())
 }
 
+/// The deepest directory containing every staged path, relative to the repository root.
+/// Empty when a staged file sits at the root or nothing is staged.
+#[must_use]
+pub fn common_staged_dir(staged: &[String]) -> PathBuf {
+    let mut dirs = staged
+        .iter()
+        .map(|file| Path::new(file).parent().unwrap_or_else(|| Path::new("")));
+    let Some(first) = dirs.next() else {
+        return PathBuf::new();
+    };
+    dirs.fold(first.to_path_buf(), |common, dir| {
+        common
+            .components()
+            .zip(dir.components())
+            .take_while(|(a, b)| a == b)
+            .map(|(a, _)| a)
+            .collect()
+    })
+}
+
+/// Returns the files with unresolved merge conflicts (`UU`, `AA`, `DU`, ...).
+///
+/// # Errors
+/// * If reading git status fails
+pub fn get_conflicted_files() -> Result<Vec<String>> {
+    let lines = run_git_status()?;
+    Ok(lines
+        .iter()
+        .filter(|line| line.len() >= 4)
+        .filter(|line| matches!(&line[..2], "DD" | "AU" | "UD" | "UA" | "DU" | "AA" | "UU"))
+        .map(|line| status_line_path(line))
+        .collect())
+}
+
 /// Counts the number of renamed files in the git status.
 ///
 /// This function helps with accurate file counting since renamed files appear
@@ -499,7 +847,65 @@ pub fn count_renamed_files() -> Result<usize> {
 
 #[cfg(test)]
 mod tests {
-    use super::unquote_git_path;
+    use std::path::Path;
+
+    use super::{FileState, StatusSnapshot, common_staged_dir, line_states, unquote_git_path};
+
+    #[test]
+    fn test_common_staged_dir() {
+        let staged = |files: &[&str]| files.iter().map(ToString::to_string).collect::<Vec<_>>();
+
+        assert_eq!(
+            common_staged_dir(&staged(&["packages/api/src/a.rs", "packages/api/b.rs"])),
+            Path::new("packages/api")
+        );
+        assert_eq!(
+            common_staged_dir(&staged(&["packages/api/a.rs", "packages/web/b.ts"])),
+            Path::new("packages")
+        );
+        assert_eq!(
+            common_staged_dir(&staged(&["packages/api/a.rs", "README.md"])),
+            Path::new("")
+        );
+        assert_eq!(common_staged_dir(&[]), Path::new(""));
+    }
+
+    #[test]
+    fn test_status_snapshot_lists() {
+        let status = StatusSnapshot {
+            lines: [
+                "M  src/a.rs",
+                " D old.rs",
+                "D  gone.rs",
+                "R  b.rs -> c.rs",
+                "?? new.rs",
+            ]
+            .map(String::from)
+            .to_vec(),
+            renamed: vec!["c.rs".to_string()],
+        };
+        let mut files = status.status_files();
+        files.sort();
+        assert_eq!(files, ["c.rs", "new.rs", "src/a.rs"]);
+        assert_eq!(status.unstaged_deletions(), ["old.rs"]);
+        assert_eq!(status.staged_changes(), ["src/a.rs", "c.rs"]);
+        assert_eq!(status.staged_deletions(), ["gone.rs"]);
+    }
+
+    #[test]
+    fn test_line_states() {
+        assert_eq!(line_states('?', '?'), [FileState::Untracked]);
+        assert_eq!(line_states('U', 'U'), [FileState::Conflicted]);
+        assert_eq!(line_states('A', 'A'), [FileState::Conflicted]);
+        assert_eq!(line_states('A', ' '), [FileState::StagedNew]);
+        assert_eq!(
+            line_states('R', 'M'),
+            [FileState::StagedRenamed, FileState::Modified]
+        );
+        assert_eq!(line_states(' ', 'D'), [FileState::Deleted]);
+        assert_eq!(line_states('T', ' '), [FileState::StagedModified]);
+        assert!(line_states('!', '!').is_empty());
+    }
 
     #[test]
     fn test_unquote_plain_path() {
@@ -0,0 +1,173 @@
+//! Staged TODO Markers
+//!
+//! Finds `TODO`, `FIXME` and `HACK` markers on the lines the staged changes add, for
+//! `rona todo` and the `[todo]` commit policy. Only added lines count, so markers
+//! already in the code base do not show up again on every commit.
+
+use std::process::Command;
+
+use regex::Regex;
+
+use crate::errors::{GitError, Result, RonaError};
+
+/// Markers searched for when `[todo]` sets none.
+pub const DEFAULT_TODO_MARKERS: [&str; 3] = ["TODO", "FIXME", "HACK"];
+
+/// The heading of the section `on_commit = "append"` adds to the commit body.
+pub const TODO_SECTION_HEADING: &str = "TODOs:";
+
+/// A marker on a line added by the staged changes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TodoMarker {
+    /// Repository-relative path
+    pub file: String,
+    /// Line number in the staged version of the file
+    pub line: usize,
+    /// The added line, trimmed
+    pub text: String,
+}
+
+/// Lists the markers on lines added by the staged changes.
+///
+/// Markers match as whole words and case-sensitively, so `todos` or `Todo` in prose
+/// are not reported.
+///
+/// # Errors
+/// * If a marker makes an invalid pattern
+/// * If `git diff --cached` fails
+pub fn staged_todos(markers: &[String]) -> Result<Vec<TodoMarker>> {
+    if markers.is_empty() {
+        return Ok(Vec::new());
+    }
+    let alternatives: Vec<String> = markers.iter().map(|m| regex::escape(m)).collect();
+    let pattern = Regex::new(&format!(r"\b({})\b", alternatives.join("|")))
+        .map_err(|e| RonaError::InvalidInput(format!("todo.markers: {e}")))?;
+
+    let output = Command::new("git")
+        .args([
+            "diff",
+            "--cached",
+            "--unified=0",
+            "--no-color",
+            "--no-ext-diff",
+        ])
+        .output()
+        .map_err(RonaError::Io)?;
+    if !output.status.success() {
+        return Err(RonaError::Git(GitError::CommandFailed {
+            command: "git diff --cached".to_string(),
+            output: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        }));
+    }
+    Ok(parse_added_todos(
+        &String::from_utf8_lossy(&output.stdout),
+        &pattern,
+    ))
+}
+
+/// Renders the markers as the `TODOs:` section appended to a commit body.
+#[must_use]
+pub fn todo_section(todos: &[TodoMarker]) -> String {
+    let lines: Vec<String> = todos
+        .iter()
+        .map(|todo| format!("- {}:{}: {}", todo.file, todo.line, todo.text))
+        .collect();
+    format!("{TODO_SECTION_HEADING}\n{}", lines.join("\n"))
+}
+
+/// Walks a zero-context diff, tracking the new-file line number of each added line.
+fn parse_added_todos(diff: &str, pattern: &Regex) -> Vec<TodoMarker> {
+    let mut todos = Vec::new();
+    let mut file: Option<String> = None;
+    let mut line = 0;
+    // File headers run from `diff --git` to the first hunk; after that, a line
+    // starting with `+++` is added content.
+    let mut in_header = false;
+
+    for diff_line in diff.lines() {
+        if diff_line.starts_with("diff --git ") {
+            in_header = true;
+        } else if in_header {
+            if let Some(path) = diff_line.strip_prefix("+++ ") {
+                // `/dev/null` for deletions; otherwise `b/<path>`
+                file = path.strip_prefix("b/").map(str::to_string);
+            } else if diff_line.starts_with("@@") {
+                in_header = false;
+                line = hunk_new_start(diff_line).unwrap_or(0);
+            }
+        } else if diff_line.starts_with("@@") {
+            line = hunk_new_start(diff_line).unwrap_or(0);
+        } else if let Some(added) = diff_line.strip_prefix('+') {
+            if let Some(file) = file.as_ref().filter(|_| pattern.is_match(added)) {
+                todos.push(TodoMarker {
+                    file: file.clone(),
+                    line,
+                    text: added.trim().to_string(),
+                });
+            }
+            line += 1;
+        }
+    }
+    todos
+}
+
+/// The first new-file line of a hunk header such as `@@ -3,0 +4,2 @@`.
+fn hunk_new_start(header: &str) -> Option<usize> {
+    let new_range = header
+        .split_whitespace()
+        .find(|part| part.starts_with('+'))?;
+    new_range[1..].split(',').next()?.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type TestResult = std::result::Result<(), Box<dyn std::error::Error>>;
+
+    #[test]
+    fn test_parse_added_todos() -> TestResult {
+        let diff = "\
+diff --git a/src/lib.rs b/src/lib.rs
+index 1111111..2222222 100644
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -3,0 +4,3 @@ fn main() {
++    // TODO: handle errors
++    let todos = vec![];
++    run(); // FIXME
+@@ -20 +23 @@ fn run() {
+-    // HACK: removed, not reported
++    // Todo is not a marker, nor is TODOS
+diff --git a/old.rs b/old.rs
+deleted file mode 100644
+--- a/old.rs
++++ /dev/null
+@@ -1 +0,0 @@
+-// TODO gone
+";
+        let pattern = Regex::new(r"\b(TODO|FIXME|HACK)\b")?;
+        let todos = parse_added_todos(diff, &pattern);
+
+        assert_eq!(
+            todos,
+            vec![
+                TodoMarker {
+                    file: "src/lib.rs".to_string(),
+                    line: 4,
+                    text: "// TODO: handle errors".to_string(),
+                },
+                TodoMarker {
+                    file: "src/lib.rs".to_string(),
+                    line: 6,
+                    text: "run(); // FIXME".to_string(),
+                },
+            ]
+        );
+        assert_eq!(
+            todo_section(&todos),
+            "TODOs:\n- src/lib.rs:4: // TODO: handle errors\n- src/lib.rs:6: run(); // FIXME"
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,428 @@
+//! Usage Report
+//!
+//! `rona usage` summarizes how rona is used in the current repository, from the
+//! operation log alone: how often each command runs, how large the commits are, and
+//! how long a message sits in `commit_message.md` before it is committed. The report
+//! is computed locally and nothing is sent anywhere.
+//!
+//! `rona stats` lists the same data per commit, and exports it for dashboards in the
+//! Prometheus text format or as CSV.
+
+use std::{collections::BTreeMap, fmt::Write, process::Command};
+
+use chrono::DateTime;
+use serde::Serialize;
+
+use crate::utils::ratio;
+
+use super::oplog::Operation;
+
+/// Lines and files changed by one commit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CommitSize {
+    /// Files touched by the commit
+    pub files: usize,
+    /// Lines added plus lines removed (binary files count as zero)
+    pub lines: usize,
+}
+
+/// Summary of the operation log.
+#[derive(Debug, Default, Clone, PartialEq, Serialize)]
+pub struct UsageReport {
+    /// Number of recorded operations
+    pub operations: usize,
+    /// Time of the oldest operation, in RFC 3339
+    pub first: Option<String>,
+    /// Time of the newest operation, in RFC 3339
+    pub last: Option<String>,
+    /// How often each command ran
+    pub commands: BTreeMap<String, usize>,
+    /// Commits whose size could be measured (amends are left out)
+    pub measured_commits: usize,
+    /// Average number of files per measured commit
+    pub average_commit_files: Option<f64>,
+    /// Average number of changed lines per measured commit
+    pub average_commit_lines: Option<f64>,
+    /// Commits that followed a `rona generate`
+    pub generated_commits: usize,
+    /// Median seconds between `rona generate` and the commit that used the message
+    pub median_generate_to_commit_secs: Option<i64>,
+    /// Average seconds between `rona generate` and the commit that used the message
+    pub average_generate_to_commit_secs: Option<i64>,
+}
+
+/// One `rona commit` from the operation log.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommitRecord {
+    /// Local time of the commit, in RFC 3339
+    pub time: String,
+    /// The commit made, `None` when `HEAD` could not be read
+    pub commit: Option<String>,
+    /// The branch committed to, `None` when detached
+    pub branch: Option<String>,
+    /// Whether the commit amended the previous one
+    pub amend: bool,
+    /// Size of the commit, `None` for amends and commits that cannot be measured
+    pub size: Option<CommitSize>,
+    /// Seconds since the `rona generate` that wrote its message, `None` without one
+    pub lead_time_secs: Option<i64>,
+}
+
+/// The commits of the operation log, oldest first.
+///
+/// `commit_size` measures a commit from the `HEAD` before and after it; commits it
+/// cannot measure (e.g. collected by `git gc` after an amend) have no size. Each commit
+/// is paired with the latest `generate` since the previous commit, if any.
+pub fn commit_records(
+    operations: &[Operation],
+    commit_size: impl Fn(&str, &str) -> Option<CommitSize>,
+) -> Vec<CommitRecord> {
+    let mut records = Vec::new();
+    let mut generated = None;
+    for operation in operations {
+        match operation.command.as_str() {
+            "generate" => generated = DateTime::parse_from_rfc3339(&operation.time).ok(),
+            "commit" => {
+                let lead_time_secs = generated.take().and_then(|generated| {
+                    DateTime::parse_from_rfc3339(&operation.time)
+                        .ok()
+                        .map(|committed| (committed - generated).num_seconds().max(0))
+                });
+                let amend = operation.args.iter().any(|arg| arg == "--amend");
+                let size = match (&operation.head_before, &operation.head_after) {
+                    (Some(before), Some(after)) if !amend && before != after => {
+                        commit_size(before, after)
+                    }
+                    _ => None,
+                };
+                records.push(CommitRecord {
+                    time: operation.time.clone(),
+                    commit: operation.head_after.clone(),
+                    branch: operation.branch.clone(),
+                    amend,
+                    size,
+                    lead_time_secs,
+                });
+            }
+            _ => {}
+        }
+    }
+    records
+}
+
+/// Summarizes the operations, oldest first, as read by `read_operations`.
+///
+/// Commits are measured and paired with their `generate` as in [`commit_records`].
+pub fn usage_report(
+    operations: &[Operation],
+    commit_size: impl Fn(&str, &str) -> Option<CommitSize>,
+) -> UsageReport {
+    let mut report = UsageReport {
+        operations: operations.len(),
+        first: operations.first().map(|op| op.time.clone()),
+        last: operations.last().map(|op| op.time.clone()),
+        ..UsageReport::default()
+    };
+    for operation in operations {
+        *report
+            .commands
+            .entry(operation.command.clone())
+            .or_default() += 1;
+    }
+
+    let records = commit_records(operations, commit_size);
+    let sizes: Vec<CommitSize> = records.iter().filter_map(|r| r.size).collect();
+    let mut waits: Vec<i64> = records.iter().filter_map(|r| r.lead_time_secs).collect();
+
+    if !sizes.is_empty() {
+        report.measured_commits = sizes.len();
+        report.average_commit_files = Some(ratio(
+            sizes.iter().map(|s| s.files).sum::<usize>(),
+            sizes.len(),
+        ));
+        report.average_commit_lines = Some(ratio(
+            sizes.iter().map(|s| s.lines).sum::<usize>(),
+            sizes.len(),
+        ));
+    }
+
+    if !waits.is_empty() {
+        waits.sort_unstable();
+        report.generated_commits = waits.len();
+        report.median_generate_to_commit_secs = Some(waits[waits.len() / 2]);
+        report.average_generate_to_commit_secs =
+            Some(waits.iter().sum::<i64>() / i64::try_from(waits.len()).unwrap_or(i64::MAX));
+    }
+
+    report
+}
+
+/// The operation counts and commits in the Prometheus text exposition format,
+/// labelled with the repository name.
+///
+/// Counts are exported as counters, commit sizes and lead times as summaries with
+/// their median.
+#[must_use]
+pub fn prometheus_metrics(
+    operations: &[Operation],
+    records: &[CommitRecord],
+    repository: &str,
+) -> String {
+    let mut commands: BTreeMap<&str, usize> = BTreeMap::new();
+    for operation in operations {
+        *commands.entry(operation.command.as_str()).or_default() += 1;
+    }
+
+    let repository = repository.replace('\\', "\\\\").replace('"', "\\\"");
+    let label = format!("repository=\"{repository}\"");
+    let mut out = String::new();
+
+    write_help(
+        &mut out,
+        "rona_operations_total",
+        "counter",
+        "Operations recorded in the rona operation log, by command.",
+    );
+    for (command, count) in commands {
+        let _ = writeln!(
+            out,
+            "rona_operations_total{{{label},command=\"{command}\"}} {count}"
+        );
+    }
+
+    write_help(
+        &mut out,
+        "rona_commits_total",
+        "counter",
+        "Commits made with rona, amends included.",
+    );
+    let _ = writeln!(out, "rona_commits_total{{{label}}} {}", records.len());
+
+    let sizes: Vec<CommitSize> = records.iter().filter_map(|r| r.size).collect();
+    let files: Vec<i64> = sizes.iter().map(|s| to_i64(s.files)).collect();
+    let lines: Vec<i64> = sizes.iter().map(|s| to_i64(s.lines)).collect();
+    let lead_times: Vec<i64> = records.iter().filter_map(|r| r.lead_time_secs).collect();
+    for (name, help, values) in [
+        (
+            "rona_commit_files",
+            "Files changed per commit, amends left out.",
+            files,
+        ),
+        (
+            "rona_commit_lines",
+            "Lines added plus removed per commit, amends left out.",
+            lines,
+        ),
+        (
+            "rona_generate_to_commit_seconds",
+            "Seconds between rona generate and the commit using its message.",
+            lead_times,
+        ),
+    ] {
+        write_summary(&mut out, name, help, &label, values);
+    }
+    out
+}
+
+/// Writes the `# HELP` and `# TYPE` lines of a Prometheus metric.
+fn write_help(out: &mut String, name: &str, kind: &str, help: &str) {
+    let _ = writeln!(out, "# HELP {name} {help}");
+    let _ = writeln!(out, "# TYPE {name} {kind}");
+}
+
+/// Writes one Prometheus summary: the median, sum and count of `values`.
+fn write_summary(out: &mut String, name: &str, help: &str, label: &str, mut values: Vec<i64>) {
+    values.sort_unstable();
+    write_help(out, name, "summary", help);
+    if let Some(median) = values.get(values.len() / 2) {
+        let _ = writeln!(out, "{name}{{{label},quantile=\"0.5\"}} {median}");
+    }
+    let _ = writeln!(out, "{name}_sum{{{label}}} {}", values.iter().sum::<i64>());
+    let _ = writeln!(out, "{name}_count{{{label}}} {}", values.len());
+}
+
+/// A count as `i64`, saturating.
+fn to_i64(value: usize) -> i64 {
+    i64::try_from(value).unwrap_or(i64::MAX)
+}
+
+/// The commits as CSV, one row per commit with a header row. Empty cells mean the
+/// value is unknown.
+#[must_use]
+pub fn commits_csv(records: &[CommitRecord]) -> String {
+    let mut out = String::from("time,commit,branch,amend,files,lines,lead_time_secs\n");
+    let cell = |value: Option<String>| value.map(|v| csv_field(&v)).unwrap_or_default();
+    for record in records {
+        let _ = writeln!(
+            out,
+            "{},{},{},{},{},{},{}",
+            csv_field(&record.time),
+            cell(record.commit.clone()),
+            cell(record.branch.clone()),
+            record.amend,
+            cell(record.size.map(|s| s.files.to_string())),
+            cell(record.size.map(|s| s.lines.to_string())),
+            cell(record.lead_time_secs.map(|s| s.to_string())),
+        );
+    }
+    out
+}
+
+/// Quotes a CSV field when it contains a comma, a quote or a line break.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Measures the changes between two commits with `git diff --numstat`.
+///
+/// Returns `None` when git cannot diff them, e.g. because one was garbage collected.
+#[must_use]
+pub fn commit_size(before: &str, after: &str) -> Option<CommitSize> {
+    let output = Command::new("git")
+        .args(["diff", "--numstat", before, after])
+        .output()
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| parse_numstat(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Sums `git diff --numstat` output. Binary files show `-` and count as zero lines.
+fn parse_numstat(output: &str) -> CommitSize {
+    let mut size = CommitSize { files: 0, lines: 0 };
+    for line in output.lines().filter(|l| !l.trim().is_empty()) {
+        let mut fields = line.split('\t');
+        let added = fields.next().and_then(|f| f.parse::<usize>().ok());
+        let removed = fields.next().and_then(|f| f.parse::<usize>().ok());
+        size.files += 1;
+        size.lines += added.unwrap_or(0) + removed.unwrap_or(0);
+    }
+    size
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn operation(time: &str, command: &str, heads: (&str, &str), args: &[&str]) -> Operation {
+        Operation {
+            time: time.to_string(),
+            command: command.to_string(),
+            args: args.iter().map(ToString::to_string).collect(),
+            branch: Some("main".to_string()),
+            head_before: Some(heads.0.to_string()),
+            head_after: Some(heads.1.to_string()),
+        }
+    }
+
+    #[test]
+    fn test_usage_report() {
+        let operations = vec![
+            operation("2026-01-02T10:00:00+01:00", "add", ("a", "a"), &["-a"]),
+            operation("2026-01-02T10:01:00+01:00", "generate", ("a", "a"), &["-g"]),
+            operation("2026-01-02T10:05:00+01:00", "commit", ("a", "b"), &["-c"]),
+            operation("2026-01-02T11:00:00+01:00", "generate", ("b", "b"), &["-g"]),
+            operation("2026-01-02T11:00:30+01:00", "commit", ("b", "c"), &["-c"]),
+            operation(
+                "2026-01-02T11:10:00+01:00",
+                "commit",
+                ("c", "d"),
+                &["-c", "--amend"],
+            ),
+            operation("2026-01-02T11:20:00+01:00", "push", ("d", "d"), &["-p"]),
+        ];
+        let sizes = |_: &str, after: &str| match after {
+            "b" => Some(CommitSize {
+                files: 1,
+                lines: 10,
+            }),
+            "c" => Some(CommitSize {
+                files: 3,
+                lines: 30,
+            }),
+            _ => None,
+        };
+
+        let report = usage_report(&operations, sizes);
+
+        assert_eq!(report.operations, 7);
+        assert_eq!(report.first.as_deref(), Some("2026-01-02T10:00:00+01:00"));
+        assert_eq!(report.commands["commit"], 3);
+        assert_eq!(report.commands["generate"], 2);
+        assert_eq!(report.measured_commits, 2);
+        assert_eq!(report.average_commit_files, Some(2.0));
+        assert_eq!(report.average_commit_lines, Some(20.0));
+        assert_eq!(report.generated_commits, 2);
+        assert_eq!(report.median_generate_to_commit_secs, Some(240));
+        assert_eq!(report.average_generate_to_commit_secs, Some(135));
+    }
+
+    #[test]
+    fn test_exports() {
+        let operations = vec![
+            operation("2026-01-02T10:01:00+01:00", "generate", ("a", "a"), &["-g"]),
+            operation("2026-01-02T10:05:00+01:00", "commit", ("a", "b"), &["-c"]),
+            operation(
+                "2026-01-02T11:10:00+01:00",
+                "commit",
+                ("b", "c"),
+                &["-c", "--amend"],
+            ),
+        ];
+        let records = commit_records(&operations, |_, _| {
+            Some(CommitSize {
+                files: 2,
+                lines: 12,
+            })
+        });
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].lead_time_secs, Some(240));
+        assert!(records[1].amend && records[1].size.is_none());
+
+        let metrics = prometheus_metrics(&operations, &records, "my \"app\"");
+        for expected in [
+            "# TYPE rona_operations_total counter",
+            "rona_operations_total{repository=\"my \\\"app\\\"\",command=\"commit\"} 2",
+            "rona_commits_total{repository=\"my \\\"app\\\"\"} 2",
+            "rona_commit_lines{repository=\"my \\\"app\\\"\",quantile=\"0.5\"} 12",
+            "rona_commit_lines_count{repository=\"my \\\"app\\\"\"} 1",
+            "rona_generate_to_commit_seconds_sum{repository=\"my \\\"app\\\"\"} 240",
+        ] {
+            assert!(
+                metrics.contains(expected),
+                "missing {expected} in:\n{metrics}"
+            );
+        }
+
+        assert_eq!(
+            commits_csv(&records),
+            "time,commit,branch,amend,files,lines,lead_time_secs\n\
+             2026-01-02T10:05:00+01:00,b,main,false,2,12,240\n\
+             2026-01-02T11:10:00+01:00,c,main,true,,,\n"
+        );
+        assert_eq!(csv_field("a,\"b\""), "\"a,\"\"b\"\"\"");
+    }
+
+    #[test]
+    fn test_usage_report_empty_log() {
+        let report = usage_report(&[], |_, _| None);
+        assert_eq!(report, UsageReport::default());
+    }
+
+    #[test]
+    fn test_parse_numstat() {
+        let size = parse_numstat("3\t1\tsrc/main.rs\n-\t-\tlogo.png\n10\t0\tREADME.md\n");
+        assert_eq!(
+            size,
+            CommitSize {
+                files: 3,
+                lines: 14
+            }
+        );
+    }
+}
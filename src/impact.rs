@@ -0,0 +1,82 @@
+//! Commit Impact
+//!
+//! `[impact]` maps path globs to labels naming the areas a commit touches, such as
+//! `"src/git/**" = "core"` or `"docs/**" = "docs-only"`. The labels of the staged
+//! files are shown by `rona generate` and exposed to commit templates as `{impact}`,
+//! so reviewers and CI can tell at a glance which build or test targets a commit
+//! concerns.
+//!
+//! Globs match paths from the repository root. `*` does not cross a `/`, so a glob
+//! for files at any depth starts with `**/`, e.g. `**/*.md`.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use glob::{MatchOptions, Pattern, PatternError};
+
+/// `*` stays within one directory, like in `.gitignore` patterns with a slash.
+const IMPACT_MATCH_OPTIONS: MatchOptions = MatchOptions {
+    case_sensitive: true,
+    require_literal_separator: true,
+    require_literal_leading_dot: false,
+};
+
+/// The labels of the globs in `rules` matching at least one of `files`, sorted and
+/// without duplicates. Invalid globs never match; see [`invalid_globs`].
+#[must_use]
+pub fn impact_labels(rules: &BTreeMap<String, String>, files: &[String]) -> Vec<String> {
+    let labels: BTreeSet<&str> = rules
+        .iter()
+        .filter(|(glob, _)| {
+            Pattern::new(glob).is_ok_and(|pattern| {
+                files
+                    .iter()
+                    .any(|file| pattern.matches_with(file, IMPACT_MATCH_OPTIONS))
+            })
+        })
+        .map(|(_, label)| label.as_str())
+        .collect();
+    labels.into_iter().map(ToString::to_string).collect()
+}
+
+/// The globs of `rules` that do not parse, with the reason.
+#[must_use]
+pub fn invalid_globs(rules: &BTreeMap<String, String>) -> Vec<(&str, PatternError)> {
+    rules
+        .keys()
+        .filter_map(|glob| Pattern::new(glob).err().map(|e| (glob.as_str(), e)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_impact_labels() {
+        let rules = BTreeMap::from([
+            ("src/git/**".to_string(), "core".to_string()),
+            ("src/*.rs".to_string(), "cli".to_string()),
+            ("docs/**".to_string(), "docs-only".to_string()),
+            ("**/*.md".to_string(), "docs-only".to_string()),
+            ("[".to_string(), "broken".to_string()),
+        ]);
+        let files = |paths: &[&str]| paths.iter().map(ToString::to_string).collect::<Vec<_>>();
+
+        assert_eq!(
+            impact_labels(&rules, &files(&["src/git/commit.rs", "README.md"])),
+            ["core", "docs-only"]
+        );
+        assert_eq!(
+            impact_labels(&rules, &files(&["src/git/sub/deep.rs"])),
+            ["core"]
+        );
+        // `*` does not cross a directory
+        assert!(impact_labels(&rules, &files(&["src/theme/mod.rs"])).is_empty());
+        assert_eq!(impact_labels(&rules, &files(&["src/cli.rs"])), ["cli"]);
+        assert!(impact_labels(&rules, &[]).is_empty());
+
+        let invalid = invalid_globs(&rules);
+        assert_eq!(invalid.len(), 1);
+        assert_eq!(invalid[0].0, "[");
+    }
+}
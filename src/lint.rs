@@ -0,0 +1,513 @@
+//! Commit Message Linting
+//!
+//! Checks a finished commit message file against the active commit template and a
+//! few structural rules. This backs `rona check-msg`, which is meant to run from
+//! git's `commit-msg` hook so messages written with plain `git commit` follow the
+//! same format as the ones Rona generates.
+//!
+//! Only the first line of the template is matched, against the subject line of the
+//! message. The same matcher extracts the variable values of a subject for
+//! `rona show` ([`parse_header`]). Comment lines (`#`) and everything below git's scissors line are ignored,
+//! like git's default message cleanup does.
+
+use std::fmt;
+
+/// A problem found in a commit message, located by 1-based line and column.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}: {}", self.line, self.column, self.message)
+    }
+}
+
+/// Git's scissors line; everything below it is dropped from the message.
+const SCISSORS: &str = "------------------------ >8 ------------------------";
+
+/// Lints a commit message against the commit template and the structural rules.
+///
+/// # Arguments
+/// * `content` - The raw message file content, including git's comment lines
+/// * `template` - The active commit template (assumed valid)
+/// * `commit_types` - Allowed values for `{commit_type}`
+///
+/// # Returns
+/// The diagnostics, in line order. An empty vector means the message is valid.
+#[must_use]
+pub fn lint_message(content: &str, template: &str, commit_types: &[&str]) -> Vec<Diagnostic> {
+    let lines: Vec<(usize, &str)> = content
+        .lines()
+        .enumerate()
+        .take_while(|(_, line)| !(line.starts_with('#') && line.contains(SCISSORS)))
+        .filter(|(_, line)| !line.starts_with('#'))
+        .map(|(index, line)| (index + 1, line))
+        .skip_while(|(_, line)| line.trim().is_empty())
+        .collect();
+
+    let Some(&(subject_line, subject)) = lines.first() else {
+        return vec![Diagnostic {
+            line: 1,
+            column: 1,
+            message: "commit message is empty".to_string(),
+        }];
+    };
+
+    let mut diagnostics = Vec::new();
+
+    let trimmed = subject.trim_end();
+    if trimmed.len() != subject.len() {
+        diagnostics.push(Diagnostic {
+            line: subject_line,
+            column: trimmed.chars().count() + 1,
+            message: "subject line has trailing whitespace".to_string(),
+        });
+    }
+
+    let header = template.lines().next().unwrap_or_default();
+    if let Err((offset, expected)) = match_header(header, trimmed, commit_types) {
+        diagnostics.push(Diagnostic {
+            line: subject_line,
+            column: trimmed[..offset].chars().count() + 1,
+            message: format!("subject does not match the commit template: {expected}"),
+        });
+    }
+
+    if let Some(&(line, text)) = lines.get(1)
+        && !text.trim().is_empty()
+    {
+        diagnostics.push(Diagnostic {
+            line,
+            column: 1,
+            message: "expected a blank line between the subject and the body".to_string(),
+        });
+    }
+
+    diagnostics
+}
+
+/// Extracts the template variables from a subject line written with `template`.
+///
+/// # Arguments
+/// * `subject` - The subject line of a commit message
+/// * `template` - The commit template; only its first line is used
+/// * `commit_types` - Allowed values for `{commit_type}`
+///
+/// # Returns
+/// The variables and their values in subject order, or `None` when the subject
+/// does not match the template. Optional blocks that are absent contribute nothing.
+#[must_use]
+pub fn parse_header(
+    subject: &str,
+    template: &str,
+    commit_types: &[&str],
+) -> Option<Vec<(String, String)>> {
+    header_captures(subject, template, commit_types).map(|captures| {
+        captures
+            .into_iter()
+            .map(|(name, start, end)| (name, subject[start..end].to_string()))
+            .collect()
+    })
+}
+
+/// Finds where `{commit_type}` is in a subject line written with `template`.
+///
+/// # Returns
+/// The byte range of the commit type, or `None` when the subject does not match the
+/// template or the template has no `{commit_type}`.
+#[must_use]
+pub fn commit_type_span(
+    subject: &str,
+    template: &str,
+    commit_types: &[&str],
+) -> Option<std::ops::Range<usize>> {
+    header_captures(subject, template, commit_types)?
+        .into_iter()
+        .find(|(name, _, _)| name == "commit_type")
+        .map(|(_, start, end)| start..end)
+}
+
+/// Matches a subject against the first line of `template`, returning the name and
+/// byte range of each captured variable.
+fn header_captures(
+    subject: &str,
+    template: &str,
+    commit_types: &[&str],
+) -> Option<Vec<(String, usize, usize)>> {
+    let header = template.lines().next().unwrap_or_default();
+    let tokens = parse_template(header);
+    let mut matcher = Matcher::new(subject, commit_types);
+
+    matcher.matches(&[&tokens], 0).then_some(matcher.captures)
+}
+
+/// A parsed piece of a template line.
+#[derive(Debug)]
+enum Token {
+    Literal(String),
+    Variable(String),
+    Optional(Vec<Self>),
+}
+
+/// Parses a template line into tokens, treating anything malformed as literal text.
+fn parse_template(template: &str) -> Vec<Token> {
+    let mut stack: Vec<(Option<String>, Vec<Token>)> = vec![(None, Vec::new())];
+    let mut literal = String::new();
+    let mut rest = template;
+
+    while let Some(c) = rest.chars().next() {
+        let tag = rest
+            .strip_prefix('{')
+            .and_then(|after| after.split_once('}'))
+            .filter(|(inner, _)| {
+                let name = inner.trim_start_matches(['?', '/']);
+                !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+            });
+
+        let Some((inner, after)) = tag else {
+            literal.push(c);
+            rest = &rest[c.len_utf8()..];
+            continue;
+        };
+
+        if !literal.is_empty()
+            && let Some((_, tokens)) = stack.last_mut()
+        {
+            tokens.push(Token::Literal(std::mem::take(&mut literal)));
+        }
+
+        if let Some(name) = inner.strip_prefix('?') {
+            stack.push((Some(name.to_string()), Vec::new()));
+        } else if let Some(name) = inner.strip_prefix('/') {
+            if stack.len() > 1
+                && stack
+                    .last()
+                    .is_some_and(|(n, _)| n.as_deref() == Some(name))
+            {
+                if let Some((_, inner_tokens)) = stack.pop()
+                    && let Some((_, tokens)) = stack.last_mut()
+                {
+                    tokens.push(Token::Optional(inner_tokens));
+                }
+            } else if let Some((_, tokens)) = stack.last_mut() {
+                tokens.push(Token::Literal(format!("{{{inner}}}")));
+            }
+        } else if let Some((_, tokens)) = stack.last_mut() {
+            tokens.push(Token::Variable(inner.to_string()));
+        }
+
+        rest = after;
+    }
+
+    if let Some((_, tokens)) = stack.last_mut()
+        && !literal.is_empty()
+    {
+        tokens.push(Token::Literal(literal));
+    }
+
+    // Unclosed blocks are kept as plain sequences.
+    while stack.len() > 1 {
+        if let Some((_, inner_tokens)) = stack.pop()
+            && let Some((_, tokens)) = stack.last_mut()
+        {
+            tokens.extend(inner_tokens);
+        }
+    }
+    stack.pop().map(|(_, tokens)| tokens).unwrap_or_default()
+}
+
+/// Matches a subject against a template line.
+///
+/// # Returns
+/// `Ok(())` on a match, otherwise the byte offset where matching got furthest and
+/// a description of what was expected there.
+fn match_header(
+    template: &str,
+    subject: &str,
+    commit_types: &[&str],
+) -> Result<(), (usize, String)> {
+    let tokens = parse_template(template);
+    let mut matcher = Matcher::new(subject, commit_types);
+
+    if matcher.matches(&[&tokens], 0) {
+        return Ok(());
+    }
+
+    let expected = match matcher.expected.as_slice() {
+        [] => "unexpected text".to_string(),
+        [only] => format!("expected {only}"),
+        many => format!("expected {}", many.join(" or ")),
+    };
+    Err((matcher.furthest, expected))
+}
+
+/// Backtracking matcher that remembers the furthest failure for error reporting.
+struct Matcher<'a> {
+    subject: &'a str,
+    commit_types: &'a [&'a str],
+    furthest: usize,
+    expected: Vec<String>,
+    /// Variable values on the current match path, as byte ranges of the subject.
+    captures: Vec<(String, usize, usize)>,
+}
+
+impl<'a> Matcher<'a> {
+    const fn new(subject: &'a str, commit_types: &'a [&'a str]) -> Self {
+        Self {
+            subject,
+            commit_types,
+            furthest: 0,
+            expected: Vec::new(),
+            captures: Vec::new(),
+        }
+    }
+
+    /// Matches the token sequences in `seq`, in order, starting at byte `pos`.
+    fn matches(&mut self, seq: &[&[Token]], pos: usize) -> bool {
+        let Some((first, rest)) = seq.split_first() else {
+            if pos == self.subject.len() {
+                return true;
+            }
+            self.fail(pos, "end of subject".to_string());
+            return false;
+        };
+        let Some((token, tail)) = first.split_first() else {
+            return self.matches(rest, pos);
+        };
+
+        let mut next: Vec<&[Token]> = Vec::with_capacity(seq.len());
+        next.push(tail);
+        next.extend_from_slice(rest);
+
+        match token {
+            Token::Literal(text) => {
+                let remaining = &self.subject[pos..];
+                if remaining.starts_with(text.as_str()) {
+                    return self.matches(&next, pos + text.len());
+                }
+                let common: usize = remaining
+                    .chars()
+                    .zip(text.chars())
+                    .take_while(|(a, b)| a == b)
+                    .map(|(a, _)| a.len_utf8())
+                    .sum();
+                self.fail(pos + common, format!("`{text}`"));
+                false
+            }
+            Token::Variable(name) => {
+                let ends = self.candidates(name, pos);
+                if ends.is_empty() {
+                    self.fail(pos, self.describe(name));
+                }
+                ends.into_iter().any(|end| {
+                    self.captures.push((name.clone(), pos, end));
+                    let matched = self.matches(&next, end);
+                    if !matched {
+                        self.captures.pop();
+                    }
+                    matched
+                })
+            }
+            Token::Optional(inner) => {
+                let mut with_block: Vec<&[Token]> = Vec::with_capacity(next.len() + 1);
+                with_block.push(inner);
+                with_block.extend_from_slice(&next);
+                self.matches(&with_block, pos) || self.matches(&next, pos)
+            }
+        }
+    }
+
+    /// Possible end offsets for a variable value starting at `pos`.
+    fn candidates(&self, name: &str, pos: usize) -> Vec<usize> {
+        let remaining = &self.subject[pos..];
+        match name {
+            "commit_number" => {
+                let digits = remaining.chars().take_while(char::is_ascii_digit).count();
+                (1..=digits).rev().map(|len| pos + len).collect()
+            }
+            "commit_type" => self
+                .commit_types
+                .iter()
+                .filter(|t| !t.is_empty() && remaining.starts_with(**t))
+                .map(|t| pos + t.len())
+                .collect(),
+            _ => {
+                let min = usize::from(matches!(
+                    name,
+                    "message" | "subject" | "branch_name" | "date" | "time" | "author" | "email"
+                ));
+                remaining
+                    .char_indices()
+                    .map(|(i, _)| i)
+                    .chain(std::iter::once(remaining.len()))
+                    .filter(|len| *len >= min)
+                    .map(|len| pos + len)
+                    .collect()
+            }
+        }
+    }
+
+    /// Human-readable description of what a variable expects.
+    fn describe(&self, name: &str) -> String {
+        match name {
+            "commit_number" => "a commit number".to_string(),
+            "commit_type" => format!(
+                "a commit type ({})",
+                self.commit_types
+                    .iter()
+                    .map(|t| format!("`{t}`"))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            other => format!("a value for {{{other}}}"),
+        }
+    }
+
+    /// Records a failed expectation, keeping only the furthest ones.
+    fn fail(&mut self, pos: usize, expected: String) {
+        if pos > self.furthest {
+            self.furthest = pos;
+            self.expected.clear();
+        }
+        if pos == self.furthest && !self.expected.contains(&expected) {
+            self.expected.push(expected);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEMPLATE: &str = "{?commit_number}[{commit_number}] {/commit_number}({commit_type} on {branch_name}) {message}";
+    const TYPES: [&str; 4] = ["chore", "feat", "fix", "test"];
+
+    fn lint(content: &str) -> Vec<Diagnostic> {
+        lint_message(content, TEMPLATE, &TYPES)
+    }
+
+    #[test]
+    fn test_valid_messages() {
+        assert!(lint("[12] (feat on login) Add login form\n").is_empty());
+        assert!(lint("(fix on main) Handle empty input").is_empty());
+        assert!(lint("[3] (chore on a) (b) c\n\nBody text\n# comment\n").is_empty());
+    }
+
+    #[test]
+    fn test_comments_and_leading_blank_lines_are_ignored() {
+        let content = "\n# Please enter the commit message\n[1] (test on x) Cover parser\n";
+        assert!(lint(content).is_empty());
+    }
+
+    #[test]
+    fn test_empty_message() {
+        let diagnostics = lint("\n# only comments\n");
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].message, "commit message is empty");
+    }
+
+    #[test]
+    fn test_unknown_commit_type_column() {
+        let diagnostics = lint("[4] (docs on main) Update readme");
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!((diagnostics[0].line, diagnostics[0].column), (1, 6));
+        assert!(diagnostics[0].message.contains("a commit type"));
+        assert!(diagnostics[0].message.contains("`feat`"));
+    }
+
+    #[test]
+    fn test_missing_message_column() {
+        let diagnostics = lint("\n[4] (feat on main)");
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!((diagnostics[0].line, diagnostics[0].column), (2, 19));
+        assert!(diagnostics[0].message.contains("expected `) `"));
+    }
+
+    #[test]
+    fn test_free_form_subject() {
+        let diagnostics = lint("Fix the thing");
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].column, 1);
+        assert!(diagnostics[0].message.contains("`[`"));
+        assert!(diagnostics[0].message.contains("`(`"));
+    }
+
+    #[test]
+    fn test_trailing_whitespace_and_missing_blank_line() {
+        let diagnostics = lint("(feat on x) Add  \nbody right away\n");
+        assert_eq!(
+            diagnostics,
+            vec![
+                Diagnostic {
+                    line: 1,
+                    column: 16,
+                    message: "subject line has trailing whitespace".to_string(),
+                },
+                Diagnostic {
+                    line: 2,
+                    column: 1,
+                    message: "expected a blank line between the subject and the body".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_scissors_line_ends_the_message() {
+        let content = "(feat on x) Add\n\n# ------------------------ >8 ------------------------\ndiff --git a/x b/x\n";
+        assert!(lint(content).is_empty());
+    }
+
+    #[test]
+    fn test_custom_template_with_extra_fields() {
+        let template = "{commit_type}{?scope}({scope}){/scope}: {message}";
+        assert!(lint_message("feat(api): Add endpoint", template, &TYPES).is_empty());
+        assert!(lint_message("feat: Add endpoint", template, &TYPES).is_empty());
+
+        let diagnostics = lint_message("feat Add endpoint", template, &TYPES);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].column, 5);
+    }
+
+    #[test]
+    fn test_parse_header() {
+        let fields = parse_header("[12] (feat on login) add form", TEMPLATE, &TYPES);
+        assert_eq!(
+            fields,
+            Some(vec![
+                ("commit_number".to_string(), "12".to_string()),
+                ("commit_type".to_string(), "feat".to_string()),
+                ("branch_name".to_string(), "login".to_string()),
+                ("message".to_string(), "add form".to_string()),
+            ])
+        );
+
+        let fields = parse_header("(fix on main) typo", TEMPLATE, &TYPES);
+        assert_eq!(
+            fields.map(|f| f.into_iter().map(|(name, _)| name).collect::<Vec<_>>()),
+            Some(vec![
+                "commit_type".to_string(),
+                "branch_name".to_string(),
+                "message".to_string(),
+            ])
+        );
+
+        assert_eq!(parse_header("Merge branch 'x'", TEMPLATE, &TYPES), None);
+        assert_eq!(
+            commit_type_span("[12] (feat on login) add form", TEMPLATE, &TYPES),
+            Some(6..10)
+        );
+        assert_eq!(commit_type_span("Merge branch 'x'", TEMPLATE, &TYPES), None);
+    }
+
+    #[test]
+    fn test_multi_byte_columns() {
+        let diagnostics = lint("(feat on café)");
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].column, 15);
+    }
+}
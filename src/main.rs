@@ -33,11 +33,36 @@
 //! # Architecture
 //!
 //! The application is organized into several modules:
+//! - `annotations`: One-line summaries of the staged changes of each file
+//! - `audit`: Compliance of existing commit messages with the configured format
+//! - `browse`: Full-screen history browser of `rona browse`
 //! - `cli`: Handles command-line interface and argument parsing
+//! - `completion`: Checks of the installed shell completions
 //! - `config`: Manages application configuration
+//! - `config_editor`: Interactive editing of the config file, keeping its comments
+//! - `config_sync`: The team's canonical config compared with and applied to the local one
+//! - `copy`: Commit references formatted for the clipboard
+//! - `editor`: Opening the commit message in an editor, with fallbacks
+//! - `environment`: What rona resolved for the current directory, for `rona env`
 //! - `errors`: Error handling and custom error types
+//! - `explain`: `git status` grouped by state and explained, for `rona explain-status`
+//! - `extra_fields`: Extra prompt fields of commit messages and branch names
+//! - `forge`: Hosting services recognized from remote URLs, web links and pull requests
+//! - `fragments`: Changelog fragments assembled into `CHANGELOG.md` at release time
 //! - `git`: Organized Git-related functionality with focused submodules
-//! - `my_clap_theme`: Custom theme for command-line output
+//! - `impact`: Impact labels of the areas a commit touches, from path globs
+//! - `lint`: Commit message checks against the commit template
+//! - `notify`: Chat webhook notifications after a push
+//! - `presets`: Built-in exclusion patterns for popular ecosystems
+//! - `release`: Next versions of `rona release`, with prerelease channels
+//! - `schema`: JSON schemas of the documents printed for tools
+//! - `shell`: Configured shell commands, with a time limit, an allowed environment and folded output
+//! - `snippets`: Reusable commit message body text
+//! - `symbols`: Functions and types a change touches, from a tree-sitter parse
+//! - `template`: Commit message templates and their variables
+//! - `theme`: Shared prompt theme and the colors of commit types
+//! - `tour`: Guided walkthrough in a throwaway demo repository
+//! - `tracker`: Issue tracker commands run for the ticket of a pushed branch
 //! - `utils`: Common utility functions
 //!
 //! # Error Handling
@@ -47,13 +72,36 @@
 //! 2. Main application logic error handling through `Result` types
 //!
 
+pub mod annotations;
+pub mod audit;
+pub mod browse;
 pub mod cli;
+pub mod completion;
 pub mod config;
+pub mod config_editor;
+pub mod config_sync;
+pub mod copy;
+pub mod editor;
+pub mod environment;
 pub mod errors;
+pub mod explain;
 pub mod extra_fields;
+pub mod forge;
+pub mod fragments;
 pub mod git;
+pub mod impact;
+pub mod lint;
+pub mod notify;
+pub mod presets;
+pub mod release;
+pub mod schema;
+pub mod shell;
+pub mod snippets;
+pub mod symbols;
 pub mod template;
 pub mod theme;
+pub mod tour;
+pub mod tracker;
 pub mod utils;
 
 use cli::run;
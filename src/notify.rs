@@ -0,0 +1,341 @@
+//! Push Notifications
+//!
+//! Posts a message to a chat webhook (Slack, Discord or Microsoft Teams) after a
+//! successful push or release, configured under `[notify.webhook]`. A push message is
+//! rendered from a template with the branch, the pushed commit subjects and a compare
+//! link, a release message from one with the version and the tag. Both are posted with
+//! `curl`, the same way rona drives `git` through its CLI.
+
+use std::{
+    io::Write,
+    process::{Command, Stdio},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    errors::{Result, RonaError},
+    forge::RemoteRepo,
+    git::{PushSummary, url_host},
+    template::{
+        PushTemplateVariables, ReleaseTemplateVariables, process_push_template,
+        process_release_template, validate_push_template, validate_release_template,
+    },
+};
+
+/// The message posted when `[notify.webhook]` sets no template.
+pub const DEFAULT_NOTIFY_TEMPLATE: &str = "{author} pushed {count} commit(s) to {branch}:\n{subjects}{?compare_url}\n{compare_url}{/compare_url}";
+
+/// The message posted after `rona release` when `[notify.webhook]` sets no
+/// `release_template`.
+pub const DEFAULT_RELEASE_NOTIFY_TEMPLATE: &str = "Released {?package}{package} {/package}{version} as {tag}{?previous} (previous: {previous}){/previous}";
+
+/// Notification settings, declared as `[notify]`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct NotifyConfig {
+    /// Chat webhook posted to after each successful push and release.
+    pub webhook: Option<WebhookConfig>,
+}
+
+/// A chat webhook, declared as `[notify.webhook]`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct WebhookConfig {
+    /// The incoming webhook URL. It usually embeds a secret, so it is never printed.
+    pub url: String,
+    /// Payload format. Inferred from the URL when absent, defaulting to Slack.
+    pub kind: Option<WebhookKind>,
+    /// Message template. Available variables: `{branch}`, `{remote}`, `{subjects}`,
+    /// `{count}`, `{compare_url}`, `{author}`.
+    pub template: Option<String>,
+    /// Message template for releases. Available variables: `{version}`, `{tag}`,
+    /// `{channel}`, `{previous}`, `{package}`.
+    pub release_template: Option<String>,
+}
+
+/// The chat service a webhook belongs to, which decides the JSON payload shape.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WebhookKind {
+    Slack,
+    Discord,
+    Teams,
+}
+
+impl WebhookConfig {
+    /// The configured kind, or the one recognized from the URL's host.
+    #[must_use]
+    pub fn kind(&self) -> WebhookKind {
+        self.kind.unwrap_or_else(|| {
+            let host = url_host(&self.url);
+            if host.ends_with("discord.com") || host.ends_with("discordapp.com") {
+                WebhookKind::Discord
+            } else if host.ends_with("webhook.office.com") || host.ends_with("logic.azure.com") {
+                WebhookKind::Teams
+            } else {
+                WebhookKind::Slack
+            }
+        })
+    }
+
+    /// The URL's host, safe to print.
+    #[must_use]
+    pub fn host(&self) -> &str {
+        url_host(&self.url)
+    }
+}
+
+/// Renders the notification text for a push.
+///
+/// # Errors
+/// * If the template uses unknown variables or malformed conditional blocks
+/// * If the git author cannot be read
+pub fn render_notification(webhook: &WebhookConfig, summary: &PushSummary) -> Result<String> {
+    let template = webhook
+        .template
+        .as_deref()
+        .unwrap_or(DEFAULT_NOTIFY_TEMPLATE);
+    validate_push_template(template)
+        .map_err(|e| RonaError::InvalidInput(format!("notify.webhook template: {e}")))?;
+
+    let compare = summary
+        .remote_url
+        .as_deref()
+        .zip(summary.old_tip.as_deref())
+        .and_then(|(url, old)| RemoteRepo::parse(url)?.compare_url(old, &summary.new_tip));
+    let variables = PushTemplateVariables::new(
+        summary.branch.clone(),
+        summary.remote.clone(),
+        &summary.subjects,
+        compare,
+    )?;
+    Ok(process_push_template(template, &variables)?
+        .trim_end()
+        .to_string())
+}
+
+/// Renders the notification text for a release.
+///
+/// # Errors
+/// * If the template uses unknown variables or malformed conditional blocks
+pub fn render_release_notification(
+    webhook: &WebhookConfig,
+    variables: &ReleaseTemplateVariables,
+) -> Result<String> {
+    let template = webhook
+        .release_template
+        .as_deref()
+        .unwrap_or(DEFAULT_RELEASE_NOTIFY_TEMPLATE);
+    validate_release_template(template)
+        .map_err(|e| RonaError::InvalidInput(format!("notify.webhook release_template: {e}")))?;
+    Ok(process_release_template(template, variables)?
+        .trim_end()
+        .to_string())
+}
+
+/// Posts a message to the webhook with `curl`.
+///
+/// # Errors
+/// * If `curl` cannot be started or the request fails
+pub fn post_webhook(webhook: &WebhookConfig, text: &str) -> Result<()> {
+    let payload = webhook_payload(webhook.kind(), text);
+
+    // The URL and the payload go through a curl config read from stdin, so neither
+    // shows up in process listings, and the URL is masked in error messages.
+    let mut child = Command::new("curl")
+        .args([
+            "--silent",
+            "--show-error",
+            "--fail",
+            "--max-time",
+            "10",
+            "--header",
+            "Content-Type: application/json",
+            "--config",
+            "-",
+        ])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(curl_config(&webhook.url, &payload).as_bytes())?;
+    }
+    let output = child.wait_with_output()?;
+
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(RonaError::CommandFailed {
+            command: format!(
+                "curl (webhook on {}): {}",
+                webhook.host(),
+                String::from_utf8_lossy(&output.stderr)
+                    .trim()
+                    .replace(&webhook.url, "<webhook url>")
+            ),
+        })
+    }
+}
+
+/// The curl config posting `payload` to `url`.
+fn curl_config(url: &str, payload: &str) -> String {
+    format!(
+        "url = {}\ndata-binary = {}\n",
+        curl_quote(url),
+        curl_quote(payload)
+    )
+}
+
+/// A value of a curl config file (`curl --config`), quoted as curl expects.
+pub(crate) fn curl_quote(value: &str) -> String {
+    let mut quoted = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '\\' => quoted.push_str("\\\\"),
+            '"' => quoted.push_str("\\\""),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Builds the JSON body each service expects for a plain-text message.
+fn webhook_payload(kind: WebhookKind, text: &str) -> String {
+    let payload = match kind {
+        WebhookKind::Slack | WebhookKind::Teams => serde_json::json!({ "text": text }),
+        WebhookKind::Discord => serde_json::json!({ "content": text }),
+    };
+    payload.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type TestResult = std::result::Result<(), Box<dyn std::error::Error>>;
+
+    fn webhook(url: &str) -> WebhookConfig {
+        WebhookConfig {
+            url: url.to_string(),
+            kind: None,
+            template: None,
+            release_template: None,
+        }
+    }
+
+    #[test]
+    fn test_webhook_kind_and_payload() {
+        assert_eq!(
+            webhook("https://hooks.slack.com/services/T/B/X").kind(),
+            WebhookKind::Slack
+        );
+        assert_eq!(
+            webhook("https://discord.com/api/webhooks/1/x").kind(),
+            WebhookKind::Discord
+        );
+        assert_eq!(
+            webhook("https://acme.webhook.office.com/webhookb2/x").kind(),
+            WebhookKind::Teams
+        );
+        assert_eq!(
+            webhook("https://discord.com/api/webhooks/1/x").host(),
+            "discord.com"
+        );
+
+        assert_eq!(
+            webhook_payload(WebhookKind::Discord, "hi \"there\""),
+            r#"{"content":"hi \"there\""}"#
+        );
+        assert_eq!(
+            webhook_payload(WebhookKind::Slack, "hi"),
+            r#"{"text":"hi"}"#
+        );
+    }
+
+    #[test]
+    fn test_curl_config() {
+        let config = curl_config("https://x.test/hook", r#"{"text":"a \"b\"\nc"}"#);
+        assert_eq!(
+            config,
+            concat!(
+                r#"url = "https://x.test/hook""#,
+                "\n",
+                r#"data-binary = "{\"text\":\"a \\\"b\\\"\\nc\"}""#,
+                "\n"
+            )
+        );
+    }
+
+    #[test]
+    fn test_render_notification() -> TestResult {
+        let summary = PushSummary {
+            remote: "origin".to_string(),
+            remote_url: Some("git@github.com:o/r.git".to_string()),
+            branch: "feat/login".to_string(),
+            old_tip: None,
+            new_tip: "bbb".to_string(),
+            subjects: vec!["add form".to_string(), "add route".to_string()],
+        };
+        let mut hook = webhook("https://hooks.slack.com/services/T/B/X");
+        hook.template = Some(
+            "{count} on {remote}/{branch}\n{subjects}{?compare_url}\n{compare_url}{/compare_url}"
+                .to_string(),
+        );
+
+        assert_eq!(
+            render_notification(&hook, &summary)?,
+            "2 on origin/feat/login\n- add form\n- add route"
+        );
+
+        let summary = PushSummary {
+            old_tip: Some("aaa".to_string()),
+            ..summary
+        };
+        assert!(
+            render_notification(&hook, &summary)?
+                .ends_with("\nhttps://github.com/o/r/compare/aaa...bbb")
+        );
+
+        hook.template = Some("{sha}".to_string());
+        assert!(render_notification(&hook, &summary).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_render_release_notification() -> TestResult {
+        let variables = ReleaseTemplateVariables {
+            version: "1.3.0".to_string(),
+            tag: "api/v1.3.0".to_string(),
+            channel: String::new(),
+            previous: "1.2.4".to_string(),
+            package: "api".to_string(),
+        };
+        let mut hook = webhook("https://hooks.slack.com/services/T/B/X");
+        assert_eq!(
+            render_release_notification(&hook, &variables)?,
+            "Released api 1.3.0 as api/v1.3.0 (previous: 1.2.4)"
+        );
+
+        let first = ReleaseTemplateVariables {
+            previous: String::new(),
+            package: String::new(),
+            tag: "v1.3.0".to_string(),
+            ..variables.clone()
+        };
+        assert_eq!(
+            render_release_notification(&hook, &first)?,
+            "Released 1.3.0 as v1.3.0"
+        );
+
+        hook.release_template = Some("{tag} is out\n".to_string());
+        assert_eq!(render_release_notification(&hook, &first)?, "v1.3.0 is out");
+
+        hook.release_template = Some("{branch}".to_string());
+        assert!(render_release_notification(&hook, &variables).is_err());
+        Ok(())
+    }
+}
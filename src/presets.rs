@@ -0,0 +1,161 @@
+//! Exclusion Presets
+//!
+//! Built-in pattern sets for the files popular ecosystems generate, selected with
+//! `preset = ["node", "rust"]`. Build output and caches are never staged by `rona -a`.
+//! Lock files are still staged, but `rona init --repo` writes them to `.commitignore`
+//! along with the build output, so they are not listed in generated messages.
+
+use std::path::Path;
+
+use crate::errors::{Result, RonaError};
+
+/// A named set of patterns for one ecosystem.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Preset {
+    /// Name used in the `preset` config list
+    pub name: &'static str,
+    /// Files at the repository root that identify the ecosystem
+    pub markers: &'static [&'static str],
+    /// Build output and caches. A trailing `/` marks a directory.
+    pub generated: &'static [&'static str],
+    /// Lock files, committed but not described in messages
+    pub lock_files: &'static [&'static str],
+}
+
+/// The built-in presets.
+pub const PRESETS: &[Preset] = &[
+    Preset {
+        name: "node",
+        markers: &["package.json"],
+        generated: &["node_modules/", "dist/", "coverage/", ".next/", ".turbo/"],
+        lock_files: &[
+            "package-lock.json",
+            "yarn.lock",
+            "pnpm-lock.yaml",
+            "bun.lockb",
+        ],
+    },
+    Preset {
+        name: "python",
+        markers: &["pyproject.toml", "setup.py", "requirements.txt"],
+        generated: &[
+            "__pycache__/",
+            "*.pyc",
+            ".venv/",
+            ".pytest_cache/",
+            ".mypy_cache/",
+            ".ruff_cache/",
+            "*.egg-info/",
+        ],
+        lock_files: &["poetry.lock", "Pipfile.lock", "uv.lock"],
+    },
+    Preset {
+        name: "rust",
+        markers: &["Cargo.toml"],
+        generated: &["target/"],
+        lock_files: &["Cargo.lock"],
+    },
+];
+
+/// Looks up presets by name.
+///
+/// # Errors
+/// * If a name is not a built-in preset
+pub fn resolve_presets(names: &[String]) -> Result<Vec<&'static Preset>> {
+    names
+        .iter()
+        .map(|name| {
+            PRESETS
+                .iter()
+                .find(|preset| preset.name == name)
+                .ok_or_else(|| {
+                    RonaError::InvalidInput(format!(
+                        "Unknown preset '{name}'. Available presets: {}",
+                        PRESETS
+                            .iter()
+                            .map(|p| p.name)
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ))
+                })
+        })
+        .collect()
+}
+
+/// The presets whose marker files exist in `root`.
+#[must_use]
+pub fn detect_presets(root: &Path) -> Vec<&'static Preset> {
+    PRESETS
+        .iter()
+        .filter(|preset| preset.markers.iter().any(|m| root.join(m).is_file()))
+        .collect()
+}
+
+/// Glob patterns excluding the generated files of the presets from `rona -a`.
+///
+/// A directory entry such as `target/` excludes the directory as git status lists it
+/// when untracked (`target/`) and every file under it, at any depth.
+#[must_use]
+pub fn staging_patterns(presets: &[&Preset]) -> Vec<String> {
+    presets
+        .iter()
+        .flat_map(|preset| preset.generated)
+        .flat_map(|entry| {
+            entry.strip_suffix('/').map_or_else(
+                || vec![(*entry).to_string()],
+                |dir| vec![dir.to_string(), format!("**/{dir}/**")],
+            )
+        })
+        .collect()
+}
+
+/// Lines for `.commitignore`: the generated files and the lock files of the presets.
+#[must_use]
+pub fn commitignore_lines(presets: &[&Preset]) -> Vec<&'static str> {
+    presets
+        .iter()
+        .flat_map(|preset| preset.generated.iter().chain(preset.lock_files))
+        .copied()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use glob::Pattern;
+
+    #[test]
+    fn test_resolve_presets() -> Result<()> {
+        let presets = resolve_presets(&["rust".to_string(), "node".to_string()])?;
+        assert_eq!(
+            presets.iter().map(|p| p.name).collect::<Vec<_>>(),
+            ["rust", "node"]
+        );
+
+        let Err(error) = resolve_presets(&["cobol".to_string()]) else {
+            return Err(RonaError::InvalidInput("cobol was resolved".to_string()));
+        };
+        assert!(error.to_string().contains("node, python, rust"), "{error}");
+        Ok(())
+    }
+
+    #[test]
+    fn test_staging_patterns() -> Result<()> {
+        let presets = resolve_presets(&["rust".to_string(), "python".to_string()])?;
+        let patterns = staging_patterns(&presets)
+            .iter()
+            .map(|p| Pattern::new(p))
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|e| RonaError::InvalidInput(e.to_string()))?;
+        let excluded = |path: &str| patterns.iter().any(|p| p.matches(path));
+
+        assert!(excluded("target/"));
+        assert!(excluded("target/debug/rona"));
+        assert!(excluded("crates/cli/target/debug/cli"));
+        assert!(excluded("app/__pycache__/main.cpython-312.pyc"));
+        assert!(!excluded("src/target.rs"));
+        assert!(!excluded("Cargo.lock"));
+        assert_eq!(commitignore_lines(&presets[..1]), ["target/", "Cargo.lock"]);
+        Ok(())
+    }
+}
@@ -0,0 +1,306 @@
+//! Releases
+//!
+//! `rona release` tags the next version of the project. The current version is the
+//! highest semver tag carrying the tag prefix (`v` by default) reachable from `HEAD`,
+//! and the next one follows semver rules:
+//!
+//! - A bump level (`--major`, `--minor`, `--patch`, default patch) moves a stable
+//!   version to the next one: `1.2.3` gives `2.0.0`, `1.3.0` or `1.2.4`.
+//! - A channel (`--alpha`, `--beta`, `--rc`) makes it a prerelease of that version:
+//!   `1.2.3` with `--minor --rc` gives `1.3.0-rc.1`.
+//! - From a prerelease, the same channel increments its number (`1.3.0-rc.1` to
+//!   `1.3.0-rc.2`), a later channel starts over at 1 (`1.3.0-beta.2` to `1.3.0-rc.1`),
+//!   and no channel releases the version itself (`1.3.0-rc.2` to `1.3.0`).
+//!
+//! Channels are ordered `alpha` < `beta` < `rc` < the release, so moving back to an
+//! earlier channel is refused.
+//!
+//! In a monorepo, each package of `[packages]` is released on its own with
+//! `--package <name>`: its versions are the tags with its own prefix (`<name>/v` by
+//! default), and only commits touching its directory count towards a release.
+
+use std::{cmp::Ordering, fmt};
+
+use crate::errors::{Result, RonaError};
+
+/// Prefix of version tags when `[release] tag_prefix` is not set.
+pub const DEFAULT_TAG_PREFIX: &str = "v";
+
+/// Message of the release commit and tag when `[release] message` is not set.
+pub const DEFAULT_RELEASE_MESSAGE: &str = "Release {?package}{package} {/package}{version}";
+
+/// A prerelease channel, in semver precedence order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Channel {
+    Alpha,
+    Beta,
+    Rc,
+}
+
+impl Channel {
+    /// The identifier of the channel in versions, e.g. `rc` in `1.3.0-rc.1`.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Alpha => "alpha",
+            Self::Beta => "beta",
+            Self::Rc => "rc",
+        }
+    }
+
+    /// Parses a channel identifier.
+    #[must_use]
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "alpha" => Some(Self::Alpha),
+            "beta" => Some(Self::Beta),
+            "rc" => Some(Self::Rc),
+            _ => None,
+        }
+    }
+}
+
+/// The part of the version a release bumps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Bump {
+    Major,
+    Minor,
+    Patch,
+}
+
+/// A semver version, optionally a prerelease `<channel>.<n>`. Build metadata and other
+/// prerelease identifiers are not supported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Version {
+    pub major: u64,
+    pub minor: u64,
+    pub patch: u64,
+    /// The channel and number of a prerelease
+    pub pre: Option<(Channel, u64)>,
+}
+
+impl Version {
+    /// A stable version.
+    #[must_use]
+    pub const fn new(major: u64, minor: u64, patch: u64) -> Self {
+        Self {
+            major,
+            minor,
+            patch,
+            pre: None,
+        }
+    }
+
+    /// Parses `1.2.3` or `1.2.3-rc.1`.
+    #[must_use]
+    pub fn parse(text: &str) -> Option<Self> {
+        let (core, pre) = match text.split_once('-') {
+            Some((core, pre)) => (core, Some(pre)),
+            None => (text, None),
+        };
+        let mut numbers = core.split('.').map(|n| {
+            // Semver forbids leading zeros.
+            (n == "0" || !n.starts_with('0'))
+                .then(|| n.parse::<u64>().ok())
+                .flatten()
+        });
+        let (major, minor, patch) = (numbers.next()??, numbers.next()??, numbers.next()??);
+        if numbers.next().is_some() {
+            return None;
+        }
+        let pre = match pre {
+            None => None,
+            Some(pre) => {
+                let (channel, number) = pre.split_once('.')?;
+                Some((Channel::parse(channel)?, number.parse().ok()?))
+            }
+        };
+        Some(Self {
+            major,
+            minor,
+            patch,
+            pre,
+        })
+    }
+
+    /// The channel of a prerelease, `None` for a stable version.
+    #[must_use]
+    pub fn channel(&self) -> Option<Channel> {
+        self.pre.map(|(channel, _)| channel)
+    }
+
+    /// The version released by this one: itself without its prerelease part.
+    #[must_use]
+    pub const fn stable(&self) -> Self {
+        Self::new(self.major, self.minor, self.patch)
+    }
+}
+
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.major, self.minor, self.patch)
+            .cmp(&(other.major, other.minor, other.patch))
+            .then_with(|| match (self.pre, other.pre) {
+                (None, None) => Ordering::Equal,
+                (None, Some(_)) => Ordering::Greater,
+                (Some(_), None) => Ordering::Less,
+                (Some(a), Some(b)) => a.cmp(&b),
+            })
+    }
+}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)?;
+        if let Some((channel, number)) = self.pre {
+            write!(f, "-{}.{number}", channel.name())?;
+        }
+        Ok(())
+    }
+}
+
+/// The highest version among `tags` that carry `prefix`. Tags that are not a
+/// supported version after the prefix are ignored.
+#[must_use]
+pub fn latest_version(tags: &[String], prefix: &str) -> Option<Version> {
+    tags.iter()
+        .filter_map(|tag| tag.strip_prefix(prefix).and_then(Version::parse))
+        .max()
+}
+
+/// Bumps a stable `version` by `bump`.
+const fn bump_stable(version: Version, bump: Bump) -> Version {
+    match bump {
+        Bump::Major => Version::new(version.major + 1, 0, 0),
+        Bump::Minor => Version::new(version.major, version.minor + 1, 0),
+        Bump::Patch => Version::new(version.major, version.minor, version.patch + 1),
+    }
+}
+
+/// The version released after `current` (`0.0.0` when there is none yet).
+///
+/// Bumping a prerelease first releases it, as long as it already bumps that part:
+/// `1.3.0-rc.1` with `--minor` gives `1.3.0`, but with `--major` gives `2.0.0`.
+///
+/// # Errors
+/// * If `channel` comes before the channel of the current prerelease
+pub fn next_version(
+    current: Option<Version>,
+    bump: Option<Bump>,
+    channel: Option<Channel>,
+) -> Result<Version> {
+    let current = current.unwrap_or(Version::new(0, 0, 0));
+    let Some((current_channel, number)) = current.pre else {
+        let next = bump_stable(current, bump.unwrap_or(Bump::Patch));
+        return Ok(Version {
+            pre: channel.map(|channel| (channel, 1)),
+            ..next
+        });
+    };
+
+    let released = current.stable();
+    let base = match bump {
+        Some(Bump::Major) if released.minor != 0 || released.patch != 0 => {
+            bump_stable(released, Bump::Major)
+        }
+        Some(Bump::Minor) if released.patch != 0 => bump_stable(released, Bump::Minor),
+        _ => released,
+    };
+    let pre = match channel {
+        None => None,
+        Some(channel) if base != released => Some((channel, 1)),
+        Some(channel) => match channel.cmp(&current_channel) {
+            Ordering::Equal => Some((channel, number + 1)),
+            Ordering::Greater => Some((channel, 1)),
+            Ordering::Less => {
+                return Err(RonaError::InvalidInput(format!(
+                    "{current} is already past {}; release {released} or bump the version",
+                    channel.name()
+                )));
+            }
+        },
+    };
+    Ok(Version { pre, ..base })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn next(current: &str, bump: Option<Bump>, channel: Option<Channel>) -> Result<String> {
+        next_version(Version::parse(current), bump, channel).map(|v| v.to_string())
+    }
+
+    #[test]
+    fn test_parse_and_order_versions() {
+        assert_eq!(Version::parse("1.2.3"), Some(Version::new(1, 2, 3)));
+        assert_eq!(
+            Version::parse("1.3.0-rc.2").map(|v| v.to_string()),
+            Some("1.3.0-rc.2".to_string())
+        );
+        for invalid in [
+            "1.2",
+            "1.2.3.4",
+            "01.2.3",
+            "1.2.3-pre.1",
+            "1.2.3-rc",
+            "v1.2.3",
+        ] {
+            assert_eq!(Version::parse(invalid), None, "{invalid}");
+        }
+
+        let tags: Vec<String> = [
+            "v1.2.0",
+            "v1.3.0-beta.1",
+            "v1.3.0-rc.1",
+            "v1.10.0-alpha.3",
+            "other",
+        ]
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+        assert_eq!(
+            latest_version(&tags, "v").map(|v| v.to_string()),
+            Some("1.10.0-alpha.3".to_string())
+        );
+        assert!(Version::parse("1.3.0") > Version::parse("1.3.0-rc.9"));
+        assert_eq!(latest_version(&tags, "pkg/v"), None);
+    }
+
+    #[test]
+    fn test_next_version() -> Result<()> {
+        assert_eq!(next("1.2.3", None, None)?, "1.2.4");
+        assert_eq!(next("1.2.3", Some(Bump::Major), None)?, "2.0.0");
+        assert_eq!(
+            next("1.2.3", Some(Bump::Minor), Some(Channel::Rc))?,
+            "1.3.0-rc.1"
+        );
+        assert_eq!(next("", Some(Bump::Minor), None)?, "0.1.0");
+
+        assert_eq!(next("1.3.0-rc.1", None, Some(Channel::Rc))?, "1.3.0-rc.2");
+        assert_eq!(next("1.3.0-beta.2", None, Some(Channel::Rc))?, "1.3.0-rc.1");
+        assert_eq!(next("1.3.0-rc.2", None, None)?, "1.3.0");
+        assert_eq!(next("1.3.0-rc.2", Some(Bump::Minor), None)?, "1.3.0");
+        assert_eq!(
+            next("1.3.0-rc.2", Some(Bump::Major), Some(Channel::Beta))?,
+            "2.0.0-beta.1"
+        );
+        assert_eq!(
+            next("1.3.1-rc.1", Some(Bump::Minor), Some(Channel::Rc))?,
+            "1.4.0-rc.1"
+        );
+
+        let error = next("1.3.0-rc.1", None, Some(Channel::Beta));
+        assert!(
+            matches!(&error, Err(RonaError::InvalidInput(m)) if m.contains("already past beta")),
+            "{error:?}"
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,472 @@
+//! Output Schemas
+//!
+//! Every JSON document rona prints for tools (`show --json`, `usage --json`, `env
+//! --json`, `history ops --json`, and the commands supporting `--output json`) carries a
+//! `schema_version`. `rona schema` prints the JSON Schema of
+//! each output, so tools can validate what they read and pin the version they were
+//! written against.
+//!
+//! Adding a field keeps the version, since the schemas allow unknown properties.
+//! Removing or renaming a field, or changing its type, bumps [`SCHEMA_VERSION`].
+
+use serde::Serialize;
+use serde_json::{Value, json};
+
+use crate::errors::{Result, RonaError};
+
+/// Version of the JSON outputs, written as `schema_version` in each of them.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// The names `rona schema` accepts, one per JSON output.
+pub const OUTPUT_NAMES: [&str; 9] = [
+    "add-dry-run",
+    "commit-dry-run",
+    "env",
+    "history-ops",
+    "list-status",
+    "push-dry-run",
+    "show",
+    "status",
+    "usage",
+];
+
+/// An output with the `schema_version` field in front of its own fields.
+#[derive(Debug, Serialize)]
+struct Versioned<'a, T> {
+    schema_version: u32,
+    #[serde(flatten)]
+    output: &'a T,
+}
+
+/// Serializes an output for `--json`, with its `schema_version`.
+///
+/// # Arguments
+/// * `output` - The value to print, which must serialize to a JSON object
+/// * `pretty` - Indent the JSON instead of writing a single line
+///
+/// # Errors
+/// * If the value cannot be serialized
+pub fn to_versioned_json<T: Serialize>(output: &T, pretty: bool) -> Result<String> {
+    let versioned = Versioned {
+        schema_version: SCHEMA_VERSION,
+        output,
+    };
+    let json = if pretty {
+        serde_json::to_string_pretty(&versioned)
+    } else {
+        serde_json::to_string(&versioned)
+    };
+    json.map_err(|e| RonaError::InvalidInput(format!("Cannot serialize output: {e}")))
+}
+
+/// The JSON Schema of one output, by the name `rona schema` takes. Every property is
+/// required; properties that can be missing are nullable instead.
+#[must_use]
+pub fn output_schema(name: &str) -> Option<Value> {
+    let (title, description, properties) = output_properties(name)?;
+    let mut required = vec!["schema_version".to_string()];
+    required.extend(
+        properties
+            .as_object()
+            .into_iter()
+            .flat_map(|p| p.keys().cloned()),
+    );
+
+    let mut schema = json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "title": title,
+        "description": description,
+        "type": "object",
+        "required": required,
+        "properties": {
+            "schema_version": { "const": SCHEMA_VERSION },
+        },
+    });
+    if let (Some(all), Value::Object(own)) = (schema["properties"].as_object_mut(), properties) {
+        all.extend(own);
+    }
+    Some(schema)
+}
+
+/// The title, description and own properties of an output's schema.
+fn output_properties(name: &str) -> Option<(&'static str, &'static str, Value)> {
+    Some(match name {
+        "history-ops" => (
+            "rona history ops --json",
+            "One operation of the log, printed as one JSON object per line, newest first.",
+            json!({
+                "time": { "type": "string", "description": "Local time the operation finished, in RFC 3339" },
+                "command": { "type": "string", "description": "The rona command, e.g. `commit`" },
+                "args": { "type": "array", "items": { "type": "string" }, "description": "The command-line arguments after the program name" },
+                "branch": { "type": ["string", "null"], "description": "The branch checked out after the operation, null when detached" },
+                "head_before": { "type": ["string", "null"], "description": "HEAD before the operation, null in a repository without commits" },
+                "head_after": { "type": ["string", "null"], "description": "HEAD after the operation" },
+            }),
+        ),
+        "show" => (
+            "rona show --json",
+            "A commit with its template fields, trailers, notes, signature and diff stat.",
+            json!({
+                "sha": { "type": "string" },
+                "author": { "type": "string" },
+                "email": { "type": "string" },
+                "date": { "type": "string", "description": "Author date, in strict ISO 8601" },
+                "subject": { "type": "string" },
+                "body": { "type": "string" },
+                "header_fields": {
+                    "type": ["array", "null"],
+                    "description": "The template variables in the subject, null when it does not match the template",
+                    "items": name_value_schema("name"),
+                },
+                "trailers": { "type": "array", "items": name_value_schema("key") },
+                "notes": { "type": ["string", "null"] },
+                "signature": {
+                    "type": "object",
+                    "required": ["status", "signer"],
+                    "properties": {
+                        "status": {
+                            "enum": ["good", "bad", "unknown-validity", "expired", "expired-key", "revoked", "unverifiable", "none"],
+                        },
+                        "signer": { "type": ["string", "null"] },
+                    },
+                },
+                "stat": { "type": "string", "description": "Output of `git show --stat`" },
+            }),
+        ),
+        "env" => (
+            "rona env --json",
+            "What rona resolved for the current directory.",
+            env_properties(),
+        ),
+        "usage" => (
+            "rona usage --json",
+            "A summary of the operation log of the repository.",
+            json!({
+                "operations": { "type": "integer", "minimum": 0 },
+                "first": { "type": ["string", "null"], "description": "Time of the oldest operation, in RFC 3339" },
+                "last": { "type": ["string", "null"], "description": "Time of the newest operation, in RFC 3339" },
+                "commands": {
+                    "type": "object",
+                    "description": "How often each command ran",
+                    "additionalProperties": { "type": "integer", "minimum": 0 },
+                },
+                "measured_commits": { "type": "integer", "minimum": 0 },
+                "average_commit_files": { "type": ["number", "null"] },
+                "average_commit_lines": { "type": ["number", "null"] },
+                "generated_commits": { "type": "integer", "minimum": 0 },
+                "median_generate_to_commit_secs": { "type": ["integer", "null"] },
+                "average_generate_to_commit_secs": { "type": ["integer", "null"] },
+            }),
+        ),
+        _ => return report_properties(name),
+    })
+}
+
+/// The title, description and own properties of the outputs of `--output json`.
+fn report_properties(name: &str) -> Option<(&'static str, &'static str, Value)> {
+    let strings = json!({ "type": "array", "items": { "type": "string" } });
+    let count = json!({ "type": "integer", "minimum": 0 });
+    Some(match name {
+        "add-dry-run" => (
+            "rona add-with-exclude --dry-run --output json",
+            "The files `rona -a` would stage.",
+            json!({
+                "add": { "type": "array", "items": { "type": "string" }, "description": "Changed files that would be staged" },
+                "delete": { "type": "array", "items": { "type": "string" }, "description": "Deleted files whose deletion would be staged" },
+                "excluded": { "type": "array", "items": { "type": "string" }, "description": "Changed files the exclude patterns leave out" },
+            }),
+        ),
+        "commit-dry-run" => (
+            "rona commit --dry-run --output json",
+            "The commit `rona commit` would create.",
+            json!({
+                "message": { "type": "string" },
+                "branch": { "type": ["string", "null"], "description": "null when HEAD is detached" },
+                "amend": { "type": "boolean" },
+                "signed": { "type": "boolean" },
+                "args": { "type": "array", "items": { "type": "string" }, "description": "Arguments passed on to `git commit`" },
+                "files": {
+                    "type": "array",
+                    "description": "The staged files",
+                    "items": {
+                        "type": "object",
+                        "required": ["path", "status"],
+                        "properties": {
+                            "path": { "type": "string" },
+                            "status": { "type": "string", "description": "e.g. `modified` or `new file`" },
+                        },
+                    },
+                },
+                "warnings": strings,
+            }),
+        ),
+        "list-status" => (
+            "rona list-status --output json",
+            "The changed files, relative to the repository root.",
+            json!({ "files": strings, "count": count }),
+        ),
+        "push-dry-run" => (
+            "rona push --dry-run --output json",
+            "What `rona push` would publish.",
+            push_preview_properties(),
+        ),
+        "status" => (
+            "rona status --output json",
+            "The branch, its upstream and the changed files.",
+            json!({
+                "branch": { "type": ["string", "null"], "description": "null when HEAD is detached" },
+                "head": { "type": ["string", "null"], "description": "null in a repository without commits" },
+                "upstream": { "type": ["string", "null"] },
+                "ahead": { "type": ["integer", "null"], "minimum": 0 },
+                "behind": { "type": ["integer", "null"], "minimum": 0 },
+                "files": {
+                    "type": "array",
+                    "description": "A file is listed once per state, e.g. staged and modified again",
+                    "items": {
+                        "type": "object",
+                        "required": ["path", "state"],
+                        "properties": {
+                            "path": { "type": "string", "description": "`old -> new` for a staged rename" },
+                            "state": {
+                                "enum": ["conflicted", "staged_new", "staged_modified", "staged_deleted", "staged_renamed", "modified", "deleted", "untracked"],
+                            },
+                        },
+                    },
+                },
+                "counts": {
+                    "type": "object",
+                    "required": ["staged", "modified", "deleted", "untracked", "conflicted"],
+                    "properties": {
+                        "staged": count,
+                        "modified": count,
+                        "deleted": count,
+                        "untracked": count,
+                        "conflicted": count,
+                    },
+                },
+            }),
+        ),
+        _ => return None,
+    })
+}
+
+/// The own properties of `rona push --dry-run --output json`.
+fn push_preview_properties() -> Value {
+    json!({
+        "remote": { "type": "string" },
+        "remote_url": { "type": ["string", "null"] },
+        "branch": { "type": "string" },
+        "old_tip": { "type": ["string", "null"], "description": "The remote branch tip, null when the branch is new there" },
+        "new_tip": { "type": "string" },
+        "subjects": { "type": "array", "items": { "type": "string" }, "description": "Subjects of the commits the remote does not have, newest first" },
+        "args": { "type": "array", "items": { "type": "string" }, "description": "Arguments passed on to `git push`" },
+        "pre_push": { "type": "array", "items": { "type": "string" }, "description": "The `[pre_push]` commands that would run first" },
+        "notify": { "type": ["string", "null"], "description": "Host of the webhook that would be notified" },
+        "tracker": {
+            "type": "array",
+            "description": "The `[tracker]` commands that would run after the push",
+            "items": {
+                "type": "object",
+                "required": ["ticket", "commit_type", "command", "subjects"],
+                "properties": {
+                    "ticket": { "type": "string" },
+                    "commit_type": { "type": "string" },
+                    "command": { "type": "string" },
+                    "subjects": { "type": "array", "items": { "type": "string" }, "description": "Subjects of the pushed commits of that type, newest first" },
+                },
+            },
+        },
+        "warnings": { "type": "array", "items": { "type": "string" } },
+    })
+}
+
+/// The own properties of `rona env --json`.
+fn env_properties() -> Value {
+    json!({
+        "version": { "type": "string", "description": "Version of rona" },
+        "git_version": { "type": ["string", "null"], "description": "Output of `git --version`, null when git cannot be run" },
+        "repo_root": { "type": ["string", "null"], "description": "null outside a repository" },
+        "git_dir": { "type": ["string", "null"], "description": "null outside a repository" },
+        "config_files": {
+            "type": "array",
+            "description": "The config files in effect, in loading order",
+            "items": {
+                "type": "object",
+                "required": ["path", "origin"],
+                "properties": {
+                    "path": { "type": "string" },
+                    "origin": { "type": "string", "description": "e.g. `global`, `project` or `extends`" },
+                },
+            },
+        },
+        "template": { "type": ["string", "null"], "description": "The `[templates]` entry in use, null for `commit_template` or the built-in one" },
+        "lang": { "type": ["string", "null"] },
+        "editor": {
+            "type": ["object", "null"],
+            "required": ["command", "source"],
+            "properties": {
+                "command": { "type": "string" },
+                "source": { "enum": ["config", "$VISUAL", "$EDITOR", "core.editor"] },
+            },
+        },
+        "signing": { "enum": ["auto", "required", "off", "denied"] },
+        "signing_key": { "type": "boolean", "description": "Whether git has a `user.signingkey`" },
+        "read_only": { "type": "boolean" },
+        "denied": {
+            "type": "array",
+            "description": "The features a `[[guards]]` entry denies",
+            "items": {
+                "type": "object",
+                "required": ["feature", "guard"],
+                "properties": {
+                    "feature": { "type": "string" },
+                    "guard": { "type": "string" },
+                },
+            },
+        },
+        "scope": { "type": ["string", "null"], "description": "The `--scope` name and directory" },
+        "sparse_checkout": { "type": "boolean" },
+        "status_source": { "enum": ["daemon", "git status"] },
+    })
+}
+
+/// An object with a `key` (or `name`) and a `value`, both strings.
+fn name_value_schema(key: &str) -> Value {
+    json!({
+        "type": "object",
+        "required": [key, "value"],
+        "properties": {
+            key: { "type": "string" },
+            "value": { "type": "string" },
+        },
+    })
+}
+
+/// The schemas of every output, by name, under the current `schema_version`.
+#[must_use]
+pub fn all_schemas() -> Value {
+    let outputs: serde_json::Map<String, Value> = OUTPUT_NAMES
+        .iter()
+        .filter_map(|name| output_schema(name).map(|schema| ((*name).to_string(), schema)))
+        .collect();
+    json!({ "schema_version": SCHEMA_VERSION, "outputs": outputs })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::environment::Environment;
+    use crate::git::{
+        CommitPreview, FileState, PushPreview, PushSummary, StagingPreview, StatusReport,
+        oplog::Operation,
+        show::{CommitDetails, Signature},
+        usage::UsageReport,
+    };
+
+    /// The top-level keys of a JSON object, sorted.
+    fn keys(value: &Value) -> Vec<String> {
+        let mut keys: Vec<String> = value
+            .as_object()
+            .map(|object| object.keys().cloned().collect())
+            .unwrap_or_default();
+        keys.sort();
+        keys
+    }
+
+    #[test]
+    fn test_schemas_match_outputs() -> Result<()> {
+        let operation = Operation {
+            time: "2026-10-18T10:00:00+02:00".to_string(),
+            command: "commit".to_string(),
+            args: vec!["-c".to_string()],
+            branch: None,
+            head_before: None,
+            head_after: None,
+        };
+        let commit = CommitDetails {
+            sha: "abc".to_string(),
+            author: "Ada".to_string(),
+            email: "ada@example.com".to_string(),
+            date: "2026-10-18T10:00:00+02:00".to_string(),
+            subject: "[1] (feat on main) Add login".to_string(),
+            body: String::new(),
+            header_fields: None,
+            trailers: Vec::new(),
+            notes: None,
+            signature: Signature {
+                status: "none",
+                signer: None,
+            },
+            stat: String::new(),
+        };
+        let commit_preview = CommitPreview {
+            message: "[1] (feat on main) Add login".to_string(),
+            branch: Some("main".to_string()),
+            amend: false,
+            signed: false,
+            args: Vec::new(),
+            files: Vec::new(),
+            warnings: Vec::new(),
+        };
+        let push_preview = PushPreview {
+            summary: PushSummary {
+                remote: "origin".to_string(),
+                remote_url: None,
+                branch: "main".to_string(),
+                old_tip: None,
+                new_tip: "abc".to_string(),
+                subjects: Vec::new(),
+            },
+            args: Vec::new(),
+            pre_push: Vec::new(),
+            notify: None,
+            tracker: Vec::new(),
+            warnings: Vec::new(),
+        };
+        let status = StatusReport::new(
+            None,
+            None,
+            None,
+            vec![(FileState::StagedNew, "a.rs".to_string())],
+        );
+        let outputs = [
+            (
+                "add-dry-run",
+                to_versioned_json(&StagingPreview::default(), false)?,
+            ),
+            ("commit-dry-run", to_versioned_json(&commit_preview, false)?),
+            ("env", to_versioned_json(&Environment::default(), true)?),
+            ("history-ops", to_versioned_json(&operation, false)?),
+            (
+                "list-status",
+                to_versioned_json(&json!({ "files": [], "count": 0 }), false)?,
+            ),
+            ("push-dry-run", to_versioned_json(&push_preview, false)?),
+            ("show", to_versioned_json(&commit, true)?),
+            ("status", to_versioned_json(&status, false)?),
+            ("usage", to_versioned_json(&UsageReport::default(), true)?),
+        ];
+
+        for (name, json) in outputs {
+            let output: Value =
+                serde_json::from_str(&json).map_err(|e| RonaError::InvalidInput(e.to_string()))?;
+            let schema = output_schema(name)
+                .ok_or_else(|| RonaError::InvalidInput(format!("no schema for {name}")))?;
+            assert_eq!(output["schema_version"], SCHEMA_VERSION);
+            assert_eq!(keys(&output), keys(&schema["properties"]), "{name}");
+
+            let mut required: Vec<String> = serde_json::from_value(schema["required"].clone())
+                .map_err(|e| RonaError::InvalidInput(e.to_string()))?;
+            required.sort();
+            assert_eq!(required, keys(&output), "{name}");
+        }
+        assert!(output_schema("log").is_none());
+        assert_eq!(
+            all_schemas()["outputs"]
+                .as_object()
+                .map(serde_json::Map::len),
+            Some(OUTPUT_NAMES.len())
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,258 @@
+//! Shell Commands
+//!
+//! Runs the shell commands of the config, such as the `[pre_push]` ones, through
+//! `sh -c` from the repository root. Each command may be given a time limit, so a
+//! hung formatter or test suite cannot block rona forever, a list of the environment
+//! variables it sees, and a number of output lines to keep: its output is then
+//! captured instead of streamed, and only its last lines are shown when it fails.
+
+use std::{
+    collections::VecDeque,
+    env,
+    io::{BufRead, BufReader},
+    path::Path,
+    process::{Child, Command, ExitStatus, Stdio},
+    sync::{Arc, Mutex, PoisonError, mpsc},
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::errors::{Result, RonaError};
+
+/// How often a command with a time limit is polled.
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// How long the output of a finished command is waited for, in case a process it
+/// left in the background holds the pipe open.
+const OUTPUT_GRACE: Duration = Duration::from_secs(1);
+
+/// The limits a shell command runs under.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Limits {
+    /// The command is killed when it runs longer
+    pub timeout: Option<Duration>,
+    /// The environment variables the command sees; all of them when `None`
+    pub pass_env: Option<Vec<String>>,
+    /// Captures the output, keeping its last lines; streamed when `None`
+    pub output_lines: Option<usize>,
+}
+
+/// How a shell command ended.
+#[derive(Debug)]
+pub struct Outcome {
+    /// The exit status, `None` when the command was killed after the timeout
+    pub status: Option<ExitStatus>,
+    /// The last lines of the output, when it is captured
+    pub tail: Vec<String>,
+}
+
+impl Outcome {
+    /// Whether the command finished in time and succeeded.
+    #[must_use]
+    pub fn success(&self) -> bool {
+        self.status.is_some_and(|status| status.success())
+    }
+}
+
+/// Runs `command` through `sh -c` in `dir`, under `limits`, with the `vars`
+/// environment variables set. Standard error is merged into the captured output.
+///
+/// # Errors
+/// * If the command cannot be started or waited for
+pub fn run_shell(
+    command: &str,
+    dir: &Path,
+    limits: &Limits,
+    vars: &[(&str, &str)],
+) -> Result<Outcome> {
+    let mut shell = Command::new("sh");
+    shell.current_dir(dir);
+    if let Some(names) = &limits.pass_env {
+        shell.env_clear().envs(
+            names
+                .iter()
+                .filter_map(|name| Some((name, env::var_os(name)?))),
+        );
+    }
+    shell.envs(vars.iter().copied());
+    // In its own process group, everything the command starts can be killed at once.
+    // Only with a time limit: the group does not get the Ctrl-C of the terminal.
+    #[cfg(unix)]
+    if limits.timeout.is_some() {
+        std::os::unix::process::CommandExt::process_group(&mut shell, 0);
+    }
+    if limits.output_lines.is_some() {
+        shell
+            .args(["-c", &format!("exec 2>&1\n{command}")])
+            .stdout(Stdio::piped());
+    } else {
+        shell.args(["-c", command]);
+    }
+
+    let mut child = shell.spawn().map_err(RonaError::Io)?;
+    let tail = Arc::new(Mutex::new(VecDeque::new()));
+    let done = limits
+        .output_lines
+        .zip(child.stdout.take())
+        .map(|(lines, stdout)| {
+            let (sender, receiver) = mpsc::channel();
+            let tail = Arc::clone(&tail);
+            // Not joined: a background process may keep the pipe open after the command.
+            thread::spawn(move || {
+                keep_last_lines(stdout, lines, &tail);
+                let _ = sender.send(());
+            });
+            receiver
+        });
+
+    let status = match limits.timeout {
+        Some(timeout) => wait_with_timeout(&mut child, timeout)?,
+        None => Some(child.wait().map_err(RonaError::Io)?),
+    };
+    if let Some(done) = done {
+        let _ = done.recv_timeout(OUTPUT_GRACE);
+    }
+
+    let tail = tail
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .drain(..)
+        .collect();
+    Ok(Outcome { status, tail })
+}
+
+/// Reads `output` line by line, keeping the last `lines` ones in `tail`.
+fn keep_last_lines(output: impl std::io::Read, lines: usize, tail: &Mutex<VecDeque<String>>) {
+    let mut reader = BufReader::new(output);
+    let mut line = Vec::new();
+    while reader
+        .read_until(b'\n', &mut line)
+        .is_ok_and(|read| read > 0)
+    {
+        let text = String::from_utf8_lossy(&line).trim_end().to_string();
+        line.clear();
+        push_line(
+            &mut tail.lock().unwrap_or_else(PoisonError::into_inner),
+            text,
+            lines,
+        );
+    }
+}
+
+/// Appends `line` to `tail`, dropping the oldest line beyond `lines`.
+fn push_line(tail: &mut VecDeque<String>, line: String, lines: usize) {
+    tail.push_back(line);
+    while tail.len() > lines {
+        tail.pop_front();
+    }
+}
+
+/// Waits for `child`, killing it once `timeout` has elapsed.
+///
+/// # Returns
+/// * The exit status, `None` when the child was killed
+fn wait_with_timeout(child: &mut Child, timeout: Duration) -> Result<Option<ExitStatus>> {
+    let start = Instant::now();
+    loop {
+        if let Some(status) = child.try_wait().map_err(RonaError::Io)? {
+            return Ok(Some(status));
+        }
+        if start.elapsed() >= timeout {
+            kill(child);
+            return Ok(None);
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/// Kills the shell and, on Unix, its process group, so every process the command
+/// started stops too, however deeply nested.
+fn kill(child: &mut Child) {
+    #[cfg(unix)]
+    let _ = Command::new("kill")
+        .args(["-KILL", "--", &format!("-{}", child.id())])
+        .stderr(Stdio::null())
+        .status();
+    let _ = child.kill();
+    let _ = child.wait();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(unix)]
+    fn test_run_shell_keeps_last_lines() -> Result<()> {
+        let limits = Limits {
+            output_lines: Some(2),
+            ..Limits::default()
+        };
+        let outcome = run_shell(
+            "echo one; echo two; echo three >&2; exit 3",
+            Path::new("."),
+            &limits,
+            &[],
+        )?;
+        assert!(!outcome.success());
+        assert_eq!(outcome.status.and_then(|status| status.code()), Some(3));
+        assert_eq!(outcome.tail, ["two", "three"]);
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_run_shell_timeout_kills_command() -> Result<()> {
+        let limits = Limits {
+            timeout: Some(Duration::from_millis(200)),
+            output_lines: Some(5),
+            ..Limits::default()
+        };
+        let start = Instant::now();
+        let outcome = run_shell("echo started; sleep 30", Path::new("."), &limits, &[])?;
+        assert!(outcome.status.is_none());
+        assert!(start.elapsed() < Duration::from_secs(10));
+        assert_eq!(outcome.tail, ["started"]);
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_run_shell_timeout_kills_nested_processes() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let limits = Limits {
+            timeout: Some(Duration::from_millis(200)),
+            ..Limits::default()
+        };
+        // Each shell waits on the next one, so `sleep` is three levels down.
+        let outcome = run_shell(
+            "sh -c \"sh -c 'sleep 1; touch leaked'; true\"; true",
+            dir.path(),
+            &limits,
+            &[],
+        )?;
+        assert!(outcome.status.is_none());
+        thread::sleep(Duration::from_millis(1500));
+        assert!(!dir.path().join("leaked").exists());
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_run_shell_pass_env() -> Result<()> {
+        let limits = Limits {
+            pass_env: Some(vec!["PATH".to_string()]),
+            output_lines: Some(5),
+            ..Limits::default()
+        };
+        let outcome = run_shell(
+            "test -n \"$PATH\" && test -z \"$HOME\" && echo clean",
+            Path::new("."),
+            &limits,
+            &[],
+        )?;
+        assert!(outcome.success());
+        assert_eq!(outcome.tail, ["clean"]);
+        Ok(())
+    }
+}
@@ -0,0 +1,183 @@
+//! Message Snippets
+//!
+//! Reusable blocks of text for commit message bodies, defined under `[snippets]` in the
+//! config (name → text). Snippet text is a commit template, so it can use the same
+//! variables as `commit_template` (`{commit_type}`, `{branch_name}`, extra fields, ...).
+//!
+//! In the interactive body prompt, a paragraph consisting only of `/name` is replaced
+//! by the rendered snippet. In editor mode, `rona snippet insert <name>` appends it to
+//! the message file with [`insert_snippet`].
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    hash::BuildHasher,
+};
+
+use crate::{
+    errors::{Result, RonaError},
+    template::{TemplateVariables, process_template, validate_template},
+};
+
+/// Returns the snippet name when `input` is a snippet reference such as `/migration`.
+///
+/// Only known snippets count, so text that merely starts with `/` (a path, say) is
+/// left as it is.
+#[must_use]
+pub fn snippet_reference<'a>(
+    input: &'a str,
+    library: &BTreeMap<String, String>,
+) -> Option<&'a str> {
+    input
+        .trim()
+        .strip_prefix('/')
+        .filter(|name| library.contains_key(*name))
+}
+
+/// Renders a snippet with the given template variables.
+///
+/// # Errors
+/// * If no snippet is named `name`
+/// * If the snippet uses unknown variables or malformed conditional blocks
+pub fn render_snippet<S: BuildHasher>(
+    library: &BTreeMap<String, String>,
+    name: &str,
+    variables: &TemplateVariables,
+    extra_values: &HashMap<String, String, S>,
+) -> Result<String> {
+    let text = library.get(name).ok_or_else(|| {
+        let available: Vec<&str> = library.keys().map(String::as_str).collect();
+        RonaError::InvalidInput(if available.is_empty() {
+            format!("Unknown snippet '{name}': no snippets are configured")
+        } else {
+            format!(
+                "Unknown snippet '{name}'. Available: {}",
+                available.join(", ")
+            )
+        })
+    })?;
+
+    let extra_names: Vec<&str> = extra_values.keys().map(String::as_str).collect();
+    validate_template(text, &extra_names)
+        .map_err(|e| RonaError::InvalidInput(format!("Snippet '{name}': {e}")))?;
+    Ok(process_template(text, variables, extra_values, false)?
+        .trim_end()
+        .to_string())
+}
+
+/// Inserts a rendered snippet at the end of a message, above git's comment lines.
+///
+/// Comments (`#`) and anything below git's scissors line are dropped by git, so the
+/// snippet goes before the first comment line to stay part of the message.
+#[must_use]
+pub fn insert_snippet(content: &str, snippet: &str) -> String {
+    let lines: Vec<&str> = content.lines().collect();
+    let split = lines
+        .iter()
+        .position(|line| line.starts_with('#'))
+        .unwrap_or(lines.len());
+    let (message, comments) = lines.split_at(split);
+
+    let message = message.join("\n");
+    let message = message.trim_end();
+    let mut result = String::new();
+    if !message.is_empty() {
+        result.push_str(message);
+        result.push_str("\n\n");
+    }
+    result.push_str(snippet);
+    result.push('\n');
+    if !comments.is_empty() {
+        result.push('\n');
+        result.push_str(&comments.join("\n"));
+        result.push('\n');
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type TestResult = std::result::Result<(), Box<dyn std::error::Error>>;
+
+    fn library() -> BTreeMap<String, String> {
+        BTreeMap::from([
+            (
+                "migration".to_string(),
+                "Migration: run `{task}` on {branch_name}\n".to_string(),
+            ),
+            ("review".to_string(), "- [ ] Tests\n- [ ] Docs".to_string()),
+        ])
+    }
+
+    fn variables() -> TemplateVariables {
+        TemplateVariables {
+            commit_number: Some(3),
+            commit_type: "feat".to_string(),
+            commit_emoji: String::new(),
+            ticket: String::new(),
+            branch_name: "login".to_string(),
+            message: String::new(),
+            date: "2024-01-15".to_string(),
+            time: "14:30:00".to_string(),
+            author: "Jane Doe".to_string(),
+            email: "jane@example.com".to_string(),
+            branch_description: String::new(),
+            staged_files: Vec::new(),
+            impact: Vec::new(),
+            changed_symbols: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_snippet_reference() {
+        let library = library();
+        assert_eq!(snippet_reference("/review", &library), Some("review"));
+        assert_eq!(
+            snippet_reference("  /migration ", &library),
+            Some("migration")
+        );
+        assert_eq!(snippet_reference("/usr/bin", &library), None);
+        assert_eq!(snippet_reference("review", &library), None);
+    }
+
+    #[test]
+    fn test_insert_snippet() {
+        assert_eq!(
+            insert_snippet("[1] (feat on x) add\n\n- `a.rs`: done\n\n", "- [ ] Docs"),
+            "[1] (feat on x) add\n\n- `a.rs`: done\n\n- [ ] Docs\n"
+        );
+        assert_eq!(
+            insert_snippet(
+                "subject\n\n# Please enter the commit message\n# On branch x\n",
+                "Notes"
+            ),
+            "subject\n\nNotes\n\n# Please enter the commit message\n# On branch x\n"
+        );
+        assert_eq!(insert_snippet("", "Notes"), "Notes\n");
+    }
+
+    #[test]
+    fn test_render_snippet() -> TestResult {
+        let library = library();
+        let extras = HashMap::from([("task".to_string(), "db:migrate".to_string())]);
+
+        assert_eq!(
+            render_snippet(&library, "migration", &variables(), &extras)?,
+            "Migration: run `db:migrate` on login"
+        );
+        assert_eq!(
+            render_snippet(&library, "review", &variables(), &HashMap::new())?,
+            "- [ ] Tests\n- [ ] Docs"
+        );
+
+        let err = render_snippet(&library, "nope", &variables(), &HashMap::new())
+            .err()
+            .ok_or("expected an error")?;
+        assert!(err.to_string().contains("Available: migration, review"));
+
+        // `{task}` is unknown without the extra field
+        assert!(render_snippet(&library, "migration", &variables(), &HashMap::new()).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,369 @@
+//! Changed Symbols
+//!
+//! Finds the definitions a staged change touches in Rust, JavaScript, TypeScript and
+//! Python files, with a tree-sitter parse of the file before and after the change.
+//! Functions, methods, types and classes are named as they appear in the code, with
+//! methods qualified by their type (`Session::login` in Rust, `Session.login`
+//! elsewhere).
+//!
+//! A changed line belongs to the innermost definition around it, so editing a method
+//! reports the method and not its class or `impl` block.
+
+use std::path::Path;
+
+use tree_sitter::{Language as Grammar, Node, Parser};
+
+/// A language whose definitions can be extracted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Language {
+    Rust,
+    JavaScript,
+    TypeScript,
+    Tsx,
+    Python,
+}
+
+impl Language {
+    /// The language of a file, from its extension.
+    #[must_use]
+    pub fn of(path: &str) -> Option<Self> {
+        match Path::new(path).extension()?.to_str()? {
+            "rs" => Some(Self::Rust),
+            "js" | "jsx" | "mjs" | "cjs" => Some(Self::JavaScript),
+            "ts" | "mts" | "cts" => Some(Self::TypeScript),
+            "tsx" => Some(Self::Tsx),
+            "py" | "pyi" => Some(Self::Python),
+            _ => None,
+        }
+    }
+
+    fn grammar(self) -> Grammar {
+        match self {
+            Self::Rust => tree_sitter_rust::LANGUAGE.into(),
+            Self::JavaScript => tree_sitter_javascript::LANGUAGE.into(),
+            Self::TypeScript => tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into(),
+            Self::Tsx => tree_sitter_typescript::LANGUAGE_TSX.into(),
+            Self::Python => tree_sitter_python::LANGUAGE.into(),
+        }
+    }
+
+    /// Separator between a type and its methods.
+    const fn separator(self) -> &'static str {
+        match self {
+            Self::Rust => "::",
+            _ => ".",
+        }
+    }
+
+    /// Node kinds that define a function.
+    const fn function_kinds(self) -> &'static [&'static str] {
+        match self {
+            Self::Rust => &["function_item", "function_signature_item"],
+            Self::JavaScript | Self::TypeScript | Self::Tsx => &[
+                "function_declaration",
+                "generator_function_declaration",
+                "method_definition",
+                "function_signature",
+                "method_signature",
+            ],
+            Self::Python => &["function_definition"],
+        }
+    }
+
+    /// Node kinds that define a type, with their name in the `name` field.
+    const fn type_kinds(self) -> &'static [&'static str] {
+        match self {
+            Self::Rust => &["struct_item", "enum_item", "union_item", "trait_item"],
+            Self::JavaScript => &["class_declaration"],
+            Self::TypeScript | Self::Tsx => &[
+                "class_declaration",
+                "abstract_class_declaration",
+                "interface_declaration",
+                "enum_declaration",
+                "type_alias_declaration",
+            ],
+            Self::Python => &["class_definition"],
+        }
+    }
+}
+
+/// A definition in a source file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Symbol {
+    /// The name, qualified by the enclosing type, with `()` after functions
+    pub label: String,
+    /// 1-based first line
+    pub start: usize,
+    /// 1-based last line
+    pub end: usize,
+}
+
+impl Symbol {
+    const fn contains(&self, line: usize) -> bool {
+        self.start <= line && line <= self.end
+    }
+
+    const fn span(&self) -> usize {
+        self.end - self.start
+    }
+}
+
+/// The definitions of `source`, in source order. `None` when the grammar cannot be
+/// loaded or the source not parsed.
+#[must_use]
+pub fn definitions(language: Language, source: &str) -> Option<Vec<Symbol>> {
+    let mut parser = Parser::new();
+    parser.set_language(&language.grammar()).ok()?;
+    let tree = parser.parse(source, None)?;
+    let mut symbols = Vec::new();
+    collect(language, tree.root_node(), source, None, &mut symbols);
+    Some(symbols)
+}
+
+/// Adds the definitions under `node` to `symbols`, qualified by `owner`.
+fn collect(
+    language: Language,
+    node: Node<'_>,
+    source: &str,
+    owner: Option<&str>,
+    symbols: &mut Vec<Symbol>,
+) {
+    let text = |node: Node<'_>| node.utf8_text(source.as_bytes()).ok().map(str::to_string);
+    let qualify = |name: String| {
+        owner.map_or_else(
+            || name.clone(),
+            |owner| format!("{owner}{}{name}", language.separator()),
+        )
+    };
+    let kind = node.kind();
+
+    let mut label = None;
+    let mut inner_owner = None;
+    if language.function_kinds().contains(&kind) {
+        label = node
+            .child_by_field_name("name")
+            .and_then(text)
+            .map(|name| format!("{}()", qualify(name)));
+    } else if language.type_kinds().contains(&kind) {
+        let name = node.child_by_field_name("name").and_then(text);
+        label.clone_from(&name);
+        inner_owner = name;
+    } else if kind == "impl_item" {
+        // `impl<T> Display for Session<T>` qualifies its methods with `Session`.
+        inner_owner = node.child_by_field_name("type").and_then(text).map(|name| {
+            name.split('<')
+                .next()
+                .unwrap_or_default()
+                .trim()
+                .to_string()
+        });
+    } else if kind == "class" {
+        inner_owner = node.child_by_field_name("name").and_then(text);
+    } else if kind == "variable_declarator"
+        && node.child_by_field_name("value").is_some_and(|value| {
+            matches!(
+                value.kind(),
+                "arrow_function" | "function_expression" | "function"
+            )
+        })
+    {
+        label = node
+            .child_by_field_name("name")
+            .and_then(text)
+            .map(|name| format!("{}()", qualify(name)));
+    }
+
+    if let Some(label) = label {
+        symbols.push(Symbol {
+            label,
+            start: node.start_position().row + 1,
+            end: node.end_position().row + 1,
+        });
+    }
+
+    let owner = inner_owner.as_deref().or(owner);
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        collect(language, child, source, owner, symbols);
+    }
+}
+
+/// The definitions a change adds, removes and changes, as labels.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SymbolChanges {
+    /// Definitions only in the new version, in source order
+    pub added: Vec<String>,
+    /// Definitions only in the old version, in source order
+    pub removed: Vec<String>,
+    /// Definitions in both versions around a changed line, in source order
+    pub changed: Vec<String>,
+}
+
+/// Compares the definitions of the `old` and `new` versions of a file, `None` for a
+/// version that does not exist. `old_lines` and `new_lines` are the 1-based lines the
+/// change removes and adds.
+#[must_use]
+pub fn symbol_changes(
+    language: Language,
+    old: Option<&str>,
+    new: Option<&str>,
+    old_lines: &[usize],
+    new_lines: &[usize],
+) -> Option<SymbolChanges> {
+    let old = match old {
+        Some(source) => definitions(language, source)?,
+        None => Vec::new(),
+    };
+    let new = match new {
+        Some(source) => definitions(language, source)?,
+        None => Vec::new(),
+    };
+    let defines = |symbols: &[Symbol], label: &str| symbols.iter().any(|s| s.label == label);
+
+    let mut changes = SymbolChanges::default();
+    for symbol in &new {
+        if !defines(&old, &symbol.label) {
+            push_unique(&mut changes.added, &symbol.label);
+        }
+    }
+    for symbol in &old {
+        if !defines(&new, &symbol.label) {
+            push_unique(&mut changes.removed, &symbol.label);
+        }
+    }
+
+    let mut touched: Vec<(usize, &str)> = new_lines
+        .iter()
+        .filter_map(|&line| innermost(&new, line).map(|s| (s.start, s.label.as_str())))
+        .chain(old_lines.iter().filter_map(|&line| {
+            innermost(&old, line).and_then(|s| {
+                // Order by where the definition is in the new version.
+                new.iter()
+                    .find(|n| n.label == s.label)
+                    .map(|n| (n.start, s.label.as_str()))
+            })
+        }))
+        .collect();
+    touched.sort_by_key(|&(start, _)| start);
+    for (_, label) in touched {
+        if defines(&old, label) && defines(&new, label) {
+            push_unique(&mut changes.changed, label);
+        }
+    }
+    Some(changes)
+}
+
+/// The smallest definition around `line`.
+fn innermost(symbols: &[Symbol], line: usize) -> Option<&Symbol> {
+    symbols
+        .iter()
+        .filter(|symbol| symbol.contains(line))
+        .min_by_key(|symbol| symbol.span())
+}
+
+fn push_unique(labels: &mut Vec<String>, label: &str) {
+    if !labels.iter().any(|l| l == label) {
+        labels.push(label.to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_definitions() -> Result<(), Box<dyn std::error::Error>> {
+        let rust = "\
+pub struct Session {
+    user: String,
+}
+
+impl<T> Display for Session<T> {
+    fn fmt(&self) {}
+}
+
+pub fn login() {}
+";
+        let labels = |language, source| -> Result<Vec<String>, &str> {
+            Ok(definitions(language, source)
+                .ok_or("no parse")?
+                .into_iter()
+                .map(|s| s.label)
+                .collect())
+        };
+        assert_eq!(
+            labels(Language::Rust, rust)?,
+            ["Session", "Session::fmt()", "login()"]
+        );
+
+        let typescript = "\
+export class Cart {
+  add(item: Item) {}
+}
+interface Item { id: string }
+const total = (items: Item[]) => 0;
+function checkout() {}
+";
+        assert_eq!(
+            labels(Language::TypeScript, typescript)?,
+            ["Cart", "Cart.add()", "Item", "total()", "checkout()"]
+        );
+
+        let python = "\
+class Parser:
+    @staticmethod
+    def parse(text):
+        pass
+
+def main():
+    pass
+";
+        assert_eq!(
+            labels(Language::Python, python)?,
+            ["Parser", "Parser.parse()", "main()"]
+        );
+
+        assert_eq!(Language::of("web/app.jsx"), Some(Language::JavaScript));
+        assert_eq!(Language::of("main.go"), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_symbol_changes() -> Result<(), Box<dyn std::error::Error>> {
+        let old = "\
+impl Session {
+    fn login(&self) {
+        check();
+    }
+
+    fn logout(&self) {}
+}
+";
+        let new = "\
+impl Session {
+    fn login(&self) {
+        check_password();
+    }
+
+    fn refresh(&self) {}
+}
+";
+        let changes = symbol_changes(Language::Rust, Some(old), Some(new), &[3, 6], &[3, 6])
+            .ok_or("no parse")?;
+        assert_eq!(changes.added, ["Session::refresh()"]);
+        assert_eq!(changes.removed, ["Session::logout()"]);
+        assert_eq!(changes.changed, ["Session::login()"]);
+
+        let changes = symbol_changes(
+            Language::Python,
+            None,
+            Some("def main():\n    pass\n"),
+            &[],
+            &[1, 2],
+        )
+        .ok_or("no parse")?;
+        assert_eq!(changes.added, ["main()"]);
+        assert!(changes.changed.is_empty());
+        Ok(())
+    }
+}
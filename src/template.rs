@@ -4,71 +4,320 @@
 //! It provides a flexible templating system that allows users to customize how their
 //! commit messages are formatted using variables.
 
-use chrono::Local;
+use chrono::{
+    DateTime, FixedOffset, Local, Locale, Utc,
+    format::{Item, StrftimeItems},
+};
 use regex::Regex;
-use std::{collections::HashMap, hash::BuildHasher};
+use std::{
+    collections::{BTreeMap, HashMap},
+    hash::BuildHasher,
+};
+
+use crate::{
+    errors::{Result, RonaError},
+    git::{
+        branch::{branch_description, get_current_branch, slugify},
+        common_staged_dir, get_staged_files, path_scope, staged_summaries,
+    },
+    impact::impact_labels,
+};
+
+/// Default chrono format of the `{date}` variable.
+pub const DEFAULT_DATE_FORMAT: &str = "%Y-%m-%d";
+
+/// Default chrono format of the `{time}` variable.
+pub const DEFAULT_TIME_FORMAT: &str = "%H:%M:%S";
+
+/// Built-in variables of commit message templates.
+pub const COMMIT_TEMPLATE_VARIABLES: [&str; 19] = [
+    "commit_number",
+    "commit_type",
+    "commit_emoji",
+    "branch_name",
+    "message",
+    "subject",
+    "body",
+    "date",
+    "time",
+    "author",
+    "email",
+    "branch_description",
+    "file_list",
+    "file_count",
+    "primary_path",
+    "scope",
+    "ticket",
+    "impact",
+    "changed_symbols",
+];
+
+/// Built-in variables of branch name templates; `type` is an alias of `branch_type`.
+pub const BRANCH_TEMPLATE_VARIABLES: [&str; 9] = [
+    "branch_type",
+    "type",
+    "description",
+    "slug",
+    "ticket",
+    "scope",
+    "date",
+    "time",
+    "author",
+];
+
+/// Variables of push notification templates.
+pub const PUSH_TEMPLATE_VARIABLES: [&str; 6] = [
+    "branch",
+    "remote",
+    "subjects",
+    "count",
+    "compare_url",
+    "author",
+];
+
+/// Variables of review ref templates (`[review] ref`).
+pub const REVIEW_REF_TEMPLATE_VARIABLES: [&str; 3] = ["branch", "target", "user"];
+
+/// Variables of release message templates (`[release] message`).
+pub const RELEASE_TEMPLATE_VARIABLES: [&str; 5] =
+    ["version", "tag", "channel", "previous", "package"];
+
+/// Ref `rona push --review` pushes to when `[review] ref` is not set: Gerrit's
+/// magic ref for the target branch.
+pub const DEFAULT_REVIEW_REF: &str = "refs/for/{target}";
+
+/// Variables of cover letter templates.
+pub const COVER_LETTER_TEMPLATE_VARIABLES: [&str; 5] =
+    ["branch", "count", "subjects", "changes", "author"];
+
+/// Variables of `rona copy` formats (`[copy] formats`).
+pub const COPY_TEMPLATE_VARIABLES: [&str; 7] = [
+    "sha",
+    "short_sha",
+    "subject",
+    "body",
+    "author",
+    "email",
+    "date",
+];
+
+/// A variable a template uses but that is not defined, with where it is.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownVariable {
+    /// The variable name, without braces or conditional markers
+    pub name: String,
+    /// 1-based line of the variable in the template
+    pub line: usize,
+    /// 1-based column, in characters
+    pub column: usize,
+}
 
-use crate::errors::{Result, RonaError};
+impl std::fmt::Display for UnknownVariable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{{{}}} at line {}, column {}",
+            self.name, self.line, self.column
+        )
+    }
+}
 
 /// Template variables that can be used in commit message templates
 #[derive(Debug, Clone)]
 pub struct TemplateVariables {
     pub commit_number: Option<u32>,
     pub commit_type: String,
+    /// The emoji of the commit type, from the table form of `commit_types`
+    pub commit_emoji: String,
     pub branch_name: String,
+    /// The ticket `ticket_pattern` finds in the current branch, e.g. `PROJ-123`
+    pub ticket: String,
+    /// The full message: a subject, optionally followed by a blank line and a body.
+    /// Exposed whole as `{message}` and split as `{subject}` and `{body}`.
     pub message: String,
     pub date: String,
     pub time: String,
     pub author: String,
     pub email: String,
+    /// The purpose of the current branch, from `rona branch describe`
+    pub branch_description: String,
+    /// The staged paths, sorted. Exposed as `{file_list}` (one `- path` line each),
+    /// `{file_count}`, `{primary_path}` (their deepest common directory) and `{scope}`
+    /// (see [`infer_scope`]).
+    pub staged_files: Vec<String>,
+    /// The `[impact]` labels of the staged files, exposed as `{impact}`
+    pub impact: Vec<String>,
+    /// The functions and types the staged change touches, such as `Session::login()`,
+    /// exposed comma-separated as `{changed_symbols}`
+    pub changed_symbols: Vec<String>,
 }
 
 impl TemplateVariables {
-    /// Creates a new `TemplateVariables` instance with current date/time and git info
+    /// Creates a new `TemplateVariables` instance with current date/time and git info.
+    ///
+    /// `{ticket}` is found by `ticket_pattern` in the current branch (see
+    /// [`extract_ticket`]), or in `branch_name` when `HEAD` is detached.
     ///
     /// # Errors
     /// * If git author information cannot be retrieved
+    /// * If `ticket_pattern` is not a valid regex
     pub fn new(
         commit_number: Option<u32>,
         commit_type: String,
         branch_name: String,
         message: String,
+        ticket_pattern: Option<&str>,
     ) -> Result<Self> {
         let (date, time) = {
             let now = Local::now();
 
             (
-                now.format("%Y-%m-%d").to_string(),
-                now.format("%H:%M:%S").to_string(),
+                now.format(DEFAULT_DATE_FORMAT).to_string(),
+                now.format(DEFAULT_TIME_FORMAT).to_string(),
             )
         };
 
         let (author, email) = get_git_author_info()?;
+        let branch = get_current_branch().ok();
+        let branch_description = branch
+            .as_deref()
+            .and_then(|branch| branch_description(branch).ok().flatten())
+            .unwrap_or_default();
+        let ticket = match ticket_pattern {
+            Some(pattern) => extract_ticket(branch.as_deref().unwrap_or(&branch_name), pattern)?,
+            None => String::new(),
+        };
+        let staged_files = get_staged_files()
+            .map(|entries| entries.into_iter().map(|entry| entry.path).collect())
+            .unwrap_or_default();
 
         Ok(Self {
             commit_number,
             commit_type,
+            commit_emoji: String::new(),
             branch_name,
+            ticket,
             message,
             date,
             time,
             author,
             email,
+            branch_description,
+            staged_files,
+            impact: Vec::new(),
+            changed_symbols: Vec::new(),
         })
     }
 
+    /// Formats `{date}` and `{time}` with the configured chrono formats (`date_format`,
+    /// `time_format`) and `timezone` instead of the defaults. Month and weekday names
+    /// (`%B`, `%A`, ...) are written in the language `lang` (see [`date_locale`]).
+    ///
+    /// A format or timezone that does not parse keeps the default; `rona template check`
+    /// reports it.
+    #[must_use]
+    pub fn with_date_formats(
+        mut self,
+        date_format: Option<&str>,
+        time_format: Option<&str>,
+        timezone: Option<&str>,
+        lang: Option<&str>,
+    ) -> Self {
+        let offset = timezone.and_then(|tz| parse_timezone(tz).ok().flatten());
+        let now: DateTime<FixedOffset> = offset.map_or_else(
+            || Local::now().fixed_offset(),
+            |offset| Utc::now().with_timezone(&offset),
+        );
+        let locale = lang.and_then(date_locale);
+        let date_format = date_format.filter(|f| validate_date_format(f).is_ok());
+        if date_format.is_some() || offset.is_some() {
+            self.date = format_date(&now, date_format.unwrap_or(DEFAULT_DATE_FORMAT), locale);
+        }
+        let time_format = time_format.filter(|f| validate_date_format(f).is_ok());
+        if time_format.is_some() || offset.is_some() {
+            self.time = format_date(&now, time_format.unwrap_or(DEFAULT_TIME_FORMAT), locale);
+        }
+        self
+    }
+
+    /// Sets `{commit_emoji}`, the emoji `commit_types` gives the commit type.
+    #[must_use]
+    pub fn with_commit_emoji(mut self, emoji: Option<&str>) -> Self {
+        self.commit_emoji = emoji.unwrap_or_default().to_string();
+        self
+    }
+
+    /// Sets `{impact}` to the labels `rules` (`[impact]`) give the staged files.
+    #[must_use]
+    pub fn with_impact(mut self, rules: &BTreeMap<String, String>) -> Self {
+        self.impact = impact_labels(rules, &self.staged_files);
+        self
+    }
+
+    /// Sets `{changed_symbols}` from the staged diff when `wanted`, since finding them
+    /// parses the staged files. A diff that cannot be read leaves it empty.
+    #[must_use]
+    pub fn with_changed_symbols(mut self, wanted: bool) -> Self {
+        if !wanted {
+            return self;
+        }
+        for summary in staged_summaries().unwrap_or_default().values() {
+            for symbol in summary.symbols() {
+                if !self.changed_symbols.iter().any(|s| s == symbol) {
+                    self.changed_symbols.push(symbol.to_string());
+                }
+            }
+        }
+        self
+    }
+
     /// Converts the variables to a `HashMap` for template substitution
     #[must_use]
     pub fn to_map(&self) -> HashMap<String, String> {
         let mut map = HashMap::new();
 
         map.insert("commit_type".to_string(), self.commit_type.clone());
+        map.insert("commit_emoji".to_string(), self.commit_emoji.clone());
         map.insert("branch_name".to_string(), self.branch_name.clone());
+        map.insert("ticket".to_string(), self.ticket.clone());
         map.insert("message".to_string(), self.message.clone());
+        let (subject, body) = self
+            .message
+            .split_once("\n\n")
+            .unwrap_or((&self.message, ""));
+        map.insert("subject".to_string(), subject.to_string());
+        map.insert("body".to_string(), body.to_string());
         map.insert("date".to_string(), self.date.clone());
         map.insert("time".to_string(), self.time.clone());
         map.insert("author".to_string(), self.author.clone());
         map.insert("email".to_string(), self.email.clone());
+        map.insert(
+            "branch_description".to_string(),
+            self.branch_description.clone(),
+        );
+        map.insert(
+            "file_list".to_string(),
+            self.staged_files
+                .iter()
+                .map(|path| format!("- {path}"))
+                .collect::<Vec<_>>()
+                .join("\n"),
+        );
+        map.insert(
+            "file_count".to_string(),
+            self.staged_files.len().to_string(),
+        );
+        let primary_path: Vec<String> = common_staged_dir(&self.staged_files)
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy().into_owned())
+            .collect();
+        map.insert("primary_path".to_string(), primary_path.join("/"));
+        map.insert("scope".to_string(), infer_scope(&self.staged_files));
+        map.insert("impact".to_string(), self.impact.join(", "));
+        map.insert(
+            "changed_symbols".to_string(),
+            self.changed_symbols.join(", "),
+        );
 
         if let Some(commit_number) = self.commit_number {
             map.insert("commit_number".to_string(), commit_number.to_string());
@@ -80,11 +329,50 @@ impl TemplateVariables {
     }
 }
 
+/// The scope of a conventional commit, inferred from the staged files.
+///
+/// It is the last directory of their common prefix, so `src/git/commit.rs` and
+/// `src/git/status.rs` give `git`. Empty when a staged file sits at the repository root.
+/// With `--scope`, it is the name of that scope instead.
+#[must_use]
+pub fn infer_scope(staged_files: &[String]) -> String {
+    if let Some(scope) = path_scope() {
+        return scope.name;
+    }
+    common_staged_dir(staged_files)
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+/// The ticket `pattern` finds in `branch`: the first capture group when the regex has
+/// one, else the whole match. Empty when it does not match.
+///
+/// # Errors
+/// * If `pattern` is not a valid regex
+pub fn extract_ticket(branch: &str, pattern: &str) -> Result<String> {
+    let regex = Regex::new(pattern).map_err(|e| {
+        RonaError::InvalidInput(format!("ticket_pattern: invalid regex '{pattern}': {e}"))
+    })?;
+    Ok(regex
+        .captures(branch)
+        .and_then(|captures| captures.get(1).or_else(|| captures.get(0)))
+        .map(|ticket| ticket.as_str().to_string())
+        .unwrap_or_default())
+}
+
 /// Branch-specific template variables for branch name generation.
 #[derive(Debug, Clone)]
 pub struct BranchTemplateVariables {
+    /// Exposed as `{branch_type}` and its short form `{type}`
     pub branch_type: String,
     pub description: String,
+    /// The description as a lowercase, dash-separated slug, exposed as `{slug}`
+    pub slug: String,
+    /// The ticket reference given with `rona branch new --ticket`; empty otherwise
+    pub ticket: String,
+    /// The name of the `--scope` directory; empty without one
+    pub scope: String,
     pub date: String,
     pub time: String,
     pub author: String,
@@ -95,14 +383,17 @@ impl BranchTemplateVariables {
     ///
     /// # Errors
     /// * If git author information cannot be retrieved
-    pub fn new(branch_type: String, description: String) -> Result<Self> {
+    pub fn new(branch_type: String, description: String, ticket: String) -> Result<Self> {
         let now = chrono::Local::now();
         let date = now.format("%Y-%m-%d").to_string();
         let time = now.format("%H:%M:%S").to_string();
         let (author, _email) = get_git_author_info()?;
         Ok(Self {
             branch_type,
+            slug: slugify(&description),
             description,
+            ticket,
+            scope: path_scope().map(|scope| scope.name).unwrap_or_default(),
             date,
             time,
             author,
@@ -114,7 +405,11 @@ impl BranchTemplateVariables {
     pub fn to_map(&self) -> HashMap<String, String> {
         let mut map = HashMap::new();
         map.insert("branch_type".to_string(), self.branch_type.clone());
+        map.insert("type".to_string(), self.branch_type.clone());
         map.insert("description".to_string(), self.description.clone());
+        map.insert("slug".to_string(), self.slug.clone());
+        map.insert("ticket".to_string(), self.ticket.clone());
+        map.insert("scope".to_string(), self.scope.clone());
         map.insert("date".to_string(), self.date.clone());
         map.insert("time".to_string(), self.time.clone());
         map.insert("author".to_string(), self.author.clone());
@@ -122,65 +417,387 @@ impl BranchTemplateVariables {
     }
 }
 
-/// Processes conditional blocks in a template string using a pre-merged variable map.
-fn process_conditional_blocks_from_map(
-    template: &str,
-    variable_map: &HashMap<String, String>,
-) -> Result<String> {
-    let mut result = template.to_string();
+/// Push notification template variables, for the `[notify.webhook]` template.
+#[derive(Debug, Clone)]
+pub struct PushTemplateVariables {
+    pub branch: String,
+    pub remote: String,
+    /// The pushed commit subjects, one `- subject` line each, newest first
+    pub subjects: String,
+    pub count: usize,
+    /// Link comparing the previous and new remote tips, empty when unknown
+    pub compare_url: String,
+    pub author: String,
+}
+
+impl PushTemplateVariables {
+    /// Creates a new `PushTemplateVariables` with the git author.
+    ///
+    /// # Errors
+    /// * If git author information cannot be retrieved
+    pub fn new(
+        branch: String,
+        remote: String,
+        subjects: &[String],
+        compare_url: Option<String>,
+    ) -> Result<Self> {
+        let (author, _email) = get_git_author_info()?;
+        Ok(Self {
+            branch,
+            remote,
+            subjects: subjects
+                .iter()
+                .map(|subject| format!("- {subject}"))
+                .collect::<Vec<_>>()
+                .join("\n"),
+            count: subjects.len(),
+            compare_url: compare_url.unwrap_or_default(),
+            author,
+        })
+    }
+
+    /// Converts the variables to a `HashMap` for template substitution.
+    #[must_use]
+    pub fn to_map(&self) -> HashMap<String, String> {
+        let mut map = HashMap::new();
+        map.insert("branch".to_string(), self.branch.clone());
+        map.insert("remote".to_string(), self.remote.clone());
+        map.insert("subjects".to_string(), self.subjects.clone());
+        map.insert("count".to_string(), self.count.to_string());
+        map.insert("compare_url".to_string(), self.compare_url.clone());
+        map.insert("author".to_string(), self.author.clone());
+        map
+    }
+}
+
+/// Cover letter template variables, for the `[format_patch]` cover letter.
+#[derive(Debug, Clone)]
+pub struct CoverLetterTemplateVariables {
+    pub branch: String,
+    pub count: usize,
+    /// The series subjects, one `- subject` line each, oldest first
+    pub subjects: String,
+    /// The series messages grouped under `type:` headings
+    pub changes: String,
+    pub author: String,
+}
+
+impl CoverLetterTemplateVariables {
+    /// Creates a new `CoverLetterTemplateVariables` with the git author.
+    ///
+    /// # Errors
+    /// * If git author information cannot be retrieved
+    pub fn new(branch: String, subjects: &[String], changes: String) -> Result<Self> {
+        let (author, _email) = get_git_author_info()?;
+        Ok(Self {
+            branch,
+            count: subjects.len(),
+            subjects: subjects
+                .iter()
+                .map(|subject| format!("- {subject}"))
+                .collect::<Vec<_>>()
+                .join("\n"),
+            changes,
+            author,
+        })
+    }
+
+    /// Converts the variables to a `HashMap` for template substitution.
+    #[must_use]
+    pub fn to_map(&self) -> HashMap<String, String> {
+        let mut map = HashMap::new();
+        map.insert("branch".to_string(), self.branch.clone());
+        map.insert("count".to_string(), self.count.to_string());
+        map.insert("subjects".to_string(), self.subjects.clone());
+        map.insert("changes".to_string(), self.changes.clone());
+        map.insert("author".to_string(), self.author.clone());
+        map
+    }
+}
+
+/// Review ref template variables, for `rona push --review`.
+#[derive(Debug, Clone)]
+pub struct ReviewRefVariables {
+    /// The branch being pushed
+    pub branch: String,
+    /// The branch the change is meant for
+    pub target: String,
+    /// The local part of git `user.email`, else the slugified `user.name`
+    pub user: String,
+}
+
+impl ReviewRefVariables {
+    /// Creates a new `ReviewRefVariables` with the user taken from git config.
+    ///
+    /// # Errors
+    /// * If git author information cannot be retrieved
+    pub fn new(branch: String, target: String) -> Result<Self> {
+        let (name, email) = get_git_author_info()?;
+        let user = email
+            .split('@')
+            .next()
+            .filter(|local| !local.is_empty())
+            .map_or_else(|| slugify(&name), ToString::to_string);
+        Ok(Self {
+            branch,
+            target,
+            user,
+        })
+    }
+
+    /// Converts the variables to a `HashMap` for template substitution.
+    #[must_use]
+    pub fn to_map(&self) -> HashMap<String, String> {
+        let mut map = HashMap::new();
+        map.insert("branch".to_string(), self.branch.clone());
+        map.insert("target".to_string(), self.target.clone());
+        map.insert("user".to_string(), self.user.clone());
+        map
+    }
+}
+
+/// Commit reference template variables, for the formats of `rona copy`.
+#[derive(Debug, Clone)]
+pub struct CopyTemplateVariables {
+    pub sha: String,
+    pub short_sha: String,
+    pub subject: String,
+    pub body: String,
+    pub author: String,
+    pub email: String,
+    /// Author date, as `YYYY-MM-DD`
+    pub date: String,
+}
+
+impl CopyTemplateVariables {
+    /// Converts the variables to a `HashMap` for template substitution.
+    #[must_use]
+    pub fn to_map(&self) -> HashMap<String, String> {
+        let mut map = HashMap::new();
+        map.insert("sha".to_string(), self.sha.clone());
+        map.insert("short_sha".to_string(), self.short_sha.clone());
+        map.insert("subject".to_string(), self.subject.clone());
+        map.insert("body".to_string(), self.body.clone());
+        map.insert("author".to_string(), self.author.clone());
+        map.insert("email".to_string(), self.email.clone());
+        map.insert("date".to_string(), self.date.clone());
+        map
+    }
+}
+
+/// Release message template variables, for `rona release`.
+#[derive(Debug, Clone)]
+pub struct ReleaseTemplateVariables {
+    /// The version released, e.g. `1.3.0-rc.1`
+    pub version: String,
+    /// The tag created for it, e.g. `v1.3.0-rc.1`
+    pub tag: String,
+    /// The prerelease channel (`alpha`, `beta`, `rc`), empty for a stable release
+    pub channel: String,
+    /// The version released before, empty for the first release
+    pub previous: String,
+    /// The package released with `--package`, empty for the whole repository
+    pub package: String,
+}
+
+impl ReleaseTemplateVariables {
+    /// Converts the variables to a `HashMap` for template substitution
+    #[must_use]
+    pub fn to_map(&self) -> HashMap<String, String> {
+        let mut map = HashMap::new();
+        map.insert("version".to_string(), self.version.clone());
+        map.insert("tag".to_string(), self.tag.clone());
+        map.insert("channel".to_string(), self.channel.clone());
+        map.insert("previous".to_string(), self.previous.clone());
+        map.insert("package".to_string(), self.package.clone());
+        map
+    }
+}
+
+/// A piece of a template: literal text (variables included), a conditional block,
+/// whose body may contain further blocks, or a closing tag without an open block.
+#[derive(Debug)]
+enum Segment<'a> {
+    Text(&'a str),
+    Block { name: &'a str, body: Vec<Self> },
+    StrayClose(&'a str),
+}
 
-    // Regex to find opening conditional tags: {?variable_name}
-    let open_regex = Regex::new(r"\{\?(\w+)\}").map_err(|e| {
+/// Parses the conditional blocks of a template into a tree.
+///
+/// Blocks nest, including blocks for the same variable: each `{/name}` closes the
+/// innermost open block. A closing tag without any open block of that name is kept,
+/// and rendering drops it like any unknown variable.
+///
+/// # Errors
+/// * If a block is never closed
+/// * If a closing tag closes an outer block while an inner one is still open
+fn parse_segments(template: &str) -> Result<Vec<Segment<'_>>> {
+    let tag_regex = Regex::new(r"\{([?/])(\w+)\}").map_err(|e| {
         RonaError::Io(std::io::Error::other(format!(
             "Invalid conditional regex: {e}"
         )))
     })?;
+    let (segments, _) = parse_body(template, &tag_regex, 0, &mut Vec::new())?;
+    Ok(segments)
+}
 
-    // Process conditional blocks iteratively
-    while let Some(open_match) = open_regex.find(&result) {
-        let open_start = open_match.start();
-        let open_end = open_match.end();
-
-        // Extract variable name from the opening tag
-        if let Some(captures) = open_regex.captures(&result[open_start..open_end]) {
-            let Some(cap) = captures.get(1) else {
-                break;
-            };
-            let var_name = cap.as_str();
-
-            // Look for the matching closing tag {/variable_name}
-            let close_pattern = format!("{{/{var_name}}}");
-            if let Some(close_pos) = result[open_end..].find(&close_pattern) {
-                let close_start = open_end + close_pos;
-                let close_end = close_start + close_pattern.len();
+/// Parses from `start` until the closing tag of the innermost block in `open`, or until
+/// the end of the template at the top level. Returns the segments and the position
+/// after the closing tag.
+fn parse_body<'a>(
+    template: &'a str,
+    tag_regex: &Regex,
+    start: usize,
+    open: &mut Vec<&'a str>,
+) -> Result<(Vec<Segment<'a>>, usize)> {
+    let mut segments = Vec::new();
+    let mut pos = start;
+    while let Some(tag) = tag_regex.captures_at(template, pos) {
+        let (Some(whole), Some(kind), Some(name)) = (tag.get(0), tag.get(1), tag.get(2)) else {
+            break;
+        };
+        if whole.start() > pos {
+            segments.push(Segment::Text(&template[pos..whole.start()]));
+        }
+        let name = name.as_str();
+        if kind.as_str() == "?" {
+            open.push(name);
+            let (body, end) = parse_body(template, tag_regex, whole.end(), open)?;
+            open.pop();
+            segments.push(Segment::Block { name, body });
+            pos = end;
+        } else if open.last() == Some(&name) {
+            return Ok((segments, whole.end()));
+        } else if open.contains(&name) {
+            let inner = open.last().copied().unwrap_or_default();
+            return Err(RonaError::Io(std::io::Error::other(format!(
+                "Overlapping conditional blocks: {{/{name}}} closes {{?{name}}} while {{?{inner}}} is still open"
+            ))));
+        } else {
+            segments.push(Segment::StrayClose(whole.as_str()));
+            pos = whole.end();
+        }
+    }
 
-                // Extract the content between opening and closing tags
-                let content = &result[open_end..close_start];
+    if let Some(name) = open.last() {
+        return Err(RonaError::Io(std::io::Error::other(format!(
+            "Unclosed conditional block: {{?{name}}}"
+        ))));
+    }
+    if pos < template.len() {
+        segments.push(Segment::Text(&template[pos..]));
+    }
+    Ok((segments, template.len()))
+}
 
-                // Check if variable has a non-empty value
-                let has_value = variable_map.get(var_name).is_some_and(|v| !v.is_empty());
+/// Renders parsed segments, keeping a block's body only when its variable has a
+/// non-empty value.
+fn render_segments(
+    segments: &[Segment<'_>],
+    variable_map: &HashMap<String, String>,
+    out: &mut String,
+) {
+    for segment in segments {
+        match segment {
+            Segment::Text(text) | Segment::StrayClose(text) => out.push_str(text),
+            Segment::Block { name, body } => {
+                if variable_map.get(*name).is_some_and(|v| !v.is_empty()) {
+                    render_segments(body, variable_map, out);
+                }
+            }
+        }
+    }
+}
 
-                // Replace the entire block
-                let replacement = if has_value { content } else { "" };
-                let full_block = &result[open_start..close_end];
-                result = result.replace(full_block, replacement);
-            } else {
+/// Finds the first check failure in parsed segments: a block for a variable that is not
+/// in `valid_variables`, or a closing tag without an open block.
+fn check_segments(segments: &[Segment<'_>], valid_variables: &[&str]) -> Result<()> {
+    for segment in segments {
+        match segment {
+            Segment::Text(_) => {}
+            Segment::StrayClose(tag) => {
                 return Err(RonaError::Io(std::io::Error::other(format!(
-                    "Unclosed conditional block: {{?{var_name}}}"
+                    "Unmatched closing tag: {tag}"
                 ))));
             }
+            Segment::Block { name, body } => {
+                if !valid_variables.contains(name) {
+                    return Err(RonaError::Io(std::io::Error::other(format!(
+                        "Unknown variable in conditional block: {{?{name}}}. Valid variables are: {}",
+                        valid_variables.join(", ")
+                    ))));
+                }
+                check_segments(body, valid_variables)?;
+            }
         }
     }
+    Ok(())
+}
+
+/// Finds the variables of a template, plain or in conditional tags, that `is_known`
+/// rejects, in template order. Each variable is reported once.
+fn find_unknown_variables(template: &str, is_known: impl Fn(&str) -> bool) -> Vec<UnknownVariable> {
+    let Ok(regex) = Regex::new(r"\{[?/]?([^{}?/]+)\}") else {
+        return Vec::new();
+    };
+    let mut unknown: Vec<UnknownVariable> = Vec::new();
+    for capture in regex.captures_iter(template) {
+        let (Some(whole), Some(name)) = (capture.get(0), capture.get(1)) else {
+            continue;
+        };
+        let name = name.as_str();
+        if is_known(name) || unknown.iter().any(|variable| variable.name == name) {
+            continue;
+        }
+        let before = &template[..whole.start()];
+        let line_start = before.rfind('\n').map_or(0, |pos| pos + 1);
+        unknown.push(UnknownVariable {
+            name: name.to_string(),
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+        });
+    }
+    unknown
+}
 
-    Ok(result)
+/// Lists the variables of a template that are not in `valid_variables`, with their
+/// positions, for `rona template check`.
+#[must_use]
+pub fn unknown_variables(template: &str, valid_variables: &[&str]) -> Vec<UnknownVariable> {
+    find_unknown_variables(template, |name| valid_variables.contains(&name))
+}
+
+/// Whether a template uses `variable`, plain or in conditional tags.
+#[must_use]
+pub fn uses_variable(template: &str, variable: &str) -> bool {
+    !find_unknown_variables(template, |name| name != variable).is_empty()
 }
 
 /// Core template substitution from a pre-merged variable map.
+///
+/// With `strict`, a variable missing from the map is an error naming it and its
+/// position; otherwise it is replaced with an empty string.
 fn process_template_from_map(
     template: &str,
     variable_map: &HashMap<String, String>,
+    strict: bool,
 ) -> Result<String> {
-    let after_conditionals = process_conditional_blocks_from_map(template, variable_map)?;
+    if strict
+        && let Some(unknown) =
+            find_unknown_variables(template, |name| variable_map.contains_key(name)).first()
+    {
+        return Err(RonaError::InvalidInput(format!(
+            "Unknown template variable {unknown}"
+        )));
+    }
+
+    let mut after_conditionals = String::with_capacity(template.len());
+    render_segments(
+        &parse_segments(template)?,
+        variable_map,
+        &mut after_conditionals,
+    );
 
     let regex = Regex::new(r"\{([^}]+)\}").map_err(|e| {
         RonaError::Io(std::io::Error::other(format!(
@@ -188,176 +805,309 @@ fn process_template_from_map(
         )))
     })?;
 
-    let mut result = after_conditionals.clone();
+    // Substitute in a single pass so values are inserted verbatim: a value that itself
+    // looks like `{message}` must not be expanded again by a later replacement.
+    let result = regex.replace_all(&after_conditionals, |capture: &regex::Captures<'_>| {
+        variable_map.get(&capture[1]).cloned().unwrap_or_default()
+    });
 
-    for capture in regex.captures_iter(&after_conditionals) {
-        if let Some(variable_name) = capture.get(1) {
-            let var_name = variable_name.as_str();
-            let empty_string = String::new();
-            let value = variable_map.get(var_name).unwrap_or(&empty_string);
-            result = result.replace(&capture[0], value);
-        }
-    }
-
-    Ok(result)
+    Ok(result.into_owned())
 }
 
 /// Processes a template string by substituting variables with their values.
 ///
+/// Unknown variables become empty strings, or an error with `strict`
+/// (`strict_templates`).
+///
 /// # Errors
 /// * If the template contains invalid variable syntax or mismatched conditional blocks
+/// * With `strict`, if the template uses a variable that is not defined
 pub fn process_template<S: BuildHasher>(
     template: &str,
     variables: &TemplateVariables,
     extra_variables: &HashMap<String, String, S>,
+    strict: bool,
 ) -> Result<String> {
     let mut variable_map = variables.to_map();
     variable_map.extend(extra_variables.iter().map(|(k, v)| (k.clone(), v.clone())));
-    process_template_from_map(template, &variable_map)
+    process_template_from_map(template, &variable_map, strict)
 }
 
 /// Processes a branch name template using `BranchTemplateVariables` and optional extra fields.
 ///
 /// Available built-in variables: `branch_type`, `description`, `date`, `time`, `author`.
+/// Unknown variables become empty strings, or an error with `strict`.
 ///
 /// # Errors
 /// * If the template contains invalid variable syntax or mismatched conditional blocks
+/// * With `strict`, if the template uses a variable that is not defined
 pub fn process_branch_template<S: BuildHasher>(
     template: &str,
     variables: &BranchTemplateVariables,
     extra_variables: &HashMap<String, String, S>,
+    strict: bool,
 ) -> Result<String> {
     let mut variable_map = variables.to_map();
     variable_map.extend(extra_variables.iter().map(|(k, v)| (k.clone(), v.clone())));
-    process_template_from_map(template, &variable_map)
+    process_template_from_map(template, &variable_map, strict)
 }
 
-/// Validates a template string against a provided set of valid variable names.
+/// Processes a push notification template.
+///
+/// Available variables: `branch`, `remote`, `subjects`, `count`, `compare_url`, `author`.
 ///
 /// # Errors
-/// * If the template contains unknown variables or mismatched conditional blocks
-fn validate_template_with_vars(template: &str, valid_variables: &[&str]) -> Result<()> {
-    // First, validate conditional blocks are properly matched
-    let conditional_regex = Regex::new(r"\{\?(\w+)\}").map_err(|e| {
-        RonaError::Io(std::io::Error::other(format!(
-            "Invalid conditional regex: {e}"
-        )))
-    })?;
+/// * If the template contains invalid variable syntax or mismatched conditional blocks
+pub fn process_push_template(template: &str, variables: &PushTemplateVariables) -> Result<String> {
+    process_template_from_map(template, &variables.to_map(), false)
+}
 
-    let closing_regex = Regex::new(r"\{/(\w+)\}")
-        .map_err(|e| RonaError::Io(std::io::Error::other(format!("Invalid closing regex: {e}"))))?;
+/// Processes a cover letter template.
+///
+/// Available variables: `branch`, `count`, `subjects`, `changes`, `author`.
+///
+/// # Errors
+/// * If the template contains invalid variable syntax or mismatched conditional blocks
+pub fn process_cover_letter_template(
+    template: &str,
+    variables: &CoverLetterTemplateVariables,
+) -> Result<String> {
+    process_template_from_map(template, &variables.to_map(), false)
+}
 
-    // Collect all opening and closing tags
-    let open_tags: Vec<(usize, &str)> = conditional_regex
-        .captures_iter(template)
-        .filter_map(|cap| {
-            let pos = cap.get(0)?.start();
-            let name = cap.get(1)?.as_str();
-            Some((pos, name))
-        })
-        .collect();
-
-    let mut close_tags: Vec<(usize, &str)> = closing_regex
-        .captures_iter(template)
-        .filter_map(|cap| {
-            let pos = cap.get(0)?.start();
-            let name = cap.get(1)?.as_str();
-            Some((pos, name))
-        })
-        .collect();
+/// Processes a review ref template.
+///
+/// Available variables: `branch`, `target`, `user`.
+///
+/// # Errors
+/// * If the template contains invalid variable syntax or mismatched conditional blocks
+pub fn process_review_ref_template(
+    template: &str,
+    variables: &ReviewRefVariables,
+) -> Result<String> {
+    process_template_from_map(template, &variables.to_map(), false)
+}
 
-    // Check that each opening tag has a matching closing tag
-    for (open_pos, open_name) in &open_tags {
-        let matching_close = close_tags
-            .iter()
-            .position(|(close_pos, close_name)| close_pos > open_pos && close_name == open_name);
+/// Processes a `rona copy` format.
+///
+/// Available variables: `sha`, `short_sha`, `subject`, `body`, `author`, `email`, `date`.
+///
+/// # Errors
+/// * If the template contains invalid variable syntax or mismatched conditional blocks
+pub fn process_copy_template(template: &str, variables: &CopyTemplateVariables) -> Result<String> {
+    process_template_from_map(template, &variables.to_map(), false)
+}
 
-        let Some(matching_close_idx) = matching_close else {
-            return Err(RonaError::Io(std::io::Error::other(format!(
-                "Unclosed conditional block: {{?{open_name}}}"
-            ))));
-        };
+/// Processes a release message template.
+///
+/// Available variables: `version`, `tag`, `channel`, `previous`, `package`.
+///
+/// # Errors
+/// * If the template contains invalid variable syntax or mismatched conditional blocks
+pub fn process_release_template(
+    template: &str,
+    variables: &ReleaseTemplateVariables,
+) -> Result<String> {
+    process_template_from_map(template, &variables.to_map(), false)
+}
 
-        // Validate that the variable in the conditional block is valid
-        if !valid_variables.contains(open_name) {
-            return Err(RonaError::Io(std::io::Error::other(format!(
-                "Unknown variable in conditional block: {{?{open_name}}}. Valid variables are: {}",
-                valid_variables.join(", ")
-            ))));
-        }
+/// Validates a template string against a provided set of valid variable names.
+///
+/// # Errors
+/// * If the template contains unknown variables or mismatched conditional blocks
+pub fn validate_template_with_vars(template: &str, valid_variables: &[&str]) -> Result<()> {
+    // First, validate conditional blocks are properly nested and closed
+    check_segments(&parse_segments(template)?, valid_variables)?;
 
-        close_tags.remove(matching_close_idx);
+    // Now validate regular variables; conditional names were checked above
+    if let Some(unknown) = unknown_variables(template, valid_variables).first() {
+        return Err(RonaError::Io(std::io::Error::other(format!(
+            "Unknown template variable: {unknown}. Valid variables are: {}",
+            valid_variables.join(", ")
+        ))));
     }
 
-    // Check for unmatched closing tags
-    if !close_tags.is_empty() {
-        let (_, unmatched_name) = close_tags[0];
+    Ok(())
+}
+
+/// Validates a chrono format string such as `date_format` (`%d/%m/%Y`).
+///
+/// # Errors
+/// * If the format contains an unknown or incomplete `%` specifier
+pub fn validate_date_format(format: &str) -> Result<()> {
+    if StrftimeItems::new(format).any(|item| item == Item::Error) {
         return Err(RonaError::Io(std::io::Error::other(format!(
-            "Unmatched closing tag: {{/{unmatched_name}}}"
+            "Invalid date format: {format}. See https://docs.rs/chrono/latest/chrono/format/strftime/"
         ))));
     }
+    Ok(())
+}
 
-    // Now validate regular variables (excluding conditional syntax)
-    let regex = Regex::new(r"\{([^}?/]+)\}").map_err(|e| {
-        RonaError::Io(std::io::Error::other(format!(
-            "Invalid template regex: {e}"
-        )))
-    })?;
+/// Formats `date` with a chrono format, with the names of `locale` when given.
+fn format_date(date: &DateTime<FixedOffset>, format: &str, locale: Option<Locale>) -> String {
+    locale.map_or_else(
+        || date.format(format).to_string(),
+        |locale| date.format_localized(format, locale).to_string(),
+    )
+}
 
-    for capture in regex.captures_iter(template) {
-        if let Some(variable_name) = capture.get(1) {
-            let var_name = variable_name.as_str();
-            // Skip if it's part of a conditional block syntax
-            if var_name.starts_with('?') || var_name.starts_with('/') {
-                continue;
-            }
-            if !valid_variables.contains(&var_name) {
-                return Err(RonaError::Io(std::io::Error::other(format!(
-                    "Unknown template variable: {{{var_name}}}. Valid variables are: {}",
-                    valid_variables.join(", ")
-                ))));
-            }
-        }
+/// The chrono locale whose month and weekday names are used for a `lang`.
+///
+/// `lang` is a locale name such as `fr_CA` or `pt-BR`, or a language code read as the
+/// language of its main country (`fr` as `fr_FR`, `en` as `en_US`). `None` for a
+/// language chrono has no names for.
+#[must_use]
+pub fn date_locale(lang: &str) -> Option<Locale> {
+    let name = lang.trim().replace('-', "_");
+    if name.contains('_') {
+        return Locale::try_from(name.as_str()).ok();
     }
+    let language = name.to_ascii_lowercase();
+    let country = match language.as_str() {
+        "en" => "US",
+        "ja" => "JP",
+        "zh" => "CN",
+        "ko" => "KR",
+        "sv" => "SE",
+        "da" => "DK",
+        "cs" => "CZ",
+        "el" => "GR",
+        "uk" => "UA",
+        "he" => "IL",
+        "nb" | "nn" => "NO",
+        "ca" => "ES",
+        "et" => "EE",
+        "sl" => "SI",
+        "vi" => "VN",
+        "hi" => "IN",
+        "ar" => "EG",
+        "fa" => "IR",
+        "ga" => "IE",
+        _ => "",
+    };
+    let country = if country.is_empty() {
+        language.to_ascii_uppercase()
+    } else {
+        country.to_string()
+    };
+    Locale::try_from(format!("{language}_{country}").as_str()).ok()
+}
 
-    Ok(())
+/// Parses a `timezone` setting: `"UTC"`, `"local"` or a fixed offset from UTC such as
+/// `"+02:00"` or `"-0530"`. Local time is `None`.
+///
+/// # Errors
+/// * If the value is none of these, or the offset is a day or more
+pub fn parse_timezone(timezone: &str) -> Result<Option<FixedOffset>> {
+    let invalid = || {
+        RonaError::InvalidInput(format!(
+            "Invalid timezone: {timezone}. Use \"UTC\", \"local\" or an offset such as \"+02:00\""
+        ))
+    };
+    let value = timezone.trim();
+    if value.eq_ignore_ascii_case("local") {
+        return Ok(None);
+    }
+    if value.eq_ignore_ascii_case("utc") || value == "Z" {
+        return Ok(FixedOffset::east_opt(0));
+    }
+
+    let (sign, offset) = match value.split_at_checked(1) {
+        Some(("+", offset)) => (1, offset),
+        Some(("-", offset)) => (-1, offset),
+        _ => return Err(invalid()),
+    };
+    let digits = offset.replacen(':', "", 1);
+    if digits.len() != 4 || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(invalid());
+    }
+    let hours: i32 = digits[..2].parse().map_err(|_| invalid())?;
+    let minutes: i32 = digits[2..].parse().map_err(|_| invalid())?;
+    if minutes >= 60 {
+        return Err(invalid());
+    }
+    FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60))
+        .map(Some)
+        .ok_or_else(invalid)
 }
 
 /// Validates a commit message template string.
 ///
 /// Valid built-in variables: `commit_number`, `commit_type`, `branch_name`, `message`,
-/// `date`, `time`, `author`, `email`. Extra field names are also accepted.
+/// `subject`, `body`, `date`, `time`, `author`, `email`, `branch_description`,
+/// `file_list`, `file_count`, `primary_path`, `scope`. Extra field names are also accepted.
 ///
 /// # Errors
 /// * If the template contains unknown variables or mismatched conditional blocks
 pub fn validate_template(template: &str, extra_variable_names: &[&str]) -> Result<()> {
-    let mut valid: Vec<&str> = vec![
-        "commit_number",
-        "commit_type",
-        "branch_name",
-        "message",
-        "date",
-        "time",
-        "author",
-        "email",
-    ];
+    let mut valid = COMMIT_TEMPLATE_VARIABLES.to_vec();
     valid.extend_from_slice(extra_variable_names);
     validate_template_with_vars(template, &valid)
 }
 
 /// Validates a branch name template string.
 ///
-/// Valid built-in variables: `branch_type`, `description`, `date`, `time`, `author`.
-/// Extra field names are also accepted.
+/// Valid built-in variables: `branch_type` (or `type`), `description`, `slug`, `ticket`,
+/// `scope`, `date`, `time`, `author`. Extra field names are also accepted.
 ///
 /// # Errors
 /// * If the template contains unknown variables or mismatched conditional blocks
 pub fn validate_branch_template(template: &str, extra_variable_names: &[&str]) -> Result<()> {
-    let mut valid: Vec<&str> = vec!["branch_type", "description", "date", "time", "author"];
+    let mut valid = BRANCH_TEMPLATE_VARIABLES.to_vec();
     valid.extend_from_slice(extra_variable_names);
     validate_template_with_vars(template, &valid)
 }
 
+/// Validates a push notification template.
+///
+/// Valid variables: `branch`, `remote`, `subjects`, `count`, `compare_url`, `author`.
+///
+/// # Errors
+/// * If the template contains unknown variables or mismatched conditional blocks
+pub fn validate_push_template(template: &str) -> Result<()> {
+    validate_template_with_vars(template, &PUSH_TEMPLATE_VARIABLES)
+}
+
+/// Validates a cover letter template.
+///
+/// Valid variables: `branch`, `count`, `subjects`, `changes`, `author`.
+///
+/// # Errors
+/// * If the template contains unknown variables or mismatched conditional blocks
+pub fn validate_cover_letter_template(template: &str) -> Result<()> {
+    validate_template_with_vars(template, &COVER_LETTER_TEMPLATE_VARIABLES)
+}
+
+/// Validates a review ref template.
+///
+/// Valid variables: `branch`, `target`, `user`.
+///
+/// # Errors
+/// * If the template contains unknown variables or mismatched conditional blocks
+pub fn validate_review_ref_template(template: &str) -> Result<()> {
+    validate_template_with_vars(template, &REVIEW_REF_TEMPLATE_VARIABLES)
+}
+
+/// Validates a `rona copy` format.
+///
+/// Valid variables: `sha`, `short_sha`, `subject`, `body`, `author`, `email`, `date`.
+///
+/// # Errors
+/// * If the template contains unknown variables or mismatched conditional blocks
+pub fn validate_copy_template(template: &str) -> Result<()> {
+    validate_template_with_vars(template, &COPY_TEMPLATE_VARIABLES)
+}
+
+/// Validates a release message template.
+///
+/// Valid variables: `version`, `tag`, `channel`, `previous`, `package`.
+///
+/// # Errors
+/// * If the template contains unknown variables or mismatched conditional blocks
+pub fn validate_release_template(template: &str) -> Result<()> {
+    validate_template_with_vars(template, &RELEASE_TEMPLATE_VARIABLES)
+}
+
 /// Gets the current git author name and email from git config.
 fn get_git_author_info() -> Result<(String, String)> {
     use std::process::Command;
@@ -401,15 +1151,21 @@ mod tests {
         let variables = TemplateVariables {
             commit_number: Some(42),
             commit_type: "feat".to_string(),
+            commit_emoji: String::new(),
+            ticket: String::new(),
             branch_name: "feature/new-feature".to_string(),
             message: "Add new functionality".to_string(),
             date: "2024-01-15".to_string(),
             time: "14:30:00".to_string(),
             author: "John Doe".to_string(),
             email: "john@example.com".to_string(),
+            branch_description: String::new(),
+            staged_files: Vec::new(),
+            impact: Vec::new(),
+            changed_symbols: Vec::new(),
         };
 
-        let result = process_template(template, &variables, &HashMap::new())?;
+        let result = process_template(template, &variables, &HashMap::new(), false)?;
         assert_eq!(
             result,
             "[42] (feat on feature/new-feature) Add new functionality"
@@ -425,15 +1181,21 @@ mod tests {
         let variables = TemplateVariables {
             commit_number: None,
             commit_type: "fix".to_string(),
+            commit_emoji: String::new(),
+            ticket: String::new(),
             branch_name: "main".to_string(),
             message: "Fix bug".to_string(),
             date: "2024-01-15".to_string(),
             time: "14:30:00".to_string(),
             author: "John Doe".to_string(),
             email: "john@example.com".to_string(),
+            branch_description: String::new(),
+            staged_files: Vec::new(),
+            impact: Vec::new(),
+            changed_symbols: Vec::new(),
         };
 
-        let result = process_template(template, &variables, &HashMap::new())?;
+        let result = process_template(template, &variables, &HashMap::new(), false)?;
         assert_eq!(result, "(fix on main) Fix bug");
 
         Ok(())
@@ -456,12 +1218,18 @@ mod tests {
         let variables = TemplateVariables {
             commit_number: Some(42),
             commit_type: "feat".to_string(),
+            commit_emoji: String::new(),
+            ticket: String::new(),
             branch_name: "feature/test".to_string(),
             message: "Test message".to_string(),
             date: "2024-01-15".to_string(),
             time: "14:30:00".to_string(),
             author: "Test Author".to_string(),
             email: "test@example.com".to_string(),
+            branch_description: String::new(),
+            staged_files: Vec::new(),
+            impact: Vec::new(),
+            changed_symbols: Vec::new(),
         };
 
         let map = variables.to_map();
@@ -492,21 +1260,143 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_extract_ticket() -> Result<()> {
+        let branch = "feat/PROJ-123-add-login";
+        assert_eq!(extract_ticket(branch, r"[A-Z]+-\d+")?, "PROJ-123");
+        assert_eq!(extract_ticket(branch, r"[A-Z]+-(\d+)")?, "123");
+        assert_eq!(extract_ticket("feat/add-login", r"[A-Z]+-\d+")?, "");
+        assert!(extract_ticket(branch, "[A-Z").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_staged_file_variables() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let mut variables = TemplateVariables {
+            commit_number: None,
+            commit_type: "refactor".to_string(),
+            commit_emoji: String::new(),
+            ticket: String::new(),
+            branch_name: "status".to_string(),
+            message: "Share status parsing".to_string(),
+            date: String::new(),
+            time: String::new(),
+            author: String::new(),
+            email: String::new(),
+            branch_description: String::new(),
+            staged_files: vec![
+                "src/git/commit.rs".to_string(),
+                "src/git/status.rs".to_string(),
+            ],
+            impact: Vec::new(),
+            changed_symbols: Vec::new(),
+        };
+
+        let template = "{commit_type}{?primary_path}({primary_path}){/primary_path}: {message} [{file_count}]\n\n{file_list}";
+        assert_eq!(
+            process_template(template, &variables, &HashMap::new(), false)?,
+            "refactor(src/git): Share status parsing [2]\n\n- src/git/commit.rs\n- src/git/status.rs"
+        );
+
+        let scoped = "{commit_type}{?scope}({scope}){/scope}: {message}";
+        assert_eq!(
+            process_template(scoped, &variables, &HashMap::new(), false)?,
+            "refactor(git): Share status parsing"
+        );
+        assert!(uses_variable(scoped, "scope"));
+        assert!(!uses_variable(template, "scope"));
+        let overridden = HashMap::from([("scope".to_string(), "status".to_string())]);
+        assert_eq!(
+            process_template(scoped, &variables, &overridden, false)?,
+            "refactor(status): Share status parsing"
+        );
+
+        let emoji = "{?commit_emoji}{commit_emoji} {/commit_emoji}{commit_type}: {message}";
+        assert_eq!(
+            process_template(emoji, &variables, &HashMap::new(), false)?,
+            "refactor: Share status parsing"
+        );
+        variables = variables.with_commit_emoji(Some("♻️"));
+        assert_eq!(
+            process_template(emoji, &variables, &HashMap::new(), false)?,
+            "♻️ refactor: Share status parsing"
+        );
+
+        variables.staged_files.push("README.md".to_string());
+        assert_eq!(
+            process_template(template, &variables, &HashMap::new(), false)?,
+            "refactor: Share status parsing [3]\n\n- src/git/commit.rs\n- src/git/status.rs\n- README.md"
+        );
+        assert_eq!(
+            process_template(scoped, &variables, &HashMap::new(), false)?,
+            "refactor: Share status parsing"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_subject_and_body_variables() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let mut variables = TemplateVariables {
+            commit_number: Some(7),
+            commit_type: "feat".to_string(),
+            commit_emoji: String::new(),
+            ticket: String::new(),
+            branch_name: "login".to_string(),
+            message: "Add login form\n\nValidates the email.\n\nRefs #12".to_string(),
+            date: "2024-01-15".to_string(),
+            time: "14:30:00".to_string(),
+            author: "Jane Doe".to_string(),
+            email: "jane@example.com".to_string(),
+            branch_description: String::new(),
+            staged_files: Vec::new(),
+            impact: Vec::new(),
+            changed_symbols: Vec::new(),
+        };
+
+        let template = "{commit_type}: {subject}{?body}\n\n{body}{/body}";
+        assert_eq!(
+            process_template(template, &variables, &HashMap::new(), false)?,
+            "feat: Add login form\n\nValidates the email.\n\nRefs #12"
+        );
+        assert_eq!(
+            process_template("{message}", &variables, &HashMap::new(), false)?,
+            variables.message
+        );
+
+        variables.message = "Fix typo".to_string();
+        let map = variables.to_map();
+        assert_eq!(map.get("subject").ok_or("subject not found")?, "Fix typo");
+        assert_eq!(map.get("body").ok_or("body not found")?, "");
+        assert_eq!(
+            process_template(template, &variables, &HashMap::new(), false)?,
+            "feat: Fix typo"
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn test_template_with_all_variables() -> std::result::Result<(), Box<dyn std::error::Error>> {
         let template = "{commit_type}: {message} by {author} <{email}> on {branch_name} at {date} {time} (#{commit_number})";
         let variables = TemplateVariables {
             commit_number: Some(123),
             commit_type: "fix".to_string(),
+            commit_emoji: String::new(),
+            ticket: String::new(),
             branch_name: "hotfix/critical-bug".to_string(),
             message: "Fix critical authentication bug".to_string(),
             date: "2024-01-15".to_string(),
             time: "14:30:00".to_string(),
             author: "Jane Doe".to_string(),
             email: "jane@company.com".to_string(),
+            branch_description: String::new(),
+            staged_files: Vec::new(),
+            impact: Vec::new(),
+            changed_symbols: Vec::new(),
         };
 
-        let result = process_template(template, &variables, &HashMap::new())?;
+        let result = process_template(template, &variables, &HashMap::new(), false)?;
         assert_eq!(
             result,
             "fix: Fix critical authentication bug by Jane Doe <jane@company.com> on hotfix/critical-bug at 2024-01-15 14:30:00 (#123)"
@@ -521,15 +1411,21 @@ mod tests {
         let variables = TemplateVariables {
             commit_number: None,
             commit_type: "feat".to_string(),
+            commit_emoji: String::new(),
+            ticket: String::new(),
             branch_name: "feature/new-feature".to_string(),
             message: "Add new feature".to_string(),
             date: "2024-01-15".to_string(),
             time: "14:30:00".to_string(),
             author: "John Doe".to_string(),
             email: "john@example.com".to_string(),
+            branch_description: String::new(),
+            staged_files: Vec::new(),
+            impact: Vec::new(),
+            changed_symbols: Vec::new(),
         };
 
-        let result = process_template(template, &variables, &HashMap::new())?;
+        let result = process_template(template, &variables, &HashMap::new(), false)?;
         assert_eq!(result, "* feat: Add new feature");
 
         Ok(())
@@ -542,15 +1438,21 @@ mod tests {
         let variables = TemplateVariables {
             commit_number: None,
             commit_type: "docs".to_string(),
+            commit_emoji: String::new(),
+            ticket: String::new(),
             branch_name: "main".to_string(),
             message: "Update documentation".to_string(),
             date: "2024-01-15".to_string(),
             time: "14:30:00".to_string(),
             author: "John Doe".to_string(),
             email: "john@example.com".to_string(),
+            branch_description: String::new(),
+            staged_files: Vec::new(),
+            impact: Vec::new(),
+            changed_symbols: Vec::new(),
         };
 
-        let result = process_template(template, &variables, &HashMap::new())?;
+        let result = process_template(template, &variables, &HashMap::new(), false)?;
         assert_eq!(result, "(docs on main) Update documentation");
 
         Ok(())
@@ -570,6 +1472,62 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_strict_template_rejects_unknown_variable()
+    -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let variables = TemplateVariables {
+            commit_number: None,
+            commit_type: "feat".to_string(),
+            commit_emoji: String::new(),
+            ticket: String::new(),
+            branch_name: "main".to_string(),
+            message: "Add form".to_string(),
+            date: String::new(),
+            time: String::new(),
+            author: String::new(),
+            email: String::new(),
+            branch_description: String::new(),
+            staged_files: Vec::new(),
+            impact: Vec::new(),
+            changed_symbols: Vec::new(),
+        };
+        let template = "({commit_typ} on {branch_name})\n{?issue}{issue}{/issue} {message}";
+
+        assert_eq!(
+            process_template(template, &variables, &HashMap::new(), false)?,
+            "( on main)\n Add form"
+        );
+        let Err(e) = process_template(template, &variables, &HashMap::new(), true) else {
+            return Err("Expected an error in strict mode".into());
+        };
+        assert_eq!(
+            e.to_string(),
+            "Invalid input: Unknown template variable {commit_typ} at line 1, column 2"
+        );
+
+        let unknown = unknown_variables(template, &COMMIT_TEMPLATE_VARIABLES);
+        assert_eq!(
+            unknown,
+            vec![
+                UnknownVariable {
+                    name: "commit_typ".to_string(),
+                    line: 1,
+                    column: 2,
+                },
+                UnknownVariable {
+                    name: "issue".to_string(),
+                    line: 2,
+                    column: 1,
+                },
+            ]
+        );
+        assert!(
+            unknown_variables(template, &["commit_typ", "branch_name", "issue", "message"])
+                .is_empty()
+        );
+        Ok(())
+    }
+
     /// REGRESSION TEST: This test would have caught the bug where using the default template
     /// with `no_commit_number` flag would produce empty brackets "[]"
     #[test]
@@ -580,15 +1538,21 @@ mod tests {
         let variables = TemplateVariables {
             commit_number: None,
             commit_type: "docs".to_string(),
+            commit_emoji: String::new(),
+            ticket: String::new(),
             branch_name: "main".to_string(),
             message: "Update docs".to_string(),
             date: "2024-01-15".to_string(),
             time: "14:30:00".to_string(),
             author: "John Doe".to_string(),
             email: "john@example.com".to_string(),
+            branch_description: String::new(),
+            staged_files: Vec::new(),
+            impact: Vec::new(),
+            changed_symbols: Vec::new(),
         };
 
-        let result = process_template(template, &variables, &HashMap::new())?;
+        let result = process_template(template, &variables, &HashMap::new(), false)?;
 
         // This demonstrates the bug: empty brackets appear
         assert_eq!(result, "[] (docs on main) Update docs");
@@ -611,15 +1575,21 @@ mod tests {
         let variables = TemplateVariables {
             commit_number: None,
             commit_type: "docs".to_string(),
+            commit_emoji: String::new(),
+            ticket: String::new(),
             branch_name: "main".to_string(),
             message: "Update docs".to_string(),
             date: "2024-01-15".to_string(),
             time: "14:30:00".to_string(),
             author: "John Doe".to_string(),
             email: "john@example.com".to_string(),
+            branch_description: String::new(),
+            staged_files: Vec::new(),
+            impact: Vec::new(),
+            changed_symbols: Vec::new(),
         };
 
-        let result = process_template(template, &variables, &HashMap::new())?;
+        let result = process_template(template, &variables, &HashMap::new(), false)?;
 
         // Correct output without empty brackets
         assert_eq!(result, "(docs on main) Update docs");
@@ -644,17 +1614,23 @@ mod tests {
         let variables = TemplateVariables {
             commit_number: None,
             commit_type: "feat".to_string(),
+            commit_emoji: String::new(),
+            ticket: String::new(),
             branch_name: "new-feature".to_string(),
             message: "Add feature".to_string(),
             date: "2024-01-15".to_string(),
             time: "14:30:00".to_string(),
             author: "Jane Doe".to_string(),
             email: "jane@example.com".to_string(),
+            branch_description: String::new(),
+            staged_files: Vec::new(),
+            impact: Vec::new(),
+            changed_symbols: Vec::new(),
         };
 
         // Test template WITH commit_number placeholder (produces empty brackets - the bug)
         let template_with = "[{commit_number}] {commit_type}: {message}";
-        let result_with = process_template(template_with, &variables, &HashMap::new())?;
+        let result_with = process_template(template_with, &variables, &HashMap::new(), false)?;
         assert!(
             result_with.starts_with("[]"),
             "Bug: produces empty brackets"
@@ -662,7 +1638,8 @@ mod tests {
 
         // Test template WITHOUT commit_number placeholder (correct)
         let template_without = "{commit_type}: {message}";
-        let result_without = process_template(template_without, &variables, &HashMap::new())?;
+        let result_without =
+            process_template(template_without, &variables, &HashMap::new(), false)?;
         assert_eq!(result_without, "feat: Add feature");
         assert!(
             !result_without.contains("[]"),
@@ -671,7 +1648,7 @@ mod tests {
 
         // Test template with optional-style syntax (shows limitation of current implementation)
         let template_prefix = "#{commit_number} {commit_type}: {message}";
-        let result_prefix = process_template(template_prefix, &variables, &HashMap::new())?;
+        let result_prefix = process_template(template_prefix, &variables, &HashMap::new(), false)?;
         assert_eq!(
             result_prefix, "# feat: Add feature",
             "Empty string for None values"
@@ -687,12 +1664,18 @@ mod tests {
         let variables = TemplateVariables {
             commit_number: None,
             commit_type: "test".to_string(),
+            commit_emoji: String::new(),
+            ticket: String::new(),
             branch_name: "testing".to_string(),
             message: "Test message".to_string(),
             date: "2024-01-15".to_string(),
             time: "14:30:00".to_string(),
             author: "Test User".to_string(),
             email: "test@example.com".to_string(),
+            branch_description: String::new(),
+            staged_files: Vec::new(),
+            impact: Vec::new(),
+            changed_symbols: Vec::new(),
         };
 
         let map = variables.to_map();
@@ -720,15 +1703,21 @@ mod tests {
         let variables = TemplateVariables {
             commit_number: Some(42),
             commit_type: "feat".to_string(),
+            commit_emoji: String::new(),
+            ticket: String::new(),
             branch_name: "new-feature".to_string(),
             message: "Add feature".to_string(),
             date: "2024-01-15".to_string(),
             time: "14:30:00".to_string(),
             author: "John Doe".to_string(),
             email: "john@example.com".to_string(),
+            branch_description: String::new(),
+            staged_files: Vec::new(),
+            impact: Vec::new(),
+            changed_symbols: Vec::new(),
         };
 
-        let result = process_template(template, &variables, &HashMap::new())?;
+        let result = process_template(template, &variables, &HashMap::new(), false)?;
         assert_eq!(result, "[42] (feat on new-feature) Add feature");
 
         Ok(())
@@ -741,15 +1730,21 @@ mod tests {
         let variables = TemplateVariables {
             commit_number: None,
             commit_type: "feat".to_string(),
+            commit_emoji: String::new(),
+            ticket: String::new(),
             branch_name: "new-feature".to_string(),
             message: "Add feature".to_string(),
             date: "2024-01-15".to_string(),
             time: "14:30:00".to_string(),
             author: "John Doe".to_string(),
             email: "john@example.com".to_string(),
+            branch_description: String::new(),
+            staged_files: Vec::new(),
+            impact: Vec::new(),
+            changed_symbols: Vec::new(),
         };
 
-        let result = process_template(template, &variables, &HashMap::new())?;
+        let result = process_template(template, &variables, &HashMap::new(), false)?;
         // The conditional block should be completely removed, including the space after it
         assert_eq!(result, "(feat on new-feature) Add feature");
         // Verify no empty brackets
@@ -764,15 +1759,21 @@ mod tests {
         let variables = TemplateVariables {
             commit_number: Some(5),
             commit_type: "fix".to_string(),
+            commit_emoji: String::new(),
+            ticket: String::new(),
             branch_name: "bugfix".to_string(),
             message: "Fix bug".to_string(),
             date: "2024-01-15".to_string(),
             time: "14:30:00".to_string(),
             author: "Jane Doe".to_string(),
             email: "jane@example.com".to_string(),
+            branch_description: String::new(),
+            staged_files: Vec::new(),
+            impact: Vec::new(),
+            changed_symbols: Vec::new(),
         };
 
-        let result = process_template(template, &variables, &HashMap::new())?;
+        let result = process_template(template, &variables, &HashMap::new(), false)?;
         assert_eq!(result, "[5] on 2024-01-15 (fix) Fix bug");
 
         Ok(())
@@ -785,15 +1786,21 @@ mod tests {
         let variables = TemplateVariables {
             commit_number: None,
             commit_type: "docs".to_string(),
+            commit_emoji: String::new(),
+            ticket: String::new(),
             branch_name: "docs".to_string(),
             message: "Update docs".to_string(),
             date: "2024-01-15".to_string(),
             time: "14:30:00".to_string(),
             author: "Alice".to_string(),
             email: "alice@example.com".to_string(),
+            branch_description: String::new(),
+            staged_files: Vec::new(),
+            impact: Vec::new(),
+            changed_symbols: Vec::new(),
         };
 
-        let result = process_template(template, &variables, &HashMap::new())?;
+        let result = process_template(template, &variables, &HashMap::new(), false)?;
         // commit_number is None, so first block removed; author has value, so second block kept
         assert_eq!(result, " by Alice - Update docs");
 
@@ -807,15 +1814,21 @@ mod tests {
         let variables = TemplateVariables {
             commit_number: Some(100),
             commit_type: "chore".to_string(),
+            commit_emoji: String::new(),
+            ticket: String::new(),
             branch_name: "main".to_string(),
             message: "Update dependencies".to_string(),
             date: "2024-01-15".to_string(),
             time: "14:30:00".to_string(),
             author: "Bob".to_string(),
             email: "bob@example.com".to_string(),
+            branch_description: String::new(),
+            staged_files: Vec::new(),
+            impact: Vec::new(),
+            changed_symbols: Vec::new(),
         };
 
-        let result = process_template(template, &variables, &HashMap::new())?;
+        let result = process_template(template, &variables, &HashMap::new(), false)?;
         assert_eq!(result, "Commit #100: Update dependencies");
 
         Ok(())
@@ -881,21 +1894,99 @@ mod tests {
         let variables = TemplateVariables {
             commit_number: None,
             commit_type: "test".to_string(),
+            commit_emoji: String::new(),
+            ticket: String::new(),
             branch_name: "test".to_string(),
             message: "Test".to_string(),
             date: "2024-01-15".to_string(),
             time: "14:30:00".to_string(),
             author: "Tester".to_string(),
             email: "test@example.com".to_string(),
+            branch_description: String::new(),
+            staged_files: Vec::new(),
+            impact: Vec::new(),
+            changed_symbols: Vec::new(),
         };
 
-        let result = process_template(template, &variables, &HashMap::new())?;
+        let result = process_template(template, &variables, &HashMap::new(), false)?;
         assert_eq!(result, "Test");
         assert!(!result.contains("[]"));
 
         Ok(())
     }
 
+    /// Renders `template` with `a` and `b` as extra values (empty means no value).
+    fn render_with(template: &str, a: &str, b: &str) -> Result<String> {
+        let variables = TemplateVariables {
+            commit_number: None,
+            commit_type: "feat".to_string(),
+            commit_emoji: String::new(),
+            ticket: String::new(),
+            branch_name: "main".to_string(),
+            message: "msg".to_string(),
+            date: String::new(),
+            time: String::new(),
+            author: String::new(),
+            email: String::new(),
+            branch_description: String::new(),
+            staged_files: Vec::new(),
+            impact: Vec::new(),
+            changed_symbols: Vec::new(),
+        };
+        let extras = HashMap::from([
+            ("a".to_string(), a.to_string()),
+            ("b".to_string(), b.to_string()),
+        ]);
+        process_template(template, &variables, &extras, false)
+    }
+
+    #[test]
+    fn test_nested_conditional_blocks() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let template = "{?a}<{a}{?b}|{b}{/b}>{/a}{message}";
+        assert!(validate_template(template, &["a", "b"]).is_ok());
+
+        assert_eq!(render_with(template, "x", "y")?, "<x|y>msg");
+        assert_eq!(render_with(template, "x", "")?, "<x>msg");
+        assert_eq!(render_with(template, "", "y")?, "msg");
+        assert_eq!(render_with(template, "", "")?, "msg");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_repeated_and_self_nested_conditional_blocks()
+    -> std::result::Result<(), Box<dyn std::error::Error>> {
+        // Identical blocks are rendered one by one, not replaced all at once
+        let repeated = "{?a}[{a}]{/a} {?b}({b}){/b} {?a}[{a}]{/a}";
+        assert_eq!(render_with(repeated, "x", "")?, "[x]  [x]");
+        assert_eq!(render_with(repeated, "", "y")?, " (y) ");
+
+        // The inner closing tag closes the inner block, not the outer one
+        let self_nested = "{?a}1{?a}2{/a}3{/a}4";
+        assert!(validate_template(self_nested, &["a"]).is_ok());
+        assert_eq!(render_with(self_nested, "x", "")?, "1234");
+        assert_eq!(render_with(self_nested, "", "")?, "4");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_overlapping_conditional_blocks_are_rejected() {
+        let template = "{?a}{?b}x{/a}{/b}";
+        let expected = "Overlapping conditional blocks: {/a} closes {?a} while {?b} is still open";
+
+        let validation = validate_template(template, &["a", "b"]);
+        assert!(validation.is_err_and(|e| e.to_string().contains(expected)));
+        let rendering = render_with(template, "x", "y");
+        assert!(rendering.is_err_and(|e| e.to_string().contains(expected)));
+    }
+
+    #[test]
+    fn test_unclosed_nested_conditional_block() {
+        let result = validate_template("{?a}{?b}x{/b}", &["a", "b"]);
+        assert!(result.is_err_and(|e| e.to_string().contains("Unclosed conditional block: {?a}")));
+    }
+
     #[test]
     fn test_original_bug_fix() -> std::result::Result<(), Box<dyn std::error::Error>> {
         // This is the original problem: using -n flag should not produce empty brackets
@@ -905,30 +1996,42 @@ mod tests {
         let with_number = TemplateVariables {
             commit_number: Some(42),
             commit_type: "feat".to_string(),
+            commit_emoji: String::new(),
+            ticket: String::new(),
             branch_name: "new-feature".to_string(),
             message: "Add feature".to_string(),
             date: "2024-01-15".to_string(),
             time: "14:30:00".to_string(),
             author: "Dev".to_string(),
             email: "dev@example.com".to_string(),
+            branch_description: String::new(),
+            staged_files: Vec::new(),
+            impact: Vec::new(),
+            changed_symbols: Vec::new(),
         };
 
-        let result_with = process_template(template, &with_number, &HashMap::new())?;
+        let result_with = process_template(template, &with_number, &HashMap::new(), false)?;
         assert_eq!(result_with, "[42] (feat on new-feature) Add feature");
 
         // Scenario 2: Without commit number (-n flag)
         let without_number = TemplateVariables {
             commit_number: None,
             commit_type: "feat".to_string(),
+            commit_emoji: String::new(),
+            ticket: String::new(),
             branch_name: "new-feature".to_string(),
             message: "Add feature".to_string(),
             date: "2024-01-15".to_string(),
             time: "14:30:00".to_string(),
             author: "Dev".to_string(),
             email: "dev@example.com".to_string(),
+            branch_description: String::new(),
+            staged_files: Vec::new(),
+            impact: Vec::new(),
+            changed_symbols: Vec::new(),
         };
 
-        let result_without = process_template(template, &without_number, &HashMap::new())?;
+        let result_without = process_template(template, &without_number, &HashMap::new(), false)?;
         assert_eq!(result_without, "(feat on new-feature) Add feature");
         // CRITICAL: No empty brackets!
         assert!(!result_without.contains("[]"));
@@ -936,4 +2039,316 @@ mod tests {
 
         Ok(())
     }
+    #[test]
+    fn test_date_formats() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        assert!(validate_date_format("%d/%m/%Y").is_ok());
+        assert!(validate_date_format("%Hh%M").is_ok());
+        assert!(validate_date_format("%d.%m.%Q").is_err());
+        assert!(validate_date_format("%").is_err());
+
+        let variables = TemplateVariables {
+            commit_number: None,
+            commit_type: "feat".to_string(),
+            commit_emoji: String::new(),
+            ticket: String::new(),
+            branch_name: "main".to_string(),
+            message: String::new(),
+            date: "2024-01-15".to_string(),
+            time: "14:30:00".to_string(),
+            author: String::new(),
+            email: String::new(),
+            branch_description: String::new(),
+            staged_files: Vec::new(),
+            impact: Vec::new(),
+            changed_symbols: Vec::new(),
+        }
+        .with_date_formats(Some("%d/%m/%Y"), Some("%Q"), None, None);
+
+        assert!(Regex::new(r"^\d{2}/\d{2}/\d{4}$")?.is_match(&variables.date));
+        // An invalid format keeps the default rendering.
+        assert_eq!(variables.time, "14:30:00");
+
+        let utc = variables.with_date_formats(None, Some("%z"), Some("UTC"), None);
+        assert_eq!(utc.time, "+0000");
+        let offset = utc.with_date_formats(None, Some("%:z"), Some("+02:00"), None);
+        assert_eq!(offset.time, "+02:00");
+        Ok(())
+    }
+
+    #[test]
+    fn test_localized_dates() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let date = DateTime::parse_from_rfc3339("2026-10-18T14:03:00+02:00")?;
+        let format = "%A %e %B %Y";
+
+        assert_eq!(format_date(&date, format, None), "Sunday 18 October 2026");
+        assert_eq!(
+            format_date(&date, format, date_locale("fr")),
+            "dimanche 18 octobre 2026"
+        );
+        assert_eq!(
+            format_date(&date, format, date_locale("de-DE")),
+            "Sonntag 18 Oktober 2026"
+        );
+        assert_eq!(date_locale("en"), Some(Locale::en_US));
+        assert_eq!(date_locale("pt_BR"), Some(Locale::pt_BR));
+        assert_eq!(date_locale("klingon"), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_timezone() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        assert_eq!(parse_timezone("local")?, None);
+        assert_eq!(parse_timezone("UTC")?, FixedOffset::east_opt(0));
+        assert_eq!(parse_timezone("+02:00")?, FixedOffset::east_opt(7200));
+        assert_eq!(parse_timezone("-0530")?, FixedOffset::west_opt(19800));
+        for invalid in ["Europe/Paris", "+2", "02:00", "+24:00", "+01:60", ""] {
+            assert!(parse_timezone(invalid).is_err(), "{invalid}");
+        }
+        Ok(())
+    }
+}
+
+/// Property-based tests for the template engine.
+///
+/// These tests throw arbitrary templates and values (braces, unicode, empty strings,
+/// nested conditionals) at the engine and check the invariants every caller relies on.
+#[cfg(test)]
+mod proptests {
+    use std::collections::HashMap;
+
+    use proptest::prelude::*;
+
+    use super::*;
+
+    /// Built-in commit template variables the generators draw from.
+    const VARIABLES: [&str; 10] = [
+        "commit_number",
+        "commit_type",
+        "branch_name",
+        "message",
+        "subject",
+        "body",
+        "date",
+        "time",
+        "author",
+        "email",
+    ];
+
+    /// A generated template, kept as a tree so the expected output can be computed
+    /// independently of the engine.
+    #[derive(Debug, Clone)]
+    enum Node {
+        Text(String),
+        Variable(&'static str),
+        Conditional(&'static str, Vec<Self>),
+    }
+
+    impl Node {
+        fn render_template(&self) -> String {
+            match self {
+                Self::Text(text) => text.clone(),
+                Self::Variable(name) => format!("{{{name}}}"),
+                Self::Conditional(name, children) => format!(
+                    "{{?{name}}}{}{{/{name}}}",
+                    children
+                        .iter()
+                        .map(Self::render_template)
+                        .collect::<String>()
+                ),
+            }
+        }
+
+        /// Reference semantics: variables expand to their value, conditional blocks keep
+        /// their content only when the variable is non-empty.
+        fn expected(&self, values: &HashMap<String, String>) -> String {
+            match self {
+                Self::Text(text) => text.clone(),
+                Self::Variable(name) => values.get(*name).cloned().unwrap_or_default(),
+                Self::Conditional(name, children) => {
+                    if values.get(*name).is_some_and(|v| !v.is_empty()) {
+                        children.iter().map(|c| c.expected(values)).collect()
+                    } else {
+                        String::new()
+                    }
+                }
+            }
+        }
+    }
+
+    fn variable_name() -> impl Strategy<Value = &'static str> {
+        prop::sample::select(VARIABLES.to_vec())
+    }
+
+    /// Template trees with literal text (never containing braces), variables and
+    /// conditional blocks nested up to a few levels deep.
+    fn node() -> impl Strategy<Value = Node> {
+        let leaf = prop_oneof![
+            "[^{}]{0,8}".prop_map(Node::Text),
+            variable_name().prop_map(Node::Variable),
+        ];
+        leaf.prop_recursive(4, 32, 4, |inner| {
+            (variable_name(), prop::collection::vec(inner, 0..4))
+                .prop_map(|(name, children)| Node::Conditional(name, children))
+        })
+    }
+
+    fn template_tree() -> impl Strategy<Value = Vec<Node>> {
+        prop::collection::vec(node(), 0..6)
+    }
+
+    /// Arbitrary values, biased towards the characters the engine treats specially.
+    fn any_value() -> BoxedStrategy<String> {
+        prop_oneof![
+            Just(String::new()),
+            "\\PC{0,16}",
+            "[{}?/a-z_ ]{0,16}",
+            prop::sample::select(VARIABLES.to_vec()).prop_map(|v| format!("{{{v}}}")),
+        ]
+        .boxed()
+    }
+
+    /// Values that can never be mistaken for template syntax.
+    fn plain_value() -> BoxedStrategy<String> {
+        prop_oneof![Just(String::new()), "[^{}]{0,16}"].boxed()
+    }
+
+    fn variables_from(value: BoxedStrategy<String>) -> impl Strategy<Value = TemplateVariables> {
+        (
+            prop::option::of(any::<u32>()),
+            value.clone(),
+            value.clone(),
+            value.clone(),
+            value.clone(),
+            value.clone(),
+            value.clone(),
+            value,
+        )
+            .prop_map(
+                |(commit_number, commit_type, branch_name, message, date, time, author, email)| {
+                    TemplateVariables {
+                        commit_number,
+                        commit_type,
+                        commit_emoji: String::new(),
+                        branch_name,
+                        ticket: String::new(),
+                        message,
+                        date,
+                        time,
+                        author,
+                        email,
+                        branch_description: String::new(),
+                        staged_files: Vec::new(),
+                        impact: Vec::new(),
+                        changed_symbols: Vec::new(),
+                    }
+                },
+            )
+    }
+
+    fn render(nodes: &[Node]) -> String {
+        nodes.iter().map(Node::render_template).collect()
+    }
+
+    proptest! {
+        #[test]
+        fn process_template_never_panics(
+            template in "\\PC{0,64}",
+            variables in variables_from(any_value()),
+        ) {
+            let _ = process_template(&template, &variables, &HashMap::<String, String>::new(), false);
+        }
+
+        #[test]
+        fn process_template_never_panics_on_brace_soup(
+            template in "[{}?/a-z_ ]{0,64}",
+            variables in variables_from(any_value()),
+        ) {
+            for strict in [false, true] {
+                let _ = process_template(&template, &variables, &HashMap::<String, String>::new(), strict);
+            }
+        }
+
+        #[test]
+        fn validate_template_never_panics(template in "\\PC{0,64}") {
+            let _ = validate_template(&template, &[]);
+            let _ = validate_branch_template(&template, &["ticket"]);
+        }
+
+        #[test]
+        fn generated_templates_are_valid(nodes in template_tree()) {
+            prop_assert!(validate_template(&render(&nodes), &[]).is_ok());
+        }
+
+        #[test]
+        fn generated_templates_match_reference_semantics(
+            nodes in template_tree(),
+            variables in variables_from(any_value()),
+        ) {
+            let template = render(&nodes);
+            let map = variables.to_map();
+            let expected: String = nodes.iter().map(|n| n.expected(&map)).collect();
+
+            let result = process_template(&template, &variables, &HashMap::<String, String>::new(), false);
+            prop_assert_eq!(result.ok(), Some(expected));
+        }
+
+        #[test]
+        fn values_are_inserted_verbatim(variables in variables_from(any_value())) {
+            let template = "{commit_type}|{branch_name}|{message}|{author}|{email}";
+            let expected = format!(
+                "{}|{}|{}|{}|{}",
+                variables.commit_type,
+                variables.branch_name,
+                variables.message,
+                variables.author,
+                variables.email
+            );
+
+            let result = process_template(template, &variables, &HashMap::<String, String>::new(), false);
+            prop_assert_eq!(result.ok(), Some(expected));
+        }
+
+        #[test]
+        fn extra_values_are_inserted_verbatim(value in any_value()) {
+            let variables = TemplateVariables {
+                commit_number: None,
+                commit_type: "feat".to_string(),
+                commit_emoji: String::new(),
+                ticket: String::new(),
+                branch_name: "main".to_string(),
+                message: "msg".to_string(),
+                date: String::new(),
+                time: String::new(),
+                author: String::new(),
+                email: String::new(),
+                branch_description: String::new(),
+            staged_files: Vec::new(),
+            impact: Vec::new(),
+            changed_symbols: Vec::new(),
+            };
+            let extras = HashMap::from([("scope".to_string(), value.clone())]);
+
+            let result = process_template("{?scope}({scope}) {/scope}{message}", &variables, &extras, false);
+            let expected = if value.is_empty() {
+                "msg".to_string()
+            } else {
+                format!("({value}) msg")
+            };
+            prop_assert_eq!(result.ok(), Some(expected));
+        }
+
+        #[test]
+        fn reprocessing_is_idempotent(
+            nodes in template_tree(),
+            variables in variables_from(plain_value()),
+        ) {
+            let no_extras = HashMap::<String, String>::new();
+            let once = process_template(&render(&nodes), &variables, &no_extras, false);
+            prop_assert!(once.is_ok());
+            let once = once.unwrap_or_default();
+
+            let twice = process_template(&once, &variables, &no_extras, false);
+            prop_assert_eq!(twice.ok(), Some(once));
+        }
+    }
 }
@@ -4,19 +4,155 @@
 //! `::with_theme(&prompt_theme())`. This module centralises the colours and icons so all
 //! prompts stay visually consistent (light cyan prompts, light magenta answers,
 //! light blue highlights).
+//!
+//! It also gives each commit type its color, configurable under `[type_colors]`, so a
+//! type looks the same in the type selectors, `rona browse` and `rona show`.
+//!
+//! With `accessibility = true`, glyphs give way to ASCII labels (`OK:`, `ERROR:`),
+//! colors are turned off and selectors become numbered lists, which screen readers
+//! can follow.
 
+use std::{
+    collections::BTreeMap,
+    fmt,
+    ops::Range,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use colored::{Color, ColoredString, Colorize};
 use dialoguer::{
+    FuzzySelect, Input,
     console::{Style, style},
-    theme::ColorfulTheme,
+    theme::{ColorfulTheme, Theme},
 };
+use fuzzy_matcher::{FuzzyMatcher, skim::SkimMatcherV2};
+
+use crate::lint::commit_type_span;
+
+/// Colors of the usual commit and branch types when `[type_colors]` does not set them.
+const DEFAULT_TYPE_COLORS: [(&str, Color); 10] = [
+    ("feat", Color::Green),
+    ("fix", Color::Red),
+    ("hotfix", Color::BrightRed),
+    ("docs", Color::Blue),
+    ("test", Color::Yellow),
+    ("refactor", Color::Cyan),
+    ("perf", Color::Magenta),
+    ("chore", Color::BrightBlack),
+    ("ci", Color::BrightBlue),
+    ("release", Color::BrightMagenta),
+];
+
+/// Colors for other types, picked from the type name so a type keeps its color.
+const FALLBACK_TYPE_COLORS: [Color; 6] = [
+    Color::BrightCyan,
+    Color::BrightGreen,
+    Color::BrightYellow,
+    Color::BrightBlue,
+    Color::BrightMagenta,
+    Color::Cyan,
+];
+
+/// Set by `accessibility = true` in the config.
+static ACCESSIBLE: AtomicBool = AtomicBool::new(false);
+
+/// Enables accessibility mode for the rest of the process, and turns colors off.
+pub fn set_accessible(accessible: bool) {
+    ACCESSIBLE.store(accessible, Ordering::Relaxed);
+    if accessible {
+        colored::control::set_override(false);
+        dialoguer::console::set_colors_enabled(false);
+        dialoguer::console::set_colors_enabled_stderr(false);
+    }
+}
+
+/// Whether output and prompts are in accessibility mode.
+#[must_use]
+pub fn is_accessible() -> bool {
+    ACCESSIBLE.load(Ordering::Relaxed)
+}
+
+/// `fancy`, or its `plain` ASCII replacement in accessibility mode.
+#[must_use]
+pub fn glyph(fancy: &'static str, plain: &'static str) -> &'static str {
+    if is_accessible() { plain } else { fancy }
+}
+
+/// The marker of a completed step: `✓`, or `OK:` in accessibility mode.
+#[must_use]
+pub fn success_mark() -> ColoredString {
+    glyph("✓", "OK:").green()
+}
+
+/// The marker of a failed check: `✗`, or `ERROR:` in accessibility mode.
+#[must_use]
+pub fn failure_mark() -> ColoredString {
+    glyph("✗", "ERROR:").red()
+}
+
+/// Asks to pick one of `items` with a fuzzy selector. In accessibility mode, the items
+/// are printed as a numbered list and the number is asked for instead. `None` when
+/// the user cancels.
+///
+/// # Errors
+/// * If the terminal is not interactive
+pub fn select_item<T: fmt::Display>(
+    theme: &dyn Theme,
+    prompt: &str,
+    items: &[T],
+) -> dialoguer::Result<Option<usize>> {
+    if !is_accessible() {
+        return FuzzySelect::with_theme(theme)
+            .with_prompt(prompt)
+            .items(items)
+            .default(0)
+            .interact_opt();
+    }
+
+    eprintln!("{prompt}");
+    for (number, item) in items.iter().enumerate() {
+        eprintln!("  {}. {}", number + 1, item);
+    }
+    let count = items.len();
+    let answer: String = Input::with_theme(theme)
+        .with_prompt(format!("Number from 1 to {count}, empty to cancel"))
+        .allow_empty(true)
+        .validate_with(|input: &String| -> std::result::Result<(), String> {
+            let input = input.trim();
+            if input.is_empty()
+                || input
+                    .parse::<usize>()
+                    .is_ok_and(|n| (1..=count).contains(&n))
+            {
+                Ok(())
+            } else {
+                Err(format!("Enter a number from 1 to {count}"))
+            }
+        })
+        .interact_text()?;
+    Ok(answer.trim().parse::<usize>().ok().map(|number| number - 1))
+}
 
 /// Build the shared [`ColorfulTheme`] used by every interactive prompt.
 ///
 /// Starts from the crate default and overrides prefixes and styles to match Rona's
 /// look: `$` prompt prefix, `✓`/`✕` success and error markers, and light cyan/magenta
-/// accents.
+/// accents. In accessibility mode, every glyph is plain ASCII.
 #[must_use]
 pub fn prompt_theme() -> ColorfulTheme {
+    if is_accessible() {
+        return ColorfulTheme {
+            prompt_suffix: style(":".to_string()).for_stderr(),
+            success_prefix: style("OK".to_string()).for_stderr(),
+            success_suffix: style(":".to_string()).for_stderr(),
+            error_prefix: style("ERROR".to_string()).for_stderr(),
+            active_item_prefix: style(">".to_string()).for_stderr(),
+            checked_item_prefix: style("[x]".to_string()).for_stderr(),
+            unchecked_item_prefix: style("[ ]".to_string()).for_stderr(),
+            picked_item_prefix: style(">".to_string()).for_stderr(),
+            ..ColorfulTheme::default()
+        };
+    }
     ColorfulTheme {
         // Input prompt label: light cyan, bold.
         prompt_style: Style::new().for_stderr().cyan().bright().bold(),
@@ -36,3 +172,305 @@ pub fn prompt_theme() -> ColorfulTheme {
         ..ColorfulTheme::default()
     }
 }
+
+/// The prompt theme for fuzzy selectors listing types or commits, drawing each commit
+/// type in its color.
+///
+/// The items stay plain text, so typing filters on what is shown and not on color
+/// escape codes. Only the methods `FuzzySelect` uses are overridden.
+pub struct TypeSelectTheme<'a> {
+    base: ColorfulTheme,
+    colors: &'a BTreeMap<String, String>,
+    items: TypeItems<'a>,
+}
+
+/// Where the type is in the items of a [`TypeSelectTheme`] selector.
+#[derive(Debug, Clone, Copy)]
+enum TypeItems<'a> {
+    /// Each item is a type name
+    Types,
+    /// Each item is a type name with its emoji before it and its description after it
+    Described { commit_types: &'a [&'a str] },
+    /// Each item is `<sha> <subject>...`, with the type found by the commit template
+    Commits {
+        template: &'a str,
+        commit_types: &'a [&'a str],
+    },
+}
+
+impl<'a> TypeSelectTheme<'a> {
+    /// Builds the theme for a selector of commit or branch type names.
+    #[must_use]
+    pub fn new(colors: &'a BTreeMap<String, String>) -> Self {
+        Self {
+            base: prompt_theme(),
+            colors,
+            items: TypeItems::Types,
+        }
+    }
+
+    /// Builds the theme for a selector of commit types shown with their emoji and
+    /// description (see [`commit_type_label`]).
+    #[must_use]
+    pub fn for_described_types(
+        colors: &'a BTreeMap<String, String>,
+        commit_types: &'a [&'a str],
+    ) -> Self {
+        Self {
+            base: prompt_theme(),
+            colors,
+            items: TypeItems::Described { commit_types },
+        }
+    }
+
+    /// Builds the theme for a selector of `<sha> <subject>...` commit lines.
+    #[must_use]
+    pub fn for_commits(
+        colors: &'a BTreeMap<String, String>,
+        template: &'a str,
+        commit_types: &'a [&'a str],
+    ) -> Self {
+        Self {
+            base: prompt_theme(),
+            colors,
+            items: TypeItems::Commits {
+                template,
+                commit_types,
+            },
+        }
+    }
+
+    /// The byte range of the commit type in an item, if it has one.
+    fn type_span(&self, text: &str) -> Option<Range<usize>> {
+        match self.items {
+            TypeItems::Types => Some(0..text.len()),
+            TypeItems::Described { commit_types } => {
+                let mut offset = 0;
+                for word in text.split(' ') {
+                    if commit_types.contains(&word) {
+                        return Some(offset..offset + word.len());
+                    }
+                    offset += word.len() + 1;
+                }
+                None
+            }
+            TypeItems::Commits {
+                template,
+                commit_types,
+            } => {
+                let (sha, subject) = text.split_once(' ')?;
+                let offset = sha.len() + 1;
+                commit_type_span(subject, template, commit_types)
+                    .map(|span| span.start + offset..span.end + offset)
+            }
+        }
+    }
+}
+
+impl fmt::Debug for TypeSelectTheme<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TypeSelectTheme")
+            .field("colors", &self.colors)
+            .field("items", &self.items)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Theme for TypeSelectTheme<'_> {
+    fn format_input_prompt_selection(
+        &self,
+        f: &mut dyn fmt::Write,
+        prompt: &str,
+        sel: &str,
+    ) -> fmt::Result {
+        self.base.format_input_prompt_selection(f, prompt, sel)
+    }
+
+    fn format_fuzzy_select_prompt(
+        &self,
+        f: &mut dyn fmt::Write,
+        prompt: &str,
+        search_term: &str,
+        bytes_pos: usize,
+    ) -> fmt::Result {
+        self.base
+            .format_fuzzy_select_prompt(f, prompt, search_term, bytes_pos)
+    }
+
+    fn format_fuzzy_select_prompt_item(
+        &self,
+        f: &mut dyn fmt::Write,
+        text: &str,
+        active: bool,
+        highlight_matches: bool,
+        matcher: &SkimMatcherV2,
+        search_term: &str,
+    ) -> fmt::Result {
+        let prefix = if active {
+            &self.base.active_item_prefix
+        } else {
+            &self.base.inactive_item_prefix
+        };
+        write!(f, "{prefix} ")?;
+
+        let span = self.type_span(text).unwrap_or_default();
+        let color = type_color(&text[span.clone()], self.colors);
+        let highlighted = if highlight_matches {
+            matcher
+                .fuzzy_indices(text, search_term)
+                .map(|(_, indices)| indices)
+                .unwrap_or_default()
+        } else {
+            Vec::new()
+        };
+        for (index, (byte, c)) in text.char_indices().enumerate() {
+            let mut styled = c.to_string().normal();
+            if span.contains(&byte) {
+                styled = styled.color(color);
+            }
+            if active {
+                styled = styled.bold();
+            }
+            if highlighted.contains(&index) {
+                styled = styled.underline();
+            }
+            write!(f, "{styled}")?;
+        }
+        Ok(())
+    }
+}
+
+/// A commit type as the type selector shows it: `<emoji> <name>  <description>`, with
+/// the names padded to `width` so the descriptions line up.
+///
+/// Types without an emoji get blanks in its place; a missing description leaves the
+/// name alone.
+#[must_use]
+pub fn commit_type_label(
+    name: &str,
+    emoji: Option<&str>,
+    description: Option<&str>,
+    width: usize,
+) -> String {
+    let label = format!(
+        "{} {name:<width$}  {}",
+        emoji.unwrap_or("  "),
+        description.unwrap_or_default()
+    );
+    label.trim_end().to_string()
+}
+
+/// The color of a commit type: its `[type_colors]` entry, else a built-in default.
+///
+/// Entries are color names as `colored` reads them (`green`, `bright blue`, ...) or
+/// `#rrggbb`. An entry that is not a color falls back to the default.
+#[must_use]
+pub fn type_color(commit_type: &str, colors: &BTreeMap<String, String>) -> Color {
+    colors
+        .get(commit_type)
+        .and_then(|name| name.parse().ok())
+        .or_else(|| {
+            DEFAULT_TYPE_COLORS
+                .iter()
+                .find(|(name, _)| *name == commit_type)
+                .map(|(_, color)| *color)
+        })
+        .unwrap_or_else(|| {
+            let sum = commit_type.bytes().map(usize::from).sum::<usize>();
+            FALLBACK_TYPE_COLORS[sum % FALLBACK_TYPE_COLORS.len()]
+        })
+}
+
+/// A commit type in its color.
+#[must_use]
+pub fn paint_type(commit_type: &str, colors: &BTreeMap<String, String>) -> String {
+    commit_type
+        .color(type_color(commit_type, colors))
+        .to_string()
+}
+
+/// A subject line with its commit type in color, found with the commit template,
+/// and in bold when `bold` is set. Subjects that do not match the template are not
+/// colored.
+#[must_use]
+pub fn paint_subject_type(
+    subject: &str,
+    template: &str,
+    commit_types: &[&str],
+    colors: &BTreeMap<String, String>,
+    bold: bool,
+) -> String {
+    let weight = |text: &str| {
+        if bold {
+            text.bold().to_string()
+        } else {
+            text.to_string()
+        }
+    };
+    commit_type_span(subject, template, commit_types).map_or_else(
+        || weight(subject),
+        |span| {
+            let commit_type = &subject[span.clone()];
+            let mut painted = commit_type.color(type_color(commit_type, colors));
+            if bold {
+                painted = painted.bold();
+            }
+            format!(
+                "{}{painted}{}",
+                weight(&subject[..span.start]),
+                weight(&subject[span.end..])
+            )
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_type_color() {
+        let colors = BTreeMap::from([
+            ("feat".to_string(), "bright blue".to_string()),
+            ("fix".to_string(), "not a color".to_string()),
+        ]);
+
+        assert_eq!(type_color("feat", &colors), Color::BrightBlue);
+        assert_eq!(type_color("fix", &colors), Color::Red);
+        assert_eq!(type_color("docs", &colors), Color::Blue);
+        assert_eq!(
+            type_color("spike", &colors),
+            type_color("spike", &BTreeMap::new())
+        );
+    }
+
+    #[test]
+    fn test_commit_type_label() {
+        let label = commit_type_label("feat", Some("✨"), Some("A new feature"), 5);
+        assert_eq!(label, "✨ feat   A new feature");
+        assert_eq!(commit_type_label("fix", None, None, 5), "   fix");
+
+        let colors = BTreeMap::new();
+        let types = ["feat", "fix"];
+        let theme = TypeSelectTheme::for_described_types(&colors, &types);
+        assert_eq!(
+            theme.type_span(&label).map(|span| &label[span]),
+            Some("feat")
+        );
+    }
+
+    #[test]
+    fn test_paint_subject_type() {
+        let template = "({commit_type} on {branch_name}) {message}";
+        let colors = BTreeMap::new();
+
+        assert_eq!(
+            paint_subject_type("(fix on main) fix it", template, &["fix"], &colors, false),
+            format!("({} on main) fix it", paint_type("fix", &colors))
+        );
+        assert_eq!(
+            paint_subject_type("Merge branch 'main'", template, &["fix"], &colors, false),
+            "Merge branch 'main'"
+        );
+    }
+}
@@ -0,0 +1,260 @@
+//! Onboarding Tour
+//!
+//! `rona tour` walks a new user through the everyday workflow in a throwaway
+//! repository: staging with exclusions, generating a commit message, committing
+//! and pushing. Every step runs the same functions as the real commands, so the
+//! output the user sees is exactly what they will get in their own projects.
+//!
+//! The sandbox lives under the system temporary directory and contains a demo
+//! repository plus a local bare repository acting as `origin`. It is removed when
+//! the tour ends (or is cancelled) unless `--keep` is given. Local git config in the
+//! sandbox disables signing and hooks so the user's global setup cannot get in the way.
+
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use colored::Colorize;
+use dialoguer::Confirm;
+use glob::Pattern;
+
+use crate::{
+    config::PrePushConfig,
+    errors::{GitError, Result, RonaError},
+    git::{
+        COMMIT_MESSAGE_FILE_PATH, Comments, FileBullet, Signing, create_needed_files,
+        generate_commit_message, get_current_commit_nb, get_staged_files,
+        git_add_with_exclude_patterns, git_commit, git_push,
+    },
+    theme::{prompt_theme, success_mark},
+};
+
+/// Number of steps shown in the step headers.
+const TOTAL_STEPS: usize = 5;
+
+/// Files written into the demo repository before the tour starts.
+const DEMO_FILES: [(&str, &str); 3] = [
+    ("README.md", "# Demo project\n\nCreated by `rona tour`.\n"),
+    (
+        "src/main.rs",
+        "fn main() {\n    println!(\"Hello from the tour!\");\n}\n",
+    ),
+    ("debug.log", "noise that should never be committed\n"),
+];
+
+/// The temporary directory holding the demo repository and its remote.
+///
+/// Removed on drop unless `keep` is set, so a cancelled tour cleans up after itself.
+#[derive(Debug)]
+struct Sandbox {
+    root: PathBuf,
+    original_dir: PathBuf,
+    keep: bool,
+}
+
+impl Sandbox {
+    fn repo(&self) -> PathBuf {
+        self.root.join("demo")
+    }
+
+    fn remote(&self) -> PathBuf {
+        self.root.join("remote.git")
+    }
+}
+
+impl Drop for Sandbox {
+    fn drop(&mut self) {
+        // Leave the sandbox before removing it.
+        let _ = env::set_current_dir(&self.original_dir);
+        if !self.keep {
+            let _ = fs::remove_dir_all(&self.root);
+        }
+    }
+}
+
+/// Runs the onboarding tour.
+///
+/// # Arguments
+/// * `yes` - Run every step without pausing for confirmation
+/// * `keep` - Keep the sandbox directory after the tour instead of deleting it
+///
+/// # Errors
+/// * If the sandbox cannot be created
+/// * If any of the demonstrated commands fails
+/// * If the user cancels between steps
+pub fn run_tour(yes: bool, keep: bool) -> Result<()> {
+    println!("{}", "Welcome to the Rona tour!".bold());
+    println!(
+        "This walks through the everyday workflow in a throwaway repository.\n\
+         Nothing outside the sandbox is touched.\n"
+    );
+
+    let sandbox = create_sandbox(keep)?;
+    println!("Sandbox: {}\n", sandbox.root.display());
+    env::set_current_dir(sandbox.repo())?;
+
+    step(1, "Stage files, excluding patterns", yes)?;
+    println!(
+        "The demo repository has a README, some code and a `debug.log`.\n\
+         `rona -a \"*.log\"` stages everything except files matching the patterns.\n"
+    );
+    let exclude = [Pattern::new("*.log")
+        .map_err(|e| RonaError::InvalidInput(format!("Invalid glob pattern: {e}")))?];
+    git_add_with_exclude_patterns(&exclude, false, false)?;
+    for entry in get_staged_files()? {
+        println!("  staged: {}", entry.path);
+    }
+    println!();
+
+    step(2, "Generate the commit message", yes)?;
+    println!(
+        "`rona -g` asks for a commit type, then writes `{COMMIT_MESSAGE_FILE_PATH}` with a\n\
+         header and one bullet per staged file, and opens your editor.\n\
+         The tour picks `feat` and fills in the bullets for you.\n"
+    );
+    create_needed_files()?;
+    generate_commit_message(
+        "feat",
+        "main",
+        Some(get_current_commit_nb()? + 1),
+        &FileBullet::default(),
+    )?;
+    let message = fs::read_to_string(COMMIT_MESSAGE_FILE_PATH)?
+        .replace("`:\n\n\t\n", "`: added in the tour\n");
+    fs::write(COMMIT_MESSAGE_FILE_PATH, &message)?;
+    println!("{}\n", message.trim_end().dimmed());
+
+    step(3, "Commit", yes)?;
+    println!(
+        "`rona -c` commits with the message file through `git commit`, so your hooks\n\
+         and signing settings apply as usual. Add `-p` to push right after.\n"
+    );
+    git_commit(&[], Signing::Off, Comments::default(), false)?;
+    println!();
+
+    step(4, "Push", yes)?;
+    println!("`rona -p` runs `git push`; here `origin` is a local bare repository.\n");
+    git_push(
+        &[
+            "--quiet".to_string(),
+            "-u".to_string(),
+            "origin".to_string(),
+            "main".to_string(),
+        ],
+        &PrePushConfig::default(),
+        true,
+        false,
+    )?;
+    println!(
+        "{} Pushed to {}\n",
+        success_mark(),
+        sandbox.remote().display()
+    );
+
+    step(5, "Next steps", yes)?;
+    println!(
+        "In your own repository:\n\
+         \x20 rona init          create the configuration (editor, template, ...)\n\
+         \x20 rona -a [PATTERN]  stage files, excluding patterns\n\
+         \x20 rona -g [-i]       generate the commit message (editor or interactive)\n\
+         \x20 rona -c [-p]       commit, and optionally push\n\
+         \x20 rona branch        create a branch from the branch template\n"
+    );
+
+    if keep {
+        println!("The sandbox was kept at {}", sandbox.root.display());
+    }
+    println!("{} Tour complete!", success_mark());
+    Ok(())
+}
+
+/// Prints a step header, pausing for confirmation unless `yes` is set.
+///
+/// # Errors
+/// * If the user declines or cancels the prompt
+fn step(number: usize, title: &str, yes: bool) -> Result<()> {
+    if !yes {
+        let proceed = Confirm::with_theme(&prompt_theme())
+            .with_prompt(format!("Continue to step {number}: {title}?"))
+            .default(true)
+            .interact_opt()
+            .map_err(|_| RonaError::UserCancelled)?;
+        if proceed != Some(true) {
+            return Err(RonaError::UserCancelled);
+        }
+    }
+    println!(
+        "{}",
+        format!("Step {number}/{TOTAL_STEPS}: {title}")
+            .cyan()
+            .bold()
+    );
+    Ok(())
+}
+
+/// Creates the sandbox with a demo repository, its files, and a bare `origin`.
+///
+/// # Errors
+/// * If directories or files cannot be created
+/// * If any git setup command fails
+fn create_sandbox(keep: bool) -> Result<Sandbox> {
+    let sandbox = Sandbox {
+        root: env::temp_dir().join(format!("rona-tour-{}", std::process::id())),
+        original_dir: env::current_dir()?,
+        keep,
+    };
+    let repo = sandbox.repo();
+    let hooks = sandbox.root.join("hooks");
+    fs::create_dir_all(&repo)?;
+    fs::create_dir_all(&hooks)?;
+
+    git(&sandbox.root, &["init", "--quiet", "--bare", "remote.git"])?;
+    git(&repo, &["init", "--quiet"])?;
+    git(&repo, &["symbolic-ref", "HEAD", "refs/heads/main"])?;
+
+    let hooks = hooks.to_string_lossy();
+    for (key, value) in [
+        ("user.name", "Rona Tour"),
+        ("user.email", "tour@example.com"),
+        ("commit.gpgsign", "false"),
+        ("core.hooksPath", hooks.as_ref()),
+    ] {
+        git(&repo, &["config", "--local", key, value])?;
+    }
+    git(
+        &repo,
+        &[
+            "remote",
+            "add",
+            "origin",
+            &sandbox.remote().to_string_lossy(),
+        ],
+    )?;
+
+    for (path, content) in DEMO_FILES {
+        let path = repo.join(path);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, content)?;
+    }
+
+    Ok(sandbox)
+}
+
+/// Runs a git setup command in `dir`.
+///
+/// # Errors
+/// * If git cannot be spawned or exits with a non-zero status
+fn git(dir: &Path, args: &[&str]) -> Result<()> {
+    let output = Command::new("git").current_dir(dir).args(args).output()?;
+    if output.status.success() {
+        return Ok(());
+    }
+    Err(RonaError::Git(GitError::CommandFailed {
+        command: format!("git {}", args.join(" ")),
+        output: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+    }))
+}
@@ -0,0 +1,176 @@
+//! Issue Tracker Transitions
+//!
+//! `[tracker.transitions]` maps commit types to the shell commands that move the
+//! ticket of the branch along its workflow, e.g. a comment and a transition to "In
+//! Review" once a `fix` is pushed. The commands run after a successful push, for
+//! each commit type among the pushed commits, and drive the tracker through its own
+//! CLI (such as `jira`, `gh issue` or `linear`), the way rona drives `git`, `gh` and
+//! `curl`.
+//!
+//! The ticket is the one `ticket_pattern` finds in the pushed branch. The commands
+//! get it, and what was pushed, through environment variables rather than template
+//! variables, so commit subjects never need shell quoting:
+//!
+//! - `RONA_TICKET`: the ticket, e.g. `PROJ-123`
+//! - `RONA_COMMIT_TYPE`: the commit type the command is configured for
+//! - `RONA_SUBJECTS`: the subjects of the pushed commits of that type, one per line
+//! - `RONA_BRANCH` and `RONA_REMOTE`: the pushed branch and the remote
+
+use std::{collections::BTreeMap, path::Path};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    errors::{Result, RonaError},
+    git::PushSummary,
+    shell::{Limits, run_shell},
+    utils::parse_duration,
+};
+
+/// Issue tracker settings, declared as `[tracker]`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct TrackerConfig {
+    /// Shell commands run after a successful push, by commit type of the pushed
+    /// commits, e.g. `fix = ["jira issue move \"$RONA_TICKET\" 'In Review'"]`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub transitions: BTreeMap<String, Vec<String>>,
+
+    /// Time limit of each command, e.g. `"1m"` (units: `s`, `m`, `h`, `d`). No limit
+    /// when absent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<String>,
+}
+
+/// A tracker command a push runs.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TrackerCommand {
+    /// The ticket of the pushed branch
+    pub ticket: String,
+    /// The commit type the command is configured for
+    pub commit_type: String,
+    /// The shell command
+    pub command: String,
+    /// Subjects of the pushed commits of that type, newest first
+    pub subjects: Vec<String>,
+}
+
+/// The commands of `tracker` for the pushed commits, in the order of the
+/// `[tracker.transitions]` keys, then of their commands.
+///
+/// # Arguments
+/// * `tracker` - The `[tracker]` config
+/// * `ticket` - The ticket of the pushed branch; nothing runs when it is empty
+/// * `commit_types` - The commit type of each pushed subject, `None` when the
+///   subject does not follow the commit template
+/// * `subjects` - The pushed subjects, newest first
+#[must_use]
+pub fn tracker_commands(
+    tracker: &TrackerConfig,
+    ticket: &str,
+    commit_types: &[Option<String>],
+    subjects: &[String],
+) -> Vec<TrackerCommand> {
+    if ticket.is_empty() {
+        return Vec::new();
+    }
+    tracker
+        .transitions
+        .iter()
+        .filter_map(|(commit_type, commands)| {
+            let subjects: Vec<String> = subjects
+                .iter()
+                .zip(commit_types)
+                .filter(|(_, subject_type)| subject_type.as_deref() == Some(commit_type))
+                .map(|(subject, _)| subject.clone())
+                .collect();
+            (!subjects.is_empty()).then_some((commit_type, commands, subjects))
+        })
+        .flat_map(|(commit_type, commands, subjects)| {
+            commands.iter().map(move |command| TrackerCommand {
+                ticket: ticket.to_string(),
+                commit_type: commit_type.clone(),
+                command: command.clone(),
+                subjects: subjects.clone(),
+            })
+        })
+        .collect()
+}
+
+/// Runs the tracker `commands` through `sh -c` from the repository root, after
+/// `summary` was pushed. A failing command does not stop the next ones.
+///
+/// # Errors
+/// * If `timeout` is invalid
+///
+/// # Returns
+/// * The failed commands with the reason, to warn about
+pub fn run_tracker_commands(
+    commands: &[TrackerCommand],
+    tracker: &TrackerConfig,
+    summary: &PushSummary,
+    repo_root: &Path,
+) -> Result<Vec<(String, String)>> {
+    let limits = Limits {
+        timeout: tracker
+            .timeout
+            .as_deref()
+            .map(parse_duration)
+            .transpose()
+            .map_err(|e| RonaError::InvalidInput(format!("[tracker] timeout: {e}")))?,
+        ..Limits::default()
+    };
+
+    let mut failures = Vec::new();
+    for command in commands {
+        println!("Running tracker command: {}", command.command);
+        let subjects = command.subjects.join("\n");
+        let vars = [
+            ("RONA_TICKET", command.ticket.as_str()),
+            ("RONA_COMMIT_TYPE", command.commit_type.as_str()),
+            ("RONA_SUBJECTS", subjects.as_str()),
+            ("RONA_BRANCH", summary.branch.as_str()),
+            ("RONA_REMOTE", summary.remote.as_str()),
+        ];
+        let reason = match run_shell(&command.command, repo_root, &limits, &vars) {
+            Ok(outcome) if outcome.success() => continue,
+            Ok(outcome) => outcome
+                .status
+                .map_or_else(|| "timed out".to_string(), |status| status.to_string()),
+            Err(e) => e.to_string(),
+        };
+        failures.push((command.command.clone(), reason));
+    }
+    Ok(failures)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tracker_commands() {
+        let tracker = TrackerConfig {
+            transitions: BTreeMap::from([
+                (
+                    "fix".to_string(),
+                    vec!["comment".to_string(), "move".to_string()],
+                ),
+                ("feat".to_string(), vec!["feature".to_string()]),
+                ("docs".to_string(), vec!["docs".to_string()]),
+            ]),
+            timeout: None,
+        };
+        let subjects = ["fix b", "chore", "fix a", "feat c"].map(ToString::to_string);
+        let commit_types = [Some("fix"), None, Some("fix"), Some("feat")]
+            .map(|commit_type| commit_type.map(ToString::to_string));
+
+        let commands = tracker_commands(&tracker, "PROJ-1", &commit_types, &subjects);
+        let names: Vec<&str> = commands.iter().map(|c| c.command.as_str()).collect();
+        assert_eq!(names, ["feature", "comment", "move"]);
+        assert_eq!(commands[1].subjects, ["fix b", "fix a"]);
+        assert_eq!(commands[1].ticket, "PROJ-1");
+
+        // No ticket in the branch, nothing to transition
+        assert!(tracker_commands(&tracker, "", &commit_types, &subjects).is_empty());
+    }
+}
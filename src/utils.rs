@@ -16,22 +16,19 @@
 //! # Core Features
 //!
 //! - Consistent message formatting
-//! - File path validation and checking
 //! - Project root directory detection
 //! - List formatting utilities
+//! - Fuzzy path filtering
 //!
 //! # Error Handling
 //!
 //! All file operations return `Result` types with detailed error messages
 //! for proper error handling throughout the application.
 
-use std::{
-    fmt::Display,
-    io::{Error as IoError, ErrorKind},
-    path::Path,
-};
+use std::{cmp::Reverse, fmt::Display, time::Duration};
 
 use colored::Colorize;
+use fuzzy_matcher::{FuzzyMatcher, skim::SkimMatcherV2};
 
 /// Trait for message types.
 #[doc(hidden)]
@@ -125,77 +122,93 @@ pub fn format_list<T: Display>(items: &[T]) -> String {
         .join("\n")
 }
 
-/// Checks if a file path starts with or is contained within a folder path.
-///
-/// # Arguments
-/// * `file_path` - Path of the file to check
-/// * `folder_path` - Path of the containing folder
+/// Parses a short duration such as `30s`, `15m`, `2h` or `1d`.
 ///
 /// # Errors
-/// Returns an error if:
-/// * The file path is invalid (empty or has an invalid parent)
-/// * The folder path is invalid or empty
-/// * Either path cannot be converted to a canonical form
-///
-/// # Returns
-/// * `Ok(bool)` - True if the file is within the folder, false otherwise
-/// * `Err(std::io::Error)` - If there's an error processing the paths
-pub fn check_for_file_in_folder(file_path: &Path, folder_path: &Path) -> Result<bool, IoError> {
-    // Validate inputs
-    if file_path.as_os_str().is_empty() {
-        return Err(IoError::new(ErrorKind::InvalidInput, "File path is empty"));
+/// * If the value is not a positive whole number followed by `s`, `m`, `h` or `d`
+pub fn parse_duration(value: &str) -> Result<Duration, String> {
+    let value = value.trim();
+    let split = value
+        .find(|c: char| !c.is_ascii_digit())
+        .ok_or_else(|| format!("'{value}' is missing a unit (s, m, h or d)"))?;
+    let (amount, unit) = value.split_at(split);
+
+    let amount: u64 = amount
+        .parse()
+        .map_err(|_| format!("'{value}' does not start with a number"))?;
+    let seconds = match unit {
+        "s" => 1,
+        "m" => 60,
+        "h" => 60 * 60,
+        "d" => 24 * 60 * 60,
+        _ => {
+            return Err(format!(
+                "'{value}' has an unknown unit (expected s, m, h or d)"
+            ));
+        }
+    };
+
+    Ok(Duration::from_secs(amount.saturating_mul(seconds)))
+}
+
+/// Formats a duration with its two largest units, e.g. `2m 10s`, `1h 5m` or `3d 2h`.
+#[must_use]
+pub fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    let (days, hours, minutes, seconds) =
+        (secs / 86_400, secs / 3_600 % 24, secs / 60 % 60, secs % 60);
+    if days > 0 {
+        format!("{days}d {hours}h")
+    } else if hours > 0 {
+        format!("{hours}h {minutes}m")
+    } else if minutes > 0 {
+        format!("{minutes}m {seconds}s")
+    } else {
+        format!("{seconds}s")
     }
-    if folder_path.as_os_str().is_empty() {
-        return Err(IoError::new(
-            ErrorKind::InvalidInput,
-            "Folder path is empty",
-        ));
+}
+
+/// Divides two counts, e.g. for an average or a percentage. `0` when `denominator` is 0.
+///
+/// Counts above `u32::MAX` saturate, so the conversion to `f64` loses no precision.
+#[must_use]
+pub fn ratio(numerator: usize, denominator: usize) -> f64 {
+    let float = |count: usize| f64::from(u32::try_from(count).unwrap_or(u32::MAX));
+    if denominator == 0 {
+        0.0
+    } else {
+        float(numerator) / float(denominator)
     }
+}
 
-    // Get the parent directory of the file
-    let file_parent = file_path.parent().ok_or_else(|| {
-        IoError::new(
-            ErrorKind::InvalidInput,
-            "Invalid file path: cannot get parent directory",
-        )
-    })?;
+/// Keeps the items whose key fuzzy-matches `query`, best matches first.
+///
+/// Matching is skim-style: the query's characters must appear in order, not
+/// necessarily next to each other, so `clrs` finds `src/cli.rs`. Matching is
+/// case-insensitive unless the query contains an uppercase letter. Items with the
+/// same score keep their original order.
+pub fn fuzzy_filter<T>(items: Vec<T>, query: &str, key: impl Fn(&T) -> &str) -> Vec<T> {
+    let query = query.trim();
+    if query.is_empty() {
+        return items;
+    }
 
-    // Check if file_path starts with folder_path
-    Ok(file_parent.starts_with(folder_path))
+    let matcher = SkimMatcherV2::default().smart_case();
+    let mut scored: Vec<(i64, T)> = items
+        .into_iter()
+        .filter_map(|item| {
+            matcher
+                .fuzzy_match(key(&item), query)
+                .map(|score| (score, item))
+        })
+        .collect();
+    scored.sort_by_key(|(score, _)| Reverse(*score));
+    scored.into_iter().map(|(_, item)| item).collect()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::path::Path;
-
-    #[test]
-    fn test_check_for_file_in_folder() -> std::result::Result<(), Box<dyn std::error::Error>> {
-        // Valid cases
-        assert!(check_for_file_in_folder(
-            Path::new("src/file.rs"),
-            Path::new("src")
-        )?);
-
-        assert!(check_for_file_in_folder(
-            Path::new("src/nested/deep/file.rs"),
-            Path::new("src")
-        )?);
-
-        assert!(!check_for_file_in_folder(
-            Path::new("other/file.rs"),
-            Path::new("src")
-        )?);
-        Ok(())
-    }
-
-    #[test]
-    fn test_check_for_file_in_folder_errors() {
-        // Empty paths
-        assert!(check_for_file_in_folder(Path::new(""), Path::new("src")).is_err());
-
-        assert!(check_for_file_in_folder(Path::new("file.txt"), Path::new("")).is_err());
-    }
 
     #[test]
     fn test_format_list() {
@@ -212,4 +225,62 @@ mod tests {
         let single = vec!["item"];
         assert_eq!(format_list(&single), "  - item");
     }
+
+    #[test]
+    fn test_parse_duration() -> std::result::Result<(), String> {
+        assert_eq!(parse_duration("30s")?, Duration::from_secs(30));
+        assert_eq!(parse_duration("15m")?, Duration::from_mins(15));
+        assert_eq!(parse_duration(" 2h ")?, Duration::from_hours(2));
+        assert_eq!(parse_duration("1d")?, Duration::from_hours(24));
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_duration_errors() {
+        assert!(parse_duration("15").is_err());
+        assert!(parse_duration("m").is_err());
+        assert!(parse_duration("15 minutes").is_err());
+        assert!(parse_duration("-5m").is_err());
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::from_secs(45)), "45s");
+        assert_eq!(format_duration(Duration::from_secs(130)), "2m 10s");
+        assert_eq!(format_duration(Duration::from_mins(65)), "1h 5m");
+        assert_eq!(format_duration(Duration::from_hours(74)), "3d 2h");
+    }
+
+    #[test]
+    fn test_ratio() {
+        assert!((ratio(3, 2) - 1.5).abs() < f64::EPSILON);
+        assert!(ratio(5, 0).abs() < f64::EPSILON);
+        assert!((ratio(usize::MAX, usize::MAX) - 1.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_fuzzy_filter() {
+        let paths = vec![
+            "README.md",
+            "src/cli.rs",
+            "src/git/commit.rs",
+            "tests/cli_tests.rs",
+        ];
+
+        assert_eq!(fuzzy_filter(paths.clone(), "", |p| p), paths);
+        assert_eq!(
+            fuzzy_filter(paths.clone(), "clirs", |p| p),
+            vec!["src/cli.rs", "tests/cli_tests.rs"]
+        );
+        assert_eq!(
+            fuzzy_filter(paths.clone(), "gitcom", |p| p),
+            vec!["src/git/commit.rs"]
+        );
+        assert_eq!(
+            fuzzy_filter(paths.clone(), "readme", |p| p),
+            vec!["README.md"]
+        );
+        // An uppercase letter makes the match case-sensitive
+        assert!(fuzzy_filter(paths, "Cli", |p| p).is_empty());
+    }
 }
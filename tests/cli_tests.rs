@@ -7,8 +7,8 @@
 //!
 //! # Test Structure
 //!
-//! - Each test creates a temporary directory for isolation
-//! - Git repositories are initialized in these temp directories
+//! - Each test creates a [`TestRepo`] fixture for isolation
+//! - The fixture initializes the repository, identity and home directory
 //! - Tests verify both successful operations and error cases
 //! - File operations are verified using git status and log commands
 //!
@@ -19,11 +19,11 @@
 //! - Write permissions for temporary directories
 //! - No interference with user's actual git configuration
 
-use assert_cmd::Command;
+mod rona_test;
+
 use assert_cmd::cargo::cargo_bin_cmd;
 use mockall::predicate;
-use std::fs;
-use tempfile::TempDir;
+use rona_test::{TestRepo, TestResult};
 
 /// Tests the help command functionality.
 ///
@@ -62,38 +62,575 @@ fn test_version_command() {
 /// - Files not matching the pattern remain unstaged
 /// - Git status shows correct staging state
 #[test]
-fn test_add_command() -> Result<(), Box<dyn std::error::Error>> {
-    let temp_dir = TempDir::new()?;
-    let temp_path = temp_dir.path();
-
-    // Initialize git repository
-    let mut git_init = Command::new("git");
-    git_init.current_dir(temp_path).arg("init");
-    git_init.assert().success();
+fn test_add_command() -> TestResult {
+    let repo = TestRepo::new()?;
 
     // Create test files
-    fs::write(temp_path.join("test.txt"), "test content")?;
-    fs::write(temp_path.join("test2.md"), "test content")?;
-    fs::write(temp_path.join("test3.md"), "test content")?;
+    repo.write("test.txt", "test content")?;
+    repo.write("test2.md", "test content")?;
+    repo.write("test3.md", "test content")?;
 
     // Test rona add with pattern exclusion
-    let mut cmd = cargo_bin_cmd!("rona");
-    cmd.current_dir(temp_path).arg("-a").arg(r"*.md"); // exclude all markdown files
-    cmd.assert().success();
+    repo.rona().arg("-a").arg(r"*.md").assert().success(); // exclude all markdown files
 
     // Verify file staging status
-    let mut git_status = Command::new("git");
-    git_status
-        .current_dir(temp_path)
-        .args(["status", "--porcelain", "-u"]);
+    let status = repo.status()?;
+    assert!(status.contains(r"A  test.txt")); // .txt file added
+    assert!(status.contains(r"?? test2.md")); // .md file excluded
+    assert!(status.contains(r"?? test3.md")); // .md file excluded
+
+    Ok(())
+}
+
+/// Tests the `preset` exclusion presets.
+///
+/// Verifies that:
+/// - `rona init --repo` writes the patterns of the presets detected at the root to
+///   `.commitignore`, once
+/// - With `preset = ["rust"]`, `rona -a` leaves the build output unstaged but stages
+///   the lock file
+/// - An unknown preset is rejected
+#[test]
+fn test_presets() -> TestResult {
+    let repo = TestRepo::new()?;
+    repo.write("Cargo.toml", "[package]\nname = \"demo\"\n")?;
+
+    repo.rona()
+        .args(["init", "--repo"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("from the rust preset"));
+    repo.rona().args(["init", "--repo"]).assert().success();
+    assert_eq!(repo.read(".commitignore")?, "target/\nCargo.lock\n");
+
+    repo.write(".rona.toml", "preset = [\"rust\"]\n")?;
+    repo.write("Cargo.lock", "# lock")?;
+    repo.write("target/debug/demo", "binary")?;
+    repo.write("src/main.rs", "fn main() {}")?;
+    repo.rona().arg("-a").assert().success();
+    let status = repo.status()?;
+    assert!(status.contains("A  src/main.rs"), "{status}");
+    assert!(status.contains("A  Cargo.lock"), "{status}");
+    assert!(status.contains("?? target/"), "{status}");
+
+    repo.write(".rona.toml", "preset = [\"cobol\"]\n")?;
+    repo.rona()
+        .arg("-a")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Unknown preset 'cobol'"));
+
+    Ok(())
+}
+
+/// Tests `rona config show --origins`.
+///
+/// Verifies that:
+/// - Each value is printed with the config file that set it, the project config
+///   overriding the global one
+/// - Values no file sets are reported as defaults
+/// - Without a configured editor, the one from `$EDITOR` is reported
+#[test]
+fn test_config_show_origins() -> TestResult {
+    let repo = TestRepo::new()?;
+    let global = repo.home().join(".config/rona.toml");
+    std::fs::create_dir_all(repo.home().join(".config"))?;
+    std::fs::write(&global, "editor = \"vim\"\ncommit_types = [\"feat\"]\n")?;
+    repo.write(".rona.toml", "editor = \"nano\"\n")?;
+
+    let output = repo.rona().args(["config", "show", "--origins"]).output()?;
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout)?;
+    let line = |key: &str| {
+        stdout
+            .lines()
+            .find(|line| line.starts_with(&format!("{key} ")))
+            .unwrap_or_default()
+            .to_string()
+    };
+
+    assert!(line("editor").contains("\"nano\""), "{stdout}");
+    assert!(line("editor").contains("project: "), "{stdout}");
+    assert!(line("commit_types").contains("global: "), "{stdout}");
+    assert!(line("change_id").contains("default"), "{stdout}");
+
+    repo.write("other.toml", "commit_types = [\"fix\"]\n")?;
+    repo.rona()
+        .args(["-f", "other.toml", "config", "show", "--origins"])
+        .env("EDITOR", "ed")
+        .env_remove("VISUAL")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"ed\"  # env: $EDITOR"));
+
+    Ok(())
+}
+
+/// Tests `rona config sync` from a branch of the remote.
+///
+/// Verifies that:
+/// - `--check` prints the differences and fails while the local config differs
+/// - `--dry-run` and a declined prompt leave the file unchanged
+/// - `-y` replaces the file, keeping the local `[config_sync]` table the canonical
+///   config does not have
+/// - A missing branch is reported
+#[test]
+fn test_config_sync_from_branch() -> TestResult {
+    let repo = TestRepo::with_initial_commit()?;
+    repo.add_bare_remote()?;
+    repo.git(&["switch", "--quiet", "--create", "conventions"])?;
+    repo.write(".rona.toml", "commit_types = [\"feat\", \"fix\"]\n")?;
+    repo.stage(&[".rona.toml"])?;
+    repo.commit("share the conventions")?;
+    repo.git(&["push", "--quiet", "origin", "conventions"])?;
+    repo.git(&["switch", "--quiet", "main"])?;
+    repo.git(&["branch", "--quiet", "-D", "conventions"])?;
+
+    let local = "commit_types = [\"feat\"]\n\n[config_sync]\nbranch = \"conventions\"\n";
+    repo.write(".rona.toml", local)?;
+
+    repo.rona()
+        .args(["config", "sync", "--check"])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("-commit_types = [\"feat\"]"))
+        .stdout(predicate::str::contains(
+            "+commit_types = [\"feat\", \"fix\"]",
+        ))
+        .stderr(predicate::str::contains(
+            ".rona.toml differs from .rona.toml on branch 'conventions' of 'origin'",
+        ));
+
+    repo.rona()
+        .args(["config", "sync", "--dry-run"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Would update"));
+    repo.rona()
+        .args(["config", "sync"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Config not changed."));
+    assert_eq!(std::fs::read_to_string(repo.join(".rona.toml"))?, local);
+
+    repo.rona()
+        .args(["config", "sync", "-y"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Updated .rona.toml"));
+    assert_eq!(
+        std::fs::read_to_string(repo.join(".rona.toml"))?,
+        "commit_types = [\"feat\", \"fix\"]\n\n[config_sync]\nbranch = \"conventions\"\n"
+    );
+    repo.rona()
+        .args(["config", "sync", "--check"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("up to date"));
+
+    repo.rona()
+        .args(["config", "sync", "--branch", "missing"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Cannot fetch branch 'missing' of 'origin'",
+        ));
+
+    Ok(())
+}
+
+/// Tests the `[[guards]]` of the global config.
+///
+/// Verifies that:
+/// - A guard matching a remote denies its features in the repository
+/// - A later guard's `allow` lifts an earlier deny
+/// - Guards for other remotes do not apply
+#[test]
+fn test_guards() -> TestResult {
+    let repo = TestRepo::with_initial_commit()?;
+    repo.git(&["remote", "add", "origin", "git@github.com:acme/app.git"])?;
+    std::fs::create_dir_all(repo.home().join(".config"))?;
+    std::fs::write(
+        repo.home().join(".config/rona.toml"),
+        "[[guards]]\ndeny = [\"stage\", \"commit\"]\n\n\
+         [[guards]]\nremote = \"github.com/acme/*\"\nallow = [\"stage\"]\n\n\
+         [[guards]]\nremote = \"gitlab.com/*\"\nallow = [\"commit\"]\n",
+    )?;
+    repo.write("notes.txt", "hello")?;
+
+    repo.rona().args(["-a"]).assert().success();
+    assert!(repo.status()?.contains("A  notes.txt"));
+
+    repo.write("commit_message.md", "(chore on main) Add notes\n")?;
+    repo.rona()
+        .args(["-c", "--yes"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Refusing to commit in this repository: denied by the guard without path or remote",
+        ));
+
+    Ok(())
+}
+
+/// Tests `accessibility = true`.
+///
+/// Verifies that:
+/// - Glyphs are replaced with ASCII labels
+/// - No color escape codes are written
+#[test]
+fn test_accessibility_output() -> TestResult {
+    let repo = TestRepo::new()?;
+    repo.write(".rona.toml", "accessibility = true\n")?;
+
+    let output = repo
+        .rona()
+        .args(["init", "--repo"])
+        .env("CLICOLOR_FORCE", "1")
+        .output()?;
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout)?;
+    assert!(stdout.starts_with("OK: "), "{stdout}");
+    assert!(stdout.is_ascii(), "{stdout}");
+    assert!(!stdout.contains('\u{1b}'), "{stdout}");
+
+    Ok(())
+}
+
+/// Tests the `rona -a --dry-run` summary.
+///
+/// Verifies that:
+/// - Added and deleted files are listed separately
+/// - The count of excluded files is printed as is, without underflowing
+/// - Nothing is staged
+#[test]
+fn test_add_dry_run_summary() -> TestResult {
+    let repo = TestRepo::with_initial_commit()?;
+    repo.write("notes.md", "notes")?;
+    repo.write("build.log", "log")?;
+    repo.write("debug.log", "log")?;
+    repo.remove("README.md")?;
+
+    repo.rona()
+        .args(["-a", "*.log", "--dry-run"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Would add 1 files:\n  + notes.md"))
+        .stdout(predicate::str::contains(
+            "Would delete 1 files:\n  - README.md",
+        ))
+        .stdout(predicate::str::contains("Would exclude 2 files"));
+
+    assert!(!repo.status()?.contains("A  notes.md"));
+    Ok(())
+}
+
+/// Tests `rona explain-status`.
+///
+/// Verifies that:
+/// - Files are grouped by state with an explanation and the rona commands for it
+/// - A file staged and edited again is listed in both groups
+/// - The next step points to `rona -g` once something is staged
+/// - A clean tree says there is nothing to commit
+#[test]
+fn test_explain_status() -> TestResult {
+    let repo = TestRepo::with_initial_commit()?;
+    repo.rona()
+        .arg("explain-status")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Nothing to commit"));
+
+    repo.write("README.md", "# staged\n")?;
+    repo.git(&["add", "README.md"])?;
+    repo.write("README.md", "# edited again\n")?;
+    repo.write("notes.md", "notes")?;
+
+    let output = repo.rona().arg("explain-status").output()?;
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout)?;
+    for expected in [
+        "Staged: modified (1)",
+        "Modified (not staged) (1)",
+        "Untracked (1)",
+        "    notes.md",
+        "rona restore <file>",
+        "Next: `rona -g`",
+    ] {
+        assert!(
+            stdout.contains(expected),
+            "missing {expected:?} in:\n{stdout}"
+        );
+    }
+    assert_eq!(stdout.matches("    README.md").count(), 2, "{stdout}");
+    Ok(())
+}
+
+/// Tests `rona status`.
+///
+/// Verifies that:
+/// - The branch and its ahead/behind count against the upstream are shown
+/// - Changed files are grouped with counts, renames as `old -> new`
+/// - The summary line sums up the groups
+/// - A clean tree is reported as such
+#[test]
+fn test_status() -> TestResult {
+    let repo = TestRepo::with_initial_commit()?;
+    repo.add_bare_remote()?;
+    repo.git(&["push", "--quiet", "-u", "origin", "HEAD"])?;
+    repo.rona()
+        .arg("status")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("up to date"))
+        .stdout(predicate::str::contains("Nothing to commit"));
+
+    repo.write("notes.md", "notes")?;
+    repo.git(&["add", "notes.md"])?;
+    repo.git(&["commit", "--quiet", "-m", "notes"])?;
+    repo.git(&["mv", "notes.md", "NOTES.md"])?;
+    repo.write("README.md", "# edited\n")?;
+    repo.write("todo.txt", "todo")?;
+
+    let output = repo.rona().arg("status").output()?;
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout)?;
+    for expected in [
+        ": 1 ahead",
+        "Staged (1)",
+        "renamed    notes.md -> NOTES.md",
+        "Modified (1)",
+        "modified   README.md",
+        "untracked  todo.txt",
+        "1 staged, 1 modified, 1 untracked",
+    ] {
+        assert!(
+            stdout.contains(expected),
+            "missing {expected:?} in:\n{stdout}"
+        );
+    }
+    Ok(())
+}
+
+/// Tests `rona schema`.
+///
+/// Verifies that:
+/// - A single output's schema is printed as JSON, listing its required fields
+/// - Without an argument, every output's schema is printed with the version
+/// - An unknown output is rejected
+#[test]
+fn test_schema() -> TestResult {
+    let repo = TestRepo::new()?;
+    let output = repo.rona().args(["schema", "usage"]).output()?;
+    assert!(output.status.success());
+    let schema: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(schema["title"], "rona usage --json");
+    assert_eq!(schema["properties"]["schema_version"]["const"], 1);
+    assert!(
+        schema["required"]
+            .as_array()
+            .is_some_and(|required| required.contains(&"operations".into()))
+    );
+
+    let output = repo.rona().arg("schema").output()?;
+    assert!(output.status.success());
+    let all: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(all["schema_version"], 1);
+    for name in ["history-ops", "show", "status", "usage"] {
+        assert!(all["outputs"][name].is_object(), "missing {name}");
+    }
+
+    repo.rona().args(["schema", "log"]).assert().failure();
+    Ok(())
+}
+
+/// Tests the global `--output json` flag.
+///
+/// Verifies that:
+/// - `list-status` prints the changed files with their count
+/// - `add --dry-run` lists the files to add, delete and exclude without staging
+/// - `status` reports the branch, upstream divergence, file states and counts
+/// - `commit --dry-run --push` prints the commit, then the push, one object per line
+#[test]
+fn test_output_json() -> TestResult {
+    let repo = TestRepo::with_initial_commit()?;
+    repo.add_bare_remote()?;
+    repo.git(&["push", "--quiet", "-u", "origin", "HEAD"])?;
+    repo.write("notes.md", "notes")?;
+    repo.write("debug.log", "log")?;
+
+    let json = |args: &[&str]| -> Result<Vec<serde_json::Value>, Box<dyn std::error::Error>> {
+        let output = repo.rona().args(["--output", "json"]).args(args).output()?;
+        assert!(output.status.success(), "{args:?}");
+        String::from_utf8(output.stdout)?
+            .lines()
+            .map(|line| Ok(serde_json::from_str(line)?))
+            .collect()
+    };
+
+    let list = json(&["list-status"])?;
+    assert_eq!(list[0]["schema_version"], 1);
+    assert_eq!(list[0]["count"], 2);
+
+    let add = json(&["-a", "*.log", "--dry-run"])?;
+    assert_eq!(add[0]["add"], serde_json::json!(["notes.md"]));
+    assert_eq!(add[0]["excluded"], serde_json::json!(["debug.log"]));
+    assert!(!repo.status()?.contains("A  notes.md"));
+
+    repo.git(&["add", "notes.md"])?;
+    let status = json(&["status"])?;
+    assert_eq!(status[0]["ahead"], 0);
+    assert_eq!(status[0]["upstream"], "origin/main");
+    assert_eq!(status[0]["files"][0]["state"], "staged_new");
+    assert_eq!(status[0]["counts"]["staged"], 1);
+    assert_eq!(status[0]["counts"]["untracked"], 1);
+
+    let commit = json(&["commit", "-m", "add notes", "-u", "--push", "--dry-run"])?;
+    assert_eq!(commit.len(), 2);
+    assert_eq!(commit[0]["signed"], false);
+    assert_eq!(commit[0]["files"][0]["path"], "notes.md");
+    assert_eq!(commit[1]["remote"], "origin");
+    assert_eq!(commit[1]["branch"], "main");
+    Ok(())
+}
+
+/// Tests that `rona commit` drops the commented diff the editor mode appends.
+///
+/// Verifies that:
+/// - Everything below the scissors line is not committed
+/// - The rest of the message is committed as written, `#123` lines included
+/// - The scissors line follows `core.commentChar`
+#[test]
+fn test_commit_strips_staged_diff() -> TestResult {
+    let repo = TestRepo::with_initial_commit()?;
+    repo.write("a.rs", "fn a() {}\n")?;
+    repo.stage(&["a.rs"])?;
+    repo.write(
+        "commit_message.md",
+        "(feat on main) add a\n\n- `a.rs`: new\n#123\n\n\
+         # ------------------------ >8 ------------------------\n\
+         # Do not modify or remove the line above.\n\
+         # diff --git a/a.rs b/a.rs\n\
+         +fn a() {}\n",
+    )?;
+
+    repo.rona()
+        .args(["commit", "--yes", "-u"])
+        .assert()
+        .success();
+    let message = repo.git(&["log", "-1", "--format=%B"])?;
+    assert_eq!(
+        message.trim_end(),
+        "(feat on main) add a\n\n- `a.rs`: new\n#123"
+    );
+
+    repo.git(&["config", "core.commentChar", ";"])?;
+    repo.write("b.rs", "fn b() {}\n")?;
+    repo.stage(&["b.rs"])?;
+    repo.write(
+        "commit_message.md",
+        "(feat on main) add b\n\n\
+         ; ------------------------ >8 ------------------------\n\
+         ; diff --git a/b.rs b/b.rs\n",
+    )?;
+    repo.rona()
+        .args(["commit", "--yes", "-u"])
+        .assert()
+        .success();
+    let message = repo.git(&["log", "-1", "--format=%B"])?;
+    assert_eq!(message.trim_end(), "(feat on main) add b");
+    Ok(())
+}
+
+/// Tests how file and pattern arguments are resolved inside the repository.
+///
+/// Verifies that:
+/// - An exclude pattern with `..` is resolved from the current directory
+/// - Patterns and files pointing outside the repository are refused
+/// - A file starting with `-` is unstaged as a file, not read as an option
+#[test]
+fn test_path_arguments() -> TestResult {
+    let repo = TestRepo::with_initial_commit()?;
+    repo.write("docs/a.md", "a")?;
+    repo.write("src/b.md", "b")?;
+    repo.stage_all()?;
+    repo.git(&["commit", "--quiet", "-m", "docs"])?;
+    repo.write("docs/a.md", "a2")?;
+    repo.write("src/b.md", "b2")?;
+
+    repo.rona_in("src")
+        .args(["-a", "../docs/*.md", "--dry-run"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("+ src/b.md"))
+        .stdout(predicate::str::contains("Would exclude 1 files"));
+    repo.rona_in("src")
+        .args(["-a", "../../*.md", "--dry-run"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("outside the repository"));
+    repo.rona()
+        .args(["reset", "/etc/passwd"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("outside the repository"));
+
+    repo.write("-rf", "x")?;
+    repo.stage(&["-rf"])?;
+    repo.rona()
+        .args(["reset", "--", "-rf"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Unstaged 1 files."));
+    assert!(repo.status()?.contains("?? -rf"), "{}", repo.status()?);
+    Ok(())
+}
+
+/// Tests `rona reset` with files given from a subdirectory.
+///
+/// Verifies that:
+/// - A file is resolved from the current directory, not the repository root
+/// - A `..` path reaching back into the repository works
+#[test]
+fn test_reset_from_subdirectory() -> TestResult {
+    let repo = TestRepo::with_initial_commit()?;
+    repo.write("src/a.rs", "a")?;
+    repo.write("README.md", "readme")?;
+    repo.stage(&["src/a.rs", "README.md"])?;
 
-    git_status
+    repo.rona_in("src")
+        .args(["reset", "a.rs", "../README.md"])
         .assert()
         .success()
-        .stdout(predicate::str::contains(r"A  test.txt")) // .txt file added
-        .stdout(predicate::str::contains(r"?? test2.md")) // .md file excluded
-        .stdout(predicate::str::contains(r"?? test3.md")); // .md file excluded
+        .stdout(predicate::str::contains("Unstaged 2 files."));
+    assert!(repo.git(&["diff", "--cached", "--name-only"])?.is_empty());
+    Ok(())
+}
+
+/// Tests `rona restore` with files given from a subdirectory.
+///
+/// Verifies that:
+/// - Files are resolved from the current directory, `..` included
+/// - Files that are not named keep their changes
+#[test]
+fn test_restore_from_subdirectory() -> TestResult {
+    let repo = TestRepo::with_initial_commit()?;
+    repo.write("src/a.rs", "a")?;
+    repo.write("src/b.rs", "b")?;
+    repo.write("notes.md", "notes")?;
+    repo.stage_all()?;
+    repo.commit("add files")?;
+    repo.write("src/a.rs", "a2")?;
+    repo.write("src/b.rs", "b2")?;
+    repo.write("notes.md", "notes2")?;
 
+    repo.rona_in("src")
+        .args(["restore", "-y", "a.rs", "../notes.md"])
+        .assert()
+        .success();
+    assert_eq!(repo.read("src/a.rs")?, "a");
+    assert_eq!(repo.read("notes.md")?, "notes");
+    assert_eq!(repo.read("src/b.rs")?, "b2");
     Ok(())
 }
 
@@ -110,37 +647,19 @@ fn test_add_command() -> Result<(), Box<dyn std::error::Error>> {
 /// - Paths are not doubled in the git index
 /// - `git add` succeeds when the user's CWD is not the repo root
 #[test]
-fn test_add_from_subdirectory() -> Result<(), Box<dyn std::error::Error>> {
-    let temp_dir = TempDir::new()?;
-    let temp_path = temp_dir.path();
-
-    // Initialize git repository at the root
-    Command::new("git")
-        .current_dir(temp_path)
-        .arg("init")
-        .assert()
-        .success();
+fn test_add_from_subdirectory() -> TestResult {
+    let repo = TestRepo::new()?;
 
-    // Create a deep nested directory structure (mirrors the reported scenario)
-    let subdir = temp_path.join("packages/preview/clean-cnam-template/1.6.4");
-    fs::create_dir_all(&subdir)?;
-
-    // Create files inside the nested directory
-    fs::write(subdir.join("thumbnail.png"), "fake png")?;
-    fs::write(subdir.join("README.md"), "# readme")?;
+    // Create files inside a deep nested directory (mirrors the reported scenario)
+    let subdir = "packages/preview/clean-cnam-template/1.6.4";
+    repo.write(&format!("{subdir}/thumbnail.png"), "fake png")?;
+    repo.write(&format!("{subdir}/README.md"), "# readme")?;
 
     // Run `rona -a` from the subdirectory, not from the repo root
-    let mut cmd = cargo_bin_cmd!("rona");
-    cmd.current_dir(&subdir).arg("-a");
-    cmd.assert().success();
+    repo.rona_in(subdir).arg("-a").assert().success();
 
     // Verify files are staged with correct (non-doubled) paths
-    let git_status = Command::new("git")
-        .current_dir(temp_path)
-        .args(["status", "--porcelain"])
-        .output()?;
-
-    let status_output = String::from_utf8_lossy(&git_status.stdout);
+    let status_output = repo.status()?;
 
     // Both files must appear as staged (index 'A') with their repo-root-relative paths
     assert!(
@@ -161,6 +680,30 @@ fn test_add_from_subdirectory() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+/// Tests `--scope` with a relative path from a subdirectory.
+///
+/// Verifies that:
+/// - The path is resolved from the current directory, not the repository root
+/// - Listed paths stay relative to the repository root
+#[test]
+fn test_scope_from_subdirectory() -> TestResult {
+    let repo = TestRepo::with_initial_commit()?;
+    repo.write("packages/api/src/lib.rs", "pub fn api() {}\n")?;
+    repo.write("packages/web/index.ts", "\n")?;
+
+    repo.rona_in("packages/api")
+        .args(["--scope", "../web", "-l"])
+        .assert()
+        .success()
+        .stdout("packages/web/\n");
+    repo.rona_in("packages/api")
+        .args(["--scope", ".", "-l"])
+        .assert()
+        .success()
+        .stdout("packages/api/\n");
+    Ok(())
+}
+
 /// Tests that `rona -a` correctly handles deleted files when run from a subdirectory.
 ///
 /// Extends the subdirectory regression test to also cover the `git rm --cached`
@@ -170,62 +713,23 @@ fn test_add_from_subdirectory() -> Result<(), Box<dyn std::error::Error>> {
 /// - A file deleted in a subdirectory is staged for deletion correctly
 /// - The deletion is reflected in `git status` without path doubling
 #[test]
-fn test_add_deleted_file_from_subdirectory() -> Result<(), Box<dyn std::error::Error>> {
-    let temp_dir = TempDir::new()?;
-    let temp_path = temp_dir.path();
-
-    // Initialize git repository
-    Command::new("git")
-        .current_dir(temp_path)
-        .arg("init")
-        .assert()
-        .success();
-
-    // Configure git user so we can commit
-    for (key, val) in [
-        ("user.name", "Test"),
-        ("user.email", "t@t.com"),
-        ("commit.gpgsign", "false"),
-    ] {
-        Command::new("git")
-            .current_dir(temp_path)
-            .args(["config", "--local", key, val])
-            .assert()
-            .success();
-    }
+fn test_add_deleted_file_from_subdirectory() -> TestResult {
+    let repo = TestRepo::new()?;
 
     // Create the nested directory and a file, then commit it
-    let subdir = temp_path.join("packages/preview/mypkg/1.0");
-    fs::create_dir_all(&subdir)?;
-    fs::write(subdir.join("asset.png"), "data")?;
-
-    Command::new("git")
-        .current_dir(temp_path)
-        .args(["add", "--all"])
-        .assert()
-        .success();
-
-    Command::new("git")
-        .current_dir(temp_path)
-        .args(["commit", "-m", "initial"])
-        .assert()
-        .success();
+    let subdir = "packages/preview/mypkg/1.0";
+    repo.write(&format!("{subdir}/asset.png"), "data")?;
+    repo.stage_all()?;
+    repo.commit("initial")?;
 
     // Delete the file
-    fs::remove_file(subdir.join("asset.png"))?;
+    repo.remove(&format!("{subdir}/asset.png"))?;
 
     // Run `rona -a` from the subdirectory
-    let mut cmd = cargo_bin_cmd!("rona");
-    cmd.current_dir(&subdir).arg("-a");
-    cmd.assert().success();
+    repo.rona_in(subdir).arg("-a").assert().success();
 
     // Deleted file must be staged (index 'D') with correct non-doubled path
-    let git_status = Command::new("git")
-        .current_dir(temp_path)
-        .args(["status", "--porcelain"])
-        .output()?;
-
-    let status_output = String::from_utf8_lossy(&git_status.stdout);
+    let status_output = repo.status()?;
 
     assert!(
         status_output.contains("D  packages/preview/mypkg/1.0/asset.png"),
@@ -248,66 +752,3718 @@ fn test_add_deleted_file_from_subdirectory() -> Result<(), Box<dyn std::error::E
 /// - Commit message is correctly applied
 /// - Git log shows the commit with correct message
 #[test]
-fn test_commit_command() -> Result<(), Box<dyn std::error::Error>> {
-    let temp_dir = TempDir::new()?;
-    let temp_path = temp_dir.path();
-
-    // Initialize git repository
-    let mut git_init = Command::new("git");
-    git_init.current_dir(temp_path).arg("init");
-    git_init.assert().success();
-
-    // Configure git user (using --local to only affect this test repository)
-    let mut git_config = Command::new("git");
-    git_config
-        .current_dir(temp_path)
-        .args(["config", "--local", "user.name", "Test User"]);
-    git_config.assert().success();
-
-    let mut git_config_email = Command::new("git");
-    git_config_email.current_dir(temp_path).args([
-        "config",
-        "--local",
-        "user.email",
-        "test@example.com",
-    ]);
-    git_config_email.assert().success();
-
-    // Ensure GPG signing does not interfere with the test (using --local)
-    let mut git_disable_gpgsign = Command::new("git");
-    git_disable_gpgsign.current_dir(temp_path).args([
-        "config",
-        "--local",
-        "commit.gpgsign",
-        "false",
-    ]);
-    git_disable_gpgsign.assert().success();
+fn test_commit_command() -> TestResult {
+    let repo = TestRepo::new()?;
 
     // Create and stage a test file
-    fs::write(temp_path.join("test.txt"), "test content")?;
-    let mut git_add = Command::new("git");
-    git_add.current_dir(temp_path).args(["add", "test.txt"]);
-    git_add.assert().success();
+    repo.write("test.txt", "test content")?;
+    repo.stage(&["test.txt"])?;
 
     // Create commit message file with proper format
     let commit_msg = "[1] (feat on main)\n\n- `test.txt`:\n\n\t\n";
-    fs::write(temp_path.join("commit_message.md"), commit_msg)?;
+    repo.write("commit_message.md", commit_msg)?;
 
     // Test rona commit with --yes to skip confirmation
-    let mut cmd = cargo_bin_cmd!("rona");
-    cmd.current_dir(temp_path).arg("-c").arg("--yes");
-    cmd.assert().success();
+    repo.rona().arg("-c").arg("--yes").assert().success();
 
     // Verify commit message in git log
-    let mut git_log = Command::new("git");
-    git_log
-        .current_dir(temp_path)
-        .args(["log", "-1", "--oneline"]);
+    let log = repo.git(&["log", "-1", "--oneline"])?;
+    assert!(log.contains("feat"), "unexpected log: {log}");
+
+    Ok(())
+}
+
+/// Tests the `[commit_size]` guardrails.
+///
+/// Verifies that:
+/// - Exempt files are not counted
+/// - Exceeding a limit warns but still commits
+/// - With `confirm = true`, a declined (non-interactive) prompt cancels the commit
+#[test]
+fn test_commit_size_guardrails() -> TestResult {
+    use mockall::PredicateBooleanExt;
+
+    let repo = TestRepo::with_initial_commit()?;
+    repo.write(
+        ".rona.toml",
+        "[commit_size]\nmax_files = 2\nmax_insertions = 3\nexempt = [\"*.lock\"]\n",
+    )?;
+    repo.write("a.rs", "1\n")?;
+    repo.write("b.rs", "1\n")?;
+    repo.write("deps.lock", "1\n2\n3\n4\n")?;
+    repo.stage(&["a.rs", "b.rs", "deps.lock"])?;
+
+    repo.rona()
+        .args(["commit", "-m", "small", "--yes", "-u", "--dry-run"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("This commit is large").not());
+
+    repo.write("c.rs", "1\n2\n")?;
+    repo.stage(&["c.rs"])?;
+    repo.rona()
+        .args(["commit", "-m", "big", "--yes", "-u"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "This commit is large: 3 files (max 2), 4 insertions (max 3)",
+        ));
+    assert_eq!(
+        repo.git(&["log", "-1", "--format=%s"])?,
+        "[2] (chore on main) big"
+    );
+
+    repo.write(
+        ".rona.toml",
+        "[commit_size]\nmax_files = 0\nconfirm = true\n",
+    )?;
+    repo.write("d.rs", "1\n")?;
+    repo.stage(&["d.rs"])?;
+    repo.rona()
+        .args(["commit", "-m", "declined", "-u"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Commit cancelled."));
+    assert_eq!(
+        repo.git(&["log", "-1", "--format=%s"])?,
+        "[2] (chore on main) big"
+    );
+
+    Ok(())
+}
+
+/// Tests `rona format-patch`.
+///
+/// Verifies that:
+/// - `--dry-run` lists the commits without writing files
+/// - The patches keep the rona subject header
+/// - The cover letter is filled from the template, grouped by commit type
+#[test]
+fn test_format_patch_writes_series_with_cover_letter() -> TestResult {
+    let repo = TestRepo::with_initial_commit()?;
+    repo.write(
+        ".rona.toml",
+        "[format_patch]\ncover_letter = \"Login series\\n\\n{changes}\"\n",
+    )?;
+    repo.write("a.rs", "1\n")?;
+    repo.stage(&["a.rs"])?;
+    repo.commit("[1] (feat on main) add form")?;
+    repo.write("b.rs", "1\n")?;
+    repo.stage(&["b.rs"])?;
+    repo.commit("[2] (fix on main) handle empty password")?;
+
+    repo.rona()
+        .args(["format-patch", "-n", "2", "--dry-run"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Would write 2 patch(es) to patches",
+        ))
+        .stdout(predicate::str::contains("[1] (feat on main) add form"));
+    assert!(!repo.join("patches").exists());
+
+    repo.rona()
+        .args(["format-patch", "-n", "2", "--cover-letter"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Wrote 3 file(s)"));
+
+    let patch = repo.read("patches/0001-1-feat-on-main-add-form.patch")?;
+    assert!(
+        patch.contains("Subject: [PATCH 1/2] [1] (feat on main) add form"),
+        "unexpected patch: {patch}"
+    );
+    let letter = repo.read("patches/0000-cover-letter.patch")?;
+    assert!(
+        letter.contains("Subject: [PATCH 0/2] Login series"),
+        "unexpected cover letter: {letter}"
+    );
+    assert!(
+        letter.contains("feat:\n- add form\n\nfix:\n- handle empty password"),
+        "unexpected cover letter: {letter}"
+    );
+    assert!(!letter.contains("*** BLURB HERE ***"));
+
+    Ok(())
+}
+
+/// Tests `rona am`.
+///
+/// Verifies that:
+/// - Cover letters are skipped and the rona subject header is kept
+/// - `--retemplate` renumbers subjects and templates plain ones
+/// - A conflicting patch stops with the conflicted files, and `--abort` restores the branch
+#[test]
+fn test_am_applies_and_retemplates_patches() -> TestResult {
+    let repo = TestRepo::with_initial_commit()?;
+    repo.write("a.rs", "1\n")?;
+    repo.stage(&["a.rs"])?;
+    repo.commit("[1] (feat on main) add form")?;
+    repo.write("a.rs", "2\n")?;
+    repo.stage(&["a.rs"])?;
+    repo.commit("plain change")?;
+    repo.rona()
+        .args(["format-patch", "-n", "2", "--cover-letter", "-o", "out"])
+        .assert()
+        .success();
+    let patches = [
+        "out/0000-cover-letter.patch",
+        "out/0001-1-feat-on-main-add-form.patch",
+        "out/0002-plain-change.patch",
+    ];
 
-    git_log
+    repo.git(&["reset", "--hard", "HEAD~2"])?;
+    repo.rona()
+        .arg("am")
+        .args(patches)
         .assert()
         .success()
-        .stdout(predicate::str::contains("feat"));
+        .stdout(predicate::str::contains("Skipping"))
+        .stdout(predicate::str::contains("Patches applied."));
+    assert_eq!(
+        repo.git(&["log", "-2", "--format=%s"])?,
+        "plain change\n[1] (feat on main) add form"
+    );
+
+    repo.git(&["reset", "--hard", "HEAD~2"])?;
+    repo.rona()
+        .args(["am", "--retemplate"])
+        .args(patches)
+        .assert()
+        .success();
+    assert_eq!(
+        repo.git(&["log", "-2", "--format=%s"])?,
+        "[3] (chore on main) plain change\n[2] (feat on main) add form"
+    );
+
+    repo.git(&["reset", "--hard", "HEAD~2"])?;
+    repo.write("a.rs", "conflicting\n")?;
+    repo.stage(&["a.rs"])?;
+    repo.commit("diverge")?;
+    repo.rona()
+        .arg("am")
+        .args(patches)
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("Conflicts to resolve"))
+        .stdout(predicate::str::contains("a.rs"));
+    repo.rona()
+        .args(["am", "--abort"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Patch session aborted."));
+    assert_eq!(repo.git(&["log", "-1", "--format=%s"])?, "diverge");
+
+    Ok(())
+}
+
+/// Tests `rona backport`.
+///
+/// Verifies that:
+/// - A missing target branch is created from the remote, and the backport is pushed
+/// - The subject is re-templated for the target branch, with a `(backport of ...)` footer
+/// - A target the commit does not apply to is reported, and the current branch restored
+#[test]
+fn test_backport_to_release_branches() -> TestResult {
+    let repo = TestRepo::with_initial_commit()?;
+    let remote = repo.add_bare_remote()?;
+    let remote_dir = format!("--git-dir={}", remote.display());
+    repo.write("lib.rs", "fn guard() {}\n")?;
+    repo.stage(&["lib.rs"])?;
+    repo.commit("[2] (chore on main) add lib")?;
+    repo.git(&[
+        "push",
+        "--quiet",
+        "origin",
+        "main",
+        "HEAD:refs/heads/release/1.x",
+    ])?;
+    repo.git(&["branch", "release/2.x"])?;
+    repo.git(&["switch", "--quiet", "release/2.x"])?;
+    repo.write("lib.rs", "fn guard() { todo!() }\n")?;
+    repo.stage(&["lib.rs"])?;
+    repo.commit("diverge")?;
+    repo.git(&["switch", "--quiet", "main"])?;
+
+    repo.write("lib.rs", "fn guard(input: &str) {}\n")?;
+    repo.stage(&["lib.rs"])?;
+    repo.commit("[3] (fix on main) guard input\n\nRejects empty input.")?;
+    let sha = repo.git(&["rev-parse", "HEAD"])?;
+
+    repo.rona()
+        .args(["backport", "HEAD", "--to", "release/1.x", "--dry-run"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Would backport"))
+        .stdout(predicate::str::contains(
+            "[3] (fix on release/1.x) guard input",
+        ));
+
+    repo.rona()
+        .args([
+            "backport",
+            "HEAD",
+            "--to",
+            "release/1.x,release/2.x",
+            "--push",
+            "-u",
+        ])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("Backported"))
+        .stdout(predicate::str::contains("release/2.x:"));
+
+    assert_eq!(repo.git(&["branch", "--show-current"])?, "main");
+    assert_eq!(
+        repo.git(&[&remote_dir, "log", "-1", "--format=%B", "release/1.x"])?,
+        format!(
+            "[3] (fix on release/1.x) guard input\n\nRejects empty input.\n\n(backport of {sha})"
+        )
+    );
+    assert_eq!(
+        repo.git(&["log", "-1", "--format=%s", "release/2.x"])?,
+        "diverge"
+    );
+
+    Ok(())
+}
+
+/// Tests how `{branch_name}` is rendered.
+///
+/// Verifies that:
+/// - Only a first segment equal to a commit type is removed
+/// - The `[branch_name]` separator, case and length rules apply
+#[test]
+fn test_commit_formats_branch_name() -> TestResult {
+    let repo = TestRepo::with_initial_commit()?;
+    repo.git(&["switch", "--quiet", "-c", "feature/test-fixtures"])?;
+    repo.write("a.rs", "fn a() {}\n")?;
+    repo.stage(&["a.rs"])?;
+    repo.rona()
+        .args(["commit", "-m", "add a", "--yes", "-u"])
+        .assert()
+        .success();
+    assert_eq!(
+        repo.git(&["log", "-1", "--format=%s"])?,
+        "[2] (chore on feature/test-fixtures) add a"
+    );
+
+    repo.git(&["switch", "--quiet", "-c", "feat/add-user_login-form"])?;
+    repo.write(
+        ".rona.toml",
+        "[branch_name]\nseparator = \" \"\ncase = \"upper\"\nmax_length = 14\n",
+    )?;
+    repo.write("b.rs", "fn b() {}\n")?;
+    repo.stage(&["b.rs"])?;
+    repo.rona()
+        .args(["commit", "-m", "add b", "--yes", "-u"])
+        .assert()
+        .success();
+    assert_eq!(
+        repo.git(&["log", "-1", "--format=%s"])?,
+        "[3] (feat on ADD USER LOGIN) add b"
+    );
+
+    Ok(())
+}
+
+/// Tests `commit_number_source = "remote"`.
+///
+/// Verifies that:
+/// - By default, `{commit_number}` counts the commits reachable from `HEAD`
+/// - With `remote`, commits pushed by someone else are counted after a fetch,
+///   together with the local commits not pushed yet
+/// - A guard denying `fetch` counts against the last fetched state
+/// - An upstream remote starting with `-` is refused
+#[test]
+fn test_commit_number_counts_remote_commits() -> TestResult {
+    let repo = TestRepo::with_initial_commit()?;
+    repo.add_bare_remote()?;
+    repo.git(&["push", "--quiet", "-u", "origin", "main"])?;
+
+    // Someone else pushes a commit that is not fetched yet.
+    repo.write("theirs.rs", "fn theirs() {}\n")?;
+    repo.stage(&["theirs.rs"])?;
+    repo.commit("theirs")?;
+    repo.git(&["push", "--quiet"])?;
+    repo.git(&["reset", "--quiet", "--hard", "HEAD~1"])?;
+    repo.git(&["update-ref", "refs/remotes/origin/main", "HEAD"])?;
+
+    repo.write("a.rs", "fn a() {}\n")?;
+    repo.stage(&["a.rs"])?;
+    repo.rona()
+        .args(["commit", "-m", "add a", "--yes", "-u"])
+        .assert()
+        .success();
+    assert_eq!(
+        repo.git(&["log", "-1", "--format=%s"])?,
+        "[2] (chore on main) add a"
+    );
+
+    repo.write(".rona.toml", "commit_number_source = \"remote\"\n")?;
+    std::fs::create_dir_all(repo.home().join(".config"))?;
+    std::fs::write(
+        repo.home().join(".config/rona.toml"),
+        "[[guards]]\ndeny = [\"fetch\"]\n",
+    )?;
+    repo.write("b.rs", "fn b() {}\n")?;
+    repo.stage(&["b.rs"])?;
+    repo.rona()
+        .args(["commit", "-m", "add b", "--yes", "-u"])
+        .assert()
+        .success();
+    assert_eq!(
+        repo.git(&["log", "-1", "--format=%s"])?,
+        "[3] (chore on main) add b"
+    );
+
+    std::fs::remove_file(repo.home().join(".config/rona.toml"))?;
+    repo.write("c.rs", "fn c() {}\n")?;
+    repo.stage(&["c.rs"])?;
+    repo.rona()
+        .args(["commit", "-m", "add c", "--yes", "-u"])
+        .assert()
+        .success();
+    assert_eq!(
+        repo.git(&["log", "-1", "--format=%s"])?,
+        "[5] (chore on main) add c"
+    );
+
+    repo.git(&["config", "branch.main.remote", "--upload-pack=touch pwned"])?;
+    repo.write("d.rs", "fn d() {}\n")?;
+    repo.stage(&["d.rs"])?;
+    repo.rona()
+        .args(["commit", "-m", "add d", "--yes", "-u"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Upstream remote cannot start with '-': --upload-pack=touch pwned",
+        ));
+    assert!(!repo.join("pwned").exists());
+
+    Ok(())
+}
+
+/// Tests `commit_number_source = "branch-only"` and `"per-type"`.
+///
+/// Verifies that:
+/// - With `branch-only`, numbering starts over on a branch forked from the default branch
+/// - With `per-type`, each commit type has its own counter
+#[test]
+fn test_commit_number_per_branch_and_type() -> TestResult {
+    let repo = TestRepo::with_initial_commit()?;
+    let commit = |file: &str, args: &[&str]| -> TestResult<String> {
+        repo.write(file, "fn f() {}\n")?;
+        repo.stage(&[file])?;
+        repo.rona()
+            .args(["commit", "--yes", "-u"])
+            .args(args)
+            .assert()
+            .success();
+        repo.git(&["log", "-1", "--format=%s"])
+    };
+    commit("a.rs", &["-m", "add a"])?;
+
+    repo.write(".rona.toml", "commit_number_source = \"branch-only\"\n")?;
+    repo.git(&["switch", "--quiet", "-c", "feat/login"])?;
+    assert_eq!(
+        commit("b.rs", &["-m", "add b"])?,
+        "[1] (feat on login) add b"
+    );
+    assert_eq!(
+        commit("c.rs", &["-m", "add c"])?,
+        "[2] (feat on login) add c"
+    );
+
+    repo.write(".rona.toml", "commit_number_source = \"per-type\"\n")?;
+    assert_eq!(
+        commit("d.rs", &["-m", "fix d", "-t", "fix"])?,
+        "[1] (fix on login) fix d"
+    );
+    assert_eq!(
+        commit("e.rs", &["-m", "add e"])?,
+        "[3] (feat on login) add e"
+    );
+
+    Ok(())
+}
+
+/// Tests commit numbers in a history rewritten by grafts.
+///
+/// Verifies that:
+/// - Grafts are honored, with a warning giving both counts
+/// - A graft that breaks the walk falls back to the stored history instead of `[1]`
+/// - `rona health` suggests converting the graft file
+#[test]
+fn test_commit_number_with_grafts() -> TestResult {
+    let repo = TestRepo::with_initial_commit()?;
+    for name in ["b.rs", "c.rs"] {
+        repo.write(name, "fn f() {}\n")?;
+        repo.stage(&[name])?;
+        repo.commit(name)?;
+    }
+    // Cuts the history below HEAD~1, like a migrated repository with a grafted root.
+    let root = repo.git(&["rev-parse", "HEAD~1"])?;
+    repo.write(".git/info/grafts", &format!("{root}\n"))?;
+
+    repo.write("d.rs", "fn d() {}\n")?;
+    repo.stage(&["d.rs"])?;
+    repo.rona()
+        .args(["commit", "-m", "add d", "--yes", "-u"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(
+            "info/grafts change the commit count: 2 with them, 3 in the stored history",
+        ));
+    assert_eq!(
+        repo.git(&["log", "-1", "--format=%s"])?,
+        "[3] (chore on main) add d"
+    );
+
+    repo.rona()
+        .arg("health")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "fix: git replace --convert-graft-file",
+        ));
+
+    // A parent that was never fetched breaks the walk.
+    let head = repo.git(&["rev-parse", "HEAD"])?;
+    repo.write(
+        ".git/info/grafts",
+        &format!("{head} 1234567890123456789012345678901234567890\n"),
+    )?;
+    repo.write("e.rs", "fn e() {}\n")?;
+    repo.stage(&["e.rs"])?;
+    repo.rona()
+        .args(["commit", "-m", "add e", "--yes", "-u"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(
+            "info/grafts break the history walk; commit numbers count the 4 stored commits",
+        ));
+    assert_eq!(
+        repo.git(&["--no-replace-objects", "log", "-1", "--format=%s"])?,
+        "[5] (chore on main) add e"
+    );
+
+    Ok(())
+}
+
+/// Tests the default branch `rona sync` syncs from.
+///
+/// Verifies that:
+/// - It is the branch `origin/HEAD` points to
+/// - Without `origin/HEAD`, it is `init.defaultBranch`
+/// - `--branch` overrides it
+#[test]
+fn test_sync_defaults_to_remote_head() -> TestResult {
+    let repo = TestRepo::with_initial_commit()?;
+    repo.add_bare_remote()?;
+    repo.git(&["push", "--quiet", "origin", "main", "main:trunk"])?;
+    repo.git(&["remote", "set-head", "origin", "trunk"])?;
+
+    repo.rona()
+        .args(["sync", "--dry-run"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Would switch to: trunk"));
+
+    repo.git(&["remote", "set-head", "origin", "--delete"])?;
+    repo.git(&["config", "init.defaultBranch", "develop"])?;
+    repo.rona()
+        .args(["sync", "--dry-run"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Would switch to: develop"));
+
+    repo.rona()
+        .args(["sync", "--dry-run", "-b", "main"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Would switch to: main"));
+
+    Ok(())
+}
+
+/// Tests the upstream check before committing.
+///
+/// Verifies that:
+/// - A warning is printed when the fetched upstream has commits `HEAD` does not
+/// - The commit still goes through with `--yes`
+/// - No warning is printed once the branch is up to date
+#[test]
+fn test_commit_warns_when_upstream_is_ahead() -> TestResult {
+    use mockall::PredicateBooleanExt;
+
+    let repo = TestRepo::with_initial_commit()?;
+    repo.add_bare_remote()?;
+    repo.write("lib.rs", "fn lib() {}\n")?;
+    repo.stage(&["lib.rs"])?;
+    repo.commit("add lib")?;
+    repo.git(&["push", "--quiet", "-u", "origin", "main"])?;
+    repo.git(&["reset", "--quiet", "--hard", "HEAD~1"])?;
+
+    repo.write("notes.md", "notes\n")?;
+    repo.stage(&["notes.md"])?;
+    repo.rona()
+        .args(["commit", "-m", "add notes", "--yes", "-u"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "origin/main is 1 commit ahead — consider pulling first",
+        ));
+    assert_eq!(
+        repo.git(&["log", "-1", "--format=%s"])?,
+        "[2] (chore on main) add notes"
+    );
+
+    repo.git(&["pull", "--quiet", "--no-rebase", "--no-edit"])?;
+    repo.git(&["push", "--quiet"])?;
+    repo.write("todo.md", "todo\n")?;
+    repo.stage(&["todo.md"])?;
+    repo.rona()
+        .args(["commit", "-m", "add todo", "--yes", "-u"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("ahead").not());
+
+    Ok(())
+}
+
+/// Tests `rona branch new`.
+///
+/// Verifies that:
+/// - `{type}`, `{ticket}` and `{slug}` are filled from the arguments
+/// - A name that does not match `branch_pattern` is refused
+/// - An unknown `--type` is refused
+#[test]
+fn test_branch_new_from_template() -> TestResult {
+    let repo = TestRepo::with_initial_commit()?;
+    repo.write(
+        ".rona.toml",
+        "branch_template = \"{type}/{ticket}-{slug}\"\n\
+         branch_pattern = \"^(feat|fix)/[A-Z]+-[0-9]+-[a-z0-9-]+$\"\n",
+    )?;
+
+    repo.rona()
+        .args([
+            "branch",
+            "new",
+            "--ticket",
+            "PROJ-1",
+            "--type",
+            "feat",
+            "Add login!",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("feat/PROJ-1-add-login"));
+    assert_eq!(
+        repo.git(&["branch", "--show-current"])?,
+        "feat/PROJ-1-add-login"
+    );
+
+    repo.rona()
+        .args(["branch", "new", "--type", "fix", "No ticket"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("does not match branch_pattern"));
+    repo.rona()
+        .args(["branch", "new", "-t", "PROJ-2", "--type", "nope", "x"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Unknown branch type 'nope'"));
+    assert_eq!(repo.git(&["branch", "--list", "fix/*"])?, "");
+
+    Ok(())
+}
+
+/// Tests `rona branch rename`.
+///
+/// Verifies that:
+/// - The local branch is renamed and keeps its description
+/// - The new branch is pushed and tracked, and the old remote branch deleted with `-y`
+/// - `--local` leaves the remote branch and tracking alone
+#[test]
+fn test_branch_rename_moves_remote_branch() -> TestResult {
+    let repo = TestRepo::with_initial_commit()?;
+    let remote = repo.add_bare_remote()?;
+    let remote_dir = format!("--git-dir={}", remote.display());
+    repo.git(&["switch", "--quiet", "-c", "feat/login"])?;
+    repo.git(&["push", "--quiet", "-u", "origin", "feat/login"])?;
+    repo.git(&["config", "branch.feat/login.description", "Login form"])?;
+
+    repo.rona()
+        .args(["branch", "rename", "feat/sign-in", "-y"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Deleted origin/feat/login"));
+    assert_eq!(repo.git(&["branch", "--show-current"])?, "feat/sign-in");
+    assert_eq!(
+        repo.git(&["config", "branch.feat/sign-in.description"])?,
+        "Login form"
+    );
+    assert_eq!(
+        repo.git(&["rev-parse", "--abbrev-ref", "feat/sign-in@{upstream}"])?,
+        "origin/feat/sign-in"
+    );
+    assert_eq!(
+        repo.git(&[&remote_dir, "branch", "--list", "--format=%(refname:short)"])?,
+        "feat/sign-in"
+    );
+
+    repo.rona()
+        .args(["branch", "rename", "feat/auth", "--local"])
+        .assert()
+        .success();
+    assert_eq!(
+        repo.git(&["rev-parse", "--abbrev-ref", "feat/auth@{upstream}"])?,
+        "origin/feat/sign-in"
+    );
+
+    Ok(())
+}
+
+/// Tests `rona branch describe` and `rona branch list`.
+///
+/// Verifies that:
+/// - The description is stored in `branch.<name>.description`
+/// - `rona branch list` shows it next to the branch
+/// - Commit templates can use `{branch_description}`
+/// - `--clear` removes it
+#[test]
+fn test_branch_describe_and_list() -> TestResult {
+    let repo = TestRepo::with_initial_commit()?;
+    repo.git(&["switch", "--quiet", "-c", "feat/login"])?;
+
+    repo.rona()
+        .args(["branch", "describe", "Login form for the mobile app"])
+        .assert()
+        .success();
+    assert_eq!(
+        repo.git(&["config", "branch.feat/login.description"])?,
+        "Login form for the mobile app"
+    );
+
+    repo.rona()
+        .args(["branch", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "feat/login  Login form for the mobile app",
+        ))
+        .stdout(predicate::str::contains("main"));
+
+    repo.write(
+        ".rona.toml",
+        "commit_template = \"{message}{?branch_description} ({branch_description}){/branch_description}\"\n",
+    )?;
+    repo.write("login.rs", "fn login() {}\n")?;
+    repo.stage(&["login.rs"])?;
+    repo.rona()
+        .args(["commit", "-m", "add form", "--yes", "-u"])
+        .assert()
+        .success();
+    assert_eq!(
+        repo.git(&["log", "-1", "--format=%s"])?,
+        "add form (Login form for the mobile app)"
+    );
+
+    repo.rona()
+        .args(["branch", "describe", "--clear"])
+        .assert()
+        .success();
+    assert!(
+        repo.git(&["config", "branch.feat/login.description"])
+            .is_err()
+    );
+
+    Ok(())
+}
+
+/// Tests `rona branch switch`, `merge`, `rebase` and `pull` with explicit branches.
+///
+/// Verifies that:
+/// - `switch --dry-run` leaves the current branch alone, `switch` checks out the branch
+/// - `merge` brings the commits of the branch into the current one
+/// - `rebase` replays the current branch on top of the branch
+/// - `pull` fetches the upstream commits of the current branch
+#[test]
+fn test_branch_switch_merge_rebase_pull() -> TestResult {
+    let repo = TestRepo::with_initial_commit()?;
+    repo.add_bare_remote()?;
+    repo.git(&["push", "--quiet", "-u", "origin", "main"])?;
+    repo.git(&["switch", "--quiet", "-c", "feat/login"])?;
+    repo.write("login.rs", "fn login() {}\n")?;
+    repo.stage(&["login.rs"])?;
+    repo.commit("add login")?;
+
+    repo.rona()
+        .args(["branch", "switch", "main", "--dry-run"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Would switch to: main"));
+    assert_eq!(repo.git(&["branch", "--show-current"])?, "feat/login");
+
+    repo.rona()
+        .args(["branch", "switch", "main"])
+        .assert()
+        .success();
+    assert_eq!(repo.git(&["branch", "--show-current"])?, "main");
+
+    repo.rona()
+        .args(["branch", "merge", "feat/login"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Merged feat/login into main"));
+    assert!(repo.path().join("login.rs").exists());
+
+    repo.git(&["push", "--quiet", "origin", "main"])?;
+    repo.git(&["reset", "--quiet", "--hard", "HEAD~1"])?;
+    repo.rona().args(["branch", "pull"]).assert().success();
+    assert!(repo.path().join("login.rs").exists());
+
+    repo.git(&["switch", "--quiet", "-c", "fix/typo", "HEAD~1"])?;
+    repo.write("typo.txt", "fixed\n")?;
+    repo.stage(&["typo.txt"])?;
+    repo.commit("fix typo")?;
+    repo.rona()
+        .args(["branch", "rebase", "main"])
+        .assert()
+        .success();
+    assert_eq!(
+        repo.git(&["rev-parse", "HEAD~1"])?,
+        repo.git(&["rev-parse", "main"])?
+    );
+
+    Ok(())
+}
+
+/// Tests `rona todo` and the `[todo]` commit policy.
+///
+/// Verifies that:
+/// - Only markers on added lines are listed, with their file and line
+/// - `--check` fails when markers are found
+/// - `on_commit = "block"` refuses the commit unless `--no-verify` is given
+/// - `on_commit = "append"` adds a `TODOs:` section to the commit body
+#[test]
+fn test_todo_lists_and_checks_staged_markers() -> TestResult {
+    let repo = TestRepo::with_initial_commit()?;
+    repo.write("lib.rs", "fn old() {} // TODO already committed\n")?;
+    repo.stage(&["lib.rs"])?;
+    repo.commit("add lib")?;
+
+    repo.rona()
+        .args(["todo", "--check"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No new TODO markers"));
+
+    repo.write(
+        "lib.rs",
+        "fn old() {} // TODO already committed\nfn new() {} // FIXME: handle errors\n",
+    )?;
+    repo.stage(&["lib.rs"])?;
+    repo.rona()
+        .arg("todo")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("1 new TODO marker(s)"))
+        .stdout(predicate::str::contains(
+            "lib.rs:2: fn new() {} // FIXME: handle errors",
+        ));
+    repo.rona().args(["todo", "--check"]).assert().failure();
+
+    repo.write(".rona.toml", "[todo]\non_commit = \"block\"\n")?;
+    repo.rona()
+        .args(["commit", "-m", "blocked", "--yes", "-u"])
+        .assert()
+        .failure();
+    assert_eq!(repo.git(&["log", "-1", "--format=%s"])?, "add lib");
+
+    repo.write(".rona.toml", "[todo]\non_commit = \"append\"\n")?;
+    repo.rona()
+        .args(["commit", "-m", "with todos", "--yes", "-u"])
+        .assert()
+        .success();
+    assert_eq!(
+        repo.git(&["log", "-1", "--format=%b"])?,
+        "TODOs:\n- lib.rs:2: fn new() {} // FIXME: handle errors"
+    );
+
+    Ok(())
+}
+
+/// Tests package `.rona.toml` files in a monorepo.
+///
+/// Verifies that:
+/// - The package config of the staged files overrides the root config
+/// - Without staged files under the package, the root config applies
+#[test]
+fn test_commit_uses_package_config() -> TestResult {
+    let repo = TestRepo::with_initial_commit()?;
+    repo.write(".rona.toml", "commit_types = [\"feat\"]\n")?;
+    repo.write(
+        "packages/api/.rona.toml",
+        "commit_types = [\"api\"]\ncommit_template = \"api({commit_type}): {message}\"\n",
+    )?;
+    repo.write("packages/api/src/main.rs", "fn main() {}\n")?;
+    repo.stage(&["packages/api/src/main.rs"])?;
+
+    repo.rona()
+        .args([
+            "commit",
+            "-m",
+            "add entry point",
+            "-t",
+            "api",
+            "--yes",
+            "-u",
+        ])
+        .assert()
+        .success();
+    assert_eq!(
+        repo.git(&["log", "-1", "--format=%s"])?,
+        "api(api): add entry point"
+    );
+
+    repo.write("README.md", "# demo\n")?;
+    repo.stage(&["README.md"])?;
+    repo.rona()
+        .args(["commit", "-m", "x", "-t", "api", "--yes", "-u"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Unknown commit type 'api'"));
+
+    Ok(())
+}
+
+/// Tests `rona commit -m`.
+///
+/// Verifies that:
+/// - The inline message is run through the template without `commit_message.md`
+/// - The commit type is inferred from the branch prefix, or taken from `--type`
+/// - Amending keeps the commit number of the amended commit
+/// - An unknown `--type` is rejected
+#[test]
+fn test_commit_inline_message() -> TestResult {
+    let repo = TestRepo::with_initial_commit()?;
+    repo.git(&["switch", "--quiet", "-c", "fix/login"])?;
+    repo.write("login.rs", "fn login() {}")?;
+    repo.stage(&["login.rs"])?;
+
+    repo.rona()
+        .args(["commit", "-m", "handle empty password", "--yes", "-u"])
+        .assert()
+        .success();
+    assert_eq!(
+        repo.git(&["log", "-1", "--format=%s"])?,
+        "[2] (fix on login) handle empty password"
+    );
+    assert!(!repo.join("commit_message.md").exists());
+
+    repo.rona()
+        .args([
+            "commit",
+            "-m",
+            "add login",
+            "-t",
+            "feat",
+            "--yes",
+            "-u",
+            "--amend",
+        ])
+        .assert()
+        .success();
+    assert_eq!(
+        repo.git(&["log", "-1", "--format=%s"])?,
+        "[2] (feat on login) add login"
+    );
+
+    repo.rona()
+        .args(["commit", "-m", "x", "-t", "nope", "--yes"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Unknown commit type 'nope'"));
+
+    Ok(())
+}
+
+/// Tests the archive of edited messages.
+///
+/// Verifies that:
+/// - `rona -v commit` shows the edits made to the generated `commit_message.md`
+/// - The generated and committed versions are appended to `.git/rona/messages.jsonl`
+/// - A message committed as generated is not archived
+#[test]
+fn test_commit_archives_message_edits() -> TestResult {
+    let repo = TestRepo::with_initial_commit()?;
+    let archive = repo.join(".git/rona/messages.jsonl");
+
+    repo.write("a.rs", "fn a() {}")?;
+    repo.stage(&["a.rs"])?;
+    repo.rona()
+        .args(["generate", "--stdin", "--no-commit-number"])
+        .write_stdin("add a\n")
+        .assert()
+        .success();
+    let generated = repo.read("commit_message.md")?;
+    repo.write(
+        "commit_message.md",
+        &generated.replace("add a", "add a()\n\nNeeded by b."),
+    )?;
+    repo.rona()
+        .args(["-v", "commit", "--yes", "-u"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("- (chore on main) add a\n"))
+        .stdout(predicate::str::contains("+ (chore on main) add a()"))
+        .stdout(predicate::str::contains("+ Needed by b."));
+    let entries = std::fs::read_to_string(&archive)?;
+    assert_eq!(entries.lines().count(), 1);
+    assert!(
+        entries.contains(r#""generated":"(chore on main) add a""#),
+        "{entries}"
+    );
+
+    repo.write("b.rs", "fn b() {}")?;
+    repo.stage(&["b.rs"])?;
+    repo.rona()
+        .args(["generate", "--stdin", "--no-commit-number"])
+        .write_stdin("add b\n")
+        .assert()
+        .success();
+    repo.rona()
+        .args(["commit", "--yes", "-u"])
+        .assert()
+        .success();
+    assert_eq!(std::fs::read_to_string(&archive)?.lines().count(), 1);
+
+    Ok(())
+}
+
+/// Tests the `{scope}` commit template variable.
+///
+/// Verifies that:
+/// - The scope is inferred from the last common directory of the staged files
+/// - The conditional block is dropped when a staged file sits at the root
+#[test]
+fn test_generate_infers_scope() -> TestResult {
+    let repo = TestRepo::with_initial_commit()?;
+    repo.write(
+        ".rona.toml",
+        "commit_template = \"{commit_type}{?scope}({scope}){/scope}: {message}\"\n",
+    )?;
+    repo.write("src/git/commit.rs", "")?;
+    repo.write("src/git/status.rs", "")?;
+    repo.stage(&["src/git/commit.rs", "src/git/status.rs"])?;
+    repo.rona()
+        .args(["generate", "--stdin"])
+        .write_stdin("share status parsing\n")
+        .assert()
+        .success();
+    assert_eq!(
+        repo.read("commit_message.md")?,
+        "chore(git): share status parsing"
+    );
+
+    repo.write("README.md", "docs")?;
+    repo.stage(&["README.md"])?;
+    repo.rona()
+        .args(["generate", "--stdin"])
+        .write_stdin("share status parsing\n")
+        .assert()
+        .success();
+    assert_eq!(
+        repo.read("commit_message.md")?,
+        "chore: share status parsing"
+    );
+
+    Ok(())
+}
+
+/// Tests the global `--scope` flag in a monorepo.
+///
+/// Verifies that:
+/// - `rona -l` and `rona -a` only consider the files under the scope directory
+/// - A `[scopes]` name resolves to its directory and becomes `{scope}`
+/// - A path scope is named after its last directory
+/// - An unknown scope is rejected with the configured names
+#[test]
+fn test_scope_narrows_status_and_staging() -> TestResult {
+    let repo = TestRepo::with_initial_commit()?;
+    repo.write(
+        ".rona.toml",
+        "commit_template = \"{commit_type}{?scope}({scope}){/scope}: {message}\"\n\n[scopes]\napi = \"packages/api\"\n",
+    )?;
+    repo.stage(&[".rona.toml"])?;
+    repo.commit("add config")?;
+    repo.write("packages/api/src/main.rs", "fn main() {}\n")?;
+    repo.write("packages/web/index.ts", "\n")?;
+
+    repo.rona()
+        .args(["--scope", "api", "-l"])
+        .assert()
+        .success()
+        .stdout("packages/api/\n");
+    repo.rona()
+        .args(["--scope", "api", "-a"])
+        .assert()
+        .success();
+    let staged = repo.git(&["diff", "--cached", "--name-only"])?;
+    assert_eq!(staged.trim(), "packages/api/src/main.rs");
+
+    repo.rona()
+        .args(["--scope", "api", "generate", "--stdin"])
+        .write_stdin("add entry point\n")
+        .assert()
+        .success();
+    assert_eq!(
+        repo.read("commit_message.md")?,
+        "chore(api): add entry point"
+    );
+
+    repo.stage(&["packages/web/index.ts"])?;
+    repo.rona()
+        .args(["--scope", "packages/web", "generate", "--stdin"])
+        .write_stdin("add page\n")
+        .assert()
+        .success();
+    assert_eq!(repo.read("commit_message.md")?, "chore(web): add page");
+
+    repo.rona()
+        .args(["--scope", "mobile", "-l"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "'mobile' is neither a scope name nor a directory; known scopes: api",
+        ));
+    Ok(())
+}
+
+/// Tests the `{ticket}` commit template variable.
+///
+/// Verifies that:
+/// - `ticket_pattern` finds the ticket in the current branch name
+/// - The conditional block is dropped on a branch without a ticket
+/// - An invalid regex fails with the key name
+#[test]
+fn test_generate_extracts_ticket() -> TestResult {
+    let repo = TestRepo::with_initial_commit()?;
+    repo.write(
+        ".rona.toml",
+        "ticket_pattern = \"[A-Z]+-\\\\d+\"\n\
+         commit_template = \"{?ticket}[{ticket}] {/ticket}{commit_type}: {message}\"\n",
+    )?;
+    repo.git(&["checkout", "--quiet", "-b", "feat/PROJ-123-add-login"])?;
+    repo.write("a.rs", "fn a() {}")?;
+    repo.stage(&["a.rs"])?;
+    repo.rona()
+        .args(["generate", "--stdin"])
+        .write_stdin("add login\n")
+        .assert()
+        .success();
+    assert_eq!(
+        repo.read("commit_message.md")?,
+        "[PROJ-123] feat: add login"
+    );
+
+    repo.git(&["checkout", "--quiet", "-b", "feat/add-logout"])?;
+    repo.rona()
+        .args(["generate", "--stdin"])
+        .write_stdin("add logout\n")
+        .assert()
+        .success();
+    assert_eq!(repo.read("commit_message.md")?, "feat: add logout");
+
+    repo.write(".rona.toml", "ticket_pattern = \"[A-Z\"\n")?;
+    repo.rona()
+        .args(["generate", "--stdin"])
+        .write_stdin("add logout\n")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("ticket_pattern: invalid regex"));
+
+    Ok(())
+}
+
+/// Tests the `[impact]` labels of `rona generate`.
+///
+/// Verifies that:
+/// - The labels of the globs matching the staged files are printed
+/// - `{impact}` lists them sorted and without duplicates
+/// - The conditional block is dropped when no glob matches
+#[test]
+fn test_generate_impact_labels() -> TestResult {
+    use mockall::PredicateBooleanExt;
+
+    let repo = TestRepo::with_initial_commit()?;
+    repo.write(
+        ".rona.toml",
+        "commit_template = \"{message}{?impact} [{impact}]{/impact}\"\n\n\
+         [impact]\n\
+         \"src/git/**\" = \"core\"\n\
+         \"docs/**\" = \"docs-only\"\n\
+         \"**/*.md\" = \"docs-only\"\n",
+    )?;
+    repo.write("src/git/commit.rs", "")?;
+    repo.write("docs/guide.md", "")?;
+    repo.stage(&["src/git/commit.rs", "docs/guide.md"])?;
+    repo.rona()
+        .args(["generate", "--stdin"])
+        .write_stdin("share parsing\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Impact: core, docs-only"));
+    assert_eq!(
+        repo.read("commit_message.md")?,
+        "share parsing [core, docs-only]"
+    );
+
+    repo.git(&["reset", "--quiet"])?;
+    repo.write("main.rs", "")?;
+    repo.stage(&["main.rs"])?;
+    repo.rona()
+        .args(["generate", "--stdin"])
+        .write_stdin("add main\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Impact:").not());
+    assert_eq!(repo.read("commit_message.md")?, "add main");
+
+    Ok(())
+}
+
+/// Tests `check_before_generate = true`.
+///
+/// Verifies that:
+/// - `rona generate` refuses to run with nothing staged
+/// - A message already committed is overwritten without asking
+/// - A message not committed yet is kept when the choice cannot be asked
+#[test]
+fn test_generate_checks_freshness() -> TestResult {
+    let repo = TestRepo::with_initial_commit()?;
+    repo.write(".rona.toml", "check_before_generate = true\n")?;
+    let generate = |message: &str| {
+        let mut cmd = repo.rona();
+        cmd.args(["generate", "--stdin", "--no-commit-number"])
+            .write_stdin(message.to_string());
+        cmd
+    };
+
+    generate("add a\n")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Nothing is staged"));
+
+    repo.write("a.rs", "fn a() {}")?;
+    repo.stage(&["a.rs"])?;
+    generate("add a\n").assert().success();
+    repo.rona()
+        .args(["commit", "--yes", "-u"])
+        .assert()
+        .success();
+
+    repo.write("b.rs", "fn b() {}")?;
+    repo.stage(&["b.rs"])?;
+    generate("add b\n").assert().success();
+    let pending = repo.read("commit_message.md")?;
+    assert!(pending.contains("add b"), "{pending}");
+
+    generate("add c\n")
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("not committed yet"));
+    assert_eq!(repo.read("commit_message.md")?, pending);
+
+    Ok(())
+}
+
+/// Tests `change_id = true`.
+///
+/// Verifies that:
+/// - `rona commit` appends a Gerrit `Change-Id:` trailer
+/// - `--amend` keeps the Change-Id of the amended commit
+/// - A new commit gets a new Change-Id
+/// - A message that already has a Change-Id is left as is
+#[test]
+fn test_commit_change_id() -> TestResult {
+    let repo = TestRepo::with_initial_commit()?;
+    repo.write(".rona.toml", "change_id = true\n")?;
+    let change_id = || {
+        repo.git(&[
+            "log",
+            "-1",
+            "--format=%(trailers:key=Change-Id,valueonly,unfold)",
+        ])
+    };
+
+    repo.write("a.rs", "fn a() {}")?;
+    repo.stage(&["a.rs"])?;
+    repo.rona()
+        .args(["commit", "-m", "add a", "-t", "feat", "--yes", "-u"])
+        .assert()
+        .success();
+    let first = change_id()?;
+    assert!(first.starts_with('I') && first.len() == 41, "{first}");
+
+    repo.rona()
+        .args([
+            "commit", "-m", "add a()", "-t", "feat", "--yes", "-u", "--amend",
+        ])
+        .assert()
+        .success();
+    assert_eq!(change_id()?, first);
+
+    repo.write("b.rs", "fn b() {}")?;
+    repo.stage(&["b.rs"])?;
+    repo.rona()
+        .args(["commit", "-m", "add b", "-t", "feat", "--yes", "-u"])
+        .assert()
+        .success();
+    let second = change_id()?;
+    assert_ne!(second, first);
+
+    let kept = "Ifedcba9876543210fedcba9876543210fedcba98";
+    repo.write("c.rs", "fn c() {}")?;
+    repo.stage(&["c.rs"])?;
+    repo.write(
+        "commit_message.md",
+        &format!("add c\n\nChange-Id: {kept}\n"),
+    )?;
+    repo.rona()
+        .args(["commit", "--yes", "-u"])
+        .assert()
+        .success();
+    assert_eq!(change_id()?, kept);
+
+    Ok(())
+}
+
+/// Tests `require_signed = true`.
+///
+/// Verifies that:
+/// - `rona commit` fails instead of warning when no signing key is configured
+/// - `--unsigned` is refused
+/// - `rona push` refuses to push unsigned commits and names them
+/// - The commits of the refspecs pushed are checked, not only those of `HEAD`
+#[test]
+fn test_require_signed() -> TestResult {
+    let repo = TestRepo::with_initial_commit()?;
+    let remote = repo.add_bare_remote()?;
+    let remote_dir = format!("--git-dir={}", remote.display());
+    repo.write(".rona.toml", "require_signed = true\n")?;
+    repo.write("a.rs", "fn a() {}")?;
+    repo.stage(&["a.rs"])?;
+
+    repo.rona()
+        .args(["commit", "-m", "add a", "-t", "feat", "--yes"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Signed commits are required (require_signed): no signing key is configured",
+        ));
+    repo.rona()
+        .args(["commit", "-m", "add a", "-t", "feat", "--yes", "-u"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--unsigned is not allowed"));
+    assert!(repo.git(&["diff", "--cached", "--quiet"]).is_err());
+
+    repo.commit("add a")?;
+    repo.rona()
+        .args(["push", "origin", "main"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "2 outgoing commit(s) are not signed",
+        ))
+        .stderr(predicate::str::contains("add a"));
+    assert!(
+        repo.git(&[&remote_dir, "rev-parse", "--verify", "main"])
+            .is_err()
+    );
+
+    repo.git(&["push", "origin", "main"])?;
+    repo.branch("side")?;
+    repo.write("b.rs", "fn b() {}")?;
+    repo.stage(&["b.rs"])?;
+    repo.commit("add b")?;
+    repo.switch("main")?;
+    repo.rona()
+        .args(["push", "origin", "side"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "1 outgoing commit(s) are not signed",
+        ))
+        .stderr(predicate::str::contains(
+            "add b (no signature git can verify)",
+        ));
+    assert!(
+        repo.git(&[&remote_dir, "rev-parse", "--verify", "side"])
+            .is_err()
+    );
+
+    Ok(())
+}
+
+/// Tests the `[pre_push]` commands.
+///
+/// Verifies that:
+/// - The commands run in order before pushing
+/// - A failing command aborts the push
+/// - `--no-verify` skips the commands
+#[test]
+fn test_push_runs_pre_push_commands() -> TestResult {
+    let repo = TestRepo::with_initial_commit()?;
+    let remote = repo.add_bare_remote()?;
+    let remote_dir = format!("--git-dir={}", remote.display());
+    repo.write(
+        ".rona.toml",
+        "[pre_push]\ncommands = [\"touch checked\", \"exit 3\"]\n",
+    )?;
+
+    repo.rona()
+        .args(["push", "origin", "main"])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("Push aborted"))
+        .stderr(predicate::str::contains("exit 3"));
+    assert!(repo.join("checked").exists());
+    assert!(
+        repo.git(&[&remote_dir, "rev-parse", "--verify", "main"])
+            .is_err()
+    );
+
+    repo.rona()
+        .args(["push", "--no-verify", "--quiet", "origin", "main"])
+        .assert()
+        .success();
+    assert_eq!(
+        repo.git(&[&remote_dir, "rev-parse", "main"])?,
+        repo.git(&["rev-parse", "main"])?
+    );
+
+    Ok(())
+}
+
+/// Tests the `[pre_push]` limits.
+///
+/// Verifies that:
+/// - A command running past `timeout` is killed and aborts the push
+/// - With `output_lines`, only the last lines of a failing command are shown
+/// - With `pass_env`, other environment variables are cleared
+#[test]
+fn test_pre_push_limits() -> TestResult {
+    let repo = TestRepo::with_initial_commit()?;
+    let remote = repo.add_bare_remote()?;
+    let remote_dir = format!("--git-dir={}", remote.display());
+    repo.write(
+        ".rona.toml",
+        "[pre_push]\ncommands = [\"sleep 30\"]\ntimeout = \"1s\"\n",
+    )?;
+    repo.rona()
+        .args(["push", "origin", "main"])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("Push aborted"))
+        .stderr(predicate::str::contains("sleep 30 (timed out after 1s)"));
+    assert!(
+        repo.git(&[&remote_dir, "rev-parse", "--verify", "main"])
+            .is_err()
+    );
+
+    repo.write(
+        ".rona.toml",
+        "[pre_push]\ncommands = [\"echo one; echo two; echo \\\"home=$HOME\\\" >&2; exit 1\"]\npass_env = [\"PATH\"]\noutput_lines = 2\n",
+    )?;
+    let output = repo.rona().args(["push", "origin", "main"]).output()?;
+    let stdout = String::from_utf8(output.stdout)?;
+    assert!(!output.status.success());
+    assert!(stdout.contains("  two\n  home=\n"));
+    assert!(!stdout.contains("  one\n"));
+
+    Ok(())
+}
+
+/// Tests the `[tracker]` transitions run after a push.
+///
+/// Verifies that:
+/// - `push --dry-run --output json` lists the commands of the pushed commit types
+/// - After the push, the commands run with the ticket of the branch and the subjects
+///   of their commit type, and commands of types that were not pushed do not run
+/// - A failing command only warns
+#[test]
+fn test_push_runs_tracker_transitions() -> TestResult {
+    let repo = TestRepo::with_initial_commit()?;
+    repo.add_bare_remote()?;
+    repo.write(
+        ".rona.toml",
+        r#"ticket_pattern = '[A-Z]+-\d+'
+
+[tracker.transitions]
+fix = ["printf '%s|%s|%s\n' \"$RONA_TICKET\" \"$RONA_COMMIT_TYPE\" \"$RONA_SUBJECTS\" >> tracker.log", "exit 4"]
+feat = ["echo feat >> tracker.log"]
+"#,
+    )?;
+    repo.git(&["add", ".rona.toml"])?;
+    repo.commit("chore: config")?;
+    repo.branch("PROJ-7-login")?;
+    repo.write("login.rs", "fn login() {}\n")?;
+    repo.stage(&["login.rs"])?;
+    repo.commit("[1] (fix on PROJ-7-login) Fix login")?;
+
+    let output = repo
+        .rona()
+        .args([
+            "--output",
+            "json",
+            "push",
+            "--dry-run",
+            "origin",
+            "PROJ-7-login",
+        ])
+        .output()?;
+    assert!(output.status.success());
+    let preview: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(preview["tracker"][0]["ticket"], "PROJ-7");
+    assert_eq!(preview["tracker"][0]["commit_type"], "fix");
+    assert_eq!(preview["tracker"].as_array().map(Vec::len), Some(2));
+
+    repo.rona()
+        .args(["push", "--quiet", "origin", "PROJ-7-login"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Tracker command failed: exit 4"));
+    assert_eq!(
+        repo.read("tracker.log")?,
+        "PROJ-7|fix|[1] (fix on PROJ-7-login) Fix login\n"
+    );
+
+    Ok(())
+}
+
+/// Tests that an invalid `[tracker]` setup does not stop a push.
+///
+/// Verifies that:
+/// - The dry-run JSON preview reports the error as a warning
+/// - The push goes through and the error is printed as a warning after it
+#[test]
+fn test_push_tracker_errors_only_warn() -> TestResult {
+    let repo = TestRepo::with_initial_commit()?;
+    repo.add_bare_remote()?;
+    repo.write(
+        ".rona.toml",
+        r#"ticket_pattern = '(['
+
+[tracker.transitions]
+fix = ["echo fix >> tracker.log"]
+"#,
+    )?;
+    repo.git(&["add", ".rona.toml"])?;
+    repo.commit("[1] (fix on main) Fix login")?;
+
+    let output = repo
+        .rona()
+        .args(["--output", "json", "push", "--dry-run", "origin", "main"])
+        .output()?;
+    assert!(output.status.success());
+    let preview: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(preview["tracker"].as_array().map(Vec::len), Some(0));
+    assert!(
+        preview["warnings"][0]
+            .as_str()
+            .is_some_and(|warning| warning.starts_with("Tracker commands:"))
+    );
+
+    repo.rona()
+        .args(["push", "--quiet", "origin", "main"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Tracker commands failed:"));
+    repo.git(&["rev-parse", "--verify", "origin/main"])?;
+    assert!(!repo.join("tracker.log").exists());
+
+    Ok(())
+}
+
+/// Tests the `[notify.webhook]` push notification.
+///
+/// Verifies that:
+/// - A successful push posts the pushed commit subjects to the webhook
+/// - The payload uses the shape of the configured kind
+/// - A failing webhook only warns, without printing its URL
+#[test]
+fn test_push_posts_webhook_notification() -> TestResult {
+    use mockall::PredicateBooleanExt;
+    use std::io::{BufRead, BufReader, Read, Write};
+
+    let repo = TestRepo::with_initial_commit()?;
+    repo.add_bare_remote()?;
+    repo.git(&["push", "--quiet", "-u", "origin", "main"])?;
+    repo.write("login.rs", "fn login() {}")?;
+    repo.stage(&["login.rs"])?;
+    repo.commit("add login form")?;
+
+    let listener = std::net::TcpListener::bind("127.0.0.1:0")?;
+    let port = listener.local_addr()?.port();
+    let server = std::thread::spawn(move || -> std::io::Result<String> {
+        let (stream, _) = listener.accept()?;
+        let mut reader = BufReader::new(stream);
+        let mut length = 0;
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line)?;
+            if let Some(value) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                length = value.trim().parse().unwrap_or(0);
+            }
+            if line.trim().is_empty() {
+                break;
+            }
+        }
+        let mut body = vec![0; length];
+        reader.read_exact(&mut body)?;
+        reader
+            .get_mut()
+            .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 0\r\n\r\n")?;
+        Ok(String::from_utf8_lossy(&body).into_owned())
+    });
+
+    repo.write(
+        ".rona.toml",
+        &format!(
+            "[notify.webhook]\nurl = \"http://127.0.0.1:{port}/hook\"\nkind = \"discord\"\ntemplate = \"{{count}} to {{branch}}: {{subjects}}\"\n"
+        ),
+    )?;
+    repo.rona()
+        .args(["push", "--quiet"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Notified 127.0.0.1"));
+    let body = server.join().map_err(|_| "webhook server panicked")??;
+    assert_eq!(body, r#"{"content":"1 to main: - add login form"}"#);
+
+    // Nothing listens on the port anymore
+    repo.write("logout.rs", "fn logout() {}")?;
+    repo.stage(&["logout.rs"])?;
+    repo.commit("add logout")?;
+    repo.rona()
+        .args(["push", "--quiet"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Push notification failed"))
+        .stdout(predicate::str::contains("/hook").not());
+
+    Ok(())
+}
+
+/// Tests `rona push --review`.
+///
+/// Verifies that:
+/// - Without `[review]`, HEAD is pushed to Gerrit's `refs/for/<target>`
+/// - `[review] ref` renders `{user}` and `{branch}` into the pushed ref
+/// - The branch itself is not pushed
+/// - A refspec on the command line is rejected
+#[test]
+fn test_push_review_ref() -> TestResult {
+    let repo = TestRepo::with_initial_commit()?;
+    let remote = repo.add_bare_remote()?;
+    let remote_dir = format!("--git-dir={}", remote.display());
+    repo.branch("feat/login")?;
+    repo.write("login.rs", "fn login() {}")?;
+    repo.stage(&["login.rs"])?;
+    repo.commit("add login")?;
+    let head = repo.git(&["rev-parse", "HEAD"])?;
+
+    repo.rona()
+        .args(["push", "--review", "--target", "main", "--quiet"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Pushed HEAD to refs/for/main"));
+    assert_eq!(
+        repo.git(&[&remote_dir, "rev-parse", "refs/for/main"])?,
+        head
+    );
+
+    repo.write(
+        ".rona.toml",
+        "[review]\nref = \"refs/reviews/{user}/{branch}\"\n",
+    )?;
+    repo.rona()
+        .args(["push", "--review", "--quiet", "origin"])
+        .assert()
+        .success();
+    assert_eq!(
+        repo.git(&[&remote_dir, "rev-parse", "refs/reviews/test/feat/login"])?,
+        head
+    );
+    assert!(
+        repo.git(&[
+            &remote_dir,
+            "rev-parse",
+            "--verify",
+            "refs/heads/feat/login"
+        ])
+        .is_err()
+    );
+
+    repo.rona()
+        .args(["push", "--review", "origin", "feat/login"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("name only the remote"));
+
+    Ok(())
+}
+
+/// Tests `check_before_push`.
+///
+/// Verifies that:
+/// - The pushed branch is fetched, so commits pushed from elsewhere are listed
+/// - A normal push warns about them and leaves the rejection to git
+/// - A force push without a terminal to confirm in is cancelled, leaving the remote
+///   branch as it was
+#[test]
+fn test_push_checks_for_divergence() -> TestResult {
+    let repo = TestRepo::with_initial_commit()?;
+    let remote = repo.add_bare_remote()?;
+    let remote_dir = format!("--git-dir={}", remote.display());
+    repo.write(".rona.toml", "check_before_push = true\n")?;
+
+    // A commit pushed from another clone, not fetched here yet
+    repo.write("login.rs", "fn login() {}")?;
+    repo.stage(&["login.rs"])?;
+    repo.commit("add login from the laptop")?;
+    repo.git(&["push", "--quiet", "-u", "origin", "main"])?;
+    repo.git(&["update-ref", "refs/remotes/origin/main", "HEAD~1"])?;
+    repo.git(&["reset", "--quiet", "--hard", "HEAD~1"])?;
+    let remote_head = repo.git(&[&remote_dir, "rev-parse", "main"])?;
+
+    repo.write("logout.rs", "fn logout() {}")?;
+    repo.stage(&["logout.rs"])?;
+    repo.commit("add logout")?;
+
+    repo.rona()
+        .args(["push", "--quiet", "origin", "main"])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains(
+            "origin/main has diverged: 1 commit(s) there and 1 here",
+        ))
+        .stdout(predicate::str::contains("add login from the laptop"))
+        .stdout(predicate::str::contains("rona branch pull"));
+
+    repo.rona()
+        .args(["push", "--force", "origin", "main"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Prefer --force-with-lease"))
+        .stdout(predicate::str::contains("Push cancelled."));
+    assert_eq!(repo.git(&[&remote_dir, "rev-parse", "main"])?, remote_head);
+
+    Ok(())
+}
+
+/// Tests `[url_rewrites]` and `expected_remotes`.
+///
+/// Verifies that:
+/// - Pushing to a remote outside `expected_remotes` warns, then pushes anyway
+/// - The check uses the remote URL after `[url_rewrites]` is applied
+/// - A remote covered by a `host/owner` prefix pushes without a warning
+#[test]
+fn test_push_warns_about_unexpected_remote() -> TestResult {
+    use mockall::PredicateBooleanExt;
+
+    let repo = TestRepo::with_initial_commit()?;
+    repo.add_bare_remote()?;
+    let url = repo.git(&["remote", "get-url", "origin"])?;
+    let config = |target: &str| {
+        format!(
+            "expected_remotes = [\"github.com/acme\"]\n\n[url_rewrites]\n{url:?} = \"git@github.com:{target}\"\n"
+        )
+    };
+
+    repo.write(".rona.toml", &config("me/app.git"))?;
+    repo.rona()
+        .args(["push", "--quiet", "origin", "main"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "origin points to github.com/me/app, not one of the expected remotes (github.com/acme)",
+        ));
+
+    repo.write(".rona.toml", &config("acme/app.git"))?;
+    repo.rona()
+        .args(["push", "--quiet", "origin", "main"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("WARNING").not());
+
+    Ok(())
+}
+
+/// Tests the operation log and `rona history ops`.
+///
+/// Verifies that:
+/// - State-changing commands are recorded with their arguments and `HEAD` movement
+/// - Read-only commands and dry runs are not recorded
+/// - `--json` prints one JSON object per operation, newest first
+#[test]
+fn test_history_ops_records_operations() -> TestResult {
+    let repo = TestRepo::with_initial_commit()?;
+    let head_before = repo.git(&["rev-parse", "HEAD"])?;
+    repo.write("login.rs", "fn login() {}")?;
+
+    repo.rona().arg("-a").assert().success();
+    repo.rona().args(["reset", "--dry-run"]).assert().success();
+    repo.rona()
+        .args(["commit", "-m", "add login", "-y", "-u"])
+        .assert()
+        .success();
+    repo.rona().arg("show").assert().success();
+    let head_after = repo.git(&["rev-parse", "HEAD"])?;
+
+    let log = repo.read(".git/rona/oplog.jsonl")?;
+    assert_eq!(log.lines().count(), 2, "unexpected log: {log}");
+
+    repo.rona()
+        .args(["history", "ops"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("commit"))
+        .stdout(predicate::str::contains(format!(
+            "{} -> {}",
+            &head_before[..7],
+            &head_after[..7]
+        )))
+        .stdout(predicate::str::contains(r#"-m "add login""#));
+
+    let output = repo.rona().args(["history", "ops", "--json"]).output()?;
+    let stdout = String::from_utf8(output.stdout)?;
+    let commands: Vec<serde_json::Value> = stdout
+        .lines()
+        .map(serde_json::from_str)
+        .collect::<Result<_, _>>()?;
+    assert_eq!(commands.len(), 2);
+    assert_eq!(commands[0]["command"], "commit");
+    assert_eq!(commands[0]["head_after"], head_after.as_str());
+    assert_eq!(commands[1]["command"], "add");
+
+    Ok(())
+}
+
+/// Tests per-language commit templates.
+///
+/// Verifies that:
+/// - `--lang` selects the commit template and `{date}` format of `[templates.locales.<lang>]`
+/// - Month names in `{date}` are written in the selected language
+/// - `lang` in the config selects a language without the flag
+/// - An unknown language fails with the configured languages listed
+/// - `rona template check` reports an invalid per-language date format
+#[test]
+fn test_locale_commit_templates() -> TestResult {
+    let repo = TestRepo::with_initial_commit()?;
+    repo.branch("feat/notes")?;
+    repo.write(
+        ".rona.toml",
+        r#"commit_template = "{commit_type}: {message}"
+
+[templates.locales.fr]
+commit_template = "{commit_type} : {message} (le {date})"
+date_format = "%d %B %Y"
+"#,
+    )?;
+    repo.git(&["add", ".rona.toml"])?;
+    repo.commit("add config")?;
+
+    repo.write("notes.txt", "un")?;
+    repo.stage(&["notes.txt"])?;
+    repo.rona()
+        .args([
+            "--lang",
+            "fr",
+            "commit",
+            "-m",
+            "ajoute les notes",
+            "--yes",
+            "-u",
+        ])
+        .assert()
+        .success();
+    let subject = repo.git(&["log", "-1", "--format=%s"])?;
+    let date_pattern = regex::Regex::new(
+        r"^feat : ajoute les notes \(le \d{2} (janvier|février|mars|avril|mai|juin|juillet|août|septembre|octobre|novembre|décembre) \d{4}\)$",
+    )?;
+    assert!(date_pattern.is_match(&subject), "got: {subject}");
+
+    repo.write("notes.txt", "deux")?;
+    repo.stage(&["notes.txt"])?;
+    repo.rona()
+        .args(["commit", "-m", "update notes", "--yes", "-u"])
+        .assert()
+        .success();
+    assert_eq!(
+        repo.git(&["log", "-1", "--format=%s"])?,
+        "feat: update notes"
+    );
+
+    repo.rona()
+        .args(["--lang", "de", "template", "check"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "No [templates.locales.de] table in the config (configured languages: fr)",
+        ));
+
+    repo.write(
+        ".rona.toml",
+        "lang = \"fr\"\n\n[templates.locales.fr]\ndate_format = \"%d/%m/%Q\"\n",
+    )?;
+    repo.rona()
+        .args(["template", "check"])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains(
+            "templates.locales.fr.date_format: IO error: Invalid date format: %d/%m/%Q",
+        ));
+
+    Ok(())
+}
+
+/// Tests named commit templates.
+///
+/// Verifies that:
+/// - `--template` renders the message through a named template of `[templates]`
+/// - `check-msg` accepts a message following a named template without the flag
+/// - `config show --origins` reports where the selected template comes from
+/// - An unknown name fails with the configured templates listed
+/// - The template named `default` replaces `commit_template`
+#[test]
+fn test_named_commit_templates() -> TestResult {
+    let repo = TestRepo::with_initial_commit()?;
+    repo.branch("feat/notes")?;
+    repo.write(
+        ".rona.toml",
+        r#"commit_template = "{commit_type}: {message}"
+
+[templates]
+release = "release({commit_type}): {message}"
+hotfix = "HOTFIX {message}"
+"#,
+    )?;
+    repo.git(&["add", ".rona.toml"])?;
+    repo.commit("add config")?;
+
+    repo.write("notes.txt", "one")?;
+    repo.stage(&["notes.txt"])?;
+    repo.rona()
+        .args([
+            "commit",
+            "-m",
+            "cut 1.2",
+            "--template",
+            "release",
+            "--yes",
+            "-u",
+        ])
+        .assert()
+        .success();
+    assert_eq!(
+        repo.git(&["log", "-1", "--format=%s"])?,
+        "release(feat): cut 1.2"
+    );
+
+    repo.write("MSG", "release(feat): cut 1.3\n")?;
+    repo.rona().args(["check-msg", "MSG"]).assert().success();
+    repo.write("MSG", "cut 1.3\n")?;
+    repo.rona().args(["check-msg", "MSG"]).assert().failure();
+
+    repo.rona()
+        .args(["--template", "release", "config", "show", "--origins"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("(via --template release)"));
+
+    repo.rona()
+        .args(["--template", "nope", "template", "check"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "No template named 'nope' in [templates] (configured templates: hotfix, release)",
+        ));
+
+    repo.write(
+        ".rona.toml",
+        "[templates]\ndefault = \"{commit_type} -> {message}\"\n",
+    )?;
+    repo.write("notes.txt", "two")?;
+    repo.stage(&["notes.txt"])?;
+    repo.rona()
+        .args(["commit", "-m", "update notes", "--yes", "-u"])
+        .assert()
+        .success();
+    assert_eq!(
+        repo.git(&["log", "-1", "--format=%s"])?,
+        "feat -> update notes"
+    );
+
+    Ok(())
+}
+
+/// Tests the `{changed_symbols}` commit template variable.
+///
+/// Verifies that:
+/// - Added, edited and removed definitions of a staged Rust file are listed
+/// - A change inside a method names the method, qualified by its type
+/// - A change that touches no definition leaves the conditional block out
+#[test]
+fn test_changed_symbols_variable() -> TestResult {
+    let repo = TestRepo::with_initial_commit()?;
+    repo.branch("feat/session")?;
+    repo.write(
+        ".rona.toml",
+        r#"commit_template = "{commit_type}: {message}{?changed_symbols}\n\nTouches: {changed_symbols}{/changed_symbols}"
+"#,
+    )?;
+    repo.write(
+        "src/session.rs",
+        "pub struct Session;\n\nimpl Session {\n    pub fn login(&self) {\n        check();\n    }\n}\n\npub fn logout() {}\n",
+    )?;
+    repo.write("notes.txt", "one")?;
+    repo.stage_all()?;
+    repo.commit("add session")?;
+
+    repo.write(
+        "src/session.rs",
+        "pub struct Session;\n\nimpl Session {\n    pub fn login(&self) {\n        check_password();\n    }\n}\n\npub fn refresh() {}\n",
+    )?;
+    repo.write("notes.txt", "two")?;
+    repo.stage_all()?;
+    repo.rona()
+        .args(["commit", "-m", "check passwords", "--yes", "-u"])
+        .assert()
+        .success();
+    assert_eq!(
+        repo.git(&["log", "-1", "--format=%B"])?.trim(),
+        "feat: check passwords\n\nTouches: refresh(), Session::login(), logout()"
+    );
+
+    repo.write("notes.txt", "three")?;
+    repo.stage_all()?;
+    repo.rona()
+        .args(["commit", "-m", "update notes", "--yes", "-u"])
+        .assert()
+        .success();
+    assert_eq!(
+        repo.git(&["log", "-1", "--format=%B"])?.trim(),
+        "feat: update notes"
+    );
+
+    Ok(())
+}
+
+/// Tests `rona env`.
+///
+/// Verifies that:
+/// - The repository root, config files, template and editor are reported
+/// - Global flags such as `--template`, `--scope` and `--read-only` are reflected
+/// - `which` is an alias, and `--json` prints the same data with a `schema_version`
+#[test]
+fn test_env_reports_resolved_environment() -> TestResult {
+    let repo = TestRepo::with_initial_commit()?;
+    repo.write(
+        ".rona.toml",
+        "editor = \"nano\"\n\n[templates]\nrelease = \"release: {message}\"\n",
+    )?;
+    repo.write("packages/api/lib.rs", "\n")?;
+
+    repo.rona()
+        .args(["--template", "release", "which"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Template    release"))
+        .stdout(predicate::str::contains("Editor      nano (config)"))
+        .stdout(predicate::str::contains("Read-only   no"));
+
+    let output = repo
+        .rona()
+        .args(["--read-only", "--scope", "packages/api", "env", "--json"])
+        .output()?;
+    assert!(output.status.success());
+    let env: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    let root = repo.git(&["rev-parse", "--show-toplevel"])?;
+    assert_eq!(env["schema_version"], 1);
+    assert_eq!(env["repo_root"], root.trim());
+    assert_eq!(env["config_files"][0]["origin"], "project");
+    assert_eq!(env["template"], serde_json::Value::Null);
+    assert_eq!(env["editor"]["command"], "nano");
+    assert_eq!(env["read_only"], true);
+    assert_eq!(env["scope"], "api (packages/api)");
+    assert_eq!(env["status_source"], "git status");
+    Ok(())
+}
+
+/// Tests `rona usage`.
+///
+/// Verifies that:
+/// - An empty operation log reports that nothing was recorded
+/// - `generate` is recorded and paired with the following commit
+/// - The report counts commands and measures the commit size from the log
+/// - `--json` prints the same report as JSON
+#[test]
+fn test_usage_summarizes_operation_log() -> TestResult {
+    let repo = TestRepo::with_initial_commit()?;
+    repo.branch("feat/notes")?;
+
+    repo.rona()
+        .arg("usage")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No operations recorded yet."));
+
+    repo.write("notes.txt", "one\ntwo\n")?;
+    repo.rona().arg("-a").assert().success();
+    repo.rona()
+        .args(["generate", "--stdin"])
+        .write_stdin("add notes")
+        .assert()
+        .success();
+    repo.rona().args(["-c", "--yes", "-u"]).assert().success();
+
+    repo.rona()
+        .arg("usage")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("3 operations"))
+        .stdout(predicate::str::contains("generate"))
+        .stdout(predicate::str::contains("2 lines in 1.0 files (1 commits)"))
+        .stdout(predicate::str::contains("Generate to commit:"))
+        .stdout(predicate::str::contains("nothing is sent anywhere"));
+
+    let output = repo.rona().args(["usage", "--json"]).output()?;
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(report["commands"]["commit"], 1);
+    assert_eq!(report["measured_commits"], 1);
+    assert_eq!(report["generated_commits"], 1);
+
+    Ok(())
+}
+
+/// Tests `rona stats` and its exports.
+///
+/// Verifies that:
+/// - The table lists each commit with its size and branch
+/// - `--export prometheus` prints counters and summaries labelled with the repository
+/// - `--export csv` prints a header and one row per commit
+#[test]
+fn test_stats_export() -> TestResult {
+    let repo = TestRepo::with_initial_commit()?;
+    repo.branch("feat/notes")?;
+    repo.rona()
+        .arg("stats")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No commits recorded yet."));
+
+    repo.write("notes.txt", "one\ntwo\n")?;
+    repo.rona().arg("-a").assert().success();
+    repo.rona()
+        .args(["generate", "--stdin"])
+        .write_stdin("add notes")
+        .assert()
+        .success();
+    repo.rona().args(["-c", "--yes", "-u"]).assert().success();
+    let head = repo.git(&["rev-parse", "HEAD"])?;
+
+    repo.rona()
+        .arg("stats")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(&head[..7]))
+        .stdout(predicate::str::contains("feat/notes"));
+
+    let output = repo
+        .rona()
+        .args(["stats", "--export", "prometheus"])
+        .output()?;
+    assert!(output.status.success());
+    let metrics = String::from_utf8(output.stdout)?;
+    let name = repo
+        .path()
+        .file_name()
+        .and_then(|name| name.to_str())
+        .ok_or("no repository name")?;
+    for expected in [
+        format!("rona_commits_total{{repository=\"{name}\"}} 1"),
+        format!("rona_commit_lines_sum{{repository=\"{name}\"}} 2"),
+        format!("rona_generate_to_commit_seconds_count{{repository=\"{name}\"}} 1"),
+    ] {
+        assert!(
+            metrics.contains(&expected),
+            "missing {expected} in:\n{metrics}"
+        );
+    }
+
+    let output = repo.rona().args(["stats", "--export", "csv"]).output()?;
+    let csv = String::from_utf8(output.stdout)?;
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines.len(), 2, "{csv}");
+    assert_eq!(
+        lines[0],
+        "time,commit,branch,amend,files,lines,lead_time_secs"
+    );
+    assert!(
+        lines[1].contains(&format!(",{head},feat/notes,false,1,2,")),
+        "{csv}"
+    );
+    Ok(())
+}
+
+/// Tests the global `-C/--chdir` option.
+///
+/// Verifies that:
+/// - `rona -C <repo>` operates on that repository even when started elsewhere
+/// - Staging through `-C` affects the target repository's index
+#[test]
+fn test_chdir_targets_other_repository() -> TestResult {
+    let repo = TestRepo::new()?;
+    repo.write("notes.txt", "hello")?;
+    repo.write("skip.log", "noise")?;
+
+    let repo_path = repo.path().to_str().ok_or("non UTF-8 repo path")?;
+
+    let mut cmd = cargo_bin_cmd!("rona");
+    cmd.current_dir(repo.home())
+        .env("HOME", repo.home())
+        .args(["-C", repo_path, "-l"]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("notes.txt"));
+
+    let mut cmd = cargo_bin_cmd!("rona");
+    cmd.current_dir(repo.home())
+        .env("HOME", repo.home())
+        .args(["--chdir", repo_path, "-a", "*.log"]);
+    cmd.assert().success();
+
+    let status = repo.status()?;
+    assert!(status.contains("A  notes.txt"), "got:\n{status}");
+    assert!(status.contains("?? skip.log"), "got:\n{status}");
+
+    Ok(())
+}
+
+/// Tests read-only mode.
+///
+/// Verifies that:
+/// - `rona --read-only -l` still lists the changed files
+/// - `rona --read-only -a` refuses to stage and leaves the index untouched
+/// - `RONA_READ_ONLY=1 rona -c` refuses to commit
+/// - `RONA_READ_ONLY=0` does not enable read-only mode
+/// - `rona --read-only config create` refuses to write the config file
+#[test]
+fn test_read_only_refuses_mutations() -> TestResult {
+    let repo = TestRepo::with_initial_commit()?;
+    repo.write("notes.txt", "hello")?;
+
+    repo.rona()
+        .args(["--read-only", "-l"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("notes.txt"));
+
+    repo.rona()
+        .args(["--read-only", "-a"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Read-only mode: refusing to stage files",
+        ));
+    assert!(repo.status()?.contains("?? notes.txt"));
+
+    repo.stage(&["notes.txt"])?;
+    repo.write("commit_message.md", "feat: add notes")?;
+    let head = repo.git(&["rev-parse", "HEAD"])?;
+    repo.rona()
+        .env("RONA_READ_ONLY", "1")
+        .args(["-c", "-u"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("refusing to commit"));
+    assert_eq!(repo.git(&["rev-parse", "HEAD"])?, head);
+
+    repo.write("other.txt", "more")?;
+    repo.rona()
+        .env("RONA_READ_ONLY", "0")
+        .args(["-a"])
+        .assert()
+        .success();
+    assert!(repo.status()?.contains("A  other.txt"));
+
+    repo.rona()
+        .args(["--read-only", "config", "create", "local"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "refusing to write the config file",
+        ));
+    assert!(!repo.join(".rona.toml").exists());
+
+    Ok(())
+}
+
+/// Tests that `-C` with a missing directory fails with a clear error.
+#[test]
+fn test_chdir_missing_directory_fails() -> TestResult {
+    let repo = TestRepo::uninitialized()?;
+    let missing = repo.join("does-not-exist");
+    let missing = missing.to_str().ok_or("non UTF-8 path")?;
+
+    repo.rona()
+        .args(["-C", missing, "-l"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Cannot change directory"));
+
+    Ok(())
+}
+
+/// Tests that `GIT_DIR` and `GIT_WORK_TREE` are honored, including relative values.
+///
+/// Verifies that:
+/// - `rona -a` run outside the working tree stages into the env-configured repository
+/// - Relative paths keep pointing at the same repository when git runs from the top level
+#[test]
+fn test_add_honors_relative_git_dir_and_work_tree() -> TestResult {
+    let repo = TestRepo::with_initial_commit()?;
+    repo.write("notes.txt", "hello")?;
+
+    let parent = repo.path().parent().ok_or("repo has no parent")?;
+    let name = repo
+        .path()
+        .file_name()
+        .and_then(|n| n.to_str())
+        .ok_or("non UTF-8 repo name")?;
+
+    let mut cmd = cargo_bin_cmd!("rona");
+    cmd.current_dir(parent)
+        .env("HOME", repo.home())
+        .env("GIT_DIR", format!("{name}/.git"))
+        .env("GIT_WORK_TREE", name)
+        .arg("-a");
+    cmd.assert().success();
+
+    let status = repo.status()?;
+    assert!(status.contains("A  notes.txt"), "got:\n{status}");
+
+    Ok(())
+}
+
+/// Tests that `GIT_INDEX_FILE` is honored when staging.
+///
+/// Verifies that:
+/// - Files are staged into the alternate index
+/// - The repository's default index is left untouched
+#[test]
+fn test_add_honors_git_index_file() -> TestResult {
+    let repo = TestRepo::with_initial_commit()?;
+    repo.write("notes.txt", "hello")?;
+    repo.mkdir("sub")?;
+    repo.git(&["read-tree", "--index-output=.git/alt-index", "HEAD"])?;
+
+    // Relative to the subdirectory rona is started from.
+    repo.rona_in("sub")
+        .env("GIT_INDEX_FILE", "../.git/alt-index")
+        .arg("-a")
+        .assert()
+        .success();
+
+    let status = repo.status()?;
+    assert!(status.contains("?? notes.txt"), "got:\n{status}");
+
+    let output = std::process::Command::new("git")
+        .current_dir(repo.path())
+        .env("GIT_INDEX_FILE", ".git/alt-index")
+        .args(["diff", "--cached", "--name-only"])
+        .output()?;
+    assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "notes.txt");
+
+    Ok(())
+}
+
+/// Tests that index-modifying commands refuse to run from a commit hook.
+///
+/// Verifies that:
+/// - A `pre-commit` hook calling `rona -a` fails with a clear error
+/// - The hook failure aborts the commit without staging anything else
+#[cfg(unix)]
+#[test]
+fn test_add_refused_inside_commit_hook() -> TestResult {
+    let repo = TestRepo::with_initial_commit()?;
+    let rona = assert_cmd::cargo::cargo_bin!("rona");
+    repo.write(
+        ".git/hooks/pre-commit",
+        &format!("#!/bin/sh\nexec '{}' -a\n", rona.display()),
+    )?;
+    std::fs::set_permissions(
+        repo.join(".git/hooks/pre-commit"),
+        std::os::unix::fs::PermissionsExt::from_mode(0o755),
+    )?;
+
+    repo.write("staged.txt", "staged")?;
+    repo.write("other.txt", "not staged")?;
+    repo.stage(&["staged.txt"])?;
+
+    let output = std::process::Command::new("git")
+        .current_dir(repo.path())
+        .env("HOME", repo.home())
+        .args(["commit", "-m", "from git"])
+        .output()?;
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("inside a git commit hook"),
+        "got:\n{stderr}"
+    );
+
+    let status = repo.status()?;
+    assert!(status.contains("A  staged.txt"), "got:\n{status}");
+    assert!(status.contains("?? other.txt"), "got:\n{status}");
+
+    Ok(())
+}
+
+/// Tests `rona prepare-msg` prefilling a message file.
+///
+/// Verifies that:
+/// - The header is rendered with the commit type inferred from the branch prefix
+/// - Git's comment lines are preserved below the header
+/// - Messages given with `-m` (source `message`) are left untouched
+#[test]
+fn test_prepare_msg_prefills_template() -> TestResult {
+    let repo = TestRepo::with_initial_commit()?;
+    repo.branch("feat/login")?;
+    repo.write("MSG", "\n# Please enter the commit message\n")?;
+
+    repo.rona().args(["prepare-msg", "MSG"]).assert().success();
+    assert_eq!(
+        repo.read("MSG")?,
+        "[2] (feat on login)\n\n# Please enter the commit message\n"
+    );
+
+    repo.write("MSG", "from -m\n")?;
+    repo.rona()
+        .args(["prepare-msg", "MSG", "message"])
+        .assert()
+        .success();
+    assert_eq!(repo.read("MSG")?, "from -m\n");
+
+    Ok(())
+}
+
+/// Tests the staged file template variables.
+///
+/// Verifies that:
+/// - `{primary_path}` is the deepest directory containing every staged file
+/// - `{file_count}` and `{file_list}` describe the staged files, sorted by path
+/// - Unstaged files are not listed
+#[test]
+fn test_staged_file_template_variables() -> TestResult {
+    let repo = TestRepo::with_initial_commit()?;
+    repo.branch("feat/status")?;
+    repo.write(
+        ".rona.toml",
+        "commit_template = \"{commit_type}({primary_path}): {file_count} files\\n\\n{file_list}\"\n",
+    )?;
+    repo.write("src/git/status.rs", "status")?;
+    repo.write("src/git/commit.rs", "commit")?;
+    repo.write("notes.txt", "not staged")?;
+    repo.stage(&["src/git/status.rs", "src/git/commit.rs"])?;
+    repo.write("MSG", "\n# Please enter the commit message\n")?;
+
+    repo.rona().args(["prepare-msg", "MSG"]).assert().success();
+    assert_eq!(
+        repo.read("MSG")?,
+        "feat(src/git): 2 files\n\n- src/git/commit.rs\n- src/git/status.rs\n\n# Please enter the commit message\n"
+    );
+
+    Ok(())
+}
+
+/// Tests `rona prepare-msg` installed as a real `prepare-commit-msg` hook.
+///
+/// Verifies that a plain `git commit` gets rona's header pre-filled.
+#[cfg(unix)]
+#[test]
+fn test_prepare_msg_from_hook() -> TestResult {
+    let repo = TestRepo::with_initial_commit()?;
+    repo.branch("fix/crash")?;
+    let rona = assert_cmd::cargo::cargo_bin!("rona");
+    repo.write(
+        ".git/hooks/prepare-commit-msg",
+        &format!(
+            "#!/bin/sh\nexec '{}' prepare-msg \"$1\" \"$2\" \"$3\"\n",
+            rona.display()
+        ),
+    )?;
+    std::fs::set_permissions(
+        repo.join(".git/hooks/prepare-commit-msg"),
+        std::os::unix::fs::PermissionsExt::from_mode(0o755),
+    )?;
+
+    repo.write("app.rs", "fn main() {}")?;
+    repo.stage(&["app.rs"])?;
+
+    let output = std::process::Command::new("git")
+        .current_dir(repo.path())
+        .env("HOME", repo.home())
+        .env("GIT_EDITOR", "true")
+        .arg("commit")
+        .output()?;
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(repo.last_subject()?, "[2] (fix on crash)");
+
+    Ok(())
+}
+
+/// Tests `rona check-msg` diagnostics and exit status.
+///
+/// Verifies that:
+/// - A message following the default template passes silently
+/// - A non-conforming message fails with a `file:line:column` diagnostic
+#[test]
+fn test_check_msg() -> TestResult {
+    let repo = TestRepo::with_initial_commit()?;
+
+    repo.write("GOOD", "[2] (feat on main) Add login\n\n# comment\n")?;
+    repo.rona()
+        .args(["check-msg", "GOOD"])
+        .assert()
+        .success()
+        .stderr(predicate::str::is_empty());
+
+    repo.write("BAD", "\n[2] (docs on main) Update readme\n")?;
+    repo.rona()
+        .args(["check-msg", "BAD"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("BAD:2:6: subject does not match"))
+        .stderr(predicate::str::contains("1 problem(s) found in BAD"));
+
+    Ok(())
+}
+
+/// Tests `rona audit` on existing history.
+///
+/// Verifies that:
+/// - Every non-merge commit is linted against the commit template
+/// - Non-conforming commits are listed with their subject and diagnostics
+/// - `--since` limits the audit to the commits after a ref, with compliance per author
+#[test]
+fn test_audit_history() -> TestResult {
+    let repo = TestRepo::with_initial_commit()?;
+    repo.git(&["tag", "v1.0"])?;
+    repo.write("login.rs", "fn login() {}\n")?;
+    repo.stage(&["login.rs"])?;
+    repo.commit("[2] (feat on main) Add login")?;
+    repo.write("login.rs", "fn login() { todo!() }\n")?;
+    repo.stage(&["login.rs"])?;
+    repo.commit("wip")?;
+
+    repo.rona()
+        .arg("audit")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Audited 3 commit(s): 1 conform (33%)",
+        ))
+        .stdout(predicate::str::contains("initial"));
+
+    repo.rona()
+        .args(["audit", "--since", "v1.0"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Audited 2 commit(s) since v1.0: 1 conform (50%)",
+        ))
+        .stdout(predicate::str::contains(" Test User wip"))
+        .stdout(predicate::str::contains("1:1: subject does not match"))
+        .stdout(predicate::str::contains("Test User  1/2 (50%)"));
+
+    repo.rona()
+        .args(["audit", "--since", "HEAD"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No commits to audit since HEAD."));
+
+    Ok(())
+}
+
+/// Tests `rona hooks install`, `list` and `uninstall`.
+///
+/// Verifies that:
+/// - Install writes managed scripts but leaves a user's own hook untouched
+/// - The installed `commit-msg` hook rejects a plain `git commit` with a bad message
+/// - Uninstall removes only the scripts rona wrote
+#[cfg(unix)]
+#[test]
+fn test_hooks_install_and_uninstall() -> TestResult {
+    let repo = TestRepo::with_initial_commit()?;
+    repo.write(".git/hooks/pre-push", "#!/bin/sh\nexit 0\n")?;
+
+    repo.rona()
+        .args(["hooks", "install"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Installed the commit-msg hook"))
+        .stderr(predicate::str::contains("pre-push was not written by rona"));
+    assert_eq!(repo.read(".git/hooks/pre-push")?, "#!/bin/sh\nexit 0\n");
+    assert!(
+        repo.read(".git/hooks/commit-msg")?
+            .contains("rona hooks run commit-msg")
+    );
+
+    repo.rona()
+        .args(["hooks", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("pre-commit  installed"))
+        .stdout(predicate::str::contains("pre-push    not managed by rona"));
+
+    let rona = assert_cmd::cargo::cargo_bin!("rona");
+    let path = format!(
+        "{}:{}",
+        rona.parent().ok_or("no binary directory")?.display(),
+        std::env::var("PATH")?
+    );
+    repo.write("app.rs", "fn main() {}")?;
+    repo.stage(&["app.rs"])?;
+    let output = std::process::Command::new("git")
+        .current_dir(repo.path())
+        .env("HOME", repo.home())
+        .env("PATH", &path)
+        .args(["commit", "-m", "not the template"])
+        .output()?;
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("subject does not match"));
+
+    repo.rona()
+        .args(["hooks", "uninstall"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Removed the commit-msg hook"))
+        .stdout(predicate::str::contains("it was not written by rona"));
+    assert!(!repo.join(".git/hooks/commit-msg").exists());
+    assert!(repo.join(".git/hooks/pre-push").exists());
+
+    Ok(())
+}
+
+/// Tests `rona fragment add`, `list` and `build`.
+///
+/// Verifies that:
+/// - Fragments are named after the branch, with a counter for repeated types
+/// - Unknown types are refused
+/// - Build adds the release below the changelog title and deletes the fragments
+#[test]
+fn test_fragment_workflow() -> TestResult {
+    let repo = TestRepo::with_initial_commit()?;
+    repo.git(&["switch", "--quiet", "-c", "feat/login"])?;
+    repo.write(
+        "CHANGELOG.md",
+        "# Changelog\n\n## 0.1.0 - 2026-01-01\n\n- First\n",
+    )?;
+
+    for (fragment_type, text) in [
+        ("feat", "Add login"),
+        ("fix", "Fix logout"),
+        ("feat", "Add SSO"),
+    ] {
+        repo.rona()
+            .args(["fragment", "add", fragment_type, text])
+            .assert()
+            .success();
+    }
+    assert_eq!(repo.read("changelog.d/feat-login.feat.2.md")?, "Add SSO\n");
+    repo.rona()
+        .args(["fragment", "add", "oops", "Nope"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Unknown fragment type 'oops'"));
+
+    repo.rona()
+        .args(["fragment", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Fix logout"));
+
+    repo.rona()
+        .args(["fragment", "build", "0.2.0", "--date", "2026-10-18"])
+        .assert()
+        .success();
+    assert_eq!(
+        repo.read("CHANGELOG.md")?,
+        "# Changelog\n\n## 0.2.0 - 2026-10-18\n\n### feat\n\n- Add login\n- Add SSO\n\n\
+         ### fix\n\n- Fix logout\n\n## 0.1.0 - 2026-01-01\n\n- First\n"
+    );
+    assert!(!repo.join("changelog.d/feat-login.feat.md").exists());
+
+    Ok(())
+}
+
+/// Tests `rona release` through release candidates to the stable release.
+///
+/// Verifies that:
+/// - `--minor --rc` starts the next minor version's first release candidate
+/// - `--rc` increments the candidate, while moving back to `--beta` is refused
+/// - The stable release adds the changelog section, commits it and removes the fragments
+#[test]
+fn test_release_channels() -> TestResult {
+    let repo = TestRepo::with_initial_commit()?;
+    repo.git(&["tag", "v1.2.0"])?;
+    repo.write("changelog.d/main.feat.md", "Add login\n")?;
+    repo.stage(&["changelog.d/main.feat.md"])?;
+    repo.git(&["commit", "--quiet", "-m", "Add a fragment"])?;
+
+    repo.rona()
+        .args(["release", "--minor", "--rc"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Released 1.3.0-rc.1 as tag v1.3.0-rc.1",
+        ));
+    assert!(repo.read("CHANGELOG.md")?.contains("## 1.3.0-rc.1 - "));
+    assert!(repo.join("changelog.d/main.feat.md").exists());
+
+    repo.rona()
+        .args(["release", "--rc", "--dry-run"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Would release 1.3.0-rc.2 (previous: 1.3.0-rc.1)",
+        ));
+    repo.rona()
+        .args(["release", "--beta"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("1.3.0-rc.1 is already past beta"));
+
+    repo.rona()
+        .args(["release"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Released 1.3.0 as tag v1.3.0"));
+    let changelog = repo.read("CHANGELOG.md")?;
+    assert!(changelog.find("## 1.3.0 - ") < changelog.find("## 1.3.0-rc.1 - "));
+    assert!(!repo.join("changelog.d/main.feat.md").exists());
+    let subject = repo.git(&["log", "-1", "--format=%s", "v1.3.0"])?;
+    assert_eq!(subject, "Release 1.3.0");
+    assert!(repo.git(&["status", "--porcelain"])?.is_empty());
+
+    Ok(())
+}
+
+/// Tests the `[notify.webhook]` release notification.
+///
+/// Verifies that:
+/// - `--dry-run` names the webhook host without posting
+/// - A release posts `release_template` to the webhook once the tag is created
+/// - A failing webhook only warns, without printing its URL
+#[test]
+fn test_release_posts_webhook_notification() -> TestResult {
+    use mockall::PredicateBooleanExt;
+    use std::io::{BufRead, BufReader, Read, Write};
+
+    let repo = TestRepo::with_initial_commit()?;
+    repo.git(&["tag", "v1.2.0"])?;
+
+    let listener = std::net::TcpListener::bind("127.0.0.1:0")?;
+    let port = listener.local_addr()?.port();
+    let server = std::thread::spawn(move || -> std::io::Result<String> {
+        let (stream, _) = listener.accept()?;
+        let mut reader = BufReader::new(stream);
+        let mut length = 0;
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line)?;
+            if let Some(value) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                length = value.trim().parse().unwrap_or(0);
+            }
+            if line.trim().is_empty() {
+                break;
+            }
+        }
+        let mut body = vec![0; length];
+        reader.read_exact(&mut body)?;
+        reader
+            .get_mut()
+            .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 0\r\n\r\n")?;
+        Ok(String::from_utf8_lossy(&body).into_owned())
+    });
+
+    std::fs::create_dir_all(repo.home().join(".config"))?;
+    std::fs::write(
+        repo.home().join(".config/rona.toml"),
+        format!(
+            "[notify.webhook]\nurl = \"http://127.0.0.1:{port}/hook\"\nkind = \"discord\"\nrelease_template = \"{{tag}} is out (was {{previous}})\"\n"
+        ),
+    )?;
+    repo.rona()
+        .args(["release", "--dry-run"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Would post a release notification to 127.0.0.1",
+        ));
+    repo.rona()
+        .arg("release")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Released 1.2.1 as tag v1.2.1"))
+        .stdout(predicate::str::contains("Notified 127.0.0.1"));
+    let body = server.join().map_err(|_| "webhook server panicked")??;
+    assert_eq!(body, r#"{"content":"v1.2.1 is out (was 1.2.0)"}"#);
+
+    // Nothing listens on the port anymore
+    repo.rona()
+        .arg("release")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Released 1.2.2 as tag v1.2.2"))
+        .stdout(predicate::str::contains("Release notification failed"))
+        .stdout(predicate::str::contains("/hook").not());
+
+    Ok(())
+}
+
+/// Tests `rona release --package` in a monorepo.
+///
+/// Verifies that:
+/// - The package's version comes from its own tags, not the repository's
+/// - A release is refused when no commit touched the package since its last one
+/// - The fragments and changelog are the ones inside the package directory
+#[test]
+fn test_release_package() -> TestResult {
+    let repo = TestRepo::with_initial_commit()?;
+    repo.write(".rona.toml", "[packages.api]\npath = \"packages/api\"\n")?;
+    repo.write("packages/api/lib.rs", "// api\n")?;
+    repo.stage(&[".rona.toml", "packages/api/lib.rs"])?;
+    repo.git(&["commit", "--quiet", "-m", "Add the api package"])?;
+    repo.git(&["tag", "api/v1.0.0"])?;
+    repo.git(&["tag", "v2.0.0"])?;
+
+    repo.rona()
+        .args(["release", "--package", "api"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "No commit touched packages/api since api/v1.0.0",
+        ));
+    repo.rona()
+        .args(["release", "--package", "web"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Configured packages: api"));
+
+    repo.write("packages/api/lib.rs", "// api v2\n")?;
+    repo.write("packages/api/changelog.d/main.feat.md", "Add pagination\n")?;
+    repo.stage(&["packages/api"])?;
+    repo.git(&["commit", "--quiet", "-m", "Paginate the api"])?;
+
+    repo.rona()
+        .args(["release", "--package", "api", "--minor"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Released 1.1.0 as tag api/v1.1.0 (previous: 1.0.0)",
+        ));
+    assert!(
+        repo.read("packages/api/CHANGELOG.md")?
+            .contains("- Add pagination")
+    );
+    assert!(!repo.join("CHANGELOG.md").exists());
+    let subject = repo.git(&["log", "-1", "--format=%s", "api/v1.1.0"])?;
+    assert_eq!(subject, "Release api 1.1.0");
+
+    Ok(())
+}
+
+/// Tests `rona handoff push` and `rona handoff pull` between two clones.
+///
+/// Verifies that:
+/// - The staged changes and the pending `commit_message.md` are pushed to a handoff ref
+/// - Another clone checks out the branch at the same commit with the changes staged
+/// - Pulling a branch without a handoff fails
+#[test]
+fn test_handoff_between_clones() -> TestResult {
+    let laptop = TestRepo::with_initial_commit()?;
+    let remote = laptop.add_bare_remote()?;
+    laptop.git(&["switch", "--quiet", "--create", "feat/login"])?;
+    laptop.write("login.rs", "fn login() {}\n")?;
+    laptop.stage(&["login.rs"])?;
+    laptop.commit("add login")?;
+    laptop.write("form.rs", "fn form() {}\n")?;
+    laptop.stage(&["form.rs"])?;
+    laptop.write("commit_message.md", "(feat on login) add form\n")?;
+
+    laptop
+        .rona()
+        .args(["handoff", "push"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Handed off 1 staged file(s) and commit_message.md to refs/rona/handoff/feat/login",
+        ));
+
+    let desktop = TestRepo::with_initial_commit()?;
+    desktop.git(&["remote", "add", "origin", remote.to_str().ok_or("path")?])?;
+    desktop
+        .rona()
+        .args(["handoff", "pull", "nope"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "No handoff at refs/rona/handoff/nope",
+        ));
+    desktop
+        .rona()
+        .args(["handoff", "pull", "feat/login"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Restored the handoff of feat/login: 1 staged file(s)",
+        ));
+
+    assert_eq!(desktop.git(&["branch", "--show-current"])?, "feat/login");
+    assert_eq!(
+        desktop.git(&["rev-parse", "HEAD"])?,
+        laptop.git(&["rev-parse", "HEAD"])?
+    );
+    assert_eq!(
+        desktop.git(&["diff", "--cached", "--name-only"])?,
+        "form.rs"
+    );
+    assert_eq!(desktop.read("form.rs")?, "fn form() {}\n");
+    assert_eq!(
+        desktop.read("commit_message.md")?,
+        "(feat on login) add form\n"
+    );
+
+    Ok(())
+}
+
+/// Tests `rona bundle create` and `rona bundle apply` between two clones.
+///
+/// Verifies that:
+/// - `--since` leaves out the commits the other clone already has
+/// - Applying fails when the bundle lacks an expected branch or its base commits
+/// - The current branch is fast-forwarded and missing branches are created
+#[test]
+fn test_bundle_between_clones() -> TestResult {
+    let laptop = TestRepo::with_initial_commit()?;
+    let remote = laptop.add_bare_remote()?;
+    laptop.git(&["push", "--quiet", "origin", "main"])?;
+    let desktop = TestRepo::new()?;
+    desktop.git(&["pull", "--quiet", remote.to_str().ok_or("path")?, "main"])?;
+
+    laptop.write("login.rs", "fn login() {}\n")?;
+    laptop.stage(&["login.rs"])?;
+    laptop.commit("add login")?;
+    laptop.git(&["switch", "--quiet", "--create", "feat/form"])?;
+    laptop.write("form.rs", "fn form() {}\n")?;
+    laptop.stage(&["form.rs"])?;
+    laptop.commit("add form")?;
+    let bundle = laptop.home().join("work.bundle");
+    let bundle = bundle.to_str().ok_or("path")?;
+
+    laptop
+        .rona()
+        .args(["bundle", "create", bundle, "--since", "origin/main"])
+        .args(["-b", "main", "-b", "feat/form"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Bundled 2 commit(s) of main, feat/form since origin/main",
+        ));
+
+    desktop
+        .rona()
+        .args(["bundle", "apply", bundle, "-b", "feat/nope"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "does not contain feat/nope; it contains: main, feat/form",
+        ));
+    let unrelated = TestRepo::new()?;
+    unrelated.write("other.txt", "unrelated\n")?;
+    unrelated.stage_all()?;
+    unrelated.commit("initial")?;
+    unrelated
+        .rona()
+        .args(["bundle", "apply", bundle])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be applied"));
+
+    desktop
+        .rona()
+        .args(["bundle", "apply", bundle, "-b", "main", "-b", "feat/form"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Updated feat/form"));
+    assert_eq!(desktop.read("login.rs")?, "fn login() {}\n");
+    assert_eq!(
+        desktop.git(&["rev-parse", "main", "feat/form"])?,
+        laptop.git(&["rev-parse", "main", "feat/form"])?
+    );
+
+    Ok(())
+}
+
+/// Tests `rona tour --yes` end to end, outside of any repository.
+///
+/// Verifies that:
+/// - Every step runs without prompting, including the push to the sandbox remote
+/// - The sandbox is kept with `--keep`, with the excluded file left unstaged
+#[test]
+fn test_tour_runs_non_interactively() -> TestResult {
+    let dir = TestRepo::uninitialized()?;
+
+    let output = dir.rona().args(["tour", "--yes", "--keep"]).output()?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "{stdout}");
+    assert!(stdout.contains("Step 4/5: Push"), "got:\n{stdout}");
+    assert!(stdout.contains("Tour complete!"), "got:\n{stdout}");
+
+    let sandbox = stdout
+        .lines()
+        .find_map(|line| line.strip_prefix("Sandbox: "))
+        .ok_or("sandbox path not printed")?;
+    let sandbox = std::path::Path::new(sandbox);
+    let pushed = std::process::Command::new("git")
+        .current_dir(sandbox.join("remote.git"))
+        .args(["log", "-1", "--format=%s", "main"])
+        .output()?;
+    assert_eq!(
+        String::from_utf8_lossy(&pushed.stdout).trim(),
+        "[1] (feat on main)"
+    );
+    assert!(sandbox.join("demo/debug.log").exists());
+
+    std::fs::remove_dir_all(sandbox)?;
+    Ok(())
+}
+
+/// Tests `rona -l --filter`.
+///
+/// Verifies that:
+/// - Only paths fuzzy-matching the query are listed
+/// - The closest match comes first
+#[test]
+fn test_list_status_fuzzy_filter() -> TestResult {
+    let repo = TestRepo::with_initial_commit()?;
+    repo.write("src/cli.rs", "")?;
+    repo.write("tests/cli_tests.rs", "")?;
+    repo.write("docs/guide.md", "")?;
+    repo.stage_all()?;
+
+    let output = repo
+        .rona()
+        .args(["-l", "--filter", "clirs"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let listed: Vec<String> = String::from_utf8(output)?
+        .lines()
+        .map(str::to_string)
+        .collect();
+    assert_eq!(listed, ["src/cli.rs", "tests/cli_tests.rs"]);
+    Ok(())
+}
+
+/// Tests staging a conflicted file marked `merge=binary`.
+///
+/// Verifies that:
+/// - `rona -a` warns that git did not merge the file
+/// - The file is still staged
+#[test]
+fn test_add_warns_about_binary_merge_conflicts() -> TestResult {
+    let repo = TestRepo::with_initial_commit()?;
+    repo.write(".gitattributes", "*.bin merge=binary\n")?;
+    repo.write("data.bin", "base\n")?;
+    repo.stage_all()?;
+    repo.commit("add data")?;
+
+    repo.git(&["switch", "--quiet", "-c", "other"])?;
+    repo.write("data.bin", "theirs\n")?;
+    repo.stage_all()?;
+    repo.commit("theirs")?;
+    repo.git(&["switch", "--quiet", "main"])?;
+    repo.write("data.bin", "ours\n")?;
+    repo.stage_all()?;
+    repo.commit("ours")?;
+    assert!(repo.git(&["merge", "--quiet", "other"]).is_err());
+
+    repo.rona()
+        .arg("-a")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "data.bin is marked merge=binary in .gitattributes",
+        ));
+    assert!(
+        repo.git(&["diff", "--name-only", "--diff-filter=U"])?
+            .is_empty()
+    );
+    Ok(())
+}
+
+/// Tests staging in a cone-mode sparse checkout.
+///
+/// Verifies that:
+/// - `rona -l` only lists changes inside the cone
+/// - `rona -a` stages the changes inside the cone instead of failing on the others
+/// - Changes outside the cone are listed in a warning
+#[test]
+fn test_add_respects_sparse_checkout_cone() -> TestResult {
+    let repo = TestRepo::new()?;
+    repo.write("app/src/main.rs", "fn main() {}\n")?;
+    repo.write("lib/lib.rs", "\n")?;
+    repo.stage_all()?;
+    repo.commit("init")?;
+    repo.git(&["sparse-checkout", "set", "app"])?;
+
+    repo.write("app/src/main.rs", "fn main() { run() }\n")?;
+    repo.write("lib/new.rs", "\n")?;
+
+    repo.rona()
+        .arg("-l")
+        .assert()
+        .success()
+        .stdout("app/src/main.rs\n");
+    repo.rona()
+        .arg("-a")
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(
+            "outside the sparse-checkout cone are left out:\n  - lib/new.rs\n",
+        ));
+
+    let staged = repo.git(&["diff", "--cached", "--name-only"])?;
+    assert_eq!(staged.trim(), "app/src/main.rs");
+    Ok(())
+}
+
+/// Tests the `auto_fetch` background fetch started by `rona -l`.
+///
+/// Verifies that:
+/// - The fetch timestamp is recorded under `.git/rona/`
+/// - The fetch runs and restores a missing remote-tracking ref
+/// - A second run within the interval does not fetch again
+#[test]
+fn test_auto_fetch_from_list_status() -> TestResult {
+    let repo = TestRepo::with_initial_commit()?;
+    repo.add_bare_remote()?;
+    repo.git(&["push", "--quiet", "origin", "main"])?;
+    repo.git(&["update-ref", "-d", "refs/remotes/origin/main"])?;
+    repo.write(".rona.toml", "auto_fetch = \"1h\"\n")?;
+
+    repo.rona().arg("-l").assert().success();
+    let stamp = repo.read(".git/rona/last-fetch")?;
+
+    let fetched = (0..50).any(|_| {
+        std::thread::sleep(std::time::Duration::from_millis(100));
+        repo.git(&[
+            "rev-parse",
+            "--verify",
+            "--quiet",
+            "refs/remotes/origin/main",
+        ])
+        .is_ok()
+    });
+    assert!(fetched, "background fetch did not restore origin/main");
+
+    repo.git(&["update-ref", "-d", "refs/remotes/origin/main"])?;
+    repo.rona().arg("-l").assert().success();
+    std::thread::sleep(std::time::Duration::from_millis(500));
+    assert_eq!(repo.read(".git/rona/last-fetch")?, stamp);
+    assert!(
+        repo.git(&[
+            "rev-parse",
+            "--verify",
+            "--quiet",
+            "refs/remotes/origin/main"
+        ])
+        .is_err()
+    );
+
+    Ok(())
+}
+
+/// Tests `rona prune` against a branch deleted on the remote.
+///
+/// Verifies that:
+/// - `--dry-run` lists the stale remote-tracking ref but keeps it
+/// - A guard denying `fetch` refuses a real run
+/// - A real run removes the ref and reports the local branch whose upstream is gone
+/// - Stale rona state files are removed
+#[test]
+fn test_prune_removes_deleted_remote_branches() -> TestResult {
+    let repo = TestRepo::with_initial_commit()?;
+    let remote = repo.add_bare_remote()?;
+    repo.branch("old")?;
+    repo.git(&["push", "--quiet", "-u", "origin", "main", "old"])?;
+    repo.git(&[
+        &format!("--git-dir={}", remote.display()),
+        "branch",
+        "-D",
+        "old",
+    ])?;
+    repo.write(".git/rona/last-fetch", "0")?;
+
+    repo.rona()
+        .args(["prune", "--dry-run", "--max-age", "0s"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Would prune 1 remote-tracking refs",
+        ))
+        .stdout(predicate::str::contains("origin/old"))
+        .stdout(predicate::str::contains("last-fetch"));
+    assert!(
+        repo.git(&[
+            "rev-parse",
+            "--verify",
+            "--quiet",
+            "refs/remotes/origin/old"
+        ])
+        .is_ok()
+    );
+    assert!(repo.join(".git/rona/last-fetch").exists());
+
+    std::fs::create_dir_all(repo.home().join(".config"))?;
+    std::fs::write(
+        repo.home().join(".config/rona.toml"),
+        "[[guards]]\ndeny = [\"fetch\"]\n",
+    )?;
+    repo.rona()
+        .args(["prune", "--max-age", "0s"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Refusing to fetch in this repository",
+        ));
+    std::fs::remove_file(repo.home().join(".config/rona.toml"))?;
+
+    repo.rona()
+        .args(["prune", "--max-age", "0s"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Pruned 1 remote-tracking refs"))
+        .stdout(predicate::str::contains("Reclaimed"))
+        .stdout(predicate::str::contains("- old"));
+    assert!(
+        repo.git(&[
+            "rev-parse",
+            "--verify",
+            "--quiet",
+            "refs/remotes/origin/old"
+        ])
+        .is_err()
+    );
+    assert!(
+        repo.git(&[
+            "rev-parse",
+            "--verify",
+            "--quiet",
+            "refs/remotes/origin/main"
+        ])
+        .is_ok()
+    );
+    assert!(!repo.join(".git/rona/last-fetch").exists());
+
+    Ok(())
+}
+
+/// Tests `rona health` on a neglected repository and after maintenance.
+///
+/// Verifies that:
+/// - A missing commit-graph is reported with the command that writes it
+/// - Large files in history and refs to missing objects are listed
+/// - `--optimize` writes the commit-graph, after which nothing is needed
+#[test]
+fn test_health_reports_findings() -> TestResult {
+    let repo = TestRepo::with_initial_commit()?;
+    repo.write("big.bin", &"x".repeat(6 * 1024 * 1024))?;
+    repo.stage(&["big.bin"])?;
+    repo.commit("add big file")?;
+    repo.write(
+        ".git/refs/heads/gone",
+        "1234567890123456789012345678901234567890\n",
+    )?;
+
+    repo.rona()
+        .arg("health")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No commit-graph"))
+        .stdout(predicate::str::contains(
+            "fix: git commit-graph write --reachable",
+        ))
+        .stdout(predicate::str::contains(
+            "Large file in history: big.bin (6 MiB)",
+        ))
+        .stdout(predicate::str::contains("Broken ref: refs/heads/gone"));
+
+    let healthy = TestRepo::with_initial_commit()?;
+    healthy
+        .rona()
+        .args(["health", "--optimize"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Wrote commit-graph"))
+        .stdout(predicate::str::contains("No maintenance needed"));
+    assert!(healthy.join(".git/objects/info/commit-graph").exists());
+    assert_eq!(
+        healthy
+            .git(&["config", "--local", "fetch.writeCommitGraph"])?
+            .trim(),
+        "true"
+    );
+
+    Ok(())
+}
+
+/// Tests `rona show` on a rona-formatted commit with a trailer and a note.
+///
+/// Verifies that:
+/// - Template fields are parsed from the subject
+/// - Trailers, notes and the diff stat are shown
+/// - `--json` prints the same data as JSON
+/// - An unknown reference is rejected
+#[test]
+fn test_show_commit() -> TestResult {
+    let repo = TestRepo::with_initial_commit()?;
+    repo.write("src/form.rs", "fn form() {}\n")?;
+    repo.stage(&["src/form.rs"])?;
+    repo.git(&[
+        "commit",
+        "--quiet",
+        "-m",
+        "[2] (feat on login) add form",
+        "-m",
+        "Refs: #12",
+    ])?;
+    repo.git(&["notes", "add", "-m", "reviewed by Sam"])?;
+
+    repo.rona()
+        .arg("show")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("commit_type    feat"))
+        .stdout(predicate::str::contains("branch_name    login"))
+        .stdout(predicate::str::contains("Refs: #12"))
+        .stdout(predicate::str::contains("reviewed by Sam"))
+        .stdout(predicate::str::contains("src/form.rs | 1 +"));
+
+    repo.rona()
+        .args(["show", "--json", "HEAD"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(r#""name": "commit_number""#))
+        .stdout(predicate::str::contains(r#""value": "2""#))
+        .stdout(predicate::str::contains(r#""status": "none""#))
+        .stdout(predicate::str::contains(r#""schema_version": 1"#));
+
+    repo.rona()
+        .args(["show", "no-such-ref"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("'no-such-ref' is not a commit"));
+
+    Ok(())
+}
+
+/// Tests `rona copy --print`.
+///
+/// Verifies that:
+/// - The default format is the short SHA followed by the quoted subject
+/// - `[copy] format` and `[copy] formats` choose and define named formats
+/// - `--format` takes a built-in name or a template
+/// - An unknown format is rejected with the available ones
+#[test]
+fn test_copy_prints_commit_reference() -> TestResult {
+    let repo = TestRepo::with_initial_commit()?;
+    repo.write("index.rs", "fn index() {}\n")?;
+    repo.stage(&["index.rs"])?;
+    repo.commit("fix: handle empty index")?;
+    let sha = repo.git(&["rev-parse", "HEAD"])?;
+    let short = repo.git(&["rev-parse", "--short", "HEAD"])?;
+
+    repo.rona()
+        .args(["copy", "--print"])
+        .assert()
+        .success()
+        .stdout(format!("{short} (\"fix: handle empty index\")\n"));
+
+    repo.rona()
+        .args(["copy", "--print", "--format", "sha"])
+        .assert()
+        .success()
+        .stdout(format!("{sha}\n"));
+
+    repo.rona()
+        .args([
+            "copy",
+            "HEAD~1",
+            "--print",
+            "--format",
+            "{subject} by {author}",
+        ])
+        .assert()
+        .success()
+        .stdout("initial by Test User\n");
+
+    repo.write(
+        ".rona.toml",
+        "[copy]\nformat = \"md\"\nformats = { md = \"[`{short_sha}`]: {subject}\" }\n",
+    )?;
+    repo.rona()
+        .args(["copy", "--print"])
+        .assert()
+        .success()
+        .stdout(format!("[`{short}`]: fix: handle empty index\n"));
+
+    repo.rona()
+        .args(["copy", "--print", "--format", "long"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Unknown copy format 'long' (formats: md, reference, sha, short, subject)",
+        ));
+
+    Ok(())
+}
+
+/// Tests commit type colors in `rona show`.
+///
+/// Verifies that:
+/// - The commit type is colored in the subject and the header fields
+/// - `[type_colors]` overrides the built-in color
+/// - Nothing is colored with `NO_COLOR`
+#[test]
+fn test_show_colors_commit_type() -> TestResult {
+    let repo = TestRepo::with_initial_commit()?;
+    repo.write("src/form.rs", "fn form() {}\n")?;
+    repo.stage(&["src/form.rs"])?;
+    repo.commit("[2] (feat on login) add form")?;
+
+    repo.rona()
+        .arg("show")
+        .env("CLICOLOR_FORCE", "1")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\u{1b}[1;32mfeat\u{1b}[0m"))
+        .stdout(predicate::str::contains(
+            "commit_type    \u{1b}[32mfeat\u{1b}[0m",
+        ));
+
+    repo.write(".rona.toml", "[type_colors]\nfeat = \"blue\"\n")?;
+    repo.rona()
+        .arg("show")
+        .env("CLICOLOR_FORCE", "1")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "commit_type    \u{1b}[34mfeat\u{1b}[0m",
+        ));
+
+    repo.rona()
+        .arg("show")
+        .env("NO_COLOR", "1")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("commit_type    feat\n"));
+
+    Ok(())
+}
+
+/// Tests `rona browse` in a repository without commits.
+///
+/// Verifies that:
+/// - The command succeeds without prompting when there is no history
+#[test]
+fn test_browse_without_commits() -> TestResult {
+    let repo = TestRepo::new()?;
+
+    repo.rona()
+        .arg("browse")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No commits yet."));
+
+    Ok(())
+}
+
+/// Tests `rona generate --stdin`.
+///
+/// Verifies that:
+/// - A piped message is run through the template without prompting
+/// - The commit type is inferred from the branch prefix
+/// - Empty input is rejected
+#[test]
+fn test_generate_from_stdin() -> TestResult {
+    let repo = TestRepo::with_initial_commit()?;
+    repo.git(&["switch", "--quiet", "-c", "fix/login"])?;
+
+    repo.rona()
+        .args(["generate", "--stdin", "--no-commit-number"])
+        .write_stdin("handle empty password\n\nThe form crashed on submit.\n")
+        .assert()
+        .success();
+    assert_eq!(
+        repo.read("commit_message.md")?,
+        "(fix on login) handle empty password\n\nThe form crashed on submit."
+    );
+
+    repo.rona()
+        .args(["generate", "--stdin"])
+        .write_stdin("  \n")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("No message on stdin"));
+
+    Ok(())
+}
+
+/// Tests `rona snippet list` and `rona snippet insert`.
+///
+/// Verifies that:
+/// - Configured snippets are listed
+/// - A snippet is rendered with template variables and inserted above comment lines
+/// - An unknown snippet is rejected with the available names
+#[test]
+fn test_snippet_insert() -> TestResult {
+    let repo = TestRepo::with_initial_commit()?;
+    repo.write(
+        ".rona.toml",
+        "[snippets]\nreview = \"Reviewed on {branch_name} ({commit_type})\"\n",
+    )?;
+    repo.git(&["switch", "--quiet", "-c", "fix/login"])?;
+    repo.write(
+        "msg.txt",
+        "(fix on login) typo\n\n# Please enter the commit message\n",
+    )?;
+
+    repo.rona()
+        .args(["snippet", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("/review"));
+
+    repo.rona()
+        .args(["snippet", "insert", "review", "--file", "msg.txt"])
+        .assert()
+        .success();
+    assert_eq!(
+        repo.read("msg.txt")?,
+        "(fix on login) typo\n\nReviewed on login (fix)\n\n# Please enter the commit message\n"
+    );
+
+    repo.rona()
+        .args(["snippet", "insert", "nope", "--file", "msg.txt"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Available: review"));
+
+    Ok(())
+}
+
+/// Tests `rona template check` and `strict_templates`.
+///
+/// Verifies that:
+/// - An unknown variable is reported with its template, line and column
+/// - Without `strict_templates`, `prepare-msg` warns and leaves the message untouched
+/// - With `strict_templates`, `prepare-msg` fails and names the variable and its position
+#[test]
+fn test_template_check_and_strict_templates() -> TestResult {
+    let repo = TestRepo::with_initial_commit()?;
+    let template = "commit_template = \"({commit_typ} on {branch_name}) {message}\"\n";
+    repo.write(".rona.toml", template)?;
+
+    repo.rona()
+        .args(["template", "check"])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains(
+            "commit_template: unknown variable {commit_typ} at line 1, column 2",
+        ));
+
+    repo.write("MSG", "\n# Please enter the commit message\n")?;
+    repo.rona()
+        .args(["prepare-msg", "MSG"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("{commit_typ} at line 1, column 2"));
+    assert_eq!(repo.read("MSG")?, "\n# Please enter the commit message\n");
+
+    repo.write(
+        ".rona.toml",
+        &format!("strict_templates = true\n{template}"),
+    )?;
+    repo.rona()
+        .args(["prepare-msg", "MSG"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Invalid commit template: IO error: Unknown template variable: {commit_typ} at line 1, column 2",
+        ));
+
+    repo.write(".rona.toml", "strict_templates = true\n")?;
+    repo.rona()
+        .args(["template", "check"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("template(s) OK"));
+
+    Ok(())
+}
+
+/// Tests `rona completion check`.
+///
+/// Verifies that:
+/// - Missing completions are reported with the command that installs them
+/// - A completion file matching the running rona is reported up to date
+/// - The `rona -l` helper is run through `PATH`
+/// - A completion file from another version is reported with the command that regenerates it
+#[cfg(unix)]
+#[test]
+fn test_completion_check() -> TestResult {
+    let repo = TestRepo::with_initial_commit()?;
+    let rona = assert_cmd::cargo::cargo_bin!("rona");
+    let bin_dir = rona.parent().ok_or("no binary directory")?;
+    let path = format!(
+        "{}:{}",
+        bin_dir.display(),
+        std::env::var("PATH").unwrap_or_default()
+    );
+    let check = || {
+        let mut cmd = repo.rona();
+        cmd.args(["completion", "check", "fish"])
+            .env("PATH", &path)
+            .env_remove("XDG_CONFIG_HOME")
+            .env_remove("XDG_DATA_HOME");
+        cmd
+    };
+    let installed = repo.home().join(".config/fish/completions/rona.fish");
+
+    check()
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains(
+            "No fish completions are installed",
+        ))
+        .stdout(predicate::str::contains(format!(
+            "fix: rona completion fish > {}",
+            installed.display()
+        )));
+
+    let script = repo.rona().args(["completion", "fish"]).output()?.stdout;
+    std::fs::create_dir_all(installed.parent().ok_or("no parent")?)?;
+    std::fs::write(&installed, &script)?;
+    check()
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(format!(
+            "{} is up to date",
+            installed.display()
+        )))
+        .stdout(predicate::str::contains("`rona -l` works"));
+
+    std::fs::write(&installed, "complete -c rona -a old\n")?;
+    check()
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("is from another rona version"));
+
+    Ok(())
+}
+
+/// Tests `rona daemon` serving `rona -l` and `rona status`.
+///
+/// Verifies that:
+/// - `rona -l` is answered by a running daemon
+/// - Staging a file refreshes the daemon's cache before the next answer
+/// - `rona status` is answered by the daemon with the file states
+/// - `rona daemon --stop` stops the daemon and removes its socket
+/// - Stopping without a running daemon is an error
+/// - An invalid `[daemon] refresh_interval` is refused before serving
+#[cfg(unix)]
+#[test]
+fn test_daemon_serves_status() -> TestResult {
+    let repo = TestRepo::with_initial_commit()?;
+    let socket = repo.join(".git/rona/daemon.sock");
+    let mut daemon = std::process::Command::new(assert_cmd::cargo::cargo_bin!("rona"))
+        .arg("daemon")
+        .current_dir(repo.path())
+        .env("HOME", repo.home())
+        .stdout(std::process::Stdio::null())
+        .spawn()?;
+    for _ in 0..100 {
+        if socket.exists() {
+            break;
+        }
+        std::thread::sleep(std::time::Duration::from_millis(50));
+    }
+    assert!(socket.exists(), "the daemon did not start");
+
+    repo.write("staged.txt", "staged")?;
+    repo.stage(&["staged.txt"])?;
+    repo.rona()
+        .args(["-v", "-l"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Status served by the daemon"))
+        .stdout(predicate::str::contains("staged.txt"));
+    repo.rona()
+        .args(["-v", "status"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Status served by the daemon"))
+        .stdout(predicate::str::contains("Staged (1)"))
+        .stdout(predicate::str::contains("staged.txt"));
+
+    repo.rona()
+        .args(["daemon", "--stop"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Stopped the rona daemon"));
+    assert!(daemon.wait()?.success());
+    assert!(!socket.exists());
+
+    repo.rona()
+        .args(["daemon", "--stop"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("No rona daemon is running"));
+
+    repo.write(".rona.toml", "[daemon]\nrefresh_interval = \"soon\"\n")?;
+    repo.rona()
+        .arg("daemon")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("[daemon] refresh_interval"));
+    assert!(!socket.exists());
 
     Ok(())
 }
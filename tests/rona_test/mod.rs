@@ -0,0 +1,306 @@
+//! Shared fixtures for Rona's integration tests.
+//!
+//! Every integration test needs the same git plumbing: a throwaway repository with a
+//! known identity, GPG signing disabled, a few files, and a way to run the `rona`
+//! binary inside it. [`TestRepo`] bundles that setup so tests only describe the
+//! scenario they care about.
+//!
+//! # Isolation
+//!
+//! - The repository lives in its own temporary directory, removed on drop
+//! - `HOME` is pointed at a separate temporary directory for every `rona` and `git`
+//!   invocation, so neither the user's global `~/.config/rona.toml` nor their
+//!   `~/.gitconfig` can leak into a test
+//! - The initial branch is always `main`, regardless of `init.defaultBranch`
+//!
+//! # Usage
+//!
+//! ```ignore
+//! mod rona_test;
+//! use rona_test::TestRepo;
+//!
+//! let repo = TestRepo::new()?;
+//! repo.write("src/lib.rs", "fn main() {}")?;
+//! repo.stage_all()?;
+//! repo.commit("initial")?;
+//! repo.rona().arg("-l").assert().success();
+//! ```
+
+// Each integration test binary compiles this module separately and only uses a
+// subset of the helpers.
+#![allow(dead_code)]
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::Output,
+};
+
+use assert_cmd::{Command, cargo::cargo_bin_cmd};
+use tempfile::TempDir;
+
+/// Result type used by the fixtures and the tests built on them.
+pub(crate) type TestResult<T = ()> = Result<T, Box<dyn std::error::Error>>;
+
+/// A temporary git repository with an isolated home directory.
+#[derive(Debug)]
+pub(crate) struct TestRepo {
+    dir: TempDir,
+    home: TempDir,
+}
+
+impl TestRepo {
+    /// Creates a repository on `main` with a test identity and GPG signing disabled.
+    ///
+    /// # Errors
+    /// * If the temporary directories cannot be created
+    /// * If any git setup command fails
+    pub(crate) fn new() -> TestResult<Self> {
+        let repo = Self::uninitialized()?;
+        repo.git(&["init", "--quiet"])?;
+        repo.git(&["symbolic-ref", "HEAD", "refs/heads/main"])?;
+
+        for (key, value) in [
+            ("user.name", "Test User"),
+            ("user.email", "test@example.com"),
+            ("commit.gpgsign", "false"),
+            ("tag.gpgsign", "false"),
+        ] {
+            repo.git(&["config", "--local", key, value])?;
+        }
+
+        Ok(repo)
+    }
+
+    /// Creates a repository with an initial commit containing a `README.md`.
+    ///
+    /// # Errors
+    /// * If the repository cannot be created or the commit fails
+    pub(crate) fn with_initial_commit() -> TestResult<Self> {
+        let repo = Self::new()?;
+        repo.write("README.md", "# test\n")?;
+        repo.stage_all()?;
+        repo.commit("initial")?;
+        Ok(repo)
+    }
+
+    /// Creates an empty directory that is *not* a git repository.
+    ///
+    /// Useful for asserting how commands behave outside a repository.
+    ///
+    /// # Errors
+    /// * If the temporary directories cannot be created
+    pub(crate) fn uninitialized() -> TestResult<Self> {
+        Ok(Self {
+            dir: TempDir::new()?,
+            home: TempDir::new()?,
+        })
+    }
+
+    /// Creates a bare repository inside this repository's temporary home and adds
+    /// it as the `origin` remote, returning its path.
+    ///
+    /// # Errors
+    /// * If the bare repository cannot be initialized or the remote cannot be added
+    pub(crate) fn add_bare_remote(&self) -> TestResult<PathBuf> {
+        let remote = self.home.path().join("remote.git");
+        let output = std::process::Command::new("git")
+            .env("HOME", self.home.path())
+            .args(["init", "--quiet", "--bare"])
+            .arg(&remote)
+            .output()?;
+        check_output("git init --bare", &output)?;
+
+        let remote_str = remote.to_str().ok_or("non UTF-8 remote path")?;
+        self.git(&["remote", "add", "origin", remote_str])?;
+        Ok(remote)
+    }
+
+    /// Root of the repository working tree.
+    pub(crate) fn path(&self) -> &Path {
+        self.dir.path()
+    }
+
+    /// The isolated home directory used for every command.
+    pub(crate) fn home(&self) -> &Path {
+        self.home.path()
+    }
+
+    /// Resolves a repository-relative path to an absolute one.
+    pub(crate) fn join(&self, relative: &str) -> PathBuf {
+        self.dir.path().join(relative)
+    }
+
+    /// Runs `git` in the repository root and returns its trimmed stdout.
+    ///
+    /// # Errors
+    /// * If git cannot be spawned or exits with a non-zero status
+    pub(crate) fn git(&self, args: &[&str]) -> TestResult<String> {
+        self.git_in(".", args)
+    }
+
+    /// Runs `git` in a repository-relative subdirectory and returns its trimmed stdout.
+    ///
+    /// # Errors
+    /// * If git cannot be spawned or exits with a non-zero status
+    pub(crate) fn git_in(&self, subdir: &str, args: &[&str]) -> TestResult<String> {
+        let output = std::process::Command::new("git")
+            .current_dir(self.join(subdir))
+            .env("HOME", self.home.path())
+            .args(args)
+            .output()?;
+        check_output(&format!("git {}", args.join(" ")), &output)?;
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+
+    /// Writes a file, creating any missing parent directories.
+    ///
+    /// # Errors
+    /// * If the directories or the file cannot be written
+    pub(crate) fn write(&self, relative: &str, content: &str) -> TestResult {
+        let path = self.join(relative);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, content)?;
+        Ok(())
+    }
+
+    /// Reads a file relative to the repository root.
+    ///
+    /// # Errors
+    /// * If the file cannot be read
+    pub(crate) fn read(&self, relative: &str) -> TestResult<String> {
+        Ok(fs::read_to_string(self.join(relative))?)
+    }
+
+    /// Deletes a file relative to the repository root.
+    ///
+    /// # Errors
+    /// * If the file cannot be removed
+    pub(crate) fn remove(&self, relative: &str) -> TestResult {
+        fs::remove_file(self.join(relative))?;
+        Ok(())
+    }
+
+    /// Creates a directory (and its parents) relative to the repository root.
+    ///
+    /// # Errors
+    /// * If the directory cannot be created
+    pub(crate) fn mkdir(&self, relative: &str) -> TestResult<PathBuf> {
+        let path = self.join(relative);
+        fs::create_dir_all(&path)?;
+        Ok(path)
+    }
+
+    /// Stages the given paths with `git add --`.
+    ///
+    /// # Errors
+    /// * If `git add` fails
+    pub(crate) fn stage(&self, paths: &[&str]) -> TestResult {
+        let mut args = vec!["add", "--"];
+        args.extend_from_slice(paths);
+        self.git(&args)?;
+        Ok(())
+    }
+
+    /// Stages every change, including deletions and untracked files.
+    ///
+    /// # Errors
+    /// * If `git add --all` fails
+    pub(crate) fn stage_all(&self) -> TestResult {
+        self.git(&["add", "--all"])?;
+        Ok(())
+    }
+
+    /// Commits the staged changes with the given message.
+    ///
+    /// # Errors
+    /// * If `git commit` fails
+    pub(crate) fn commit(&self, message: &str) -> TestResult {
+        self.git(&["commit", "--quiet", "-m", message])?;
+        Ok(())
+    }
+
+    /// Creates a branch at `HEAD` and switches to it.
+    ///
+    /// # Errors
+    /// * If `git switch -c` fails
+    pub(crate) fn branch(&self, name: &str) -> TestResult {
+        self.git(&["switch", "--quiet", "-c", name])?;
+        Ok(())
+    }
+
+    /// Switches to an existing branch.
+    ///
+    /// # Errors
+    /// * If `git switch` fails
+    pub(crate) fn switch(&self, name: &str) -> TestResult {
+        self.git(&["switch", "--quiet", name])?;
+        Ok(())
+    }
+
+    /// Name of the currently checked out branch.
+    ///
+    /// # Errors
+    /// * If `git symbolic-ref` fails (e.g. detached `HEAD`)
+    pub(crate) fn current_branch(&self) -> TestResult<String> {
+        self.git(&["symbolic-ref", "--short", "HEAD"])
+    }
+
+    /// Output of `git status --porcelain -u`, untrimmed so column alignment is preserved.
+    ///
+    /// # Errors
+    /// * If `git status` fails
+    pub(crate) fn status(&self) -> TestResult<String> {
+        let output = std::process::Command::new("git")
+            .current_dir(self.path())
+            .env("HOME", self.home.path())
+            .args(["status", "--porcelain", "-u"])
+            .output()?;
+        check_output("git status", &output)?;
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+
+    /// Subject line of the latest commit.
+    ///
+    /// # Errors
+    /// * If `git log` fails (e.g. no commits yet)
+    pub(crate) fn last_subject(&self) -> TestResult<String> {
+        self.git(&["log", "-1", "--format=%s"])
+    }
+
+    /// Full message of the latest commit.
+    ///
+    /// # Errors
+    /// * If `git log` fails (e.g. no commits yet)
+    pub(crate) fn last_message(&self) -> TestResult<String> {
+        self.git(&["log", "-1", "--format=%B"])
+    }
+
+    /// A `rona` command running in the repository root with the isolated home.
+    pub(crate) fn rona(&self) -> Command {
+        self.rona_in(".")
+    }
+
+    /// A `rona` command running in a repository-relative subdirectory.
+    pub(crate) fn rona_in(&self, subdir: &str) -> Command {
+        let mut cmd = cargo_bin_cmd!("rona");
+        cmd.current_dir(self.join(subdir))
+            .env("HOME", self.home.path())
+            .env_remove("RUST_LOG");
+        cmd
+    }
+}
+
+/// Turns a failed command into an error carrying its stderr.
+fn check_output(label: &str, output: &Output) -> TestResult {
+    if output.status.success() {
+        return Ok(());
+    }
+    Err(format!(
+        "`{label}` failed: {}",
+        String::from_utf8_lossy(&output.stderr).trim()
+    )
+    .into())
+}